@@ -3,6 +3,8 @@
 //! This library provides the core functionality for discovering, parsing,
 //! and executing scripts from various sources (bash, npm, devbox, taskfiles, makefiles,
 //! justfiles, cargo, nx, terraform/opentofu, gradle, and bazel).
+//!
+//! Use [`script::discover_all`] to run Jarvis's discovery without the TUI.
 
 pub mod script;
 pub mod ui;
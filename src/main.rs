@@ -22,6 +22,17 @@
 //!
 //! # Debug mode - print discovered scripts and exit
 //! jarvis --debug
+//!
+//! # List discovered commands (tab-separated or JSON) and exit
+//! jarvis --list
+//! jarvis --list --format json
+//!
+//! # Run a single command without the TUI (exits with its exit code)
+//! jarvis --run build
+//! jarvis --run NxJson:my-app:test
+//!
+//! # Print a shell completion script (bash, zsh, fish, elvish, powershell)
+//! jarvis --completions zsh > ~/.zfunc/_jarvis
 //! ```
 //!
 //! ## Architecture
@@ -43,6 +54,7 @@
 //! - `/` - Enter search mode
 //! - `Tab` - Toggle focus between panes
 //! - `i` - Show/hide info modal
+//! - `x` / `Ctrl+c` - Cancel the running command
 //!
 //! ### Output Panel (right panel)
 //! - `j` / `k` - Scroll down/up by line
@@ -55,11 +67,13 @@
 
 use jarvis::script;
 use jarvis::ui;
+use jarvis::ui::keymap::{self, Action, KeyContext};
 use jarvis::ui::App;
-use jarvis::usage::{UsageTracker, FREQUENTLY_USED_CATEGORY, MAX_FREQUENT_COMMANDS};
+use jarvis::usage::{PinStore, UiStateStore, UsageTracker};
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{CommandFactory, Parser, ValueEnum, ValueHint};
+use clap_complete::Shell;
 use crossterm::{
     event::{
         self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
@@ -73,7 +87,13 @@ use std::io;
 use std::panic;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Redraw interval while a command runs or the UI otherwise animates (~60fps)
+const TICK_RATE: Duration = Duration::from_millis(16);
+
+/// Event poll timeout while idle; nothing is redrawn until an event arrives
+const IDLE_POLL: Duration = Duration::from_millis(250);
 
 /// Trait for reading terminal events (allows dependency injection for testing)
 trait EventReader {
@@ -95,15 +115,31 @@ impl EventReader for CrosstermEventReader {
     }
 }
 
+/// Output format for `--list`
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+enum ListFormat {
+    /// `category<TAB>name<TAB>display_name<TAB>description`, one command per line
+    #[default]
+    Text,
+    /// A JSON array of command objects
+    Json,
+}
+
 /// Jarvis - A beautiful TUI for managing and executing bash scripts
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Default)]
 #[command(name = "jarvis")]
 #[command(author = "Luckystrike561")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 #[command(about = "Your trusted AI assistant for automating scripts", long_about = None)]
 struct Args {
     /// Path to the base directory to search for bash scripts
-    #[arg(short, long, value_name = "DIR", conflicts_with = "file")]
+    #[arg(
+        short,
+        long,
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        conflicts_with = "file"
+    )]
     path: Option<PathBuf>,
 
     /// Path to a single script file to run Jarvis on
@@ -111,13 +147,67 @@ struct Args {
         short = 'f',
         long = "file",
         value_name = "FILE",
+        value_hint = ValueHint::FilePath,
         conflicts_with = "path"
     )]
     file: Option<PathBuf>,
 
+    /// Scan every directory up to N levels below the project root
+    /// (0 = the root only). By default Jarvis scans the root plus
+    /// ./script/, ./scripts/ and ./jarvis/
+    #[arg(long, value_name = "N", conflicts_with = "file")]
+    depth: Option<usize>,
+
+    /// Skip files and directories matching a glob pattern during discovery
+    /// (repeatable). Patterns without a `/` match a name at any depth, others
+    /// a path relative to the project root. Added to `exclude` in jarvis.toml
+    #[arg(long, value_name = "PATTERN", conflicts_with = "file")]
+    exclude: Vec<String>,
+
     /// Print debug information about discovered scripts and exit
     #[arg(long)]
     debug: bool,
+
+    /// Print discovered commands and exit (see `--format`)
+    #[arg(long)]
+    list: bool,
+
+    /// Output format for `--list`
+    #[arg(long, value_enum, default_value_t = ListFormat::Text, requires = "list")]
+    format: ListFormat,
+
+    /// Run a single command without the TUI and exit with its exit code.
+    /// Accepts a command name or a `Type:name` key (e.g. `Makefile:build`)
+    #[arg(long, value_name = "TARGET")]
+    run: Option<String>,
+
+    /// Fail inline commands still running after SECS seconds (0 = no timeout).
+    /// Overrides `timeout_secs` from the config file
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Run every command in DIR instead of its script's directory
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+    cwd: Option<PathBuf>,
+
+    /// Project name shown in the header instead of the directory name.
+    /// Overrides `name` from jarvis.toml
+    #[arg(long, value_name = "NAME")]
+    project_name: Option<String>,
+
+    /// Disable colors (also disabled when the `NO_COLOR` environment variable
+    /// is set)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Print a completion script for SHELL to stdout and exit.
+    /// Only flags complete; `--run` targets are discovered at runtime
+    #[arg(long, value_name = "SHELL", value_enum)]
+    completions: Option<Shell>,
+
+    /// Print per-parser discovery timings to stderr and exit (for contributors)
+    #[arg(long, hide = true)]
+    profile_discovery: bool,
 }
 
 #[tokio::main]
@@ -125,6 +215,11 @@ async fn main() -> Result<()> {
     // Parse command-line arguments
     let args = Args::parse();
 
+    if let Some(shell) = args.completions {
+        write_completions(shell, &mut io::stdout());
+        return Ok(());
+    }
+
     // Set up panic hook to ensure terminal is restored on panic
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
@@ -145,28 +240,81 @@ async fn main() -> Result<()> {
     result
 }
 
+/// Validate the `--cwd` directory, made absolute so it survives directory changes
+fn resolve_cwd(dir: &std::path::Path) -> Result<PathBuf> {
+    let resolved = dir
+        .canonicalize()
+        .with_context(|| format!("Failed to access --cwd directory: {}", dir.display()))?;
+    if !resolved.is_dir() {
+        anyhow::bail!("--cwd is not a directory: {}", dir.display());
+    }
+    Ok(resolved)
+}
+
+/// Whether to draw colors: not with `--no-color`, nor when `NO_COLOR` is set
+/// to a non-empty value (see <https://no-color.org>)
+fn colors_enabled(no_color_flag: bool, no_color_env: Option<std::ffi::OsString>) -> bool {
+    !no_color_flag && no_color_env.is_none_or(|value| value.is_empty())
+}
+
+/// The project name shown in the header: `--project-name`, then `name` from
+/// the project config, then the directory's name in title case
+fn project_title(flag: Option<&str>, config_name: Option<&str>, dir: &std::path::Path) -> String {
+    let explicit = [flag, config_name]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|name| !name.is_empty());
+    match explicit {
+        Some(name) => name.to_string(),
+        None => script::format_display_name(
+            dir.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Project"),
+        ),
+    }
+}
+
+/// Write the completion script for `shell` to `out`
+fn write_completions(shell: Shell, out: &mut dyn io::Write) {
+    clap_complete::generate(shell, &mut Args::command(), "jarvis", out);
+}
+
 async fn run_application(args: Args) -> Result<()> {
-    // Pre-warm tool availability checks in parallel (devbox, task, make, just, cargo, nx)
-    // These run in background threads so they're ready by the time discovery needs them
-    script::prewarm_tool_checks();
+    if let Some(dir) = args.cwd.as_deref() {
+        ui::pty_runner::set_working_dir_override(resolve_cwd(dir)?);
+    }
+
+    let discovery_started = Instant::now();
 
-    // Determine script files based on mode: single file or directory discovery
-    let (script_files, current_dir) = if let Some(file_path) = args.file {
+    // Determine script files based on mode: single file or directory discovery.
+    // The project config is loaded first in both modes since its binary
+    // overrides must be in place before any tool availability check runs.
+    let (mut script_files, current_dir, project_config, source) = if let Some(file_path) = args.file
+    {
         // Single file mode: discover only from the specified file
         let canonical_path = file_path
             .canonicalize()
             .with_context(|| format!("Failed to access file: {}", file_path.display()))?;
 
-        let script_file = script::discover_single_file(&canonical_path)
-            .with_context(|| format!("Failed to parse file: {}", canonical_path.display()))?;
-
         // Use the file's parent directory as the working directory
         let dir = canonical_path
             .parent()
             .map(std::path::Path::to_path_buf)
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
-        (vec![script_file], dir)
+        let project_config = load_project_config(&dir);
+        script::prewarm_tool_checks();
+
+        let script_file = script::discover_single_file(&canonical_path)
+            .with_context(|| format!("Failed to parse file: {}", canonical_path.display()))?;
+
+        (
+            vec![script_file],
+            dir,
+            project_config,
+            ScriptSource::File(canonical_path),
+        )
     } else {
         // Directory mode: discover scripts from directory tree
         let current_dir = if let Some(path) = args.path {
@@ -176,36 +324,30 @@ async fn run_application(args: Args) -> Result<()> {
             std::env::current_dir().context("Failed to get current working directory")?
         };
 
-        // Discover scripts from multiple locations:
-        // 1. Current directory (root .sh files only, depth 1 to avoid subdirs)
-        // 2. ./script/ folder (if exists)
-        // 3. ./scripts/ folder (if exists)
-        // 4. ./jarvis/ folder (if exists)
-        let mut script_files = Vec::new();
-
-        // Scan current directory for .sh files (shallow, only immediate directory)
-        let root_files = script::discover_scripts_shallow(&current_dir)
-            .with_context(|| format!("Failed to discover scripts in: {}", current_dir.display()))?;
-        script_files.extend(root_files);
-
-        // Check optional subdirectories (with depth 2 for nested structures)
-        let possible_dirs = vec!["script", "scripts", "jarvis"];
-        for dir_name in possible_dirs {
-            let dir_path = current_dir.join(dir_name);
-            if dir_path.exists() && dir_path.is_dir() {
-                let files = script::discover_scripts(&dir_path).with_context(|| {
-                    format!("Failed to discover scripts in: {}", dir_path.display())
-                })?;
-                script_files.extend(files);
-            }
-        }
+        let project_config = load_project_config(&current_dir);
+
+        // Discover scripts from the root directory (shallow) and the
+        // ./script/, ./scripts/ and ./jarvis/ folders (if they exist), or
+        // down to --depth levels when given
+        let options = script::DiscoveryOptions {
+            depth: args.depth,
+            exclude: project_config
+                .iter()
+                .flat_map(|config| config.exclude.iter().cloned())
+                .chain(args.exclude)
+                .collect(),
+            ..script::DiscoveryOptions::default()
+        };
+        let script_files = script::discover_script_files(&current_dir, &options)?;
 
         if script_files.is_empty() {
-            anyhow::bail!("No scripts found in {} (also checked: ./script/, ./scripts/, ./jarvis/). Add bash scripts (.sh), package.json, devbox.json, Taskfile.yml, Makefile, justfile, Cargo.toml, nx.json, *.tf, build.gradle, WORKSPACE/BUILD (Bazel), .github/workflows/ (GitHub Actions), or magefile.go/mage.go (Mage) files to get started.", current_dir.display());
+            anyhow::bail!("No scripts found in {} (also checked: ./script/, ./scripts/, ./jarvis/). Add bash scripts (.sh), package.json, devbox.json, Taskfile.yml, Makefile, justfile, Rakefile, Cargo.toml, nx.json, pyproject.toml, deno.json, composer.json, *.tf, build.gradle, WORKSPACE/BUILD (Bazel), compose.yaml/docker-compose.yml (Docker Compose), Procfile, CMakeLists.txt with a build/ directory (CMake), meson.build with a configured build directory (Meson), dagger.json (Dagger), mix.exs (Mix), lefthook.yml (Lefthook), .pre-commit-config.yaml (pre-commit), mise.toml (mise), .github/workflows/ (GitHub Actions), magefile.go/mage.go (Mage), build.sbt (sbt), pom.xml (Maven), or Ansible playbook (*.yml) files to get started.", current_dir.display());
         }
 
-        (script_files, current_dir)
+        let source = ScriptSource::Directory(current_dir.clone(), options);
+        (script_files, current_dir, project_config, source)
     };
+    let discovery_elapsed = discovery_started.elapsed();
 
     // Debug mode: print discovered scripts and exit
     if args.debug {
@@ -222,318 +364,21 @@ async fn run_application(args: Args) -> Result<()> {
     }
 
     // Parse all scripts in parallel using threads for subprocess-heavy parsers
-    enum ParseResult {
-        Functions(Vec<script::ScriptFunction>),
-        NxFunctions(
-            Vec<script::ScriptFunction>,
-            std::collections::HashMap<String, String>,
-        ),
-        Error(String, anyhow::Error),
-    }
-
-    let parse_handles: Vec<std::thread::JoinHandle<ParseResult>> = script_files
-        .iter()
-        .map(|script_file| {
-            let path = script_file.path.clone();
-            let category = script_file.category.clone();
-            let script_type = script_file.script_type;
-
-            std::thread::spawn(move || match script_type {
-                script::ScriptType::Bash => match script::parse_script(&path, &category) {
-                    Ok(functions) => {
-                        let visible: Vec<_> =
-                            functions.into_iter().filter(|f| !f.ignored).collect();
-                        ParseResult::Functions(visible)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-                script::ScriptType::PackageJson => {
-                    match script::parse_package_json(&path, &category) {
-                        Ok(npm_scripts) => {
-                            let functions: Vec<script::ScriptFunction> = npm_scripts
-                                .into_iter()
-                                .map(|s| script::ScriptFunction {
-                                    name: s.name,
-                                    display_name: s.display_name,
-                                    category: s.category,
-                                    description: s.description,
-                                    emoji: None,
-                                    ignored: false,
-                                    script_type: script::ScriptType::PackageJson,
-                                })
-                                .collect();
-                            ParseResult::Functions(functions)
-                        }
-                        Err(e) => ParseResult::Error(path.display().to_string(), e),
-                    }
-                }
-                script::ScriptType::DevboxJson => {
-                    match script::parse_devbox_json(&path, &category) {
-                        Ok(devbox_scripts) => {
-                            let functions: Vec<script::ScriptFunction> = devbox_scripts
-                                .into_iter()
-                                .map(|s| script::ScriptFunction {
-                                    name: s.name,
-                                    display_name: s.display_name,
-                                    category: s.category,
-                                    description: s.description,
-                                    emoji: None,
-                                    ignored: false,
-                                    script_type: script::ScriptType::DevboxJson,
-                                })
-                                .collect();
-                            ParseResult::Functions(functions)
-                        }
-                        Err(e) => ParseResult::Error(path.display().to_string(), e),
-                    }
-                }
-                script::ScriptType::Task => match script::list_tasks(&path, &category) {
-                    Ok(tasks) => {
-                        let functions: Vec<script::ScriptFunction> = tasks
-                            .into_iter()
-                            .filter(|t| !t.ignored)
-                            .map(|t| script::ScriptFunction {
-                                name: t.name,
-                                display_name: t.display_name,
-                                category: t.category,
-                                description: t.description,
-                                emoji: t.emoji,
-                                ignored: t.ignored,
-                                script_type: script::ScriptType::Task,
-                            })
-                            .collect();
-                        ParseResult::Functions(functions)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-                script::ScriptType::Makefile => match script::list_make_targets(&path, &category) {
-                    Ok(targets) => {
-                        let functions: Vec<script::ScriptFunction> = targets
-                            .into_iter()
-                            .filter(|t| !t.ignored)
-                            .map(|t| script::ScriptFunction {
-                                name: t.name,
-                                display_name: t.display_name,
-                                category: t.category,
-                                description: t.description,
-                                emoji: t.emoji,
-                                ignored: t.ignored,
-                                script_type: script::ScriptType::Makefile,
-                            })
-                            .collect();
-                        ParseResult::Functions(functions)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-                script::ScriptType::Just => match script::list_just_recipes(&path, &category) {
-                    Ok(recipes) => {
-                        let functions: Vec<script::ScriptFunction> = recipes
-                            .into_iter()
-                            .filter(|r| !r.ignored)
-                            .map(|r| script::ScriptFunction {
-                                name: r.name,
-                                display_name: r.display_name,
-                                category: r.category,
-                                description: r.description,
-                                emoji: r.emoji,
-                                ignored: r.ignored,
-                                script_type: script::ScriptType::Just,
-                            })
-                            .collect();
-                        ParseResult::Functions(functions)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-                script::ScriptType::Mage => match script::list_mage_targets(&path, &category) {
-                    Ok(targets) => {
-                        let functions: Vec<script::ScriptFunction> = targets
-                            .into_iter()
-                            .filter(|t| !t.ignored)
-                            .map(|t| script::ScriptFunction {
-                                name: t.name,
-                                display_name: t.display_name,
-                                category: t.category,
-                                description: t.description,
-                                emoji: t.emoji,
-                                ignored: t.ignored,
-                                script_type: script::ScriptType::Mage,
-                            })
-                            .collect();
-                        ParseResult::Functions(functions)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-
-                script::ScriptType::CargoToml => {
-                    match script::list_cargo_targets(&path, &category) {
-                        Ok(targets) => {
-                            let functions: Vec<script::ScriptFunction> = targets
-                                .into_iter()
-                                .filter(|t| !t.ignored)
-                                .map(|t| {
-                                    let prefixed_name = match t.target_type {
-                                        script::cargo_parser::CargoTargetType::Binary => {
-                                            format!("bin:{}", t.name)
-                                        }
-                                        script::cargo_parser::CargoTargetType::Example => {
-                                            format!("example:{}", t.name)
-                                        }
-                                    };
-                                    script::ScriptFunction {
-                                        name: prefixed_name,
-                                        display_name: t.display_name,
-                                        category: t.category,
-                                        description: t.description,
-                                        emoji: t.emoji,
-                                        ignored: t.ignored,
-                                        script_type: script::ScriptType::CargoToml,
-                                    }
-                                })
-                                .collect();
-                            ParseResult::Functions(functions)
-                        }
-                        Err(e) => ParseResult::Error(path.display().to_string(), e),
-                    }
-                }
-                script::ScriptType::NxJson => match script::list_nx_targets(&path, &category) {
-                    Ok(nx_targets) => {
-                        let display_names =
-                            script::nx_parser::collect_category_display_names(&nx_targets);
-                        let functions: Vec<script::ScriptFunction> = nx_targets
-                            .into_iter()
-                            .filter(|t| !t.ignored)
-                            .map(|t| script::ScriptFunction {
-                                name: t.name,
-                                display_name: t.display_name,
-                                category: t.category,
-                                description: t.description,
-                                emoji: t.emoji,
-                                ignored: t.ignored,
-                                script_type: script::ScriptType::NxJson,
-                            })
-                            .collect();
-                        ParseResult::NxFunctions(functions, display_names)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-                script::ScriptType::Terraform => {
-                    match script::list_terraform_commands(&path, &category) {
-                        Ok(commands) => {
-                            let functions: Vec<script::ScriptFunction> = commands
-                                .into_iter()
-                                .filter(|c| !c.ignored)
-                                .map(|c| script::ScriptFunction {
-                                    name: c.name,
-                                    display_name: c.display_name,
-                                    category: c.category,
-                                    description: c.description,
-                                    emoji: c.emoji,
-                                    ignored: c.ignored,
-                                    script_type: script::ScriptType::Terraform,
-                                })
-                                .collect();
-                            ParseResult::Functions(functions)
-                        }
-                        Err(e) => ParseResult::Error(path.display().to_string(), e),
-                    }
-                }
-                script::ScriptType::Gradle => match script::list_gradle_tasks(&path, &category) {
-                    Ok(tasks) => {
-                        let functions: Vec<script::ScriptFunction> = tasks
-                            .into_iter()
-                            .filter(|t| !t.ignored)
-                            .map(|t| script::ScriptFunction {
-                                name: t.name,
-                                display_name: t.display_name,
-                                category: t.category,
-                                description: t.description,
-                                emoji: t.emoji,
-                                ignored: t.ignored,
-                                script_type: script::ScriptType::Gradle,
-                            })
-                            .collect();
-                        ParseResult::Functions(functions)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-                script::ScriptType::Bazel => match script::list_bazel_targets(&path, &category) {
-                    Ok(targets) => {
-                        let functions: Vec<script::ScriptFunction> = targets
-                            .into_iter()
-                            .filter(|t| !t.ignored)
-                            .map(|t| {
-                                let prefixed_name = match t.target_type {
-                                    script::bazel_parser::BazelTargetType::Binary => {
-                                        format!("run:{}", t.label)
-                                    }
-                                    script::bazel_parser::BazelTargetType::Test => {
-                                        format!("test:{}", t.label)
-                                    }
-                                };
-                                script::ScriptFunction {
-                                    name: prefixed_name,
-                                    display_name: t.display_name,
-                                    category: t.category,
-                                    description: t.description,
-                                    emoji: t.emoji,
-                                    ignored: t.ignored,
-                                    script_type: script::ScriptType::Bazel,
-                                }
-                            })
-                            .collect();
-                        ParseResult::Functions(functions)
-                    }
-                    Err(e) => ParseResult::Error(path.display().to_string(), e),
-                },
-                script::ScriptType::GithubActions => {
-                    match script::list_github_workflows(&path, &category) {
-                        Ok(workflows) => {
-                            let functions: Vec<script::ScriptFunction> = workflows
-                                .into_iter()
-                                .filter(|w| !w.ignored)
-                                .map(|w| script::ScriptFunction {
-                                    name: w.file_name,
-                                    display_name: w.display_name,
-                                    category: w.category,
-                                    description: w.description,
-                                    emoji: None,
-                                    ignored: w.ignored,
-                                    script_type: script::ScriptType::GithubActions,
-                                })
-                                .collect();
-                            ParseResult::Functions(functions)
-                        }
-                        Err(e) => ParseResult::Error(path.display().to_string(), e),
-                    }
-                }
-            })
-        })
-        .collect();
-
-    // Collect results from all threads
-    let mut all_functions = Vec::new();
-    let mut parse_errors = Vec::new();
-    let mut nx_category_display_names = std::collections::HashMap::new();
-
-    for handle in parse_handles {
-        match handle.join() {
-            Ok(ParseResult::Functions(functions)) => {
-                all_functions.extend(functions);
-            }
-            Ok(ParseResult::NxFunctions(functions, display_names)) => {
-                all_functions.extend(functions);
-                nx_category_display_names.extend(display_names);
-            }
-            Ok(ParseResult::Error(path, err)) => {
-                parse_errors.push((path, err));
-            }
-            Err(_) => {
-                parse_errors.push((
-                    "unknown".to_string(),
-                    anyhow::anyhow!("Script parsing thread panicked"),
-                ));
-            }
-        }
+    let parse_started = Instant::now();
+    let parsed = script::parse_script_files(&script_files);
+    let parse_elapsed = parse_started.elapsed();
+    let all_functions = parsed.functions;
+    let parse_errors = parsed.parse_errors;
+
+    // Profiling mode: report discovery/parse timings and exit
+    if args.profile_discovery {
+        print_discovery_profile(
+            discovery_elapsed,
+            parse_elapsed,
+            &parsed.timings,
+            parse_errors.len(),
+        );
+        return Ok(());
     }
 
     // Report any parse errors
@@ -561,6 +406,21 @@ async fn run_application(args: Args) -> Result<()> {
         return Ok(());
     }
 
+    // List mode: print commands for other tools and exit (parse errors went to stderr)
+    if args.list {
+        println!("{}", format_function_list(&all_functions, args.format)?);
+        return Ok(());
+    }
+
+    // Headless mode: run a single command and exit with its exit code
+    if let Some(target) = args.run.as_deref() {
+        let code = run_target(target, &all_functions, &script_files, &current_dir)?;
+        if code != 0 {
+            std::process::exit(code);
+        }
+        return Ok(());
+    }
+
     if all_functions.is_empty() {
         anyhow::bail!(
             "No functions found in any scripts. Make sure your scripts define bash functions."
@@ -578,12 +438,11 @@ async fn run_application(args: Args) -> Result<()> {
     let mut terminal = Terminal::new(backend).context("Failed to create terminal")?;
 
     // Create app with formatted project name
-    let project_name = current_dir
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("Project");
-
-    let formatted_project_name = script::format_display_name(project_name);
+    let formatted_project_name = project_title(
+        args.project_name.as_deref(),
+        project_config.as_ref().and_then(|c| c.name.as_deref()),
+        &current_dir,
+    );
 
     // Load theme from config
     let config = ui::config::Config::load();
@@ -591,18 +450,49 @@ async fn run_application(args: Args) -> Result<()> {
         *ui::theme::Theme::by_name(&config.theme).unwrap_or_else(ui::theme::Theme::default_theme);
 
     let mut app = App::new(all_functions.clone(), formatted_project_name, theme);
-
-    // Build category display names map from script files
-    let mut category_display_names = std::collections::HashMap::new();
-    for script_file in &script_files {
-        category_display_names.insert(
-            script_file.category.clone(),
-            script_file.display_name.clone(),
+    app.execution_mode = config.execution_mode;
+    app.show_git_info = config.show_git_info;
+    app.command_timeout = args
+        .timeout
+        .or(config.timeout_secs)
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs);
+    app.finish_alerts = config.finish_alerts();
+    app.sequence_continue_on_error = config.sequence_continue_on_error;
+    app.colors_enabled = colors_enabled(args.no_color, std::env::var_os("NO_COLOR"));
+    match keymap::Keymap::from_config(&config.keys) {
+        Ok(remapped) => {
+            keymap::set_keymap(remapped);
+        }
+        Err(e) => app.set_status_message(format!(
+            "Invalid keys in config, using the default keybindings: {:#}",
+            e
+        )),
+    }
+    if config.load_dotenv {
+        app.dotenv_root = Some(current_dir.clone());
+    }
+    app.script_files = script_files.clone();
+
+    // Category display names (incl. one per Nx project) and descriptions
+    // (file header comments, manifest descriptions, Nx roots)
+    app.set_category_display_names(parsed.category_display_names);
+    app.set_category_descriptions(parsed.category_descriptions);
+
+    // Apply project-level settings from jarvis.toml (if present)
+    if let Some(project_config) = project_config {
+        app.set_category_order(&project_config.category_order);
+        app.set_hidden_categories(&project_config.hidden_categories);
+        app.rename_categories(&project_config.category_names);
+        app.danger_pattern = project_config.danger_regex().unwrap_or_default();
+        app.set_env_profiles(
+            project_config
+                .profiles
+                .into_iter()
+                .map(|(name, profile)| (name, profile.env))
+                .collect(),
         );
     }
-    // Add per-project Nx category display names (one per Nx project)
-    category_display_names.extend(nx_category_display_names);
-    app.set_category_display_names(category_display_names);
 
     // Initialize usage tracking (gracefully handle errors)
     let usage_tracker = match UsageTracker::new(current_dir.clone()) {
@@ -613,33 +503,78 @@ async fn run_application(args: Args) -> Result<()> {
         }
     };
 
-    // Load frequently used functions into the app
-    if let Some(ref tracker) = usage_tracker {
+    // Usage counts and timestamps back the "most used" and "recent" sort modes
+    if let Some(tracker) = usage_tracker.as_ref() {
         if let Ok(tracker_guard) = tracker.lock() {
-            let frequent_entries = tracker_guard.get_frequent(MAX_FREQUENT_COMMANDS);
-            let frequent_functions: Vec<script::ScriptFunction> = frequent_entries
-                .iter()
-                .filter_map(|entry| {
-                    // Find the matching function in all_functions
-                    all_functions
-                        .iter()
-                        .find(|f| {
-                            f.name == entry.function_name && f.script_type == entry.script_type
-                        })
-                        .cloned()
-                })
-                .collect();
+            app.set_usage_stats(tracker_guard.entries().clone());
+        }
+    }
+
+    // Load frequently used functions into the app (a limit of 0 hides the category)
+    let frequent_limit = config.effective_frequent_limit();
+    if let Some(tracker) = usage_tracker.as_ref().filter(|_| frequent_limit > 0) {
+        if let Ok(tracker_guard) = tracker.lock() {
+            let frequent_entries = tracker_guard.get_frequent(frequent_limit);
+            let mut frequent_functions: Vec<script::ScriptFunction> = Vec::new();
+            for entry in frequent_entries {
+                // Find the matching function in all_functions
+                let Some(func) = all_functions
+                    .iter()
+                    .find(|f| f.name == entry.function_name && f.script_type == entry.script_type)
+                else {
+                    continue;
+                };
+                if let Some(exit_code) = entry.last_exit_code {
+                    app.record_last_exit_code(func, exit_code);
+                }
+                frequent_functions.push(func.clone());
+            }
             app.set_frequent_functions(frequent_functions);
         }
     }
 
+    // Load recently used functions below them (a limit of 0 hides the category)
+    let recent_limit = config.recent_limit;
+    if let Some(tracker) = usage_tracker.as_ref().filter(|_| recent_limit > 0) {
+        if let Ok(tracker_guard) = tracker.lock() {
+            let mut recent_functions: Vec<script::ScriptFunction> = Vec::new();
+            for entry in tracker_guard.get_recent(recent_limit) {
+                let Some(func) = all_functions
+                    .iter()
+                    .find(|f| f.name == entry.function_name && f.script_type == entry.script_type)
+                else {
+                    continue;
+                };
+                if let Some(exit_code) = entry.last_exit_code {
+                    app.record_last_exit_code(func, exit_code);
+                }
+                recent_functions.push(func.clone());
+            }
+            app.set_recent_functions(recent_functions);
+        }
+    }
+
+    // Load pinned commands (before the UI state, so the "Pinned" category
+    // can be restored as expanded)
+    if let Ok(store) = PinStore::new(&current_dir) {
+        app.pins = store.load();
+        app.pin_store = Some(store);
+    }
+
+    // Restore expanded categories and selection from the last session
+    let ui_state_store = UiStateStore::new(&current_dir).ok();
+    if let Some(ref store) = ui_state_store {
+        app.restore_ui_state(&store.load());
+    }
+
     // Run the app and ensure cleanup happens even on error
     let mut event_reader = CrosstermEventReader;
     let mut deferred_warnings = Vec::new();
     let run_result = run_app(
         &mut terminal,
         &mut app,
-        &script_files,
+        &mut script_files,
+        &source,
         &mut event_reader,
         usage_tracker.clone(),
         &mut deferred_warnings,
@@ -649,6 +584,12 @@ async fn run_application(args: Args) -> Result<()> {
     // Restore terminal (always runs, even if run_app failed)
     let cleanup_result = cleanup_terminal(&mut terminal);
 
+    if let Some(ref store) = ui_state_store {
+        if let Err(e) = store.save(&app.ui_state()) {
+            deferred_warnings.push(format!("Failed to save UI state: {}", e));
+        }
+    }
+
     // Print any warnings that occurred while the TUI was active
     for warning in &deferred_warnings {
         eprintln!("Warning: {}", warning);
@@ -661,6 +602,201 @@ async fn run_application(args: Args) -> Result<()> {
     Ok(())
 }
 
+/// Load `jarvis.toml` from the project directory and install its binary
+/// overrides. Problems are reported as warnings; Jarvis still starts.
+fn load_project_config(project_dir: &std::path::Path) -> Option<ui::project_config::ProjectConfig> {
+    match ui::project_config::ProjectConfig::load(project_dir) {
+        Ok(Some(config)) => {
+            script::binaries::set_overrides(config.binaries.clone());
+            Some(config)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            eprintln!("Warning: {:#}", e);
+            None
+        }
+    }
+}
+
+/// Render functions for `--list`.
+fn format_function_list(
+    functions: &[script::ScriptFunction],
+    format: ListFormat,
+) -> Result<String> {
+    match format {
+        ListFormat::Text => {
+            // Keep one record per line: tabs and newlines would break the columns
+            let clean = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+            Ok(functions
+                .iter()
+                .map(|f| {
+                    format!(
+                        "{}\t{}\t{}\t{}",
+                        f.category,
+                        f.name,
+                        clean(&f.display_name),
+                        clean(&f.description)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        ListFormat::Json => {
+            serde_json::to_string_pretty(functions).context("Failed to serialize command list")
+        }
+    }
+}
+
+/// Resolve a `--run` target to a single function.
+///
+/// Matching order:
+/// 1. The exact [`ui::pty_runner::CommandHistory::key_for`] key (`Type:name`,
+///    type compared case-insensitively)
+/// 2. The exact function name, if it is unique
+/// 3. A `type:name` qualifier, where `type` is a case-insensitive prefix of the
+///    script type (so `nx:my-app:test` matches `NxJson:my-app:test`)
+fn resolve_target<'a>(
+    functions: &'a [script::ScriptFunction],
+    target: &str,
+) -> Result<&'a script::ScriptFunction> {
+    let target_lower = target.to_lowercase();
+    if let Some(func) = functions
+        .iter()
+        .find(|f| ui::pty_runner::CommandHistory::key_for(f).to_lowercase() == target_lower)
+    {
+        return Ok(func);
+    }
+
+    let by_name: Vec<_> = functions.iter().filter(|f| f.name == target).collect();
+    let candidates = if by_name.is_empty() {
+        match target.split_once(':') {
+            Some((qualifier, name)) => {
+                let qualifier = qualifier.to_lowercase();
+                functions
+                    .iter()
+                    .filter(|f| {
+                        f.name == name
+                            && f.script_type.name().to_lowercase().starts_with(&qualifier)
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    } else {
+        by_name
+    };
+
+    match candidates.as_slice() {
+        [func] => Ok(func),
+        [] => anyhow::bail!(
+            "No command matches '{}'. Available targets:\n{}",
+            target,
+            format_target_list(functions.iter())
+        ),
+        many => anyhow::bail!(
+            "'{}' is ambiguous; qualify it with its type:\n{}",
+            target,
+            format_target_list(many.iter().copied())
+        ),
+    }
+}
+
+/// One `Type:name` key per line, indented, for error messages
+fn format_target_list<'a>(functions: impl Iterator<Item = &'a script::ScriptFunction>) -> String {
+    functions
+        .map(|f| format!("  {}", ui::pty_runner::CommandHistory::key_for(f)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Run a `--run` target attached to the real terminal and return its exit code.
+/// Runs are recorded in the usage history like TUI runs.
+fn run_target(
+    target: &str,
+    functions: &[script::ScriptFunction],
+    script_files: &[script::ScriptFile],
+    project_dir: &std::path::Path,
+) -> Result<i32> {
+    let func = resolve_target(functions, target)?;
+    let script_file = ui::pty_runner::find_script_file(func, &func.category, script_files)
+        .with_context(|| format!("No script file found for '{}'", func.name))?;
+    if !script_file.available {
+        anyhow::bail!(
+            "Cannot run '{}': '{}' is not installed or not in PATH",
+            func.name,
+            script_file.binary().unwrap_or("its tool")
+        );
+    }
+
+    let env = if ui::config::Config::load().load_dotenv {
+        ui::pty_runner::working_dir(func, script_file)
+            .map(|dir| ui::dotenv::load_for(project_dir, &dir))
+            .unwrap_or_default()
+    } else {
+        ui::pty_runner::EnvOverrides::new()
+    };
+    let options = ui::pty_runner::RunOptions {
+        env,
+        ..Default::default()
+    };
+    let code = ui::pty_runner::run_foreground_command(func, script_file, &options)?;
+
+    if let Ok(mut tracker) = UsageTracker::new(project_dir.to_path_buf()) {
+        if let Err(e) = tracker.record(&func.name, func.script_type, &func.category, code) {
+            eprintln!("Warning: Failed to record usage: {}", e);
+        }
+    }
+
+    Ok(code)
+}
+
+/// Print a per-parser timing breakdown to stderr
+fn print_discovery_profile(
+    discovery: Duration,
+    parse_wall: Duration,
+    timings: &[script::ParseTiming],
+    error_count: usize,
+) {
+    // Aggregate per script type: (files, functions, total time, slowest file)
+    let mut by_type: Vec<(String, usize, usize, Duration, Duration)> = Vec::new();
+    for timing in timings {
+        let name = timing.script_type.to_string();
+        match by_type.iter_mut().find(|(n, ..)| *n == name) {
+            Some((_, files, functions, total, max)) => {
+                *files += 1;
+                *functions += timing.functions;
+                *total += timing.elapsed;
+                *max = (*max).max(timing.elapsed);
+            }
+            None => by_type.push((name, 1, timing.functions, timing.elapsed, timing.elapsed)),
+        }
+    }
+    by_type.sort_by_key(|(.., total, _)| std::cmp::Reverse(*total));
+
+    eprintln!("=== Discovery Profile ===");
+    eprintln!(
+        "Discovery: {:>9.2?} ({} script files)",
+        discovery,
+        timings.len()
+    );
+    eprintln!("Parsing:   {:>9.2?} wall clock (parallel)", parse_wall);
+    eprintln!();
+    eprintln!(
+        "{:<16} {:>6} {:>10} {:>12} {:>12}",
+        "Parser", "Files", "Functions", "Total", "Slowest"
+    );
+    for (name, files, functions, total, max) in &by_type {
+        eprintln!(
+            "{:<16} {:>6} {:>10} {:>12.2?} {:>12.2?}",
+            name, files, functions, total, max
+        );
+    }
+    if error_count > 0 {
+        eprintln!();
+        eprintln!("{} script file(s) failed to parse", error_count);
+    }
+}
+
 /// Clean up terminal state
 fn cleanup_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
     disable_raw_mode().context("Failed to disable raw mode")?;
@@ -677,301 +813,197 @@ fn cleanup_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Re
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::VecDeque;
+/// Restore the TUI after [`cleanup_terminal`] handed the terminal to another program
+fn resume_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    enable_raw_mode().context("Failed to enable raw mode for terminal")?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture
+    )
+    .context("Failed to setup terminal")?;
+    terminal.clear().context("Failed to clear terminal")?;
 
-    /// Mock event reader for testing that returns a predetermined sequence of events
-    struct MockEventReader {
-        events: VecDeque<Event>,
+    Ok(())
+}
+
+/// Suspend the TUI to open the selected function's script file in `$EDITOR`
+/// (at the function's line when known), then re-parse the file so edits show
+/// up right away.
+fn open_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+) -> Result<()> {
+    let Some((func, script_file)) = app.editor_target() else {
+        return Ok(());
+    };
+    let (program, args) =
+        ui::editor::editor_command(&ui::editor::editor_from_env(), &script_file.path, func.line);
+
+    cleanup_terminal(terminal)?;
+    let status = std::process::Command::new(&program).args(&args).status();
+    resume_terminal(terminal)?;
+
+    match status {
+        Ok(_) => app.reload_script_file(&script_file),
+        Err(e) => app.set_status_message(format!("Failed to start {}: {}", program, e)),
     }
+    Ok(())
+}
 
-    impl MockEventReader {
-        fn new(events: Vec<Event>) -> Self {
-            Self {
-                events: VecDeque::from(events),
+/// Run a function using the session's execution mode (inline PTY or full-screen)
+fn run_function(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    func: &script::ScriptFunction,
+    script_files: &[script::ScriptFile],
+    usage_tracker: Option<Arc<Mutex<UsageTracker>>>,
+    deferred_warnings: &mut Vec<String>,
+) -> Result<()> {
+    if !app.check_available(func) {
+        return Ok(());
+    }
+    let category = app.original_category(func);
+    app.record_last_run(func, &category);
+    match app.execution_mode {
+        ui::config::ExecutionMode::Inline => {
+            let size = terminal.size()?;
+            execute_inline(
+                app,
+                func,
+                script_files,
+                usage_tracker,
+                (size.width, size.height),
+                false,
+            )
+        }
+        ui::config::ExecutionMode::Fullscreen => {
+            let exit_code = execute_fullscreen(terminal, app, func, script_files)?;
+            // Usage is recorded here since there is no PTY to poll for completion
+            if let Some(exit_code) = exit_code {
+                app.record_last_exit_code(func, exit_code);
+                if let Some(ref tracker) = usage_tracker {
+                    if let Ok(mut tracker_guard) = tracker.lock() {
+                        if let Err(e) =
+                            tracker_guard.record(&func.name, func.script_type, &category, exit_code)
+                        {
+                            deferred_warnings.push(format!("Failed to record usage: {}", e));
+                        }
+                        app.set_usage_stats(tracker_guard.entries().clone());
+                    }
+                }
             }
+            Ok(())
         }
     }
+}
 
-    impl EventReader for MockEventReader {
-        fn read_event(&mut self, _timeout: Duration) -> Result<Option<Event>> {
-            Ok(self.events.pop_front())
+/// Activate the selected tree item, as with Enter: toggle a category, or run
+/// a command (asking for confirmation first when it needs it)
+fn activate_selected(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    script_files: &[script::ScriptFile],
+    usage_tracker: Option<Arc<Mutex<UsageTracker>>>,
+    deferred_warnings: &mut Vec<String>,
+) -> Result<()> {
+    match app.selected_item() {
+        Some(ui::app::TreeItem::Category(category)) => {
+            // Toggle category expansion
+            app.toggle_category(&category);
+            Ok(())
         }
+        Some(ui::app::TreeItem::Function(func)) => run_or_confirm(
+            terminal,
+            app,
+            func,
+            script_files,
+            usage_tracker,
+            deferred_warnings,
+        ),
+        None => Ok(()),
     }
+}
 
-    /// Helper to create a key event
-    fn key_event(code: KeyCode) -> Event {
-        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+/// Run a function, or open the confirmation modal first when it needs one
+fn run_or_confirm(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    func: script::ScriptFunction,
+    script_files: &[script::ScriptFile],
+    usage_tracker: Option<Arc<Mutex<UsageTracker>>>,
+    deferred_warnings: &mut Vec<String>,
+) -> Result<()> {
+    if !app.check_available(&func) {
+        return Ok(());
+    }
+    if app.requires_confirmation(&func) {
+        app.request_confirmation(func);
+        return Ok(());
     }
+    run_function(
+        terminal,
+        app,
+        &func,
+        script_files,
+        usage_tracker,
+        deferred_warnings,
+    )
+}
 
-    #[test]
-    fn test_mock_event_reader() {
-        let events = vec![
-            key_event(KeyCode::Char('a')),
-            key_event(KeyCode::Char('b')),
-            key_event(KeyCode::Enter),
-        ];
+/// Suspend the TUI, run a function attached to the real terminal, then restore
+/// the TUI once the user presses Enter. Returns the exit code, or `None` if no
+/// script file matched the function.
+fn execute_fullscreen(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    func: &script::ScriptFunction,
+    script_files: &[script::ScriptFile],
+) -> Result<Option<i32>> {
+    let original_category = app.original_category(func);
+    let Some(script_file) =
+        ui::pty_runner::find_script_file(func, &original_category, script_files)
+    else {
+        return Ok(None);
+    };
 
-        let mut reader = MockEventReader::new(events);
+    cleanup_terminal(terminal)?;
+    println!("\u{25b6} Running {}\n", func.display_name);
 
-        // Should return events in order
-        assert!(matches!(
-            reader.read_event(Duration::from_millis(10)).unwrap(),
-            Some(Event::Key(KeyEvent {
-                code: KeyCode::Char('a'),
-                ..
-            }))
-        ));
-        assert!(matches!(
-            reader.read_event(Duration::from_millis(10)).unwrap(),
-            Some(Event::Key(KeyEvent {
-                code: KeyCode::Char('b'),
-                ..
-            }))
-        ));
-        assert!(matches!(
-            reader.read_event(Duration::from_millis(10)).unwrap(),
-            Some(Event::Key(KeyEvent {
-                code: KeyCode::Enter,
-                ..
-            }))
-        ));
-
-        // Should return None when no more events
-        assert!(reader
-            .read_event(Duration::from_millis(10))
-            .unwrap()
-            .is_none());
-    }
-
-    #[test]
-    fn test_crossterm_event_reader_type() {
-        // Just verify that CrosstermEventReader exists and implements the trait
-        let _reader: Box<dyn EventReader> = Box::new(CrosstermEventReader);
-    }
-
-    #[tokio::test]
-    async fn test_run_application_nonexistent_directory() {
-        let args = Args {
-            path: Some(PathBuf::from("/nonexistent/directory/that/does/not/exist")),
-            file: None,
-            debug: false,
-        };
-
-        let result = run_application(args).await;
-        assert!(result.is_err());
-        let err_msg = result.unwrap_err().to_string();
-        assert!(err_msg.contains("Failed to access directory"));
-    }
-
-    #[tokio::test]
-    async fn test_run_application_file_instead_of_directory() {
-        use std::fs;
-        use tempfile::TempDir;
-
-        let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("notadir.txt");
-        fs::write(&file_path, "test content").unwrap();
-
-        let args = Args {
-            path: Some(file_path.clone()),
-            file: None,
-            debug: false,
-        };
-
-        let result = run_application(args).await;
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_args_parsing_with_path() {
-        // Test that Args can parse path argument
-        let args = Args {
-            path: Some(PathBuf::from("/some/path")),
-            file: None,
-            debug: false,
-        };
-        assert_eq!(args.path, Some(PathBuf::from("/some/path")));
-    }
-
-    #[test]
-    fn test_args_parsing_without_path() {
-        // Test that Args works without path
-        let args = Args {
-            path: None,
-            file: None,
-            debug: false,
-        };
-        assert_eq!(args.path, None);
-    }
-
-    #[test]
-    fn test_args_parsing_with_file() {
-        // Test that Args can parse file argument
-        let args = Args {
-            path: None,
-            file: Some(PathBuf::from("/some/file.sh")),
-            debug: false,
-        };
-        assert_eq!(args.file, Some(PathBuf::from("/some/file.sh")));
-    }
-
-    #[tokio::test]
-    async fn test_run_application_with_file_nonexistent() {
-        let args = Args {
-            path: None,
-            file: Some(PathBuf::from("/nonexistent/file.sh")),
-            debug: false,
-        };
-
-        let result = run_application(args).await;
-        assert!(result.is_err());
-        let err_msg = result.unwrap_err().to_string();
-        assert!(err_msg.contains("Failed to access file"));
-    }
-
-    #[tokio::test]
-    async fn test_run_application_with_file_unsupported_type() {
-        use std::fs;
-        use tempfile::TempDir;
-
-        let temp_dir = TempDir::new().unwrap();
-        let txt_path = temp_dir.path().join("readme.txt");
-        fs::write(&txt_path, "text content").unwrap();
-
-        let args = Args {
-            path: None,
-            file: Some(txt_path),
-            debug: false,
-        };
-
-        let result = run_application(args).await;
-        assert!(result.is_err());
-        let err_msg = format!("{:?}", result.unwrap_err());
-        // The error chain includes "Failed to parse file" and "Unsupported file type"
-        assert!(
-            err_msg.contains("Unsupported file type") || err_msg.contains("Failed to parse file")
-        );
-    }
-
-    // --- key_event_to_bytes tests ---
-
-    #[test]
-    fn test_key_event_to_bytes_regular_char() {
-        let ke = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&ke), vec![b'a']);
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_uppercase_char() {
-        let ke = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
-        assert_eq!(key_event_to_bytes(&ke), vec![b'A']);
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_ctrl_c() {
-        let ke = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
-        assert_eq!(key_event_to_bytes(&ke), vec![0x03]); // ETX
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_ctrl_a() {
-        let ke = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
-        assert_eq!(key_event_to_bytes(&ke), vec![0x01]); // SOH
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_ctrl_z() {
-        let ke = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL);
-        assert_eq!(key_event_to_bytes(&ke), vec![0x1A]); // SUB
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_enter() {
-        let ke = KeyEvent::new(KeyCode::Enter, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&ke), vec![b'\r']);
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_backspace() {
-        let ke = KeyEvent::new(KeyCode::Backspace, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&ke), vec![0x7f]);
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_escape() {
-        let ke = KeyEvent::new(KeyCode::Esc, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&ke), vec![0x1b]);
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_tab() {
-        let ke = KeyEvent::new(KeyCode::Tab, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&ke), vec![b'\t']);
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_arrow_keys() {
-        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&up), b"\x1b[A".to_vec());
-
-        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&down), b"\x1b[B".to_vec());
-
-        let right = KeyEvent::new(KeyCode::Right, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&right), b"\x1b[C".to_vec());
-
-        let left = KeyEvent::new(KeyCode::Left, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&left), b"\x1b[D".to_vec());
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_function_keys() {
-        let f1 = KeyEvent::new(KeyCode::F(1), KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&f1), b"\x1bOP".to_vec());
-
-        let f5 = KeyEvent::new(KeyCode::F(5), KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&f5), b"\x1b[15~".to_vec());
-
-        let f12 = KeyEvent::new(KeyCode::F(12), KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&f12), b"\x1b[24~".to_vec());
-    }
-
-    #[test]
-    fn test_key_event_to_bytes_special_keys() {
-        let home = KeyEvent::new(KeyCode::Home, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&home), b"\x1b[H".to_vec());
-
-        let end = KeyEvent::new(KeyCode::End, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&end), b"\x1b[F".to_vec());
-
-        let del = KeyEvent::new(KeyCode::Delete, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&del), b"\x1b[3~".to_vec());
-
-        let insert = KeyEvent::new(KeyCode::Insert, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&insert), b"\x1b[2~".to_vec());
-
-        let pgup = KeyEvent::new(KeyCode::PageUp, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&pgup), b"\x1b[5~".to_vec());
-
-        let pgdn = KeyEvent::new(KeyCode::PageDown, KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&pgdn), b"\x1b[6~".to_vec());
-    }
+    let started_at = Instant::now();
+    let options = app.run_options(func, script_file);
+    let result = ui::pty_runner::run_foreground_command(func, script_file, &options);
 
-    #[test]
-    fn test_key_event_to_bytes_utf8_char() {
-        let ke = KeyEvent::new(KeyCode::Char('\u{00e9}'), KeyModifiers::empty()); // 'é'
-        let bytes = key_event_to_bytes(&ke);
-        assert_eq!(std::str::from_utf8(&bytes).unwrap(), "\u{00e9}");
-    }
+    let summary = match result {
+        Ok(code) => format!(
+            "{} ran in full-screen mode and exited with code {}.",
+            func.display_name, code
+        ),
+        Err(ref e) => format!("{} failed to start: {:#}", func.display_name, e),
+    };
+    println!("\n{}\nPress Enter to return to Jarvis...", summary);
+    let mut line = String::new();
+    let _ = io::stdin().read_line(&mut line);
+
+    resume_terminal(terminal)?;
+
+    // Keep a record of the run so the output pane shows its status
+    let exit_code = result.unwrap_or(1);
+    let mut tracking_func = func.clone();
+    tracking_func.category = original_category.clone();
+    app.command_history.insert(
+        &tracking_func,
+        ui::pty_runner::ExecutionState::finished(
+            &func.display_name,
+            &original_category,
+            exit_code,
+            started_at,
+            &summary,
+        ),
+    );
+    app.active_function = Some(tracking_func);
 
-    #[test]
-    fn test_key_event_to_bytes_unknown_returns_empty() {
-        let ke = KeyEvent::new(KeyCode::F(20), KeyModifiers::empty());
-        assert_eq!(key_event_to_bytes(&ke), Vec::<u8>::new());
-    }
+    Ok(Some(exit_code))
 }
 
 /// Execute a selected function inline using PTY
@@ -981,19 +1013,9 @@ fn execute_inline(
     script_files: &[script::ScriptFile],
     _usage_tracker: Option<Arc<Mutex<UsageTracker>>>,
     terminal_size: (u16, u16),
+    dry_run: bool,
 ) -> Result<()> {
-    let func_name = func.name.clone();
-
-    // If the function is from "Frequently Used" category, find the original category
-    let original_category = if func.category == FREQUENTLY_USED_CATEGORY {
-        app.functions
-            .iter()
-            .find(|f| f.name == func_name && f.script_type == func.script_type)
-            .map(|f| f.category.clone())
-            .unwrap_or_else(|| func.category.clone())
-    } else {
-        func.category.clone()
-    };
+    let original_category = app.original_category(func);
 
     // Find the script file
     if let Some(script_file) =
@@ -1008,8 +1030,19 @@ fn execute_inline(
         let rows = terminal_size.1.saturating_sub(6).max(10);
 
         // Spawn the command in a PTY
-        let handle =
-            ui::pty_runner::spawn_pty_command(func, script_file, &original_category, cols, rows)?;
+        let options = ui::pty_runner::RunOptions {
+            dry_run,
+            ..app.run_options(func, script_file)
+        };
+        let handle = ui::pty_runner::spawn_pty_command(
+            func,
+            script_file,
+            &original_category,
+            &options,
+            app.command_timeout,
+            cols,
+            rows,
+        )?;
 
         // Store the original function for tracking
         let mut tracking_func = func.clone();
@@ -1019,8 +1052,9 @@ fn execute_inline(
         // Store the PTY handle
         app.pty_handle = Some(handle);
 
-        // Reset output scroll to bottom (most recent)
+        // Reset output scroll to bottom (most recent) and drop stale search matches
         app.output_scroll = 0;
+        app.clear_output_search();
 
         // Focus on the output pane
         app.focus = ui::app::FocusPane::Output;
@@ -1088,76 +1122,225 @@ fn key_event_to_bytes(key: &KeyEvent) -> Vec<u8> {
     }
 }
 
-async fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    app: &mut App,
-    script_files: &[script::ScriptFile],
-    event_reader: &mut dyn EventReader,
-    usage_tracker: Option<Arc<Mutex<UsageTracker>>>,
-    deferred_warnings: &mut Vec<String>,
-) -> Result<()> {
-    // Track whether we need to record usage for completed commands
-    let mut pending_usage_record: Option<(String, script::ScriptType, String)> = None;
+/// Where the session's scripts come from, so they can be discovered again
+/// on refresh (`R`)
+enum ScriptSource {
+    /// A single file given with `--file`
+    File(PathBuf),
+    /// A project directory
+    Directory(PathBuf, script::DiscoveryOptions),
+}
 
-    // Theme saved before opening the picker (for cancel/restore)
-    let mut theme_before_picker: Option<ui::theme::Theme> = None;
+impl ScriptSource {
+    fn discover(&self) -> Result<Vec<script::ScriptFile>> {
+        match self {
+            Self::File(path) => Ok(vec![script::discover_single_file(path)?]),
+            Self::Directory(root, options) => script::discover_script_files(root, options),
+        }
+    }
+
+    /// Directory watched for changes by run-and-watch (`w`)
+    fn root(&self) -> &std::path::Path {
+        match self {
+            Self::File(path) => path.parent().unwrap_or(path),
+            Self::Directory(root, _) => root,
+        }
+    }
+
+    /// Exclude patterns that run-and-watch skips, as used for discovery
+    fn excludes(&self) -> script::ExcludeSet {
+        match self {
+            Self::File(_) => script::ExcludeSet::default(),
+            // Already validated by the initial discovery
+            Self::Directory(_, options) => {
+                script::ExcludeSet::new(&options.exclude).unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// Discover and parse the scripts again (`R`), keeping the current list when
+/// discovery itself fails
+fn refresh_scripts(
+    app: &mut App,
+    source: &ScriptSource,
+    script_files: &mut Vec<script::ScriptFile>,
+) {
+    match source.discover() {
+        Ok(files) => {
+            let parsed = script::parse_script_files(&files);
+            script_files.clone_from(&files);
+            app.apply_discovery(files, parsed);
+        }
+        Err(e) => app.set_status_message(format!("Refresh failed: {:#}", e)),
+    }
+}
+
+async fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    script_files: &mut Vec<script::ScriptFile>,
+    source: &ScriptSource,
+    event_reader: &mut dyn EventReader,
+    usage_tracker: Option<Arc<Mutex<UsageTracker>>>,
+    deferred_warnings: &mut Vec<String>,
+) -> Result<()> {
+    // Track whether we need to record usage for completed commands
+    let mut pending_usage_record: Option<(String, script::ScriptType, String, i32)> = None;
+
+    // Theme saved before opening the picker (for cancel/restore)
+    let mut theme_before_picker: Option<ui::theme::Theme> = None;
+
+    // Redraw on the first pass, after every event, and on each tick while the
+    // UI animates; an idle screen is left alone so it costs no CPU
+    let mut needs_redraw = true;
+    let mut was_ticking = false;
+
+    let mut completion = ui::notify::CompletionDetector::default();
 
     loop {
         // Check if a running PTY has completed
-        if let Some(ref handle) = app.pty_handle {
-            let status = handle.poll_status();
-            if status == ui::pty_runner::ExecutionStatus::Succeeded
-                || status == ui::pty_runner::ExecutionStatus::Failed
-            {
-                // Record the details before finalizing
-                if status == ui::pty_runner::ExecutionStatus::Succeeded {
-                    if let Some(ref func) = app.active_function {
-                        pending_usage_record =
-                            Some((func.name.clone(), func.script_type, func.category.clone()));
-                    }
-                }
-                app.finalize_pty();
+        let polled = app
+            .pty_handle
+            .as_ref()
+            .map(|handle| (handle.poll_status(), handle.poll_exit_code()));
+
+        // Alert once on the tick the command finishes (opt-in bell / notification)
+        if let Some(status) = completion.update(polled.map(|(status, _)| status)) {
+            if let Some(handle) = app.pty_handle.as_ref() {
+                app.finish_alerts
+                    .alert(&handle.display_name, status, handle.started_at.elapsed());
+            }
+        }
+
+        let finished = polled.filter(|(status, _)| {
+            *status == ui::pty_runner::ExecutionStatus::Succeeded
+                || *status == ui::pty_runner::ExecutionStatus::Failed
+        });
+        if let Some((status, exit_code)) = finished {
+            // Record the details before finalizing
+            let exit_code = exit_code
+                .unwrap_or_else(|| i32::from(status != ui::pty_runner::ExecutionStatus::Succeeded));
+            // Dry runs are previews, not uses of the command
+            let dry_run = app.pty_handle.as_ref().is_some_and(|handle| handle.dry_run);
+            let finished_func = app.active_function.clone();
+            if let Some(func) = finished_func.clone().filter(|_| !dry_run) {
+                app.record_last_exit_code(&func, exit_code);
+                pending_usage_record =
+                    Some((func.name, func.script_type, func.category, exit_code));
             }
+            app.finalize_pty();
+
+            // Start the next step of a running sequence
+            let next_step = finished_func.and_then(|func| app.sequence_next(&func, exit_code == 0));
+            if let Some(func) = next_step {
+                let category = app.original_category(&func);
+                app.record_last_run(&func, &category);
+                let size = terminal.size()?;
+                execute_inline(
+                    app,
+                    &func,
+                    script_files,
+                    usage_tracker.clone(),
+                    (size.width, size.height),
+                    false,
+                )?;
+                needs_redraw = true;
+            }
+        }
+
+        // Re-run the watched command once file changes have settled, keeping
+        // the focus where it was
+        if let Some(func) = app.watch_rerun_target() {
+            let focus = app.focus;
+            let size = terminal.size()?;
+            execute_inline(
+                app,
+                &func,
+                script_files,
+                usage_tracker.clone(),
+                (size.width, size.height),
+                false,
+            )?;
+            app.focus = focus;
+            needs_redraw = true;
         }
 
+        // Hint when a running command has gone quiet, likely waiting on a prompt
+        app.awaiting_input = app
+            .pty_handle
+            .as_ref()
+            .is_some_and(ui::pty_runner::PtyHandle::is_probably_waiting_for_input);
+
         // Process pending usage recording
-        if let Some((func_name, script_type, category)) = pending_usage_record.take() {
+        if let Some((func_name, script_type, category, exit_code)) = pending_usage_record.take() {
             if let Some(ref tracker) = usage_tracker {
                 if let Ok(mut tracker_guard) = tracker.lock() {
-                    if let Err(e) = tracker_guard.record(&func_name, script_type, &category) {
+                    if let Err(e) =
+                        tracker_guard.record(&func_name, script_type, &category, exit_code)
+                    {
                         deferred_warnings.push(format!("Failed to record usage: {}", e));
                     }
+                    app.set_usage_stats(tracker_guard.entries().clone());
                 }
             }
         }
 
-        terminal
-            .draw(|f| ui::render(f, app))
-            .context("Failed to draw terminal UI")?;
+        // Refresh git provenance for the selected command (cached per file)
+        if app.show_git_info {
+            let source_path = app.selected_function().and_then(|func| {
+                ui::pty_runner::find_script_file(&func, &func.category, script_files)
+                    .map(|sf| sf.path.clone())
+            });
+            app.update_git_info(source_path.as_deref());
+        }
 
-        // Use a short timeout for polling so we can update animations and PTY output
-        let poll_timeout = if app.pty_handle.is_some() {
-            Duration::from_millis(16) // ~60fps when a command is running
-        } else {
-            Duration::from_millis(100) // Normal or showing results
-        };
+        // One more frame once ticking stops, so the final state is shown
+        let ticking = app.needs_tick();
+        if needs_redraw || ticking || was_ticking {
+            app.follow_output();
+            terminal
+                .draw(|f| ui::render(f, app))
+                .context("Failed to draw terminal UI")?;
+        }
+        was_ticking = ticking;
 
+        let poll_timeout = if ticking { TICK_RATE } else { IDLE_POLL };
         let event = event_reader.read_event(poll_timeout)?;
 
-        // If no event, continue the loop (re-render for animations/PTY updates)
+        // If no event, continue the loop (tick: re-render for animations/PTY updates)
         let event = match event {
             Some(e) => e,
-            None => continue,
+            None => {
+                needs_redraw = false;
+                continue;
+            }
         };
+        needs_redraw = true;
 
         if let Event::Key(key) = event {
+            // Modals resolve keys with the script list bindings, so remaps apply
+            let modal_action = keymap::action_for(KeyContext::Normal, &key);
+            let is_esc = key.code == KeyCode::Esc;
+
+            // Handle the help overlay
+            if app.show_help {
+                if is_esc || matches!(modal_action, Some(Action::ToggleHelp | Action::Quit)) {
+                    app.toggle_help();
+                }
+                continue;
+            }
+
+            // Any key closes the "explain command" modal
+            if app.explain.is_some() {
+                app.close_explain();
+                continue;
+            }
+
             // Handle info modal close first
             if app.show_info {
-                match key.code {
-                    KeyCode::Char('i') | KeyCode::Esc => {
-                        app.toggle_info();
-                    }
-                    _ => {}
+                if is_esc || modal_action == Some(Action::ToggleInfo) {
+                    app.toggle_info();
                 }
                 continue;
             }
@@ -1165,8 +1348,8 @@ async fn run_app(
             // Handle theme picker modal
             if app.show_theme_picker {
                 let themes = ui::theme::Theme::all();
-                match key.code {
-                    KeyCode::Esc | KeyCode::Char('t') => {
+                match modal_action {
+                    _ if is_esc || modal_action == Some(Action::OpenThemePicker) => {
                         // Cancel: restore the previous theme
                         if let Some(ref saved) = theme_before_picker {
                             app.theme = *saved;
@@ -1178,12 +1361,12 @@ async fn run_app(
                         app.show_theme_picker = false;
                         theme_before_picker = None;
                     }
-                    KeyCode::Down | KeyCode::Char('j') => {
+                    Some(Action::Next) => {
                         app.theme_picker_index = (app.theme_picker_index + 1) % themes.len();
                         // Live preview: apply the highlighted theme immediately
                         app.theme = themes[app.theme_picker_index];
                     }
-                    KeyCode::Up | KeyCode::Char('k') => {
+                    Some(Action::Previous) => {
                         if app.theme_picker_index == 0 {
                             app.theme_picker_index = themes.len() - 1;
                         } else {
@@ -1191,13 +1374,13 @@ async fn run_app(
                         }
                         app.theme = themes[app.theme_picker_index];
                     }
-                    KeyCode::Enter => {
+                    Some(Action::Activate) => {
                         // Confirm: keep the current theme and save config
                         app.show_theme_picker = false;
                         theme_before_picker = None;
-                        let config = ui::config::Config {
-                            theme: app.theme.name.to_string(),
-                        };
+                        // Reload so other persisted settings are kept as-is
+                        let mut config = ui::config::Config::load();
+                        config.theme = app.theme.name.to_string();
                         if let Err(e) = config.save() {
                             deferred_warnings.push(format!("Failed to save theme config: {}", e));
                         }
@@ -1207,44 +1390,141 @@ async fn run_app(
                 continue;
             }
 
-            // Handle search mode separately
-            if app.search_mode {
+            // Handle the Terraform var file picker
+            if app.var_file_picker.is_some() {
+                match modal_action {
+                    _ if is_esc || modal_action == Some(Action::SelectVarFile) => {
+                        app.close_var_file_picker();
+                    }
+                    Some(Action::Next) => app.var_file_picker_next(),
+                    Some(Action::Previous) => app.var_file_picker_previous(),
+                    Some(Action::Activate) => app.confirm_var_file_picker(),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Handle the category jump overlay
+            if app.show_category_jump {
                 match key.code {
-                    KeyCode::Esc => {
-                        app.exit_search_mode();
+                    KeyCode::Esc => app.close_category_jump(),
+                    KeyCode::Enter => app.confirm_category_jump(),
+                    KeyCode::Down => app.category_jump_next(),
+                    KeyCode::Up => app.category_jump_previous(),
+                    KeyCode::Backspace => app.category_jump_pop_char(),
+                    KeyCode::Char(c) => app.category_jump_push_char(c),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Handle the command palette
+            if app.palette_mode {
+                let has_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                match key.code {
+                    KeyCode::Esc => app.close_palette(),
+                    KeyCode::Enter => {
+                        if let Some(func) = app.confirm_palette() {
+                            run_or_confirm(
+                                terminal,
+                                app,
+                                func,
+                                script_files,
+                                usage_tracker.clone(),
+                                deferred_warnings,
+                            )?;
+                        }
                     }
-                    KeyCode::Down => {
-                        app.next();
+                    KeyCode::Down => app.palette_next(),
+                    KeyCode::Up => app.palette_previous(),
+                    KeyCode::Char('n') if has_ctrl => app.palette_next(),
+                    KeyCode::Char('p') if has_ctrl => app.palette_previous(),
+                    KeyCode::Backspace => app.palette_pop_char(),
+                    KeyCode::Char(c) if !has_ctrl => app.palette_push_char(c),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Handle the environment overrides modal
+            if app.env_target.is_some() {
+                match key.code {
+                    KeyCode::Esc => app.close_env_editor(),
+                    KeyCode::Enter => app.confirm_env_editor(),
+                    KeyCode::Backspace => app.env_pop_char(),
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.env_push_char(c);
                     }
-                    KeyCode::Up => {
-                        app.previous();
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Handle the confirmation modal (`@sudo` and destructive commands)
+            if let Some(pending) = app.pending_confirmation.clone() {
+                match keymap::action_for(KeyContext::Confirm, &key) {
+                    Some(Action::Activate) => {
+                        app.cancel_confirmation();
+                        run_function(
+                            terminal,
+                            app,
+                            &pending.func,
+                            script_files,
+                            usage_tracker.clone(),
+                            deferred_warnings,
+                        )?;
+                        if app.search_mode {
+                            app.exit_search_mode();
+                        }
                     }
-                    KeyCode::Backspace => {
-                        app.search_pop_char();
+                    Some(Action::Back) => app.cancel_confirmation(),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Handle typing an output search query
+            if app.output_search_input {
+                match key.code {
+                    KeyCode::Esc => app.clear_output_search(),
+                    KeyCode::Enter => app.confirm_output_search(),
+                    KeyCode::Backspace => app.output_search_pop_char(),
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.output_search_push_char(c);
                     }
-                    KeyCode::Enter => {
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Handle search mode separately
+            if app.search_mode {
+                match keymap::action_for(KeyContext::Search, &key) {
+                    Some(Action::Back) => app.exit_search_mode(),
+                    Some(Action::Next) => app.next(),
+                    Some(Action::Previous) => app.previous(),
+                    Some(Action::DeleteChar) => app.search_pop_char(),
+                    Some(Action::Activate) => {
                         // Execute function if one is selected
                         if let Some(ui::app::TreeItem::Function(func)) = app.selected_item() {
-                            let size = terminal.size()?;
-                            execute_inline(
+                            run_or_confirm(
+                                terminal,
                                 app,
-                                &func,
+                                func,
                                 script_files,
                                 usage_tracker.clone(),
-                                (size.width, size.height),
+                                deferred_warnings,
                             )?;
                             app.exit_search_mode();
                         }
                     }
-                    KeyCode::Char(c) => {
-                        app.search_push_char(c);
+                    _ => {
+                        if let KeyCode::Char(c) = key.code {
+                            app.search_push_char(c);
+                        }
                     }
-                    _ => {}
                 }
             } else if app.focus == ui::app::FocusPane::Output {
-                // Output pane keybindings
-                let has_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
-
                 // Check if a PTY is running AND the selected function is the one running
                 let selected = app.selected_function();
                 let is_running = if let (Some(ref handle), Some(ref active), Some(ref sel)) =
@@ -1259,14 +1539,14 @@ async fn run_app(
 
                 if is_running {
                     // --- Interactive PTY mode: forward input to the running process ---
-                    // Only Esc and Tab are reserved for TUI navigation
-                    match key.code {
-                        KeyCode::Esc => {
+                    // Only the Terminal bindings are reserved for TUI navigation
+                    match keymap::action_for(KeyContext::Terminal, &key) {
+                        Some(Action::Back) => {
                             app.focus = ui::app::FocusPane::ScriptList;
                             app.clear_mouse_selection();
                             app.pending_g = false;
                         }
-                        KeyCode::Tab if !has_ctrl => {
+                        Some(Action::ToggleFocus) => {
                             app.toggle_focus();
                         }
                         _ => {
@@ -1283,108 +1563,225 @@ async fn run_app(
                     // --- Scroll/review mode: command finished, navigate output ---
                     let size = terminal.size()?;
                     let visible_height = size.height.saturating_sub(6) as usize;
+                    let was_pending_g = std::mem::take(&mut app.pending_g);
 
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => {
-                            // Return focus to script list (don't quit)
-                            app.focus = ui::app::FocusPane::ScriptList;
-                            app.clear_mouse_selection();
-                            app.pending_g = false;
-                        }
-                        KeyCode::Tab => {
-                            app.toggle_focus();
-                        }
-                        KeyCode::Char('j') | KeyCode::Down => {
-                            app.scroll_output_down();
-                            app.pending_g = false;
-                        }
-                        KeyCode::Char('k') | KeyCode::Up => {
-                            app.scroll_output_up();
-                            app.pending_g = false;
+                    match keymap::action_for(KeyContext::Output, &key) {
+                        Some(Action::Back) => {
+                            if app.has_output_search() {
+                                app.clear_output_search();
+                            } else {
+                                // Return focus to script list (don't quit)
+                                app.focus = ui::app::FocusPane::ScriptList;
+                                app.clear_mouse_selection();
+                            }
                         }
-                        KeyCode::Char('d') if has_ctrl => {
+                        Some(Action::SearchOutput) => app.start_output_search(),
+                        Some(Action::NextMatch) => app.output_search_next(),
+                        Some(Action::PreviousMatch) => app.output_search_previous(),
+                        Some(Action::ToggleFocus) => app.toggle_focus(),
+                        Some(Action::Next) => app.scroll_output_down(),
+                        Some(Action::Previous) => app.scroll_output_up(),
+                        Some(Action::HalfPageDown) => {
                             app.scroll_output_half_page_down(visible_height);
                         }
-                        KeyCode::Char('u') if has_ctrl => {
+                        Some(Action::HalfPageUp) => {
                             app.scroll_output_half_page_up(visible_height);
                         }
-                        KeyCode::Char('G') => {
-                            app.scroll_output_to_bottom();
-                            app.pending_g = false;
-                        }
-                        KeyCode::Char('g') => {
-                            if app.pending_g {
+                        Some(Action::Last) => app.scroll_output_to_bottom(),
+                        Some(Action::First) => {
+                            if was_pending_g {
                                 app.scroll_output_to_top();
-                                app.pending_g = false;
                             } else {
                                 app.pending_g = true;
                             }
                         }
-                        KeyCode::Char('i') => {
-                            app.toggle_info();
-                            app.pending_g = false;
+                        Some(Action::ToggleInfo) => app.toggle_info(),
+                        Some(Action::ToggleHelp) => app.toggle_help(),
+                        Some(Action::CopyOutput) => {
+                            app.copy_full_output();
                         }
-                        _ => {
-                            app.pending_g = false;
+                        Some(Action::ToggleWrap) => app.toggle_wrap_output(),
+                        Some(Action::ToggleLineNumbers) => app.toggle_line_numbers(),
+                        Some(Action::ToggleFullscreen) => app.toggle_fullscreen_output(),
+                        Some(Action::ClearOutput) => app.clear_output(),
+                        Some(Action::SaveOutput) => {
+                            app.save_full_output(std::path::Path::new("."));
                         }
+                        _ => {}
                     }
                 }
             } else {
                 // Normal mode keybindings (ScriptList or Details focus)
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Char('Q') => {
-                        app.should_quit = true;
-                    }
-                    KeyCode::Char('i') => {
-                        app.toggle_info();
+                let was_pending_g = std::mem::take(&mut app.pending_g);
+                let in_list = app.focus == ui::app::FocusPane::ScriptList;
+                if app.pending_letter_jump {
+                    let letter = match key.code {
+                        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            Some(c)
+                        }
+                        _ => None,
+                    };
+                    app.finish_letter_jump(letter);
+                    continue;
+                }
+                match keymap::action_for(KeyContext::Normal, &key) {
+                    Some(Action::Quit) => app.should_quit = true,
+                    Some(Action::Refresh) => refresh_scripts(app, source, script_files),
+                    Some(Action::ToggleInfo) => app.toggle_info(),
+                    Some(Action::ToggleHelp) => app.toggle_help(),
+                    Some(Action::StartSearch) => app.enter_search_mode(),
+                    Some(Action::OpenPalette) => app.open_palette(),
+                    Some(Action::CancelCommand) => {
+                        app.cancel_running_command();
                     }
-                    KeyCode::Char('/') => {
-                        app.enter_search_mode();
+                    Some(Action::JumpToCategory) => app.open_category_jump(),
+                    Some(Action::JumpToLetter) => app.start_letter_jump(),
+                    Some(Action::ExpandAll) => app.expand_all(),
+                    Some(Action::CollapseAll) => app.collapse_all(),
+                    Some(Action::CycleTypeFilter) => app.cycle_type_filter(),
+                    Some(Action::CycleSort) => app.cycle_sort_mode(),
+                    Some(Action::ExplainCommand) => app.open_explain(),
+                    Some(Action::CycleEnvProfile) => {
+                        app.cycle_env_profile();
                     }
-                    KeyCode::Char('t') => {
-                        // Open theme picker
-                        theme_before_picker = Some(app.theme);
-                        app.show_theme_picker = true;
+                    Some(Action::OpenInEditor) => open_in_editor(terminal, app)?,
+                    Some(Action::CopyCommand) => {
+                        app.copy_command_line();
                     }
-                    KeyCode::Tab => {
-                        app.toggle_focus();
+                    Some(Action::DryRun) => {
+                        if let Some(func) = app.dry_run_target() {
+                            let size = terminal.size()?;
+                            execute_inline(
+                                app,
+                                &func,
+                                script_files,
+                                usage_tracker.clone(),
+                                (size.width, size.height),
+                                true,
+                            )?;
+                        }
                     }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        app.next();
+                    Some(Action::ToggleExecutionMode) => app.toggle_execution_mode(),
+                    Some(Action::EditEnv) => app.open_env_editor(),
+                    Some(Action::EditMakeVars) => app.open_make_vars_editor(),
+                    Some(Action::SelectVarFile) => app.open_var_file_picker(),
+                    Some(Action::TogglePin) => app.toggle_pin(),
+                    Some(Action::ToggleWatch) => {
+                        if app.watch.is_some() {
+                            app.stop_watch();
+                        } else if let Some(func) =
+                            app.start_watch(source.root(), &source.excludes())
+                        {
+                            let category = app.original_category(&func);
+                            app.record_last_run(&func, &category);
+                            let size = terminal.size()?;
+                            execute_inline(
+                                app,
+                                &func,
+                                script_files,
+                                usage_tracker.clone(),
+                                (size.width, size.height),
+                                false,
+                            )?;
+                        }
                     }
-                    KeyCode::Up | KeyCode::Char('k') => {
-                        app.previous();
+                    Some(Action::ToggleMark) => app.toggle_mark(),
+                    Some(Action::RunSequence) => {
+                        if let Some(func) = app.start_sequence() {
+                            let category = app.original_category(&func);
+                            app.record_last_run(&func, &category);
+                            let size = terminal.size()?;
+                            execute_inline(
+                                app,
+                                &func,
+                                script_files,
+                                usage_tracker.clone(),
+                                (size.width, size.height),
+                                false,
+                            )?;
+                        }
                     }
-                    KeyCode::Left | KeyCode::Char('h') => {
-                        if app.focus == ui::app::FocusPane::ScriptList {
-                            app.handle_left();
+                    Some(Action::Rerun) => {
+                        if let Some(func) = app.rerun_target() {
+                            run_or_confirm(
+                                terminal,
+                                app,
+                                func,
+                                script_files,
+                                usage_tracker.clone(),
+                                deferred_warnings,
+                            )?;
                         }
                     }
-                    KeyCode::Right | KeyCode::Char('l') => {
-                        if app.focus == ui::app::FocusPane::ScriptList {
-                            app.handle_right();
+                    Some(Action::OpenThemePicker) => {
+                        theme_before_picker = Some(app.theme);
+                        app.show_theme_picker = true;
+                    }
+                    Some(Action::ToggleFocus) => app.toggle_focus(),
+                    Some(Action::Next) => app.next(),
+                    Some(Action::Previous) => app.previous(),
+                    Some(Action::First) => {
+                        if was_pending_g {
+                            app.select_first();
+                        } else {
+                            app.pending_g = true;
                         }
                     }
-                    KeyCode::Enter => {
-                        // Handle Enter based on selected item
-                        if let Some(item) = app.selected_item() {
-                            match item {
-                                ui::app::TreeItem::Category(category) => {
-                                    // Toggle category expansion
-                                    app.toggle_category(&category);
-                                }
-                                ui::app::TreeItem::Function(func) => {
-                                    let size = terminal.size()?;
-                                    execute_inline(
-                                        app,
-                                        &func,
-                                        script_files,
-                                        usage_tracker.clone(),
-                                        (size.width, size.height),
-                                    )?;
-                                }
-                            }
+                    Some(Action::Last) => app.select_last(),
+                    Some(Action::PageDown) => app.page_down(app.script_page_size()),
+                    Some(Action::PageUp) => app.page_up(app.script_page_size()),
+                    Some(Action::Collapse) if in_list => app.handle_left(),
+                    Some(Action::Expand) if in_list => app.handle_right(),
+                    Some(Action::Activate) => {
+                        activate_selected(
+                            terminal,
+                            app,
+                            script_files,
+                            usage_tracker.clone(),
+                            deferred_warnings,
+                        )?;
+                    }
+                    // Unbound letters jump to the next category starting with them
+                    None => match key.code {
+                        KeyCode::Char(letter)
+                            if in_list
+                                && letter.is_alphabetic()
+                                && !key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            app.jump_to_category_letter(letter);
+                        }
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        // Handle mouse events in the script list: click to select, double-click
+        // to toggle/run, wheel to move the selection
+        if let Event::Mouse(mouse) = event {
+            if !app.is_modal_open() && app.is_in_script_list(mouse.column, mouse.row) {
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        app.clear_mouse_selection();
+                        if app.click_script_list(mouse.column, mouse.row, Instant::now()) {
+                            activate_selected(
+                                terminal,
+                                app,
+                                script_files,
+                                usage_tracker.clone(),
+                                deferred_warnings,
+                            )?;
                         }
+                        continue;
+                    }
+                    MouseEventKind::ScrollDown => {
+                        app.next();
+                        continue;
+                    }
+                    MouseEventKind::ScrollUp => {
+                        app.previous();
+                        continue;
                     }
                     _ => {}
                 }
@@ -1437,3 +1834,502 @@ async fn run_app(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Mock event reader for testing that returns a predetermined sequence of events
+    struct MockEventReader {
+        events: VecDeque<Event>,
+    }
+
+    impl MockEventReader {
+        fn new(events: Vec<Event>) -> Self {
+            Self {
+                events: VecDeque::from(events),
+            }
+        }
+    }
+
+    impl EventReader for MockEventReader {
+        fn read_event(&mut self, _timeout: Duration) -> Result<Option<Event>> {
+            Ok(self.events.pop_front())
+        }
+    }
+
+    /// Helper to create a key event
+    fn key_event(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn test_mock_event_reader() {
+        let events = vec![
+            key_event(KeyCode::Char('a')),
+            key_event(KeyCode::Char('b')),
+            key_event(KeyCode::Enter),
+        ];
+
+        let mut reader = MockEventReader::new(events);
+
+        // Should return events in order
+        assert!(matches!(
+            reader.read_event(Duration::from_millis(10)).unwrap(),
+            Some(Event::Key(KeyEvent {
+                code: KeyCode::Char('a'),
+                ..
+            }))
+        ));
+        assert!(matches!(
+            reader.read_event(Duration::from_millis(10)).unwrap(),
+            Some(Event::Key(KeyEvent {
+                code: KeyCode::Char('b'),
+                ..
+            }))
+        ));
+        assert!(matches!(
+            reader.read_event(Duration::from_millis(10)).unwrap(),
+            Some(Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            }))
+        ));
+
+        // Should return None when no more events
+        assert!(reader
+            .read_event(Duration::from_millis(10))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_crossterm_event_reader_type() {
+        // Just verify that CrosstermEventReader exists and implements the trait
+        let _reader: Box<dyn EventReader> = Box::new(CrosstermEventReader);
+    }
+
+    #[tokio::test]
+    async fn test_run_application_nonexistent_directory() {
+        let args = Args {
+            path: Some(PathBuf::from("/nonexistent/directory/that/does/not/exist")),
+            ..Args::default()
+        };
+
+        let result = run_application(args).await;
+        assert!(result.is_err());
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("Failed to access directory"));
+    }
+
+    #[tokio::test]
+    async fn test_run_application_file_instead_of_directory() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("notadir.txt");
+        fs::write(&file_path, "test content").unwrap();
+
+        let args = Args {
+            path: Some(file_path.clone()),
+            ..Args::default()
+        };
+
+        let result = run_application(args).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_args_parsing_with_path() {
+        // Test that Args can parse path argument
+        let args = Args {
+            path: Some(PathBuf::from("/some/path")),
+            ..Args::default()
+        };
+        assert_eq!(args.path, Some(PathBuf::from("/some/path")));
+    }
+
+    #[test]
+    fn test_args_parsing_without_path() {
+        // Test that Args works without path
+        let args = Args::default();
+        assert_eq!(args.path, None);
+    }
+
+    #[test]
+    fn test_args_profile_discovery_is_hidden() {
+        use clap::CommandFactory;
+
+        let args = Args::try_parse_from(["jarvis", "--profile-discovery"]).unwrap();
+        assert!(args.profile_discovery);
+
+        let help = Args::command().render_long_help().to_string();
+        assert!(!help.contains("profile-discovery"));
+    }
+
+    #[test]
+    fn test_args_parsing_with_file() {
+        // Test that Args can parse file argument
+        let args = Args {
+            file: Some(PathBuf::from("/some/file.sh")),
+            ..Args::default()
+        };
+        assert_eq!(args.file, Some(PathBuf::from("/some/file.sh")));
+    }
+
+    #[tokio::test]
+    async fn test_run_application_with_file_nonexistent() {
+        let args = Args {
+            file: Some(PathBuf::from("/nonexistent/file.sh")),
+            ..Args::default()
+        };
+
+        let result = run_application(args).await;
+        assert!(result.is_err());
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("Failed to access file"));
+    }
+
+    #[tokio::test]
+    async fn test_run_application_with_file_unsupported_type() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let txt_path = temp_dir.path().join("readme.txt");
+        fs::write(&txt_path, "text content").unwrap();
+
+        let args = Args {
+            file: Some(txt_path),
+            ..Args::default()
+        };
+
+        let result = run_application(args).await;
+        assert!(result.is_err());
+        let err_msg = format!("{:?}", result.unwrap_err());
+        // The error chain includes "Failed to parse file" and "Unsupported file type"
+        assert!(
+            err_msg.contains("Unsupported file type") || err_msg.contains("Failed to parse file")
+        );
+    }
+
+    // --- key_event_to_bytes tests ---
+
+    #[test]
+    fn test_key_event_to_bytes_regular_char() {
+        let ke = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&ke), vec![b'a']);
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_uppercase_char() {
+        let ke = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert_eq!(key_event_to_bytes(&ke), vec![b'A']);
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_ctrl_c() {
+        let ke = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(key_event_to_bytes(&ke), vec![0x03]); // ETX
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_ctrl_a() {
+        let ke = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(key_event_to_bytes(&ke), vec![0x01]); // SOH
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_ctrl_z() {
+        let ke = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(key_event_to_bytes(&ke), vec![0x1A]); // SUB
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_enter() {
+        let ke = KeyEvent::new(KeyCode::Enter, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&ke), vec![b'\r']);
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_backspace() {
+        let ke = KeyEvent::new(KeyCode::Backspace, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&ke), vec![0x7f]);
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_escape() {
+        let ke = KeyEvent::new(KeyCode::Esc, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&ke), vec![0x1b]);
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_tab() {
+        let ke = KeyEvent::new(KeyCode::Tab, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&ke), vec![b'\t']);
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_arrow_keys() {
+        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&up), b"\x1b[A".to_vec());
+
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&down), b"\x1b[B".to_vec());
+
+        let right = KeyEvent::new(KeyCode::Right, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&right), b"\x1b[C".to_vec());
+
+        let left = KeyEvent::new(KeyCode::Left, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&left), b"\x1b[D".to_vec());
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_function_keys() {
+        let f1 = KeyEvent::new(KeyCode::F(1), KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&f1), b"\x1bOP".to_vec());
+
+        let f5 = KeyEvent::new(KeyCode::F(5), KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&f5), b"\x1b[15~".to_vec());
+
+        let f12 = KeyEvent::new(KeyCode::F(12), KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&f12), b"\x1b[24~".to_vec());
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_special_keys() {
+        let home = KeyEvent::new(KeyCode::Home, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&home), b"\x1b[H".to_vec());
+
+        let end = KeyEvent::new(KeyCode::End, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&end), b"\x1b[F".to_vec());
+
+        let del = KeyEvent::new(KeyCode::Delete, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&del), b"\x1b[3~".to_vec());
+
+        let insert = KeyEvent::new(KeyCode::Insert, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&insert), b"\x1b[2~".to_vec());
+
+        let pgup = KeyEvent::new(KeyCode::PageUp, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&pgup), b"\x1b[5~".to_vec());
+
+        let pgdn = KeyEvent::new(KeyCode::PageDown, KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&pgdn), b"\x1b[6~".to_vec());
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_utf8_char() {
+        let ke = KeyEvent::new(KeyCode::Char('\u{00e9}'), KeyModifiers::empty()); // 'é'
+        let bytes = key_event_to_bytes(&ke);
+        assert_eq!(std::str::from_utf8(&bytes).unwrap(), "\u{00e9}");
+    }
+
+    #[test]
+    fn test_key_event_to_bytes_unknown_returns_empty() {
+        let ke = KeyEvent::new(KeyCode::F(20), KeyModifiers::empty());
+        assert_eq!(key_event_to_bytes(&ke), Vec::<u8>::new());
+    }
+
+    fn make_target(name: &str, script_type: script::ScriptType) -> script::ScriptFunction {
+        script::ScriptFunction {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            category: "test".to_string(),
+            description: String::new(),
+            emoji: None,
+            ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
+            script_type,
+        }
+    }
+
+    fn sample_targets() -> Vec<script::ScriptFunction> {
+        vec![
+            make_target("build", script::ScriptType::Makefile),
+            make_target("build", script::ScriptType::PackageJson),
+            make_target("deploy", script::ScriptType::Bash),
+            make_target("my-app:test", script::ScriptType::NxJson),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_target_unique_name() {
+        let functions = sample_targets();
+        let func = resolve_target(&functions, "deploy").unwrap();
+        assert_eq!(func.script_type, script::ScriptType::Bash);
+    }
+
+    #[test]
+    fn test_resolve_target_history_key() {
+        let functions = sample_targets();
+        let func = resolve_target(&functions, "PackageJson:build").unwrap();
+        assert_eq!(func.script_type, script::ScriptType::PackageJson);
+
+        let func = resolve_target(&functions, "makefile:build").unwrap();
+        assert_eq!(func.script_type, script::ScriptType::Makefile);
+    }
+
+    #[test]
+    fn test_resolve_target_type_prefix_qualifier() {
+        let functions = sample_targets();
+        let func = resolve_target(&functions, "nx:my-app:test").unwrap();
+        assert_eq!(func.name, "my-app:test");
+
+        // A name containing ':' still matches directly
+        let func = resolve_target(&functions, "my-app:test").unwrap();
+        assert_eq!(func.script_type, script::ScriptType::NxJson);
+    }
+
+    #[test]
+    fn test_resolve_target_ambiguous_name() {
+        let functions = sample_targets();
+        let err = resolve_target(&functions, "build").unwrap_err().to_string();
+        assert!(err.contains("ambiguous"));
+        assert!(err.contains("Makefile:build"));
+        assert!(err.contains("PackageJson:build"));
+        assert!(!err.contains("Bash:deploy"));
+    }
+
+    #[test]
+    fn test_resolve_target_not_found_lists_targets() {
+        let functions = sample_targets();
+        let err = resolve_target(&functions, "missing")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("No command matches 'missing'"));
+        assert!(err.contains("Bash:deploy"));
+        assert!(err.contains("NxJson:my-app:test"));
+    }
+
+    #[test]
+    fn test_bash_completions() {
+        let args = Args::try_parse_from(["jarvis", "--completions", "bash"]).unwrap();
+        assert_eq!(args.completions, Some(Shell::Bash));
+
+        let mut out = Vec::new();
+        write_completions(Shell::Bash, &mut out);
+        let script = String::from_utf8(out).unwrap();
+
+        assert!(script.contains("jarvis"));
+        for flag in ["--path", "--file", "--list", "--run"] {
+            assert!(script.contains(flag), "missing {}", flag);
+        }
+    }
+
+    #[test]
+    fn test_args_parsing_run() {
+        let args = Args::try_parse_from(["jarvis", "--run", "nx:my-app:test"]).unwrap();
+        assert_eq!(args.run.as_deref(), Some("nx:my-app:test"));
+    }
+
+    #[test]
+    fn test_format_function_list_json() {
+        let mut functions = sample_targets();
+        functions[2].description = "Deploy the app".to_string();
+
+        let json = format_function_list(&functions, ListFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let items = value.as_array().unwrap();
+
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[2]["name"], "deploy");
+        assert_eq!(items[2]["category"], "test");
+        assert_eq!(items[2]["display_name"], "deploy");
+        assert_eq!(items[2]["description"], "Deploy the app");
+        assert_eq!(items[2]["script_type"], "Bash");
+        assert_eq!(items[3]["script_type"], "NxJson");
+    }
+
+    #[test]
+    fn test_format_function_list_text() {
+        let mut functions = sample_targets();
+        functions[2].description = "Deploy\nthe\tapp".to_string();
+
+        let text = format_function_list(&functions[2..3], ListFormat::Text).unwrap();
+        assert_eq!(text, "test\tdeploy\tdeploy\tDeploy the app");
+    }
+
+    #[test]
+    fn test_args_parsing_exclude() {
+        let args =
+            Args::try_parse_from(["jarvis", "--exclude", "vendor", "--exclude", "gen/**"]).unwrap();
+        assert_eq!(args.exclude, vec!["vendor", "gen/**"]);
+
+        assert!(Args::try_parse_from(["jarvis"]).unwrap().exclude.is_empty());
+        assert!(Args::try_parse_from(["jarvis", "--exclude", "x", "--file", "x.sh"]).is_err());
+    }
+
+    #[test]
+    fn test_args_parsing_depth() {
+        let args = Args::try_parse_from(["jarvis", "--depth", "3"]).unwrap();
+        assert_eq!(args.depth, Some(3));
+
+        assert_eq!(Args::try_parse_from(["jarvis"]).unwrap().depth, None);
+        assert!(Args::try_parse_from(["jarvis", "--depth", "-1"]).is_err());
+        assert!(Args::try_parse_from(["jarvis", "--depth", "1", "--file", "x.sh"]).is_err());
+    }
+
+    #[test]
+    fn test_colors_enabled() {
+        assert!(colors_enabled(false, None));
+        assert!(!colors_enabled(true, None));
+        assert!(!colors_enabled(false, Some("1".into())));
+        // An empty NO_COLOR does not count
+        assert!(colors_enabled(false, Some(String::new().into())));
+    }
+
+    #[test]
+    fn test_project_title_precedence() {
+        let dir = std::path::Path::new("/work/billing_api");
+        assert_eq!(project_title(Some("Flag"), Some("Config"), dir), "Flag");
+        assert_eq!(project_title(None, Some("Config"), dir), "Config");
+        assert_eq!(project_title(None, None, dir), "Billing Api");
+        // Blank names fall through to the next source
+        assert_eq!(project_title(Some("  "), Some("Config"), dir), "Config");
+        assert_eq!(project_title(None, Some(""), dir), "Billing Api");
+    }
+
+    #[test]
+    fn test_resolve_cwd() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("notadir.txt");
+        std::fs::write(&file_path, "test content").unwrap();
+
+        assert_eq!(
+            resolve_cwd(temp_dir.path()).unwrap(),
+            temp_dir.path().canonicalize().unwrap()
+        );
+        let err = resolve_cwd(&file_path).unwrap_err().to_string();
+        assert!(err.contains("--cwd is not a directory"));
+        let err = resolve_cwd(&temp_dir.path().join("missing"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Failed to access --cwd directory"));
+    }
+
+    #[tokio::test]
+    async fn test_run_application_rejects_missing_cwd() {
+        let args = Args::try_parse_from(["jarvis", "--cwd", "/nonexistent/cwd/dir"]).unwrap();
+        let result = run_application(args).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to access --cwd directory"));
+    }
+
+    #[test]
+    fn test_args_parsing_list_format() {
+        let args = Args::try_parse_from(["jarvis", "--list", "--format", "json"]).unwrap();
+        assert!(args.list);
+        assert_eq!(args.format, ListFormat::Json);
+
+        let args = Args::try_parse_from(["jarvis", "--list"]).unwrap();
+        assert_eq!(args.format, ListFormat::Text);
+    }
+}
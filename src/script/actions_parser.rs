@@ -0,0 +1,132 @@
+//! # GitHub Actions Job Parser
+//!
+//! This module lists the individual jobs of GitHub Actions workflows so they
+//! can be run locally one at a time with [`act`](https://github.com/nektos/act).
+//!
+//! ## Overview
+//!
+//! Each workflow file in `.github/workflows/` becomes its own category, named
+//! after the file, containing one [`ActionsJob`] per key of its `jobs:`
+//! section:
+//!
+//! ```yaml
+//! name: CI
+//! on: push
+//! jobs:
+//!   lint:
+//!     runs-on: ubuntu-latest
+//!   test:
+//!     name: Unit tests
+//!     runs-on: ubuntu-latest
+//! ```
+//!
+//! A job's `name:` (when set) is used as its description. Jobs are read with
+//! [`extract_jobs`], the same indentation scan that lists the jobs of a whole
+//! workflow, rather than a YAML parser.
+//!
+//! ## Execution
+//!
+//! Jobs run as `act -j <job> -W .github/workflows/<file>` from the repository
+//! root, with the workflow's trigger event when it has one. Job discovery is
+//! skipped entirely when `act` is not installed; whole workflows are still
+//! listed under "GitHub Actions".
+//!
+//! ## Key Types
+//!
+//! - [`ActionsJob`] - A single workflow job with display metadata
+//! - [`list_jobs`] - Main entry point to list the jobs of a workflow file
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::script::discovery::format_display_name;
+use crate::script::github_actions_parser::extract_jobs;
+
+/// Workflow job item for TUI display
+#[derive(Debug, Clone)]
+pub struct ActionsJob {
+    /// The job id (the key under `jobs:`), passed to `act -j`
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Read a workflow file and list its jobs.
+pub fn list_jobs(workflow_path: &Path, category: &str) -> Result<Vec<ActionsJob>> {
+    let content = std::fs::read_to_string(workflow_path)
+        .with_context(|| format!("Failed to read workflow file: {}", workflow_path.display()))?;
+
+    let file_name = workflow_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+
+    Ok(extract_jobs(&content)
+        .into_iter()
+        .map(|(id, name)| ActionsJob {
+            display_name: format_display_name(&id),
+            category: category.to_string(),
+            description: name.unwrap_or_else(|| format!("Job '{}' of {}", id, file_name)),
+            name: id,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = "\
+name: CI
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+env:
+  name: not-a-job
+
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - name: Run linter
+        run: make lint
+
+  # Unit and integration tests
+  test:
+    name: \"Unit tests\"
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        name: [a, b]
+  build-release:
+    needs: [lint, test]
+    name: Release build
+    runs-on: ubuntu-latest
+";
+
+    #[test]
+    fn test_list_jobs_reads_workflow() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("ci.yml");
+        fs::write(&path, SAMPLE).unwrap();
+
+        let jobs = list_jobs(&path, "ci.yml").unwrap();
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[0].name, "lint");
+        assert_eq!(jobs[0].description, "Job 'lint' of ci.yml");
+        assert_eq!(jobs[1].description, "Unit tests");
+        assert_eq!(jobs[2].display_name, "Build Release");
+        assert_eq!(jobs[2].category, "ci.yml");
+    }
+
+    #[test]
+    fn test_list_jobs_missing_file() {
+        assert!(list_jobs(Path::new("/nonexistent/ci.yml"), "ci.yml").is_err());
+    }
+}
@@ -0,0 +1,363 @@
+//! # Ansible Playbook Parser
+//!
+//! This module lists the Ansible playbooks of a directory for display in the
+//! Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Ansible repositories hold many YAML files (inventories, variables, role
+//! tasks), and only some of them are playbooks. A playbook is a top-level
+//! list of plays, each targeting `hosts:`:
+//!
+//! ```yaml
+//! - name: Configure web servers
+//!   hosts: web
+//!   roles:
+//!     - nginx
+//!
+//! - import_playbook: db.yml
+//! ```
+//!
+//! Role task files are lists too, but of tasks without `hosts:`, and
+//! variable files are mappings, so [`is_playbook`] tells them apart by
+//! looking for a `hosts:` (or `import_playbook:`) key in a top-level list
+//! item. Like [`actions_parser`](crate::script::actions_parser), files are
+//! read with a line-based indentation scan rather than a YAML parser.
+//!
+//! Each directory with playbooks becomes one category, listing one
+//! [`AnsiblePlay`] per playbook file. Playbooks in a `playbooks/` directory
+//! at the project root are picked up too.
+//!
+//! ## Execution
+//!
+//! Playbooks run as `ansible-playbook <file>` from their directory. Discovery
+//! is skipped entirely when `ansible-playbook` is not installed.
+//!
+//! ## Key Types
+//!
+//! - [`AnsiblePlay`] - A single playbook with display metadata
+//! - [`is_ansible_available`] - Checks if `ansible-playbook` is installed
+//! - [`is_playbook`] - The play-detection heuristic
+//! - [`play_names`] - The `name:` of each play of a playbook
+//! - [`playbook_dir`] - Directory a playbook runs from
+//! - [`list_playbooks`] - Main entry point to list the playbooks of a directory
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for ansible-playbook availability check (checked once per process)
+static ANSIBLE_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Directory (relative to the project root) conventionally holding playbooks
+pub const PLAYBOOKS_DIR: &str = "playbooks";
+
+/// Keys that only appear at the top level of a play
+const PLAY_KEYS: &[&str] = &[
+    "hosts",
+    "import_playbook",
+    "ansible.builtin.import_playbook",
+];
+
+/// Ansible playbook item for TUI display
+#[derive(Debug, Clone)]
+pub struct AnsiblePlay {
+    /// The playbook's file name, passed to `ansible-playbook`
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if `ansible-playbook` is available.
+pub fn is_ansible_available() -> bool {
+    *ANSIBLE_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("ansible-playbook"))
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Whether a file name has a YAML extension
+pub fn is_yaml_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext == "yml" || ext == "yaml")
+}
+
+/// The key of a `key: value` line, if it has one
+fn key_of(line: &str) -> Option<&str> {
+    line.split_once(':')
+        .map(|(key, _)| key.trim().trim_matches('"').trim_matches('\''))
+}
+
+/// Scan the top-level list items of YAML content, calling `visit` with each
+/// first-level key line of every item (e.g. `hosts: web`).
+///
+/// Returns `false` as soon as the document turns out not to be a top-level
+/// list (a mapping or scalar at column 0).
+fn scan_items(content: &str, mut visit: impl FnMut(usize, &str)) -> bool {
+    let mut item = 0;
+    let mut key_indent: Option<usize> = None;
+    let mut seen_item = false;
+
+    for line in content.lines() {
+        let text = line.trim_start();
+        if text.is_empty() || text.starts_with('#') || text.starts_with("---") {
+            continue;
+        }
+        let indent = line.len() - text.len();
+
+        if indent == 0 {
+            let Some(rest) = text.strip_prefix('-') else {
+                return false;
+            };
+            let first_key = rest.trim_start();
+            if seen_item {
+                item += 1;
+            }
+            seen_item = true;
+            if first_key.is_empty() {
+                // `-` alone: the item's keys follow on the next lines
+                key_indent = None;
+            } else {
+                key_indent = Some(text.len() - first_key.len());
+                visit(item, first_key);
+            }
+            continue;
+        }
+
+        if !seen_item {
+            continue;
+        }
+        if *key_indent.get_or_insert(indent) == indent {
+            visit(item, text);
+        }
+    }
+
+    seen_item
+}
+
+/// Whether YAML content is an Ansible playbook: a top-level list with at
+/// least one play, recognized by its `hosts:` (or `import_playbook:`) key.
+pub fn is_playbook(content: &str) -> bool {
+    let mut found = false;
+    let is_list = scan_items(content, |_, line| {
+        found |= key_of(line).is_some_and(|key| PLAY_KEYS.contains(&key));
+    });
+    is_list && found
+}
+
+/// The `name:` of each named play of a playbook, in file order
+pub fn play_names(content: &str) -> Vec<String> {
+    let mut names: Vec<(usize, String)> = Vec::new();
+    scan_items(content, |item, line| {
+        if key_of(line) != Some("name") {
+            return;
+        }
+        let name = line
+            .split_once(':')
+            .map(|(_, value)| value.trim().trim_matches('"').trim_matches('\''))
+            .unwrap_or_default();
+        if !name.is_empty() && names.last().is_none_or(|(last, _)| *last != item) {
+            names.push((item, name.to_string()));
+        }
+    });
+    names.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Whether a file is a playbook, reading it from disk
+pub fn is_playbook_file(path: &Path) -> bool {
+    is_yaml_file(path) && std::fs::read_to_string(path).is_ok_and(|content| is_playbook(&content))
+}
+
+/// The playbook files directly inside `dir`, sorted by name
+pub fn playbook_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_file() && is_playbook_file(path))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Directory the playbooks of a script file path run from: the path itself
+/// for a directory of playbooks, the file's directory for a single playbook
+/// (`--file site.yml`)
+pub fn playbook_dir(path: &Path) -> &Path {
+    if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    }
+}
+
+/// List the playbooks of a directory, or the single playbook at `path`.
+pub fn list_playbooks(path: &Path, category: &str) -> Result<Vec<AnsiblePlay>> {
+    let files = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else if path.is_dir() {
+        playbook_files(path)
+    } else {
+        anyhow::bail!("Ansible directory not found: {}", path.display());
+    };
+
+    files
+        .into_iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read playbook: {}", path.display()))?;
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .context("Invalid playbook filename")?
+                .to_string();
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or(&file_name);
+
+            let names = play_names(&content);
+            let description = if names.is_empty() {
+                format!("ansible-playbook {}", file_name)
+            } else {
+                names.join("\n")
+            };
+
+            Ok(AnsiblePlay {
+                display_name: format_display_name(stem),
+                category: category.to_string(),
+                description,
+                name: file_name,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const PLAYBOOK: &str = "\
+---
+# Site playbook
+- name: Configure web servers
+  hosts: web
+  become: true
+  roles:
+    - nginx
+
+- hosts: db
+  tasks:
+    - name: Install postgres
+      ansible.builtin.package:
+        name: postgresql
+";
+
+    const ROLE_TASKS: &str = "\
+---
+- name: Install nginx
+  ansible.builtin.package:
+    name: nginx
+    state: present
+
+- name: Start nginx
+  ansible.builtin.service:
+    name: nginx
+    state: started
+";
+
+    const VARS: &str = "\
+---
+hosts: web
+nginx_port: 8080
+";
+
+    #[test]
+    fn test_is_playbook_detects_plays() {
+        assert!(is_playbook(PLAYBOOK));
+        assert!(is_playbook(
+            "- import_playbook: web.yml\n- import_playbook: db.yml\n"
+        ));
+        assert!(is_playbook("-\n  hosts: all\n  tasks: []\n"));
+    }
+
+    #[test]
+    fn test_is_playbook_rejects_role_and_vars_files() {
+        assert!(!is_playbook(ROLE_TASKS));
+        assert!(!is_playbook(VARS));
+        assert!(!is_playbook(""));
+        // `hosts:` nested inside a task's module arguments is not a play key
+        assert!(!is_playbook(
+            "- name: Add host\n  ansible.builtin.add_host:\n    hosts: web\n"
+        ));
+    }
+
+    #[test]
+    fn test_is_playbook_rejects_other_yaml() {
+        let workflow = "name: CI\non: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n";
+        let compose = "services:\n  web:\n    image: nginx\n";
+        assert!(!is_playbook(workflow));
+        assert!(!is_playbook(compose));
+    }
+
+    #[test]
+    fn test_play_names() {
+        assert_eq!(play_names(PLAYBOOK), vec!["Configure web servers"]);
+        assert_eq!(
+            play_names("- hosts: web\n  name: \"Web\"\n- name: Db\n  hosts: db\n"),
+            vec!["Web", "Db"]
+        );
+    }
+
+    #[test]
+    fn test_list_playbooks_skips_non_playbooks() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("site.yml"), PLAYBOOK).unwrap();
+        fs::write(temp_dir.path().join("deploy-app.yaml"), "- hosts: app\n").unwrap();
+        fs::write(temp_dir.path().join("tasks.yml"), ROLE_TASKS).unwrap();
+        fs::write(temp_dir.path().join("vars.yml"), VARS).unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "- hosts: all\n").unwrap();
+
+        let plays = list_playbooks(temp_dir.path(), "infra").unwrap();
+        let names: Vec<&str> = plays.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["deploy-app.yaml", "site.yml"]);
+        assert_eq!(plays[0].display_name, "Deploy App");
+        assert_eq!(plays[0].description, "ansible-playbook deploy-app.yaml");
+        assert_eq!(plays[1].description, "Configure web servers");
+        assert_eq!(plays[1].category, "infra");
+    }
+
+    #[test]
+    fn test_list_playbooks_single_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("site.yml");
+        fs::write(&path, PLAYBOOK).unwrap();
+        fs::write(temp_dir.path().join("other.yml"), "- hosts: all\n").unwrap();
+
+        let plays = list_playbooks(&path, "infra").unwrap();
+        assert_eq!(plays.len(), 1);
+        assert_eq!(plays[0].name, "site.yml");
+        assert_eq!(playbook_dir(&path), temp_dir.path());
+        assert_eq!(playbook_dir(temp_dir.path()), temp_dir.path());
+    }
+
+    #[test]
+    fn test_list_playbooks_missing_dir() {
+        assert!(list_playbooks(Path::new("/nonexistent/ansible"), "infra").is_err());
+    }
+}
@@ -50,6 +50,7 @@ use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
 
 /// Cache for resolved bazel/bazelisk binary name (checked once per process).
@@ -88,7 +89,7 @@ pub fn is_bazel_available() -> bool {
 /// Prefers `bazelisk` over `bazel`. Returns `None` if neither is installed.
 pub fn get_bazel_command() -> Option<&'static str> {
     *BAZEL_BINARY.get_or_init(|| {
-        if Command::new("bazelisk")
+        if Command::new(binaries::program("bazelisk"))
             .arg("version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -99,7 +100,7 @@ pub fn get_bazel_command() -> Option<&'static str> {
             return Some("bazelisk");
         }
 
-        if Command::new("bazel")
+        if Command::new(binaries::program("bazel"))
             .arg("version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -233,7 +234,7 @@ fn parse_label(label: &str) -> Result<(String, String)> {
 pub fn list_targets(workspace_dir: &Path, category: &str) -> Result<Vec<BazelTarget>> {
     let bazel_cmd = get_bazel_command().context("Bazel or Bazelisk not found")?;
 
-    let output = Command::new(bazel_cmd)
+    let output = Command::new(binaries::program(bazel_cmd))
         .arg("query")
         .arg("kind(\".*_(binary|test)\", //...)")
         .arg("--output=label_kind")
@@ -0,0 +1,116 @@
+//! # Binary Overrides
+//!
+//! Resolves the program used for each external tool (`task`, `make`, `just`,
+//! `cargo`, `terraform`, ...), honoring per-project overrides from the
+//! `[binaries]` table in `jarvis.toml`:
+//!
+//! ```toml
+//! [binaries]
+//! task = "/opt/tools/bin/task"
+//! make = "gmake"
+//! ```
+//!
+//! ## Overview
+//!
+//! Overrides are installed once at startup with [`set_overrides`], before any
+//! availability check runs, and are consulted by every parser's
+//! `Command::new` call (via [`program`]) as well as by the command runner.
+//! Tools without an override keep their usual name and are looked up on `PATH`.
+//!
+//! Tools without a parser of their own (package managers, `deno`, `composer`,
+//! `poetry`, `python`) are checked with [`is_installed`].
+
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+
+/// Program overrides keyed by the tool's default binary name
+static OVERRIDES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Install the binary overrides for this process.
+///
+/// Must be called before discovery starts; availability checks are cached, so
+/// overrides installed later would not affect them. Returns `false` if
+/// overrides were already installed.
+pub fn set_overrides(overrides: HashMap<String, String>) -> bool {
+    OVERRIDES.set(overrides).is_ok()
+}
+
+/// The installed overrides (empty when none were configured).
+pub fn overrides() -> &'static HashMap<String, String> {
+    static EMPTY: OnceLock<HashMap<String, String>> = OnceLock::new();
+    OVERRIDES
+        .get()
+        .unwrap_or_else(|| EMPTY.get_or_init(HashMap::new))
+}
+
+/// Resolve the program to run for a tool, using the installed overrides.
+pub fn program(name: &str) -> String {
+    resolve(name, overrides())
+}
+
+/// Check whether a tool runs (`<program> --version` succeeds), caching the
+/// answer per tool for the rest of the process.
+pub fn is_installed(name: &str) -> bool {
+    static INSTALLED: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
+    let cache = INSTALLED.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(&installed) = cache.lock().ok().as_ref().and_then(|c| c.get(name)) {
+        return installed;
+    }
+
+    let installed = Command::new(program(name))
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(name.to_string(), installed);
+    }
+    installed
+}
+
+/// Resolve the program to run for a tool against an explicit override map.
+pub fn resolve(name: &str, overrides: &HashMap<String, String>) -> String {
+    overrides
+        .get(name)
+        .filter(|path| !path.trim().is_empty())
+        .cloned()
+        .unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_without_override() {
+        assert_eq!(resolve("task", &HashMap::new()), "task");
+    }
+
+    #[test]
+    fn test_resolve_with_override() {
+        let mut overrides = HashMap::new();
+        overrides.insert("task".to_string(), "/opt/tools/bin/task".to_string());
+
+        assert_eq!(resolve("task", &overrides), "/opt/tools/bin/task");
+        assert_eq!(resolve("make", &overrides), "make");
+    }
+
+    #[test]
+    fn test_is_installed() {
+        assert!(!is_installed("jarvis-test-no-such-tool"));
+        // Cached: the second answer is the same
+        assert!(!is_installed("jarvis-test-no-such-tool"));
+    }
+
+    #[test]
+    fn test_resolve_ignores_empty_override() {
+        let mut overrides = HashMap::new();
+        overrides.insert("just".to_string(), "  ".to_string());
+
+        assert_eq!(resolve("just", &overrides), "just");
+    }
+}
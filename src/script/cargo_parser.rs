@@ -44,6 +44,7 @@ use std::sync::OnceLock;
 use anyhow::{Context, Result};
 use serde_json::Value;
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
 
 /// Cache for cargo availability check (checked once per process)
@@ -73,7 +74,7 @@ pub struct CargoTarget {
 /// Check if the `cargo` binary is available.
 pub fn is_cargo_available() -> bool {
     *CARGO_AVAILABLE.get_or_init(|| {
-        Command::new("cargo")
+        Command::new(binaries::program("cargo"))
             .arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -172,7 +173,7 @@ impl CargoTargetType {
 ///
 /// Extracts binary and example targets from the Cargo.toml manifest.
 pub fn list_targets(manifest_path: &Path, category: &str) -> Result<Vec<CargoTarget>> {
-    let output = Command::new("cargo")
+    let output = Command::new(binaries::program("cargo"))
         .arg("metadata")
         .arg("--format-version")
         .arg("1")
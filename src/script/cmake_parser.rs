@@ -0,0 +1,287 @@
+//! # `CMake` Parser
+//!
+//! This module lists the targets of a configured `CMake` project for display in
+//! the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Targets only exist once a project has been configured, so a
+//! `CMakeLists.txt` is picked up when a `build/` directory sits next to it.
+//! Like the rake and mage parsers, this parser asks the CLI rather than
+//! reading `CMakeLists.txt`, since targets are often defined by functions,
+//! loops and subdirectories.
+//!
+//! ## Key Types
+//!
+//! - [`CMakeTarget`] - Represents a build target with display metadata
+//! - [`is_cmake_available`] - Checks if the `cmake` CLI is installed
+//! - [`parse_help_output`] - Parse the output of the `help` target
+//! - [`list_targets`] - Main function to list targets of a project
+//!
+//! ## CLI Integration
+//!
+//! The parser runs (in the `CMakeLists.txt` directory):
+//! ```bash
+//! cmake --build build --target help
+//! ```
+//!
+//! With the Makefile generators the output looks like:
+//!
+//! ```text
+//! The following are some of the valid targets for this Makefile:
+//! ... all (the default if no target is provided)
+//! ... clean
+//! ... edit_cache
+//! ... myapp
+//! ... main.o
+//! ```
+//!
+//! and with Ninja:
+//!
+//! ```text
+//! [1/1] All primary targets available:
+//! myapp: phony
+//! clean: clean all built files
+//! ```
+//!
+//! `CMake`'s own bookkeeping targets (`edit_cache`, `rebuild_cache`, ...) and
+//! per-file targets (`main.o`, `main.i`, `main.s`) are filtered out. Commands
+//! run as `cmake --build build --target <name>`.
+//!
+//! ## Availability Caching
+//!
+//! The `cmake` binary availability is cached using [`OnceLock`] to avoid
+//! repeated process spawning during discovery.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for cmake availability check (checked once per process)
+static CMAKE_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Build directory (relative to `CMakeLists.txt`) targets are listed from
+pub const BUILD_DIR: &str = "build";
+
+/// Targets `CMake` generates for its own maintenance
+const INTERNAL_TARGETS: &[&str] = &[
+    "depend",
+    "edit_cache",
+    "help",
+    "list_install_components",
+    "rebuild_cache",
+];
+
+/// Extensions of the per-source-file targets of the Makefile generators
+const FILE_TARGET_EXTENSIONS: &[&str] = &[".o", ".obj", ".i", ".s"];
+
+/// `CMake` target item for TUI display (mirrors other script types)
+#[derive(Debug, Clone)]
+pub struct CMakeTarget {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if the `cmake` binary is available.
+pub fn is_cmake_available() -> bool {
+    *CMAKE_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("cmake"))
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Whether a target is worth listing: not one of `CMake`'s internal targets,
+/// not a per-file object/preprocess/assembly target and not a path
+fn is_user_target(name: &str) -> bool {
+    !name.is_empty()
+        && !INTERNAL_TARGETS.contains(&name)
+        && !name.contains('/')
+        && !FILE_TARGET_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Parse output of `cmake --build <dir> --target help` into targets.
+///
+/// Handles both the Makefile (`... name`) and Ninja (`name: rule`) formats.
+pub fn parse_help_output(output: &str, category: &str) -> Vec<CMakeTarget> {
+    let mut targets: Vec<CMakeTarget> = Vec::new();
+    let mut in_ninja_list = false;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.contains("All primary targets available") {
+            in_ninja_list = true;
+            continue;
+        }
+
+        let (name, note) = if let Some(rest) = line.strip_prefix("... ") {
+            match rest.split_once(" (") {
+                Some((name, note)) => (name.trim(), Some(note.trim_end_matches(')'))),
+                None => (rest.trim(), None),
+            }
+        } else if in_ninja_list {
+            match line.split_once(':') {
+                Some((name, _)) => (name.trim(), None),
+                None => continue,
+            }
+        } else {
+            continue;
+        };
+
+        if !is_user_target(name) || targets.iter().any(|t| t.name == name) {
+            continue;
+        }
+
+        let description = match note {
+            Some(note) => format!("cmake --build {} --target {} ({})", BUILD_DIR, name, note),
+            None => format!("cmake --build {} --target {}", BUILD_DIR, name),
+        };
+
+        targets.push(CMakeTarget {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: category.to_string(),
+            description,
+        });
+    }
+
+    targets.sort_by(|a, b| a.name.cmp(&b.name));
+    targets
+}
+
+/// Run the `help` target of the project's build directory and parse the result.
+pub fn list_targets(cmakelists_path: &Path, category: &str) -> Result<Vec<CMakeTarget>> {
+    let project_dir = cmakelists_path
+        .parent()
+        .context("Failed to get parent directory of CMakeLists.txt")?;
+
+    let output = Command::new(binaries::program("cmake"))
+        .args(["--build", BUILD_DIR, "--target", "help"])
+        .current_dir(project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run cmake for: {}", cmakelists_path.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "cmake --target help failed for {}: {}",
+            cmakelists_path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_help_output(
+        &String::from_utf8_lossy(&output.stdout),
+        category,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAKEFILE_HELP: &str = "\
+The following are some of the valid targets for this Makefile:
+... all (the default if no target is provided)
+... clean
+... depend
+... edit_cache
+... install
+... install/local
+... install/strip
+... list_install_components
+... rebuild_cache
+... test
+... mylib
+... myapp
+... src/main.o
+... main.o
+... main.i
+... main.s
+";
+
+    const NINJA_HELP: &str = "\
+[1/1] All primary targets available:
+
+edit_cache: phony
+rebuild_cache: phony
+install: phony
+all: phony
+myapp: phony
+mylib: phony
+clean: clean all built files
+help: list targets
+";
+
+    #[test]
+    fn test_parse_help_output_makefile_generator() {
+        let targets = parse_help_output(MAKEFILE_HELP, "app");
+
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["all", "clean", "install", "myapp", "mylib", "test"]
+        );
+        assert_eq!(
+            targets[0].description,
+            "cmake --build build --target all (the default if no target is provided)"
+        );
+        assert_eq!(targets[3].description, "cmake --build build --target myapp");
+        assert_eq!(targets[3].category, "app");
+    }
+
+    #[test]
+    fn test_parse_help_output_ninja_generator() {
+        let targets = parse_help_output(NINJA_HELP, "app");
+
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["all", "clean", "install", "myapp", "mylib"]);
+    }
+
+    #[test]
+    fn test_parse_help_output_filters_internal_targets() {
+        for output in [MAKEFILE_HELP, NINJA_HELP] {
+            let targets = parse_help_output(output, "app");
+            for internal in ["edit_cache", "rebuild_cache", "depend", "help", "main.o"] {
+                assert!(
+                    !targets.iter().any(|t| t.name == internal),
+                    "{internal} should be filtered"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_help_output_ignores_noise() {
+        let output =
+            "gmake: Entering directory '/app/build'\n... myapp\ngmake: Leaving directory\n";
+        let targets = parse_help_output(output, "app");
+
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].name, "myapp");
+    }
+
+    #[test]
+    fn test_list_targets_unconfigured_project_fails() {
+        if !is_cmake_available() {
+            return;
+        }
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("CMakeLists.txt");
+        std::fs::write(&path, "project(app)\n").unwrap();
+
+        assert!(list_targets(&path, "app").is_err());
+    }
+}
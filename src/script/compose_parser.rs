@@ -0,0 +1,260 @@
+//! # Docker Compose Parser
+//!
+//! This module lists the services of a Docker Compose file as runnable
+//! commands for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! When a `compose.yaml` / `docker-compose.yml` (or a `.yml`/`.yaml` variant)
+//! is found and `docker compose` is available, every service under the
+//! top-level `services:` key gets three commands, like the Terraform
+//! per-resource fan-out:
+//!
+//! - `up <service>` — start the service (and its dependencies)
+//! - `logs <service>` — show the service's logs
+//! - `restart <service>` — restart the service's containers
+//!
+//! ```yaml
+//! services:
+//!   web:
+//!     image: nginx
+//!   db:
+//!     image: postgres
+//! ```
+//!
+//! ## Parsing
+//!
+//! Only the service names are needed, so the file is read line by line
+//! instead of with a full YAML parser: service names are the keys indented one
+//! level below `services:`, up to the next top-level key.
+//!
+//! ## Execution
+//!
+//! Commands run as `docker compose <subcommand> <service>` in the compose
+//! file's directory, so `docker compose` also picks up override files
+//! (`compose.override.yaml`) and `.env` the same way it does from a shell.
+//!
+//! ## Key Types
+//!
+//! - [`ComposeService`] - A single service command with display metadata
+//! - [`is_docker_compose_available`] - Checks if `docker compose` is installed
+//! - [`parse_compose_services`] - Extract service names from compose file content
+//! - [`list_services`] - Main entry point to list all service commands
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+use crate::script::utils::{indent_of, unquote};
+
+/// Cache for `docker compose` availability check (checked once per process)
+static DOCKER_COMPOSE_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Per-service subcommands, in display order, with a description verb.
+const SERVICE_COMMANDS: &[(&str, &str)] = &[
+    ("up", "Start the"),
+    ("logs", "Show logs for the"),
+    ("restart", "Restart the"),
+];
+
+/// Docker Compose service command item for TUI display
+#[derive(Debug, Clone)]
+pub struct ComposeService {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+    pub emoji: Option<String>,
+    pub ignored: bool,
+    /// The compose service this command targets
+    pub service: String,
+    /// The `docker compose` subcommand (`up`, `logs` or `restart`)
+    pub subcommand: String,
+}
+
+/// Check if `docker compose` (the Compose v2 plugin) is available.
+pub fn is_docker_compose_available() -> bool {
+    *DOCKER_COMPOSE_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("docker"))
+            .args(["compose", "version"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Extract the service names from the top-level `services:` mapping.
+///
+/// Service names are the keys at the first indentation level below
+/// `services:`; nested keys, comments and blank lines are skipped, and the
+/// mapping ends at the next top-level key. Quoted names are unquoted.
+pub fn parse_compose_services(content: &str) -> Vec<String> {
+    let mut services: Vec<String> = Vec::new();
+    let mut in_services = false;
+    let mut service_indent: Option<usize> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let indent = indent_of(line);
+        if indent == 0 {
+            in_services = trimmed
+                .strip_prefix("services:")
+                .is_some_and(|rest| rest.trim().is_empty() || rest.trim().starts_with('#'));
+            service_indent = None;
+            continue;
+        }
+        if !in_services {
+            continue;
+        }
+
+        let level = *service_indent.get_or_insert(indent);
+        if indent != level {
+            continue;
+        }
+
+        let Some((key, _)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let name = unquote(key);
+        if !name.is_empty() && !services.iter().any(|s| s == name) {
+            services.push(name.to_string());
+        }
+    }
+
+    services
+}
+
+/// Build the `up` / `logs` / `restart` commands for each service.
+pub fn build_service_commands(services: &[String], category: &str) -> Vec<ComposeService> {
+    let mut commands = Vec::new();
+
+    for service in services {
+        for (subcommand, verb) in SERVICE_COMMANDS {
+            commands.push(ComposeService {
+                name: format!("{subcommand} {service}"),
+                display_name: format!("{} {}", format_display_name(subcommand), service),
+                category: category.to_string(),
+                description: format!("{verb} {service} service"),
+                emoji: Some("\u{1f433}".to_string()), // 🐳
+                ignored: false,
+                service: service.clone(),
+                subcommand: (*subcommand).to_string(),
+            });
+        }
+    }
+
+    commands
+}
+
+/// Read a compose file and list the commands for each of its services.
+pub fn list_services(compose_path: &Path, category: &str) -> Result<Vec<ComposeService>> {
+    let content = std::fs::read_to_string(compose_path)
+        .with_context(|| format!("Failed to read {}", compose_path.display()))?;
+
+    Ok(build_service_commands(
+        &parse_compose_services(&content),
+        category,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = "\
+# Local development stack
+name: myapp
+
+services:
+  web:
+    build: .
+    ports:
+      - \"8080:80\"
+    depends_on:
+      - db
+  # The database
+  db:
+    image: postgres:16
+    environment:
+      POSTGRES_PASSWORD: secret
+
+  \"worker\":
+    image: myapp-worker
+
+volumes:
+  data:
+";
+
+    #[test]
+    fn test_parse_compose_services() {
+        assert_eq!(parse_compose_services(SAMPLE), vec!["web", "db", "worker"]);
+    }
+
+    #[test]
+    fn test_parse_compose_services_ignores_other_top_level_keys() {
+        let services = parse_compose_services(SAMPLE);
+        assert!(!services.contains(&"data".to_string()));
+        assert!(!services.contains(&"image".to_string()));
+    }
+
+    #[test]
+    fn test_parse_compose_services_four_space_indent() {
+        let content = "services:\n    api:\n        image: api\n    cache:\n        image: redis\n";
+        assert_eq!(parse_compose_services(content), vec!["api", "cache"]);
+    }
+
+    #[test]
+    fn test_parse_compose_services_without_services() {
+        assert!(parse_compose_services("version: '3'\nvolumes:\n  data:\n").is_empty());
+    }
+
+    #[test]
+    fn test_build_service_commands_fan_out() {
+        let commands = build_service_commands(&["web".to_string(), "db".to_string()], "myapp");
+
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "up web",
+                "logs web",
+                "restart web",
+                "up db",
+                "logs db",
+                "restart db"
+            ]
+        );
+        assert_eq!(commands[0].display_name, "Up web");
+        assert_eq!(commands[0].description, "Start the web service");
+        assert_eq!(commands[0].category, "myapp");
+        assert_eq!(commands[4].subcommand, "logs");
+        assert_eq!(commands[4].service, "db");
+    }
+
+    #[test]
+    fn test_list_services_reads_compose_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("compose.yaml");
+        fs::write(&path, SAMPLE).unwrap();
+
+        let commands = list_services(&path, "myapp").unwrap();
+        assert_eq!(commands.len(), 9);
+        assert!(commands.iter().any(|c| c.name == "restart worker"));
+    }
+
+    #[test]
+    fn test_list_services_missing_file() {
+        assert!(list_services(Path::new("/nonexistent/compose.yaml"), "x").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! # Composer Script Parser
+//!
+//! This module parses `composer.json` files to extract PHP Composer scripts
+//! for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Composer scripts are defined in the `scripts` section of `composer.json`.
+//! A script is either a single command or a list of commands run in order:
+//!
+//! ```json
+//! {
+//!   "scripts": {
+//!     "test": "phpunit",
+//!     "check": ["@lint", "@test"],
+//!     "lint": "php-cs-fixer fix --dry-run"
+//!   }
+//! }
+//! ```
+//!
+//! The parser extracts these scripts and converts them to [`ComposerScript`]
+//! structs for display in the TUI. Scripts run with `composer run-script <name>`.
+//!
+//! ## Key Types
+//!
+//! - [`ComposerJson`] - Deserializes the composer.json structure
+//! - [`ComposerScript`] - Represents a single Composer script with display metadata
+//! - [`parse_composer_json`] - Main parsing function
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::script::discovery::format_display_name;
+
+/// Maximum length of the command preview shown in a script's description
+const MAX_COMMAND_PREVIEW: usize = 60;
+
+/// A script definition: one command or a list of commands
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ComposerCommand {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl ComposerCommand {
+    /// The command(s) as a single line, joining lists with `&&`
+    pub fn to_command_line(&self) -> String {
+        match self {
+            ComposerCommand::Single(command) => command.clone(),
+            ComposerCommand::Multiple(commands) => commands.join(" && "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ComposerJson {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub scripts: HashMap<String, ComposerCommand>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComposerScript {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+    pub command: String,
+}
+
+/// Parse `composer.json` content into Composer scripts
+pub fn parse_composer_scripts(content: &str, category: &str) -> Result<Vec<ComposerScript>> {
+    let composer: ComposerJson =
+        serde_json::from_str(content).context("Failed to parse composer.json")?;
+
+    let mut scripts: Vec<ComposerScript> = composer
+        .scripts
+        .into_iter()
+        .map(|(name, command)| {
+            let command = command.to_command_line();
+            let preview = if command.chars().count() > MAX_COMMAND_PREVIEW {
+                let truncated: String = command.chars().take(MAX_COMMAND_PREVIEW - 3).collect();
+                format!("{}...", truncated)
+            } else {
+                command.clone()
+            };
+
+            ComposerScript {
+                display_name: format_display_name(&name),
+                category: category.to_string(),
+                description: format!("composer run-script {} - {}", name, preview),
+                name,
+                command,
+            }
+        })
+        .collect();
+
+    // Sort scripts alphabetically by name for consistent display
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(scripts)
+}
+
+/// Parse a composer.json file and extract Composer scripts
+pub fn parse_composer_json(path: &Path, category: &str) -> Result<Vec<ComposerScript>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read composer.json: {}", path.display()))?;
+
+    parse_composer_scripts(&content, category)
+        .with_context(|| format!("Failed to parse composer.json: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_string_scripts() {
+        let content = r#"{
+  "name": "acme/app",
+  "scripts": {
+    "test": "phpunit",
+    "lint": "php-cs-fixer fix --dry-run"
+  }
+}"#;
+        let scripts = parse_composer_scripts(content, "app").unwrap();
+
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].name, "lint");
+        assert_eq!(scripts[1].name, "test");
+        assert_eq!(scripts[1].display_name, "Test");
+        assert_eq!(scripts[1].category, "app");
+        assert_eq!(scripts[1].command, "phpunit");
+        assert_eq!(scripts[1].description, "composer run-script test - phpunit");
+    }
+
+    #[test]
+    fn test_parse_array_scripts() {
+        let content = r#"{
+  "scripts": {
+    "check": ["@lint", "@test"],
+    "post-install-cmd": ["php artisan clear-compiled"]
+  }
+}"#;
+        let scripts = parse_composer_scripts(content, "app").unwrap();
+
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].name, "check");
+        assert_eq!(scripts[0].command, "@lint && @test");
+        assert_eq!(
+            scripts[0].description,
+            "composer run-script check - @lint && @test"
+        );
+        assert_eq!(scripts[1].command, "php artisan clear-compiled");
+    }
+
+    #[test]
+    fn test_parse_long_command_is_truncated() {
+        let long = "x".repeat(100);
+        let content = format!(r#"{{ "scripts": {{ "build": "{}" }} }}"#, long);
+        let scripts = parse_composer_scripts(&content, "app").unwrap();
+
+        assert!(scripts[0].description.ends_with("..."));
+        assert_eq!(scripts[0].command, long);
+    }
+
+    #[test]
+    fn test_parse_without_scripts() {
+        let scripts = parse_composer_scripts(r#"{ "name": "acme/lib" }"#, "lib").unwrap();
+        assert!(scripts.is_empty());
+    }
+
+    #[test]
+    fn test_parse_composer_json_invalid() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("composer.json");
+        fs::write(&path, "{ not json").unwrap();
+
+        assert!(parse_composer_json(&path, "app").is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! # Dagger Parser
+//!
+//! This module lists the functions of a [Dagger](https://dagger.io) module
+//! for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! A Dagger module is marked by a `dagger.json`; its functions are written in
+//! any of Dagger's SDK languages, so they are read from the CLI rather than
+//! from source:
+//!
+//! ```bash
+//! dagger functions
+//! ```
+//!
+//! The output is a table of function names and descriptions:
+//!
+//! ```text
+//! Name        Description
+//! build       Build the application container
+//! test        Return the result of running unit tests
+//! build-env   -
+//! ```
+//!
+//! ## Execution
+//!
+//! Functions run as `dagger call <name>` from the module's directory.
+//! Functions that take required arguments report them when called without.
+//!
+//! ## Key Types
+//!
+//! - [`DaggerFunction`] - A module function with display metadata
+//! - [`is_dagger_available`] - Checks if `dagger` is installed
+//! - [`parse_functions_output`] - Parse the output of `dagger functions`
+//! - [`list_functions`] - Main entry point to list the functions of a module
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for dagger availability check (checked once per process)
+static DAGGER_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Dagger function item for TUI display
+#[derive(Debug, Clone)]
+pub struct DaggerFunction {
+    /// The function name passed to `dagger call`
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if the `dagger` binary is available.
+pub fn is_dagger_available() -> bool {
+    *DAGGER_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("dagger"))
+            .arg("version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Parse the output of `dagger functions` into functions, in listed order.
+///
+/// The `Name Description` header and blank lines are skipped. A function
+/// without a description is shown as `-` by Dagger and gets the command that
+/// runs it instead.
+pub fn parse_functions_output(output: &str, category: &str) -> Vec<DaggerFunction> {
+    let mut functions: Vec<DaggerFunction> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let Some(name) = parts.next().filter(|name| !name.is_empty()) else {
+            continue;
+        };
+        let description = parts.next().map(str::trim).unwrap_or_default();
+        if name == "Name" && description == "Description" {
+            continue;
+        }
+        if functions.iter().any(|f| f.name == name) {
+            continue;
+        }
+
+        let description = if description.is_empty() || description == "-" {
+            format!("dagger call {}", name)
+        } else {
+            description.to_string()
+        };
+        functions.push(DaggerFunction {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: category.to_string(),
+            description,
+        });
+    }
+
+    functions
+}
+
+/// Run `dagger functions` for the module at `dagger_json_path` and parse the
+/// result.
+pub fn list_functions(dagger_json_path: &Path, category: &str) -> Result<Vec<DaggerFunction>> {
+    let module_dir = dagger_json_path
+        .parent()
+        .context("Failed to get parent directory of dagger.json")?;
+
+    let output = Command::new(binaries::program("dagger"))
+        .arg("functions")
+        .current_dir(module_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run dagger for: {}", dagger_json_path.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "dagger functions failed for {}: {}",
+            dagger_json_path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_functions_output(
+        &String::from_utf8_lossy(&output.stdout),
+        category,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FUNCTIONS_OUTPUT: &str = "\
+Name          Description
+build         Build the application container
+test          Return the result of running unit tests
+build-env     -
+publish       Publish the application container after building and testing it
+";
+
+    #[test]
+    fn test_parse_functions_output() {
+        let functions = parse_functions_output(FUNCTIONS_OUTPUT, "app");
+
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test", "build-env", "publish"]);
+        assert_eq!(functions[0].display_name, "Build");
+        assert_eq!(functions[0].description, "Build the application container");
+        assert_eq!(functions[2].display_name, "Build Env");
+        assert!(functions.iter().all(|f| f.category == "app"));
+    }
+
+    #[test]
+    fn test_parse_functions_output_without_description() {
+        let functions = parse_functions_output("Name Description\nlint\nbuild-env   -\n", "app");
+        assert_eq!(functions[0].description, "dagger call lint");
+        assert_eq!(functions[1].description, "dagger call build-env");
+    }
+
+    #[test]
+    fn test_parse_functions_output_empty() {
+        assert!(parse_functions_output("", "app").is_empty());
+        assert!(parse_functions_output("Name   Description\n\n", "app").is_empty());
+    }
+}
@@ -0,0 +1,251 @@
+//! # Deno Task Parser
+//!
+//! This module parses `deno.json` / `deno.jsonc` files to extract Deno tasks
+//! for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Deno tasks are defined in the `tasks` object, either as a command string or
+//! (Deno 2) as an object with a `command` and an optional `description`:
+//!
+//! ```jsonc
+//! {
+//!   // Development tasks
+//!   "tasks": {
+//!     "dev": "deno run --watch main.ts",
+//!     "test": { "command": "deno test -A", "description": "Run all tests" }
+//!   }
+//! }
+//! ```
+//!
+//! `deno.jsonc` comments (`//` and `/* */`) and trailing commas are stripped
+//! before parsing.
+//!
+//! ## Key Types
+//!
+//! - [`DenoTask`] - Represents a single Deno task with display metadata
+//! - [`parse_deno_json`] - Main parsing function
+//!
+//! ## Execution
+//!
+//! Tasks are executed with `deno task <name>` in the config file's directory.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::script::discovery::format_display_name;
+
+/// Deno task item for TUI display (mirrors other script types)
+#[derive(Debug, Clone)]
+pub struct DenoTask {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    /// The task's description, or its command when it has none
+    pub description: String,
+    /// The command the task runs
+    pub command: String,
+}
+
+/// Parse a `deno.json` or `deno.jsonc` file into tasks.
+pub fn parse_deno_json(path: &Path, category: &str) -> Result<Vec<DenoTask>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read: {}", path.display()))?;
+    parse_deno_content(&content, category)
+        .with_context(|| format!("Failed to parse Deno config: {}", path.display()))
+}
+
+/// Parse Deno config content (JSON or JSONC) into tasks.
+fn parse_deno_content(content: &str, category: &str) -> Result<Vec<DenoTask>> {
+    let config: Value = serde_json::from_str(&strip_jsonc(content)).context("Invalid JSON")?;
+
+    let Some(tasks) = config.get("tasks").and_then(Value::as_object) else {
+        return Ok(Vec::new());
+    };
+
+    let mut result: Vec<DenoTask> = tasks
+        .iter()
+        .filter_map(|(name, value)| {
+            let (command, description) = match value {
+                Value::String(command) => (command.clone(), None),
+                Value::Object(task) => (
+                    task.get("command")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    task.get("description")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                ),
+                _ => return None,
+            };
+            Some(DenoTask {
+                name: name.clone(),
+                display_name: format_display_name(name),
+                category: category.to_string(),
+                description: description.unwrap_or_else(|| command.clone()),
+                command,
+            })
+        })
+        .collect();
+
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(result)
+}
+
+/// Strip JSONC comments and trailing commas so the content parses as JSON.
+///
+/// String literals are copied verbatim, so `"https://deno.land"` is untouched.
+pub fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                out.push(escaped);
+                            }
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = '\0';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ',' => {
+                // Drop trailing commas: the next significant char closes the container
+                let rest: String = chars.clone().collect();
+                let next = strip_leading_trivia(&rest);
+                if !next.starts_with('}') && !next.starts_with(']') {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Skip whitespace and comments at the start of `s`.
+fn strip_leading_trivia(mut s: &str) -> &str {
+    loop {
+        let trimmed = s.trim_start();
+        if let Some(rest) = trimmed.strip_prefix("//") {
+            s = rest.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if let Some(rest) = trimmed.strip_prefix("/*") {
+            s = rest.split_once("*/").map_or("", |(_, rest)| rest);
+        } else {
+            return trimmed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_plain_json() {
+        let content = r#"{
+  "tasks": {
+    "dev": "deno run --watch main.ts",
+    "test": "deno test -A"
+  }
+}"#;
+        let tasks = parse_deno_content(content, "app").unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].name, "dev");
+        assert_eq!(tasks[0].display_name, "Dev");
+        assert_eq!(tasks[0].category, "app");
+        assert_eq!(tasks[0].description, "deno run --watch main.ts");
+        assert_eq!(tasks[1].command, "deno test -A");
+    }
+
+    #[test]
+    fn test_parse_jsonc_with_comments() {
+        let content = r#"{
+  // Tasks for local development
+  "tasks": {
+    /* start the dev server */
+    "dev": "deno run --allow-net=https://deno.land main.ts",
+    "lint": "deno lint", // trailing comment
+    "test": {
+      "command": "deno test -A",
+      "description": "Run all tests"
+    },
+  },
+}"#;
+        let tasks = parse_deno_content(content, "app").unwrap();
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "lint", "test"]);
+        // URLs inside strings are not treated as comments
+        assert_eq!(
+            tasks[0].command,
+            "deno run --allow-net=https://deno.land main.ts"
+        );
+        assert_eq!(tasks[2].description, "Run all tests");
+        assert_eq!(tasks[2].command, "deno test -A");
+    }
+
+    #[test]
+    fn test_parse_without_tasks() {
+        let tasks = parse_deno_content(r#"{ "imports": {} }"#, "app").unwrap();
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid_json() {
+        assert!(parse_deno_content("{ \"tasks\": ", "app").is_err());
+    }
+
+    #[test]
+    fn test_strip_jsonc_keeps_escaped_quotes() {
+        let content = r#"{ "a": "say \"//hi\"" /* c */ }"#;
+        let value: Value = serde_json::from_str(&strip_jsonc(content)).unwrap();
+        assert_eq!(value["a"], "say \"//hi\"");
+    }
+
+    #[test]
+    fn test_parse_deno_json_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("deno.jsonc");
+        fs::write(
+            &path,
+            "{\n  // comment\n  \"tasks\": { \"start\": \"deno run main.ts\" }\n}\n",
+        )
+        .unwrap();
+
+        let tasks = parse_deno_json(&path, "app").unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].name, "start");
+    }
+}
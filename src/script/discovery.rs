@@ -6,6 +6,7 @@
 //!
 //! - **Bash scripts** (`.sh` files) - Functions are extracted by the parser
 //! - **npm scripts** (`package.json`) - Scripts from the "scripts" section
+//! - **Composer scripts** (`composer.json`) - PHP scripts from the "scripts" section
 //! - **Devbox scripts** (`devbox.json`) - Scripts from the "shell.scripts" section
 //! - **Taskfiles** (`Taskfile.yml`, etc.) - Tasks defined in go-task format
 //! - **Makefiles** (`Makefile`, etc.) - Targets defined in GNU Make format
@@ -14,6 +15,21 @@
 //! - **Bazel** (`WORKSPACE`, `BUILD`, `MODULE.bazel`) — Bazel build targets
 //! - **Mage** (`magefile.go`, `mage.go`) — Mage build targets
 //! - **Gradle** (`build.gradle`, `build.gradle.kts`) — Gradle build tasks
+//! - **Rake** (`Rakefile`, `rakefile`, `Rakefile.rb`) — Ruby Rake tasks
+//! - **sbt** (`build.sbt`) — Scala sbt tasks
+//! - **Maven** (`pom.xml`) — Maven lifecycle phases and profiles
+//! - **Docker Compose** (`compose.yaml`, `docker-compose.yml`, etc.) — Compose services
+//! - **Procfile** (`Procfile`, `Procfile.dev`) — Foreman/Heroku processes
+//! - **mise** (`mise.toml`, `.mise.toml`, `.config/mise/config.toml`) — mise tasks
+//! - **`CMake`** (`CMakeLists.txt` with a `build/` directory) — `CMake` build targets
+//! - **Meson** (`meson.build` with a configured build directory) — Meson build targets
+//! - **Dagger** (`dagger.json`) — Dagger module functions
+//! - **Mix** (`mix.exs`) — Elixir Mix tasks
+//! - **Lefthook** (`lefthook.yml`, `.lefthook.yml`, etc.) — Lefthook git hooks
+//! - **pre-commit** (`.pre-commit-config.yaml`) — pre-commit hooks
+//! - **GitHub Actions** (`.github/workflows/*.yml`) — Workflows, plus their
+//!   individual jobs when `act` is installed
+//! - **Ansible** (`*.yml` playbooks, `playbooks/`) — Ansible playbooks
 //!
 //! ## Discovery Locations
 //!
@@ -36,32 +52,254 @@
 //!
 //! - [`discover_scripts`] - Full recursive discovery with depth 2
 //! - [`discover_scripts_shallow`] - Shallow discovery with depth 1
+//! - [`discover_scripts_excluding`] - Discovery that skips excluded paths
 //! - [`format_display_name`] - Converts `snake_case` to Title Case
 
+use crate::script::binaries;
+use crate::script::exclude::ExcludeSet;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::str::FromStr;
 use std::sync::OnceLock;
 use walkdir::WalkDir;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ScriptType {
+    Ansible,
     Bazel,
     Bash,
     CargoToml,
+    CMake,
+    ComposerJson,
+    Dagger,
+    DenoJson,
     DevboxJson,
+    DockerCompose,
     GithubActions,
+    GithubActionsJob,
     Gradle,
     Just,
+    Lefthook,
     Mage,
     Makefile,
+    Maven,
+    Meson,
+    Mise,
+    Mix,
     NxJson,
     PackageJson,
+    PreCommit,
+    Procfile,
+    PyProject,
+    Rakefile,
+    Sbt,
     Task,
     Terraform,
 }
 
+impl ScriptType {
+    /// Every script type, in declaration order
+    pub const ALL: [ScriptType; 30] = [
+        ScriptType::Ansible,
+        ScriptType::Bazel,
+        ScriptType::Bash,
+        ScriptType::CargoToml,
+        ScriptType::CMake,
+        ScriptType::ComposerJson,
+        ScriptType::Dagger,
+        ScriptType::DenoJson,
+        ScriptType::DevboxJson,
+        ScriptType::DockerCompose,
+        ScriptType::GithubActions,
+        ScriptType::GithubActionsJob,
+        ScriptType::Gradle,
+        ScriptType::Just,
+        ScriptType::Lefthook,
+        ScriptType::Mage,
+        ScriptType::Makefile,
+        ScriptType::Maven,
+        ScriptType::Meson,
+        ScriptType::Mise,
+        ScriptType::Mix,
+        ScriptType::NxJson,
+        ScriptType::PackageJson,
+        ScriptType::PreCommit,
+        ScriptType::Procfile,
+        ScriptType::PyProject,
+        ScriptType::Rakefile,
+        ScriptType::Sbt,
+        ScriptType::Task,
+        ScriptType::Terraform,
+    ];
+
+    /// Name of the variant, as stored in usage files and shown in `--run`
+    /// keys (`NxJson:my-app:test`)
+    pub fn name(self) -> &'static str {
+        match self {
+            ScriptType::Ansible => "Ansible",
+            ScriptType::Bazel => "Bazel",
+            ScriptType::Bash => "Bash",
+            ScriptType::CargoToml => "CargoToml",
+            ScriptType::CMake => "CMake",
+            ScriptType::ComposerJson => "ComposerJson",
+            ScriptType::Dagger => "Dagger",
+            ScriptType::DenoJson => "DenoJson",
+            ScriptType::DevboxJson => "DevboxJson",
+            ScriptType::DockerCompose => "DockerCompose",
+            ScriptType::GithubActions => "GithubActions",
+            ScriptType::GithubActionsJob => "GithubActionsJob",
+            ScriptType::Gradle => "Gradle",
+            ScriptType::Just => "Just",
+            ScriptType::Lefthook => "Lefthook",
+            ScriptType::Mage => "Mage",
+            ScriptType::Makefile => "Makefile",
+            ScriptType::Maven => "Maven",
+            ScriptType::Meson => "Meson",
+            ScriptType::Mise => "Mise",
+            ScriptType::Mix => "Mix",
+            ScriptType::NxJson => "NxJson",
+            ScriptType::PackageJson => "PackageJson",
+            ScriptType::PreCommit => "PreCommit",
+            ScriptType::Procfile => "Procfile",
+            ScriptType::PyProject => "PyProject",
+            ScriptType::Rakefile => "Rakefile",
+            ScriptType::Sbt => "Sbt",
+            ScriptType::Task => "Task",
+            ScriptType::Terraform => "Terraform",
+        }
+    }
+
+    /// Short human-readable name of the tool behind this script type
+    pub fn label(self) -> &'static str {
+        match self {
+            ScriptType::Ansible => "Ansible",
+            ScriptType::Bazel => "Bazel",
+            ScriptType::Bash => "Bash",
+            ScriptType::CargoToml => "Cargo",
+            ScriptType::CMake => "CMake",
+            ScriptType::ComposerJson => "Composer",
+            ScriptType::Dagger => "Dagger",
+            ScriptType::DenoJson => "Deno",
+            ScriptType::DevboxJson => "Devbox",
+            ScriptType::DockerCompose => "Docker Compose",
+            ScriptType::GithubActions => "GitHub Actions",
+            ScriptType::GithubActionsJob => "GitHub Actions jobs",
+            ScriptType::Gradle => "Gradle",
+            ScriptType::Just => "Just",
+            ScriptType::Lefthook => "Lefthook",
+            ScriptType::Mage => "Mage",
+            ScriptType::Makefile => "Make",
+            ScriptType::Maven => "Maven",
+            ScriptType::Meson => "Meson",
+            ScriptType::Mise => "mise",
+            ScriptType::Mix => "Mix",
+            ScriptType::NxJson => "Nx",
+            ScriptType::PackageJson => "npm",
+            ScriptType::PreCommit => "pre-commit",
+            ScriptType::Procfile => "Procfile",
+            ScriptType::PyProject => "Python",
+            ScriptType::Rakefile => "Rake",
+            ScriptType::Sbt => "sbt",
+            ScriptType::Task => "Task",
+            ScriptType::Terraform => "Terraform",
+        }
+    }
+
+    /// Icon shown before category headers whose functions are mostly of this type
+    pub fn icon(self) -> &'static str {
+        match self {
+            ScriptType::Ansible => "🅰️",
+            ScriptType::Bazel => "🌿",
+            ScriptType::Bash => "🧾",
+            ScriptType::CargoToml => "🦀",
+            ScriptType::CMake => "🔺",
+            ScriptType::ComposerJson => "🎼",
+            ScriptType::Dagger => "🗡️",
+            ScriptType::DenoJson => "🦕",
+            ScriptType::DevboxJson => "🧰",
+            ScriptType::DockerCompose => "🐳",
+            ScriptType::GithubActions | ScriptType::GithubActionsJob => "🐙",
+            ScriptType::Gradle => "🐘",
+            ScriptType::Just => "⚡",
+            ScriptType::Lefthook => "🥊",
+            ScriptType::Mage => "🧙",
+            ScriptType::Makefile => "🔨",
+            ScriptType::Maven => "🪶",
+            ScriptType::Meson => "🧱",
+            ScriptType::Mise => "🍳",
+            ScriptType::Mix => "💧",
+            ScriptType::NxJson => "🔷",
+            ScriptType::PackageJson => "📦",
+            ScriptType::PreCommit => "🪝",
+            ScriptType::Procfile => "🏭",
+            ScriptType::PyProject => "🐍",
+            ScriptType::Rakefile => "💎",
+            ScriptType::Sbt => "🪜",
+            ScriptType::Task => "📋",
+            ScriptType::Terraform => "🏗️",
+        }
+    }
+
+    /// The program commands of this type run through, named in "not
+    /// installed" messages (`None` when no particular tool is required, or
+    /// when it depends on the project: see [`ScriptFile::binary`])
+    pub fn binary(self) -> Option<&'static str> {
+        match self {
+            ScriptType::Ansible => Some("ansible-playbook"),
+            ScriptType::Bazel => Some("bazel"),
+            ScriptType::CargoToml => Some("cargo"),
+            ScriptType::CMake => Some("cmake"),
+            ScriptType::ComposerJson => Some("composer"),
+            ScriptType::Dagger => Some("dagger"),
+            ScriptType::DenoJson => Some("deno"),
+            ScriptType::DevboxJson => Some("devbox"),
+            ScriptType::DockerCompose => Some("docker compose"),
+            ScriptType::GithubActionsJob => Some("act"),
+            ScriptType::Gradle => Some("gradle"),
+            ScriptType::Just => Some("just"),
+            ScriptType::Lefthook => Some("lefthook"),
+            ScriptType::Mage => Some("mage"),
+            ScriptType::Makefile => Some("make"),
+            ScriptType::Maven => Some("mvn"),
+            ScriptType::Meson => Some("meson"),
+            ScriptType::Mise => Some("mise"),
+            ScriptType::Mix => Some("mix"),
+            ScriptType::NxJson => Some("nx"),
+            ScriptType::PreCommit => Some("pre-commit"),
+            ScriptType::Rakefile => Some("rake"),
+            ScriptType::Sbt => Some("sbt"),
+            ScriptType::Task => Some("task"),
+            ScriptType::Terraform => Some("terraform"),
+            ScriptType::Bash
+            | ScriptType::GithubActions
+            | ScriptType::PackageJson
+            | ScriptType::Procfile
+            | ScriptType::PyProject => None,
+        }
+    }
+}
+
+impl fmt::Display for ScriptType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ScriptType {
+    type Err = anyhow::Error;
+
+    /// Parse a variant name (`PackageJson`) or a label (`npm`), ignoring case
+    fn from_str(s: &str) -> Result<Self> {
+        ScriptType::ALL
+            .into_iter()
+            .find(|t| t.name().eq_ignore_ascii_case(s) || t.label().eq_ignore_ascii_case(s))
+            .with_context(|| format!("Unknown script type '{}'", s))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ScriptFile {
     pub path: PathBuf,
@@ -70,20 +308,59 @@ pub struct ScriptFile {
     pub category: String,
     pub display_name: String,
     pub script_type: ScriptType,
+    /// Whether the tool that runs this file's commands is installed. Files
+    /// whose targets can be read without it (Makefiles, mise configs) are
+    /// still listed when it is missing, but their commands are not run.
+    pub available: bool,
+}
+
+impl ScriptFile {
+    /// The program this file's commands run through, named in "not
+    /// installed" messages: [`ScriptType::binary`], resolved from the
+    /// project's files for npm-style and Python projects.
+    pub fn binary(&self) -> Option<&'static str> {
+        file_binary(self.script_type, &self.path)
+    }
+}
+
+/// The program commands of a file run through, see [`ScriptFile::binary`]
+fn file_binary(script_type: ScriptType, file_path: &Path) -> Option<&'static str> {
+    let dir = file_path.parent().unwrap_or(Path::new("."));
+    match script_type {
+        ScriptType::PackageJson => {
+            Some(crate::script::npm_parser::detect_package_manager(dir).program())
+        }
+        ScriptType::PyProject => Some(if crate::script::python_parser::uses_poetry(dir) {
+            "poetry"
+        } else {
+            "python"
+        }),
+        _ => script_type.binary(),
+    }
 }
 
-/// Taskfile names to detect (all variants from taskfile.dev)
+/// Taskfile names to detect (all variants from taskfile.dev), in the order
+/// `task` itself prefers them: a local `Taskfile.yml` overrides the
+/// committed `Taskfile.dist.yml`
 const TASKFILE_NAMES: &[&str] = &[
-    "taskfile.dist.yaml",
-    "Taskfile.dist.yaml",
-    "taskfile.dist.yml",
-    "Taskfile.dist.yml",
-    "taskfile.yaml",
-    "Taskfile.yaml",
-    "taskfile.yml",
     "Taskfile.yml",
+    "taskfile.yml",
+    "Taskfile.yaml",
+    "taskfile.yaml",
+    "Taskfile.dist.yml",
+    "taskfile.dist.yml",
+    "Taskfile.dist.yaml",
+    "taskfile.dist.yaml",
 ];
 
+/// Priority of a Taskfile name (lower wins), per [`TASKFILE_NAMES`]
+fn taskfile_priority(path: &Path) -> usize {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|name| TASKFILE_NAMES.iter().position(|n| *n == name))
+        .unwrap_or(TASKFILE_NAMES.len())
+}
+
 /// Makefile names to detect
 const MAKEFILE_NAMES: &[&str] = &["Makefile", "makefile", "GNUmakefile"];
 
@@ -92,12 +369,67 @@ const JUSTFILE_NAMES: &[&str] = &["justfile", "Justfile", ".justfile"];
 /// Magefile names to detect
 const MAGEFILE_NAMES: &[&str] = &["magefile.go", "mage.go"];
 
+/// Rakefile names to detect
+const RAKEFILE_NAMES: &[&str] = &["Rakefile", "rakefile", "Rakefile.rb"];
+
+/// sbt build definition names to detect
+const SBT_NAMES: &[&str] = &["build.sbt"];
+
+/// Maven project file names to detect
+const MAVEN_NAMES: &[&str] = &["pom.xml"];
+
 /// Cargo manifest names to detect
 const CARGO_TOML_NAMES: &[&str] = &["Cargo.toml"];
 
+/// Composer manifest names to detect
+const COMPOSER_JSON_NAMES: &[&str] = &["composer.json"];
+
+/// Deno config names to detect
+const DENO_JSON_NAMES: &[&str] = &["deno.json", "deno.jsonc"];
+
+/// Python project manifest names to detect
+const PYPROJECT_NAMES: &[&str] = &["pyproject.toml"];
+
 /// Nx workspace config names to detect
 const NX_JSON_NAMES: &[&str] = &["nx.json"];
 
+/// Docker Compose file names to detect (in the order `docker compose` prefers them)
+const COMPOSE_NAMES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// `CMake` project file names to detect
+const CMAKE_NAMES: &[&str] = &["CMakeLists.txt"];
+
+/// Meson project file names to detect
+const MESON_NAMES: &[&str] = &["meson.build"];
+
+/// Dagger module config names to detect
+const DAGGER_NAMES: &[&str] = &["dagger.json"];
+
+/// Elixir Mix project file names to detect
+const MIX_NAMES: &[&str] = &["mix.exs"];
+
+/// Lefthook config names to detect
+const LEFTHOOK_NAMES: &[&str] = &[
+    "lefthook.yml",
+    "lefthook.yaml",
+    ".lefthook.yml",
+    ".lefthook.yaml",
+];
+
+/// pre-commit config names to detect
+const PRE_COMMIT_NAMES: &[&str] = &[".pre-commit-config.yaml"];
+
+/// mise config names to detect (besides `.config/mise/config.toml` at the root)
+const MISE_NAMES: &[&str] = &["mise.toml", ".mise.toml"];
+
+/// Procfile names to detect (Foreman/Heroku)
+const PROCFILE_NAMES: &[&str] = &["Procfile", "Procfile.dev"];
+
 /// Gradle build file names to detect
 const GRADLE_BUILD_NAMES: &[&str] = &["build.gradle", "build.gradle.kts"];
 
@@ -119,7 +451,7 @@ static DEVBOX_AVAILABLE: OnceLock<bool> = OnceLock::new();
 /// Check if devbox is installed and available in PATH
 fn is_devbox_available() -> bool {
     *DEVBOX_AVAILABLE.get_or_init(|| {
-        Command::new("devbox")
+        Command::new(binaries::program("devbox"))
             .arg("version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -129,6 +461,64 @@ fn is_devbox_available() -> bool {
     })
 }
 
+/// Whether the tool behind a script type is installed, using the cached
+/// availability check of its parser. Types without a required tool (Procfiles
+/// and workflows fall back to `bash` and `echo`), and types whose tool depends
+/// on the project (see [`is_tool_available_for`]), are always available.
+pub fn is_tool_available(script_type: ScriptType) -> bool {
+    match script_type {
+        ScriptType::Ansible => crate::script::ansible_parser::is_ansible_available(),
+        ScriptType::Bazel => crate::script::bazel_parser::is_bazel_available(),
+        ScriptType::CargoToml => crate::script::cargo_parser::is_cargo_available(),
+        ScriptType::CMake => crate::script::cmake_parser::is_cmake_available(),
+        ScriptType::ComposerJson => binaries::is_installed("composer"),
+        ScriptType::Dagger => crate::script::dagger_parser::is_dagger_available(),
+        ScriptType::DenoJson => binaries::is_installed("deno"),
+        ScriptType::DevboxJson => is_devbox_available(),
+        ScriptType::DockerCompose => crate::script::compose_parser::is_docker_compose_available(),
+        ScriptType::GithubActionsJob => crate::script::github_actions_parser::is_act_available(),
+        ScriptType::Gradle => crate::script::gradle_parser::is_gradle_available(),
+        ScriptType::Just => crate::script::just_parser::is_just_available(),
+        ScriptType::Lefthook => crate::script::lefthook_parser::is_lefthook_available(),
+        ScriptType::Mage => crate::script::mage_parser::is_mage_available(),
+        ScriptType::Makefile => crate::script::makefile_parser::is_make_available(),
+        ScriptType::Maven => crate::script::maven_parser::is_maven_available(),
+        ScriptType::Meson => crate::script::meson_parser::is_meson_available(),
+        ScriptType::Mise => crate::script::mise_parser::is_mise_available(),
+        ScriptType::Mix => crate::script::mix_parser::is_mix_available(),
+        ScriptType::NxJson => crate::script::nx_parser::is_nx_available(),
+        ScriptType::PreCommit => crate::script::precommit_parser::is_pre_commit_available(),
+        ScriptType::Rakefile => crate::script::rake_parser::is_rake_available(),
+        ScriptType::Sbt => crate::script::sbt_parser::is_sbt_available(),
+        ScriptType::Task => crate::script::task_parser::is_task_available(),
+        ScriptType::Terraform => crate::script::terraform_parser::is_terraform_available(),
+        ScriptType::Bash
+        | ScriptType::GithubActions
+        | ScriptType::PackageJson
+        | ScriptType::Procfile
+        | ScriptType::PyProject => true,
+    }
+}
+
+/// Whether the tool behind the script file at `file_path` is installed: like
+/// [`is_tool_available`], but checking the package manager (npm, pnpm, yarn,
+/// bun) or Python runner (poetry, python) the project resolves to, and also
+/// counting a Maven wrapper (`mvnw`) shipped with the project, which runs it
+/// without a system `mvn`.
+pub fn is_tool_available_for(script_type: ScriptType, file_path: &Path) -> bool {
+    match script_type {
+        ScriptType::Maven => {
+            let project_dir = file_path.parent().unwrap_or(Path::new("."));
+            crate::script::maven_parser::maven_wrapper(project_dir).is_some()
+                || crate::script::maven_parser::is_maven_available()
+        }
+        ScriptType::PackageJson | ScriptType::PyProject => {
+            file_binary(script_type, file_path).is_none_or(binaries::is_installed)
+        }
+        _ => is_tool_available(script_type),
+    }
+}
+
 /// Pre-warm all tool availability checks in parallel.
 ///
 /// This spawns threads to check each tool concurrently, so by the time
@@ -140,12 +530,25 @@ pub fn prewarm_tool_checks() {
     std::thread::spawn(crate::script::makefile_parser::is_make_available);
     std::thread::spawn(crate::script::just_parser::is_just_available);
     std::thread::spawn(crate::script::mage_parser::is_mage_available);
+    std::thread::spawn(crate::script::rake_parser::is_rake_available);
     std::thread::spawn(crate::script::cargo_parser::is_cargo_available);
     std::thread::spawn(crate::script::nx_parser::is_nx_available);
     std::thread::spawn(crate::script::terraform_parser::is_terraform_available);
     std::thread::spawn(crate::script::gradle_parser::is_gradle_available);
     std::thread::spawn(crate::script::github_actions_parser::is_gh_available);
     std::thread::spawn(crate::script::github_actions_parser::is_act_available);
+    std::thread::spawn(crate::script::compose_parser::is_docker_compose_available);
+    std::thread::spawn(crate::script::procfile_parser::is_foreman_available);
+    std::thread::spawn(crate::script::cmake_parser::is_cmake_available);
+    std::thread::spawn(crate::script::mise_parser::is_mise_available);
+    std::thread::spawn(crate::script::ansible_parser::is_ansible_available);
+    std::thread::spawn(crate::script::sbt_parser::is_sbt_available);
+    std::thread::spawn(crate::script::maven_parser::is_maven_available);
+    std::thread::spawn(crate::script::meson_parser::is_meson_available);
+    std::thread::spawn(crate::script::dagger_parser::is_dagger_available);
+    std::thread::spawn(crate::script::mix_parser::is_mix_available);
+    std::thread::spawn(crate::script::lefthook_parser::is_lefthook_available);
+    std::thread::spawn(crate::script::precommit_parser::is_pre_commit_available);
 }
 
 /// Formats a filename into a display-friendly name
@@ -184,6 +587,10 @@ pub fn discover_scripts_shallow(scripts_dir: &Path) -> Result<Vec<ScriptFile>> {
     discover_scripts_with_depth(scripts_dir, 1)
 }
 
+/// Directories never descended into: they hold dependencies or VCS data, not
+/// project scripts, and can be huge
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules"];
+
 /// Discover a single script file and return its `ScriptFile` representation.
 ///
 /// This function determines the script type from the file extension/name:
@@ -191,13 +598,26 @@ pub fn discover_scripts_shallow(scripts_dir: &Path) -> Result<Vec<ScriptFile>> {
 /// - `.tf` files → Terraform
 /// - `package.json` → `PackageJson`
 /// - `devbox.json` → `DevboxJson`
+/// - `composer.json` → `ComposerJson`
+/// - `deno.json` / `deno.jsonc` → `DenoJson`
 /// - `Taskfile.yml` (and variants) → Task
 /// - `Makefile` (and variants) → Makefile
 /// - `justfile` (and variants) → Just
 /// - `Cargo.toml` → `CargoToml`
 /// - `nx.json` → `NxJson`
+/// - `pyproject.toml` → `PyProject`
 /// - `build.gradle` / `build.gradle.kts` → Gradle
 /// - `WORKSPACE` / `BUILD` / `MODULE.bazel` → Bazel
+/// - `compose.yaml` / `docker-compose.yml` (and variants) → `DockerCompose`
+/// - `Procfile` / `Procfile.dev` → Procfile
+/// - `CMakeLists.txt` (with a `build/` directory) → `CMake`
+/// - `meson.build` (with a configured build directory) → Meson
+/// - `dagger.json` → Dagger
+/// - `mix.exs` → Mix
+/// - `lefthook.yml` / `.lefthook.yml` (and variants) → Lefthook
+/// - `.pre-commit-config.yaml` → `PreCommit`
+/// - `mise.toml` / `.mise.toml` / `.config/mise/config.toml` → Mise
+/// - `*.yml` / `*.yaml` Ansible playbooks → Ansible
 ///
 /// # Arguments
 ///
@@ -236,16 +656,31 @@ pub fn discover_single_file(file_path: &Path) -> Result<ScriptFile> {
 
     // Get the file stem (name without extension) for category/display name
     let name = match script_type {
-        ScriptType::Bazel
+        ScriptType::Ansible
+        | ScriptType::Bazel
         | ScriptType::GithubActions
+        | ScriptType::GithubActionsJob
         | ScriptType::PackageJson
+        | ScriptType::ComposerJson
+        | ScriptType::DenoJson
         | ScriptType::DevboxJson
+        | ScriptType::DockerCompose
+        | ScriptType::CMake
+        | ScriptType::Meson
+        | ScriptType::Dagger
+        | ScriptType::Mix
+        | ScriptType::Lefthook
+        | ScriptType::PreCommit
         | ScriptType::Task
         | ScriptType::Makefile
         | ScriptType::Just
         | ScriptType::Mage
+        | ScriptType::Rakefile
+        | ScriptType::Sbt
+        | ScriptType::Maven
         | ScriptType::CargoToml
         | ScriptType::NxJson
+        | ScriptType::PyProject
         | ScriptType::Terraform
         | ScriptType::Gradle => {
             // For JSON/YAML config files and Makefile, use the parent directory name or the filename
@@ -259,6 +694,8 @@ pub fn discover_single_file(file_path: &Path) -> Result<ScriptFile> {
                 filename.to_string()
             }
         }
+        ScriptType::Procfile => procfile_name(file_path),
+        ScriptType::Mise => mise_name(file_path),
         ScriptType::Bash => {
             // For .sh files, use the file stem
             file_path
@@ -271,16 +708,32 @@ pub fn discover_single_file(file_path: &Path) -> Result<ScriptFile> {
 
     let category = name.clone();
     let display_name = match script_type {
+        ScriptType::Ansible => format!("🅰️ {}", format_display_name(&name)),
         ScriptType::Bazel => format!("🌿 {}", format_display_name(&name)),
         ScriptType::Task => format!("📋 {}", format_display_name(&name)),
         ScriptType::Makefile => format!("🔨 {}", format_display_name(&name)),
         ScriptType::Just => format!("⚡ {}", format_display_name(&name)),
         ScriptType::Mage => format!("🧙 {}", format_display_name(&name)),
+        ScriptType::Rakefile => format!("💎 {}", format_display_name(&name)),
+        ScriptType::Sbt => format!("🪜 {}", format_display_name(&name)),
+        ScriptType::Maven => format!("🪶 {}", format_display_name(&name)),
         ScriptType::CargoToml => format!("🦀 {}", format_display_name(&name)),
         ScriptType::NxJson => format!("🔷 {}", format_display_name(&name)),
+        ScriptType::PyProject => format!("🐍 {}", format_display_name(&name)),
+        ScriptType::DenoJson => format!("🦕 {}", format_display_name(&name)),
+        ScriptType::ComposerJson => format!("🎼 {}", format_display_name(&name)),
         ScriptType::Terraform => format!("🏗️ {}", format_display_name(&name)),
         ScriptType::Gradle => format!("🐘 {}", format_display_name(&name)),
         ScriptType::GithubActions => format!("🐙 {}", format_display_name(&name)),
+        ScriptType::DockerCompose => format!("🐳 {}", format_display_name(&name)),
+        ScriptType::Procfile => format!("🏭 {}", format_display_name(&name)),
+        ScriptType::CMake => format!("🔺 {}", format_display_name(&name)),
+        ScriptType::Meson => format!("🧱 {}", format_display_name(&name)),
+        ScriptType::Dagger => format!("🗡️ {}", format_display_name(&name)),
+        ScriptType::Mix => format!("💧 {}", format_display_name(&name)),
+        ScriptType::Lefthook => format!("🥊 {}", format_display_name(&name)),
+        ScriptType::PreCommit => format!("🪝 {}", format_display_name(&name)),
+        ScriptType::Mise => format!("🍳 {}", format_display_name(&name)),
         _ => format_display_name(&name),
     };
 
@@ -290,9 +743,57 @@ pub fn discover_single_file(file_path: &Path) -> Result<ScriptFile> {
         category,
         display_name,
         script_type,
+        available: is_tool_available_for(script_type, file_path),
     })
 }
 
+/// Whether a `CMakeLists.txt` has a build directory next to it to list targets from
+fn has_cmake_build_dir(cmakelists_path: &Path) -> bool {
+    cmakelists_path
+        .parent()
+        .is_some_and(|dir| dir.join(crate::script::cmake_parser::BUILD_DIR).is_dir())
+}
+
+/// Whether a `meson.build` has a configured build directory next to it
+fn has_meson_build_dir(meson_build_path: &Path) -> bool {
+    meson_build_path
+        .parent()
+        .and_then(crate::script::meson_parser::find_build_dir)
+        .is_some()
+}
+
+/// Category name for a Procfile: its directory's name, with a `-dev` suffix
+/// for `Procfile.dev` so it does not share a category with a `Procfile` next to it
+fn procfile_name(path: &Path) -> String {
+    let dir = path
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|s| s.to_str())
+        .unwrap_or("procfile");
+    if path.file_name().and_then(|s| s.to_str()) == Some("Procfile.dev") {
+        format!("{dir}-dev")
+    } else {
+        dir.to_string()
+    }
+}
+
+/// Category name for a mise config: its project directory's name
+fn mise_name(path: &Path) -> String {
+    crate::script::mise_parser::project_dir(path)
+        .and_then(Path::file_name)
+        .and_then(|s| s.to_str())
+        .unwrap_or("mise")
+        .to_string()
+}
+
+/// Whether a file is the `.config/mise/config.toml` of a project
+fn is_mise_config_dir_file(filename: &str, path: &Path) -> bool {
+    filename == "config.toml"
+        && path
+            .parent()
+            .is_some_and(|dir| dir.ends_with(".config/mise"))
+}
+
 /// Determine the script type from the filename
 fn determine_script_type(filename: &str, file_path: &Path) -> Result<ScriptType> {
     // Check for specific filenames first
@@ -320,13 +821,8 @@ fn determine_script_type(filename: &str, file_path: &Path) -> Result<ScriptType>
         return Ok(ScriptType::Task);
     }
 
+    // Makefiles are read directly when make is missing (and marked unavailable)
     if MAKEFILE_NAMES.contains(&filename) {
-        if !crate::script::makefile_parser::is_make_available() {
-            anyhow::bail!(
-                "Makefile found but 'make' is not installed or not in PATH. \
-                Please install make to use this file."
-            );
-        }
         return Ok(ScriptType::Makefile);
     }
 
@@ -340,6 +836,36 @@ fn determine_script_type(filename: &str, file_path: &Path) -> Result<ScriptType>
         return Ok(ScriptType::Just);
     }
 
+    if RAKEFILE_NAMES.contains(&filename) {
+        if !crate::script::rake_parser::is_rake_available() {
+            anyhow::bail!(
+                "Rakefile found but 'rake' is not installed or not in PATH. \
+                Please install Ruby and Rake to use this file."
+            );
+        }
+        return Ok(ScriptType::Rakefile);
+    }
+
+    if SBT_NAMES.contains(&filename) {
+        if !crate::script::sbt_parser::is_sbt_available() {
+            anyhow::bail!(
+                "build.sbt found but 'sbt' is not installed or not in PATH. \
+                Please install sbt to use this file."
+            );
+        }
+        return Ok(ScriptType::Sbt);
+    }
+
+    if MAVEN_NAMES.contains(&filename) {
+        if !is_tool_available_for(ScriptType::Maven, file_path) {
+            anyhow::bail!(
+                "pom.xml found but neither a Maven wrapper (mvnw) nor 'mvn' is available. \
+                Please install Maven or add a Maven wrapper to use this file."
+            );
+        }
+        return Ok(ScriptType::Maven);
+    }
+
     if CARGO_TOML_NAMES.contains(&filename) {
         if !crate::script::cargo_parser::is_cargo_available() {
             anyhow::bail!(
@@ -350,6 +876,18 @@ fn determine_script_type(filename: &str, file_path: &Path) -> Result<ScriptType>
         return Ok(ScriptType::CargoToml);
     }
 
+    if PYPROJECT_NAMES.contains(&filename) {
+        return Ok(ScriptType::PyProject);
+    }
+
+    if DENO_JSON_NAMES.contains(&filename) {
+        return Ok(ScriptType::DenoJson);
+    }
+
+    if COMPOSER_JSON_NAMES.contains(&filename) {
+        return Ok(ScriptType::ComposerJson);
+    }
+
     if NX_JSON_NAMES.contains(&filename) {
         if !crate::script::nx_parser::is_nx_available() {
             anyhow::bail!(
@@ -370,6 +908,98 @@ fn determine_script_type(filename: &str, file_path: &Path) -> Result<ScriptType>
         return Ok(ScriptType::Gradle);
     }
 
+    if COMPOSE_NAMES.contains(&filename) {
+        if !crate::script::compose_parser::is_docker_compose_available() {
+            anyhow::bail!(
+                "Compose file found but 'docker compose' is not installed or not in PATH. \
+                Please install Docker with the Compose plugin to use this file."
+            );
+        }
+        return Ok(ScriptType::DockerCompose);
+    }
+
+    if PROCFILE_NAMES.contains(&filename) {
+        return Ok(ScriptType::Procfile);
+    }
+
+    if MISE_NAMES.contains(&filename) || is_mise_config_dir_file(filename, file_path) {
+        return Ok(ScriptType::Mise);
+    }
+
+    if CMAKE_NAMES.contains(&filename) {
+        if !crate::script::cmake_parser::is_cmake_available() {
+            anyhow::bail!(
+                "CMakeLists.txt found but 'cmake' is not installed or not in PATH. \
+                Please install CMake to use this file."
+            );
+        }
+        if !has_cmake_build_dir(file_path) {
+            anyhow::bail!(
+                "CMakeLists.txt found but the project has no '{}/' directory. \
+                Configure it first with 'cmake -B {}'.",
+                crate::script::cmake_parser::BUILD_DIR,
+                crate::script::cmake_parser::BUILD_DIR
+            );
+        }
+        return Ok(ScriptType::CMake);
+    }
+
+    if MESON_NAMES.contains(&filename) {
+        if !crate::script::meson_parser::is_meson_available() {
+            anyhow::bail!(
+                "meson.build found but 'meson' is not installed or not in PATH. \
+                Please install Meson to use this file."
+            );
+        }
+        if !has_meson_build_dir(file_path) {
+            anyhow::bail!(
+                "meson.build found but the project has no configured build directory. \
+                Set one up first with 'meson setup builddir'."
+            );
+        }
+        return Ok(ScriptType::Meson);
+    }
+
+    if DAGGER_NAMES.contains(&filename) {
+        if !crate::script::dagger_parser::is_dagger_available() {
+            anyhow::bail!(
+                "dagger.json found but 'dagger' is not installed or not in PATH. \
+                Please install the Dagger CLI to use this file."
+            );
+        }
+        return Ok(ScriptType::Dagger);
+    }
+
+    if MIX_NAMES.contains(&filename) {
+        if !crate::script::mix_parser::is_mix_available() {
+            anyhow::bail!(
+                "mix.exs found but 'mix' is not installed or not in PATH. \
+                Please install Elixir to use this file."
+            );
+        }
+        return Ok(ScriptType::Mix);
+    }
+
+    if LEFTHOOK_NAMES.contains(&filename) {
+        if !crate::script::lefthook_parser::is_lefthook_available() {
+            anyhow::bail!(
+                "Lefthook config found but 'lefthook' is not installed or not in PATH. \
+                Please install Lefthook to use this file."
+            );
+        }
+        return Ok(ScriptType::Lefthook);
+    }
+
+    if PRE_COMMIT_NAMES.contains(&filename) {
+        if !crate::script::precommit_parser::is_pre_commit_available() {
+            anyhow::bail!(
+                ".pre-commit-config.yaml found but 'pre-commit' is not installed or not in PATH. \
+                Please install pre-commit to use this file."
+            );
+        }
+        return Ok(ScriptType::PreCommit);
+    }
+
     if BAZEL_NAMES.contains(&filename) {
         if !crate::script::bazel_parser::is_bazel_available() {
             anyhow::bail!(
@@ -380,6 +1010,16 @@ fn determine_script_type(filename: &str, file_path: &Path) -> Result<ScriptType>
         return Ok(ScriptType::Bazel);
     }
 
+    if crate::script::ansible_parser::is_playbook_file(file_path) {
+        if !crate::script::ansible_parser::is_ansible_available() {
+            anyhow::bail!(
+                "Ansible playbook found but 'ansible-playbook' is not installed or not in PATH. \
+                Please install Ansible to use this file."
+            );
+        }
+        return Ok(ScriptType::Ansible);
+    }
+
     // Check file extension for .sh files
     if let Some(ext) = file_path.extension() {
         if ext == "sh" {
@@ -400,13 +1040,33 @@ fn determine_script_type(filename: &str, file_path: &Path) -> Result<ScriptType>
     anyhow::bail!(
         "Unsupported file type: '{}'. \
         Supported types: .sh (bash), .tf (terraform), package.json (npm), devbox.json (devbox), \
-        Taskfile.yml (task), Makefile (make), justfile (just), Cargo.toml (cargo), nx.json (nx), \
-        build.gradle (gradle), WORKSPACE/BUILD (bazel)",
+        Taskfile.yml (task), Makefile (make), justfile (just), Rakefile (rake), build.sbt (sbt), pom.xml (maven), Cargo.toml (cargo), \
+        nx.json (nx), pyproject.toml (python), deno.json (deno), composer.json (composer), build.gradle (gradle), WORKSPACE/BUILD (bazel), compose.yaml (docker compose), Procfile (foreman), CMakeLists.txt (cmake), meson.build (meson), dagger.json (dagger), mix.exs (mix), lefthook.yml (lefthook), .pre-commit-config.yaml (pre-commit), mise.toml (mise), *.yml playbooks (ansible)",
         filename
     );
 }
 
-fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<Vec<ScriptFile>> {
+/// Discover scripts in `scripts_dir` and up to `max_depth - 1` levels of
+/// subdirectories (`max_depth` 1 scans only the directory's own files).
+///
+/// Symlinked directories are followed, but each directory is visited at most
+/// once (by canonical path), so symlink loops cannot recurse forever.
+pub fn discover_scripts_with_depth(
+    scripts_dir: &Path,
+    max_depth: usize,
+) -> Result<Vec<ScriptFile>> {
+    discover_scripts_excluding(scripts_dir, max_depth, &ExcludeSet::default(), scripts_dir)
+}
+
+/// Like [`discover_scripts_with_depth`], skipping every file and directory
+/// whose path relative to `root` matches `excludes`.
+pub fn discover_scripts_excluding(
+    scripts_dir: &Path,
+    max_depth: usize,
+    excludes: &ExcludeSet,
+    root: &Path,
+) -> Result<Vec<ScriptFile>> {
+    let is_excluded = |path: &Path| excludes.is_excluded(path.strip_prefix(root).unwrap_or(path));
     let mut scripts = Vec::new();
 
     // Track directories that already have a Terraform ScriptFile registered.
@@ -421,6 +1081,26 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
     // Multiple bazel files (WORKSPACE, BUILD, MODULE.bazel, etc.) in the same directory should produce only one entry.
     let mut bazel_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
 
+    // Track directories that already have a Docker Compose ScriptFile registered.
+    // `docker compose` reads only one of compose.yaml / docker-compose.yml per directory.
+    let mut compose_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
+    // Track directories that already have a Lefthook ScriptFile registered.
+    // `lefthook` reads only one of lefthook.yml / .lefthook.yml / ... per directory.
+    let mut lefthook_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
+    // Track the Taskfile registered for each directory (index into `scripts`).
+    // `task` reads only one of Taskfile.yml / Taskfile.dist.yml / ... per directory.
+    let mut task_dirs: std::collections::HashMap<PathBuf, usize> = std::collections::HashMap::new();
+
+    // Track directories that already have a mise ScriptFile registered.
+    // `mise.toml`, `.mise.toml` and `.config/mise/config.toml` share one task list.
+    let mut mise_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
+    // Track directories that already have an Ansible ScriptFile registered.
+    // Every playbook of a directory is listed under one category.
+    let mut ansible_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
     // Verify the directory exists and is readable
     if !scripts_dir.exists() {
         return Ok(scripts); // Return empty vec if directory doesn't exist
@@ -436,37 +1116,78 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
     // Detect .github/workflows/ directory and add a single ScriptFile entry.
     // Each workflow file within it becomes an individual item during parsing.
     let github_workflows_dir = scripts_dir.join(".github").join("workflows");
-    if github_workflows_dir.is_dir() {
-        let has_workflows = std::fs::read_dir(&github_workflows_dir)
-            .map(|mut rd| {
-                rd.any(|e| {
-                    e.ok().is_some_and(|entry| {
-                        let p = entry.path();
-                        p.is_file()
-                            && matches!(
-                                p.extension().and_then(|x| x.to_str()),
-                                Some("yml") | Some("yaml")
-                            )
-                    })
-                })
-            })
-            .unwrap_or(false);
-
-        if has_workflows {
+    if github_workflows_dir.is_dir() && !is_excluded(&github_workflows_dir) {
+        let workflow_files: Vec<PathBuf> =
+            crate::script::github_actions_parser::workflow_files(&github_workflows_dir)
+                .into_iter()
+                .filter(|path| !is_excluded(path))
+                .collect();
+
+        if !workflow_files.is_empty() {
             scripts.push(ScriptFile {
                 path: github_workflows_dir,
                 name: "GitHub Actions".to_string(),
                 category: "GitHub Actions".to_string(),
                 display_name: "🐙 GitHub Actions".to_string(),
                 script_type: ScriptType::GithubActions,
+                available: true,
             });
         }
+
+        // With act installed, every workflow also gets a category of its jobs
+        if crate::script::github_actions_parser::is_act_available() {
+            for workflow_path in workflow_files {
+                let Some(file_name) = workflow_path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                let file_name = file_name.to_string();
+                let workflow_name = std::fs::read_to_string(&workflow_path)
+                    .ok()
+                    .and_then(|content| {
+                        crate::script::github_actions_parser::extract_workflow_name(&content)
+                    })
+                    .unwrap_or_else(|| file_name.clone());
+
+                scripts.push(ScriptFile {
+                    path: workflow_path,
+                    name: file_name.clone(),
+                    category: file_name,
+                    display_name: format!("🐙 {} jobs", workflow_name),
+                    script_type: ScriptType::GithubActionsJob,
+                    available: true,
+                });
+            }
+        }
     }
 
+    // Canonical paths of the directories walked so far (symlink loop guard)
+    let mut visited_dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
     // Walk the directory and collect scripts
     for entry in WalkDir::new(scripts_dir)
         .max_depth(max_depth)
+        .follow_links(true)
         .into_iter()
+        .filter_entry(|entry| {
+            if is_excluded(entry.path()) {
+                return false;
+            }
+            if !entry.file_type().is_dir() {
+                return true;
+            }
+            if entry.depth() > 0
+                && entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| SKIPPED_DIRS.contains(&name))
+            {
+                return false;
+            }
+            match entry.path().canonicalize() {
+                Ok(canonical) => visited_dirs.insert(canonical),
+                Err(_) => false,
+            }
+        })
         .filter_map(|e| match e {
             Ok(entry) => Some(entry),
             Err(err) => {
@@ -505,6 +1226,7 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     category,
                     display_name,
                     script_type: ScriptType::PackageJson,
+                    available: is_tool_available_for(ScriptType::PackageJson, path),
                 });
                 continue;
             }
@@ -535,6 +1257,7 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     category,
                     display_name,
                     script_type: ScriptType::DevboxJson,
+                    available: true,
                 });
                 continue;
             }
@@ -544,6 +1267,16 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     continue;
                 }
 
+                // One Taskfile per directory: the one `task` would pick
+                let task_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
+                if let Some(&index) = task_dirs.get(&task_dir) {
+                    if taskfile_priority(path) < taskfile_priority(&scripts[index].path) {
+                        scripts[index].path = path.to_path_buf();
+                    }
+                    continue;
+                }
+                task_dirs.insert(task_dir, scripts.len());
+
                 let name = if let Some(parent) = path.parent() {
                     parent
                         .file_name()
@@ -563,15 +1296,12 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     category,
                     display_name,
                     script_type: ScriptType::Task,
+                    available: true,
                 });
                 continue;
             }
 
             if MAKEFILE_NAMES.contains(&filename) {
-                if !crate::script::makefile_parser::is_make_available() {
-                    continue;
-                }
-
                 let name = if let Some(parent) = path.parent() {
                     parent
                         .file_name()
@@ -591,6 +1321,7 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     category,
                     display_name,
                     script_type: ScriptType::Makefile,
+                    available: crate::script::makefile_parser::is_make_available(),
                 });
                 continue;
             }
@@ -619,6 +1350,7 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     category,
                     display_name,
                     script_type: ScriptType::Just,
+                    available: true,
                 });
                 continue;
             }
@@ -647,12 +1379,13 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     category,
                     display_name,
                     script_type: ScriptType::Mage,
+                    available: true,
                 });
                 continue;
             }
 
-            if CARGO_TOML_NAMES.contains(&filename) {
-                if !crate::script::cargo_parser::is_cargo_available() {
+            if RAKEFILE_NAMES.contains(&filename) {
+                if !crate::script::rake_parser::is_rake_available() {
                     continue;
                 }
 
@@ -660,134 +1393,553 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     parent
                         .file_name()
                         .and_then(|s| s.to_str())
-                        .unwrap_or("cargo")
+                        .unwrap_or("rake")
                         .to_string()
                 } else {
-                    "cargo".to_string()
+                    "rake".to_string()
                 };
 
                 let category = name.clone();
-                let display_name = format!("🦀 {}", format_display_name(&name));
+                let display_name = format!("💎 {}", format_display_name(&name));
 
                 scripts.push(ScriptFile {
                     path: path.to_path_buf(),
                     name,
                     category,
                     display_name,
-                    script_type: ScriptType::CargoToml,
+                    script_type: ScriptType::Rakefile,
+                    available: true,
                 });
                 continue;
             }
 
-            if NX_JSON_NAMES.contains(&filename) {
-                if !crate::script::nx_parser::is_nx_available() {
+            if MAVEN_NAMES.contains(&filename) {
+                if !is_tool_available_for(ScriptType::Maven, path) {
                     continue;
                 }
+                let project_dir = path.parent().unwrap_or(Path::new("."));
 
-                let name = if let Some(parent) = path.parent() {
-                    parent
-                        .file_name()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("nx")
-                        .to_string()
-                } else {
-                    "nx".to_string()
-                };
+                let name = project_dir
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("maven")
+                    .to_string();
 
                 let category = name.clone();
-                let display_name = format!("🔷 {}", format_display_name(&name));
+                let display_name = format!("🪶 {}", format_display_name(&name));
 
                 scripts.push(ScriptFile {
                     path: path.to_path_buf(),
                     name,
                     category,
                     display_name,
-                    script_type: ScriptType::NxJson,
+                    script_type: ScriptType::Maven,
+                    available: true,
                 });
                 continue;
             }
 
-            if GRADLE_BUILD_NAMES.contains(&filename) || GRADLE_SETTINGS_NAMES.contains(&filename) {
-                if !crate::script::gradle_parser::is_gradle_available() {
-                    continue;
-                }
-
-                // Only register one ScriptFile per gradle directory
-                let gradle_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
-
-                if gradle_dirs.contains(&gradle_dir) {
+            if SBT_NAMES.contains(&filename) {
+                if !crate::script::sbt_parser::is_sbt_available() {
                     continue;
                 }
-                gradle_dirs.insert(gradle_dir.clone());
 
                 let name = if let Some(parent) = path.parent() {
                     parent
                         .file_name()
                         .and_then(|s| s.to_str())
-                        .unwrap_or("gradle")
+                        .unwrap_or("sbt")
                         .to_string()
                 } else {
-                    "gradle".to_string()
+                    "sbt".to_string()
                 };
 
                 let category = name.clone();
-                let display_name = format!("🐘 {}", format_display_name(&name));
+                let display_name = format!("🪜 {}", format_display_name(&name));
 
                 scripts.push(ScriptFile {
-                    path: gradle_dir,
+                    path: path.to_path_buf(),
                     name,
                     category,
                     display_name,
-                    script_type: ScriptType::Gradle,
+                    script_type: ScriptType::Sbt,
+                    available: true,
                 });
                 continue;
             }
 
-            if BAZEL_NAMES.contains(&filename) {
-                if !crate::script::bazel_parser::is_bazel_available() {
-                    continue;
-                }
-
-                // Only register one ScriptFile per bazel directory
-                let bazel_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
-
-                if bazel_dirs.contains(&bazel_dir) {
+            if CARGO_TOML_NAMES.contains(&filename) {
+                if !crate::script::cargo_parser::is_cargo_available() {
                     continue;
                 }
-                bazel_dirs.insert(bazel_dir.clone());
 
                 let name = if let Some(parent) = path.parent() {
                     parent
                         .file_name()
                         .and_then(|s| s.to_str())
-                        .unwrap_or("bazel")
+                        .unwrap_or("cargo")
                         .to_string()
                 } else {
-                    "bazel".to_string()
+                    "cargo".to_string()
                 };
 
                 let category = name.clone();
-                let display_name = format!("🌿 {}", format_display_name(&name));
+                let display_name = format!("🦀 {}", format_display_name(&name));
 
                 scripts.push(ScriptFile {
-                    path: bazel_dir,
+                    path: path.to_path_buf(),
                     name,
                     category,
                     display_name,
-                    script_type: ScriptType::Bazel,
+                    script_type: ScriptType::CargoToml,
+                    available: true,
                 });
                 continue;
             }
-        }
 
-        // Check file extension for .sh files
-        let extension = match path.extension() {
-            Some(ext) => ext,
-            None => continue,
-        };
-
-        if extension != "sh" {
-            // Check for Terraform .tf files
+            if DENO_JSON_NAMES.contains(&filename) {
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("deno")
+                        .to_string()
+                } else {
+                    "deno".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🦕 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::DenoJson,
+                    available: is_tool_available_for(ScriptType::DenoJson, path),
+                });
+                continue;
+            }
+
+            if COMPOSER_JSON_NAMES.contains(&filename) {
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("composer")
+                        .to_string()
+                } else {
+                    "composer".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🎼 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::ComposerJson,
+                    available: is_tool_available_for(ScriptType::ComposerJson, path),
+                });
+                continue;
+            }
+
+            if PYPROJECT_NAMES.contains(&filename) {
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("python")
+                        .to_string()
+                } else {
+                    "python".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🐍 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::PyProject,
+                    available: is_tool_available_for(ScriptType::PyProject, path),
+                });
+                continue;
+            }
+
+            if NX_JSON_NAMES.contains(&filename) {
+                if !crate::script::nx_parser::is_nx_available() {
+                    continue;
+                }
+
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("nx")
+                        .to_string()
+                } else {
+                    "nx".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🔷 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::NxJson,
+                    available: true,
+                });
+                continue;
+            }
+
+            if GRADLE_BUILD_NAMES.contains(&filename) || GRADLE_SETTINGS_NAMES.contains(&filename) {
+                if !crate::script::gradle_parser::is_gradle_available() {
+                    continue;
+                }
+
+                // Only register one ScriptFile per gradle directory
+                let gradle_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
+
+                if gradle_dirs.contains(&gradle_dir) {
+                    continue;
+                }
+                gradle_dirs.insert(gradle_dir.clone());
+
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("gradle")
+                        .to_string()
+                } else {
+                    "gradle".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🐘 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Gradle,
+                    available: true,
+                });
+                continue;
+            }
+
+            if COMPOSE_NAMES.contains(&filename) {
+                if !crate::script::compose_parser::is_docker_compose_available() {
+                    continue;
+                }
+
+                // Only register one ScriptFile per compose directory
+                let compose_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
+
+                if compose_dirs.contains(&compose_dir) {
+                    continue;
+                }
+                compose_dirs.insert(compose_dir);
+
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("compose")
+                        .to_string()
+                } else {
+                    "compose".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🐳 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::DockerCompose,
+                    available: true,
+                });
+                continue;
+            }
+
+            if CMAKE_NAMES.contains(&filename) {
+                if !crate::script::cmake_parser::is_cmake_available() || !has_cmake_build_dir(path)
+                {
+                    continue;
+                }
+
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("cmake")
+                        .to_string()
+                } else {
+                    "cmake".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🔺 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::CMake,
+                    available: true,
+                });
+                continue;
+            }
+
+            if MESON_NAMES.contains(&filename) {
+                if !crate::script::meson_parser::is_meson_available() || !has_meson_build_dir(path)
+                {
+                    continue;
+                }
+
+                let name = path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("meson")
+                    .to_string();
+
+                let category = name.clone();
+                let display_name = format!("🧱 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Meson,
+                    available: true,
+                });
+                continue;
+            }
+
+            if DAGGER_NAMES.contains(&filename) {
+                if !crate::script::dagger_parser::is_dagger_available() {
+                    continue;
+                }
+
+                let name = path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("dagger")
+                    .to_string();
+
+                let category = name.clone();
+                let display_name = format!("🗡️ {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Dagger,
+                    available: true,
+                });
+                continue;
+            }
+
+            if MIX_NAMES.contains(&filename) {
+                if !crate::script::mix_parser::is_mix_available() {
+                    continue;
+                }
+
+                let name = path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("mix")
+                    .to_string();
+
+                let category = name.clone();
+                let display_name = format!("💧 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Mix,
+                    available: true,
+                });
+                continue;
+            }
+
+            if LEFTHOOK_NAMES.contains(&filename) {
+                if !crate::script::lefthook_parser::is_lefthook_available() {
+                    continue;
+                }
+
+                // Only register one ScriptFile per Lefthook directory
+                let lefthook_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
+                if !lefthook_dirs.insert(lefthook_dir) {
+                    continue;
+                }
+
+                let name = path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("lefthook")
+                    .to_string();
+
+                let category = name.clone();
+                let display_name = format!("🥊 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Lefthook,
+                    available: true,
+                });
+                continue;
+            }
+
+            if PRE_COMMIT_NAMES.contains(&filename) {
+                if !crate::script::precommit_parser::is_pre_commit_available() {
+                    continue;
+                }
+
+                let name = path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("pre-commit")
+                    .to_string();
+
+                let category = name.clone();
+                let display_name = format!("🪝 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::PreCommit,
+                    available: true,
+                });
+                continue;
+            }
+
+            if MISE_NAMES.contains(&filename) {
+                // mise merges the configs of a directory, so list its tasks once
+                let mise_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
+                if !mise_dirs.insert(mise_dir) {
+                    continue;
+                }
+
+                let name = mise_name(path);
+                let category = name.clone();
+                let display_name = format!("🍳 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Mise,
+                    available: crate::script::mise_parser::is_mise_available(),
+                });
+                continue;
+            }
+
+            if PROCFILE_NAMES.contains(&filename) {
+                let name = procfile_name(path);
+                let category = name.clone();
+                let display_name = format!("🏭 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: path.to_path_buf(),
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Procfile,
+                    available: true,
+                });
+                continue;
+            }
+
+            if BAZEL_NAMES.contains(&filename) {
+                if !crate::script::bazel_parser::is_bazel_available() {
+                    continue;
+                }
+
+                // Only register one ScriptFile per bazel directory
+                let bazel_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
+
+                if bazel_dirs.contains(&bazel_dir) {
+                    continue;
+                }
+                bazel_dirs.insert(bazel_dir.clone());
+
+                let name = if let Some(parent) = path.parent() {
+                    parent
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("bazel")
+                        .to_string()
+                } else {
+                    "bazel".to_string()
+                };
+
+                let category = name.clone();
+                let display_name = format!("🌿 {}", format_display_name(&name));
+
+                scripts.push(ScriptFile {
+                    path: bazel_dir,
+                    name,
+                    category,
+                    display_name,
+                    script_type: ScriptType::Bazel,
+                    available: true,
+                });
+                continue;
+            }
+        }
+
+        // Ansible playbooks, told apart from other YAML by their `hosts:` plays
+        if crate::script::ansible_parser::is_yaml_file(path)
+            && crate::script::ansible_parser::is_ansible_available()
+        {
+            let ansible_dir = path.parent().unwrap_or(scripts_dir).to_path_buf();
+            if !ansible_dirs.contains(&ansible_dir)
+                && crate::script::ansible_parser::is_playbook_file(path)
+            {
+                ansible_dirs.insert(ansible_dir.clone());
+                scripts.push(ansible_script_file(ansible_dir));
+            }
+            continue;
+        }
+
+        // Check file extension for .sh files
+        let extension = match path.extension() {
+            Some(ext) => ext,
+            None => continue,
+        };
+
+        if extension != "sh" {
+            // Check for Terraform .tf files
             if extension == "tf" {
                 if !crate::script::terraform_parser::is_terraform_available() {
                     continue;
@@ -816,33 +1968,188 @@ fn discover_scripts_with_depth(scripts_dir: &Path, max_depth: usize) -> Result<V
                     category,
                     display_name,
                     script_type: ScriptType::Terraform,
+                    available: true,
                 });
             }
             continue;
         }
-
-        // Extract filename (without extension) to use as the category
-        let name = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .with_context(|| format!("Invalid filename for script: {}", path.display()))?
-            .to_string();
-
-        // Use the filename as category (key)
-        // Format the display name for UI presentation
-        let category = name.clone();
-        let display_name = format_display_name(&name);
-
-        scripts.push(ScriptFile {
-            path: path.to_path_buf(),
-            name,
-            category,
-            display_name,
-            script_type: ScriptType::Bash,
-        });
+
+        // Extract filename (without extension) to use as the category
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .with_context(|| format!("Invalid filename for script: {}", path.display()))?
+            .to_string();
+
+        // Use the filename as category (key)
+        // Format the display name for UI presentation
+        let category = name.clone();
+        let display_name = format_display_name(&name);
+
+        scripts.push(ScriptFile {
+            path: path.to_path_buf(),
+            name,
+            category,
+            display_name,
+            script_type: ScriptType::Bash,
+            available: true,
+        });
+    }
+
+    // `.config/mise/config.toml` sits below the walk depth; use it when the
+    // directory has no `mise.toml` of its own
+    let mise_config = scripts_dir.join(".config").join("mise").join("config.toml");
+    if mise_config.is_file() && !mise_dirs.contains(scripts_dir) {
+        let name = mise_name(&mise_config);
+        scripts.push(ScriptFile {
+            path: mise_config,
+            category: name.clone(),
+            display_name: format!("🍳 {}", format_display_name(&name)),
+            name,
+            script_type: ScriptType::Mise,
+            available: crate::script::mise_parser::is_mise_available(),
+        });
+    }
+
+    // `playbooks/` sits below the shallow walk depth; list it when it has playbooks
+    let playbooks_dir = scripts_dir.join(crate::script::ansible_parser::PLAYBOOKS_DIR);
+    if crate::script::ansible_parser::is_ansible_available()
+        && !ansible_dirs.contains(&playbooks_dir)
+        && !crate::script::ansible_parser::playbook_files(&playbooks_dir).is_empty()
+    {
+        scripts.push(ansible_script_file(playbooks_dir));
+    }
+
+    Ok(scripts)
+}
+
+/// The `ScriptFile` of a directory of Ansible playbooks
+fn ansible_script_file(dir: PathBuf) -> ScriptFile {
+    let name = dir
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("ansible")
+        .to_string();
+    ScriptFile {
+        category: name.clone(),
+        display_name: format!("🅰️ {}", format_display_name(&name)),
+        name,
+        path: dir,
+        script_type: ScriptType::Ansible,
+        available: true,
+    }
+}
+
+/// Build a short description for a script file's category.
+///
+/// - Directory-backed categories (Terraform, GitHub Actions) describe their directory
+/// - JSON manifests use their top-level `"description"` field
+/// - `pyproject.toml` uses the `[project]` (or `[tool.poetry]`) description
+/// - Other files use the first comment block at the top of the file, skipping
+///   the shebang and `@` annotations
+///
+/// Returns `None` when the file has nothing worth showing.
+pub fn category_description(script_file: &ScriptFile) -> Option<String> {
+    match script_file.script_type {
+        ScriptType::Terraform => Some(format!(
+            "Terraform directory: {}",
+            script_file.path.display()
+        )),
+        ScriptType::Ansible => Some(format!(
+            "Ansible playbooks in {}",
+            crate::script::ansible_parser::playbook_dir(&script_file.path).display()
+        )),
+        ScriptType::GithubActions => Some(format!(
+            "Workflows directory: {}",
+            script_file.path.display()
+        )),
+        ScriptType::GithubActionsJob => Some(format!(
+            "Jobs of {}, run locally with act",
+            script_file.path.display()
+        )),
+        ScriptType::PackageJson
+        | ScriptType::ComposerJson
+        | ScriptType::Dagger
+        | ScriptType::DevboxJson
+        | ScriptType::NxJson => {
+            let content = std::fs::read_to_string(&script_file.path).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            json.get("description")
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(String::from)
+        }
+        ScriptType::PyProject => {
+            let content = std::fs::read_to_string(&script_file.path).ok()?;
+            crate::script::python_parser::project_description(&content)
+        }
+        ScriptType::Maven => {
+            let content = std::fs::read_to_string(&script_file.path).ok()?;
+            crate::script::maven_parser::project_description(&content)
+        }
+        ScriptType::DenoJson | ScriptType::Gradle | ScriptType::Mage | ScriptType::Sbt => {
+            let content = std::fs::read_to_string(&script_file.path).ok()?;
+            leading_comment_block(&content, "//")
+        }
+        ScriptType::Bash
+        | ScriptType::Bazel
+        | ScriptType::CargoToml
+        | ScriptType::CMake
+        | ScriptType::DockerCompose
+        | ScriptType::Just
+        | ScriptType::Lefthook
+        | ScriptType::Makefile
+        | ScriptType::Meson
+        | ScriptType::Mise
+        | ScriptType::Mix
+        | ScriptType::PreCommit
+        | ScriptType::Procfile
+        | ScriptType::Rakefile
+        | ScriptType::Task => {
+            let content = std::fs::read_to_string(&script_file.path).ok()?;
+            leading_comment_block(&content, "#")
+        }
+    }
+}
+
+/// Extract the first block of consecutive comment lines at the top of a file.
+///
+/// Leading blank lines, a shebang and `@` annotation comments are skipped.
+/// The block ends at the first non-comment line.
+fn leading_comment_block(content: &str, prefix: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("#!") {
+            continue;
+        }
+        if trimmed.is_empty() {
+            if lines.is_empty() {
+                continue;
+            }
+            break;
+        }
+        let Some(text) = trimmed.strip_prefix(prefix) else {
+            break;
+        };
+        let text = text.trim_start_matches(prefix).trim();
+        if text.starts_with('@') {
+            continue;
+        }
+        if text.is_empty() && lines.is_empty() {
+            continue;
+        }
+        lines.push(text.to_string());
+    }
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
     }
-
-    Ok(scripts)
 }
 
 #[cfg(test)]
@@ -867,321 +2174,892 @@ mod tests {
     }
 
     #[test]
-    fn test_discover_scripts_single_script() {
+    fn test_discover_scripts_single_script() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("test.sh");
+        fs::write(&script_path, "#!/bin/bash\necho 'test'").unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "test");
+        assert_eq!(result[0].category, "test");
+        assert_eq!(result[0].display_name, "Test");
+        assert_eq!(result[0].script_type, ScriptType::Bash);
+    }
+
+    #[test]
+    fn test_discover_scripts_categorization() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Create scripts with different names
+        fs::write(temp_dir.path().join("fedora.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("homelab.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("util.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("custom.sh"), "#!/bin/bash").unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 4);
+
+        // Check categorization - uses filename as category, formatted as display name
+        let fedora = result.iter().find(|s| s.name == "fedora").unwrap();
+        assert_eq!(fedora.category, "fedora");
+        assert_eq!(fedora.display_name, "Fedora");
+
+        let homelab = result.iter().find(|s| s.name == "homelab").unwrap();
+        assert_eq!(homelab.category, "homelab");
+        assert_eq!(homelab.display_name, "Homelab");
+
+        let util = result.iter().find(|s| s.name == "util").unwrap();
+        assert_eq!(util.category, "util");
+        assert_eq!(util.display_name, "Util");
+
+        let custom = result.iter().find(|s| s.name == "custom").unwrap();
+        assert_eq!(custom.category, "custom");
+        assert_eq!(custom.display_name, "Custom");
+    }
+
+    #[test]
+    fn test_discover_scripts_ignores_non_sh_files() {
+        let temp_dir = TempDir::new().unwrap();
+
+        fs::write(temp_dir.path().join("script.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("readme.txt"), "text file").unwrap();
+        fs::write(temp_dir.path().join("data.json"), "{}").unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "script");
+        assert_eq!(result[0].display_name, "Script");
+    }
+
+    #[test]
+    fn test_discover_scripts_subdirectories() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Create script in root
+        fs::write(temp_dir.path().join("root.sh"), "#!/bin/bash").unwrap();
+
+        // Create subdirectory with script
+        let sub_dir = temp_dir.path().join("subdir");
+        fs::create_dir(&sub_dir).unwrap();
+        fs::write(sub_dir.join("sub.sh"), "#!/bin/bash").unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_discover_scripts_file_instead_of_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("file.txt");
+        fs::write(&file_path, "content").unwrap();
+
+        let result = discover_scripts(&file_path);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not a directory"));
+    }
+
+    #[test]
+    fn test_discover_scripts_with_emoji_filenames() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Create scripts with emoji in filenames
+        fs::write(temp_dir.path().join("🏠 homelab.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("🛠️ utilities.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("🖥️ system.sh"), "#!/bin/bash").unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 3);
+
+        // Check that emoji is preserved and text is capitalized
+        let homelab = result.iter().find(|s| s.name == "🏠 homelab").unwrap();
+        assert_eq!(homelab.category, "🏠 homelab");
+        assert_eq!(homelab.display_name, "🏠 Homelab");
+
+        let utilities = result.iter().find(|s| s.name == "🛠️ utilities").unwrap();
+        assert_eq!(utilities.category, "🛠️ utilities");
+        assert_eq!(utilities.display_name, "🛠️ Utilities");
+
+        let system = result.iter().find(|s| s.name == "🖥️ system").unwrap();
+        assert_eq!(system.category, "🖥️ system");
+        assert_eq!(system.display_name, "🖥️ System");
+    }
+
+    #[test]
+    fn test_discover_scripts_with_underscores_and_hyphens() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Create scripts with underscores and hyphens
+        fs::write(temp_dir.path().join("example_file.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("example-file.sh"), "#!/bin/bash").unwrap();
+        fs::write(temp_dir.path().join("my_test_script.sh"), "#!/bin/bash").unwrap();
+        fs::write(
+            temp_dir.path().join("another-test-script.sh"),
+            "#!/bin/bash",
+        )
+        .unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 4);
+
+        // Check underscore handling
+        let example_underscore = result.iter().find(|s| s.name == "example_file").unwrap();
+        assert_eq!(example_underscore.category, "example_file");
+        assert_eq!(example_underscore.display_name, "Example File");
+
+        // Check hyphen handling
+        let example_hyphen = result.iter().find(|s| s.name == "example-file").unwrap();
+        assert_eq!(example_hyphen.category, "example-file");
+        assert_eq!(example_hyphen.display_name, "Example File");
+
+        // Check multiple underscores
+        let my_test = result.iter().find(|s| s.name == "my_test_script").unwrap();
+        assert_eq!(my_test.category, "my_test_script");
+        assert_eq!(my_test.display_name, "My Test Script");
+
+        // Check multiple hyphens
+        let another_test = result
+            .iter()
+            .find(|s| s.name == "another-test-script")
+            .unwrap();
+        assert_eq!(another_test.category, "another-test-script");
+        assert_eq!(another_test.display_name, "Another Test Script");
+    }
+
+    #[test]
+    fn test_format_display_name() {
+        assert_eq!(format_display_name("example"), "Example");
+        assert_eq!(format_display_name("example_file"), "Example File");
+        assert_eq!(format_display_name("example-file"), "Example File");
+        assert_eq!(format_display_name("my_test_script"), "My Test Script");
+        assert_eq!(
+            format_display_name("another-test-script"),
+            "Another Test Script"
+        );
+        assert_eq!(format_display_name("🏠 homelab"), "🏠 Homelab");
+        assert_eq!(format_display_name("🛠️ utilities"), "🛠️ Utilities");
+        assert_eq!(format_display_name("UPPERCASE"), "Uppercase");
+        assert_eq!(
+            format_display_name("mixed_CASE-example"),
+            "Mixed Case Example"
+        );
+        assert_eq!(format_display_name("service.auth"), "Service Auth");
+        assert_eq!(
+            format_display_name("service.api-gateway"),
+            "Service Api Gateway"
+        );
+    }
+
+    #[test]
+    fn test_discover_package_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_path = temp_dir.path().join("package.json");
+
+        let content = r#"{
+  "name": "test-project",
+  "scripts": {
+    "test": "jest"
+  }
+}"#;
+        fs::write(&package_path, content).unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].script_type, ScriptType::PackageJson);
+        // Category should be the parent directory name
+        assert!(!result[0].category.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover_scripts_survives_symlink_loop() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+        fs::write(nested.join("build.sh"), "build() {\n  echo build\n}\n").unwrap();
+        // nested/loop -> root, so following links could recurse forever
+        std::os::unix::fs::symlink(temp_dir.path(), nested.join("loop")).unwrap();
+
+        let result = discover_scripts_with_depth(temp_dir.path(), 10).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].path, nested.join("build.sh"));
+    }
+
+    #[test]
+    fn test_discover_scripts_skips_dependency_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let modules = temp_dir.path().join("node_modules").join("left-pad");
+        fs::create_dir_all(&modules).unwrap();
+        fs::write(modules.join("package.json"), r#"{"scripts":{"x":"y"}}"#).unwrap();
+
+        assert!(discover_scripts_with_depth(temp_dir.path(), 3)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_discover_deno_json() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("deno.jsonc"),
+            "// Deno tasks\n{ \"tasks\": { \"dev\": \"deno run main.ts\" } }\n",
+        )
+        .unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].script_type, ScriptType::DenoJson);
+        assert!(result[0].display_name.starts_with("🦕 "));
+        assert_eq!(
+            category_description(&result[0]),
+            Some("Deno tasks".to_string())
+        );
+    }
+
+    #[test]
+    fn test_discover_composer_json() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("composer.json"),
+            r#"{ "description": "PHP app", "scripts": { "test": "phpunit" } }"#,
+        )
+        .unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].script_type, ScriptType::ComposerJson);
+        assert!(result[0].display_name.starts_with("🎼 "));
+        assert_eq!(
+            category_description(&result[0]),
+            Some("PHP app".to_string())
+        );
+    }
+
+    #[test]
+    fn test_discover_pyproject_toml() {
         let temp_dir = TempDir::new().unwrap();
-        let script_path = temp_dir.path().join("test.sh");
-        fs::write(&script_path, "#!/bin/bash\necho 'test'").unwrap();
+        fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[project]\ndescription = \"Sample\"\n\n[project.scripts]\nserve = \"app:main\"\n",
+        )
+        .unwrap();
 
         let result = discover_scripts(temp_dir.path()).unwrap();
         assert_eq!(result.len(), 1);
-        assert_eq!(result[0].name, "test");
-        assert_eq!(result[0].category, "test");
-        assert_eq!(result[0].display_name, "Test");
-        assert_eq!(result[0].script_type, ScriptType::Bash);
+        assert_eq!(result[0].script_type, ScriptType::PyProject);
+        assert!(result[0].display_name.starts_with("🐍 "));
+        assert_eq!(category_description(&result[0]), Some("Sample".to_string()));
     }
 
     #[test]
-    fn test_discover_scripts_categorization() {
+    fn test_discover_mixed_scripts() {
         let temp_dir = TempDir::new().unwrap();
 
-        // Create scripts with different names
-        fs::write(temp_dir.path().join("fedora.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("homelab.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("util.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("custom.sh"), "#!/bin/bash").unwrap();
-
-        let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 4);
+        // Create bash script
+        fs::write(temp_dir.path().join("script.sh"), "#!/bin/bash").unwrap();
 
-        // Check categorization - uses filename as category, formatted as display name
-        let fedora = result.iter().find(|s| s.name == "fedora").unwrap();
-        assert_eq!(fedora.category, "fedora");
-        assert_eq!(fedora.display_name, "Fedora");
+        // Create package.json
+        let content = r#"{"name": "test", "scripts": {"test": "jest"}}"#;
+        fs::write(temp_dir.path().join("package.json"), content).unwrap();
 
-        let homelab = result.iter().find(|s| s.name == "homelab").unwrap();
-        assert_eq!(homelab.category, "homelab");
-        assert_eq!(homelab.display_name, "Homelab");
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 2);
 
-        let util = result.iter().find(|s| s.name == "util").unwrap();
-        assert_eq!(util.category, "util");
-        assert_eq!(util.display_name, "Util");
+        // Should find both types
+        let bash_count = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::Bash)
+            .count();
+        let npm_count = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::PackageJson)
+            .count();
 
-        let custom = result.iter().find(|s| s.name == "custom").unwrap();
-        assert_eq!(custom.category, "custom");
-        assert_eq!(custom.display_name, "Custom");
+        assert_eq!(bash_count, 1);
+        assert_eq!(npm_count, 1);
     }
 
     #[test]
-    fn test_discover_scripts_ignores_non_sh_files() {
+    fn test_discover_devbox_json() {
         let temp_dir = TempDir::new().unwrap();
+        let devbox_path = temp_dir.path().join("devbox.json");
 
-        fs::write(temp_dir.path().join("script.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("readme.txt"), "text file").unwrap();
-        fs::write(temp_dir.path().join("data.json"), "{}").unwrap();
+        let content = r#"{
+  "shell": {
+    "scripts": {
+      "test": ["cargo test"]
+    }
+  }
+}"#;
+        fs::write(&devbox_path, content).unwrap();
 
         let result = discover_scripts(temp_dir.path()).unwrap();
         assert_eq!(result.len(), 1);
-        assert_eq!(result[0].name, "script");
-        assert_eq!(result[0].display_name, "Script");
+        assert_eq!(result[0].script_type, ScriptType::DevboxJson);
+        // Category should be the parent directory name
+        assert!(!result[0].category.is_empty());
     }
 
     #[test]
-    fn test_discover_scripts_subdirectories() {
+    fn test_discover_all_script_types() {
         let temp_dir = TempDir::new().unwrap();
 
-        // Create script in root
-        fs::write(temp_dir.path().join("root.sh"), "#!/bin/bash").unwrap();
+        // Create bash script
+        fs::write(temp_dir.path().join("script.sh"), "#!/bin/bash").unwrap();
 
-        // Create subdirectory with script
-        let sub_dir = temp_dir.path().join("subdir");
-        fs::create_dir(&sub_dir).unwrap();
-        fs::write(sub_dir.join("sub.sh"), "#!/bin/bash").unwrap();
+        // Create package.json
+        let npm_content = r#"{"name": "test", "scripts": {"test": "jest"}}"#;
+        fs::write(temp_dir.path().join("package.json"), npm_content).unwrap();
 
-        let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 2);
+        // Create devbox.json
+        let devbox_content = r#"{
+  "shell": {
+    "scripts": {
+      "build": ["cargo build"]
     }
+  }
+}"#;
+        fs::write(temp_dir.path().join("devbox.json"), devbox_content).unwrap();
 
-    #[test]
-    fn test_discover_scripts_file_instead_of_directory() {
-        let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("file.txt");
-        fs::write(&file_path, "content").unwrap();
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        assert_eq!(result.len(), 3);
 
-        let result = discover_scripts(&file_path);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("not a directory"));
+        // Should find all three types
+        let bash_count = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::Bash)
+            .count();
+        let npm_count = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::PackageJson)
+            .count();
+        let devbox_count = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::DevboxJson)
+            .count();
+
+        assert_eq!(bash_count, 1);
+        assert_eq!(npm_count, 1);
+        assert_eq!(devbox_count, 1);
     }
 
     #[test]
-    fn test_discover_scripts_with_emoji_filenames() {
+    fn test_discover_taskfile() {
         let temp_dir = TempDir::new().unwrap();
+        let taskfile_path = temp_dir.path().join("Taskfile.yml");
 
-        // Create scripts with emoji in filenames
-        fs::write(temp_dir.path().join("🏠 homelab.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("🛠️ utilities.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("🖥️ system.sh"), "#!/bin/bash").unwrap();
+        let content = r#"version: '3'
+tasks:
+  default:
+    desc: Default task
+    cmds: [echo hello]
+"#;
+        fs::write(&taskfile_path, content).unwrap();
 
         let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 3);
+        // If task binary is installed we get 1 ScriptFile with Task type, else 0
+        let task_files: Vec<_> = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::Task)
+            .collect();
+        assert!(
+            task_files.len() <= 1,
+            "should have at most one Task script file"
+        );
+        if let Some(sf) = task_files.first() {
+            assert_eq!(sf.script_type, ScriptType::Task);
+            assert!(sf.path.ends_with("Taskfile.yml"));
+        }
+    }
 
-        // Check that emoji is preserved and text is capitalized
-        let homelab = result.iter().find(|s| s.name == "🏠 homelab").unwrap();
-        assert_eq!(homelab.category, "🏠 homelab");
-        assert_eq!(homelab.display_name, "🏠 Homelab");
+    #[test]
+    fn test_taskfile_priority_prefers_local_over_dist() {
+        assert!(
+            taskfile_priority(Path::new("/app/Taskfile.yml"))
+                < taskfile_priority(Path::new("/app/Taskfile.dist.yml"))
+        );
+        assert!(
+            taskfile_priority(Path::new("/app/taskfile.yaml"))
+                < taskfile_priority(Path::new("/app/Taskfile.dist.yaml"))
+        );
+        assert_eq!(
+            taskfile_priority(Path::new("/app/other.yml")),
+            TASKFILE_NAMES.len()
+        );
+    }
 
-        let utilities = result.iter().find(|s| s.name == "🛠️ utilities").unwrap();
-        assert_eq!(utilities.category, "🛠️ utilities");
-        assert_eq!(utilities.display_name, "🛠️ Utilities");
+    #[test]
+    fn test_discover_taskfile_variants_share_one_entry() {
+        if !crate::script::task_parser::is_task_available() {
+            return;
+        }
+        let temp_dir = TempDir::new().unwrap();
+        let content = "version: '3'\ntasks:\n  build:\n    cmds: [echo hi]\n";
+        fs::write(temp_dir.path().join("Taskfile.dist.yml"), content).unwrap();
+        fs::write(temp_dir.path().join("Taskfile.yaml"), content).unwrap();
 
-        let system = result.iter().find(|s| s.name == "🖥️ system").unwrap();
-        assert_eq!(system.category, "🖥️ system");
-        assert_eq!(system.display_name, "🖥️ System");
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        let task_files: Vec<_> = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::Task)
+            .collect();
+        assert_eq!(task_files.len(), 1);
+        assert!(task_files[0].path.ends_with("Taskfile.yaml"));
     }
 
     #[test]
-    fn test_discover_scripts_with_underscores_and_hyphens() {
+    fn test_discover_rakefile() {
         let temp_dir = TempDir::new().unwrap();
-
-        // Create scripts with underscores and hyphens
-        fs::write(temp_dir.path().join("example_file.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("example-file.sh"), "#!/bin/bash").unwrap();
-        fs::write(temp_dir.path().join("my_test_script.sh"), "#!/bin/bash").unwrap();
         fs::write(
-            temp_dir.path().join("another-test-script.sh"),
-            "#!/bin/bash",
+            temp_dir.path().join("Rakefile"),
+            "# Ruby tasks\ntask :build do\n  puts 'building'\nend\n",
         )
         .unwrap();
 
         let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 4);
+        // If rake is installed we get 1 ScriptFile with Rakefile type, else 0
+        let rake_files: Vec<_> = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::Rakefile)
+            .collect();
+        assert_eq!(
+            rake_files.len(),
+            usize::from(crate::script::rake_parser::is_rake_available())
+        );
+        if let Some(sf) = rake_files.first() {
+            assert!(sf.display_name.starts_with("💎 "));
+            assert_eq!(category_description(sf), Some("Ruby tasks".to_string()));
+        }
+    }
 
-        // Check underscore handling
-        let example_underscore = result.iter().find(|s| s.name == "example_file").unwrap();
-        assert_eq!(example_underscore.category, "example_file");
-        assert_eq!(example_underscore.display_name, "Example File");
+    #[test]
+    fn test_discover_sbt_build() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("build.sbt"),
+            "// Hello service\nscalaVersion := \"3.3.1\"\n",
+        )
+        .unwrap();
 
-        // Check hyphen handling
-        let example_hyphen = result.iter().find(|s| s.name == "example-file").unwrap();
-        assert_eq!(example_hyphen.category, "example-file");
-        assert_eq!(example_hyphen.display_name, "Example File");
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        // If sbt is installed we get 1 ScriptFile with Sbt type, else 0
+        let sbt_files: Vec<_> = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::Sbt)
+            .collect();
+        assert_eq!(
+            sbt_files.len(),
+            usize::from(crate::script::sbt_parser::is_sbt_available())
+        );
+        if let Some(sf) = sbt_files.first() {
+            assert!(sf.display_name.starts_with("🪜 "));
+            assert_eq!(category_description(sf), Some("Hello service".to_string()));
+        }
+    }
 
-        // Check multiple underscores
-        let my_test = result.iter().find(|s| s.name == "my_test_script").unwrap();
-        assert_eq!(my_test.category, "my_test_script");
-        assert_eq!(my_test.display_name, "My Test Script");
+    #[test]
+    fn test_discover_maven_project_with_wrapper() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("pom.xml"),
+            "<project><description>Billing service</description></project>",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir
+                .path()
+                .join(if cfg!(windows) { "mvnw.cmd" } else { "mvnw" }),
+            "#!/bin/sh\n",
+        )
+        .unwrap();
 
-        // Check multiple hyphens
-        let another_test = result
+        // The wrapper makes the project runnable without a system mvn
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        let maven_files: Vec<_> = result
             .iter()
-            .find(|s| s.name == "another-test-script")
-            .unwrap();
-        assert_eq!(another_test.category, "another-test-script");
-        assert_eq!(another_test.display_name, "Another Test Script");
+            .filter(|s| s.script_type == ScriptType::Maven)
+            .collect();
+        assert_eq!(maven_files.len(), 1);
+        assert!(maven_files[0].display_name.starts_with("🪶 "));
+        assert!(maven_files[0].available);
+        assert_eq!(
+            category_description(maven_files[0]),
+            Some("Billing service".to_string())
+        );
+
+        // `--file pom.xml` agrees with directory discovery
+        let single = discover_single_file(&temp_dir.path().join("pom.xml")).unwrap();
+        assert_eq!(single.script_type, ScriptType::Maven);
+        assert!(single.available);
+        assert!(is_tool_available_for(
+            ScriptType::Maven,
+            &temp_dir.path().join("pom.xml")
+        ));
     }
 
     #[test]
-    fn test_format_display_name() {
-        assert_eq!(format_display_name("example"), "Example");
-        assert_eq!(format_display_name("example_file"), "Example File");
-        assert_eq!(format_display_name("example-file"), "Example File");
-        assert_eq!(format_display_name("my_test_script"), "My Test Script");
+    fn test_discover_compose_file_once_per_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let services = "# Dev stack\nservices:\n  web:\n    image: nginx\n";
+        fs::write(temp_dir.path().join("compose.yaml"), services).unwrap();
+        fs::write(temp_dir.path().join("docker-compose.yml"), services).unwrap();
+
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        // If docker compose is installed we get 1 ScriptFile for the directory, else 0
+        let compose_files: Vec<_> = result
+            .iter()
+            .filter(|s| s.script_type == ScriptType::DockerCompose)
+            .collect();
         assert_eq!(
-            format_display_name("another-test-script"),
-            "Another Test Script"
+            compose_files.len(),
+            usize::from(crate::script::compose_parser::is_docker_compose_available())
         );
-        assert_eq!(format_display_name("🏠 homelab"), "🏠 Homelab");
-        assert_eq!(format_display_name("🛠️ utilities"), "🛠️ Utilities");
-        assert_eq!(format_display_name("UPPERCASE"), "Uppercase");
+        if let Some(sf) = compose_files.first() {
+            assert!(sf.display_name.starts_with("🐳 "));
+            assert_eq!(category_description(sf), Some("Dev stack".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_script_type_icons() {
+        assert_eq!(ScriptType::CargoToml.icon(), "🦀");
+        assert_eq!(ScriptType::PackageJson.icon(), "📦");
+        assert_eq!(ScriptType::Makefile.icon(), "🔨");
+        assert_eq!(ScriptType::Bash.icon(), "🧾");
+        // Types discovered with an emoji in their category name use the same one
+        assert_eq!(ScriptType::NxJson.icon(), "🔷");
+        assert_eq!(ScriptType::DockerCompose.icon(), "🐳");
         assert_eq!(
-            format_display_name("mixed_CASE-example"),
-            "Mixed Case Example"
+            ScriptType::GithubActionsJob.icon(),
+            ScriptType::GithubActions.icon()
         );
-        assert_eq!(format_display_name("service.auth"), "Service Auth");
+    }
+
+    #[test]
+    fn test_script_type_display_from_str_round_trip() {
+        for script_type in ScriptType::ALL {
+            assert_eq!(
+                script_type.to_string().parse::<ScriptType>().unwrap(),
+                script_type
+            );
+            assert_eq!(
+                script_type.label().parse::<ScriptType>().unwrap(),
+                script_type
+            );
+        }
+        assert_eq!("nxjson".parse::<ScriptType>().unwrap(), ScriptType::NxJson);
         assert_eq!(
-            format_display_name("service.api-gateway"),
-            "Service Api Gateway"
+            "NPM".parse::<ScriptType>().unwrap(),
+            ScriptType::PackageJson
         );
+        assert!("Ninja".parse::<ScriptType>().is_err());
     }
 
     #[test]
-    fn test_discover_package_json() {
+    fn test_script_type_serializes_as_its_name() {
+        for script_type in ScriptType::ALL {
+            let json = serde_json::to_string(&script_type).unwrap();
+            assert_eq!(json, format!("\"{}\"", script_type));
+            // Names stored in existing usage files still load
+            let loaded: ScriptType = serde_json::from_str(&json).unwrap();
+            assert_eq!(loaded, script_type);
+        }
+    }
+
+    #[test]
+    fn test_discover_github_workflows_and_jobs() {
         let temp_dir = TempDir::new().unwrap();
-        let package_path = temp_dir.path().join("package.json");
+        let workflows_dir = temp_dir.path().join(".github").join("workflows");
+        fs::create_dir_all(&workflows_dir).unwrap();
+        fs::write(
+            workflows_dir.join("ci.yml"),
+            "name: CI\non: push\njobs:\n  lint:\n    runs-on: ubuntu-latest\n",
+        )
+        .unwrap();
+        fs::write(workflows_dir.join("notes.txt"), "not a workflow").unwrap();
 
-        let content = r#"{
-  "name": "test-project",
-  "scripts": {
-    "test": "jest"
-  }
-}"#;
-        fs::write(&package_path, content).unwrap();
+        let scripts = discover_scripts(temp_dir.path()).unwrap();
 
-        let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].script_type, ScriptType::PackageJson);
-        // Category should be the parent directory name
-        assert!(result[0].category.len() > 0);
+        let workflows: Vec<&ScriptFile> = scripts
+            .iter()
+            .filter(|s| s.script_type == ScriptType::GithubActions)
+            .collect();
+        assert_eq!(workflows.len(), 1);
+        assert_eq!(workflows[0].path, workflows_dir);
+
+        // Job categories only exist when act can run them
+        let jobs: Vec<&ScriptFile> = scripts
+            .iter()
+            .filter(|s| s.script_type == ScriptType::GithubActionsJob)
+            .collect();
+        if crate::script::github_actions_parser::is_act_available() {
+            assert_eq!(jobs.len(), 1);
+            assert_eq!(jobs[0].category, "ci.yml");
+            assert_eq!(jobs[0].display_name, "🐙 CI jobs");
+        } else {
+            assert!(jobs.is_empty());
+        }
     }
 
     #[test]
-    fn test_discover_mixed_scripts() {
+    fn test_discover_cmake_requires_build_dir() {
         let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("CMakeLists.txt"),
+            "# Example app\ncmake_minimum_required(VERSION 3.20)\nproject(app)\n",
+        )
+        .unwrap();
 
-        // Create bash script
-        fs::write(temp_dir.path().join("script.sh"), "#!/bin/bash").unwrap();
+        let cmake_files = |dir: &Path| -> Vec<ScriptFile> {
+            discover_scripts(dir)
+                .unwrap()
+                .into_iter()
+                .filter(|s| s.script_type == ScriptType::CMake)
+                .collect()
+        };
 
-        // Create package.json
-        let content = r#"{"name": "test", "scripts": {"test": "jest"}}"#;
-        fs::write(temp_dir.path().join("package.json"), content).unwrap();
+        // Not configured yet: nothing to list
+        assert!(cmake_files(temp_dir.path()).is_empty());
 
-        let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 2);
+        fs::create_dir(temp_dir.path().join("build")).unwrap();
+        let found = cmake_files(temp_dir.path());
+        assert_eq!(
+            found.len(),
+            usize::from(crate::script::cmake_parser::is_cmake_available())
+        );
+        if let Some(sf) = found.first() {
+            assert!(sf.display_name.starts_with("🔺 "));
+            assert_eq!(category_description(sf), Some("Example app".to_string()));
+        }
+    }
 
-        // Should find both types
-        let bash_count = result
-            .iter()
-            .filter(|s| s.script_type == ScriptType::Bash)
-            .count();
-        let npm_count = result
-            .iter()
-            .filter(|s| s.script_type == ScriptType::PackageJson)
-            .count();
+    #[test]
+    fn test_discover_meson_requires_build_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("meson.build"),
+            "# Example app\nproject('app', 'c')\nexecutable('app', 'main.c')\n",
+        )
+        .unwrap();
 
-        assert_eq!(bash_count, 1);
-        assert_eq!(npm_count, 1);
+        let meson_files = |dir: &Path| -> Vec<ScriptFile> {
+            discover_scripts(dir)
+                .unwrap()
+                .into_iter()
+                .filter(|s| s.script_type == ScriptType::Meson)
+                .collect()
+        };
+
+        // Not set up yet: nothing to list
+        assert!(meson_files(temp_dir.path()).is_empty());
+
+        fs::create_dir_all(temp_dir.path().join("builddir/meson-info")).unwrap();
+        let found = meson_files(temp_dir.path());
+        assert_eq!(
+            found.len(),
+            usize::from(crate::script::meson_parser::is_meson_available())
+        );
+        if let Some(sf) = found.first() {
+            assert!(sf.display_name.starts_with("🧱 "));
+            assert_eq!(category_description(sf), Some("Example app".to_string()));
+        }
     }
 
     #[test]
-    fn test_discover_devbox_json() {
+    fn test_discover_dagger_module() {
         let temp_dir = TempDir::new().unwrap();
-        let devbox_path = temp_dir.path().join("devbox.json");
+        fs::write(
+            temp_dir.path().join("dagger.json"),
+            r#"{"name": "app", "sdk": "go", "description": "CI pipelines"}"#,
+        )
+        .unwrap();
 
-        let content = r#"{
-  "shell": {
-    "scripts": {
-      "test": ["cargo test"]
+        let found: Vec<ScriptFile> = discover_scripts(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.script_type == ScriptType::Dagger)
+            .collect();
+        assert_eq!(
+            found.len(),
+            usize::from(crate::script::dagger_parser::is_dagger_available())
+        );
+        if let Some(sf) = found.first() {
+            assert!(sf.display_name.starts_with("🗡️ "));
+            assert_eq!(category_description(sf), Some("CI pipelines".to_string()));
+        }
     }
-  }
-}"#;
-        fs::write(&devbox_path, content).unwrap();
 
-        let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].script_type, ScriptType::DevboxJson);
-        // Category should be the parent directory name
-        assert!(result[0].category.len() > 0);
+    #[test]
+    fn test_discover_mix_project() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("mix.exs"),
+            "# Storefront backend\ndefmodule Shop.MixProject do\n  use Mix.Project\nend\n",
+        )
+        .unwrap();
+
+        let found: Vec<ScriptFile> = discover_scripts(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.script_type == ScriptType::Mix)
+            .collect();
+        assert_eq!(
+            found.len(),
+            usize::from(crate::script::mix_parser::is_mix_available())
+        );
+        if let Some(sf) = found.first() {
+            assert!(sf.display_name.starts_with("💧 "));
+            assert_eq!(
+                category_description(sf),
+                Some("Storefront backend".to_string())
+            );
+        }
     }
 
     #[test]
-    fn test_discover_all_script_types() {
+    fn test_discover_git_hook_configs() {
         let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("lefthook.yml"),
+            "# Git hooks\npre-commit:\n  commands:\n    lint:\n      run: make lint\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join(".lefthook.yml"),
+            "pre-push:\n  commands:\n    test:\n      run: make test\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join(".pre-commit-config.yaml"),
+            "repos:\n  - repo: local\n    hooks:\n      - id: fmt\n",
+        )
+        .unwrap();
 
-        // Create bash script
-        fs::write(temp_dir.path().join("script.sh"), "#!/bin/bash").unwrap();
-
-        // Create package.json
-        let npm_content = r#"{"name": "test", "scripts": {"test": "jest"}}"#;
-        fs::write(temp_dir.path().join("package.json"), npm_content).unwrap();
+        let scripts = discover_scripts(temp_dir.path()).unwrap();
+        let count = |script_type| {
+            scripts
+                .iter()
+                .filter(|s| s.script_type == script_type)
+                .count()
+        };
 
-        // Create devbox.json
-        let devbox_content = r#"{
-  "shell": {
-    "scripts": {
-      "build": ["cargo build"]
+        // One Lefthook config per directory
+        assert_eq!(
+            count(ScriptType::Lefthook),
+            usize::from(crate::script::lefthook_parser::is_lefthook_available())
+        );
+        assert_eq!(
+            count(ScriptType::PreCommit),
+            usize::from(crate::script::precommit_parser::is_pre_commit_available())
+        );
+        if let Some(sf) = scripts
+            .iter()
+            .find(|s| s.script_type == ScriptType::PreCommit)
+        {
+            assert!(sf.display_name.starts_with("🪝 "));
+        }
     }
-  }
-}"#;
-        fs::write(temp_dir.path().join("devbox.json"), devbox_content).unwrap();
 
-        let result = discover_scripts(temp_dir.path()).unwrap();
-        assert_eq!(result.len(), 3);
+    #[test]
+    fn test_discover_procfiles() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Procfile"),
+            "# Production processes\nweb: bin/server\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("Procfile.dev"), "web: bin/dev\n").unwrap();
 
-        // Should find all three types
-        let bash_count = result
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        let mut procfiles: Vec<_> = result
             .iter()
-            .filter(|s| s.script_type == ScriptType::Bash)
-            .count();
-        let npm_count = result
+            .filter(|s| s.script_type == ScriptType::Procfile)
+            .collect();
+        procfiles.sort_by(|a, b| a.category.cmp(&b.category));
+        assert_eq!(procfiles.len(), 2);
+
+        let dir = temp_dir.path().file_name().unwrap().to_str().unwrap();
+        assert_eq!(procfiles[0].category, dir);
+        assert_eq!(procfiles[1].category, format!("{dir}-dev"));
+        assert!(procfiles[0].display_name.starts_with("🏭 "));
+        assert_eq!(
+            category_description(procfiles[0]),
+            Some("Production processes".to_string())
+        );
+    }
+
+    #[test]
+    fn test_discover_mise_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().file_name().unwrap().to_str().unwrap();
+
+        // .config/mise/config.toml alone is picked up from the project root
+        let config_dir = temp_dir.path().join(".config").join("mise");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("config.toml"),
+            "[tasks.build]\nrun = \"cargo build\"\n",
+        )
+        .unwrap();
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        let mise: Vec<_> = result
             .iter()
-            .filter(|s| s.script_type == ScriptType::PackageJson)
-            .count();
-        let devbox_count = result
+            .filter(|s| s.script_type == ScriptType::Mise)
+            .collect();
+        assert_eq!(mise.len(), 1);
+        assert_eq!(mise[0].path, config_dir.join("config.toml"));
+        assert_eq!(mise[0].category, dir);
+        assert!(mise[0].display_name.starts_with("🍳 "));
+
+        // mise.toml and .mise.toml in the same directory share one entry
+        fs::write(temp_dir.path().join("mise.toml"), "# Dev tasks\n[tasks]\n").unwrap();
+        fs::write(temp_dir.path().join(".mise.toml"), "[tasks]\n").unwrap();
+        let result = discover_scripts(temp_dir.path()).unwrap();
+        let mise: Vec<_> = result
             .iter()
-            .filter(|s| s.script_type == ScriptType::DevboxJson)
-            .count();
+            .filter(|s| s.script_type == ScriptType::Mise)
+            .collect();
+        assert_eq!(mise.len(), 1);
+        assert_eq!(mise[0].path.parent(), Some(temp_dir.path()));
 
-        assert_eq!(bash_count, 1);
-        assert_eq!(npm_count, 1);
-        assert_eq!(devbox_count, 1);
+        let single = discover_single_file(&config_dir.join("config.toml")).unwrap();
+        assert_eq!(single.script_type, ScriptType::Mise);
+        assert_eq!(single.category, dir);
     }
 
     #[test]
-    fn test_discover_taskfile() {
+    fn test_discover_ansible_playbooks() {
         let temp_dir = TempDir::new().unwrap();
-        let taskfile_path = temp_dir.path().join("Taskfile.yml");
-
-        let content = r#"version: '3'
-tasks:
-  default:
-    desc: Default task
-    cmds: [echo hello]
-"#;
-        fs::write(&taskfile_path, content).unwrap();
+        fs::write(temp_dir.path().join("site.yml"), "- hosts: all\n").unwrap();
+        fs::write(temp_dir.path().join("db.yml"), "- hosts: db\n").unwrap();
+        fs::write(temp_dir.path().join("vars.yml"), "port: 80\n").unwrap();
+        let playbooks = temp_dir.path().join("playbooks");
+        fs::create_dir(&playbooks).unwrap();
+        fs::write(
+            playbooks.join("deploy.yaml"),
+            "- name: Deploy\n  hosts: app\n",
+        )
+        .unwrap();
 
-        let result = discover_scripts(temp_dir.path()).unwrap();
-        // If task binary is installed we get 1 ScriptFile with Task type, else 0
-        let task_files: Vec<_> = result
+        let result = discover_scripts_shallow(temp_dir.path()).unwrap();
+        let ansible: Vec<_> = result
             .iter()
-            .filter(|s| s.script_type == ScriptType::Task)
+            .filter(|s| s.script_type == ScriptType::Ansible)
             .collect();
-        assert!(
-            task_files.len() <= 1,
-            "should have at most one Task script file"
-        );
-        if let Some(sf) = task_files.first() {
-            assert_eq!(sf.script_type, ScriptType::Task);
-            assert!(sf.path.ends_with("Taskfile.yml"));
+
+        if !crate::script::ansible_parser::is_ansible_available() {
+            assert!(ansible.is_empty());
+            return;
         }
+        // One entry per directory of playbooks, `playbooks/` included
+        assert_eq!(ansible.len(), 2);
+        assert_eq!(ansible[0].path, temp_dir.path());
+        assert_eq!(ansible[1].path, playbooks);
+        assert_eq!(ansible[1].display_name, "🅰️ Playbooks");
+
+        let single = discover_single_file(&temp_dir.path().join("site.yml")).unwrap();
+        assert_eq!(single.script_type, ScriptType::Ansible);
+        assert!(discover_single_file(&temp_dir.path().join("vars.yml")).is_err());
     }
 
     #[test]
@@ -1193,20 +3071,89 @@ tasks:
         fs::write(&makefile_path, content).unwrap();
 
         let result = discover_scripts(temp_dir.path()).unwrap();
-        // If make binary is installed we get 1 ScriptFile with Makefile type, else 0
+        // Listed whether or not make is installed; marked unavailable without it
         let make_files: Vec<_> = result
             .iter()
             .filter(|s| s.script_type == ScriptType::Makefile)
             .collect();
-        assert!(
-            make_files.len() <= 1,
-            "should have at most one Makefile script file"
+        assert_eq!(make_files.len(), 1);
+        let sf = make_files[0];
+        assert!(sf.path.ends_with("Makefile"));
+        assert!(sf.display_name.contains("🔨"));
+        assert_eq!(
+            sf.available,
+            crate::script::makefile_parser::is_make_available()
         );
-        if let Some(sf) = make_files.first() {
-            assert_eq!(sf.script_type, ScriptType::Makefile);
-            assert!(sf.path.ends_with("Makefile"));
-            assert!(sf.display_name.contains("🔨"));
+    }
+
+    #[test]
+    fn test_tool_availability_by_script_type() {
+        // Types that need no particular tool are always available
+        for script_type in [ScriptType::Bash, ScriptType::Procfile] {
+            assert!(is_tool_available(script_type));
+            assert_eq!(script_type.binary(), None);
         }
+        assert_eq!(ScriptType::Makefile.binary(), Some("make"));
+        assert_eq!(ScriptType::DenoJson.binary(), Some("deno"));
+        assert_eq!(
+            is_tool_available(ScriptType::ComposerJson),
+            binaries::is_installed("composer")
+        );
+        assert_eq!(
+            is_tool_available(ScriptType::Just),
+            crate::script::just_parser::is_just_available()
+        );
+    }
+
+    #[test]
+    fn test_package_manager_availability_follows_the_lockfile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"scripts": {"build": "tsc"}}"#,
+        )
+        .unwrap();
+
+        let package_json = |dir: &Path| {
+            discover_scripts(dir)
+                .unwrap()
+                .into_iter()
+                .find(|s| s.script_type == ScriptType::PackageJson)
+                .unwrap()
+        };
+
+        let sf = package_json(temp_dir.path());
+        assert_eq!(sf.binary(), Some("npm"));
+        assert_eq!(sf.available, binaries::is_installed("npm"));
+
+        // A bun lockfile runs the scripts through bun, which must be installed
+        fs::write(temp_dir.path().join("bun.lock"), "{}").unwrap();
+        let sf = package_json(temp_dir.path());
+        assert_eq!(sf.binary(), Some("bun"));
+        assert_eq!(sf.available, binaries::is_installed("bun"));
+
+        let single = discover_single_file(&temp_dir.path().join("package.json")).unwrap();
+        assert_eq!(single.available, sf.available);
+    }
+
+    #[test]
+    fn test_python_runner_availability() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject = temp_dir.path().join("pyproject.toml");
+        assert_eq!(
+            is_tool_available_for(ScriptType::PyProject, &pyproject),
+            binaries::is_installed("python")
+        );
+
+        fs::write(temp_dir.path().join("poetry.lock"), "").unwrap();
+        assert_eq!(
+            file_binary(ScriptType::PyProject, &pyproject),
+            Some("poetry")
+        );
+        assert_eq!(
+            is_tool_available_for(ScriptType::PyProject, &pyproject),
+            binaries::is_installed("poetry")
+        );
     }
 
     // Tests for discover_single_file
@@ -1393,5 +3340,50 @@ tasks:
             gradle_files.len() <= 1,
             "should have at most one Gradle script file per directory"
         );
+        // The entry points at the build file itself, like other script types
+        assert!(gradle_files
+            .iter()
+            .all(|s| s.path.is_file() && s.path.parent() == Some(temp_dir.path())));
+    }
+
+    #[test]
+    fn test_category_description_from_leading_comment() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("deploy.sh");
+        fs::write(
+            &script_path,
+            "#!/bin/bash\n# Deployment helpers\n# for staging and prod\n\n# Not this\nfoo() {}\n",
+        )
+        .unwrap();
+
+        let sf = discover_single_file(&script_path).unwrap();
+        assert_eq!(
+            category_description(&sf).as_deref(),
+            Some("Deployment helpers\nfor staging and prod")
+        );
+    }
+
+    #[test]
+    fn test_category_description_skips_annotations() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("tools.sh");
+        fs::write(&script_path, "#!/bin/bash\n# @emoji 🔧\nfoo() {}\n").unwrap();
+
+        let sf = discover_single_file(&script_path).unwrap();
+        assert_eq!(category_description(&sf), None);
+    }
+
+    #[test]
+    fn test_category_description_from_package_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let pkg_path = temp_dir.path().join("package.json");
+        fs::write(
+            &pkg_path,
+            r#"{"name": "web", "description": "Frontend app", "scripts": {"dev": "vite"}}"#,
+        )
+        .unwrap();
+
+        let sf = discover_single_file(&pkg_path).unwrap();
+        assert_eq!(category_description(&sf).as_deref(), Some("Frontend app"));
     }
 }
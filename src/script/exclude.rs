@@ -0,0 +1,143 @@
+//! # Exclude Patterns
+//!
+//! Glob patterns that keep files and directories out of discovery, from
+//! `--exclude` flags and the `exclude` list of the project config.
+//!
+//! ## Syntax
+//!
+//! Patterns are [`globset`] globs following `.gitignore` conventions:
+//!
+//! - `*` matches any run of characters except `/`, `?` a single one
+//! - `[abc]`, `[a-z]` and `[!a-z]` match one character of (or not of) a set
+//! - `**` as a whole path segment matches any number of directories
+//! - `\` makes the next character literal
+//!
+//! A pattern without a `/` matches a file or directory name at any depth
+//! (`vendor`, `*.generated.sh`). A pattern with one is matched against the
+//! path relative to the project root (`tools/legacy`, `**/fixtures/*.sh`).
+//! A leading `./` and a trailing `/` are ignored.
+//!
+//! A matched directory excludes everything below it, so discovery does not
+//! descend into it at all.
+//!
+//! ## Key Types
+//!
+//! - [`ExcludeSet`] - Compiled patterns, checked with [`ExcludeSet::is_excluded`]
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::{Component, Path, PathBuf};
+
+/// A set of exclude patterns
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeSet {
+    /// Patterns without a `/`, matched against each name of a path
+    names: GlobSet,
+    /// Patterns with a `/`, matched against the path and its parent directories
+    paths: GlobSet,
+    /// Whether any pattern was given
+    has_patterns: bool,
+}
+
+impl ExcludeSet {
+    /// Compile `patterns`, failing on an empty or malformed one.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self> {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            let trimmed = pattern.trim();
+            let trimmed = trimmed.strip_prefix("./").unwrap_or(trimmed);
+            let trimmed = trimmed.trim_end_matches('/');
+            if trimmed.is_empty() {
+                anyhow::bail!("Empty exclude pattern: {:?}", pattern);
+            }
+
+            let glob = GlobBuilder::new(trimmed)
+                .literal_separator(true)
+                .backslash_escape(true)
+                .build()
+                .with_context(|| format!("Invalid exclude pattern {:?}", pattern))?;
+            if trimmed.contains('/') {
+                paths.add(glob);
+            } else {
+                names.add(glob);
+            }
+        }
+
+        Ok(Self {
+            names: names
+                .build()
+                .context("Failed to compile exclude patterns")?,
+            paths: paths
+                .build()
+                .context("Failed to compile exclude patterns")?,
+            has_patterns: !patterns.is_empty(),
+        })
+    }
+
+    /// Whether the set has no patterns
+    pub fn is_empty(&self) -> bool {
+        !self.has_patterns
+    }
+
+    /// Whether `relative` (a path relative to the project root) or one of the
+    /// directories it is in matches a pattern.
+    pub fn is_excluded(&self, relative: &Path) -> bool {
+        if !self.has_patterns {
+            return false;
+        }
+
+        let mut prefix = PathBuf::new();
+        for component in relative.components() {
+            let Component::Normal(name) = component else {
+                continue;
+            };
+            prefix.push(name);
+            if self.names.is_match(name) || self.paths.is_match(&prefix) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn excluded(patterns: &[&str], path: &str) -> bool {
+        ExcludeSet::new(patterns)
+            .unwrap()
+            .is_excluded(Path::new(path))
+    }
+
+    #[test]
+    fn test_name_patterns_match_at_any_depth() {
+        assert!(excluded(&["vendor"], "vendor"));
+        assert!(excluded(&["vendor"], "scripts/vendor/build.sh"));
+        assert!(!excluded(&["vendor"], "scripts/vendored/build.sh"));
+        assert!(excluded(&["*.gen.sh"], "scripts/db/migrate.gen.sh"));
+    }
+
+    #[test]
+    fn test_path_patterns_are_anchored_to_the_root() {
+        assert!(excluded(&["tools/legacy"], "tools/legacy/deploy.sh"));
+        assert!(!excluded(&["tools/legacy"], "app/tools/legacy/deploy.sh"));
+        assert!(excluded(&["./tools/legacy/"], "tools/legacy/deploy.sh"));
+        assert!(!excluded(&["scripts/*.sh"], "scripts/sub/a.sh"));
+        assert!(excluded(&["**/fixtures"], "a/b/fixtures/c.sh"));
+    }
+
+    #[test]
+    fn test_empty_and_invalid_patterns() {
+        let set = ExcludeSet::default();
+        assert!(set.is_empty());
+        assert!(!set.is_excluded(Path::new("vendor/a.sh")));
+
+        assert!(ExcludeSet::new(&[""]).is_err());
+        assert!(ExcludeSet::new(&["./"]).is_err());
+        assert!(ExcludeSet::new(&["scripts/[a-"]).is_err());
+    }
+}
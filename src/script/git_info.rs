@@ -0,0 +1,130 @@
+//! # Git Provenance
+//!
+//! Looks up the last commit that touched a script file, so the TUI can show
+//! who changed a command and when.
+//!
+//! ## Overview
+//!
+//! [`last_commit_for`] runs a single `git log` for a path:
+//!
+//! ```bash
+//! git log -1 --format=%h%x1f%an%x1f%ar -- <file>
+//! ```
+//!
+//! The lookup spawns a subprocess, so callers should cache results per path
+//! and only enable it when asked to (see `show_git_info` in the user config).
+//!
+//! ## Availability Caching
+//!
+//! The `git` binary availability is cached using [`OnceLock`] to avoid
+//! repeated process spawning.
+
+use crate::script::binaries;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+/// Cache for git availability check (checked once per process)
+static GIT_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// The most recent commit that touched a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitCommitInfo {
+    /// Abbreviated commit hash
+    pub hash: String,
+    /// Commit author name
+    pub author: String,
+    /// Human-readable relative date (e.g. "3 days ago")
+    pub relative_date: String,
+}
+
+/// Check if the `git` binary is available.
+pub fn is_git_available() -> bool {
+    *GIT_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("git"))
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Find the last commit that touched `path` (a file or a directory).
+///
+/// Returns `None` when git is unavailable, the path is not inside a git
+/// repository, or the path has no history yet.
+pub fn last_commit_for(path: &Path) -> Option<GitCommitInfo> {
+    if !is_git_available() {
+        return None;
+    }
+
+    let (dir, target) = if path.is_dir() {
+        (path, Path::new("."))
+    } else {
+        (path.parent()?, Path::new(path.file_name()?))
+    };
+
+    let output = Command::new(binaries::program("git"))
+        .args(["log", "-1", "--format=%h%x1f%an%x1f%ar", "--"])
+        .arg(target)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_git_log_line(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse a `%h%x1f%an%x1f%ar` line produced by `git log`.
+fn parse_git_log_line(line: &str) -> Option<GitCommitInfo> {
+    let mut parts = line.trim().splitn(3, '\u{1f}');
+    let hash = parts.next()?.trim();
+    let author = parts.next()?.trim();
+    let relative_date = parts.next()?.trim();
+
+    if hash.is_empty() {
+        return None;
+    }
+
+    Some(GitCommitInfo {
+        hash: hash.to_string(),
+        author: author.to_string(),
+        relative_date: relative_date.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_log_line() {
+        let info = parse_git_log_line("abc1234\u{1f}Jane Doe\u{1f}3 days ago\n").unwrap();
+        assert_eq!(info.hash, "abc1234");
+        assert_eq!(info.author, "Jane Doe");
+        assert_eq!(info.relative_date, "3 days ago");
+    }
+
+    #[test]
+    fn test_parse_git_log_line_empty() {
+        assert_eq!(parse_git_log_line(""), None);
+        assert_eq!(parse_git_log_line("\n"), None);
+    }
+
+    #[test]
+    fn test_last_commit_for_untracked_path() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("build.sh");
+        std::fs::write(&script_path, "#!/bin/bash\n").unwrap();
+
+        assert_eq!(last_commit_for(&script_path), None);
+    }
+}
@@ -18,6 +18,7 @@
 //! - [`GithubWorkflow`] — Represents a single workflow file with display metadata
 //! - [`is_gh_available`] — Checks if the `gh` CLI is installed
 //! - [`is_act_available`] — Checks if the `act` CLI is installed (nektos/act)
+//! - [`extract_jobs`] — Job ids and names of a workflow, also used for per-job entries
 //! - [`list_workflows`] — Main function to list workflows from `.github/workflows/`
 //!
 //! ## Execution
@@ -38,7 +39,9 @@ use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
+use crate::script::utils::{indent_of, unquote};
 
 /// Cache for act CLI availability check (checked once per process)
 static ACT_AVAILABLE: OnceLock<bool> = OnceLock::new();
@@ -48,7 +51,7 @@ static ACT_AVAILABLE: OnceLock<bool> = OnceLock::new();
 /// `act` allows running GitHub Actions workflows locally using Docker.
 pub fn is_act_available() -> bool {
     *ACT_AVAILABLE.get_or_init(|| {
-        Command::new("act")
+        Command::new(binaries::program("act"))
             .arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -64,7 +67,7 @@ static GH_AVAILABLE: OnceLock<bool> = OnceLock::new();
 /// Check if the `gh` CLI is available.
 pub fn is_gh_available() -> bool {
     *GH_AVAILABLE.get_or_init(|| {
-        Command::new("gh")
+        Command::new(binaries::program("gh"))
             .arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -138,7 +141,10 @@ pub fn parse_workflow_file(path: &Path, category: &str) -> Result<GithubWorkflow
     let triggers = extract_triggers(&content);
 
     // Extract job IDs from the `jobs:` block
-    let jobs = extract_jobs(&content);
+    let jobs: Vec<String> = extract_jobs(&content)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
 
     let display_name = format!("🐙 {}", format_display_name(&workflow_name));
 
@@ -158,7 +164,7 @@ pub fn parse_workflow_file(path: &Path, category: &str) -> Result<GithubWorkflow
 }
 
 /// Extract the top-level `name:` value from workflow YAML content.
-fn extract_workflow_name(content: &str) -> Option<String> {
+pub fn extract_workflow_name(content: &str) -> Option<String> {
     for line in content.lines() {
         // Match `name: <value>` at column 0 (not indented)
         if let Some(stripped) = line.strip_prefix("name:") {
@@ -244,33 +250,54 @@ fn extract_triggers(content: &str) -> Vec<String> {
     triggers
 }
 
-/// Extract job IDs from the `jobs:` section of workflow YAML.
-fn extract_jobs(content: &str) -> Vec<String> {
-    let mut jobs = Vec::new();
+/// Extract the jobs of a workflow as `(job id, job name)` pairs, in file order.
+///
+/// Job ids are the keys at the first indentation level of the top-level
+/// `jobs:` block; a job's name is its `name:` one level further in.
+pub fn extract_jobs(content: &str) -> Vec<(String, Option<String>)> {
+    let mut jobs: Vec<(String, Option<String>)> = Vec::new();
     let mut in_jobs_block = false;
+    let mut job_indent: Option<usize> = None;
+    let mut field_indent: Option<usize> = None;
 
     for line in content.lines() {
-        if line.starts_with("jobs:") {
-            in_jobs_block = true;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
             continue;
         }
 
+        let indent = indent_of(line);
+        if indent == 0 {
+            in_jobs_block = trimmed
+                .strip_prefix("jobs:")
+                .is_some_and(|rest| rest.trim().is_empty() || rest.trim().starts_with('#'));
+            continue;
+        }
         if !in_jobs_block {
             continue;
         }
 
-        // Job IDs are at exactly 2-space indent
-        if line.starts_with("  ") && !line.starts_with("   ") {
-            let key = line.trim_start().trim_end_matches(':').trim();
-            if !key.is_empty() && !key.starts_with('#') {
-                jobs.push(key.to_string());
+        let job_level = *job_indent.get_or_insert(indent);
+        if indent == job_level {
+            if let Some(id) = trimmed.strip_suffix(':') {
+                jobs.push((unquote(id).to_string(), None));
+                field_indent = None;
             }
             continue;
         }
+        if indent < job_level {
+            continue;
+        }
 
-        // Any non-indented, non-empty line ends the jobs block
-        if !line.starts_with(' ') && !line.starts_with('\t') && !line.trim().is_empty() {
-            in_jobs_block = false;
+        // First-level fields of the current job
+        if *field_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+        if let (Some(value), Some((_, name))) = (trimmed.strip_prefix("name:"), jobs.last_mut()) {
+            let value = unquote(value);
+            if !value.is_empty() {
+                *name = Some(value.to_string());
+            }
         }
     }
 
@@ -296,6 +323,27 @@ fn build_description(triggers: &[String], jobs: &[String]) -> String {
     }
 }
 
+/// The workflow files (`*.yml`, `*.yaml`) in `workflows_dir`, sorted by name.
+/// An unreadable directory yields none.
+pub fn workflow_files(workflows_dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut files: Vec<std::path::PathBuf> = std::fs::read_dir(workflows_dir)
+        .map(|rd| {
+            rd.filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|p| {
+                    p.is_file()
+                        && matches!(
+                            p.extension().and_then(|x| x.to_str()),
+                            Some("yml") | Some("yaml")
+                        )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
 /// Discover all workflow files under `workflows_dir` (`.github/workflows/`)
 /// and return a list of [`GithubWorkflow`] items.
 pub fn list_workflows(workflows_dir: &Path, category: &str) -> Result<Vec<GithubWorkflow>> {
@@ -406,7 +454,59 @@ mod tests {
         let content =
             "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n  test:\n    runs-on: ubuntu-latest\n";
         let jobs = extract_jobs(content);
-        assert_eq!(jobs, vec!["build", "test"]);
+        assert_eq!(
+            jobs,
+            vec![("build".to_string(), None), ("test".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn test_extract_jobs_names() {
+        let content = "\
+name: CI
+on: push
+
+env:
+  name: not-a-job
+
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Run linter
+        run: make lint
+
+  # Unit and integration tests
+  test:
+    name: \"Unit tests\"
+    strategy:
+      matrix:
+        name: [a, b]
+  build-release:
+    needs: [lint, test]
+    name: Release build
+";
+        assert_eq!(
+            extract_jobs(content),
+            vec![
+                ("lint".to_string(), None),
+                ("test".to_string(), Some("Unit tests".to_string())),
+                (
+                    "build-release".to_string(),
+                    Some("Release build".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_jobs_four_space_indent() {
+        let content = "on: push\njobs: # all of them\n    build:\n        name: Build\n        steps:\n            - run: make\n    deploy:\n        runs-on: ubuntu-latest\n";
+        let ids: Vec<String> = extract_jobs(content)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec!["build", "deploy"]);
     }
 
     #[test]
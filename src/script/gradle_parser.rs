@@ -12,17 +12,19 @@
 //! - [`GradleTask`] - Represents a Gradle task with display metadata for the TUI
 //! - [`is_gradle_available`] - Checks if `gradle` CLI or Gradle wrapper is available
 //! - [`list_tasks`] - Main function to list tasks from a Gradle project
+//! - [`collect_category_display_names`] - Display names for the per-group categories
 //!
 //! ## CLI Integration
 //!
 //! The parser runs:
 //! ```bash
-//! ./gradlew tasks --all -q
+//! ./gradlew tasks --all --console=plain -q
 //! # or
-//! gradle tasks --all -q
+//! gradle tasks --all --console=plain -q
 //! ```
 //!
-//! And parses the output to extract tasks grouped by category.
+//! And parses the output to extract tasks, with one category per task group
+//! (`gradle:<project>:<group>`). Ungrouped (`Other`) tasks and rules are hidden.
 //!
 //! ## Execution
 //!
@@ -43,6 +45,7 @@ use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
 
 static GRADLE_AVAILABLE: OnceLock<bool> = OnceLock::new();
@@ -76,7 +79,7 @@ fn get_gradle_wrapper(project_dir: &Path) -> Option<std::path::PathBuf> {
 pub fn is_gradle_available() -> bool {
     *GRADLE_AVAILABLE.get_or_init(|| {
         // Check if system gradle is installed
-        if Command::new("gradle")
+        if Command::new(binaries::program("gradle"))
             .arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -104,85 +107,84 @@ pub fn get_gradle_command(project_dir: &Path) -> Option<String> {
     None
 }
 
-fn parse_gradle_tasks_output(output: &str, category: &str) -> Result<Vec<GradleTask>> {
-    let mut tasks = Vec::with_capacity(20);
-    let mut current_group: Option<String> = None;
+/// Task groups that only hold internal or pattern-based tasks, hidden from the TUI
+const HIDDEN_GROUPS: &[&str] = &["Other", "Rules"];
+
+/// Category key for the tasks of `group` in the Gradle project `project`
+/// (e.g. `"gradle:my-app:build-setup"`)
+pub fn group_category(project: &str, group: &str) -> String {
+    format!(
+        "gradle:{}:{}",
+        project,
+        group.to_lowercase().replace(' ', "-")
+    )
+}
 
-    for line in output.lines() {
-        let line = line.trim();
+/// Whether `line` underlines a section header (`-----------`)
+fn is_underline(line: &str) -> bool {
+    !line.is_empty() && line.chars().all(|c| c == '-')
+}
 
-        if line.is_empty() {
-            continue;
-        }
+/// Parse the output of `gradle tasks --all` into tasks, one category per task group.
+///
+/// Each group is a header underlined with dashes, followed by lines like
+/// `build - Assembles and tests this project.` (the description is optional).
+/// Tasks in [`HIDDEN_GROUPS`] are skipped; they are mostly compile and
+/// processing steps that are not meant to be run directly.
+fn parse_gradle_tasks_output(output: &str, project: &str) -> Result<Vec<GradleTask>> {
+    let mut tasks = Vec::with_capacity(20);
+    let mut current_group: Option<String> = None;
+    let lines: Vec<&str> = output.lines().map(str::trim).collect();
 
-        if line.starts_with("------------------------------------------------------------") {
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() || is_underline(line) {
             continue;
         }
 
-        if line.starts_with("Tasks runnable from") {
+        if lines.get(i + 1).is_some_and(|next| is_underline(next)) {
+            // The banner (`Tasks runnable from root project 'x'`) is underlined too
+            if !line.starts_with("Tasks runnable from") {
+                current_group = Some(line.trim_end_matches(" tasks").trim().to_string());
+            }
             continue;
         }
 
-        if line.starts_with("Other tasks") {
-            break;
-        }
-
-        if line.ends_with(" tasks") && !line.contains('-') {
-            current_group = Some(line.trim_end_matches(" tasks").trim().to_string());
+        let Some(ref group) = current_group else {
             continue;
-        }
-
-        if line.starts_with("Build tasks") {
-            current_group = Some("Build".to_string());
+        };
+        if HIDDEN_GROUPS.contains(&group.as_str()) {
             continue;
         }
 
-        if line.starts_with('-') || line.starts_with("Other") {
+        let (name, description) = match line.split_once(" - ") {
+            Some((name, description)) => (name.trim(), description.trim()),
+            None => (*line, ""),
+        };
+        // Anything else with spaces is prose (`To see all tasks ...`), not a task
+        if name.is_empty() || name.contains(char::is_whitespace) {
             continue;
         }
 
-        if let Some(dash_pos) = line.find(" - ") {
-            let name = line[..dash_pos].trim().to_string();
-            let description = line[dash_pos + 3..].trim().to_string();
-
-            if name.is_empty() {
-                continue;
-            }
-
-            let display_name = format_display_name(&name);
-
-            tasks.push(GradleTask {
-                name,
-                display_name,
-                category: category.to_string(),
-                group: current_group.clone(),
-                description,
-                emoji: None,
-                ignored: false,
-            });
-        }
+        tasks.push(GradleTask {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: group_category(project, group),
+            group: Some(group.clone()),
+            description: description.to_string(),
+            emoji: None,
+            ignored: false,
+        });
     }
 
-    tasks.sort_by(|a, b| {
-        let group_cmp = a.group.cmp(&b.group);
-        if group_cmp == std::cmp::Ordering::Equal {
-            a.name.cmp(&b.name)
-        } else {
-            group_cmp
-        }
-    });
+    tasks.sort_by(|a, b| a.group.cmp(&b.group).then_with(|| a.name.cmp(&b.name)));
 
     Ok(tasks)
 }
 
-pub fn list_tasks(project_dir: &Path, category: &str) -> Result<Vec<GradleTask>> {
-    let gradle_cmd = get_gradle_command(project_dir).context(
-        "Neither Gradle wrapper (gradlew) nor system Gradle is available. \
-         Please ensure Gradle is installed or a Gradle wrapper is present in the project.",
-    )?;
-
-    let output = Command::new(&gradle_cmd)
-        .args(["tasks", "--all", "-q"])
+/// Run `<gradle> tasks --all` in `project_dir` and return its output
+fn run_tasks_command(gradle_cmd: &str, project_dir: &Path) -> Result<String> {
+    let output = Command::new(binaries::program(gradle_cmd))
+        .args(["tasks", "--all", "--console=plain", "-q"])
         .current_dir(project_dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -198,8 +200,46 @@ pub fn list_tasks(project_dir: &Path, category: &str) -> Result<Vec<GradleTask>>
         );
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    parse_gradle_tasks_output(&output_str, category)
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// List the tasks of the Gradle project in `project_dir`.
+///
+/// Uses the Gradle wrapper when present, falling back to the system `gradle`
+/// if the wrapper cannot run. `project` names the project in category keys.
+pub fn list_tasks(project_dir: &Path, project: &str) -> Result<Vec<GradleTask>> {
+    let gradle_cmd = get_gradle_command(project_dir).context(
+        "Neither Gradle wrapper (gradlew) nor system Gradle is available. \
+         Please ensure Gradle is installed or a Gradle wrapper is present in the project.",
+    )?;
+
+    let output = match run_tasks_command(&gradle_cmd, project_dir) {
+        Ok(output) => output,
+        Err(e) if gradle_cmd != "gradle" => run_tasks_command("gradle", project_dir)
+            .with_context(|| format!("Gradle wrapper failed: {:#}", e))?,
+        Err(e) => return Err(e),
+    };
+
+    parse_gradle_tasks_output(&output, project)
+}
+
+/// Collect per-group category display names from a list of Gradle tasks.
+///
+/// Returns a map from category key (e.g. `"gradle:my-app:build"`) to display
+/// name (e.g. `"🐘 My App · Build"`).
+pub fn collect_category_display_names(
+    tasks: &[GradleTask],
+    project: &str,
+) -> std::collections::HashMap<String, String> {
+    let mut names = std::collections::HashMap::new();
+    for task in tasks {
+        if let Some(ref group) = task.group {
+            names
+                .entry(task.category.clone())
+                .or_insert_with(|| format!("🐘 {} · {}", format_display_name(project), group));
+        }
+    }
+    names
 }
 
 #[cfg(test)]
@@ -293,12 +333,87 @@ build - Assembles and tests this project.
 
     #[test]
     fn test_parse_gradle_tasks_category() {
-        let output = "Build tasks\n-----------\nassemble - Build project.\n";
+        let output = "Build tasks\n-----------\nassemble - Build project.\n\n\
+                      Build Setup tasks\n-----------------\ninit - Initializes a new Gradle build.\n";
         let tasks = parse_gradle_tasks_output(output, "my-gradle-project").unwrap();
 
-        for task in &tasks {
-            assert_eq!(task.category, "my-gradle-project");
-        }
+        let assemble = tasks.iter().find(|t| t.name == "assemble").unwrap();
+        assert_eq!(assemble.category, "gradle:my-gradle-project:build");
+        let init = tasks.iter().find(|t| t.name == "init").unwrap();
+        assert_eq!(init.category, "gradle:my-gradle-project:build-setup");
+    }
+
+    #[test]
+    fn test_parse_gradle_tasks_all_output() {
+        let output = r#"
+------------------------------------------------------------
+Tasks runnable from root project 'demo'
+------------------------------------------------------------
+
+Application tasks
+-----------------
+run - Runs this project as a JVM application
+
+Build tasks
+-----------
+assemble - Assembles the outputs of this project.
+build - Assembles and tests this project.
+app:build - Assembles and tests project ':app'.
+
+Verification tasks
+------------------
+check - Runs all checks.
+integrationTest
+
+Other tasks
+-----------
+compileJava - Compiles main Java source.
+prepareKotlinBuildScriptModel
+
+Rules
+-----
+Pattern: clean<TaskName>: Cleans the output files of a task.
+
+To see more detail about a task, run gradle help --task <task>
+"#;
+        let tasks = parse_gradle_tasks_output(output, "demo").unwrap();
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "run",
+                "app:build",
+                "assemble",
+                "build",
+                "check",
+                "integrationTest"
+            ]
+        );
+
+        let integration = tasks.iter().find(|t| t.name == "integrationTest").unwrap();
+        assert_eq!(integration.description, "");
+        assert_eq!(integration.group.as_deref(), Some("Verification"));
+        assert_eq!(integration.category, "gradle:demo:verification");
+    }
+
+    #[test]
+    fn test_parse_gradle_tasks_without_group_are_hidden() {
+        // Tasks before any group header have no category to live in
+        let tasks = parse_gradle_tasks_output("stray - No header above.\n", "demo").unwrap();
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn test_collect_category_display_names() {
+        let output = "Build tasks\n-----------\nbuild - Build.\nclean - Clean.\n\n\
+                      Help tasks\n----------\nhelp - Help.\n";
+        let tasks = parse_gradle_tasks_output(output, "my-app").unwrap();
+        let names = collect_category_display_names(&tasks, "my-app");
+
+        assert_eq!(names.len(), 2);
+        assert_eq!(names["gradle:my-app:build"], "🐘 My App · Build");
+        assert_eq!(names["gradle:my-app:help"], "🐘 My App · Help");
     }
 
     #[test]
@@ -19,6 +19,9 @@
 //! - [`JustRecipe`] - Represents a just recipe with display metadata for the TUI
 //! - [`JustAnnotations`] - Annotations extracted from justfile comments
 //! - [`is_just_available`] - Checks if `just` CLI is installed
+//! - [`split_module`] - Split a recipe name into its module path and local name
+//! - [`module_category`] - Category key of a module
+//! - [`collect_category_display_names`] - Display names of the module categories
 //! - [`list_recipes`] - Main function to list recipes from a justfile
 //!
 //! ## CLI Integration
@@ -30,6 +33,16 @@
 //!
 //! And parses the output which includes recipe names and optional descriptions.
 //!
+//! ## Modules
+//!
+//! Modules (`mod docker`) are collapsed in the listing as `docker ...`. Each
+//! one is listed in turn with `just --list docker` (recursively for nested
+//! modules) and its recipes are qualified with the module path
+//! (`docker::build`), which is also how they run. Module recipes are put in a
+//! category of their own per module (`just:<dir>:docker`, shown as
+//! "⚡ Dir · docker") and displayed by their local name, like Taskfile
+//! namespaces.
+//!
 //! ## Annotations
 //!
 //! Recipes can be annotated with special comments above their definitions:
@@ -52,6 +65,8 @@
 //! | `@emoji <emoji>` | Display emoji prefix in the TUI |
 //! | `@description <text>` | Custom description for the details panel |
 //! | `@ignore` | Hide the recipe from the TUI |
+//! | `@sudo` | Run the recipe via `sudo` |
+//! | `@category <name>` | Show the recipe under another category |
 //!
 //! ## Availability Caching
 //!
@@ -66,7 +81,9 @@ use std::path::Path;
 use std::process::{Command, Stdio};
 use std::sync::OnceLock;
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
+use crate::script::utils::comment_description;
 
 /// Cache for just availability check (checked once per process)
 static JUST_AVAILABLE: OnceLock<bool> = OnceLock::new();
@@ -80,6 +97,9 @@ pub struct JustRecipe {
     pub description: String,
     pub emoji: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
+    /// The module path (`docker`, `ci::lint`) of a module recipe
+    pub module: Option<String>,
 }
 
 /// Annotations extracted from justfile comments above a recipe definition
@@ -88,12 +108,15 @@ pub struct JustAnnotations {
     pub emoji: Option<String>,
     pub description: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
+    /// Category to show the recipe under instead of the file's category
+    pub category: Option<String>,
 }
 
 /// Check if the `just` binary is available.
 pub fn is_just_available() -> bool {
     *JUST_AVAILABLE.get_or_init(|| {
-        Command::new("just")
+        Command::new(binaries::program("just"))
             .arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -109,6 +132,8 @@ pub fn is_just_available() -> bool {
 /// - `# @emoji <emoji>` - Display emoji prefix in the TUI
 /// - `# @description <text>` - Custom description for the details panel
 /// - `# @ignore` - Hide the recipe from the TUI
+/// - `# @sudo` - Run the recipe via `sudo`
+/// - `# @category <name>` - Show the recipe under another category
 ///
 /// Returns a map of recipe names to their annotations.
 pub fn parse_justfile_annotations(
@@ -131,10 +156,12 @@ pub fn parse_justfile_annotations_from_content(
     // Regex patterns for annotations (same as other parsers)
     let emoji_re =
         Regex::new(r"^\s*#\s*@emoji\s+(.+)$").context("Failed to compile emoji regex pattern")?;
-    let desc_re = Regex::new(r"^\s*#\s*@description\s+(.+)$")
-        .context("Failed to compile description regex pattern")?;
     let ignore_re =
         Regex::new(r"^\s*#\s*@ignore\s*$").context("Failed to compile ignore regex pattern")?;
+    let sudo_re =
+        Regex::new(r"^\s*#\s*@sudo\s*$").context("Failed to compile sudo regex pattern")?;
+    let category_re = Regex::new(r"^\s*#\s*@category\s+(.+)$")
+        .context("Failed to compile category regex pattern")?;
     let comment_re = Regex::new(r"^\s*#").context("Failed to compile comment regex pattern")?;
 
     // Regex to match recipe definitions in justfile
@@ -173,10 +200,12 @@ pub fn parse_justfile_annotations_from_content(
 
             // Extract annotations from preceding comment lines
             let mut emoji: Option<String> = None;
-            let mut description: Option<String> = None;
             let mut ignored = false;
+            let mut sudo = false;
+            let mut category: Option<String> = None;
 
             // Look backwards from the recipe line through consecutive comment lines
+            let mut block_start = line_idx;
             let mut check_idx = line_idx.saturating_sub(1);
             loop {
                 if check_idx >= line_idx {
@@ -189,20 +218,26 @@ pub fn parse_justfile_annotations_from_content(
                 if !prev_line.trim().is_empty() && !comment_re.is_match(prev_line) {
                     break;
                 }
+                block_start = check_idx;
 
                 // Check for ignore annotation
                 if ignore_re.is_match(prev_line) {
                     ignored = true;
                 }
 
+                // Check for sudo annotation
+                if sudo_re.is_match(prev_line) {
+                    sudo = true;
+                }
+
                 // Check for emoji annotation
                 if let Some(emoji_cap) = emoji_re.captures(prev_line) {
                     emoji = Some(emoji_cap[1].trim().to_string());
                 }
 
-                // Check for description annotation
-                if let Some(desc_cap) = desc_re.captures(prev_line) {
-                    description = Some(desc_cap[1].trim().to_string());
+                // Check for category annotation
+                if let Some(category_cap) = category_re.captures(prev_line) {
+                    category = Some(category_cap[1].trim().to_string());
                 }
 
                 if check_idx == 0 {
@@ -211,14 +246,18 @@ pub fn parse_justfile_annotations_from_content(
                 check_idx -= 1;
             }
 
+            let description = comment_description(&lines[block_start..line_idx], "#");
+
             // Only add if there are any annotations
-            if emoji.is_some() || description.is_some() || ignored {
+            if emoji.is_some() || description.is_some() || ignored || sudo || category.is_some() {
                 annotations_map.insert(
                     recipe_name.to_string(),
                     JustAnnotations {
                         emoji,
                         description,
                         ignored,
+                        sudo,
+                        category,
                     },
                 );
             }
@@ -227,6 +266,53 @@ pub fn parse_justfile_annotations_from_content(
 
     Ok(annotations_map)
 }
+/// Separator between the module path and the recipe name (`docker::build`)
+pub const MODULE_SEPARATOR: &str = "::";
+
+/// Split a recipe name into its module path and local name: `docker::build`
+/// becomes `(Some("docker"), "build")`, `build` stays `(None, "build")`.
+pub fn split_module(name: &str) -> (Option<&str>, &str) {
+    match name.rsplit_once(MODULE_SEPARATOR) {
+        Some((module, local)) if !module.is_empty() && !local.is_empty() => (Some(module), local),
+        _ => (None, name),
+    }
+}
+
+/// Category key for the recipes of `module` in the justfile category
+/// `category` (e.g. `"just:my-app:docker"`)
+pub fn module_category(category: &str, module: &str) -> String {
+    format!("just:{}:{}", category, module)
+}
+
+/// Collect per-module category display names from a list of recipes.
+///
+/// Returns a map from category key (e.g. `"just:my-app:docker"`) to display
+/// name (e.g. `"⚡ My App · docker"`).
+pub fn collect_category_display_names(
+    recipes: &[JustRecipe],
+    category: &str,
+) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    for recipe in recipes {
+        if let Some(ref module) = recipe.module {
+            names
+                .entry(module_category(category, module))
+                .or_insert_with(|| format!("⚡ {} · {}", format_display_name(category), module));
+        }
+    }
+    names
+}
+
+/// The name of the module on a collapsed `just --list` line (`docker ...`),
+/// if the line is one
+pub fn collapsed_module(line: &str) -> Option<&str> {
+    let name_part = line.split('#').next().unwrap_or_default();
+    let mut words = name_part.split_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some(name), Some("..."), None) => Some(name),
+        _ => None,
+    }
+}
 
 /// Parse output from `just --list` to extract recipe names and descriptions.
 ///
@@ -235,7 +321,12 @@ pub fn parse_justfile_annotations_from_content(
 /// recipe-name # optional description
 /// another-recipe
 /// recipe-with-args arg1 arg2 # builds the project
+/// docker::build # a recipe of the docker module
 /// ```
+///
+/// Module recipes (`docker::build`) are put in their module's category and
+/// displayed by their local name. Collapsed modules (`docker ...`) are
+/// skipped; [`list_recipes`] lists them separately.
 pub fn parse_just_list_output(
     output: &str,
     category: &str,
@@ -245,7 +336,7 @@ pub fn parse_just_list_output(
 
     for line in output.lines() {
         let line = line.trim();
-        if line.is_empty() {
+        if line.is_empty() || collapsed_module(line).is_some() {
             continue;
         }
 
@@ -263,11 +354,13 @@ pub fn parse_just_list_output(
             Some(name) => name.to_string(),
             None => continue,
         };
+        let (module, local_name) = split_module(&recipe_name);
+        let module = module.map(str::to_string);
 
         // Look up annotations for this recipe
         let recipe_annotations = annotations.and_then(|a| a.get(&recipe_name));
 
-        let display_name = format_display_name(&recipe_name);
+        let display_name = format_display_name(local_name);
 
         // Use annotation description, then just comment, then default
         let description = recipe_annotations
@@ -277,14 +370,25 @@ pub fn parse_just_list_output(
 
         let emoji = recipe_annotations.and_then(|a| a.emoji.clone());
         let ignored = recipe_annotations.is_some_and(|a| a.ignored);
+        let sudo = recipe_annotations.is_some_and(|a| a.sudo);
+        // An @category annotation takes precedence over the module and the
+        // file's category
+        let recipe_category = recipe_annotations
+            .and_then(|a| a.category.clone())
+            .unwrap_or_else(|| match module {
+                Some(ref module) => module_category(category, module),
+                None => category.to_string(),
+            });
 
         recipes.push(JustRecipe {
             name: recipe_name,
             display_name,
-            category: category.to_string(),
+            category: recipe_category,
             description,
             emoji,
             ignored,
+            sudo,
+            module,
         });
     }
 
@@ -292,15 +396,23 @@ pub fn parse_just_list_output(
     Ok(recipes)
 }
 
-/// Run `just --list` and parse the result.
-///
-/// Also parses annotations from the justfile comments.
-pub fn list_recipes(justfile_path: &Path, category: &str) -> Result<Vec<JustRecipe>> {
-    // First, parse annotations from the justfile
-    let annotations = parse_justfile_annotations(justfile_path).ok();
+/// Prefix the recipe names on `just --list` output lines with `module`, so
+/// that `build # Build` becomes `docker::build # Build`
+fn qualify_list_output(output: &str, module: &str) -> String {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("{}{}{}\n", module, MODULE_SEPARATOR, line))
+        .collect()
+}
 
-    let output = Command::new("just")
+/// Run `just --list` for the justfile itself or one of its modules
+/// (`module_path` like `["docker", "compose"]`).
+fn run_just_list(justfile_path: &Path, module_path: &[&str]) -> Result<String> {
+    let output = Command::new(binaries::program("just"))
         .arg("--list")
+        .args(module_path)
         .arg("--unsorted")
         .arg("--list-heading")
         .arg("")
@@ -322,10 +434,50 @@ pub fn list_recipes(justfile_path: &Path, category: &str) -> Result<Vec<JustReci
         );
     }
 
-    let output_str = match String::from_utf8(output.stdout) {
+    Ok(match String::from_utf8(output.stdout) {
         Ok(s) => s,
         Err(e) => String::from_utf8_lossy(e.as_bytes()).to_string(),
+    })
+}
+
+/// Append the `just --list` output of a module and of its submodules to
+/// `collected`, with recipe names qualified by their module path.
+fn collect_module_output(justfile_path: &Path, module_path: &[&str], collected: &mut String) {
+    // A module that fails to list (e.g. an older just) is left out
+    let Ok(output) = run_just_list(justfile_path, module_path) else {
+        return;
     };
+    let qualified = module_path.join(MODULE_SEPARATOR);
+    for line in output.lines() {
+        if let Some(submodule) = collapsed_module(line) {
+            let mut path = module_path.to_vec();
+            path.push(submodule);
+            collect_module_output(justfile_path, &path, collected);
+        }
+    }
+    if !collected.is_empty() && !collected.ends_with('\n') {
+        collected.push('\n');
+    }
+    collected.push_str(&qualify_list_output(&output, &qualified));
+}
+
+/// Run `just --list` and parse the result.
+///
+/// Modules are listed recursively with `just --list <module>`. Also parses
+/// annotations from the justfile comments.
+pub fn list_recipes(justfile_path: &Path, category: &str) -> Result<Vec<JustRecipe>> {
+    // First, parse annotations from the justfile
+    let annotations = parse_justfile_annotations(justfile_path).ok();
+
+    let mut output_str = run_just_list(justfile_path, &[])?;
+    let modules: Vec<String> = output_str
+        .lines()
+        .filter_map(collapsed_module)
+        .map(str::to_string)
+        .collect();
+    for module in &modules {
+        collect_module_output(justfile_path, &[module.as_str()], &mut output_str);
+    }
 
     parse_just_list_output(&output_str, category, annotations.as_ref())
 }
@@ -382,6 +534,65 @@ mod tests {
         assert_eq!(result[2].name, "z_last");
     }
 
+    #[test]
+    fn test_parse_just_list_output_module_recipes() {
+        let output = "build # Build the project\n\
+                      docker ... # Container recipes\n\
+                      docker::build tag=\"latest\" # Build the image\n\
+                      docker::push\n\
+                      ci::lint::fix # Fix lint errors\n";
+        let result = parse_just_list_output(output, "myproject", None).unwrap();
+
+        let names: Vec<&str> = result.iter().map(|r| r.name.as_str()).collect();
+        // The collapsed module line is not a recipe
+        assert_eq!(
+            names,
+            vec!["build", "ci::lint::fix", "docker::build", "docker::push"]
+        );
+
+        assert_eq!(result[0].category, "myproject");
+        assert_eq!(result[0].module, None);
+
+        // Listed by their local name in the module's category
+        assert_eq!(result[2].display_name, "Build");
+        assert_eq!(result[2].category, "just:myproject:docker");
+        assert_eq!(result[2].module.as_deref(), Some("docker"));
+        assert_eq!(result[2].description, "Build the image");
+        assert_eq!(result[3].description, "just recipe docker::push");
+
+        assert_eq!(result[1].display_name, "Fix");
+        assert_eq!(result[1].category, "just:myproject:ci::lint");
+
+        let names = collect_category_display_names(&result, "myproject");
+        assert_eq!(names.len(), 2);
+        assert_eq!(names["just:myproject:docker"], "⚡ Myproject · docker");
+        assert_eq!(names["just:myproject:ci::lint"], "⚡ Myproject · ci::lint");
+    }
+
+    #[test]
+    fn test_split_module() {
+        assert_eq!(split_module("docker::build"), (Some("docker"), "build"));
+        assert_eq!(split_module("a::b::c"), (Some("a::b"), "c"));
+        assert_eq!(split_module("build"), (None, "build"));
+        assert_eq!(split_module("::build"), (None, "::build"));
+    }
+
+    #[test]
+    fn test_collapsed_module() {
+        assert_eq!(collapsed_module("docker ..."), Some("docker"));
+        assert_eq!(collapsed_module("docker ... # Containers"), Some("docker"));
+        assert_eq!(collapsed_module("build"), None);
+        assert_eq!(collapsed_module("test +args # Run ..."), None);
+    }
+
+    #[test]
+    fn test_qualify_list_output() {
+        assert_eq!(
+            qualify_list_output("build # Build\n\npush\n", "docker"),
+            "docker::build # Build\ndocker::push\n"
+        );
+    }
+
     #[test]
     fn test_parse_just_list_output_empty() {
         let output = "";
@@ -400,6 +611,8 @@ mod tests {
                 emoji: Some("🚀".to_string()),
                 description: Some("Custom deploy description".to_string()),
                 ignored: false,
+                sudo: false,
+                category: None,
             },
         );
 
@@ -430,6 +643,8 @@ mod tests {
                 emoji: None,
                 description: Some("Overridden description from annotation".to_string()),
                 ignored: false,
+                sudo: false,
+                category: None,
             },
         );
 
@@ -441,6 +656,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_justfile_annotations_category() {
+        let content = r#"# @category Deployment
+# @emoji 🚀
+deploy:
+    ./deploy.sh
+
+build:
+    cargo build
+"#;
+
+        let annotations = parse_justfile_annotations_from_content(content).unwrap();
+        assert_eq!(
+            annotations["deploy"].category,
+            Some("Deployment".to_string())
+        );
+        assert!(!annotations.contains_key("build"));
+    }
+
+    #[test]
+    fn test_parse_just_list_output_category_override() {
+        let annotations = parse_justfile_annotations_from_content(
+            "# @category Deployment\ndeploy:\n    ./deploy.sh\n",
+        )
+        .unwrap();
+
+        let result = parse_just_list_output("build\ndeploy\n", "app", Some(&annotations)).unwrap();
+
+        assert_eq!(result[0].name, "build");
+        assert_eq!(result[0].category, "app");
+        assert_eq!(result[1].name, "deploy");
+        assert_eq!(result[1].category, "Deployment");
+    }
+
     #[test]
     fn test_parse_justfile_annotations_emoji() {
         let content = r#"# Build the project
@@ -471,6 +720,34 @@ build:
         );
     }
 
+    #[test]
+    fn test_parse_justfile_annotations_multiline_description() {
+        let content = r#"# @description Build the project
+# @description with optimizations
+build:
+    cargo build --release
+
+# @description-start
+# Run the test suite.
+# Needs a running database.
+# @description-end
+# @emoji 🧪
+test:
+    cargo test
+"#;
+
+        let annotations = parse_justfile_annotations_from_content(content).unwrap();
+        assert_eq!(
+            annotations["build"].description,
+            Some("Build the project\nwith optimizations".to_string())
+        );
+        assert_eq!(
+            annotations["test"].description,
+            Some("Run the test suite.\nNeeds a running database.".to_string())
+        );
+        assert_eq!(annotations["test"].emoji, Some("🧪".to_string()));
+    }
+
     #[test]
     fn test_parse_justfile_annotations_ignore() {
         let content = r#"# @ignore
@@ -0,0 +1,350 @@
+//! # Lefthook Parser
+//!
+//! This module lists the git hooks of a [Lefthook](https://lefthook.dev)
+//! config for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! When a `lefthook.yml` (or `.lefthook.yml`, or a `.yaml` variant) is found
+//! and `lefthook` is installed, every hook that defines `commands`, `jobs` or
+//! `scripts` gets an entry that runs the whole hook, followed by one entry per
+//! named command and job:
+//!
+//! ```yaml
+//! pre-commit:
+//!   parallel: true
+//!   commands:
+//!     lint:
+//!       run: cargo clippy
+//!   jobs:
+//!     - name: test
+//!       run: cargo test
+//! ```
+//!
+//! ## Parsing
+//!
+//! Only names are needed, so the file is read line by line instead of with a
+//! full YAML parser, like Docker Compose files: hooks are top-level keys,
+//! commands are the keys one level below their `commands:` key, and jobs are
+//! the `name:` of the items of their `jobs:` list. Settings such as
+//! `min_version` or `output` have no commands and are skipped.
+//!
+//! ## Execution
+//!
+//! Hooks run as `lefthook run <hook>` from the config's directory. A single
+//! command or job runs with `--commands <name>` or `--jobs <name>`.
+//!
+//! ## Key Types
+//!
+//! - [`LefthookHook`] - A hook, command or job with display metadata
+//! - [`HookConfig`] - A hook and the commands and jobs it defines
+//! - [`is_lefthook_available`] - Checks if `lefthook` is installed
+//! - [`parse_hooks`] - Extract hooks from config content
+//! - [`run_args`] - Arguments that run a hook, command or job
+//! - [`list_hooks`] - Main entry point to list the hooks of a config
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+use crate::script::utils::{indent_of, unquote};
+
+/// Cache for lefthook availability check (checked once per process)
+static LEFTHOOK_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Keys of a hook whose entries make it runnable
+const SECTION_KEYS: &[&str] = &["commands", "jobs", "scripts"];
+
+/// Lefthook item for TUI display
+#[derive(Debug, Clone)]
+pub struct LefthookHook {
+    /// `<hook>`, `<hook>:commands:<name>` or `<hook>:jobs:<name>`
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// A hook of a Lefthook config with its named commands and jobs, in file order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookConfig {
+    pub name: String,
+    pub commands: Vec<String>,
+    pub jobs: Vec<String>,
+}
+
+/// Check if the `lefthook` binary is available.
+pub fn is_lefthook_available() -> bool {
+    *LEFTHOOK_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("lefthook"))
+            .arg("version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// The key of a `key:` line, unquoted, if the line is one
+fn mapping_key(trimmed: &str) -> Option<&str> {
+    let (key, _) = trimmed.split_once(':')?;
+    let key = unquote(key);
+    (!key.is_empty()).then_some(key)
+}
+
+/// Extract the hooks of a Lefthook config.
+///
+/// Hooks are top-level keys with a `commands:`, `jobs:` or `scripts:` key one
+/// level below them. Comments, blank lines and other settings are skipped.
+pub fn parse_hooks(content: &str) -> Vec<HookConfig> {
+    let mut hooks: Vec<HookConfig> = Vec::new();
+    // The hook being read, and whether it has a section that makes it runnable
+    let mut current: Option<(HookConfig, bool)> = None;
+    // Indentation of the hook's own keys
+    let mut hook_indent: Option<usize> = None;
+    // The section being read and the indentation of its entries
+    let mut section: Option<&str> = None;
+    let mut entry_indent: Option<usize> = None;
+
+    let mut finish = |current: Option<(HookConfig, bool)>| {
+        if let Some((hook, true)) = current {
+            hooks.push(hook);
+        }
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let indent = indent_of(line);
+        if indent == 0 {
+            finish(current.take());
+            current = mapping_key(trimmed).map(|key| {
+                let hook = HookConfig {
+                    name: key.to_string(),
+                    ..HookConfig::default()
+                };
+                (hook, false)
+            });
+            hook_indent = None;
+            section = None;
+            continue;
+        }
+        let Some((ref mut hook, ref mut runnable)) = current else {
+            continue;
+        };
+
+        let level = *hook_indent.get_or_insert(indent);
+        if indent <= level {
+            section = mapping_key(trimmed)
+                .and_then(|key| SECTION_KEYS.iter().copied().find(|section| *section == key));
+            *runnable |= section.is_some();
+            entry_indent = None;
+            continue;
+        }
+
+        let Some(name) = section else {
+            continue;
+        };
+        let entry = *entry_indent.get_or_insert(indent);
+        match name {
+            "commands" if indent == entry => {
+                if let Some(key) = mapping_key(trimmed) {
+                    if !hook.commands.iter().any(|c| c == key) {
+                        hook.commands.push(key.to_string());
+                    }
+                }
+            }
+            "jobs" => {
+                // `- name: x` starts an item, `name: x` may also follow its
+                // first key two columns further in
+                let key_line = match trimmed.strip_prefix("- ") {
+                    Some(rest) if indent == entry => Some(rest.trim_start()),
+                    _ if indent == entry + 2 => Some(trimmed),
+                    _ => None,
+                };
+                if let Some(job) = key_line
+                    .and_then(|l| l.strip_prefix("name:"))
+                    .map(unquote)
+                    .filter(|v| !v.is_empty())
+                {
+                    if !hook.jobs.iter().any(|j| j == job) {
+                        hook.jobs.push(job.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    finish(current);
+
+    hooks
+}
+
+/// Arguments that run an entry: `run <hook>`, plus `--commands <name>` or
+/// `--jobs <name>` for a single command or job
+pub fn run_args(name: &str) -> Vec<String> {
+    let mut parts = name.splitn(3, ':');
+    let hook = parts.next().unwrap_or_default();
+    let mut args = vec!["run".to_string(), hook.to_string()];
+    if let (Some(kind @ ("commands" | "jobs")), Some(entry)) = (parts.next(), parts.next()) {
+        args.push(format!("--{}", kind));
+        args.push(entry.to_string());
+    }
+    args
+}
+
+/// The entries of the hooks: each hook, then its commands and jobs
+pub fn build_entries(hooks: &[HookConfig], category: &str) -> Vec<LefthookHook> {
+    let mut entries = Vec::new();
+
+    for hook in hooks {
+        let hook_display = format_display_name(&hook.name);
+        entries.push(LefthookHook {
+            name: hook.name.clone(),
+            display_name: hook_display.clone(),
+            category: category.to_string(),
+            description: format!("lefthook run {}", hook.name),
+        });
+        for (kind, names) in [("commands", &hook.commands), ("jobs", &hook.jobs)] {
+            for entry in names {
+                entries.push(LefthookHook {
+                    name: format!("{}:{}:{}", hook.name, kind, entry),
+                    display_name: format!("{} · {}", hook_display, entry),
+                    category: category.to_string(),
+                    description: format!("lefthook run {} --{} {}", hook.name, kind, entry),
+                });
+            }
+        }
+    }
+
+    entries
+}
+
+/// Read a Lefthook config and list its hooks, commands and jobs.
+pub fn list_hooks(config_path: &Path, category: &str) -> Result<Vec<LefthookHook>> {
+    let content = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+
+    Ok(build_entries(&parse_hooks(&content), category))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = "\
+min_version: 1.5.0
+output:
+  - summary
+
+pre-commit:
+  parallel: true
+  commands:
+    lint:
+      glob: \"*.rs\"
+      run: cargo clippy
+    \"fmt\":
+      run: cargo fmt --check
+  jobs:
+    - name: test
+      run: cargo test
+    - run: echo unnamed
+    - run: cargo deny check
+      name: deny
+    - group:
+        jobs:
+          - name: nested
+            run: echo nested
+
+# Pushes run the full suite
+pre-push:
+  scripts:
+    \"check.sh\":
+      runner: bash
+
+commit-msg:
+  skip: true
+";
+
+    #[test]
+    fn test_parse_hooks() {
+        let hooks = parse_hooks(SAMPLE);
+
+        let names: Vec<&str> = hooks.iter().map(|h| h.name.as_str()).collect();
+        // Settings and hooks without commands are skipped
+        assert_eq!(names, vec!["pre-commit", "pre-push"]);
+
+        assert_eq!(hooks[0].commands, vec!["lint", "fmt"]);
+        assert_eq!(hooks[0].jobs, vec!["test", "deny"]);
+        assert!(hooks[1].commands.is_empty());
+        assert!(hooks[1].jobs.is_empty());
+    }
+
+    #[test]
+    fn test_parse_hooks_four_space_indent() {
+        let content = "pre-commit:\n    commands:\n        lint:\n            run: make lint\n";
+        let hooks = parse_hooks(content);
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].commands, vec!["lint"]);
+    }
+
+    #[test]
+    fn test_build_entries() {
+        let entries = build_entries(&parse_hooks(SAMPLE), "repo");
+
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "pre-commit",
+                "pre-commit:commands:lint",
+                "pre-commit:commands:fmt",
+                "pre-commit:jobs:test",
+                "pre-commit:jobs:deny",
+                "pre-push",
+            ]
+        );
+        assert_eq!(entries[0].display_name, "Pre Commit");
+        assert_eq!(entries[0].description, "lefthook run pre-commit");
+        assert_eq!(entries[1].display_name, "Pre Commit · lint");
+        assert_eq!(
+            entries[3].description,
+            "lefthook run pre-commit --jobs test"
+        );
+        assert!(entries.iter().all(|e| e.category == "repo"));
+    }
+
+    #[test]
+    fn test_run_args() {
+        assert_eq!(run_args("pre-commit"), vec!["run", "pre-commit"]);
+        assert_eq!(
+            run_args("pre-commit:commands:lint"),
+            vec!["run", "pre-commit", "--commands", "lint"]
+        );
+        assert_eq!(
+            run_args("pre-push:jobs:unit tests"),
+            vec!["run", "pre-push", "--jobs", "unit tests"]
+        );
+    }
+
+    #[test]
+    fn test_list_hooks_reads_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("lefthook.yml");
+        fs::write(&path, SAMPLE).unwrap();
+
+        let entries = list_hooks(&path, "repo").unwrap();
+        assert_eq!(entries.len(), 6);
+        assert!(list_hooks(&temp_dir.path().join("missing.yml"), "repo").is_err());
+    }
+}
@@ -29,17 +29,19 @@ use std::path::Path;
 use std::process::{Command, Stdio};
 use std::sync::OnceLock;
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
+use crate::script::utils::comment_description;
 
 /// Cache for mage availability check (checked once per process)
 static MAGE_AVAILABLE: OnceLock<bool> = OnceLock::new();
 
 /// Module-level regex for emoji annotation (compiled once)
 static EMOJI_RE: OnceLock<Regex> = OnceLock::new();
-/// Module-level regex for description annotation (compiled once)
-static DESC_RE: OnceLock<Regex> = OnceLock::new();
 /// Module-level regex for ignore annotation (compiled once)
 static IGNORE_RE: OnceLock<Regex> = OnceLock::new();
+/// Module-level regex for sudo annotation (compiled once)
+static SUDO_RE: OnceLock<Regex> = OnceLock::new();
 /// Module-level regex for comment lines (compiled once)
 static COMMENT_RE: OnceLock<Regex> = OnceLock::new();
 /// Module-level regex for exported Go function definitions (compiled once)
@@ -49,14 +51,14 @@ fn emoji_re() -> &'static Regex {
     EMOJI_RE.get_or_init(|| Regex::new(r"^\s*//\s*@emoji\s+(.+)$").expect("valid regex"))
 }
 
-fn desc_re() -> &'static Regex {
-    DESC_RE.get_or_init(|| Regex::new(r"^\s*//\s*@description\s+(.+)$").expect("valid regex"))
-}
-
 fn ignore_re() -> &'static Regex {
     IGNORE_RE.get_or_init(|| Regex::new(r"^\s*//\s*@ignore\s*$").expect("valid regex"))
 }
 
+fn sudo_re() -> &'static Regex {
+    SUDO_RE.get_or_init(|| Regex::new(r"^\s*//\s*@sudo\s*$").expect("valid regex"))
+}
+
 fn comment_re() -> &'static Regex {
     COMMENT_RE.get_or_init(|| Regex::new(r"^\s*//").expect("valid regex"))
 }
@@ -74,6 +76,7 @@ pub struct MageTarget {
     pub description: String,
     pub emoji: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
 }
 
 /// Annotations extracted from magefile comments
@@ -82,12 +85,13 @@ pub struct MageAnnotations {
     pub emoji: Option<String>,
     pub description: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
 }
 
 /// Check if the `mage` binary is available.
 pub fn is_mage_available() -> bool {
     *MAGE_AVAILABLE.get_or_init(|| {
-        Command::new("mage")
+        Command::new(binaries::program("mage"))
             .arg("-version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -128,9 +132,10 @@ pub fn parse_magefile_annotations_from_content(
         if let Some(cap) = func_re().captures(line) {
             let target_name = &cap[1];
             let mut emoji: Option<String> = None;
-            let mut description: Option<String> = None;
             let mut ignored = false;
+            let mut sudo = false;
 
+            let mut block_start = line_idx;
             let mut check_idx = line_idx.saturating_sub(1);
             loop {
                 if check_idx >= line_idx {
@@ -140,22 +145,25 @@ pub fn parse_magefile_annotations_from_content(
                 if !prev_line.trim().is_empty() && !comment_re().is_match(prev_line) {
                     break;
                 }
+                block_start = check_idx;
                 if ignore_re().is_match(prev_line) {
                     ignored = true;
                 }
+                if sudo_re().is_match(prev_line) {
+                    sudo = true;
+                }
                 if let Some(emoji_cap) = emoji_re().captures(prev_line) {
                     emoji = Some(emoji_cap[1].trim().to_string());
                 }
-                if let Some(desc_cap) = desc_re().captures(prev_line) {
-                    description = Some(desc_cap[1].trim().to_string());
-                }
                 if check_idx == 0 {
                     break;
                 }
                 check_idx -= 1;
             }
 
-            if emoji.is_some() || description.is_some() || ignored {
+            let description = comment_description(&lines[block_start..line_idx], "//");
+
+            if emoji.is_some() || description.is_some() || ignored || sudo {
                 // Lowercase the key so it matches the `mage -l` output which
                 // lowercases the first character of each target (e.g. `Build` → `build`).
                 let key = {
@@ -171,6 +179,7 @@ pub fn parse_magefile_annotations_from_content(
                         emoji,
                         description,
                         ignored,
+                        sudo,
                     },
                 );
             }
@@ -222,6 +231,7 @@ pub fn parse_mage_list_output(
 
         let emoji = target_annotations.and_then(|a| a.emoji.clone());
         let ignored = target_annotations.is_some_and(|a| a.ignored);
+        let sudo = target_annotations.is_some_and(|a| a.sudo);
 
         targets.push(MageTarget {
             name: target_name,
@@ -230,6 +240,7 @@ pub fn parse_mage_list_output(
             description,
             emoji,
             ignored,
+            sudo,
         });
     }
     targets.sort_by(|a, b| a.name.cmp(&b.name));
@@ -243,7 +254,7 @@ pub fn list_targets(magefile_path: &Path, category: &str) -> Result<Vec<MageTarg
         .parent()
         .context("Failed to get parent directory of magefile")?;
 
-    let output = Command::new("mage")
+    let output = Command::new(binaries::program("mage"))
         .arg("-l")
         .current_dir(magefile_dir)
         .stdout(Stdio::piped())
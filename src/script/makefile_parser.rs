@@ -20,6 +20,7 @@
 //! - [`MakeAnnotations`] - Annotations extracted from Makefile comments
 //! - [`is_make_available`] - Checks if `make` CLI is installed
 //! - [`list_targets`] - Main function to list targets from a Makefile
+//! - [`list_variables`] - Variables a Makefile assigns, offered as overrides
 //!
 //! ## CLI Integration
 //!
@@ -28,7 +29,9 @@
 //! make --print-data-base --dry-run --file <path> 2>/dev/null
 //! ```
 //!
-//! And parses the output which includes target information.
+//! And parses the output which includes target information. When `make` is
+//! not installed, or the command fails, target names are read from the
+//! Makefile itself.
 //!
 //! ## Annotations
 //!
@@ -52,6 +55,14 @@
 //! | `@emoji <emoji>` | Display emoji prefix in the TUI |
 //! | `@description <text>` | Custom description for the details panel |
 //! | `@ignore` | Hide the target from the TUI |
+//! | `@sudo` | Run the target via `sudo` |
+//! | `@category <name>` | Show the target under another category |
+//!
+//! ## Variable Overrides
+//!
+//! Variables assigned at the top level of a Makefile (`PROFILE ?= debug`,
+//! `CC := gcc`, `export PREFIX = /usr/local`) are suggested when entering
+//! overrides for a target, which then runs as `make ... build PROFILE=release`.
 //!
 //! ## Availability Caching
 //!
@@ -67,11 +78,16 @@ use std::sync::OnceLock;
 use anyhow::{Context, Result};
 use regex::Regex;
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
+use crate::script::utils::comment_description;
 
 /// Cache for make availability check (checked once per process)
 static MAKE_AVAILABLE: OnceLock<bool> = OnceLock::new();
 
+/// Module-level regex for top-level variable assignments (compiled once)
+static ASSIGNMENT_RE: OnceLock<Regex> = OnceLock::new();
+
 /// Special Makefile target names that should be excluded from the TUI
 const SKIP_PATTERNS: &[&str] = &[
     ".PHONY",
@@ -104,6 +120,7 @@ pub struct MakeTarget {
     pub description: String,
     pub emoji: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
 }
 
 /// Annotations extracted from Makefile comments above a target definition
@@ -112,12 +129,15 @@ pub struct MakeAnnotations {
     pub emoji: Option<String>,
     pub description: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
+    /// Category to show the target under instead of the file's category
+    pub category: Option<String>,
 }
 
 /// Check if the `make` binary is available.
 pub fn is_make_available() -> bool {
     *MAKE_AVAILABLE.get_or_init(|| {
-        Command::new("make")
+        Command::new(binaries::program("make"))
             .arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -133,6 +153,8 @@ pub fn is_make_available() -> bool {
 /// - `# @emoji <emoji>` - Display emoji prefix in the TUI
 /// - `# @description <text>` - Custom description for the details panel
 /// - `# @ignore` - Hide the target from the TUI
+/// - `# @sudo` - Run the target via `sudo`
+/// - `# @category <name>` - Show the target under another category
 ///
 /// Returns a map of target names to their annotations.
 pub fn parse_makefile_annotations(
@@ -155,10 +177,14 @@ pub fn parse_makefile_annotations_from_content(
     // Regex patterns for annotations (same as other parsers)
     let emoji_re =
         Regex::new(r"^\s*#\s*@emoji\s+(.+)$").context("Failed to compile emoji regex pattern")?;
-    let desc_re = Regex::new(r"^\s*#\s*@description\s+(.+)$")
+    let desc_re = Regex::new(r"^\s*#\s*@description")
         .context("Failed to compile description regex pattern")?;
     let ignore_re =
         Regex::new(r"^\s*#\s*@ignore\s*$").context("Failed to compile ignore regex pattern")?;
+    let sudo_re =
+        Regex::new(r"^\s*#\s*@sudo\s*$").context("Failed to compile sudo regex pattern")?;
+    let category_re = Regex::new(r"^\s*#\s*@category\s+(.+)$")
+        .context("Failed to compile category regex pattern")?;
     let comment_re = Regex::new(r"^\s*#").context("Failed to compile comment regex pattern")?;
     let plain_comment_re =
         Regex::new(r"^\s*#\s+(.+)$").context("Failed to compile plain comment regex pattern")?;
@@ -182,11 +208,13 @@ pub fn parse_makefile_annotations_from_content(
 
             // Extract annotations from preceding comment lines
             let mut emoji: Option<String> = None;
-            let mut description: Option<String> = None;
             let mut plain_comment: Option<String> = None;
             let mut ignored = false;
+            let mut sudo = false;
+            let mut category: Option<String> = None;
 
             // Look backwards from the target line through consecutive comment lines
+            let mut block_start = line_idx;
             let mut check_idx = line_idx.saturating_sub(1);
             loop {
                 if check_idx >= line_idx {
@@ -199,27 +227,35 @@ pub fn parse_makefile_annotations_from_content(
                 if prev_line.trim().is_empty() || !comment_re.is_match(prev_line) {
                     break;
                 }
+                block_start = check_idx;
 
                 // Check for ignore annotation
                 if ignore_re.is_match(prev_line) {
                     ignored = true;
                 }
 
+                // Check for sudo annotation
+                if sudo_re.is_match(prev_line) {
+                    sudo = true;
+                }
+
                 // Check for emoji annotation
                 if let Some(emoji_cap) = emoji_re.captures(prev_line) {
                     emoji = Some(emoji_cap[1].trim().to_string());
                 }
 
-                // Check for description annotation
-                if let Some(desc_cap) = desc_re.captures(prev_line) {
-                    description = Some(desc_cap[1].trim().to_string());
+                // Check for category annotation
+                if let Some(category_cap) = category_re.captures(prev_line) {
+                    category = Some(category_cap[1].trim().to_string());
                 }
 
                 // Check for plain comment (not an annotation) as fallback description
                 if plain_comment.is_none()
                     && !ignore_re.is_match(prev_line)
+                    && !sudo_re.is_match(prev_line)
                     && !emoji_re.is_match(prev_line)
                     && !desc_re.is_match(prev_line)
+                    && !category_re.is_match(prev_line)
                 {
                     if let Some(plain_cap) = plain_comment_re.captures(prev_line) {
                         let text = plain_cap[1].trim().to_string();
@@ -236,16 +272,24 @@ pub fn parse_makefile_annotations_from_content(
             }
 
             // Use @description if present, otherwise fall back to plain comment
-            let final_description = description.or(plain_comment);
+            let final_description =
+                comment_description(&lines[block_start..line_idx], "#").or(plain_comment);
 
             // Add if there are any annotations or a plain comment description
-            if emoji.is_some() || final_description.is_some() || ignored {
+            if emoji.is_some()
+                || final_description.is_some()
+                || ignored
+                || sudo
+                || category.is_some()
+            {
                 annotations_map.insert(
                     target_name.to_string(),
                     MakeAnnotations {
                         emoji,
                         description: final_description,
                         ignored,
+                        sudo,
+                        category,
                     },
                 );
             }
@@ -300,6 +344,12 @@ fn parse_make_database(
 
         // Try to match a target definition
         if let Some(cap) = target_re.captures(line) {
+            // Variable assignments (`CURDIR := /path`, `X ::= y`) are not targets
+            let rest = &line[cap.get(0).map_or(0, |m| m.end())..];
+            if rest.starts_with('=') || rest.starts_with(":=") {
+                continue;
+            }
+
             let target_name = cap[1].to_string();
 
             // Skip special targets
@@ -334,14 +384,20 @@ fn parse_make_database(
 
             let emoji = target_annotations.and_then(|a| a.emoji.clone());
             let ignored = target_annotations.is_some_and(|a| a.ignored);
+            let sudo = target_annotations.is_some_and(|a| a.sudo);
+            // An @category annotation takes precedence over the file's category
+            let target_category = target_annotations
+                .and_then(|a| a.category.clone())
+                .unwrap_or_else(|| category.to_string());
 
             targets.push(MakeTarget {
                 name: target_name,
                 display_name,
-                category: category.to_string(),
+                category: target_category,
                 description,
                 emoji,
                 ignored,
+                sudo,
             });
         }
     }
@@ -357,21 +413,21 @@ pub fn list_targets(makefile_path: &Path, category: &str) -> Result<Vec<MakeTarg
     // First, parse annotations from the Makefile
     let annotations = parse_makefile_annotations(makefile_path).ok();
 
-    let output = Command::new("make")
+    let output = Command::new(binaries::program("make"))
         .arg("--print-data-base")
         .arg("--dry-run")
         .arg("--file")
         .arg(makefile_path)
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
-        .output()
-        .with_context(|| format!("Failed to run make for: {}", makefile_path.display()))?;
+        .output();
 
-    if !output.status.success() {
-        // If make --print-data-base fails, try a simpler approach:
-        // just parse the Makefile directly for target names
-        return list_targets_from_parsing(makefile_path, category, annotations.as_ref());
-    }
+    // If make is missing or --print-data-base fails, try a simpler approach:
+    // just parse the Makefile directly for target names
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        _ => return list_targets_from_parsing(makefile_path, category, annotations.as_ref()),
+    };
 
     let output_str = match String::from_utf8(output.stdout) {
         Ok(s) => s,
@@ -451,14 +507,20 @@ fn list_targets_from_parsing(
 
             let emoji = target_annotations.and_then(|a| a.emoji.clone());
             let ignored = target_annotations.is_some_and(|a| a.ignored);
+            let sudo = target_annotations.is_some_and(|a| a.sudo);
+            // An @category annotation takes precedence over the file's category
+            let target_category = target_annotations
+                .and_then(|a| a.category.clone())
+                .unwrap_or_else(|| category.to_string());
 
             targets.push(MakeTarget {
                 name: target_name,
                 display_name,
-                category: category.to_string(),
+                category: target_category,
                 description,
                 emoji,
                 ignored,
+                sudo,
             });
         }
     }
@@ -467,10 +529,82 @@ fn list_targets_from_parsing(
     Ok(targets)
 }
 
+/// Extract the names of variables assigned at the top level of Makefile
+/// content, in file order without duplicates.
+///
+/// Recognizes `=`, `?=`, `:=`, `::=` and `:::=` assignments, optionally
+/// prefixed with `export` or `override`. Appends (`+=`), shell assignments
+/// (`!=`), target-specific variables and recipe lines are ignored.
+pub fn parse_variables_from_content(content: &str) -> Vec<String> {
+    let assignment_re = ASSIGNMENT_RE.get_or_init(|| {
+        Regex::new(r"^(?:(?:export|override)\s+)*([A-Za-z_][A-Za-z0-9_]*)\s*(?:\?|:{1,3})?=")
+            .expect("valid regex")
+    });
+
+    let mut variables: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let Some(cap) = assignment_re.captures(line.trim_start()) else {
+            continue;
+        };
+        let name = &cap[1];
+        if matches!(name, "export" | "override") || variables.iter().any(|v| v == name) {
+            continue;
+        }
+        variables.push(name.to_string());
+    }
+    variables
+}
+
+/// Read a Makefile and list the variables it assigns (see
+/// [`parse_variables_from_content`]).
+pub fn list_variables(makefile_path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(makefile_path)
+        .with_context(|| format!("Failed to read Makefile: {}", makefile_path.display()))?;
+    Ok(parse_variables_from_content(&content))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_makefile_annotations_category() {
+        let content = r#"# Deploy to production
+# @category Deployment
+deploy:
+	./deploy.sh
+"#;
+
+        let annotations = parse_makefile_annotations_from_content(content).unwrap();
+        assert_eq!(
+            annotations["deploy"].category,
+            Some("Deployment".to_string())
+        );
+        // The annotation is not mistaken for a plain comment description
+        assert_eq!(
+            annotations["deploy"].description,
+            Some("Deploy to production".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_make_database_category_override() {
+        let annotations = parse_makefile_annotations_from_content(
+            "# @category Deployment\ndeploy:\n\t./deploy.sh\n",
+        )
+        .unwrap();
+
+        let targets = parse_make_database("build:\ndeploy:\n", "app", Some(&annotations)).unwrap();
+
+        assert_eq!(targets[0].name, "build");
+        assert_eq!(targets[0].category, "app");
+        assert_eq!(targets[1].name, "deploy");
+        assert_eq!(targets[1].category, "Deployment");
+    }
+
     #[test]
     fn test_parse_makefile_annotations_emoji() {
         let content = r#"# Makefile for my project
@@ -781,6 +915,16 @@ build:
         );
     }
 
+    #[test]
+    fn test_parse_make_database_skips_variable_assignments() {
+        let db_output = "# makefile (from 'Makefile', line 1)\nCURDIR := /tmp/app\nMAKE_VERSION := 4.3\nFLAGS ::= -O2\nbuild:\ntest:: build\n";
+
+        let targets = parse_make_database(db_output, "test", None).unwrap();
+
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test"]);
+    }
+
     #[test]
     fn test_parse_make_database_filters_artifact_targets() {
         let db_output = "build:\nmain.o:\nlibfoo.a:\napp.so:\nresult.out:\n";
@@ -847,4 +991,33 @@ libfoo.a:
             "Should NOT find any artifact targets"
         );
     }
+
+    #[test]
+    fn test_parse_variables_from_content() {
+        let content = "\
+PROFILE ?= debug
+CC := gcc
+export PREFIX = /usr/local
+override VERSION ::= 1.0
+CFLAGS += -O2
+GIT_SHA != git rev-parse HEAD
+PROFILE = release
+
+build: LDFLAGS = -s
+build:
+\tOUT=bin/app $(CC) -o $$OUT main.c
+ifeq ($(PROFILE),release)
+  STRIP := 1
+endif
+";
+        assert_eq!(
+            parse_variables_from_content(content),
+            vec!["PROFILE", "CC", "PREFIX", "VERSION", "STRIP"]
+        );
+    }
+
+    #[test]
+    fn test_parse_variables_from_content_without_assignments() {
+        assert!(parse_variables_from_content("build:\n\tcargo build\n").is_empty());
+    }
 }
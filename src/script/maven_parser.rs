@@ -0,0 +1,315 @@
+//! # Maven Goal Parser
+//!
+//! This module lists the goals of a Java [Maven](https://maven.apache.org)
+//! project (`pom.xml`) for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Maven builds run lifecycle phases rather than project-defined tasks, so
+//! every project offers the standard [`LIFECYCLE_PHASES`]. On top of them,
+//! each build profile declared in the POM gets an entry that packages the
+//! project with that profile:
+//!
+//! ```xml
+//! <project>
+//!   <profiles>
+//!     <profile>
+//!       <id>release</id>
+//!     </profile>
+//!   </profiles>
+//! </project>
+//! ```
+//!
+//! Profile ids are read with a lightweight tag scan ([`profile_ids`]) rather
+//! than a full XML parser: only `<id>` elements directly inside
+//! `<profiles><profile>` count, so the ids of repositories or plugins
+//! declared within a profile are ignored.
+//!
+//! ## Execution
+//!
+//! Phases run as `mvn <phase>` and profiles as `mvn -P<id> package`, from the
+//! POM's directory. The Maven wrapper (`./mvnw`) is preferred when the project
+//! (or the parent project of a module) has one.
+//!
+//! ## Key Types
+//!
+//! - [`MavenGoal`] - A single phase or profile with display metadata
+//! - [`is_maven_available`] - Checks if `mvn` is installed
+//! - [`maven_wrapper`] - Finds the project's `mvnw`
+//! - [`profile_ids`] - The profile ids declared in a POM
+//! - [`goal_args`] - Arguments that run a goal
+//! - [`list_goals`] - Main entry point to list the goals of a project
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for mvn availability check (checked once per process)
+static MAVEN_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Lifecycle phases offered for every project, in lifecycle order
+pub const LIFECYCLE_PHASES: &[(&str, &str)] = &[
+    ("clean", "Remove the files generated by previous builds"),
+    ("compile", "Compile the project's source code"),
+    ("test", "Run the unit tests"),
+    ("package", "Package the compiled code (e.g. as a JAR)"),
+    ("verify", "Run integration tests and quality checks"),
+    ("install", "Install the package into the local repository"),
+];
+
+/// Prefix of the goal names that build with a profile (`profile:<id>`)
+pub const PROFILE_PREFIX: &str = "profile:";
+
+/// Maven goal item for TUI display
+#[derive(Debug, Clone)]
+pub struct MavenGoal {
+    /// The phase name, or `profile:<id>` for a profile
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if `mvn` is available.
+pub fn is_maven_available() -> bool {
+    *MAVEN_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("mvn"))
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// The Maven wrapper of the project in `project_dir`: its own `mvnw`, or that
+/// of the nearest parent project (modules share the root's wrapper)
+pub fn maven_wrapper(project_dir: &Path) -> Option<PathBuf> {
+    let wrapper = if cfg!(windows) { "mvnw.cmd" } else { "mvnw" };
+    project_dir
+        .ancestors()
+        .take_while(|dir| *dir == project_dir || dir.join("pom.xml").is_file())
+        .map(|dir| dir.join(wrapper))
+        .find(|path| path.is_file())
+}
+
+/// Arguments that run a goal: the phase itself, or `-P<id> package` for a
+/// profile
+pub fn goal_args(name: &str) -> Vec<String> {
+    match name.strip_prefix(PROFILE_PREFIX) {
+        Some(id) => vec![format!("-P{}", id), "package".to_string()],
+        None => vec![name.to_string()],
+    }
+}
+
+/// Walk the elements of an XML document, calling `visit` with the path of
+/// open elements (e.g. `["project", "profiles", "profile", "id"]`) and the
+/// text directly inside the innermost one.
+///
+/// Comments, processing instructions and declarations are skipped. This is
+/// not a validating parser: it only needs to follow well-formed POMs.
+fn scan_elements(content: &str, mut visit: impl FnMut(&[&str], &str)) {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find('<') {
+        let text = rest[..start].trim();
+        if !text.is_empty() {
+            visit(&stack, text);
+        }
+        rest = &rest[start..];
+
+        let end_marker = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<![CDATA[") {
+            "]]>"
+        } else {
+            ">"
+        };
+        let Some(end) = rest.find(end_marker) else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + end_marker.len()..];
+
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            if let Some(open) = stack.iter().rposition(|open| *open == name) {
+                stack.truncate(open);
+            }
+            continue;
+        }
+        let self_closing = tag.ends_with('/');
+        let name = tag
+            .trim_end_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+        if !self_closing && !name.is_empty() {
+            stack.push(name);
+        }
+    }
+}
+
+/// The ids of the build profiles declared in a POM, in file order
+pub fn profile_ids(content: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    scan_elements(content, |path, text| {
+        if path.ends_with(&["profiles", "profile", "id"])
+            && path.len() == 4
+            && !ids.iter().any(|id| id == text)
+        {
+            ids.push(text.to_string());
+        }
+    });
+    ids
+}
+
+/// The project's `<description>`, if it has one
+pub fn project_description(content: &str) -> Option<String> {
+    let mut description = None;
+    scan_elements(content, |path, text| {
+        if path == ["project", "description"] && description.is_none() {
+            description = Some(text.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    });
+    description
+}
+
+/// The [`LIFECYCLE_PHASES`] as goals
+pub fn lifecycle_goals(category: &str) -> Vec<MavenGoal> {
+    LIFECYCLE_PHASES
+        .iter()
+        .map(|(name, description)| MavenGoal {
+            name: (*name).to_string(),
+            display_name: format_display_name(name),
+            category: category.to_string(),
+            description: (*description).to_string(),
+        })
+        .collect()
+}
+
+/// List the goals of the Maven project at `pom_path`: the lifecycle phases,
+/// then one goal per profile.
+pub fn list_goals(pom_path: &Path, category: &str) -> Result<Vec<MavenGoal>> {
+    let content = std::fs::read_to_string(pom_path)
+        .with_context(|| format!("Failed to read {}", pom_path.display()))?;
+
+    let mut goals = lifecycle_goals(category);
+    goals.extend(profile_ids(&content).into_iter().map(|id| MavenGoal {
+        name: format!("{}{}", PROFILE_PREFIX, id),
+        display_name: format!("Package ({})", id),
+        category: category.to_string(),
+        description: format!("mvn -P{} package", id),
+    }));
+    Ok(goals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>billing</artifactId>
+  <description>
+    Billing service
+  </description>
+
+  <!-- <profiles><profile><id>commented</id></profile></profiles> -->
+  <profiles>
+    <profile>
+      <id>dev</id>
+      <activation>
+        <activeByDefault>true</activeByDefault>
+      </activation>
+    </profile>
+    <profile>
+      <id>release</id>
+      <repositories>
+        <repository>
+          <id>internal-releases</id>
+          <url>https://repo.example.com/releases</url>
+        </repository>
+      </repositories>
+      <build><plugins/></build>
+    </profile>
+  </profiles>
+</project>
+"#;
+
+    #[test]
+    fn test_profile_ids() {
+        assert_eq!(profile_ids(POM), vec!["dev", "release"]);
+        assert!(profile_ids("<project><modelVersion>4.0.0</modelVersion></project>").is_empty());
+    }
+
+    #[test]
+    fn test_project_description() {
+        assert_eq!(
+            project_description(POM),
+            Some("Billing service".to_string())
+        );
+        assert_eq!(project_description("<project/>"), None);
+    }
+
+    #[test]
+    fn test_lifecycle_goals() {
+        let goals = lifecycle_goals("billing");
+        let names: Vec<&str> = goals.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["clean", "compile", "test", "package", "verify", "install"]
+        );
+        assert!(goals.iter().all(|g| g.category == "billing"));
+        assert_eq!(goals[0].display_name, "Clean");
+    }
+
+    #[test]
+    fn test_list_goals_with_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let pom = temp_dir.path().join("pom.xml");
+        fs::write(&pom, POM).unwrap();
+
+        let goals = list_goals(&pom, "billing").unwrap();
+        assert_eq!(goals.len(), LIFECYCLE_PHASES.len() + 2);
+        let release = goals.last().unwrap();
+        assert_eq!(release.name, "profile:release");
+        assert_eq!(release.display_name, "Package (release)");
+        assert_eq!(release.description, "mvn -Prelease package");
+    }
+
+    #[test]
+    fn test_goal_args() {
+        assert_eq!(goal_args("install"), vec!["install"]);
+        assert_eq!(goal_args("profile:release"), vec!["-Prelease", "package"]);
+    }
+
+    #[test]
+    fn test_maven_wrapper_found_in_parent_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let module = root.join("api");
+        fs::create_dir_all(&module).unwrap();
+        fs::write(root.join("pom.xml"), "<project/>").unwrap();
+        fs::write(module.join("pom.xml"), "<project/>").unwrap();
+        assert_eq!(maven_wrapper(&module), None);
+
+        let wrapper = root.join(if cfg!(windows) { "mvnw.cmd" } else { "mvnw" });
+        fs::write(&wrapper, "#!/bin/sh\n").unwrap();
+        assert_eq!(maven_wrapper(&module), Some(wrapper));
+    }
+}
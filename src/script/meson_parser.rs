@@ -0,0 +1,307 @@
+//! # Meson Parser
+//!
+//! This module lists the targets of a configured [Meson](https://mesonbuild.com)
+//! project for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Like `CMake`, Meson only knows its targets once a build directory has been
+//! set up (`meson setup builddir`), so a `meson.build` is picked up when one
+//! of the [`BUILD_DIRS`] next to it holds Meson's `meson-info/` data. Targets
+//! are read from Meson itself rather than from `meson.build`, which defines
+//! them with arbitrary code:
+//!
+//! ```bash
+//! meson introspect --targets builddir
+//! ```
+//!
+//! The output is a JSON array of targets:
+//!
+//! ```json
+//! [
+//!   {"name": "app", "type": "executable", "subproject": null, ...},
+//!   {"name": "docs", "type": "custom", "subproject": null, ...}
+//! ]
+//! ```
+//!
+//! Targets of subprojects (vendored dependencies) and of types `meson
+//! compile` cannot build are skipped. Commands run as
+//! `meson compile -C <builddir> <name>`, qualified with the target type
+//! (`name:shared_library`) when several targets share a name.
+//!
+//! ## Key Types
+//!
+//! - [`MesonTarget`] - A build target with display metadata
+//! - [`is_meson_available`] - Checks if `meson` is installed
+//! - [`find_build_dir`] - The project's configured build directory
+//! - [`parse_targets`] - Parse the output of `meson introspect --targets`
+//! - [`build_command`] - Arguments that build a target
+//! - [`list_targets`] - Main entry point to list the targets of a project
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for meson availability check (checked once per process)
+static MESON_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Build directory names (relative to `meson.build`) checked for a
+/// configured build, in order
+pub const BUILD_DIRS: &[&str] = &["builddir", "build", "_build"];
+
+/// Target types `meson compile` can build, as reported by introspection
+const BUILDABLE_TYPES: &[&str] = &[
+    "executable",
+    "static library",
+    "shared library",
+    "shared module",
+    "custom",
+    "alias",
+    "run",
+    "jar",
+];
+
+/// Meson target item for TUI display
+#[derive(Debug, Clone)]
+pub struct MesonTarget {
+    /// The name passed to `meson compile` (`name` or `name:type`)
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+    /// The target type (e.g. `executable`, `shared library`)
+    pub target_type: String,
+}
+
+/// A target as reported by `meson introspect --targets`
+#[derive(Debug, Deserialize)]
+struct IntrospectTarget {
+    name: String,
+    #[serde(rename = "type")]
+    target_type: String,
+    #[serde(default)]
+    subproject: Option<String>,
+}
+
+/// Check if the `meson` binary is available.
+pub fn is_meson_available() -> bool {
+    *MESON_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("meson"))
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// The first of the [`BUILD_DIRS`] in `project_dir` that Meson has set up
+pub fn find_build_dir(project_dir: &Path) -> Option<&'static str> {
+    BUILD_DIRS
+        .iter()
+        .copied()
+        .find(|dir| project_dir.join(dir).join("meson-info").is_dir())
+}
+
+/// Arguments that build `name` from `build_dir`
+pub fn build_command(build_dir: &str, name: &str) -> Vec<String> {
+    vec![
+        "compile".to_string(),
+        "-C".to_string(),
+        build_dir.to_string(),
+        name.to_string(),
+    ]
+}
+
+/// Parse the output of `meson introspect --targets` into buildable targets,
+/// sorted by name.
+pub fn parse_targets(json: &str, build_dir: &str, category: &str) -> Result<Vec<MesonTarget>> {
+    let targets: Vec<IntrospectTarget> =
+        serde_json::from_str(json).context("Failed to parse meson introspect output")?;
+
+    let buildable: Vec<IntrospectTarget> = targets
+        .into_iter()
+        .filter(|t| {
+            !t.name.is_empty()
+                && t.subproject.is_none()
+                && BUILDABLE_TYPES.contains(&t.target_type.as_str())
+        })
+        .collect();
+
+    let mut result: Vec<MesonTarget> = buildable
+        .iter()
+        .map(|t| {
+            let ambiguous = buildable.iter().filter(|o| o.name == t.name).count() > 1;
+            let name = if ambiguous {
+                format!("{}:{}", t.name, t.target_type.replace(' ', "_"))
+            } else {
+                t.name.clone()
+            };
+            let display_name = if ambiguous {
+                format!("{} ({})", format_display_name(&t.name), t.target_type)
+            } else {
+                format_display_name(&t.name)
+            };
+            MesonTarget {
+                description: format!(
+                    "meson compile -C {} {} ({})",
+                    build_dir, name, t.target_type
+                ),
+                name,
+                display_name,
+                category: category.to_string(),
+                target_type: t.target_type.clone(),
+            }
+        })
+        .collect();
+
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    result.dedup_by(|a, b| a.name == b.name);
+    Ok(result)
+}
+
+/// Introspect the targets of the Meson project at `meson_build_path`.
+pub fn list_targets(meson_build_path: &Path, category: &str) -> Result<Vec<MesonTarget>> {
+    let project_dir = meson_build_path
+        .parent()
+        .context("Failed to get parent directory of meson.build")?;
+    let build_dir = find_build_dir(project_dir).with_context(|| {
+        format!(
+            "No configured Meson build directory in {}",
+            project_dir.display()
+        )
+    })?;
+
+    let output = Command::new(binaries::program("meson"))
+        .args(["introspect", "--targets", build_dir])
+        .current_dir(project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run meson for: {}", meson_build_path.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "meson introspect failed for {}: {}",
+            meson_build_path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    parse_targets(
+        &String::from_utf8_lossy(&output.stdout),
+        build_dir,
+        category,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TARGETS_JSON: &str = r#"[
+  {
+    "name": "app",
+    "id": "app@exe",
+    "type": "executable",
+    "defined_in": "/src/meson.build",
+    "filename": ["/src/builddir/app"],
+    "build_by_default": true,
+    "subproject": null,
+    "installed": true
+  },
+  {
+    "name": "util",
+    "id": "util@sha",
+    "type": "shared library",
+    "subproject": null
+  },
+  {
+    "name": "util",
+    "id": "util@sta",
+    "type": "static library",
+    "subproject": null
+  },
+  {
+    "name": "docs",
+    "id": "docs@cus",
+    "type": "custom",
+    "subproject": null
+  },
+  {
+    "name": "zlib",
+    "id": "zlib@sta",
+    "type": "static library",
+    "subproject": "zlib"
+  },
+  {
+    "name": "gen",
+    "id": "gen@unknown",
+    "type": "unknown",
+    "subproject": null
+  }
+]"#;
+
+    #[test]
+    fn test_parse_targets() {
+        let targets = parse_targets(TARGETS_JSON, "builddir", "app").unwrap();
+
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["app", "docs", "util:shared_library", "util:static_library"]
+        );
+        assert_eq!(targets[0].display_name, "App");
+        assert_eq!(targets[0].target_type, "executable");
+        assert_eq!(
+            targets[0].description,
+            "meson compile -C builddir app (executable)"
+        );
+        assert_eq!(targets[2].display_name, "Util (shared library)");
+        assert!(targets.iter().all(|t| t.category == "app"));
+    }
+
+    #[test]
+    fn test_parse_targets_skips_non_buildable() {
+        let targets = parse_targets(TARGETS_JSON, "builddir", "app").unwrap();
+        // Subproject targets and unknown types are skipped
+        assert!(!targets.iter().any(|t| t.name == "zlib" || t.name == "gen"));
+
+        assert!(parse_targets("[]", "builddir", "app").unwrap().is_empty());
+        assert!(parse_targets("not json", "builddir", "app").is_err());
+    }
+
+    #[test]
+    fn test_build_command() {
+        assert_eq!(
+            build_command("builddir", "util:static_library"),
+            vec!["compile", "-C", "builddir", "util:static_library"]
+        );
+    }
+
+    #[test]
+    fn test_find_build_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        assert_eq!(find_build_dir(project), None);
+
+        // A plain build/ directory is not a Meson build
+        std::fs::create_dir_all(project.join("build")).unwrap();
+        assert_eq!(find_build_dir(project), None);
+
+        std::fs::create_dir_all(project.join("build/meson-info")).unwrap();
+        assert_eq!(find_build_dir(project), Some("build"));
+
+        std::fs::create_dir_all(project.join("builddir/meson-info")).unwrap();
+        assert_eq!(find_build_dir(project), Some("builddir"));
+    }
+}
@@ -0,0 +1,352 @@
+//! # mise Task Parser
+//!
+//! This module lists the tasks of a [mise](https://mise.jdx.dev) project for
+//! display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Tasks are defined under `[tasks.*]` in `mise.toml`, `.mise.toml` or
+//! `.config/mise/config.toml`:
+//!
+//! ```toml
+//! [tasks.build]
+//! description = "Build the CLI"
+//! run = "cargo build"
+//!
+//! [tasks.test]
+//! run = ["cargo test", "cargo clippy"]
+//!
+//! [tasks]
+//! lint = "cargo clippy"
+//! ```
+//!
+//! When `mise` is installed the parser asks it with `mise tasks ls --json`, so
+//! file tasks (`mise-tasks/`, `.mise/tasks/`) and includes are listed too.
+//! Tasks defined outside the project (e.g. in the global config) are left
+//! out. Without `mise`, or when the CLI fails, the config file is read
+//! directly.
+//!
+//! A task's `description` is shown in the details pane, falling back to its
+//! `run` command. Tasks with `hide = true` are skipped.
+//!
+//! ## Execution
+//!
+//! Tasks run as `mise run <name>` from the project directory.
+//!
+//! ## Key Types
+//!
+//! - [`MiseTask`] - A single task with display metadata
+//! - [`is_mise_available`] - Checks if `mise` is installed
+//! - [`parse_mise_toml`] - Extract tasks from config content
+//! - [`parse_tasks_json`] - Parse the output of `mise tasks ls --json`
+//! - [`project_dir`] - Directory a config file's tasks run from
+//! - [`list_tasks`] - Main entry point to list the tasks of a project
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use toml::Value;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for mise availability check (checked once per process)
+static MISE_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// mise task item for TUI display
+#[derive(Debug, Clone)]
+pub struct MiseTask {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+    pub aliases: Vec<String>,
+}
+
+/// One entry of `mise tasks ls --json`
+#[derive(Debug, Deserialize)]
+struct JsonTask {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    hide: bool,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    run: Vec<String>,
+}
+
+/// Check if `mise` is available.
+pub fn is_mise_available() -> bool {
+    *MISE_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("mise"))
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Directory the tasks of a config file run from: the file's directory, or
+/// the project root for `.config/mise/config.toml`
+pub fn project_dir(config_path: &Path) -> Option<&Path> {
+    let dir = config_path.parent()?;
+    if dir.ends_with(".config/mise") {
+        dir.parent()?.parent()
+    } else {
+        Some(dir)
+    }
+}
+
+/// Build a task, describing it by its `run` commands when it has no description
+fn make_task(
+    name: &str,
+    description: &str,
+    run: &[String],
+    aliases: Vec<String>,
+    category: &str,
+) -> MiseTask {
+    let description = if !description.is_empty() {
+        description.to_string()
+    } else if !run.is_empty() {
+        run.join(" && ")
+    } else {
+        format!("mise task {}", name)
+    };
+    MiseTask {
+        name: name.to_string(),
+        display_name: format_display_name(name),
+        category: category.to_string(),
+        description,
+        aliases,
+    }
+}
+
+/// A value that is either a string or a list of strings (`run`, `alias`)
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(item)) => vec![item.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse the `[tasks]` of mise config content, sorted by name.
+///
+/// Accepts both `[tasks.<name>]` tables and the `<name> = "command"` shorthand.
+pub fn parse_mise_toml(content: &str, category: &str) -> Result<Vec<MiseTask>> {
+    let document: Value = toml::from_str(content).context("Failed to parse mise config")?;
+    let Some(tasks) = document.get("tasks").and_then(Value::as_table) else {
+        return Ok(Vec::new());
+    };
+
+    let mut result: Vec<MiseTask> = Vec::new();
+    for (name, value) in tasks {
+        let task = match value {
+            Value::String(command) => make_task(
+                name,
+                "",
+                std::slice::from_ref(command),
+                Vec::new(),
+                category,
+            ),
+            Value::Table(table) => {
+                if table.get("hide").and_then(Value::as_bool) == Some(true) {
+                    continue;
+                }
+                let description = table
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let aliases = string_list(table.get("alias"));
+                make_task(
+                    name,
+                    description,
+                    &string_list(table.get("run")),
+                    aliases,
+                    category,
+                )
+            }
+            _ => continue,
+        };
+        result.push(task);
+    }
+
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(result)
+}
+
+/// Parse the output of `mise tasks ls --json`, keeping only visible tasks
+/// whose source lies inside `project_dir`.
+pub fn parse_tasks_json(output: &str, project_dir: &Path, category: &str) -> Result<Vec<MiseTask>> {
+    let tasks: Vec<JsonTask> =
+        serde_json::from_str(output).context("Failed to parse mise tasks output")?;
+
+    let mut result: Vec<MiseTask> = tasks
+        .into_iter()
+        .filter(|t| !t.hide)
+        .filter(|t| {
+            t.source
+                .as_deref()
+                .is_none_or(|source| Path::new(source).starts_with(project_dir))
+        })
+        .map(|t| make_task(&t.name, &t.description, &t.run, t.aliases, category))
+        .collect();
+
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(result)
+}
+
+/// Ask `mise` for the tasks of the project at `dir`
+fn list_tasks_from_cli(dir: &Path, category: &str) -> Result<Vec<MiseTask>> {
+    let output = Command::new(binaries::program("mise"))
+        .args(["tasks", "ls", "--json"])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run mise in {}", dir.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "mise tasks ls failed in {}: {}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    // Sources are absolute, so compare against the canonical project path
+    let project = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    parse_tasks_json(&String::from_utf8_lossy(&output.stdout), &project, category)
+}
+
+/// List the tasks of a mise config file, through the CLI when available and
+/// by reading the file otherwise.
+pub fn list_tasks(config_path: &Path, category: &str) -> Result<Vec<MiseTask>> {
+    if is_mise_available() {
+        if let Some(dir) = project_dir(config_path) {
+            if let Ok(tasks) = list_tasks_from_cli(dir, category) {
+                return Ok(tasks);
+            }
+        }
+    }
+
+    let content = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    parse_mise_toml(&content, category)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = r#"
+[tools]
+node = "20"
+
+[tasks.build]
+description = "Build the CLI"
+run = "cargo build"
+alias = "b"
+
+[tasks.test]
+run = ["cargo test", "cargo clippy"]
+depends = ["build"]
+
+[tasks."db:migrate"]
+description = "Run database migrations"
+run = "sqlx migrate run"
+
+[tasks.internal]
+run = "echo hidden"
+hide = true
+
+[tasks.release]
+file = "scripts/release.sh"
+"#;
+
+    #[test]
+    fn test_parse_mise_toml_multiple_tasks() {
+        let tasks = parse_mise_toml(SAMPLE, "app").unwrap();
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "db:migrate", "release", "test"]);
+
+        assert_eq!(tasks[0].description, "Build the CLI");
+        assert_eq!(tasks[0].aliases, vec!["b"]);
+        assert_eq!(tasks[0].category, "app");
+        assert_eq!(tasks[1].description, "Run database migrations");
+        assert_eq!(tasks[1].display_name, "Db:migrate");
+        assert_eq!(tasks[2].description, "mise task release");
+        assert_eq!(tasks[3].description, "cargo test && cargo clippy");
+    }
+
+    #[test]
+    fn test_parse_mise_toml_shorthand_tasks() {
+        let tasks = parse_mise_toml("[tasks]\nlint = \"cargo clippy\"\n", "app").unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].name, "lint");
+        assert_eq!(tasks[0].description, "cargo clippy");
+    }
+
+    #[test]
+    fn test_parse_mise_toml_without_tasks() {
+        assert!(parse_mise_toml("[tools]\nnode = \"20\"\n", "app")
+            .unwrap()
+            .is_empty());
+        assert!(parse_mise_toml("not = [valid", "app").is_err());
+    }
+
+    #[test]
+    fn test_parse_tasks_json_filters_hidden_and_global() {
+        let output = r#"[
+  {"name": "build", "description": "Build it", "aliases": ["b"], "source": "/app/mise.toml", "hide": false, "run": ["cargo build"]},
+  {"name": "lint", "description": "", "source": "/app/mise-tasks/lint", "run": []},
+  {"name": "secret", "description": "", "source": "/app/mise.toml", "hide": true},
+  {"name": "global", "description": "", "source": "/home/me/.config/mise/config.toml"}
+]"#;
+        let tasks = parse_tasks_json(output, Path::new("/app"), "app").unwrap();
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "lint"]);
+        assert_eq!(tasks[0].aliases, vec!["b"]);
+        assert_eq!(tasks[1].description, "mise task lint");
+    }
+
+    #[test]
+    fn test_project_dir() {
+        assert_eq!(
+            project_dir(Path::new("/app/.config/mise/config.toml")),
+            Some(Path::new("/app"))
+        );
+        assert_eq!(
+            project_dir(Path::new("/app/mise.toml")),
+            Some(Path::new("/app"))
+        );
+    }
+
+    #[test]
+    fn test_list_tasks_reads_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(".mise.toml");
+        fs::write(&path, SAMPLE).unwrap();
+
+        // The CLI (when installed) and the file agree on the project's tasks
+        let tasks = list_tasks(&path, "app").unwrap();
+        assert!(tasks.iter().any(|t| t.name == "build"));
+        assert!(!tasks.iter().any(|t| t.name == "internal"));
+    }
+}
@@ -0,0 +1,219 @@
+//! # Mix Parser
+//!
+//! This module lists the tasks of an Elixir [Mix](https://hexdocs.pm/mix)
+//! project for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! A Mix project is marked by a `mix.exs`; its tasks come from Mix itself,
+//! its dependencies and the project, so they are read from the CLI rather
+//! than from source:
+//!
+//! ```bash
+//! mix help
+//! ```
+//!
+//! The output lists one task per line with its short description:
+//!
+//! ```text
+//! mix                   # Runs the default task (current: "mix run")
+//! mix compile           # Compiles source files
+//! mix ecto.migrate      # Runs the repository migrations
+//! iex -S mix            # Starts IEx and runs the default task
+//! ```
+//!
+//! The bare `mix` line, the `iex -S mix` hint and tasks that manage Mix
+//! itself rather than the project (`help`, `new`, `archive.*`, `local.*`,
+//! ...) are left out.
+//!
+//! ## Execution
+//!
+//! Tasks run as `mix <task>` from the project's directory.
+//!
+//! ## Key Types
+//!
+//! - [`MixTask`] - A task with display metadata
+//! - [`is_mix_available`] - Checks if `mix` is installed
+//! - [`parse_help_output`] - Parse the output of `mix help`
+//! - [`list_tasks`] - Main entry point to list the tasks of a project
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for mix availability check (checked once per process)
+static MIX_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Tasks that manage Mix itself or create projects, not useful as commands
+const META_TASKS: &[&str] = &["help", "new", "escript.install", "escript.uninstall"];
+
+/// Namespaces of tasks that install or remove global archives and tools
+const META_NAMESPACES: &[&str] = &["archive", "local"];
+
+/// Mix task item for TUI display
+#[derive(Debug, Clone)]
+pub struct MixTask {
+    /// The task name passed to `mix`
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if the `mix` binary is available.
+pub fn is_mix_available() -> bool {
+    *MIX_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("mix"))
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Whether a task manages Mix itself rather than the project
+fn is_meta_task(name: &str) -> bool {
+    let namespace = name.split('.').next().unwrap_or(name);
+    META_TASKS.contains(&name) || META_NAMESPACES.contains(&namespace)
+}
+
+/// Parse the output of `mix help` into tasks, in listed order.
+///
+/// Only `mix <task>  # <description>` lines are read; meta tasks are skipped.
+pub fn parse_help_output(output: &str, category: &str) -> Vec<MixTask> {
+    let mut tasks: Vec<MixTask> = Vec::new();
+
+    for line in output.lines() {
+        let Some(rest) = line.trim().strip_prefix("mix ") else {
+            continue;
+        };
+        let (name, description) = match rest.split_once('#') {
+            Some((name, description)) => (name.trim(), description.trim()),
+            None => (rest.trim(), ""),
+        };
+        // Tasks are single words; anything else is usage text
+        if name.is_empty() || name.contains(char::is_whitespace) || is_meta_task(name) {
+            continue;
+        }
+        if tasks.iter().any(|t| t.name == name) {
+            continue;
+        }
+
+        let description = if description.is_empty() {
+            format!("mix {}", name)
+        } else {
+            description.to_string()
+        };
+        tasks.push(MixTask {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: category.to_string(),
+            description,
+        });
+    }
+
+    tasks
+}
+
+/// Run `mix help` for the project at `mix_exs_path` and parse the result.
+pub fn list_tasks(mix_exs_path: &Path, category: &str) -> Result<Vec<MixTask>> {
+    let project_dir = mix_exs_path
+        .parent()
+        .context("Failed to get parent directory of mix.exs")?;
+
+    let output = Command::new(binaries::program("mix"))
+        .arg("help")
+        .current_dir(project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run mix for: {}", mix_exs_path.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "mix help failed for {}: {}",
+            mix_exs_path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_help_output(
+        &String::from_utf8_lossy(&output.stdout),
+        category,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELP_OUTPUT: &str = "\
+mix                   # Runs the default task (current: \"mix run\")
+mix app.start         # Starts all registered apps
+mix archive           # Lists installed archives
+mix archive.install   # Installs an archive locally
+mix compile           # Compiles source files
+mix deps.get          # Gets all out of date dependencies
+mix ecto.migrate      # Runs the repository migrations
+mix help              # Prints help information for tasks
+mix local.hex         # Installs Hex locally
+mix new               # Creates a new Elixir project
+mix phx.server        # Starts applications and their servers
+mix test              # Runs a project's tests
+iex -S mix            # Starts IEx and runs the default task
+";
+
+    #[test]
+    fn test_parse_help_output() {
+        let tasks = parse_help_output(HELP_OUTPUT, "shop");
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "app.start",
+                "compile",
+                "deps.get",
+                "ecto.migrate",
+                "phx.server",
+                "test"
+            ]
+        );
+        assert_eq!(tasks[3].display_name, "Ecto Migrate");
+        assert_eq!(tasks[3].description, "Runs the repository migrations");
+        assert_eq!(tasks[5].description, "Runs a project's tests");
+        assert!(tasks.iter().all(|t| t.category == "shop"));
+    }
+
+    #[test]
+    fn test_parse_help_output_without_description() {
+        let tasks = parse_help_output("mix lint\nmix format  #\nmix lint # again\n", "shop");
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].description, "mix lint");
+        assert_eq!(tasks[1].description, "mix format");
+    }
+
+    #[test]
+    fn test_is_meta_task() {
+        assert!(is_meta_task("help"));
+        assert!(is_meta_task("local.rebar"));
+        assert!(is_meta_task("archive.uninstall"));
+        assert!(!is_meta_task("compile"));
+        assert!(!is_meta_task("hex.outdated"));
+        assert!(!is_meta_task("localize"));
+    }
+
+    #[test]
+    fn test_parse_help_output_empty() {
+        assert!(parse_help_output("", "shop").is_empty());
+        assert!(parse_help_output("** (Mix) Could not find a Mix.Project\n", "shop").is_empty());
+    }
+}
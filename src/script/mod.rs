@@ -9,7 +9,9 @@
 //! |------|------|--------|
 //! | Bash | `*.sh` | [`parser::parse_script`] |
 //! | npm | `package.json` | [`npm_parser::parse_package_json`] |
+//! | Composer | `composer.json` | [`composer_parser::parse_composer_json`] |
 //! | Devbox | `devbox.json` | [`devbox_parser::parse_devbox_json`] |
+//! | Deno | `deno.json`, `deno.jsonc` | [`deno_parser::parse_deno_json`] |
 //! | Task | `Taskfile.yml` | [`task_parser::list_tasks`] |
 //! | Makefile | `Makefile` | [`makefile_parser::list_targets`] |
 //! | Just | `justfile` | [`just_parser::list_recipes`] |
@@ -19,39 +21,109 @@
 //! | Gradle | `build.gradle`, `build.gradle.kts` | [`gradle_parser::list_tasks`] |
 //! | Bazel | `WORKSPACE`, `BUILD`, `MODULE.bazel` | [`bazel_parser::list_targets`] |
 //! | GitHub Actions | `.github/workflows/*.yml` | [`github_actions_parser::list_workflows`] |
+//! | GitHub Actions jobs (with `act`) | `.github/workflows/*.yml` | [`actions_parser::list_jobs`] |
 //! | Mage | `magefile.go`, `mage.go` | [`mage_parser::list_targets`] |
+//! | Python | `pyproject.toml` | [`python_parser::list_scripts`] |
+//! | Rake | `Rakefile`, `rakefile`, `Rakefile.rb` | [`rake_parser::list_tasks`] |
+//! | Docker Compose | `compose.yaml`, `docker-compose.yml` | [`compose_parser::list_services`] |
+//! | `CMake` | `CMakeLists.txt` + `build/` | [`cmake_parser::list_targets`] |
+//! | Meson | `meson.build` + a configured build directory | [`meson_parser::list_targets`] |
+//! | Dagger | `dagger.json` | [`dagger_parser::list_functions`] |
+//! | Lefthook | `lefthook.yml`, `.lefthook.yml` | [`lefthook_parser::list_hooks`] |
+//! | pre-commit | `.pre-commit-config.yaml` | [`precommit_parser::list_hooks`] |
+//! | Procfile | `Procfile`, `Procfile.dev` | [`procfile_parser::list_processes`] |
+//! | Mix | `mix.exs` | [`mix_parser::list_tasks`] |
+//! | mise | `mise.toml`, `.mise.toml`, `.config/mise/config.toml` | [`mise_parser::list_tasks`] |
+//! | Ansible | `*.yml` playbooks, `playbooks/` | [`ansible_parser::list_playbooks`] |
+//! | sbt | `build.sbt` | [`sbt_parser::list_tasks`] |
+//! | Maven | `pom.xml` | [`maven_parser::list_goals`] |
+//!
+//! ## Library Usage
+//!
+//! [`discover_all`] runs the same discovery + parse pipeline as the `jarvis`
+//! binary and returns every [`ScriptFunction`] found under a project root,
+//! along with the [`ScriptFile`]s they came from. See [`pipeline`] for details.
+//! Paths matching [`DiscoveryOptions::exclude`] are skipped (see [`exclude`]).
+//!
+//! Each function's [`ScriptType`] converts to and from text: it displays as
+//! its variant name (`PackageJson`), the same string it is serialized as in
+//! usage files and `--run` keys, and parses from that name or its label
+//! (`npm`), ignoring case.
 //!
 
+pub mod actions_parser;
+pub mod ansible_parser;
 pub mod bazel_parser;
+pub mod binaries;
 pub mod cargo_parser;
+pub mod cmake_parser;
+pub mod compose_parser;
+pub mod composer_parser;
+pub mod dagger_parser;
+pub mod deno_parser;
 pub mod devbox_parser;
 pub mod discovery;
+pub mod exclude;
+pub mod git_info;
 pub mod github_actions_parser;
 pub mod gradle_parser;
 pub mod just_parser;
+pub mod lefthook_parser;
 pub mod mage_parser;
 pub mod makefile_parser;
+pub mod maven_parser;
+pub mod meson_parser;
+pub mod mise_parser;
+pub mod mix_parser;
 pub mod npm_parser;
 pub mod nx_parser;
 pub mod parser;
+pub mod pipeline;
+pub mod precommit_parser;
+pub mod procfile_parser;
+pub mod python_parser;
+pub mod rake_parser;
+pub mod sbt_parser;
 pub mod task_parser;
 pub mod terraform_parser;
 pub mod utils;
 
+pub use actions_parser::list_jobs as list_actions_jobs;
+pub use ansible_parser::list_playbooks as list_ansible_playbooks;
 pub use bazel_parser::list_targets as list_bazel_targets;
 pub use cargo_parser::list_targets as list_cargo_targets;
+pub use cmake_parser::list_targets as list_cmake_targets;
+pub use compose_parser::list_services as list_compose_services;
+pub use composer_parser::parse_composer_json;
+pub use dagger_parser::list_functions as list_dagger_functions;
+pub use deno_parser::parse_deno_json;
 pub use devbox_parser::parse_devbox_json;
 pub use discovery::{
-    discover_scripts, discover_scripts_shallow, discover_single_file, format_display_name,
-    prewarm_tool_checks, ScriptFile, ScriptType,
+    category_description, discover_scripts, discover_scripts_shallow, discover_single_file,
+    format_display_name, prewarm_tool_checks, ScriptFile, ScriptType,
 };
+pub use exclude::ExcludeSet;
 pub use github_actions_parser::list_workflows as list_github_workflows;
 pub use gradle_parser::list_tasks as list_gradle_tasks;
 pub use just_parser::list_recipes as list_just_recipes;
+pub use lefthook_parser::list_hooks as list_lefthook_hooks;
 pub use mage_parser::list_targets as list_mage_targets;
 pub use makefile_parser::list_targets as list_make_targets;
+pub use maven_parser::list_goals as list_maven_goals;
+pub use meson_parser::list_targets as list_meson_targets;
+pub use mise_parser::list_tasks as list_mise_tasks;
+pub use mix_parser::list_tasks as list_mix_tasks;
 pub use npm_parser::parse_package_json;
 pub use nx_parser::list_targets as list_nx_targets;
 pub use parser::{parse_script, ScriptFunction};
+pub use pipeline::{
+    discover_all, discover_script_files, parse_script_files, Discovery, DiscoveryOptions,
+    ParseTiming, ParsedScripts,
+};
+pub use precommit_parser::list_hooks as list_pre_commit_hooks;
+pub use procfile_parser::list_processes as list_procfile_processes;
+pub use python_parser::list_scripts as list_python_scripts;
+pub use rake_parser::list_tasks as list_rake_tasks;
+pub use sbt_parser::list_tasks as list_sbt_tasks;
 pub use task_parser::list_tasks;
 pub use terraform_parser::list_commands as list_terraform_commands;
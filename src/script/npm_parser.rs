@@ -20,11 +20,29 @@
 //! The parser extracts these scripts and converts them to [`NpmScript`] structs
 //! for display in the TUI.
 //!
+//! ## Descriptions
+//!
+//! Since `package.json` can't hold comments, scripts can be described in a
+//! sibling `scripts-info` object ([npm-scripts-info] style):
+//!
+//! ```json
+//! {
+//!   "scripts": { "build": "webpack --mode production" },
+//!   "scripts-info": { "build": "Bundle the app for production" }
+//! }
+//! ```
+//!
+//! Scripts without an entry are described by their invocation and command
+//! (e.g. `npm run build - webpack --mode production`).
+//!
+//! [npm-scripts-info]: https://github.com/srph/npm-scripts-info
+//!
 //! ## Key Types
 //!
 //! - [`PackageJson`] - Deserializes the package.json structure
 //! - [`NpmScript`] - Represents a single npm script with display metadata
 //! - [`parse_package_json`] - Main parsing function
+//! - [`detect_package_manager`] - Picks npm, pnpm, yarn or Bun from the lockfile next to `package.json`
 //!
 //! ## Display Name Generation
 //!
@@ -47,6 +65,9 @@ pub struct PackageJson {
     pub name: Option<String>,
     #[serde(default)]
     pub scripts: HashMap<String, String>,
+    /// Script descriptions keyed by script name; non-string values are ignored
+    #[serde(default, rename = "scripts-info")]
+    pub scripts_info: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +80,57 @@ pub struct NpmScript {
     pub command: String,
 }
 
+/// The package manager a project uses to run its scripts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+}
+
+/// Lockfiles identifying each package manager, in detection priority order
+const LOCKFILES: &[(&str, PackageManager)] = &[
+    // Bun's binary lockfile, and the text lockfile of Bun 1.2+
+    ("bun.lockb", PackageManager::Bun),
+    ("bun.lock", PackageManager::Bun),
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("yarn.lock", PackageManager::Yarn),
+    ("package-lock.json", PackageManager::Npm),
+];
+
+impl PackageManager {
+    /// The package manager's binary name
+    pub fn program(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// Arguments that run `script` (`run <script>`, or just `<script>` for yarn)
+    pub fn run_args(self, script: &str) -> Vec<String> {
+        match self {
+            PackageManager::Npm | PackageManager::Pnpm | PackageManager::Bun => {
+                vec!["run".to_string(), script.to_string()]
+            }
+            PackageManager::Yarn => vec![script.to_string()],
+        }
+    }
+}
+
+/// Detect the package manager of the project in `dir` from its lockfile.
+///
+/// Falls back to npm when no known lockfile is present.
+pub fn detect_package_manager(dir: &Path) -> PackageManager {
+    LOCKFILES
+        .iter()
+        .find(|(lockfile, _)| dir.join(lockfile).is_file())
+        .map_or(PackageManager::Npm, |(_, manager)| *manager)
+}
+
 /// Parse a package.json file and extract npm scripts
 pub fn parse_package_json(path: &Path, category: &str) -> Result<Vec<NpmScript>> {
     // Read package.json file
@@ -69,6 +141,7 @@ pub fn parse_package_json(path: &Path, category: &str) -> Result<Vec<NpmScript>>
     let package: PackageJson = serde_json::from_str(&content)
         .with_context(|| format!("Failed to parse package.json: {}", path.display()))?;
 
+    let manager = detect_package_manager(path.parent().unwrap_or(Path::new(".")));
     let mut scripts = Vec::new();
 
     // Convert each npm script to a NpmScript
@@ -77,10 +150,23 @@ pub fn parse_package_json(path: &Path, category: &str) -> Result<Vec<NpmScript>>
         let display_name = format_display_name(&script_name);
 
         // Create description with command preview
-        let description = if script_command.len() > 60 {
-            format!("npm run {} - {}...", script_name, &script_command[..57])
+        let invocation = format!(
+            "{} {}",
+            manager.program(),
+            manager.run_args(&script_name).join(" ")
+        );
+        let info = package
+            .scripts_info
+            .get(&script_name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|info| !info.is_empty());
+        let description = if let Some(info) = info {
+            info.to_string()
+        } else if script_command.len() > 60 {
+            format!("{} - {}...", invocation, &script_command[..57])
         } else {
-            format!("npm run {} - {}", script_name, script_command)
+            format!("{} - {}", invocation, script_command)
         };
 
         scripts.push(NpmScript {
@@ -256,4 +342,106 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].category, "MyCategory");
     }
+
+    #[test]
+    fn test_detect_package_manager_from_lockfile() {
+        for (lockfile, expected) in [
+            ("pnpm-lock.yaml", PackageManager::Pnpm),
+            ("yarn.lock", PackageManager::Yarn),
+            ("package-lock.json", PackageManager::Npm),
+            ("bun.lockb", PackageManager::Bun),
+            ("bun.lock", PackageManager::Bun),
+        ] {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(temp_dir.path().join(lockfile), "").unwrap();
+            assert_eq!(
+                detect_package_manager(temp_dir.path()),
+                expected,
+                "{lockfile}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_package_manager_defaults_to_npm() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(detect_package_manager(temp_dir.path()), PackageManager::Npm);
+    }
+
+    #[test]
+    fn test_detect_package_manager_prefers_pnpm_then_yarn() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package-lock.json"), "").unwrap();
+        fs::write(temp_dir.path().join("yarn.lock"), "").unwrap();
+        assert_eq!(
+            detect_package_manager(temp_dir.path()),
+            PackageManager::Yarn
+        );
+
+        fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(
+            detect_package_manager(temp_dir.path()),
+            PackageManager::Pnpm
+        );
+    }
+
+    #[test]
+    fn test_detect_package_manager_prefers_bun_lockfile() {
+        // Projects moved to Bun often keep their old lockfile around
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package-lock.json"), "").unwrap();
+        fs::write(temp_dir.path().join("bun.lockb"), [0u8, 1, 2]).unwrap();
+        assert_eq!(detect_package_manager(temp_dir.path()), PackageManager::Bun);
+        assert_eq!(PackageManager::Bun.program(), "bun");
+    }
+
+    #[test]
+    fn test_package_manager_run_args() {
+        assert_eq!(PackageManager::Npm.run_args("build"), vec!["run", "build"]);
+        assert_eq!(PackageManager::Pnpm.run_args("build"), vec!["run", "build"]);
+        assert_eq!(PackageManager::Yarn.run_args("build"), vec!["build"]);
+        assert_eq!(PackageManager::Bun.run_args("build"), vec!["run", "build"]);
+    }
+
+    #[test]
+    fn test_parse_package_json_description_uses_package_manager() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_path = temp_dir.path().join("package.json");
+        fs::write(&package_path, r#"{ "scripts": { "test": "jest" } }"#).unwrap();
+        fs::write(temp_dir.path().join("yarn.lock"), "").unwrap();
+
+        let result = parse_package_json(&package_path, "Test").unwrap();
+        assert_eq!(result[0].description, "yarn test - jest");
+    }
+
+    #[test]
+    fn test_parse_package_json_scripts_info_descriptions() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_path = temp_dir.path().join("package.json");
+        fs::write(
+            &package_path,
+            r#"{
+  "scripts": {
+    "build": "webpack --mode production",
+    "lint": "eslint src/",
+    "test": "jest"
+  },
+  "scripts-info": {
+    "build": "Bundle the app for production",
+    "test": "  Run the unit tests  ",
+    "lint": { "not": "a string" },
+    "deploy": "Describes a script that does not exist"
+  }
+}"#,
+        )
+        .unwrap();
+
+        let result = parse_package_json(&package_path, "Test").unwrap();
+        let names: Vec<&str> = result.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "lint", "test"]);
+        assert_eq!(result[0].description, "Bundle the app for production");
+        assert_eq!(result[1].description, "npm run lint - eslint src/");
+        assert_eq!(result[2].description, "Run the unit tests");
+        assert_eq!(result[2].command, "jest");
+    }
 }
@@ -30,6 +30,31 @@
 //! npx nx run <project>:<target>
 //! ```
 //!
+//! ## Metadata Annotations
+//!
+//! Projects and targets can carry Jarvis annotations in their Nx `metadata`,
+//! mirroring the `@emoji` / `@description` / `@ignore` comments other parsers read:
+//!
+//! ```json
+//! {
+//!   "metadata": { "jarvis": { "emoji": "🚀" } },
+//!   "tags": ["jarvis:ignore"],
+//!   "targets": {
+//!     "deploy": {
+//!       "metadata": {
+//!         "description": "Deploy to staging",
+//!         "jarvis": { "emoji": "🚢", "ignore": false }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! - Project-level `metadata.jarvis` applies to every target of the project
+//! - Target-level `metadata.jarvis` overrides the project-level values
+//! - A target's native Nx `metadata.description` is used as its description
+//! - The `jarvis:ignore` project tag hides all of the project's targets
+//!
 //! ## Availability Caching
 //!
 //! The Nx binary availability is cached using [`OnceLock`] to avoid
@@ -42,6 +67,7 @@ use std::sync::OnceLock;
 use anyhow::{Context, Result};
 use serde_json::Value;
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
 
 /// Cache for nx availability check (checked once per process)
@@ -61,6 +87,51 @@ pub struct NxTarget {
     pub ignored: bool,
     pub project: String,
     pub target: String,
+    /// Project root relative to the workspace (e.g. `apps/web`), if reported by Nx
+    pub root: Option<String>,
+}
+
+/// Jarvis annotations read from Nx project/target `metadata`
+#[derive(Debug, Clone, Default, PartialEq)]
+struct NxAnnotations {
+    emoji: Option<String>,
+    description: Option<String>,
+    ignored: Option<bool>,
+}
+
+impl NxAnnotations {
+    /// Read annotations from a `metadata` object: `metadata.jarvis.{emoji,description,ignore}`,
+    /// falling back to Nx's own `metadata.description`.
+    fn from_metadata(metadata: Option<&Value>) -> Self {
+        let Some(metadata) = metadata else {
+            return Self::default();
+        };
+        let jarvis = metadata.get("jarvis");
+        let non_empty_str = |v: Option<&Value>| {
+            v.and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+
+        Self {
+            emoji: non_empty_str(jarvis.and_then(|j| j.get("emoji"))),
+            description: non_empty_str(jarvis.and_then(|j| j.get("description")))
+                .or_else(|| non_empty_str(metadata.get("description"))),
+            ignored: jarvis
+                .and_then(|j| j.get("ignore"))
+                .and_then(Value::as_bool),
+        }
+    }
+
+    /// Layer `self` (more specific) over `base` (less specific)
+    fn or(self, base: &Self) -> Self {
+        Self {
+            emoji: self.emoji.or_else(|| base.emoji.clone()),
+            description: self.description.or_else(|| base.description.clone()),
+            ignored: self.ignored.or(base.ignored),
+        }
+    }
 }
 
 /// Check if `nx` is available (either via `npx` or globally).
@@ -70,7 +141,7 @@ pub fn is_nx_available() -> bool {
     *NX_AVAILABLE.get_or_init(|| {
         // Check for local nx via npx first
         // stdin must be null to prevent npx from prompting to install nx
-        let npx_available = Command::new("npx")
+        let npx_available = Command::new(binaries::program("npx"))
             .args(["nx", "--version"])
             .stdin(Stdio::null())
             .stdout(Stdio::null())
@@ -84,7 +155,7 @@ pub fn is_nx_available() -> bool {
         }
 
         // Fall back to global nx
-        Command::new("nx")
+        Command::new(binaries::program("nx"))
             .arg("--version")
             .stdin(Stdio::null())
             .stdout(Stdio::null())
@@ -101,7 +172,7 @@ pub fn is_nx_available() -> bool {
 /// Caches the result to avoid repeated subprocess spawns.
 pub fn nx_command() -> (&'static str, Vec<&'static str>) {
     let use_npx = *NX_USE_NPX.get_or_init(|| {
-        Command::new("npx")
+        Command::new(binaries::program("npx"))
             .args(["nx", "--version"])
             .stdin(Stdio::null())
             .stdout(Stdio::null())
@@ -127,7 +198,7 @@ fn fetch_project_graph(workspace_dir: &Path) -> Result<Value> {
     let (cmd, mut base_args) = nx_command();
     base_args.extend(["graph", "--file=stdout"]);
 
-    let output = Command::new(cmd)
+    let output = Command::new(binaries::program(cmd))
         .args(&base_args)
         .current_dir(workspace_dir)
         .stdin(Stdio::null())
@@ -181,21 +252,42 @@ fn extract_targets_from_graph(graph: &Value, workspace_name: &str) -> Vec<NxTarg
         };
 
         let project_category = format!("nx:{}:{}", workspace_name, project_name);
-
-        for (target_name, _target_config) in targets_obj {
+        let data = node.get("data");
+        let root = data
+            .and_then(|d| d.get("root"))
+            .and_then(Value::as_str)
+            .map(String::from);
+
+        let mut project_annotations =
+            NxAnnotations::from_metadata(data.and_then(|d| d.get("metadata")));
+        // Project descriptions describe the project, not each of its targets
+        project_annotations.description = None;
+        let project_ignored_by_tag = data
+            .and_then(|d| d.get("tags"))
+            .and_then(Value::as_array)
+            .is_some_and(|tags| tags.iter().any(|t| t.as_str() == Some("jarvis:ignore")));
+
+        for (target_name, target_config) in targets_obj {
             let qualified_name = format!("{}:{}", project_name, target_name);
             let display_name = format_display_name(target_name);
-            let description = format!("nx run {}:{}", project_name, target_name);
+            let annotations = NxAnnotations::from_metadata(target_config.get("metadata"))
+                .or(&project_annotations);
+            let description = annotations
+                .description
+                .unwrap_or_else(|| format!("nx run {}:{}", project_name, target_name));
 
             all_targets.push(NxTarget {
                 name: qualified_name,
                 display_name,
                 category: project_category.clone(),
                 description,
-                emoji: Some("\u{1f537}".to_string()), // 🔷
-                ignored: false,
-                project: project_name.to_string(),
-                target: target_name.to_string(),
+                emoji: Some(
+                    annotations.emoji.unwrap_or_else(|| "\u{1f537}".to_string()), // 🔷
+                ),
+                ignored: annotations.ignored.unwrap_or(project_ignored_by_tag),
+                project: project_name.clone(),
+                target: target_name.clone(),
+                root: root.clone(),
             });
         }
     }
@@ -248,11 +340,29 @@ pub fn collect_category_display_names(
     names
 }
 
+/// Collect per-project category descriptions from a list of Nx targets.
+///
+/// Returns a map from category key to a short description naming the
+/// project's root path (e.g. `"Nx project root: apps/web"`).
+pub fn collect_category_descriptions(
+    targets: &[NxTarget],
+) -> std::collections::HashMap<String, String> {
+    let mut descriptions = std::collections::HashMap::new();
+    for target in targets {
+        if let Some(ref root) = target.root {
+            descriptions
+                .entry(target.category.clone())
+                .or_insert_with(|| format!("Nx project root: {}", root));
+        }
+    }
+    descriptions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    /// Build a graph JSON value from a list of (project_name, [target_names]) pairs.
+    /// Build a graph JSON value from a list of (`project_name`, [`target_names`]) pairs.
     fn build_graph_json(projects: &[(&str, &[&str])]) -> Value {
         let mut nodes = serde_json::Map::new();
         for (project, targets) in projects {
@@ -491,4 +601,98 @@ mod tests {
             Some(&"🔷 Service Api".to_string())
         );
     }
+
+    #[test]
+    fn test_collect_category_descriptions() {
+        let graph = build_graph_json(&[("web", &["build"])]);
+        let all_targets = extract_targets_from_graph(&graph, "monopoly");
+
+        let descriptions = collect_category_descriptions(&all_targets);
+        assert_eq!(
+            descriptions.get("nx:monopoly:web"),
+            Some(&"Nx project root: apps/web".to_string())
+        );
+    }
+
+    fn graph_with_metadata() -> Value {
+        serde_json::json!({
+            "graph": {
+                "nodes": {
+                    "web": {
+                        "name": "web",
+                        "type": "app",
+                        "data": {
+                            "root": "apps/web",
+                            "metadata": { "jarvis": { "emoji": "🌐", "description": "Web app" } },
+                            "targets": {
+                                "build": {},
+                                "deploy": {
+                                    "metadata": {
+                                        "description": "Deploy to staging",
+                                        "jarvis": { "emoji": "🚀" }
+                                    }
+                                },
+                                "internal": {
+                                    "metadata": { "jarvis": { "ignore": true } }
+                                }
+                            }
+                        }
+                    },
+                    "legacy": {
+                        "name": "legacy",
+                        "type": "lib",
+                        "data": {
+                            "root": "libs/legacy",
+                            "tags": ["scope:shared", "jarvis:ignore"],
+                            "targets": {
+                                "build": {},
+                                "lint": { "metadata": { "jarvis": { "ignore": false } } }
+                            }
+                        }
+                    }
+                },
+                "dependencies": {}
+            }
+        })
+    }
+
+    #[test]
+    fn test_extract_targets_project_metadata_emoji() {
+        let targets = extract_targets_from_graph(&graph_with_metadata(), "ws");
+        let build = targets.iter().find(|t| t.name == "web:build").unwrap();
+
+        assert_eq!(build.emoji.as_deref(), Some("🌐"));
+        // Project-level descriptions are not copied onto targets
+        assert_eq!(build.description, "nx run web:build");
+        assert!(!build.ignored);
+    }
+
+    #[test]
+    fn test_extract_targets_target_metadata_overrides_project() {
+        let targets = extract_targets_from_graph(&graph_with_metadata(), "ws");
+        let deploy = targets.iter().find(|t| t.name == "web:deploy").unwrap();
+
+        assert_eq!(deploy.emoji.as_deref(), Some("🚀"));
+        assert_eq!(deploy.description, "Deploy to staging");
+    }
+
+    #[test]
+    fn test_extract_targets_metadata_ignore() {
+        let targets = extract_targets_from_graph(&graph_with_metadata(), "ws");
+        let internal = targets.iter().find(|t| t.name == "web:internal").unwrap();
+
+        assert!(internal.ignored);
+    }
+
+    #[test]
+    fn test_extract_targets_ignore_tag() {
+        let targets = extract_targets_from_graph(&graph_with_metadata(), "ws");
+        let build = targets.iter().find(|t| t.name == "legacy:build").unwrap();
+        let lint = targets.iter().find(|t| t.name == "legacy:lint").unwrap();
+
+        assert!(build.ignored);
+        assert_eq!(build.emoji.as_deref(), Some("🔷"));
+        // An explicit target-level `ignore: false` wins over the project tag
+        assert!(!lint.ignored);
+    }
 }
@@ -42,7 +42,23 @@
 //! |------------|-------------|
 //! | `@emoji <emoji>` | Display emoji prefix in the TUI |
 //! | `@description <text>` | Custom description for the details panel |
+//! | `@description-start` ... `@description-end` | Multi-line description (the comment lines in between) |
 //! | `@ignore` | Hide the function from the TUI |
+//! | `@sudo` | Run the function via `sudo` (always asks for confirmation) |
+//! | `@category <name>` | Show the function under another category |
+//!
+//! A plain `# usage: <text>` (or `# args: <text>`) comment documents the
+//! arguments the function expects and is shown next to its name:
+//!
+//! ```bash
+//! # usage: deploy <env> [--force]
+//! deploy() {
+//!     ./deploy.sh "$@"
+//! }
+//! ```
+//!
+//! Consecutive `@description` lines also make a multi-line description, one
+//! line each (see [`comment_description`]).
 //!
 //! ## Key Types
 //!
@@ -50,13 +66,14 @@
 
 use anyhow::{Context, Result};
 use regex::Regex;
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 
 use crate::script::discovery::{format_display_name, ScriptType};
-use crate::script::utils::is_valid_bash_identifier;
+use crate::script::utils::{comment_description, is_valid_bash_identifier};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ScriptFunction {
     pub name: String,
     pub display_name: String,
@@ -64,6 +81,18 @@ pub struct ScriptFunction {
     pub description: String,
     pub emoji: Option<String>,
     pub ignored: bool,
+    /// Whether the command must be run with elevated privileges via `sudo`
+    pub sudo: bool,
+    /// Alternative names the command is also known by (e.g. go-task `aliases`)
+    pub aliases: Vec<String>,
+    /// Category of the script file the function was found in, when an
+    /// `@category` annotation moved it to another group (`None` otherwise)
+    pub file_category: Option<String>,
+    /// Argument hint from a `# usage:` / `# args:` comment above a bash function
+    pub usage: Option<String>,
+    /// 1-based line of the definition in its script file, when the parser
+    /// tracks it (bash functions)
+    pub line: Option<usize>,
     pub script_type: ScriptType,
 }
 
@@ -87,10 +116,14 @@ pub fn parse_script(path: &Path, category: &str) -> Result<Vec<ScriptFunction>>
     // Regex patterns for metadata comments
     let emoji_re =
         Regex::new(r"^\s*#\s*@emoji\s+(.+)$").context("Failed to compile emoji regex pattern")?;
-    let desc_re = Regex::new(r"^\s*#\s*@description\s+(.+)$")
-        .context("Failed to compile description regex pattern")?;
     let ignore_re =
         Regex::new(r"^\s*#\s*@ignore\s*$").context("Failed to compile ignore regex pattern")?;
+    let sudo_re =
+        Regex::new(r"^\s*#\s*@sudo\s*$").context("Failed to compile sudo regex pattern")?;
+    let category_re = Regex::new(r"^\s*#\s*@category\s+(.+)$")
+        .context("Failed to compile category regex pattern")?;
+    let usage_re = Regex::new(r"(?i)^\s*#\s*(?:usage|args):\s*(.+)$")
+        .context("Failed to compile usage regex pattern")?;
     let comment_re = Regex::new(r"^\s*#").context("Failed to compile comment regex pattern")?;
 
     // Iterate through lines to find function definitions
@@ -105,10 +138,13 @@ pub fn parse_script(path: &Path, category: &str) -> Result<Vec<ScriptFunction>>
 
             // Extract metadata from preceding comment lines
             let mut emoji: Option<String> = None;
-            let mut description: Option<String> = None;
             let mut ignored = false;
+            let mut sudo = false;
+            let mut category_override: Option<String> = None;
+            let mut usage: Option<String> = None;
 
             // Look backwards from the function line, but only through consecutive comment lines
+            let mut block_start = line_idx;
             let mut check_idx = line_idx.saturating_sub(1);
             loop {
                 if check_idx >= line_idx {
@@ -121,20 +157,31 @@ pub fn parse_script(path: &Path, category: &str) -> Result<Vec<ScriptFunction>>
                 if !prev_line.trim().is_empty() && !comment_re.is_match(prev_line) {
                     break;
                 }
+                block_start = check_idx;
 
                 // Check for ignore annotation
                 if ignore_re.is_match(prev_line) {
                     ignored = true;
                 }
 
+                // Check for sudo annotation
+                if sudo_re.is_match(prev_line) {
+                    sudo = true;
+                }
+
                 // Check for emoji annotation
                 if let Some(emoji_cap) = emoji_re.captures(prev_line) {
                     emoji = Some(emoji_cap[1].trim().to_string());
                 }
 
-                // Check for description annotation
-                if let Some(desc_cap) = desc_re.captures(prev_line) {
-                    description = Some(desc_cap[1].trim().to_string());
+                // Check for category annotation
+                if let Some(category_cap) = category_re.captures(prev_line) {
+                    category_override = Some(category_cap[1].trim().to_string());
+                }
+
+                // Check for usage hint
+                if let Some(usage_cap) = usage_re.captures(prev_line) {
+                    usage = Some(usage_cap[1].trim().to_string());
                 }
 
                 if check_idx == 0 {
@@ -143,6 +190,8 @@ pub fn parse_script(path: &Path, category: &str) -> Result<Vec<ScriptFunction>>
                 check_idx -= 1;
             }
 
+            let description = comment_description(&lines[block_start..line_idx], "#");
+
             // Auto-generate display name from function name
             let display_name = format_display_name(func_name);
 
@@ -150,13 +199,24 @@ pub fn parse_script(path: &Path, category: &str) -> Result<Vec<ScriptFunction>>
             let final_description =
                 description.unwrap_or_else(|| format!("Execute: {}", display_name));
 
+            // An @category annotation takes precedence over the file's category
+            let file_category = category_override
+                .as_ref()
+                .filter(|c| c.as_str() != category)
+                .map(|_| category.to_string());
+
             functions.push(ScriptFunction {
                 name: func_name.to_string(),
                 display_name: display_name.clone(),
-                category: category.to_string(),
+                category: category_override.unwrap_or_else(|| category.to_string()),
                 description: final_description,
                 emoji,
                 ignored,
+                sudo,
+                aliases: Vec::new(),
+                file_category,
+                usage,
+                line: Some(line_idx + 1),
                 script_type: ScriptType::Bash,
             });
         }
@@ -189,6 +249,22 @@ hello_world() {
         assert_eq!(result[0].name, "hello_world");
         assert_eq!(result[0].display_name, "Hello World");
         assert_eq!(result[0].category, "Test");
+        assert_eq!(result[0].line, Some(3));
+    }
+
+    #[test]
+    fn test_parse_script_tracks_definition_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("test.sh");
+
+        let content =
+            "#!/bin/bash\n\n# Build it\nbuild() {\n  make\n}\n\nfunction deploy() {\n  ./deploy\n}\n";
+        fs::write(&script_path, content).unwrap();
+
+        let result = parse_script(&script_path, "Test").unwrap();
+        let lines: Vec<(&str, Option<usize>)> =
+            result.iter().map(|f| (f.name.as_str(), f.line)).collect();
+        assert_eq!(lines, vec![("build", Some(4)), ("deploy", Some(8))]);
     }
 
     #[test]
@@ -441,6 +517,44 @@ my_function() {
         assert_eq!(result[0].description, "This is a custom description");
     }
 
+    #[test]
+    fn test_parse_script_multiline_description() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("test.sh");
+
+        let content = r#"#!/bin/bash
+
+# @description Deploy the application
+# @description to the production cluster
+# @emoji 🚀
+deploy() {
+    echo "deploy"
+}
+
+# Not part of the description
+# @description-start
+# Restore the database from the latest backup.
+#
+# Stops the app while restoring.
+# @description-end
+restore() {
+    echo "restore"
+}
+"#;
+        fs::write(&script_path, content).unwrap();
+
+        let result = parse_script(&script_path, "Test").unwrap();
+        assert_eq!(
+            result[0].description,
+            "Deploy the application\nto the production cluster"
+        );
+        assert_eq!(result[0].emoji, Some("🚀".to_string()));
+        assert_eq!(
+            result[1].description,
+            "Restore the database from the latest backup.\n\nStops the app while restoring."
+        );
+    }
+
     #[test]
     fn test_parse_script_annotations_with_spacing() {
         let temp_dir = TempDir::new().unwrap();
@@ -486,11 +600,11 @@ public_function() {
 
         // First function should be ignored
         let format_func = result.iter().find(|f| f.name == "format_string").unwrap();
-        assert_eq!(format_func.ignored, true);
+        assert!(format_func.ignored);
 
         // Second function should not be ignored
         let public_func = result.iter().find(|f| f.name == "public_function").unwrap();
-        assert_eq!(public_func.ignored, false);
+        assert!(!public_func.ignored);
     }
 
     #[test]
@@ -523,13 +637,13 @@ deploy() {
             .iter()
             .find(|f| f.name == "_helper_function")
             .unwrap();
-        assert_eq!(helper.ignored, true);
+        assert!(helper.ignored);
         assert_eq!(helper.emoji, Some("🔧".to_string()));
         assert_eq!(helper.description, "Helper function for string formatting");
 
         // Deploy function should not be ignored
         let deploy = result.iter().find(|f| f.name == "deploy").unwrap();
-        assert_eq!(deploy.ignored, false);
+        assert!(!deploy.ignored);
         assert_eq!(deploy.emoji, Some("🚀".to_string()));
         assert_eq!(deploy.description, "Main deployment function");
     }
@@ -571,6 +685,128 @@ another_helper() {
 
         // Verify main_function is not ignored
         let main = result.iter().find(|f| f.name == "main_function").unwrap();
-        assert_eq!(main.ignored, false);
+        assert!(!main.ignored);
+    }
+
+    #[test]
+    fn test_parse_script_category_annotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("test.sh");
+
+        let content = r#"#!/bin/bash
+
+# @category Deployment
+deploy() {
+    ./deploy.sh
+}
+
+# @category Test
+status() {
+    echo ok
+}
+
+build() {
+    make
+}
+"#;
+        fs::write(&script_path, content).unwrap();
+
+        let result = parse_script(&script_path, "Test").unwrap();
+
+        let deploy = result.iter().find(|f| f.name == "deploy").unwrap();
+        assert_eq!(deploy.category, "Deployment");
+        assert_eq!(deploy.file_category, Some("Test".to_string()));
+
+        // Naming the file's own category is not an override
+        let status = result.iter().find(|f| f.name == "status").unwrap();
+        assert_eq!(status.category, "Test");
+        assert_eq!(status.file_category, None);
+
+        let build = result.iter().find(|f| f.name == "build").unwrap();
+        assert_eq!(build.category, "Test");
+        assert_eq!(build.file_category, None);
+    }
+
+    #[test]
+    fn test_parse_script_usage_comment() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("test.sh");
+
+        let content = r#"#!/bin/bash
+
+# Deploy the app
+# usage: deploy <env> [--force]
+deploy() {
+    ./deploy.sh "$@"
+}
+
+# Args: <name>
+greet() {
+    echo "hello $1"
+}
+"#;
+        fs::write(&script_path, content).unwrap();
+
+        let result = parse_script(&script_path, "Test").unwrap();
+
+        let deploy = result.iter().find(|f| f.name == "deploy").unwrap();
+        assert_eq!(deploy.usage.as_deref(), Some("deploy <env> [--force]"));
+        let greet = result.iter().find(|f| f.name == "greet").unwrap();
+        assert_eq!(greet.usage.as_deref(), Some("<name>"));
+    }
+
+    #[test]
+    fn test_parse_script_without_usage_comment() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("test.sh");
+
+        let content = r#"#!/bin/bash
+
+# usage: belongs to nothing
+
+echo "top level"
+
+# @description Build it
+build() {
+    make
+}
+"#;
+        fs::write(&script_path, content).unwrap();
+
+        let result = parse_script(&script_path, "Test").unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].usage, None);
+    }
+
+    #[test]
+    fn test_parse_script_sudo_annotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("test.sh");
+
+        let content = r#"#!/bin/bash
+
+# @sudo
+# @description Restart the system service
+restart_service() {
+    systemctl restart my-service
+}
+
+status() {
+    systemctl status my-service
+}
+"#;
+        fs::write(&script_path, content).unwrap();
+
+        let result = parse_script(&script_path, "Test").unwrap();
+        assert_eq!(result.len(), 2);
+
+        let restart = result.iter().find(|f| f.name == "restart_service").unwrap();
+        assert!(restart.sudo);
+        assert!(!restart.ignored);
+        assert_eq!(restart.description, "Restart the system service");
+
+        let status = result.iter().find(|f| f.name == "status").unwrap();
+        assert!(!status.sudo);
     }
 }
@@ -0,0 +1,1200 @@
+//! # Discovery Pipeline
+//!
+//! Runs the full discovery + parse pipeline used by the `jarvis` binary, so
+//! other Rust programs can reuse Jarvis's multi-tool discovery without the TUI.
+//!
+//! ## Overview
+//!
+//! 1. [`discover_script_files`] scans the project root (shallow) plus the
+//!    optional `script/`, `scripts/` and `jarvis/` subdirectories, or the
+//!    whole tree down to [`DiscoveryOptions::depth`] when a depth is set,
+//!    skipping paths that match [`DiscoveryOptions::exclude`].
+//! 2. [`parse_script_files`] parses every [`ScriptFile`] in parallel (one
+//!    thread per file, since most parsers shell out to their tool) and maps
+//!    the results to [`ScriptFunction`]s, dropping ignored entries.
+//! 3. [`discover_all`] chains both steps.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use jarvis::script::{self, DiscoveryOptions};
+//! use std::path::Path;
+//!
+//! let discovery = script::discover_all(Path::new("."), &DiscoveryOptions::default())?;
+//! for func in &discovery.functions {
+//!     println!("{} [{}] {:?}", func.name, func.category, func.script_type);
+//! }
+//! for (path, err) in &discovery.parse_errors {
+//!     eprintln!("failed to parse {path}: {err}");
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::script::discovery::{
+    category_description, discover_scripts_excluding, prewarm_tool_checks, ScriptFile, ScriptType,
+};
+use crate::script::exclude::ExcludeSet;
+use crate::script::{
+    bazel_parser, cargo_parser, gradle_parser, just_parser, list_actions_jobs,
+    list_ansible_playbooks, list_bazel_targets, list_cargo_targets, list_cmake_targets,
+    list_compose_services, list_dagger_functions, list_github_workflows, list_gradle_tasks,
+    list_just_recipes, list_lefthook_hooks, list_mage_targets, list_make_targets, list_maven_goals,
+    list_meson_targets, list_mise_tasks, list_mix_tasks, list_nx_targets, list_pre_commit_hooks,
+    list_procfile_processes, list_python_scripts, list_rake_tasks, list_sbt_tasks, list_tasks,
+    list_terraform_commands, nx_parser, parse_composer_json, parse_deno_json, parse_devbox_json,
+    parse_package_json, parse_script, task_parser, ScriptFunction,
+};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Subdirectories scanned (recursively) in addition to the project root
+pub const DEFAULT_SCRIPT_DIRS: &[&str] = &["script", "scripts", "jarvis"];
+
+/// Options controlling where [`discover_all`] looks for scripts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryOptions {
+    /// Subdirectories of the root to scan recursively (missing ones are skipped)
+    pub script_dirs: Vec<String>,
+    /// Pre-warm tool availability checks in background threads before scanning
+    pub prewarm: bool,
+    /// Scan every directory up to this many levels below the root instead of
+    /// the root plus `script_dirs` (`Some(0)` scans only the root itself)
+    pub depth: Option<usize>,
+    /// Glob patterns of files and directories to skip, matched against paths
+    /// relative to the root (see [`ExcludeSet`])
+    pub exclude: Vec<String>,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            script_dirs: DEFAULT_SCRIPT_DIRS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            prewarm: true,
+            depth: None,
+            exclude: Vec::new(),
+        }
+    }
+}
+
+/// Time spent parsing a single script file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParseTiming {
+    /// Type of the parsed file
+    pub script_type: ScriptType,
+    /// Number of (visible) functions the file produced
+    pub functions: usize,
+    /// Time spent parsing the file
+    pub elapsed: Duration,
+}
+
+/// Result of parsing a set of script files
+#[derive(Debug, Default)]
+pub struct ParsedScripts {
+    /// All visible functions, in script file order
+    pub functions: Vec<ScriptFunction>,
+    /// Human-readable name for each category key
+    pub category_display_names: HashMap<String, String>,
+    /// Description for categories that have one (file header, manifest, Nx project root)
+    pub category_descriptions: HashMap<String, String>,
+    /// Files that failed to parse, as `(path, error)`
+    pub parse_errors: Vec<(String, anyhow::Error)>,
+    /// Per-file parse timings (files whose parser panicked are omitted)
+    pub timings: Vec<ParseTiming>,
+}
+
+/// Everything discovered under a project root
+#[derive(Debug, Default)]
+pub struct Discovery {
+    /// The discovered script files
+    pub script_files: Vec<ScriptFile>,
+    /// All visible functions, in script file order
+    pub functions: Vec<ScriptFunction>,
+    /// Human-readable name for each category key
+    pub category_display_names: HashMap<String, String>,
+    /// Description for categories that have one
+    pub category_descriptions: HashMap<String, String>,
+    /// Files that failed to parse, as `(path, error)`
+    pub parse_errors: Vec<(String, anyhow::Error)>,
+}
+
+/// Discover script files under `root` and parse them into functions.
+///
+/// Parse failures of individual files do not fail the whole call; they are
+/// reported in [`Discovery::parse_errors`].
+pub fn discover_all(root: &Path, options: &DiscoveryOptions) -> Result<Discovery> {
+    let script_files = discover_script_files(root, options)?;
+    let parsed = parse_script_files(&script_files);
+
+    Ok(Discovery {
+        script_files,
+        functions: parsed.functions,
+        category_display_names: parsed.category_display_names,
+        category_descriptions: parsed.category_descriptions,
+        parse_errors: parsed.parse_errors,
+    })
+}
+
+/// Discover script files from the root directory (shallow) and the configured
+/// script subdirectories (recursive), or from the whole tree down to
+/// [`DiscoveryOptions::depth`] when it is set.
+pub fn discover_script_files(root: &Path, options: &DiscoveryOptions) -> Result<Vec<ScriptFile>> {
+    if options.prewarm {
+        prewarm_tool_checks();
+    }
+
+    let excludes = ExcludeSet::new(&options.exclude)?;
+
+    if let Some(depth) = options.depth {
+        // Walk depth counts the root itself, so files directly in it are at 1
+        return discover_scripts_excluding(root, depth + 1, &excludes, root)
+            .with_context(|| format!("Failed to discover scripts in: {}", root.display()));
+    }
+
+    let mut script_files = discover_scripts_excluding(root, 1, &excludes, root)
+        .with_context(|| format!("Failed to discover scripts in: {}", root.display()))?;
+
+    for dir_name in &options.script_dirs {
+        let dir_path = root.join(dir_name);
+        if dir_path.is_dir() {
+            let files =
+                discover_scripts_excluding(&dir_path, 2, &excludes, root).with_context(|| {
+                    format!("Failed to discover scripts in: {}", dir_path.display())
+                })?;
+            script_files.extend(files);
+        }
+    }
+
+    Ok(script_files)
+}
+
+/// Outcome of parsing one script file on a worker thread
+enum ParseResult {
+    Functions(Vec<ScriptFunction>),
+    GroupedFunctions(
+        Vec<ScriptFunction>,
+        HashMap<String, String>,
+        HashMap<String, String>,
+    ),
+    Error(String, anyhow::Error),
+}
+
+/// Parse script files in parallel (one thread per file) into functions.
+pub fn parse_script_files(script_files: &[ScriptFile]) -> ParsedScripts {
+    let mut parsed = ParsedScripts::default();
+
+    // Category display names and descriptions derived from the files themselves
+    for script_file in script_files {
+        parsed.category_display_names.insert(
+            script_file.category.clone(),
+            script_file.display_name.clone(),
+        );
+        if let Some(description) = category_description(script_file) {
+            parsed
+                .category_descriptions
+                .entry(script_file.category.clone())
+                .or_insert(description);
+        }
+    }
+
+    let handles: Vec<_> = script_files
+        .iter()
+        .map(|script_file| {
+            let path = script_file.path.clone();
+            let category = script_file.category.clone();
+            let script_type = script_file.script_type;
+
+            std::thread::spawn(move || {
+                let started = Instant::now();
+                let result = parse_one(&path, &category, script_type);
+                (result, started.elapsed())
+            })
+        })
+        .collect();
+
+    for (handle, script_file) in handles.into_iter().zip(script_files) {
+        match handle.join() {
+            Ok((result, elapsed)) => {
+                let function_count = match result {
+                    ParseResult::Functions(functions) => {
+                        let count = functions.len();
+                        parsed.functions.extend(functions);
+                        count
+                    }
+                    ParseResult::GroupedFunctions(functions, display_names, descriptions) => {
+                        // One category per Nx project, Gradle task group or Taskfile namespace
+                        let count = functions.len();
+                        parsed.functions.extend(functions);
+                        parsed.category_display_names.extend(display_names);
+                        parsed.category_descriptions.extend(descriptions);
+                        count
+                    }
+                    ParseResult::Error(path, err) => {
+                        parsed.parse_errors.push((path, err));
+                        0
+                    }
+                };
+                parsed.timings.push(ParseTiming {
+                    script_type: script_file.script_type,
+                    functions: function_count,
+                    elapsed,
+                });
+            }
+            Err(_) => {
+                parsed.parse_errors.push((
+                    script_file.path.display().to_string(),
+                    anyhow::anyhow!("Script parsing thread panicked"),
+                ));
+            }
+        }
+    }
+
+    parsed
+}
+
+/// The file category to remember for a function whose `@category` annotation
+/// moved it out of its file's category
+fn file_category_if_moved(category: &str, file_category: &str) -> Option<String> {
+    (category != file_category).then(|| file_category.to_string())
+}
+
+/// Parse a single script file with the parser matching its type
+fn parse_one(path: &Path, category: &str, script_type: ScriptType) -> ParseResult {
+    match script_type {
+        ScriptType::Bash => match parse_script(path, category) {
+            Ok(functions) => {
+                let visible: Vec<_> = functions.into_iter().filter(|f| !f.ignored).collect();
+                ParseResult::Functions(visible)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::PackageJson => match parse_package_json(path, category) {
+            Ok(npm_scripts) => {
+                let functions: Vec<ScriptFunction> = npm_scripts
+                    .into_iter()
+                    .map(|s| ScriptFunction {
+                        name: s.name,
+                        display_name: s.display_name,
+                        category: s.category,
+                        description: s.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::PackageJson,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::DevboxJson => match parse_devbox_json(path, category) {
+            Ok(devbox_scripts) => {
+                let functions: Vec<ScriptFunction> = devbox_scripts
+                    .into_iter()
+                    .map(|s| ScriptFunction {
+                        name: s.name,
+                        display_name: s.display_name,
+                        category: s.category,
+                        description: s.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::DevboxJson,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Task => match list_tasks(path, category) {
+            Ok(tasks) => {
+                let display_names = task_parser::collect_category_display_names(&tasks, category);
+                let functions: Vec<ScriptFunction> = tasks
+                    .into_iter()
+                    .filter(|t| !t.ignored)
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category.clone(),
+                        description: t.description,
+                        emoji: t.emoji,
+                        ignored: t.ignored,
+                        sudo: t.sudo,
+                        aliases: t.aliases,
+                        file_category: file_category_if_moved(&t.category, category),
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Task,
+                    })
+                    .collect();
+                // One category per included Taskfile namespace
+                ParseResult::GroupedFunctions(functions, display_names, HashMap::new())
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Makefile => match list_make_targets(path, category) {
+            Ok(targets) => {
+                let functions: Vec<ScriptFunction> = targets
+                    .into_iter()
+                    .filter(|t| !t.ignored)
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category.clone(),
+                        description: t.description,
+                        emoji: t.emoji,
+                        ignored: t.ignored,
+                        sudo: t.sudo,
+                        aliases: Vec::new(),
+                        file_category: file_category_if_moved(&t.category, category),
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Makefile,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Just => match list_just_recipes(path, category) {
+            Ok(recipes) => {
+                let display_names = just_parser::collect_category_display_names(&recipes, category);
+                let functions: Vec<ScriptFunction> = recipes
+                    .into_iter()
+                    .filter(|r| !r.ignored)
+                    .map(|r| ScriptFunction {
+                        name: r.name,
+                        display_name: r.display_name,
+                        category: r.category.clone(),
+                        description: r.description,
+                        emoji: r.emoji,
+                        ignored: r.ignored,
+                        sudo: r.sudo,
+                        aliases: Vec::new(),
+                        file_category: file_category_if_moved(&r.category, category),
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Just,
+                    })
+                    .collect();
+                // One category per module
+                ParseResult::GroupedFunctions(functions, display_names, HashMap::new())
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Mage => match list_mage_targets(path, category) {
+            Ok(targets) => {
+                let functions: Vec<ScriptFunction> = targets
+                    .into_iter()
+                    .filter(|t| !t.ignored)
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: t.emoji,
+                        ignored: t.ignored,
+                        sudo: t.sudo,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Mage,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Rakefile => match list_rake_tasks(path, category) {
+            Ok(tasks) => {
+                let functions: Vec<ScriptFunction> = tasks
+                    .into_iter()
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Rakefile,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Maven => match list_maven_goals(path, category) {
+            Ok(goals) => {
+                let functions: Vec<ScriptFunction> = goals
+                    .into_iter()
+                    .map(|g| ScriptFunction {
+                        name: g.name,
+                        display_name: g.display_name,
+                        category: g.category,
+                        description: g.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Maven,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Sbt => match list_sbt_tasks(path, category) {
+            Ok(tasks) => {
+                let functions: Vec<ScriptFunction> = tasks
+                    .into_iter()
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Sbt,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::Ansible => match list_ansible_playbooks(path, category) {
+            Ok(plays) => {
+                let functions: Vec<ScriptFunction> = plays
+                    .into_iter()
+                    .map(|p| ScriptFunction {
+                        name: p.name,
+                        display_name: p.display_name,
+                        category: p.category,
+                        description: p.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Ansible,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::CMake => match list_cmake_targets(path, category) {
+            Ok(targets) => {
+                let functions: Vec<ScriptFunction> = targets
+                    .into_iter()
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::CMake,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::Meson => match list_meson_targets(path, category) {
+            Ok(targets) => {
+                let functions: Vec<ScriptFunction> = targets
+                    .into_iter()
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Meson,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::Dagger => match list_dagger_functions(path, category) {
+            Ok(dagger_functions) => {
+                let functions: Vec<ScriptFunction> = dagger_functions
+                    .into_iter()
+                    .map(|f| ScriptFunction {
+                        name: f.name,
+                        display_name: f.display_name,
+                        category: f.category,
+                        description: f.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Dagger,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::Mix => match list_mix_tasks(path, category) {
+            Ok(mix_tasks) => {
+                let functions: Vec<ScriptFunction> = mix_tasks
+                    .into_iter()
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Mix,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::Lefthook => match list_lefthook_hooks(path, category) {
+            Ok(hooks) => {
+                let functions: Vec<ScriptFunction> = hooks
+                    .into_iter()
+                    .map(|h| ScriptFunction {
+                        name: h.name,
+                        display_name: h.display_name,
+                        category: h.category,
+                        description: h.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Lefthook,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::PreCommit => match list_pre_commit_hooks(path, category) {
+            Ok(hooks) => {
+                let functions: Vec<ScriptFunction> = hooks
+                    .into_iter()
+                    .map(|h| ScriptFunction {
+                        name: h.name,
+                        display_name: h.display_name,
+                        category: h.category,
+                        description: h.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::PreCommit,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+
+        ScriptType::CargoToml => match list_cargo_targets(path, category) {
+            Ok(targets) => {
+                let functions: Vec<ScriptFunction> = targets
+                    .into_iter()
+                    .filter(|t| !t.ignored)
+                    .map(|t| {
+                        let prefixed_name = match t.target_type {
+                            cargo_parser::CargoTargetType::Binary => {
+                                format!("bin:{}", t.name)
+                            }
+                            cargo_parser::CargoTargetType::Example => {
+                                format!("example:{}", t.name)
+                            }
+                        };
+                        ScriptFunction {
+                            name: prefixed_name,
+                            display_name: t.display_name,
+                            category: t.category,
+                            description: t.description,
+                            emoji: t.emoji,
+                            ignored: t.ignored,
+                            sudo: false,
+                            aliases: Vec::new(),
+                            file_category: None,
+                            usage: None,
+                            line: None,
+                            script_type: ScriptType::CargoToml,
+                        }
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::NxJson => match list_nx_targets(path, category) {
+            Ok(nx_targets) => {
+                let display_names = nx_parser::collect_category_display_names(&nx_targets);
+                let descriptions = nx_parser::collect_category_descriptions(&nx_targets);
+                let functions: Vec<ScriptFunction> = nx_targets
+                    .into_iter()
+                    .filter(|t| !t.ignored)
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: t.emoji,
+                        ignored: t.ignored,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::NxJson,
+                    })
+                    .collect();
+                ParseResult::GroupedFunctions(functions, display_names, descriptions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::ComposerJson => match parse_composer_json(path, category) {
+            Ok(scripts) => {
+                let functions: Vec<ScriptFunction> = scripts
+                    .into_iter()
+                    .map(|s| ScriptFunction {
+                        name: s.name,
+                        display_name: s.display_name,
+                        category: s.category,
+                        description: s.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::ComposerJson,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::DenoJson => match parse_deno_json(path, category) {
+            Ok(tasks) => {
+                let functions: Vec<ScriptFunction> = tasks
+                    .into_iter()
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::DenoJson,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::PyProject => match list_python_scripts(path, category) {
+            Ok(scripts) => {
+                let functions: Vec<ScriptFunction> = scripts
+                    .into_iter()
+                    .filter(|s| !s.ignored)
+                    .map(|s| ScriptFunction {
+                        name: s.name,
+                        display_name: s.display_name,
+                        category: s.category,
+                        description: s.description,
+                        emoji: s.emoji,
+                        ignored: s.ignored,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::PyProject,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Terraform => match list_terraform_commands(path, category) {
+            Ok(commands) => {
+                let functions: Vec<ScriptFunction> = commands
+                    .into_iter()
+                    .filter(|c| !c.ignored)
+                    .map(|c| ScriptFunction {
+                        name: c.name,
+                        display_name: c.display_name,
+                        category: c.category,
+                        description: c.description,
+                        emoji: c.emoji,
+                        ignored: c.ignored,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Terraform,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::DockerCompose => match list_compose_services(path, category) {
+            Ok(services) => {
+                let functions: Vec<ScriptFunction> = services
+                    .into_iter()
+                    .filter(|s| !s.ignored)
+                    .map(|s| ScriptFunction {
+                        name: s.name,
+                        display_name: s.display_name,
+                        category: s.category,
+                        description: s.description,
+                        emoji: s.emoji,
+                        ignored: s.ignored,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::DockerCompose,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Mise => match list_mise_tasks(path, category) {
+            Ok(tasks) => {
+                let functions: Vec<ScriptFunction> = tasks
+                    .into_iter()
+                    .map(|t| ScriptFunction {
+                        name: t.name,
+                        display_name: t.display_name,
+                        category: t.category,
+                        description: t.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: t.aliases,
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Mise,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Procfile => match list_procfile_processes(path, category) {
+            Ok(entries) => {
+                let functions: Vec<ScriptFunction> = entries
+                    .into_iter()
+                    .filter(|e| !e.ignored)
+                    .map(|e| ScriptFunction {
+                        name: e.name,
+                        display_name: e.display_name,
+                        category: e.category,
+                        description: e.description,
+                        emoji: e.emoji,
+                        ignored: e.ignored,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::Procfile,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::Gradle => {
+            let project_dir = path.parent().unwrap_or(path);
+            match list_gradle_tasks(project_dir, category) {
+                Ok(tasks) => {
+                    let display_names =
+                        gradle_parser::collect_category_display_names(&tasks, category);
+                    let functions: Vec<ScriptFunction> = tasks
+                        .into_iter()
+                        .filter(|t| !t.ignored)
+                        .map(|t| ScriptFunction {
+                            name: t.name,
+                            display_name: t.display_name,
+                            category: t.category,
+                            description: t.description,
+                            emoji: t.emoji,
+                            ignored: t.ignored,
+                            sudo: false,
+                            aliases: Vec::new(),
+                            file_category: None,
+                            usage: None,
+                            line: None,
+                            script_type: ScriptType::Gradle,
+                        })
+                        .collect();
+                    ParseResult::GroupedFunctions(functions, display_names, HashMap::new())
+                }
+                Err(e) => ParseResult::Error(path.display().to_string(), e),
+            }
+        }
+        ScriptType::Bazel => match list_bazel_targets(path, category) {
+            Ok(targets) => {
+                let functions: Vec<ScriptFunction> = targets
+                    .into_iter()
+                    .filter(|t| !t.ignored)
+                    .map(|t| {
+                        let prefixed_name = match t.target_type {
+                            bazel_parser::BazelTargetType::Binary => {
+                                format!("run:{}", t.label)
+                            }
+                            bazel_parser::BazelTargetType::Test => {
+                                format!("test:{}", t.label)
+                            }
+                        };
+                        ScriptFunction {
+                            name: prefixed_name,
+                            display_name: t.display_name,
+                            category: t.category,
+                            description: t.description,
+                            emoji: t.emoji,
+                            ignored: t.ignored,
+                            sudo: false,
+                            aliases: Vec::new(),
+                            file_category: None,
+                            usage: None,
+                            line: None,
+                            script_type: ScriptType::Bazel,
+                        }
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::GithubActionsJob => match list_actions_jobs(path, category) {
+            Ok(jobs) => {
+                let functions: Vec<ScriptFunction> = jobs
+                    .into_iter()
+                    .map(|j| ScriptFunction {
+                        name: j.name,
+                        display_name: j.display_name,
+                        category: j.category,
+                        description: j.description,
+                        emoji: None,
+                        ignored: false,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::GithubActionsJob,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+        ScriptType::GithubActions => match list_github_workflows(path, category) {
+            Ok(workflows) => {
+                let functions: Vec<ScriptFunction> = workflows
+                    .into_iter()
+                    .filter(|w| !w.ignored)
+                    .map(|w| ScriptFunction {
+                        name: w.file_name,
+                        display_name: w.display_name,
+                        category: w.category,
+                        description: w.description,
+                        emoji: None,
+                        ignored: w.ignored,
+                        sudo: false,
+                        aliases: Vec::new(),
+                        file_category: None,
+                        usage: None,
+                        line: None,
+                        script_type: ScriptType::GithubActions,
+                    })
+                    .collect();
+                ParseResult::Functions(functions)
+            }
+            Err(e) => ParseResult::Error(path.display().to_string(), e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn options() -> DiscoveryOptions {
+        DiscoveryOptions {
+            prewarm: false,
+            ..DiscoveryOptions::default()
+        }
+    }
+
+    /// `a.sh` in the root, then `b.sh`, `c.sh`, `d.sh` one level deeper each
+    fn nested_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let mut dir = temp_dir.path().to_path_buf();
+        for (level, name) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            if level > 0 {
+                dir = dir.join(format!("l{}", level));
+                fs::create_dir(&dir).unwrap();
+            }
+            fs::write(
+                dir.join(format!("{}.sh", name)),
+                format!("#!/bin/bash\n{}() {{\n  echo {}\n}}\n", name, name),
+            )
+            .unwrap();
+        }
+        temp_dir
+    }
+
+    fn discovered_files(root: &Path, depth: Option<usize>) -> Vec<String> {
+        let options = DiscoveryOptions { depth, ..options() };
+        let mut names: Vec<String> = discover_script_files(root, &options)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_discover_script_files_depth() {
+        let tree = nested_tree();
+
+        assert_eq!(discovered_files(tree.path(), Some(0)), vec!["a.sh"]);
+        assert_eq!(discovered_files(tree.path(), Some(1)), vec!["a.sh", "b.sh"]);
+        assert_eq!(
+            discovered_files(tree.path(), Some(2)),
+            vec!["a.sh", "b.sh", "c.sh"]
+        );
+        assert_eq!(
+            discovered_files(tree.path(), Some(3)),
+            vec!["a.sh", "b.sh", "c.sh", "d.sh"]
+        );
+        // Default: root files only, since `l1` is not a script directory
+        assert_eq!(discovered_files(tree.path(), None), vec!["a.sh"]);
+    }
+
+    #[test]
+    fn test_discover_script_files_exclude() {
+        let tree = nested_tree();
+        let files = |depth, exclude: &[&str]| {
+            let options = DiscoveryOptions {
+                depth,
+                exclude: exclude.iter().map(ToString::to_string).collect(),
+                ..options()
+            };
+            let mut names: Vec<String> = discover_script_files(tree.path(), &options)
+                .unwrap()
+                .into_iter()
+                .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort_unstable();
+            names
+        };
+
+        // An excluded directory takes everything below it along
+        assert_eq!(files(Some(3), &["l2"]), vec!["a.sh", "b.sh"]);
+        assert_eq!(files(Some(3), &["l1/l2/l3"]), vec!["a.sh", "b.sh", "c.sh"]);
+        // Files match by name at any depth
+        assert_eq!(files(Some(3), &["[ac].sh"]), vec!["b.sh", "d.sh"]);
+        assert_eq!(
+            files(Some(3), &["**/l3/*.sh", "a.sh"]),
+            vec!["b.sh", "c.sh"]
+        );
+        assert_eq!(files(Some(3), &["nothing"]).len(), 4);
+
+        // Script directories are matched relative to the root too
+        let scripts_dir = tree.path().join("scripts");
+        fs::create_dir_all(scripts_dir.join("vendor")).unwrap();
+        fs::write(scripts_dir.join("build.sh"), "build() {\n  echo\n}\n").unwrap();
+        fs::write(scripts_dir.join("vendor/lib.sh"), "lib() {\n  echo\n}\n").unwrap();
+        assert_eq!(files(None, &[]), vec!["a.sh", "build.sh", "lib.sh"]);
+        assert_eq!(files(None, &["scripts/vendor"]), vec!["a.sh", "build.sh"]);
+        assert_eq!(files(None, &["scripts"]), vec!["a.sh"]);
+    }
+
+    #[test]
+    fn test_discover_script_files_rejects_invalid_exclude() {
+        let tree = nested_tree();
+        let options = DiscoveryOptions {
+            exclude: vec!["[".to_string()],
+            ..options()
+        };
+        assert!(discover_script_files(tree.path(), &options).is_err());
+    }
+
+    #[test]
+    fn test_discover_all_bash_scripts() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("deploy.sh"),
+            "#!/bin/bash\n# Deploy the app\ndeploy() {\n  echo deploy\n}\n",
+        )
+        .unwrap();
+        let scripts_dir = temp_dir.path().join("scripts");
+        fs::create_dir(&scripts_dir).unwrap();
+        fs::write(
+            scripts_dir.join("build.sh"),
+            "#!/bin/bash\nbuild() {\n  echo build\n}\n",
+        )
+        .unwrap();
+
+        let discovery = discover_all(temp_dir.path(), &options()).unwrap();
+
+        assert_eq!(discovery.script_files.len(), 2);
+        let mut names: Vec<_> = discovery
+            .functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["build", "deploy"]);
+        assert!(discovery.parse_errors.is_empty());
+        assert_eq!(
+            discovery
+                .category_display_names
+                .get("deploy")
+                .map(String::as_str),
+            Some("Deploy")
+        );
+    }
+
+    #[test]
+    fn test_discover_all_skips_unlisted_subdirectories() {
+        let temp_dir = TempDir::new().unwrap();
+        let scripts_dir = temp_dir.path().join("scripts");
+        fs::create_dir(&scripts_dir).unwrap();
+        fs::write(scripts_dir.join("build.sh"), "build() {\n  echo build\n}\n").unwrap();
+
+        let discovery = discover_all(
+            temp_dir.path(),
+            &DiscoveryOptions {
+                script_dirs: Vec::new(),
+                prewarm: false,
+                depth: None,
+                exclude: Vec::new(),
+            },
+        )
+        .unwrap();
+
+        assert!(discovery.script_files.is_empty());
+        assert!(discovery.functions.is_empty());
+    }
+
+    #[test]
+    fn test_parse_script_files_records_timings() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("tools.sh"),
+            "one() {\n  :\n}\ntwo() {\n  :\n}\n",
+        )
+        .unwrap();
+        let script_files = discover_script_files(temp_dir.path(), &options()).unwrap();
+
+        let parsed = parse_script_files(&script_files);
+
+        assert_eq!(parsed.timings.len(), 1);
+        assert_eq!(parsed.timings[0].script_type, ScriptType::Bash);
+        assert_eq!(parsed.timings[0].functions, 2);
+    }
+
+    #[test]
+    fn test_parse_script_files_collects_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(&package_json, "{ not json").unwrap();
+        let script_file = ScriptFile {
+            path: package_json,
+            name: "package".to_string(),
+            category: "package".to_string(),
+            display_name: "Package".to_string(),
+            script_type: ScriptType::PackageJson,
+            available: true,
+        };
+
+        let parsed = parse_script_files(&[script_file]);
+
+        assert!(parsed.functions.is_empty());
+        assert_eq!(parsed.parse_errors.len(), 1);
+    }
+}
@@ -0,0 +1,249 @@
+//! # pre-commit Parser
+//!
+//! This module lists the hooks of a [pre-commit](https://pre-commit.com)
+//! config (`.pre-commit-config.yaml`) for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! When the config is found and `pre-commit` is installed, every hook id
+//! declared under the `hooks:` of a repository becomes a command:
+//!
+//! ```yaml
+//! repos:
+//!   - repo: https://github.com/pre-commit/pre-commit-hooks
+//!     rev: v4.6.0
+//!     hooks:
+//!       - id: trailing-whitespace
+//!       - id: check-yaml
+//!   - repo: local
+//!     hooks:
+//!       - id: cargo-fmt
+//!         name: cargo fmt
+//!         entry: cargo fmt --check
+//! ```
+//!
+//! ## Parsing
+//!
+//! Only ids and names are needed, so the file is read line by line instead
+//! of with a full YAML parser, like Docker Compose files: a hook starts at an
+//! `id:` key of a `hooks:` list item, and a `name:` key of the same item is
+//! used as its description. An id used by several repositories is listed once,
+//! since `pre-commit run <id>` runs all of them.
+//!
+//! ## Execution
+//!
+//! Hooks run as `pre-commit run <id>` from the config's directory, which
+//! checks the staged files like the git hook does.
+//!
+//! ## Key Types
+//!
+//! - [`PreCommitHook`] - A single hook with display metadata
+//! - [`is_pre_commit_available`] - Checks if `pre-commit` is installed
+//! - [`parse_hooks`] - Extract hook ids and names from config content
+//! - [`list_hooks`] - Main entry point to list the hooks of a config
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+use crate::script::utils::{indent_of, unquote};
+
+/// Cache for pre-commit availability check (checked once per process)
+static PRE_COMMIT_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// pre-commit hook item for TUI display
+#[derive(Debug, Clone)]
+pub struct PreCommitHook {
+    /// The hook id passed to `pre-commit run`
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if the `pre-commit` binary is available.
+pub fn is_pre_commit_available() -> bool {
+    *PRE_COMMIT_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("pre-commit"))
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// The unquoted value of a `key: value` line, if the line sets `key`
+fn key_value<'a>(trimmed: &'a str, key: &str) -> Option<&'a str> {
+    let value = trimmed.strip_prefix(key)?.strip_prefix(':')?;
+    let value = unquote(value.split(" #").next().unwrap_or_default());
+    (!value.is_empty()).then_some(value)
+}
+
+/// Extract the hooks of a pre-commit config as `(id, name)` pairs, in file
+/// order.
+///
+/// Hooks are the items of the `hooks:` lists: an item starts with its dash,
+/// and its `id:` and `name:` keys are read at the item's key indentation.
+pub fn parse_hooks(content: &str) -> Vec<(String, Option<String>)> {
+    let mut hooks: Vec<(String, Option<String>)> = Vec::new();
+    // Indentation of the `hooks:` key being read
+    let mut hooks_indent: Option<usize> = None;
+    // Key indentation of the current hook item, and its index in `hooks`
+    let mut item: Option<(usize, Option<usize>)> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = indent_of(line);
+
+        // A key of a repo item (`- repo:`, `hooks:`) at or left of `hooks:`
+        // ends the list
+        if let Some(level) = hooks_indent {
+            let dash = trimmed.starts_with('-');
+            if indent < level || (indent == level && !dash) {
+                hooks_indent = None;
+                item = None;
+            }
+        }
+        let key_line = trimmed.strip_prefix("- ").map_or(trimmed, str::trim_start);
+        if key_line.strip_prefix("hooks:").is_some_and(|rest| {
+            let rest = rest.trim();
+            rest.is_empty() || rest.starts_with('#')
+        }) {
+            let key_indent = indent + (trimmed.len() - key_line.len());
+            hooks_indent = Some(key_indent);
+            item = None;
+            continue;
+        }
+        if hooks_indent.is_none() {
+            continue;
+        }
+
+        let key_indent = if let Some(rest) = trimmed.strip_prefix("- ") {
+            let key_indent = indent + 2 + indent_of(rest);
+            item = Some((key_indent, None));
+            key_indent
+        } else {
+            indent
+        };
+        let Some((level, ref mut index)) = item else {
+            continue;
+        };
+        if key_indent != level {
+            continue;
+        }
+
+        if let Some(id) = key_value(key_line, "id") {
+            match hooks.iter().position(|(existing, _)| existing == id) {
+                Some(existing) => *index = Some(existing),
+                None => {
+                    hooks.push((id.to_string(), None));
+                    *index = Some(hooks.len() - 1);
+                }
+            }
+        } else if let Some(name) = key_value(key_line, "name") {
+            if let Some(hook) = index.and_then(|i| hooks.get_mut(i)) {
+                hook.1.get_or_insert_with(|| name.to_string());
+            }
+        }
+    }
+
+    hooks
+}
+
+/// The hooks as commands
+pub fn build_hooks(hooks: Vec<(String, Option<String>)>, category: &str) -> Vec<PreCommitHook> {
+    hooks
+        .into_iter()
+        .map(|(id, name)| PreCommitHook {
+            display_name: format_display_name(&id),
+            category: category.to_string(),
+            description: name.unwrap_or_else(|| format!("pre-commit run {}", id)),
+            name: id,
+        })
+        .collect()
+}
+
+/// Read a pre-commit config and list its hooks.
+pub fn list_hooks(config_path: &Path, category: &str) -> Result<Vec<PreCommitHook>> {
+    let content = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+
+    Ok(build_hooks(parse_hooks(&content), category))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = "\
+default_stages: [pre-commit]
+repos:
+  - repo: https://github.com/pre-commit/pre-commit-hooks
+    rev: v4.6.0
+    hooks:
+      - id: trailing-whitespace
+      - id: check-yaml
+        args: [--allow-multiple-documents]
+  # Project hooks
+  - repo: local
+    hooks:
+    - id: cargo-fmt
+      name: \"cargo fmt\"
+      entry: cargo fmt --check
+      language: system
+    - id: trailing-whitespace
+  - repo: https://github.com/psf/black
+    rev: 24.4.2
+    hooks:
+      - name: unnamed hook without an id
+";
+
+    #[test]
+    fn test_parse_hooks() {
+        let hooks = parse_hooks(SAMPLE);
+        assert_eq!(
+            hooks,
+            vec![
+                ("trailing-whitespace".to_string(), None),
+                ("check-yaml".to_string(), None),
+                ("cargo-fmt".to_string(), Some("cargo fmt".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_hooks_ignores_keys_outside_hooks() {
+        let content = "repos:\n  - repo: local\n    name: not a hook\n    id: nope\n";
+        assert!(parse_hooks(content).is_empty());
+    }
+
+    #[test]
+    fn test_build_hooks() {
+        let hooks = build_hooks(parse_hooks(SAMPLE), "repo");
+        assert_eq!(hooks[0].name, "trailing-whitespace");
+        assert_eq!(hooks[0].display_name, "Trailing Whitespace");
+        assert_eq!(hooks[0].description, "pre-commit run trailing-whitespace");
+        assert_eq!(hooks[2].description, "cargo fmt");
+        assert!(hooks.iter().all(|h| h.category == "repo"));
+    }
+
+    #[test]
+    fn test_list_hooks_reads_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(".pre-commit-config.yaml");
+        fs::write(&path, SAMPLE).unwrap();
+
+        assert_eq!(list_hooks(&path, "repo").unwrap().len(), 3);
+    }
+}
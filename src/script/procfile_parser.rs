@@ -0,0 +1,212 @@
+//! # Procfile Parser
+//!
+//! This module parses Foreman/Heroku-style `Procfile`s into runnable process
+//! entries for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! A `Procfile` (or `Procfile.dev`) lists named processes, one per line:
+//!
+//! ```text
+//! # Processes for local development
+//! web: bundle exec puma -C config/puma.rb
+//! worker: bundle exec sidekiq
+//! ```
+//!
+//! Every `name: command` line becomes an entry whose description is the
+//! command itself. Comments and blank lines are ignored.
+//!
+//! ## Execution
+//!
+//! When `foreman` is installed, a process runs as
+//! `foreman start -f <Procfile> <name>` in the Procfile's directory, so
+//! Foreman's `.env` handling and output prefixing apply. Otherwise the command
+//! runs directly via `bash -c` in the same directory. No tool is required for
+//! discovery.
+//!
+//! ## Key Types
+//!
+//! - [`ProcfileEntry`] - A single process with display metadata
+//! - [`is_foreman_available`] - Checks if `foreman` is installed
+//! - [`parse_procfile`] - Extract processes from Procfile content
+//! - [`find_command`] - Look up a process's command when running it
+//! - [`list_processes`] - Main entry point to list all processes
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for foreman availability check (checked once per process)
+static FOREMAN_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Procfile process item for TUI display
+#[derive(Debug, Clone)]
+pub struct ProcfileEntry {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+    pub emoji: Option<String>,
+    pub ignored: bool,
+    /// The shell command the process runs
+    pub command: String,
+}
+
+/// Check if `foreman` is available.
+pub fn is_foreman_available() -> bool {
+    *FOREMAN_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("foreman"))
+            .arg("version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Split a Procfile line into its process name and command.
+///
+/// Process names are made of alphanumerics, `_` and `-`; lines that are
+/// comments, blank or have an empty command yield `None`.
+fn parse_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (name, command) = line.split_once(':')?;
+    let command = command.trim();
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    (valid_name && !command.is_empty()).then_some((name, command))
+}
+
+/// Parse Procfile content into process entries, in file order.
+///
+/// A process defined twice keeps its first definition.
+pub fn parse_procfile(content: &str, category: &str) -> Vec<ProcfileEntry> {
+    let mut entries: Vec<ProcfileEntry> = Vec::new();
+
+    for (name, command) in content.lines().filter_map(parse_line) {
+        if entries.iter().any(|e| e.name == name) {
+            continue;
+        }
+        entries.push(ProcfileEntry {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: category.to_string(),
+            description: command.to_string(),
+            emoji: Some("\u{1f3ed}".to_string()), // 🏭
+            ignored: false,
+            command: command.to_string(),
+        });
+    }
+
+    entries
+}
+
+/// Read a Procfile and return the command of the process called `name`.
+pub fn find_command(procfile_path: &Path, name: &str) -> Result<String> {
+    let content = std::fs::read_to_string(procfile_path)
+        .with_context(|| format!("Failed to read {}", procfile_path.display()))?;
+    content
+        .lines()
+        .filter_map(parse_line)
+        .find(|(process, _)| *process == name)
+        .map(|(_, command)| command.to_string())
+        .with_context(|| {
+            format!(
+                "Process '{}' not found in {}",
+                name,
+                procfile_path.display()
+            )
+        })
+}
+
+/// Read a Procfile and list its processes.
+pub fn list_processes(procfile_path: &Path, category: &str) -> Result<Vec<ProcfileEntry>> {
+    let content = std::fs::read_to_string(procfile_path)
+        .with_context(|| format!("Failed to read {}", procfile_path.display()))?;
+
+    Ok(parse_procfile(&content, category))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = "\
+# Processes for local development
+web: bundle exec puma -C config/puma.rb
+worker:   bundle exec sidekiq -q default
+
+release: bin/rails db:migrate
+  # indented comment
+css-watch: bin/rails tailwindcss:watch
+";
+
+    #[test]
+    fn test_parse_procfile_multiple_processes() {
+        let entries = parse_procfile(SAMPLE, "myapp");
+
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["web", "worker", "release", "css-watch"]);
+
+        assert_eq!(entries[0].command, "bundle exec puma -C config/puma.rb");
+        assert_eq!(entries[0].description, entries[0].command);
+        assert_eq!(entries[1].command, "bundle exec sidekiq -q default");
+        assert_eq!(entries[3].display_name, "Css Watch");
+        assert_eq!(entries[3].category, "myapp");
+    }
+
+    #[test]
+    fn test_parse_procfile_skips_invalid_lines() {
+        let content = "not a process\nempty:\nbad name: echo x\nweb: echo ok\nweb: echo again\n";
+        let entries = parse_procfile(content, "app");
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "web");
+        assert_eq!(entries[0].command, "echo ok");
+    }
+
+    #[test]
+    fn test_parse_procfile_empty() {
+        assert!(parse_procfile("# nothing here\n\n", "app").is_empty());
+    }
+
+    #[test]
+    fn test_find_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("Procfile");
+        fs::write(&path, SAMPLE).unwrap();
+
+        assert_eq!(
+            find_command(&path, "release").unwrap(),
+            "bin/rails db:migrate"
+        );
+        assert!(find_command(&path, "missing").is_err());
+    }
+
+    #[test]
+    fn test_list_processes_reads_procfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("Procfile.dev");
+        fs::write(&path, SAMPLE).unwrap();
+
+        let entries = list_processes(&path, "myapp").unwrap();
+        assert_eq!(entries.len(), 4);
+    }
+
+    #[test]
+    fn test_list_processes_missing_file() {
+        assert!(list_processes(Path::new("/nonexistent/Procfile"), "x").is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! # Python Project Parser
+//!
+//! This module parses `pyproject.toml` files to extract console scripts for
+//! display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Both the PEP 621 and the Poetry tables are supported:
+//!
+//! ```toml
+//! [project.scripts]
+//! serve = "myapp.server:main"
+//!
+//! [tool.poetry.scripts]
+//! migrate = "myapp.db:migrate"
+//! seed = { reference = "myapp.db:seed", type = "console" }
+//! ```
+//!
+//! When a script is defined in both tables, the `[project.scripts]` entry wins.
+//!
+//! ## Key Types
+//!
+//! - [`PythonScript`] - Represents a single script with display metadata
+//! - [`list_scripts`] - Main function to list scripts from a `pyproject.toml`
+//! - [`command_for`] - Resolves the command used to run a script
+//!
+//! ## Execution
+//!
+//! - With a `poetry.lock` next to `pyproject.toml`: `poetry run <name>`
+//! - Otherwise: `python -m <module>`, where `<module>` is the part of the
+//!   entry point before the `:`
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use toml::Value;
+
+use crate::script::discovery::format_display_name;
+
+/// Lock file whose presence means scripts run through Poetry
+const POETRY_LOCK: &str = "poetry.lock";
+
+/// Python console script item for TUI display (mirrors other script types)
+#[derive(Debug, Clone)]
+pub struct PythonScript {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+    pub emoji: Option<String>,
+    pub ignored: bool,
+    /// Entry point reference, e.g. `myapp.server:main`
+    pub entry_point: String,
+}
+
+impl PythonScript {
+    /// Module part of the entry point (`myapp.server` for `myapp.server:main`)
+    pub fn module(&self) -> &str {
+        self.entry_point
+            .split(':')
+            .next()
+            .unwrap_or(&self.entry_point)
+            .trim()
+    }
+
+    /// Program and arguments used to run this script
+    pub fn command(&self, use_poetry: bool) -> (String, Vec<String>) {
+        if use_poetry {
+            (
+                "poetry".to_string(),
+                vec!["run".to_string(), self.name.clone()],
+            )
+        } else {
+            (
+                "python".to_string(),
+                vec!["-m".to_string(), self.module().to_string()],
+            )
+        }
+    }
+}
+
+/// Check whether scripts in `project_dir` should run through Poetry.
+pub fn uses_poetry(project_dir: &Path) -> bool {
+    project_dir.join(POETRY_LOCK).is_file()
+}
+
+/// Parse `pyproject.toml` content into scripts.
+fn parse_pyproject(content: &str, category: &str, use_poetry: bool) -> Result<Vec<PythonScript>> {
+    let document: Value = toml::from_str(content).context("Failed to parse pyproject.toml")?;
+
+    let tables = [
+        document.get("project").and_then(|p| p.get("scripts")),
+        document
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .and_then(|p| p.get("scripts")),
+    ];
+
+    let mut scripts: Vec<PythonScript> = Vec::new();
+    for table in tables.into_iter().flatten() {
+        let Some(table) = table.as_table() else {
+            continue;
+        };
+        for (name, value) in table {
+            if scripts.iter().any(|s| s.name == *name) {
+                continue;
+            }
+            // Poetry also accepts `{ reference = "...", type = "console" }`
+            let entry_point = match value {
+                Value::String(s) => s.clone(),
+                Value::Table(t) => match t.get("reference").or_else(|| t.get("callable")) {
+                    Some(Value::String(s)) => s.clone(),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            let mut script = PythonScript {
+                name: name.clone(),
+                display_name: format_display_name(name),
+                category: category.to_string(),
+                description: String::new(),
+                emoji: None,
+                ignored: false,
+                entry_point,
+            };
+            let (program, args) = script.command(use_poetry);
+            script.description = format!("{} {} ({})", program, args.join(" "), script.entry_point);
+            scripts.push(script);
+        }
+    }
+
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(scripts)
+}
+
+/// Read a `pyproject.toml` and list its console scripts.
+pub fn list_scripts(pyproject_path: &Path, category: &str) -> Result<Vec<PythonScript>> {
+    let content = fs::read_to_string(pyproject_path)
+        .with_context(|| format!("Failed to read: {}", pyproject_path.display()))?;
+    let use_poetry = pyproject_path.parent().is_some_and(uses_poetry);
+    parse_pyproject(&content, category, use_poetry)
+}
+
+/// Resolve the program and arguments that run script `name` from a `pyproject.toml`.
+pub fn command_for(pyproject_path: &Path, name: &str) -> Result<(String, Vec<String>)> {
+    let scripts = list_scripts(pyproject_path, "")?;
+    let use_poetry = pyproject_path.parent().is_some_and(uses_poetry);
+    scripts
+        .iter()
+        .find(|s| s.name == name)
+        .map(|s| s.command(use_poetry))
+        .with_context(|| {
+            format!(
+                "Script '{}' not found in {}",
+                name,
+                pyproject_path.display()
+            )
+        })
+}
+
+/// Read the project description from `[project]` or `[tool.poetry]`.
+pub fn project_description(content: &str) -> Option<String> {
+    let document: Value = toml::from_str(content).ok()?;
+    let project = document.get("project");
+    let poetry = document.get("tool").and_then(|t| t.get("poetry"));
+    let description = [project, poetry]
+        .into_iter()
+        .flatten()
+        .filter_map(|table| table.get("description").and_then(Value::as_str))
+        .map(str::trim)
+        .find(|d| !d.is_empty())
+        .map(String::from);
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = r#"
+[project]
+name = "myapp"
+description = "My sample app"
+
+[project.scripts]
+serve = "myapp.server:main"
+shared = "myapp.shared:pep621"
+
+[tool.poetry.scripts]
+migrate = "myapp.db:migrate"
+seed = { reference = "myapp.db:seed", type = "console" }
+shared = "myapp.shared:poetry"
+"#;
+
+    #[test]
+    fn test_parse_pyproject_both_tables() {
+        let scripts = parse_pyproject(SAMPLE, "myapp", false).unwrap();
+
+        let names: Vec<&str> = scripts.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["migrate", "seed", "serve", "shared"]);
+        assert_eq!(scripts[1].entry_point, "myapp.db:seed");
+        assert_eq!(scripts[1].display_name, "Seed");
+        assert_eq!(scripts[1].category, "myapp");
+        // [project.scripts] wins over [tool.poetry.scripts]
+        assert_eq!(scripts[3].entry_point, "myapp.shared:pep621");
+    }
+
+    #[test]
+    fn test_parse_pyproject_python_commands() {
+        let scripts = parse_pyproject(SAMPLE, "myapp", false).unwrap();
+        let serve = scripts.iter().find(|s| s.name == "serve").unwrap();
+
+        assert_eq!(
+            serve.command(false),
+            (
+                "python".to_string(),
+                vec!["-m".to_string(), "myapp.server".to_string()]
+            )
+        );
+        assert_eq!(
+            serve.description,
+            "python -m myapp.server (myapp.server:main)"
+        );
+    }
+
+    #[test]
+    fn test_parse_pyproject_poetry_commands() {
+        let scripts = parse_pyproject(SAMPLE, "myapp", true).unwrap();
+        let seed = scripts.iter().find(|s| s.name == "seed").unwrap();
+
+        assert_eq!(
+            seed.command(true),
+            (
+                "poetry".to_string(),
+                vec!["run".to_string(), "seed".to_string()]
+            )
+        );
+        assert_eq!(seed.description, "poetry run seed (myapp.db:seed)");
+    }
+
+    #[test]
+    fn test_parse_pyproject_without_scripts() {
+        let scripts = parse_pyproject("[project]\nname = \"lib\"\n", "lib", false).unwrap();
+        assert!(scripts.is_empty());
+    }
+
+    #[test]
+    fn test_parse_pyproject_invalid_toml() {
+        assert!(parse_pyproject("[project", "bad", false).is_err());
+    }
+
+    #[test]
+    fn test_command_for_detects_poetry_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject = temp_dir.path().join("pyproject.toml");
+        fs::write(&pyproject, SAMPLE).unwrap();
+
+        let (program, _) = command_for(&pyproject, "serve").unwrap();
+        assert_eq!(program, "python");
+
+        fs::write(temp_dir.path().join(POETRY_LOCK), "").unwrap();
+        let (program, args) = command_for(&pyproject, "serve").unwrap();
+        assert_eq!(program, "poetry");
+        assert_eq!(args, vec!["run", "serve"]);
+
+        assert!(command_for(&pyproject, "missing").is_err());
+    }
+
+    #[test]
+    fn test_project_description() {
+        assert_eq!(
+            project_description(SAMPLE),
+            Some("My sample app".to_string())
+        );
+        assert_eq!(project_description("[project]\nname = \"x\"\n"), None);
+    }
+}
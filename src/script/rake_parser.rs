@@ -0,0 +1,210 @@
+//! # Rake (Rakefile) Parser
+//!
+//! This module lists Ruby Rake tasks for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Like the just and mage parsers, this parser asks the CLI rather than
+//! reading the Rakefile, since Rakefiles are Ruby code and tasks are often
+//! generated or imported from gems.
+//!
+//! ## Key Types
+//!
+//! - [`RakeTask`] - Represents a rake task with display metadata for the TUI
+//! - [`is_rake_available`] - Checks if `rake` CLI is installed
+//! - [`list_tasks`] - Main function to list tasks from a Rakefile
+//!
+//! ## CLI Integration
+//!
+//! The parser runs (in the Rakefile's directory):
+//! ```bash
+//! rake -T -A
+//! ```
+//!
+//! `-A` includes tasks without a description. The output looks like:
+//!
+//! ```text
+//! rake build              # Build the gem
+//! rake db:migrate         # Migrate the database
+//! rake db:seed
+//! rake test[pattern]      # Run tests
+//! ```
+//!
+//! Namespaced tasks keep their full `namespace:task` name, and task arguments
+//! (`[pattern]`) are dropped from the name and shown in the description.
+//!
+//! ## Availability Caching
+//!
+//! The `rake` binary availability is cached using [`OnceLock`] to avoid
+//! repeated process spawning during discovery.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for rake availability check (checked once per process)
+static RAKE_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Rake task item for TUI display (mirrors other script types)
+#[derive(Debug, Clone)]
+pub struct RakeTask {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if the `rake` binary is available.
+pub fn is_rake_available() -> bool {
+    *RAKE_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("rake"))
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Parse output from `rake -T -A` into tasks.
+pub fn parse_rake_output(output: &str, category: &str) -> Vec<RakeTask> {
+    let mut tasks: Vec<RakeTask> = Vec::new();
+
+    for line in output.lines() {
+        let Some(rest) = line.trim().strip_prefix("rake ") else {
+            continue;
+        };
+
+        let (signature, comment) = match rest.split_once('#') {
+            Some((signature, comment)) => (signature.trim(), comment.trim()),
+            None => (rest.trim(), ""),
+        };
+        if signature.is_empty() {
+            continue;
+        }
+
+        let (name, args) = match signature.split_once('[') {
+            Some((name, args)) => (name, Some(args.trim_end_matches(']'))),
+            None => (signature, None),
+        };
+        if tasks.iter().any(|t| t.name == name) {
+            continue;
+        }
+
+        let mut description = if comment.is_empty() {
+            format!("rake task {}", name)
+        } else {
+            comment.to_string()
+        };
+        if let Some(args) = args {
+            description.push_str(&format!(" (args: {})", args));
+        }
+
+        tasks.push(RakeTask {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: category.to_string(),
+            description,
+        });
+    }
+
+    tasks.sort_by(|a, b| a.name.cmp(&b.name));
+    tasks
+}
+
+/// Run `rake -T -A` next to a Rakefile and parse the result.
+pub fn list_tasks(rakefile_path: &Path, category: &str) -> Result<Vec<RakeTask>> {
+    let rakefile_dir = rakefile_path
+        .parent()
+        .context("Failed to get parent directory of Rakefile")?;
+
+    let output = Command::new(binaries::program("rake"))
+        .args(["-T", "-A"])
+        .current_dir(rakefile_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run rake for: {}", rakefile_path.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "rake -T failed for {}: {}",
+            rakefile_path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_rake_output(
+        &String::from_utf8_lossy(&output.stdout),
+        category,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+rake build              # Build the gem into the pkg directory
+rake db:migrate         # Migrate the database (options: VERSION=x)
+rake db:seed
+rake default
+rake test[pattern]      # Run tests
+";
+
+    #[test]
+    fn test_parse_rake_output() {
+        let tasks = parse_rake_output(SAMPLE, "app");
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["build", "db:migrate", "db:seed", "default", "test"]
+        );
+        assert_eq!(tasks[0].description, "Build the gem into the pkg directory");
+        assert_eq!(tasks[0].category, "app");
+    }
+
+    #[test]
+    fn test_parse_rake_output_namespaced_tasks() {
+        let tasks = parse_rake_output(SAMPLE, "app");
+        let migrate = tasks.iter().find(|t| t.name == "db:migrate").unwrap();
+
+        assert_eq!(
+            migrate.description,
+            "Migrate the database (options: VERSION=x)"
+        );
+        assert_eq!(migrate.display_name, format_display_name("db:migrate"));
+    }
+
+    #[test]
+    fn test_parse_rake_output_without_description() {
+        let tasks = parse_rake_output(SAMPLE, "app");
+        let seed = tasks.iter().find(|t| t.name == "db:seed").unwrap();
+
+        assert_eq!(seed.description, "rake task db:seed");
+    }
+
+    #[test]
+    fn test_parse_rake_output_task_arguments() {
+        let tasks = parse_rake_output(SAMPLE, "app");
+        let test = tasks.iter().find(|t| t.name == "test").unwrap();
+
+        assert_eq!(test.description, "Run tests (args: pattern)");
+    }
+
+    #[test]
+    fn test_parse_rake_output_ignores_noise() {
+        let output = "(in /home/user/app)\n\nrake build  # Build\n";
+        let tasks = parse_rake_output(output, "app");
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].name, "build");
+    }
+}
@@ -0,0 +1,275 @@
+//! # sbt Task Parser
+//!
+//! This module lists the tasks of a Scala [sbt](https://www.scala-sbt.org)
+//! build for display in the Jarvis TUI.
+//!
+//! ## Overview
+//!
+//! Builds are Scala code, and most tasks come from sbt itself and its plugins,
+//! so the parser asks sbt rather than reading `build.sbt`. It runs (in the
+//! build's directory):
+//!
+//! ```bash
+//! sbt -batch "tasks -V"
+//! ```
+//!
+//! which lists every task with its description, one per line, indented below
+//! a short preamble:
+//!
+//! ```text
+//! [info] welcome to sbt 1.9.7 (Eclipse Adoptium Java 17.0.8)
+//!
+//! This is a list of tasks defined for the current project.
+//!
+//!   clean      Deletes files produced by the build, such as generated sources.
+//!   compile    Compiles sources.
+//!   myTask     A custom task from build.sbt
+//! ```
+//!
+//! sbt starts slowly (it boots a JVM and loads the build), so when it fails
+//! to list the tasks (e.g. the build does not compile yet) a fixed list of
+//! [`COMMON_TASKS`] is shown instead.
+//!
+//! ## Execution
+//!
+//! Tasks run as `sbt <task>` from the build's directory.
+//!
+//! ## Key Types
+//!
+//! - [`SbtTask`] - A single sbt task with display metadata
+//! - [`is_sbt_available`] - Checks if `sbt` is installed
+//! - [`parse_tasks_output`] - Parse the output of `sbt tasks -V`
+//! - [`list_tasks`] - Main entry point to list the tasks of a build
+//!
+//! ## Availability Caching
+//!
+//! The `sbt` binary availability is cached using [`OnceLock`] to avoid
+//! repeated process spawning during discovery.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::script::binaries;
+use crate::script::discovery::format_display_name;
+
+/// Cache for sbt availability check (checked once per process)
+static SBT_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Tasks listed when `sbt tasks` cannot be run
+pub const COMMON_TASKS: &[(&str, &str)] = &[
+    ("clean", "Deletes files produced by the build"),
+    ("compile", "Compiles sources"),
+    (
+        "console",
+        "Starts the Scala interpreter with the project classes",
+    ),
+    (
+        "package",
+        "Produces the main artifact, such as a binary jar",
+    ),
+    (
+        "publishLocal",
+        "Publishes artifacts to the local Ivy repository",
+    ),
+    ("run", "Runs a main class"),
+    ("test", "Executes all tests"),
+    ("update", "Resolves and optionally retrieves dependencies"),
+];
+
+/// sbt task item for TUI display
+#[derive(Debug, Clone)]
+pub struct SbtTask {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub description: String,
+}
+
+/// Check if `sbt` is available.
+///
+/// Asks the launcher script for its version, which (unlike `sbt --version`)
+/// answers without starting a JVM.
+pub fn is_sbt_available() -> bool {
+    *SBT_AVAILABLE.get_or_init(|| {
+        Command::new(binaries::program("sbt"))
+            .arg("--script-version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+fn make_task(name: &str, description: &str, category: &str) -> SbtTask {
+    SbtTask {
+        name: name.to_string(),
+        display_name: format_display_name(name),
+        category: category.to_string(),
+        description: if description.is_empty() {
+            format!("sbt {}", name)
+        } else {
+            description.to_string()
+        },
+    }
+}
+
+/// Whether a word is an sbt task key (`compile`, `publishLocal`, `Test/run`)
+fn is_task_key(word: &str) -> bool {
+    word.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '/' || c == '_')
+}
+
+/// Parse the output of `sbt tasks -V` into tasks, sorted by name.
+///
+/// Task lines are indented, start with the task key and are followed by its
+/// description. Log lines (`[info] ...`), the preamble and the closing hint
+/// are not indented and are skipped.
+pub fn parse_tasks_output(output: &str, category: &str) -> Vec<SbtTask> {
+    let mut tasks: Vec<SbtTask> = Vec::new();
+
+    for line in output.lines() {
+        // Some launchers prefix every line with the log level
+        let line = line.strip_prefix("[info] ").unwrap_or(line);
+        if !line.starts_with(' ') && !line.starts_with('\t') {
+            continue;
+        }
+        let line = line.trim();
+        let (name, description) = match line.split_once(char::is_whitespace) {
+            Some((name, description)) => (name, description.trim()),
+            None => (line, ""),
+        };
+        if !is_task_key(name) || tasks.iter().any(|t| t.name == name) {
+            continue;
+        }
+        tasks.push(make_task(name, description, category));
+    }
+
+    tasks.sort_by(|a, b| a.name.cmp(&b.name));
+    tasks
+}
+
+/// The [`COMMON_TASKS`], for builds whose tasks could not be listed
+pub fn common_tasks(category: &str) -> Vec<SbtTask> {
+    COMMON_TASKS
+        .iter()
+        .map(|(name, description)| make_task(name, description, category))
+        .collect()
+}
+
+/// Run `sbt -batch "tasks -V"` next to a `build.sbt` and parse the result
+fn list_tasks_from_cli(build_dir: &Path, category: &str) -> Result<Vec<SbtTask>> {
+    let output = Command::new(binaries::program("sbt"))
+        .args(["-batch", "-no-colors", "tasks -V"])
+        .current_dir(build_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run sbt in {}", build_dir.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "sbt tasks failed in {}: {}",
+            build_dir.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_tasks_output(
+        &String::from_utf8_lossy(&output.stdout),
+        category,
+    ))
+}
+
+/// List the tasks of the sbt build at `build_sbt_path`, falling back to the
+/// [`COMMON_TASKS`] when sbt cannot list them.
+pub fn list_tasks(build_sbt_path: &Path, category: &str) -> Result<Vec<SbtTask>> {
+    let build_dir = build_sbt_path
+        .parent()
+        .context("Failed to get parent directory of build.sbt")?;
+
+    match list_tasks_from_cli(build_dir, category) {
+        Ok(tasks) if !tasks.is_empty() => Ok(tasks),
+        _ => Ok(common_tasks(category)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+[info] welcome to sbt 1.9.7 (Eclipse Adoptium Java 17.0.8)
+[info] loading settings for project hello-build from plugins.sbt ...
+[info] set current project to hello (in build file:/home/me/hello/)
+
+This is a list of tasks defined for the current project.
+It does not list the scopes the tasks are defined in; use the 'inspect' command for that.
+Tasks produce values.  Use the 'show' command to run the task and print the resulting value.
+
+  bgRun                  Start an application's default main class as a background job
+  clean                  Deletes files produced by the build, such as generated sources, compiled classes, and task caches.
+  compile                Compiles sources.
+  myTask                 Generates the release notes
+  publishLocal
+  test                   Executes all tests.
+
+More task information available with the 'help' command.
+";
+
+    #[test]
+    fn test_parse_tasks_output() {
+        let tasks = parse_tasks_output(SAMPLE, "hello");
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "bgRun",
+                "clean",
+                "compile",
+                "myTask",
+                "publishLocal",
+                "test"
+            ]
+        );
+        assert_eq!(tasks[2].description, "Compiles sources.");
+        assert_eq!(tasks[3].description, "Generates the release notes");
+        assert_eq!(tasks[3].display_name, format_display_name("myTask"));
+        assert_eq!(tasks[3].category, "hello");
+    }
+
+    #[test]
+    fn test_parse_tasks_output_without_description() {
+        let tasks = parse_tasks_output(SAMPLE, "hello");
+        let publish = tasks.iter().find(|t| t.name == "publishLocal").unwrap();
+        assert_eq!(publish.description, "sbt publishLocal");
+    }
+
+    #[test]
+    fn test_parse_tasks_output_info_prefixed_lines() {
+        let output = "[info]   compile    Compiles sources.\n[info]   Test/run   Runs tests\n[info] Loading project\n";
+        let tasks = parse_tasks_output(output, "app");
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Test/run", "compile"]);
+    }
+
+    #[test]
+    fn test_parse_tasks_output_skips_preamble() {
+        let output = "This is a list of tasks defined for the current project.\nMore task information available with the 'help' command.\n";
+        assert!(parse_tasks_output(output, "app").is_empty());
+    }
+
+    #[test]
+    fn test_common_tasks() {
+        let tasks = common_tasks("app");
+        assert!(tasks.iter().any(|t| t.name == "compile"));
+        assert!(tasks.iter().all(|t| t.category == "app"));
+    }
+}
@@ -23,6 +23,8 @@
 //! - [`TaskAnnotations`] - Annotations extracted from YAML comments
 //! - [`is_task_available`] - Checks if `task` CLI is installed
 //! - [`list_tasks`] - Main function to list tasks from a Taskfile
+//! - [`namespace_category`] - Category key of an included namespace
+//! - [`collect_category_display_names`] - Display names of the namespace categories
 //!
 //! ## CLI Integration
 //!
@@ -34,8 +36,13 @@
 //! And parses the JSON output which includes:
 //! - Task name and description
 //! - Task summary (fallback for description)
+//! - Task aliases (shown next to the display name and matched by search)
 //! - Location information (file, line, column)
 //!
+//! Tasks from included Taskfiles keep their namespaced name (`docker:build`)
+//! and run with it, but are listed in a category of their own per namespace
+//! (`task:<dir>:docker`, shown as "📋 Dir · docker"), like Nx projects.
+//!
 //! ## Annotations
 //!
 //! Tasks can be annotated with special comments above their definitions:
@@ -61,6 +68,8 @@
 //! | `@emoji <emoji>` | Display emoji prefix in the TUI |
 //! | `@description <text>` | Custom description for the details panel |
 //! | `@ignore` | Hide the task from the TUI |
+//! | `@sudo` | Run the task via `sudo` |
+//! | `@category <name>` | Show the task under another category |
 //!
 //! ## Availability Caching
 //!
@@ -76,7 +85,9 @@ use std::path::Path;
 use std::process::{Command, Stdio};
 use std::sync::OnceLock;
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
+use crate::script::utils::comment_description;
 
 /// Cache for task availability check (checked once per process)
 static TASK_AVAILABLE: OnceLock<bool> = OnceLock::new();
@@ -95,6 +106,9 @@ pub struct TaskInfo {
     pub name: String,
     pub desc: Option<String>,
     pub summary: Option<String>,
+    /// Alternative names the task can be invoked by
+    #[serde(default)]
+    pub aliases: Vec<String>,
     #[allow(dead_code)]
     pub up_to_date: Option<bool>,
     #[allow(dead_code)]
@@ -120,6 +134,11 @@ pub struct TaskTask {
     pub description: String,
     pub emoji: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
+    pub aliases: Vec<String>,
+    /// Namespace of an included Taskfile the task comes from (`docker` for
+    /// `docker:build`, `ci:lint` for `ci:lint:go`)
+    pub namespace: Option<String>,
 }
 
 /// Annotations extracted from YAML comments above a task definition
@@ -128,6 +147,9 @@ pub struct TaskAnnotations {
     pub emoji: Option<String>,
     pub description: Option<String>,
     pub ignored: bool,
+    pub sudo: bool,
+    /// Category to show the task under instead of the file's category
+    pub category: Option<String>,
 }
 
 /// Parse annotations from YAML comments in a Taskfile.
@@ -136,6 +158,8 @@ pub struct TaskAnnotations {
 /// - `# @emoji <emoji>` - Display emoji prefix in the TUI
 /// - `# @description <text>` - Custom description for the details panel
 /// - `# @ignore` - Hide the task from the TUI
+/// - `# @sudo` - Run the task via `sudo`
+/// - `# @category <name>` - Show the task under another category
 ///
 /// Returns a map of task names to their annotations.
 pub fn parse_taskfile_annotations(
@@ -158,10 +182,12 @@ pub fn parse_taskfile_annotations_from_content(
     // Regex patterns for annotations (same as bash parser)
     let emoji_re =
         Regex::new(r"^\s*#\s*@emoji\s+(.+)$").context("Failed to compile emoji regex pattern")?;
-    let desc_re = Regex::new(r"^\s*#\s*@description\s+(.+)$")
-        .context("Failed to compile description regex pattern")?;
     let ignore_re =
         Regex::new(r"^\s*#\s*@ignore\s*$").context("Failed to compile ignore regex pattern")?;
+    let sudo_re =
+        Regex::new(r"^\s*#\s*@sudo\s*$").context("Failed to compile sudo regex pattern")?;
+    let category_re = Regex::new(r"^\s*#\s*@category\s+(.+)$")
+        .context("Failed to compile category regex pattern")?;
     let comment_re = Regex::new(r"^\s*#").context("Failed to compile comment regex pattern")?;
 
     // Regex to match task definitions in YAML
@@ -218,10 +244,12 @@ pub fn parse_taskfile_annotations_from_content(
 
                 // Extract annotations from preceding comment lines
                 let mut emoji: Option<String> = None;
-                let mut description: Option<String> = None;
                 let mut ignored = false;
+                let mut sudo = false;
+                let mut category: Option<String> = None;
 
                 // Look backwards from the task line through consecutive comment lines
+                let mut block_start = line_idx;
                 let mut check_idx = line_idx.saturating_sub(1);
                 loop {
                     if check_idx >= line_idx {
@@ -234,20 +262,26 @@ pub fn parse_taskfile_annotations_from_content(
                     if !prev_line.trim().is_empty() && !comment_re.is_match(prev_line) {
                         break;
                     }
+                    block_start = check_idx;
 
                     // Check for ignore annotation
                     if ignore_re.is_match(prev_line) {
                         ignored = true;
                     }
 
+                    // Check for sudo annotation
+                    if sudo_re.is_match(prev_line) {
+                        sudo = true;
+                    }
+
                     // Check for emoji annotation
                     if let Some(emoji_cap) = emoji_re.captures(prev_line) {
                         emoji = Some(emoji_cap[1].trim().to_string());
                     }
 
-                    // Check for description annotation
-                    if let Some(desc_cap) = desc_re.captures(prev_line) {
-                        description = Some(desc_cap[1].trim().to_string());
+                    // Check for category annotation
+                    if let Some(category_cap) = category_re.captures(prev_line) {
+                        category = Some(category_cap[1].trim().to_string());
                     }
 
                     if check_idx == 0 {
@@ -256,14 +290,19 @@ pub fn parse_taskfile_annotations_from_content(
                     check_idx -= 1;
                 }
 
+                let description = comment_description(&lines[block_start..line_idx], "#");
+
                 // Only add if there are any annotations
-                if emoji.is_some() || description.is_some() || ignored {
+                if emoji.is_some() || description.is_some() || ignored || sudo || category.is_some()
+                {
                     annotations_map.insert(
                         task_name.to_string(),
                         TaskAnnotations {
                             emoji,
                             description,
                             ignored,
+                            sudo,
+                            category,
                         },
                     );
                 }
@@ -274,9 +313,59 @@ pub fn parse_taskfile_annotations_from_content(
     Ok(annotations_map)
 }
 
+/// Display name for a task, listing its aliases so they can be spotted in the
+/// tree: `build` with aliases `b`, `compile` becomes "Build (b, compile)".
+fn display_name_with_aliases(name: &str, aliases: &[String]) -> String {
+    let display_name = format_display_name(name);
+    if aliases.is_empty() {
+        display_name
+    } else {
+        format!("{} ({})", display_name, aliases.join(", "))
+    }
+}
+
+/// Split a task name into its namespace and local name: `docker:build`
+/// becomes `(Some("docker"), "build")`, `build` stays `(None, "build")`.
+pub fn split_namespace(name: &str) -> (Option<&str>, &str) {
+    match name.rsplit_once(':') {
+        Some((namespace, local)) if !namespace.is_empty() && !local.is_empty() => {
+            (Some(namespace), local)
+        }
+        _ => (None, name),
+    }
+}
+
+/// Category key for the tasks of `namespace` in the Taskfile category
+/// `category` (e.g. `"task:my-app:docker"`)
+pub fn namespace_category(category: &str, namespace: &str) -> String {
+    format!("task:{}:{}", category, namespace)
+}
+
+/// Collect per-namespace category display names from a list of tasks.
+///
+/// Returns a map from category key (e.g. `"task:my-app:docker"`) to display
+/// name (e.g. `"📋 My App · docker"`).
+pub fn collect_category_display_names(
+    tasks: &[TaskTask],
+    category: &str,
+) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    for task in tasks {
+        if let Some(ref namespace) = task.namespace {
+            names
+                .entry(namespace_category(category, namespace))
+                .or_insert_with(|| format!("📋 {} · {}", format_display_name(category), namespace));
+        }
+    }
+    names
+}
+
 /// Parse JSON output from `task --list-all --json` into a list of `TaskTask`.
 ///
 /// If annotations are provided, they will be applied to matching tasks.
+///
+/// Namespaced tasks (`docker:build`) are put in their namespace's category
+/// and displayed by their local name.
 pub fn parse_task_list_json(
     json_str: &str,
     category: &str,
@@ -287,7 +376,9 @@ pub fn parse_task_list_json(
 
     let mut tasks = Vec::new();
     for info in output.tasks {
-        let display_name = format_display_name(&info.name);
+        let (namespace, local_name) = split_namespace(&info.name);
+        let namespace = namespace.map(str::to_string);
+        let display_name = display_name_with_aliases(local_name, &info.aliases);
 
         // Look up annotations for this task
         let task_annotations = annotations.and_then(|a| a.get(&info.name));
@@ -301,14 +392,26 @@ pub fn parse_task_list_json(
 
         let emoji = task_annotations.and_then(|a| a.emoji.clone());
         let ignored = task_annotations.is_some_and(|a| a.ignored);
+        let sudo = task_annotations.is_some_and(|a| a.sudo);
+        // An @category annotation takes precedence over the namespace and
+        // the file's category
+        let task_category = task_annotations
+            .and_then(|a| a.category.clone())
+            .unwrap_or_else(|| match namespace {
+                Some(ref namespace) => namespace_category(category, namespace),
+                None => category.to_string(),
+            });
 
         tasks.push(TaskTask {
             name: info.name,
             display_name,
-            category: category.to_string(),
+            category: task_category,
             description,
             emoji,
             ignored,
+            sudo,
+            aliases: info.aliases,
+            namespace,
         });
     }
 
@@ -319,7 +422,7 @@ pub fn parse_task_list_json(
 /// Check if the `task` binary is available.
 pub fn is_task_available() -> bool {
     *TASK_AVAILABLE.get_or_init(|| {
-        Command::new("task")
+        Command::new(binaries::program("task"))
             .arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -336,7 +439,7 @@ pub fn list_tasks(taskfile_path: &Path, category: &str) -> Result<Vec<TaskTask>>
     // First, parse annotations from the Taskfile
     let annotations = parse_taskfile_annotations(taskfile_path).ok();
 
-    let output = Command::new("task")
+    let output = Command::new(binaries::program("task"))
         .arg("--list-all")
         .arg("--json")
         .arg("--taskfile")
@@ -403,6 +506,95 @@ mod tests {
         assert!(!result[1].ignored);
     }
 
+    #[test]
+    fn test_parse_task_list_json_aliases() {
+        let json = r#"{
+            "tasks": [
+                { "name": "build", "desc": "Build", "aliases": ["b", "compile"] },
+                { "name": "test", "desc": "Test" }
+            ]
+        }"#;
+
+        let result = parse_task_list_json(json, "mydir", None).unwrap();
+        assert_eq!(result[0].aliases, vec!["b", "compile"]);
+        assert_eq!(result[0].display_name, "Build (b, compile)");
+        assert!(result[1].aliases.is_empty());
+        assert_eq!(result[1].display_name, "Test");
+    }
+
+    #[test]
+    fn test_parse_task_list_json_namespaced_tasks() {
+        let json = r#"{
+            "tasks": [
+                { "name": "lint", "desc": "Lint" },
+                { "name": "docker:push", "desc": "Push the image" },
+                { "name": "docker:build", "desc": "Build the image", "aliases": ["docker:b"] }
+            ]
+        }"#;
+
+        let result = parse_task_list_json(json, "mydir", None).unwrap();
+        let names: Vec<&str> = result.iter().map(|t| t.name.as_str()).collect();
+        // Namespaced tasks keep the colon and sort together
+        assert_eq!(names, vec!["docker:build", "docker:push", "lint"]);
+        assert_eq!(result[0].aliases, vec!["docker:b"]);
+        // Listed by their local name in the namespace's category
+        assert_eq!(result[0].display_name, "Build (docker:b)");
+        assert_eq!(result[0].category, "task:mydir:docker");
+        assert_eq!(result[0].namespace.as_deref(), Some("docker"));
+        assert_eq!(result[1].display_name, "Push");
+        assert_eq!(result[1].category, "task:mydir:docker");
+        assert_eq!(result[2].category, "mydir");
+        assert_eq!(result[2].namespace, None);
+    }
+
+    #[test]
+    fn test_parse_task_list_json_nested_namespaces() {
+        let json = r#"{
+            "tasks": [
+                { "name": "ci:lint:go", "desc": "Lint Go" },
+                { "name": "ci:test", "desc": "Test" },
+                { "name": "ci:lint:js", "desc": "Lint JS" }
+            ]
+        }"#;
+
+        let result = parse_task_list_json(json, "app", None).unwrap();
+        let categories: Vec<&str> = result.iter().map(|t| t.category.as_str()).collect();
+        assert_eq!(
+            categories,
+            vec!["task:app:ci:lint", "task:app:ci:lint", "task:app:ci"]
+        );
+        assert_eq!(result[0].display_name, "Go");
+
+        let names = collect_category_display_names(&result, "app");
+        assert_eq!(names.len(), 2);
+        assert_eq!(names["task:app:ci:lint"], "📋 App · ci:lint");
+        assert_eq!(names["task:app:ci"], "📋 App · ci");
+    }
+
+    #[test]
+    fn test_parse_task_list_json_category_annotation_beats_namespace() {
+        let json = r#"{ "tasks": [{ "name": "docker:build" }] }"#;
+        let mut annotations = HashMap::new();
+        annotations.insert(
+            "docker:build".to_string(),
+            TaskAnnotations {
+                category: Some("Release".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let result = parse_task_list_json(json, "app", Some(&annotations)).unwrap();
+        assert_eq!(result[0].category, "Release");
+    }
+
+    #[test]
+    fn test_split_namespace() {
+        assert_eq!(split_namespace("docker:build"), (Some("docker"), "build"));
+        assert_eq!(split_namespace("a:b:c"), (Some("a:b"), "c"));
+        assert_eq!(split_namespace("build"), (None, "build"));
+        assert_eq!(split_namespace(":build"), (None, ":build"));
+    }
+
     #[test]
     fn test_parse_task_list_json_sorted() {
         let json = r#"{
@@ -480,6 +672,24 @@ tasks:
         assert!(!annotations["deploy"].ignored);
     }
 
+    #[test]
+    fn test_parse_taskfile_annotations_category() {
+        let content = r#"version: '3'
+
+tasks:
+  # @category Deployment
+  deploy:
+    cmds:
+      - ./deploy.sh
+"#;
+
+        let annotations = parse_taskfile_annotations_from_content(content).unwrap();
+        assert_eq!(
+            annotations["deploy"].category,
+            Some("Deployment".to_string())
+        );
+    }
+
     #[test]
     fn test_parse_taskfile_annotations_description() {
         let content = r#"version: '3'
@@ -610,6 +820,8 @@ tasks:
                 emoji: Some("🚀".to_string()),
                 description: Some("Custom deploy description".to_string()),
                 ignored: false,
+                sudo: false,
+                category: None,
             },
         );
 
@@ -645,6 +857,8 @@ tasks:
                 emoji: None,
                 description: Some("Overridden description from annotation".to_string()),
                 ignored: false,
+                sudo: false,
+                category: None,
             },
         );
 
@@ -34,6 +34,8 @@
 //! - [`list_commands`] — Main entry point to list all Terraform commands
 //! - [`parse_tf_resource_addresses`] — Extract resource addresses from `.tf` content
 //! - [`discover_resource_addresses`] — Scan a directory for targetable resources
+//! - [`discover_tfvars`] — List the variable files of a directory
+//! - [`insert_var_file`] — Pass a variable file to `plan`, `apply` or `destroy`
 //!
 //! ## CLI Integration
 //!
@@ -52,6 +54,10 @@
 //! - Workspace selection: `<binary> workspace select <name>`
 //! - Targeted commands: `<binary> <cmd> --target=<addr>` (e.g., `terraform apply --target=aws_instance.web`)
 //!
+//! When a variable file is selected for the directory (e.g. `prod.tfvars`),
+//! `plan`, `apply` and `destroy` get `-var-file=<file>` right after the
+//! subcommand, targeted variants included.
+//!
 //! ## Availability Caching
 //!
 //! The resolved binary name is cached using [`OnceLock`] to avoid repeated
@@ -64,6 +70,7 @@ use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 
+use crate::script::binaries;
 use crate::script::discovery::format_display_name;
 
 /// Cache for resolved terraform/tofu binary name (checked once per process).
@@ -95,7 +102,7 @@ pub struct TerraformCommand {
 
 /// Try a single binary name to see if it responds to `--version`.
 fn check_binary(name: &str) -> bool {
-    Command::new(name)
+    Command::new(binaries::program(name))
         .arg("--version")
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -247,6 +254,36 @@ pub fn discover_resource_addresses(tf_dir: &Path) -> Vec<String> {
     addresses
 }
 
+/// Subcommands that accept `-var-file`
+pub const VAR_FILE_COMMANDS: &[&str] = &["plan", "apply", "destroy"];
+
+/// The variable files (`*.tfvars`, `*.tfvars.json`) in a directory, sorted
+pub fn discover_tfvars(tf_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(tf_dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(String::from))
+        .filter(|name| name.ends_with(".tfvars") || name.ends_with(".tfvars.json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Insert `-var-file=<var_file>` after the subcommand of `args` when it is
+/// one of the [`VAR_FILE_COMMANDS`]; other commands are left unchanged.
+pub fn insert_var_file(args: &mut Vec<String>, var_file: &str) {
+    if args
+        .first()
+        .is_some_and(|command| VAR_FILE_COMMANDS.contains(&command.as_str()))
+    {
+        args.insert(1, format!("-var-file={}", var_file));
+    }
+}
+
 /// Build targeted commands (`plan --target=...`, `apply --target=...`, etc.)
 /// for each resource address.
 fn build_targeted_commands(addresses: &[String], category: &str) -> Vec<TerraformCommand> {
@@ -352,7 +389,7 @@ pub fn list_commands(tf_dir: &Path, category: &str) -> Result<Vec<TerraformComma
     let binary = resolve_binary().context("Neither 'terraform' nor 'tofu' binary is available")?;
 
     // Try to list workspaces — this may fail if `terraform init` hasn't been run
-    let workspace_output = Command::new(binary)
+    let workspace_output = Command::new(binaries::program(binary))
         .arg("workspace")
         .arg("list")
         .current_dir(tf_dir)
@@ -858,6 +895,66 @@ output "result" {
 
     // --- TerraformCommandType ---
 
+    // --- discover_tfvars / insert_var_file ---
+
+    #[test]
+    fn test_discover_tfvars() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for name in [
+            "prod.tfvars",
+            "dev.tfvars",
+            "staging.tfvars.json",
+            "main.tf",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        fs::create_dir(dir.join("old.tfvars")).unwrap();
+
+        assert_eq!(
+            discover_tfvars(dir),
+            vec!["dev.tfvars", "prod.tfvars", "staging.tfvars.json"]
+        );
+        assert!(discover_tfvars(&dir.join("missing")).is_empty());
+    }
+
+    fn args(command: &str) -> Vec<String> {
+        command.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_insert_var_file_common_commands() {
+        for command in ["plan", "apply", "destroy"] {
+            let mut command_args = args(command);
+            insert_var_file(&mut command_args, "prod.tfvars");
+            assert_eq!(command_args, vec![command, "-var-file=prod.tfvars"]);
+        }
+    }
+
+    #[test]
+    fn test_insert_var_file_targeted_commands() {
+        let mut command_args = args("apply --target=aws_instance.web");
+        insert_var_file(&mut command_args, "prod.tfvars");
+        assert_eq!(
+            command_args,
+            vec![
+                "apply",
+                "-var-file=prod.tfvars",
+                "--target=aws_instance.web"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_var_file_skips_other_commands() {
+        for command in ["init", "validate", "fmt", "workspace select plan"] {
+            let mut command_args = args(command);
+            insert_var_file(&mut command_args, "prod.tfvars");
+            assert_eq!(command_args, args(command));
+        }
+    }
+
     #[test]
     fn test_terraform_command_type_equality() {
         assert_eq!(TerraformCommandType::Common, TerraformCommandType::Common);
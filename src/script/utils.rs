@@ -22,10 +22,110 @@ pub fn is_valid_bash_identifier(name: &str) -> bool {
     name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
+/// Number of leading whitespace characters of a line, for the YAML scanners
+pub fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// A YAML scalar or key without surrounding whitespace and quotes
+pub fn unquote(value: &str) -> &str {
+    value.trim().trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// The `@description` annotation in a block of comment lines (in file
+/// order), where `marker` starts a comment (`#`, `//`).
+///
+/// Consecutive `@description <text>` lines add a line each, and the comment
+/// lines between `@description-start` and `@description-end` are taken as
+/// written, blank ones included. Lines are joined with newlines; `None` when
+/// the block has no description.
+pub fn comment_description(comments: &[&str], marker: &str) -> Option<String> {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_block = false;
+
+    for comment in comments {
+        let Some(text) = comment.trim_start().strip_prefix(marker) else {
+            continue;
+        };
+        let text = text.trim();
+        if in_block {
+            if text == "@description-end" {
+                in_block = false;
+            } else {
+                lines.push(text);
+            }
+        } else if text == "@description-start" {
+            in_block = true;
+        } else if let Some(rest) = text.strip_prefix("@description") {
+            if rest.starts_with(char::is_whitespace) && !rest.trim().is_empty() {
+                lines.push(rest.trim());
+            }
+        }
+    }
+
+    let first = lines.iter().position(|line| !line.is_empty())?;
+    let last = lines.iter().rposition(|line| !line.is_empty())?;
+    Some(lines[first..=last].join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_comment_description_single_line() {
+        assert_eq!(
+            comment_description(&["# @emoji 🚀", "#   @description   Deploy it  "], "#"),
+            Some("Deploy it".to_string())
+        );
+        assert_eq!(
+            comment_description(&["# Deploy it", "# @ignore"], "#"),
+            None
+        );
+        // `@description-start` alone is not a one-line description
+        assert_eq!(comment_description(&["# @description-start"], "#"), None);
+    }
+
+    #[test]
+    fn test_comment_description_continuation_lines() {
+        assert_eq!(
+            comment_description(
+                &[
+                    "// @description Build the binary",
+                    "// @description for the current platform",
+                ],
+                "//"
+            ),
+            Some("Build the binary\nfor the current platform".to_string())
+        );
+    }
+
+    #[test]
+    fn test_comment_description_block() {
+        let comments = [
+            "# @description-start",
+            "# Deploy to production.",
+            "#",
+            "# Requires VPN access.",
+            "# @description-end",
+            "# @sudo",
+        ];
+        assert_eq!(
+            comment_description(&comments, "#"),
+            Some("Deploy to production.\n\nRequires VPN access.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_indent_of_and_unquote() {
+        assert_eq!(indent_of("    name: build"), 4);
+        assert_eq!(indent_of("\tname: build"), 1);
+        assert_eq!(indent_of("jobs:"), 0);
+        assert_eq!(unquote(" \"Build app\" "), "Build app");
+        assert_eq!(unquote("'lint'"), "lint");
+        assert_eq!(unquote("plain"), "plain");
+    }
+
     #[test]
     fn test_is_valid_bash_identifier_valid_names() {
         assert!(is_valid_bash_identifier("valid_name"));
@@ -11,6 +11,8 @@
 //! - UI focus (which pane is active)
 //! - Expanded/collapsed category state
 //! - Frequently used commands tracking
+//! - Commands pinned by the user
+//! - Commands marked for a sequence run
 //! - Inline terminal execution state with PTY
 //! - Per-target command history (session-scoped)
 //!
@@ -19,6 +21,7 @@
 //! Scripts are displayed in a tree structure with categories:
 //!
 //! ```text
+//! ▶ 📌 Pinned           (commands pinned with `p`, in pin order)
 //! ▼ ⭐ Frequently Used    (pinned at top when usage exists)
 //!   ├─ build
 //!   └─ test
@@ -38,12 +41,160 @@
 //! - `Details` - The details panel showing script info
 //! - `Output` - The output panel showing execution results (with inline terminal)
 
-use crate::script::ScriptFunction;
-use crate::ui::pty_runner::{CommandHistory, ExecutionStatus, PtyHandle};
+use crate::script::git_info::{self, GitCommitInfo};
+use crate::script::terraform_parser;
+use crate::script::{
+    parse_script_files, ExcludeSet, ParsedScripts, ScriptFile, ScriptFunction, ScriptType,
+};
+use crate::ui::config::ExecutionMode;
+use crate::ui::dotenv;
+use crate::ui::notify::FinishAlerts;
+use crate::ui::project_config::normalize_category_name;
+use crate::ui::pty_runner::{
+    describe_execution, dry_run_command, find_script_file, format_env_pairs, parse_env_pairs,
+    preview_command, reset_execution, working_dir, CommandHistory, EnvOverrides, ExecutionPlan,
+    ExecutionStatus, PtyHandle, RunOptions,
+};
+use crate::ui::sequence::{Sequence, SequenceOutcome, SequenceStep};
 use crate::ui::theme::Theme;
-use crate::usage::FREQUENTLY_USED_CATEGORY;
-use std::collections::HashMap;
-use std::time::Instant;
+use crate::ui::watch::Watch;
+use crate::usage::{
+    PinStore, Pins, SelectedItem, UiState, UsageEntry, FREQUENTLY_USED_CATEGORY, PINNED_CATEGORY,
+    RECENTLY_USED_CATEGORY,
+};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Two clicks on the same script list row within this interval count as a double-click
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Why a command has to be confirmed before it runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfirmReason {
+    /// The command is annotated with `@sudo`
+    Sudo,
+    /// The command looks destructive (e.g. `terraform destroy`, `make clean`)
+    Destructive,
+}
+
+/// A command waiting for a yes/no answer in the confirmation modal
+#[derive(Debug, Clone)]
+pub struct PendingExec {
+    pub func: ScriptFunction,
+    pub reason: ConfirmReason,
+}
+
+/// The var file picker: the `.tfvars` files of one Terraform directory
+#[derive(Debug, Clone)]
+pub struct VarFilePicker {
+    /// The Terraform directory (the script file path)
+    pub dir: PathBuf,
+    /// File names, sorted
+    pub files: Vec<String>,
+    /// Highlighted entry: 0 is "no var file", `n` is `files[n - 1]`
+    pub index: usize,
+}
+
+/// What the `KEY=VALUE` overrides modal edits
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OverrideKind {
+    /// Environment variables for the command
+    #[default]
+    Env,
+    /// `make` variables, appended to the target as `VAR=value` arguments
+    MakeVars,
+}
+
+/// How functions are ordered within each category of the script list
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortMode {
+    /// By display name
+    #[default]
+    Alphabetical,
+    /// By run count, most used first
+    MostUsed,
+    /// Grouped by script type, then by display name
+    ByType,
+    /// By last run, most recent first
+    RecentlyUsed,
+}
+
+impl SortMode {
+    /// Short label shown in the header
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Alphabetical => "A-Z",
+            SortMode::MostUsed => "most used",
+            SortMode::ByType => "type",
+            SortMode::RecentlyUsed => "recent",
+        }
+    }
+
+    /// The mode after this one, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Alphabetical => SortMode::MostUsed,
+            SortMode::MostUsed => SortMode::ByType,
+            SortMode::ByType => SortMode::RecentlyUsed,
+            SortMode::RecentlyUsed => SortMode::Alphabetical,
+        }
+    }
+}
+
+/// How long a footer status message (e.g. "Copied 12 lines") stays visible
+pub const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(3);
+
+/// Frames of the spinner shown next to running commands
+pub const SPINNER_FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// How long each spinner frame is shown
+pub const SPINNER_FRAME_DURATION: Duration = Duration::from_millis(100);
+
+/// The spinner frame shown `elapsed` after the animation started. Frames
+/// follow the clock rather than the redraw rate, so the spinner turns at the
+/// same speed however often the screen is drawn.
+pub fn spinner_frame_at(elapsed: Duration) -> char {
+    spinner_char(elapsed.as_millis() / SPINNER_FRAME_DURATION.as_millis())
+}
+
+/// The spinner character of the `frame`-th frame
+fn spinner_char(frame: u128) -> char {
+    SPINNER_FRAMES[(frame % SPINNER_FRAMES.len() as u128) as usize]
+}
+
+/// Set the system clipboard through the terminal with an OSC 52 escape sequence
+fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    // Format: ESC ] 52 ; c ; <base64-encoded-text> BEL
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let osc = format!("\x1b]52;c;{}\x07", encoded);
+    let mut stdout = std::io::stdout();
+    std::io::Write::write_all(&mut stdout, osc.as_bytes())?;
+    std::io::Write::flush(&mut stdout)
+}
+
+/// Make targets that are treated as destructive
+const DESTRUCTIVE_MAKE_TARGETS: &[&str] = &["clean", "deploy"];
+
+/// Terraform subcommands that are treated as destructive
+const DESTRUCTIVE_TERRAFORM_COMMANDS: &[&str] = &["apply", "destroy"];
+
+/// Whether a function is destructive by its built-in rules: Terraform
+/// `apply`/`destroy` (including `--target=` variants) and make `clean`/`deploy`.
+pub fn is_destructive(func: &ScriptFunction) -> bool {
+    match func.script_type {
+        ScriptType::Terraform => func
+            .name
+            .split_whitespace()
+            .next()
+            .is_some_and(|cmd| DESTRUCTIVE_TERRAFORM_COMMANDS.contains(&cmd)),
+        ScriptType::Makefile => DESTRUCTIVE_MAKE_TARGETS.contains(&func.name.as_str()),
+        _ => false,
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FocusPane {
@@ -56,17 +207,58 @@ pub struct App {
     pub functions: Vec<ScriptFunction>,
     /// Functions that appear in the "Frequently Used" category
     pub frequent_functions: Vec<ScriptFunction>,
+    /// Functions that appear in the "Recently Used" category
+    pub recent_functions: Vec<ScriptFunction>,
+    /// Commands pinned to the "Pinned" category
+    pub pins: Pins,
+    /// Where pins are saved when toggled (`None` when storage is unavailable)
+    pub pin_store: Option<PinStore>,
+    /// Last known exit code per `CommandHistory` key, from the usage data and
+    /// this session's runs (shown as a dot next to frequently used commands)
+    pub last_exit_codes: HashMap<String, i32>,
+    /// The last command run this session (with its original category), for `r`
+    pub last_run: Option<ScriptFunction>,
     pub selected_index: usize,
     pub output_scroll: usize,
+    /// Wrap output lines wider than the output pane instead of truncating them
+    pub wrap_output: bool,
+    /// Number the output lines in a gutter left of the output pane
+    pub show_line_numbers: bool,
+    /// Give the whole screen to the output pane while it has focus (`z`)
+    pub fullscreen_output: bool,
+    /// Draw with the theme's colors (off with `--no-color` or `NO_COLOR`)
+    pub colors_enabled: bool,
     pub script_scroll: usize,
+    /// Height of the script list viewport at the last render (page size)
+    pub script_list_height: usize,
+    /// The inner area of the script list (set during render, used for mouse hit-testing)
+    pub script_list_area: Option<(u16, u16, u16, u16)>,
+    /// Tree index and time of the last left click in the script list (double-click detection)
+    pub last_list_click: Option<(usize, Instant)>,
     pub should_quit: bool,
     pub focus: FocusPane,
     pub expanded_categories: Vec<String>,
+    /// Only show functions of this script type (`None` shows all)
+    pub type_filter: Option<ScriptType>,
+    /// Ordering of functions within regular categories
+    pub sort_mode: SortMode,
+    /// Recorded usage of this project's commands, keyed by function name
+    pub usage_stats: HashMap<String, UsageEntry>,
     pub search_mode: bool,
     pub search_query: String,
     pub show_info: bool,
+    /// Whether the keybinding help overlay (`?`) is visible
+    pub show_help: bool,
     pub category_display_names: HashMap<String, String>,
+    /// Short per-category descriptions shown when a category header is selected
+    pub category_descriptions: HashMap<String, String>,
+    /// Explicit category order from `jarvis.toml` (normalized names)
+    pub category_order: Vec<String>,
+    /// Categories hidden by `jarvis.toml` (normalized names)
+    pub hidden_categories: Vec<String>,
     pub project_title: String,
+    /// The discovered script files, used to preview the command a function runs
+    pub script_files: Vec<ScriptFile>,
 
     // --- Inline terminal execution state ---
     /// Currently running PTY process handle (if any)
@@ -75,12 +267,18 @@ pub struct App {
     pub command_history: CommandHistory,
     /// The function currently being executed or last executed
     pub active_function: Option<ScriptFunction>,
-    /// Animation tick counter for running state border animation
+    /// Spinner frames elapsed since `animation_started` (advanced by the tick loop)
     pub animation_tick: u64,
-    /// Last animation update timestamp
-    pub last_animation_tick: Instant,
+    /// When the spinner animation started
+    pub animation_started: Instant,
+    /// Whether the running command looks blocked on stdin (set by the poll loop)
+    pub awaiting_input: bool,
     /// Whether the 'g' key was pressed (waiting for second 'g' for gg)
     pub pending_g: bool,
+    /// Whether the jump-to-letter key was pressed (waiting for the letter)
+    pub pending_letter_jump: bool,
+    /// Transient footer message and when it was set
+    pub status_message: Option<(String, Instant)>,
 
     // --- Mouse selection state (right pane only) ---
     /// Whether a mouse drag selection is in progress
@@ -95,10 +293,92 @@ pub struct App {
     // --- Theme state ---
     /// The active color theme
     pub theme: Theme,
+    /// Whether Enter runs commands inline (PTY) or full-screen (suspending the TUI)
+    pub execution_mode: ExecutionMode,
+    /// Inline commands still running after this long are killed and marked failed
+    pub command_timeout: Option<Duration>,
+    /// Bell / desktop notification when an inline command finishes
+    pub finish_alerts: FinishAlerts,
+    /// Project root whose `.env` files are loaded into commands (`None` when
+    /// `load_dotenv` is off)
+    pub dotenv_root: Option<PathBuf>,
+    /// Named environment profiles from the project config, in name order
+    pub env_profiles: Vec<(String, EnvOverrides)>,
+    /// Index into `env_profiles` of the active profile (`None` for no profile)
+    pub active_profile: Option<usize>,
+    /// The command re-run on file changes (`w`), if any
+    pub watch: Option<Watch>,
+    /// Commands marked with Space for the next sequence, in mark order (keys
+    /// from [`CommandHistory::key_for`])
+    pub marks: Vec<String>,
+    /// The sequence of marked commands being run (`S`), if any
+    pub sequence: Option<Sequence>,
+    /// Keep running a sequence after a step fails
+    pub sequence_continue_on_error: bool,
+    /// Selected Terraform variable file per Terraform directory (file name,
+    /// keyed by the directory path)
+    pub var_files: BTreeMap<String, String>,
+    /// The Terraform var file picker (`F`), when open
+    pub var_file_picker: Option<VarFilePicker>,
     /// Whether the theme picker modal is visible
     pub show_theme_picker: bool,
     /// Currently highlighted index in the theme picker list
     pub theme_picker_index: usize,
+
+    // --- Confirmation modal state ---
+    /// Command waiting for explicit confirmation before it runs
+    pub pending_confirmation: Option<PendingExec>,
+    /// Function and plan shown by the "explain command" modal (the error
+    /// message when its command could not be built)
+    pub explain: Option<(ScriptFunction, Result<ExecutionPlan, String>)>,
+    /// Extra pattern (from `danger_pattern` in `jarvis.toml`) marking commands as destructive
+    pub danger_pattern: Option<Regex>,
+
+    // --- Git provenance state ---
+    /// Whether to look up the last commit for the selected command (opt-in)
+    pub show_git_info: bool,
+    /// Last commit per source path, cached so git runs at most once per file
+    pub git_info_cache: HashMap<PathBuf, Option<GitCommitInfo>>,
+    /// Last commit for the selected command's source file, if known
+    pub selected_git_info: Option<GitCommitInfo>,
+
+    // --- Category jump overlay state ---
+    /// Whether the category jump overlay is visible
+    pub show_category_jump: bool,
+    /// Fuzzy filter typed into the category jump overlay
+    pub category_jump_query: String,
+    /// Highlighted index within the filtered category list
+    pub category_jump_index: usize,
+
+    // --- Command palette state ---
+    /// Whether the command palette overlay is visible
+    pub palette_mode: bool,
+    /// Fuzzy filter typed into the command palette
+    pub palette_query: String,
+    /// Highlighted index within the palette results
+    pub palette_index: usize,
+
+    // --- Output search state ---
+    /// Whether an output search query is being typed (`/` in the output pane)
+    pub output_search_input: bool,
+    /// The output search query
+    pub output_search_query: String,
+    /// Match starts `(line, col)` of the query in the selected output
+    pub output_search_matches: Vec<(usize, usize)>,
+    /// Index of the current match in `output_search_matches`
+    pub output_search_index: usize,
+
+    // --- Environment overrides modal state ---
+    /// Function whose environment overrides are being edited (modal is open when set)
+    pub env_target: Option<ScriptFunction>,
+    /// Whether the modal edits environment variables or `make` variables
+    pub env_kind: OverrideKind,
+    /// Variables the target's Makefile assigns, suggested in the `make` variables modal
+    pub make_var_candidates: Vec<String>,
+    /// `KEY=VALUE` pairs typed into the env modal
+    pub env_input: String,
+    /// Validation error for the current env input, shown in the modal
+    pub env_error: Option<String>,
 }
 
 impl App {
@@ -112,30 +392,86 @@ impl App {
         Self {
             functions,
             frequent_functions: Vec::new(),
+            recent_functions: Vec::new(),
+            pins: Pins::default(),
+            pin_store: None,
+            last_exit_codes: HashMap::new(),
+            last_run: None,
             selected_index: 0,
             output_scroll: 0,
+            wrap_output: false,
+            show_line_numbers: false,
+            fullscreen_output: false,
+            colors_enabled: true,
             script_scroll: 0,
+            script_list_height: 0,
+            script_list_area: None,
+            last_list_click: None,
             should_quit: false,
             focus: FocusPane::ScriptList,
             expanded_categories: Vec::new(),
+            type_filter: None,
+            sort_mode: SortMode::default(),
+            usage_stats: HashMap::new(),
             search_mode: false,
             search_query: String::new(),
             show_info: false,
+            show_help: false,
             category_display_names: HashMap::new(),
+            category_descriptions: HashMap::new(),
+            category_order: Vec::new(),
+            hidden_categories: Vec::new(),
             project_title,
+            script_files: Vec::new(),
             pty_handle: None,
             command_history: CommandHistory::new(),
             active_function: None,
             animation_tick: 0,
-            last_animation_tick: Instant::now(),
+            animation_started: Instant::now(),
+            awaiting_input: false,
             pending_g: false,
+            pending_letter_jump: false,
+            status_message: None,
             mouse_selecting: false,
             mouse_sel_start: None,
             mouse_sel_end: None,
             output_inner_area: None,
             theme,
+            execution_mode: ExecutionMode::default(),
+            command_timeout: None,
+            finish_alerts: FinishAlerts::default(),
+            dotenv_root: None,
+            env_profiles: Vec::new(),
+            active_profile: None,
+            watch: None,
+            marks: Vec::new(),
+            sequence: None,
+            sequence_continue_on_error: false,
+            var_files: BTreeMap::new(),
+            var_file_picker: None,
             show_theme_picker: false,
             theme_picker_index,
+            pending_confirmation: None,
+            explain: None,
+            danger_pattern: None,
+            show_git_info: false,
+            git_info_cache: HashMap::new(),
+            selected_git_info: None,
+            show_category_jump: false,
+            category_jump_query: String::new(),
+            category_jump_index: 0,
+            palette_mode: false,
+            palette_query: String::new(),
+            palette_index: 0,
+            output_search_input: false,
+            output_search_query: String::new(),
+            output_search_matches: Vec::new(),
+            output_search_index: 0,
+            env_target: None,
+            env_kind: OverrideKind::Env,
+            make_var_candidates: Vec::new(),
+            env_input: String::new(),
+            env_error: None,
         }
     }
 
@@ -144,6 +480,107 @@ impl App {
         self.frequent_functions = functions;
     }
 
+    /// Set the recently used functions to display in the special category
+    pub fn set_recent_functions(&mut self, functions: Vec<ScriptFunction>) {
+        self.recent_functions = functions;
+    }
+
+    /// Set the recorded usage consulted by the most/recently used sort modes
+    pub fn set_usage_stats(&mut self, stats: HashMap<String, UsageEntry>) {
+        self.usage_stats = stats;
+    }
+
+    /// Pinned functions that still exist, in pin order
+    pub fn pinned_functions(&self) -> Vec<&ScriptFunction> {
+        self.pins
+            .pinned
+            .iter()
+            .filter_map(|key| {
+                self.functions
+                    .iter()
+                    .find(|f| CommandHistory::key_for(f) == *key)
+            })
+            .collect()
+    }
+
+    /// Pin or unpin the selected command and save the pins. The selection
+    /// stays on the same row when it is still in the tree.
+    pub fn toggle_pin(&mut self) {
+        let Some(TreeItem::Function(row)) = self.selected_item() else {
+            self.set_status_message("Select a command to pin it");
+            return;
+        };
+        let Some(func) = self.selected_function() else {
+            return;
+        };
+        let key = CommandHistory::key_for(&func);
+        let pinned = self.pins.toggle(&key);
+
+        let items = self.tree_items();
+        let same_row = items.iter().position(|item| {
+            matches!(item, TreeItem::Function(f)
+                if f.category == row.category && CommandHistory::key_for(f) == key)
+        });
+        match same_row {
+            Some(index) => self.select_index(index),
+            None => {
+                self.select_index(self.selected_index.min(items.len().saturating_sub(1)));
+                self.select_function_if_visible(&func);
+            }
+        }
+
+        let saved = self.pin_store.as_ref().map(|store| store.save(&self.pins));
+        if let Some(Err(e)) = saved {
+            self.set_status_message(format!("Failed to save pins: {e}"));
+        } else if pinned {
+            self.set_status_message(format!("\u{1f4cc} Pinned {}", func.display_name));
+        } else {
+            self.set_status_message(format!("Unpinned {}", func.display_name));
+        }
+    }
+
+    /// Remember the exit code of a function's latest run
+    pub fn record_last_exit_code(&mut self, func: &ScriptFunction, exit_code: i32) {
+        self.last_exit_codes
+            .insert(CommandHistory::key_for(func), exit_code);
+    }
+
+    /// Remember a function as the last command run, under its original
+    /// category (not "Frequently Used")
+    pub fn record_last_run(&mut self, func: &ScriptFunction, original_category: &str) {
+        let mut func = func.clone();
+        func.category = original_category.to_string();
+        self.last_run = Some(func);
+    }
+
+    /// The command to re-run with `r`: the last command run, if it is still
+    /// among the discovered functions. It is selected in the tree when visible
+    /// there; otherwise a footer hint explains why nothing runs.
+    pub fn rerun_target(&mut self) -> Option<ScriptFunction> {
+        let still_exists = self.last_run.as_ref().is_some_and(|last| {
+            self.functions.iter().any(|f| {
+                f.name == last.name
+                    && f.script_type == last.script_type
+                    && f.category == last.category
+            })
+        });
+        if !still_exists {
+            self.last_run = None;
+            self.set_status_message("Nothing to re-run yet");
+            return None;
+        }
+        let func = self.last_run.clone()?;
+        self.select_function_if_visible(&func);
+        Some(func)
+    }
+
+    /// Exit code of a function's latest known run
+    pub fn last_exit_code(&self, func: &ScriptFunction) -> Option<i32> {
+        self.last_exit_codes
+            .get(&CommandHistory::key_for(func))
+            .copied()
+    }
+
     pub fn set_category_display_names(&mut self, display_names: HashMap<String, String>) {
         self.category_display_names = display_names;
     }
@@ -155,6 +592,108 @@ impl App {
             .unwrap_or_else(|| category.to_string())
     }
 
+    /// The script type most of a category's functions have, if it has any
+    pub fn category_script_type(&self, category: &str) -> Option<ScriptType> {
+        dominant_script_type(self.functions.iter().filter(|f| f.category == category))
+    }
+
+    /// A category's display name for the tree header, prefixed with the icon
+    /// of its dominant script type unless it already starts with an emoji
+    /// (file-derived names like "🦀 Api", Nx projects, "⭐ Frequently Used")
+    pub fn category_label(&self, category: &str) -> String {
+        let display_name = self.get_category_display_name(category);
+        let has_emoji = display_name.starts_with(|c: char| !c.is_ascii() && !c.is_alphanumeric());
+        match self.category_script_type(category) {
+            Some(script_type) if !has_emoji => format!("{} {}", script_type.icon(), display_name),
+            _ => display_name,
+        }
+    }
+
+    pub fn set_category_descriptions(&mut self, descriptions: HashMap<String, String>) {
+        self.category_descriptions = descriptions;
+    }
+
+    /// Pin an explicit category order; unlisted categories follow alphabetically.
+    /// Entries match a category's key or display name (see
+    /// [`normalize_category_name`]).
+    pub fn set_category_order(&mut self, order: &[String]) {
+        self.category_order = order.iter().map(|c| normalize_category_name(c)).collect();
+    }
+
+    /// Hide categories from the tree. Entries match like [`Self::set_category_order`].
+    pub fn set_hidden_categories(&mut self, hidden: &[String]) {
+        self.hidden_categories = hidden.iter().map(|c| normalize_category_name(c)).collect();
+    }
+
+    /// Override category display names. Keys match a category's key or its
+    /// current display name (see [`normalize_category_name`]); when several
+    /// match, the alphabetically first key wins.
+    pub fn rename_categories(&mut self, names: &HashMap<String, String>) {
+        let mut overrides: Vec<(String, &String)> = names
+            .iter()
+            .map(|(entry, name)| (normalize_category_name(entry), name))
+            .collect();
+        overrides.sort();
+        let categories: std::collections::HashSet<String> =
+            self.functions.iter().map(|f| f.category.clone()).collect();
+        for category in categories {
+            let [key, display] = self.normalized_category_names(&category);
+            if let Some((_, name)) = overrides
+                .iter()
+                .find(|(entry, _)| *entry == key || *entry == display)
+            {
+                self.category_display_names
+                    .insert(category, (*name).clone());
+            }
+        }
+    }
+
+    pub fn get_category_description(&self, category: &str) -> Option<&str> {
+        self.category_descriptions.get(category).map(String::as_str)
+    }
+
+    /// The real category of a function, resolving entries of the "Pinned" and
+    /// "Frequently Used" categories to the category they were discovered in
+    pub fn original_category(&self, func: &ScriptFunction) -> String {
+        if is_special_category(&func.category) {
+            self.functions
+                .iter()
+                .find(|f| f.name == func.name && f.script_type == func.script_type)
+                .map(|f| f.category.clone())
+                .unwrap_or_else(|| func.category.clone())
+        } else {
+            func.category.clone()
+        }
+    }
+
+    /// The script file a function is run from, if it is known
+    pub fn script_file_for(&self, func: &ScriptFunction) -> Option<&ScriptFile> {
+        find_script_file(func, &self.original_category(func), &self.script_files)
+    }
+
+    /// Whether the tool that runs a function is installed (functions without a
+    /// known script file are assumed runnable)
+    pub fn is_available(&self, func: &ScriptFunction) -> bool {
+        self.script_file_for(func).is_none_or(|sf| sf.available)
+    }
+
+    /// Check that a function can run before anything is spawned for it. When
+    /// its tool is missing, the footer explains why and `false` is returned.
+    pub fn check_available(&mut self, func: &ScriptFunction) -> bool {
+        if self.is_available(func) {
+            return true;
+        }
+        let binary = self
+            .script_file_for(func)
+            .and_then(ScriptFile::binary)
+            .unwrap_or("its tool");
+        self.set_status_message(format!(
+            "Cannot run {}: '{}' is not installed or not in PATH",
+            func.display_name, binary
+        ));
+        false
+    }
+
     pub fn toggle_focus(&mut self) {
         self.focus = match self.focus {
             FocusPane::ScriptList => {
@@ -215,13 +754,40 @@ impl App {
         ExecutionStatus::Idle
     }
 
-    /// Update the animation tick (called from the event loop)
+    /// How long the selected function's command has been running, or how long
+    /// its last run took once it finished. `None` if it has not run.
+    pub fn current_execution_duration(&self) -> Option<Duration> {
+        let selected = self.selected_function()?;
+        let (started_at, finished_at) = match self.pty_handle {
+            Some(ref handle)
+                if self.active_function.as_ref().is_some_and(|active| {
+                    active.name == selected.name && active.script_type == selected.script_type
+                }) =>
+            {
+                (handle.started_at, handle.poll_finished_at())
+            }
+            _ => {
+                let state = self.command_history.get(&selected)?;
+                (state.started_at, state.finished_at)
+            }
+        };
+        Some(finished_at.map_or_else(
+            || started_at.elapsed(),
+            |finished| finished.saturating_duration_since(started_at),
+        ))
+    }
+
+    /// Update the animation tick from the time since the animation started
+    /// (called from the event loop)
     pub fn tick_animation(&mut self) {
-        let now = Instant::now();
-        if now.duration_since(self.last_animation_tick).as_millis() >= 100 {
-            self.animation_tick = self.animation_tick.wrapping_add(1);
-            self.last_animation_tick = now;
-        }
+        let frames =
+            self.animation_started.elapsed().as_millis() / SPINNER_FRAME_DURATION.as_millis();
+        self.animation_tick = u64::try_from(frames).unwrap_or(u64::MAX);
+    }
+
+    /// The current spinner frame (see [`spinner_frame_at`])
+    pub fn spinner_frame(&self) -> char {
+        spinner_char(u128::from(self.animation_tick))
     }
 
     /// Finalize a completed PTY handle: move it into command history
@@ -240,55 +806,115 @@ impl App {
         }
     }
 
-    /// Get the total number of scrollable lines in the terminal output for the selected function
-    pub fn terminal_total_lines(&self) -> usize {
-        let selected = self.selected_function();
-        // Check running PTY if it belongs to the selected function
-        if let Some(ref handle) = self.pty_handle {
-            if let (Some(ref active), Some(ref sel)) = (&self.active_function, &selected) {
-                if active.name == sel.name && active.script_type == sel.script_type {
-                    return crate::ui::terminal_widget::total_content_lines(&handle.parser);
-                }
+    /// Whether a command is currently running in the inline PTY
+    pub fn is_command_running(&self) -> bool {
+        self.pty_handle
+            .as_ref()
+            .is_some_and(|handle| handle.poll_status() == ExecutionStatus::Running)
+    }
+
+    /// Cancel the command running in the inline PTY, if any.
+    ///
+    /// Returns `true` if a cancellation was started.
+    pub fn cancel_running_command(&mut self) -> bool {
+        match self.pty_handle.as_ref() {
+            Some(handle) if handle.poll_status() == ExecutionStatus::Running => {
+                handle.kill().is_ok()
             }
+            _ => false,
         }
-        // Check history for the selected function
-        if let Some(ref func) = selected {
-            if let Some(state) = self.command_history.get(func) {
-                return crate::ui::terminal_widget::total_content_lines(&state.parser);
-            }
+    }
+
+    /// Largest output scroll offset for the selected function: the number of
+    /// scrollback rows above the visible screen of its terminal output
+    pub fn max_output_scroll(&self) -> usize {
+        self.output_parser()
+            .map(|parser| crate::ui::terminal_widget::max_scrollback(&parser))
+            .unwrap_or(0)
+    }
+
+    /// Move the output scroll offset by `delta` lines (positive scrolls up
+    /// towards older output), clamped to `0..=max_output_scroll()`. The
+    /// current offset is clamped first, so an offset left over from a longer
+    /// output never needs extra key presses to come back into range.
+    fn scroll_output_by(&mut self, delta: isize) {
+        let max = self.max_output_scroll();
+        let current = self.output_scroll.min(max);
+        self.output_scroll = current.saturating_add_signed(delta).min(max);
+        self.update_output_follow();
+    }
+
+    /// The auto-follow flag of the selected function's output: the live
+    /// PTY's while it runs, otherwise its saved history entry's
+    fn output_follow_mut(&mut self) -> Option<&mut bool> {
+        let selected = self.selected_function()?;
+        if let Some(ref mut handle) = self.pty_handle {
+            let is_selected = self.active_function.as_ref().is_some_and(|active| {
+                active.name == selected.name && active.script_type == selected.script_type
+            });
+            return is_selected.then_some(&mut handle.auto_follow);
+        }
+        self.command_history
+            .get_mut(&selected)
+            .map(|state| &mut state.auto_follow)
+    }
+
+    /// Follow new output again at the bottom, stop following anywhere above it
+    fn update_output_follow(&mut self) {
+        let at_bottom = self.output_scroll == 0;
+        if let Some(follow) = self.output_follow_mut() {
+            *follow = at_bottom;
+        }
+    }
+
+    /// Whether the selected function's output sticks to the bottom as it grows
+    pub fn is_following_output(&self) -> bool {
+        let Some(selected) = self.selected_function() else {
+            return true;
+        };
+        if let Some(ref handle) = self.pty_handle {
+            let is_selected = self.active_function.as_ref().is_some_and(|active| {
+                active.name == selected.name && active.script_type == selected.script_type
+            });
+            return !is_selected || handle.auto_follow;
+        }
+        self.command_history
+            .get(&selected)
+            .is_none_or(|state| state.auto_follow)
+    }
+
+    /// Called on every redraw: snap the output view back to the bottom while
+    /// the selected output is being followed
+    pub fn follow_output(&mut self) {
+        if self.is_following_output() {
+            self.output_scroll = 0;
         }
-        0
     }
 
     /// Scroll output down by half a page
     pub fn scroll_output_half_page_down(&mut self, visible_height: usize) {
-        let half = visible_height / 2;
-        let total = self.terminal_total_lines();
-        let max_scroll = total.saturating_sub(visible_height);
+        let half = (visible_height / 2).max(1);
         // output_scroll represents "how many lines from the bottom we've scrolled up"
         // So scrolling "down" (towards bottom) means decreasing the offset
-        self.output_scroll = self.output_scroll.saturating_sub(half);
-        let _ = max_scroll; // max_scroll not needed for down scroll
+        self.scroll_output_by(-isize::try_from(half).unwrap_or(isize::MAX));
     }
 
     /// Scroll output up by half a page
     pub fn scroll_output_half_page_up(&mut self, visible_height: usize) {
-        let half = visible_height / 2;
-        let total = self.terminal_total_lines();
-        let max_scroll = total.saturating_sub(visible_height);
-        self.output_scroll = (self.output_scroll + half).min(max_scroll);
+        let half = (visible_height / 2).max(1);
+        self.scroll_output_by(isize::try_from(half).unwrap_or(isize::MAX));
     }
 
     /// Jump to the bottom of output
     pub fn scroll_output_to_bottom(&mut self) {
         self.output_scroll = 0;
+        self.update_output_follow();
     }
 
-    /// Jump to the top of output
+    /// Jump to the top of output (the first scrollback row at the top of the pane)
     pub fn scroll_output_to_top(&mut self) {
-        let total = self.terminal_total_lines();
-        // Max scroll would show the very first line at the top
-        self.output_scroll = total;
+        self.output_scroll = self.max_output_scroll();
+        self.update_output_follow();
     }
 
     /// Clear any active mouse selection
@@ -298,17 +924,186 @@ impl App {
         self.mouse_sel_end = None;
     }
 
+    /// Toggle soft-wrapping of long output lines
+    pub fn toggle_wrap_output(&mut self) {
+        self.wrap_output = !self.wrap_output;
+        // Selection coordinates depend on the layout, so drop any selection
+        self.clear_mouse_selection();
+        self.set_status_message(if self.wrap_output {
+            "Line wrap on"
+        } else {
+            "Line wrap off"
+        });
+    }
+
+    /// Toggle the line-number gutter of the output pane
+    pub fn toggle_line_numbers(&mut self) {
+        self.show_line_numbers = !self.show_line_numbers;
+        // Selection coordinates depend on the layout, so drop any selection
+        self.clear_mouse_selection();
+        self.set_status_message(if self.show_line_numbers {
+            "Line numbers on"
+        } else {
+            "Line numbers off"
+        });
+    }
+
+    /// Toggle the fullscreen output view
+    pub fn toggle_fullscreen_output(&mut self) {
+        self.fullscreen_output = !self.fullscreen_output;
+        // Selection coordinates depend on the layout, so drop any selection
+        self.clear_mouse_selection();
+        self.set_status_message(if self.fullscreen_output {
+            "Fullscreen output on"
+        } else {
+            "Fullscreen output off"
+        });
+    }
+
+    /// Whether the output pane takes the whole screen: fullscreen is on and
+    /// the output has focus, so going back to the list shows it again
+    pub fn is_output_fullscreen(&self) -> bool {
+        self.fullscreen_output && self.focus == FocusPane::Output && self.has_terminal_output()
+    }
+
+    /// Start typing an output search query
+    pub fn start_output_search(&mut self) {
+        self.output_search_input = true;
+        self.output_search_query.clear();
+        self.output_search_matches.clear();
+        self.output_search_index = 0;
+    }
+
+    pub fn output_search_push_char(&mut self, c: char) {
+        self.output_search_query.push(c);
+    }
+
+    pub fn output_search_pop_char(&mut self) {
+        self.output_search_query.pop();
+    }
+
+    /// Stop typing and drop the query and its highlights
+    pub fn clear_output_search(&mut self) {
+        self.output_search_input = false;
+        self.output_search_query.clear();
+        self.output_search_matches.clear();
+        self.output_search_index = 0;
+    }
+
+    /// Whether an output search has highlighted matches
+    pub fn has_output_search(&self) -> bool {
+        !self.output_search_matches.is_empty()
+    }
+
+    /// Search the selected output for the typed query and jump to the first
+    /// match at or below the top of the current view (wrapping around)
+    pub fn confirm_output_search(&mut self) {
+        self.output_search_input = false;
+        let Some(parser) = self.output_parser() else {
+            self.clear_output_search();
+            return;
+        };
+        self.output_search_matches =
+            crate::ui::terminal_widget::find_matches(&parser, &self.output_search_query);
+        if self.output_search_matches.is_empty() {
+            if !self.output_search_query.is_empty() {
+                self.set_status_message(format!("No matches for '{}'", self.output_search_query));
+            }
+            self.output_search_query.clear();
+            return;
+        }
+
+        let max = crate::ui::terminal_widget::max_scrollback(&parser);
+        let top_line = max - self.output_scroll.min(max);
+        self.output_search_index = self
+            .output_search_matches
+            .iter()
+            .position(|&(line, _)| line >= top_line)
+            .unwrap_or(0);
+        self.scroll_to_output_match();
+    }
+
+    /// Jump to the next output search match (`n`)
+    pub fn output_search_next(&mut self) {
+        let count = self.output_search_matches.len();
+        if count > 0 {
+            self.output_search_index = (self.output_search_index + 1) % count;
+            self.scroll_to_output_match();
+        }
+    }
+
+    /// Jump to the previous output search match (`N`)
+    pub fn output_search_previous(&mut self) {
+        let count = self.output_search_matches.len();
+        if count > 0 {
+            self.output_search_index = (self.output_search_index + count - 1) % count;
+            self.scroll_to_output_match();
+        }
+    }
+
+    /// The output search match the user navigated to, if any
+    pub fn current_output_match(&self) -> Option<(usize, usize)> {
+        self.output_search_matches
+            .get(self.output_search_index)
+            .copied()
+    }
+
+    /// Scroll the current match into view and report its position in the footer
+    fn scroll_to_output_match(&mut self) {
+        let (Some((line, _)), Some(parser)) = (self.current_output_match(), self.output_parser())
+        else {
+            return;
+        };
+        self.output_scroll =
+            crate::ui::terminal_widget::scroll_offset_for_line(&parser, line, self.output_scroll);
+        self.update_output_follow();
+        self.set_status_message(format!(
+            "Match {} of {}",
+            self.output_search_index + 1,
+            self.output_search_matches.len()
+        ));
+    }
+
+    /// Map an output-pane-relative (row, col) to terminal coordinates,
+    /// accounting for the line-number gutter and wrapped lines
+    fn output_position(&self, row: usize, col: usize) -> (usize, usize) {
+        let parser = self.output_parser();
+        let (col, gutter) = match parser.as_ref().filter(|_| self.show_line_numbers) {
+            Some(parser) => {
+                let lines = crate::ui::terminal_widget::numbered_line_count(parser);
+                (
+                    crate::ui::terminal_widget::content_column(col, lines),
+                    crate::ui::terminal_widget::gutter_width(lines),
+                )
+            }
+            None => (col, 0),
+        };
+        match (self.wrap_output, self.output_inner_area, parser) {
+            (true, Some((_, _, width, height)), Some(parser)) => {
+                crate::ui::terminal_widget::wrapped_source_position(
+                    &parser,
+                    self.output_scroll,
+                    (width as usize).saturating_sub(gutter),
+                    height as usize,
+                    (row, col),
+                )
+            }
+            _ => (row, col),
+        }
+    }
+
     /// Start a mouse drag selection at the given terminal-relative (row, col)
     pub fn start_mouse_selection(&mut self, row: usize, col: usize) {
+        let position = self.output_position(row, col);
         self.mouse_selecting = true;
-        self.mouse_sel_start = Some((row, col));
-        self.mouse_sel_end = Some((row, col));
+        self.mouse_sel_start = Some(position);
+        self.mouse_sel_end = Some(position);
     }
 
     /// Update the mouse drag selection end position
     pub fn update_mouse_selection(&mut self, row: usize, col: usize) {
         if self.mouse_selecting {
-            self.mouse_sel_end = Some((row, col));
+            self.mouse_sel_end = Some(self.output_position(row, col));
         }
     }
 
@@ -335,784 +1130,4639 @@ impl App {
             return false;
         }
 
-        let scroll_offset = self.output_scroll;
-        let selected = self.selected_function();
-
-        let text = if let Some(ref handle) = self.pty_handle {
-            let is_selected =
-                if let (Some(ref active), Some(ref sel)) = (&self.active_function, &selected) {
-                    active.name == sel.name && active.script_type == sel.script_type
-                } else {
-                    false
-                };
-            if is_selected {
-                crate::ui::terminal_widget::get_selected_text(
-                    &handle.parser,
-                    scroll_offset,
-                    start,
-                    end,
-                )
-            } else {
-                String::new()
-            }
-        } else if let Some(ref func) = selected {
-            if let Some(state) = self.command_history.get(func) {
+        let text = self
+            .output_parser()
+            .map(|parser| {
                 crate::ui::terminal_widget::get_selected_text(
-                    &state.parser,
-                    scroll_offset,
+                    &parser,
+                    self.output_scroll,
                     start,
                     end,
                 )
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
+            })
+            .unwrap_or_default();
 
         if !text.is_empty() {
-            // Use OSC 52 escape sequence to set the system clipboard via the terminal.
-            // Format: ESC ] 52 ; c ; <base64-encoded-text> BEL
-            use base64::Engine;
-            let encoded = base64::engine::general_purpose::STANDARD.encode(&text);
-            let osc = format!("\x1b]52;c;{}\x07", encoded);
-            let _ = std::io::Write::write_all(&mut std::io::stdout(), osc.as_bytes());
-            let _ = std::io::Write::flush(&mut std::io::stdout());
+            if let Err(e) = copy_to_clipboard(&text) {
+                self.set_status_message(format!("Clipboard unavailable: {}", e));
+            }
         }
 
         // Keep selection visible (don't clear highlight yet — cleared on next click)
         true
     }
 
-    pub fn toggle_info(&mut self) {
-        self.show_info = !self.show_info;
+    /// The terminal parser holding the output shown for the selected function:
+    /// the live PTY while it runs, otherwise its saved history entry
+    fn output_parser(&self) -> Option<Arc<Mutex<vt100::Parser>>> {
+        let selected = self.selected_function()?;
+        if let Some(ref handle) = self.pty_handle {
+            let is_selected = self.active_function.as_ref().is_some_and(|active| {
+                active.name == selected.name && active.script_type == selected.script_type
+            });
+            return is_selected.then(|| Arc::clone(&handle.parser));
+        }
+        self.command_history
+            .get(&selected)
+            .map(|state| Arc::clone(&state.parser))
     }
 
-    pub fn scroll_output_up(&mut self) {
-        // Scrolling "up" means showing earlier content = increasing scroll offset
-        let total = self.terminal_total_lines();
-        let max_scroll = total;
-        if self.output_scroll < max_scroll {
-            self.output_scroll += 1;
+    /// Clear the selected function's finished output (`d`), leaving it `Idle`.
+    /// The output of a running command cannot be cleared.
+    pub fn clear_output(&mut self) {
+        let Some(func) = self.selected_function() else {
+            return;
+        };
+        let running = self.pty_handle.is_some()
+            && self.active_function.as_ref().is_some_and(|active| {
+                active.name == func.name && active.script_type == func.script_type
+            });
+        if running {
+            self.set_status_message("Cannot clear the output of a running command");
+            return;
         }
+        let Some(state) = self.command_history.get_mut(&func) else {
+            self.set_status_message("Nothing to clear");
+            return;
+        };
+        reset_execution(state);
+        self.output_scroll = 0;
+        self.clear_output_search();
+        self.clear_mouse_selection();
+        self.set_status_message(format!("Cleared output of {}", func.display_name));
     }
 
-    pub fn scroll_output_down(&mut self) {
-        // Scrolling "down" means showing later content = decreasing scroll offset
-        if self.output_scroll > 0 {
-            self.output_scroll -= 1;
+    /// Copy the selected function's whole output (scrollback included) to the
+    /// clipboard and report it in the footer. Returns the number of lines copied.
+    pub fn copy_full_output(&mut self) -> usize {
+        let text = self
+            .output_parser()
+            .map(|parser| crate::ui::terminal_widget::get_full_text(&parser))
+            .unwrap_or_default();
+
+        if text.is_empty() {
+            self.set_status_message("Nothing to copy");
+            return 0;
         }
-    }
 
-    pub fn reset_output_scroll(&mut self) {
-        self.output_scroll = 0;
+        if let Err(e) = copy_to_clipboard(&text) {
+            self.set_status_message(format!("Clipboard unavailable: {}", e));
+            return 0;
+        }
+        let lines = text.lines().count();
+        self.set_status_message(format!(
+            "Copied {} line{}",
+            lines,
+            if lines == 1 { "" } else { "s" }
+        ));
+        lines
     }
 
-    pub fn toggle_category(&mut self, category: &str) {
-        if let Some(pos) = self.expanded_categories.iter().position(|c| c == category) {
-            self.expanded_categories.remove(pos);
-        } else {
-            self.expanded_categories.push(category.to_string());
+    /// Copy the exact command line the selected function runs (see
+    /// [`preview_command`]) to the clipboard, to paste into a shell, and
+    /// confirm it in the footer. Returns the copied command line.
+    pub fn copy_command_line(&mut self) -> Option<String> {
+        let Some(func) = self.selected_function() else {
+            self.set_status_message("No command selected");
+            return None;
+        };
+        let Some(script_file) = self.script_file_for(&func) else {
+            self.set_status_message(format!("No script file found for {}", func.display_name));
+            return None;
+        };
+        let line = preview_command(
+            &func,
+            script_file,
+            &self.preview_options(&func, script_file),
+        );
+
+        if let Err(e) = copy_to_clipboard(&line) {
+            self.set_status_message(format!("Clipboard unavailable: {}", e));
+            return None;
         }
+        self.set_status_message(format!("Copied command: {}", line));
+        Some(line)
     }
 
-    pub fn is_category_expanded(&self, category: &str) -> bool {
-        self.expanded_categories.contains(&category.to_string())
+    /// The selected function and the script file it was read from, to open
+    /// in an editor. Reports in the footer when there is none.
+    pub fn editor_target(&mut self) -> Option<(ScriptFunction, ScriptFile)> {
+        let Some(func) = self.selected_function() else {
+            self.set_status_message("No command selected");
+            return None;
+        };
+        let Some(script_file) = self.script_file_for(&func).cloned() else {
+            self.set_status_message(format!("No script file found for {}", func.display_name));
+            return None;
+        };
+        Some((func, script_file))
     }
 
-    pub fn expand_category(&mut self, category: &str) {
-        if !self.is_category_expanded(category) {
-            self.expanded_categories.push(category.to_string());
+    /// Parse `script_file` again and replace the functions read from it, so
+    /// edits show up without restarting. Parse errors keep the old functions
+    /// and are reported in the footer.
+    pub fn reload_script_file(&mut self, script_file: &ScriptFile) {
+        let parsed = parse_script_files(std::slice::from_ref(script_file));
+        if let Some((path, e)) = parsed.parse_errors.first() {
+            self.set_status_message(format!("Failed to reload {}: {}", path, e));
+            return;
+        }
+
+        let from_file = |f: &ScriptFunction, script_files: &[ScriptFile]| {
+            find_script_file(f, &f.category, script_files)
+                .is_some_and(|s| s.path == script_file.path)
+        };
+        let position = self
+            .functions
+            .iter()
+            .position(|f| from_file(f, &self.script_files))
+            .unwrap_or(self.functions.len());
+        self.functions.retain(|f| !from_file(f, &self.script_files));
+
+        let count = parsed.functions.len();
+        self.functions.splice(position..position, parsed.functions);
+        for (category, name) in parsed.category_display_names {
+            self.category_display_names.entry(category).or_insert(name);
         }
+
+        let last = self.tree_items().len().saturating_sub(1);
+        self.select_index(self.selected_index.min(last));
+        self.set_status_message(format!(
+            "Reloaded {} ({} commands)",
+            script_file.display_name, count
+        ));
     }
 
-    pub fn collapse_category(&mut self, category: &str) {
-        if let Some(pos) = self.expanded_categories.iter().position(|c| c == category) {
-            self.expanded_categories.remove(pos);
+    /// Replace the script files and functions with a fresh discovery, keeping
+    /// the expanded categories and the selected item (matched by key) when
+    /// they still exist. Files that failed to parse keep their previous
+    /// functions instead of disappearing.
+    pub fn apply_discovery(&mut self, script_files: Vec<ScriptFile>, parsed: ParsedScripts) {
+        let state = self.ui_state();
+
+        let failed: Vec<PathBuf> = parsed
+            .parse_errors
+            .iter()
+            .map(|(path, _)| PathBuf::from(path))
+            .collect();
+        let kept: Vec<ScriptFunction> = self
+            .functions
+            .iter()
+            .filter(|f| {
+                find_script_file(f, &f.category, &self.script_files)
+                    .is_some_and(|s| failed.contains(&s.path))
+            })
+            .cloned()
+            .collect();
+
+        let mut functions = parsed.functions;
+        functions.extend(kept);
+        self.functions = functions;
+        self.script_files = script_files;
+        for (category, name) in parsed.category_display_names {
+            self.category_display_names.entry(category).or_insert(name);
+        }
+        self.category_descriptions
+            .extend(parsed.category_descriptions);
+
+        // Frequently and recently used entries follow their refreshed functions
+        let frequent = std::mem::take(&mut self.frequent_functions);
+        self.frequent_functions = self.refreshed_functions(&frequent);
+        let recent = std::mem::take(&mut self.recent_functions);
+        self.recent_functions = self.refreshed_functions(&recent);
+
+        self.restore_ui_state(&state);
+
+        let mut message = format!(
+            "Refreshed {} commands from {} files",
+            self.functions.len(),
+            self.script_files.len()
+        );
+        if !failed.is_empty() {
+            message.push_str(&format!(
+                " ({} failed to parse, kept their previous commands)",
+                failed.len()
+            ));
         }
+        self.set_status_message(message);
     }
 
-    // Handle left arrow: collapse category or move to parent category
-    pub fn handle_left(&mut self) {
-        if let Some(item) = self.selected_item() {
-            match item {
-                TreeItem::Category(category) => {
-                    // Collapse if expanded
-                    if self.is_category_expanded(&category) {
-                        self.collapse_category(&category);
-                    }
-                }
-                TreeItem::Function(func) => {
-                    // Move to parent category
-                    let items = self.tree_items();
-                    // Find the category that contains this function
-                    for (i, tree_item) in items.iter().enumerate() {
-                        if let TreeItem::Category(cat) = tree_item {
-                            if cat == &func.category && i < self.selected_index {
-                                self.selected_index = i;
-                                break;
-                            }
-                        }
-                    }
-                }
+    /// The current versions of `functions` (matched by name and script type),
+    /// dropping those that no longer exist
+    fn refreshed_functions(&self, functions: &[ScriptFunction]) -> Vec<ScriptFunction> {
+        functions
+            .iter()
+            .filter_map(|old| {
+                self.functions
+                    .iter()
+                    .find(|f| f.name == old.name && f.script_type == old.script_type)
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Write the selected function's whole output (scrollback included) to a
+    /// timestamped log file in `dir` and report the path in the footer.
+    /// Write errors are reported in the footer too. Returns the path written.
+    pub fn save_full_output(&mut self, dir: &Path) -> Option<PathBuf> {
+        let func = self.selected_function()?;
+        let text = self
+            .output_parser()
+            .map(|parser| crate::ui::terminal_widget::get_full_text(&parser))
+            .unwrap_or_default();
+        if text.is_empty() {
+            self.set_status_message("Nothing to save");
+            return None;
+        }
+
+        let file_name =
+            output_log_file_name(&CommandHistory::key_for(&func), &chrono::Local::now());
+        let path = unique_path(&dir.join(file_name));
+        match std::fs::write(&path, format!("{}\n", text)) {
+            Ok(()) => {
+                self.set_status_message(format!("Saved output to {}", path.display()));
+                Some(path)
+            }
+            Err(e) => {
+                self.set_status_message(format!("Failed to save output: {}", e));
+                None
             }
         }
     }
 
-    // Handle right arrow: expand category or do nothing on function
-    pub fn handle_right(&mut self) {
-        if let Some(TreeItem::Category(category)) = self.selected_item() {
-            // Expand if collapsed
-            if !self.is_category_expanded(&category) {
-                self.expand_category(&category);
-            }
+    /// Whether the UI must keep redrawing without input: a command is running
+    /// (live output, spinner, elapsed time) or a footer message is waiting to expire
+    pub fn needs_tick(&self) -> bool {
+        self.pty_handle.is_some()
+            || self.command_history.has_running()
+            || self.status_message().is_some()
+    }
+
+    /// Show a transient message in the footer
+    pub fn set_status_message(&mut self, message: impl Into<String>) {
+        self.status_message = Some((message.into(), Instant::now()));
+    }
+
+    /// The footer message, if one was set within [`STATUS_MESSAGE_DURATION`]
+    pub fn status_message(&self) -> Option<&str> {
+        self.status_message
+            .as_ref()
+            .filter(|(_, set_at)| set_at.elapsed() < STATUS_MESSAGE_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
+    /// Why a function must be confirmed before it is executed, if it must
+    pub fn confirmation_reason(&self, func: &ScriptFunction) -> Option<ConfirmReason> {
+        if func.sudo {
+            Some(ConfirmReason::Sudo)
+        } else if is_destructive(func)
+            || self
+                .danger_pattern
+                .as_ref()
+                .is_some_and(|pattern| pattern.is_match(&func.name))
+        {
+            Some(ConfirmReason::Destructive)
+        } else {
+            None
         }
     }
 
-    // Get all items in tree view (categories and their functions)
-    // The "Frequently Used" category appears first if there are any frequent functions
-    pub fn tree_items(&self) -> Vec<TreeItem> {
-        let mut items = Vec::new();
+    /// Whether a function must be confirmed before it is executed
+    pub fn requires_confirmation(&self, func: &ScriptFunction) -> bool {
+        self.confirmation_reason(func).is_some()
+    }
 
-        // Add "Frequently Used" category first (if there are frequent functions)
-        if !self.frequent_functions.is_empty() {
-            let frequent_category = FREQUENTLY_USED_CATEGORY.to_string();
+    /// Open the confirmation modal for a function
+    pub fn request_confirmation(&mut self, func: ScriptFunction) {
+        let reason = self
+            .confirmation_reason(&func)
+            .unwrap_or(ConfirmReason::Destructive);
+        self.pending_confirmation = Some(PendingExec { func, reason });
+    }
 
-            // Filter frequent functions by search
-            let frequent_funcs: Vec<&ScriptFunction> = self
-                .frequent_functions
-                .iter()
-                .filter(|f| self.matches_search(f))
-                .collect();
+    /// Dismiss the confirmation modal without running anything
+    pub fn cancel_confirmation(&mut self) {
+        self.pending_confirmation = None;
+    }
+
+    /// Update [`Self::selected_git_info`] for the selected command's source path.
+    /// Runs `git` only the first time a path is seen, and not at all unless
+    /// [`Self::show_git_info`] is enabled.
+    pub fn update_git_info(&mut self, source_path: Option<&Path>) {
+        if !self.show_git_info {
+            self.selected_git_info = None;
+            return;
+        }
+        self.selected_git_info = source_path.and_then(|path| {
+            self.git_info_cache
+                .entry(path.to_path_buf())
+                .or_insert_with(|| git_info::last_commit_for(path))
+                .clone()
+        });
+    }
 
-            // Only show if there are matching functions (when searching)
-            if !self.search_mode || !frequent_funcs.is_empty() {
-                items.push(TreeItem::Category(frequent_category.clone()));
+    /// Open the category jump overlay with an empty filter
+    pub fn open_category_jump(&mut self) {
+        self.show_category_jump = true;
+        self.category_jump_query.clear();
+        self.category_jump_index = 0;
+    }
 
-                // Auto-expand when searching, or show if manually expanded
-                if self.search_mode || self.is_category_expanded(&frequent_category) {
-                    for func in frequent_funcs {
-                        // Create a copy with the "Frequently Used" category
-                        let mut freq_func = func.clone();
-                        freq_func.category = frequent_category.clone();
-                        items.push(TreeItem::Function(freq_func));
-                    }
-                }
-            }
+    pub fn close_category_jump(&mut self) {
+        self.show_category_jump = false;
+        self.category_jump_query.clear();
+        self.category_jump_index = 0;
+    }
+
+    /// Categories matching the jump overlay filter (fuzzy, on key or display name)
+    pub fn category_jump_matches(&self) -> Vec<String> {
+        self.categories()
+            .into_iter()
+            .filter(|cat| {
+                fuzzy_matches(cat, &self.category_jump_query)
+                    || fuzzy_matches(
+                        &self.get_category_display_name(cat),
+                        &self.category_jump_query,
+                    )
+            })
+            .collect()
+    }
+
+    pub fn category_jump_push_char(&mut self, c: char) {
+        self.category_jump_query.push(c);
+        self.category_jump_index = 0;
+    }
+
+    pub fn category_jump_pop_char(&mut self) {
+        self.category_jump_query.pop();
+        self.category_jump_index = 0;
+    }
+
+    pub fn category_jump_next(&mut self) {
+        let count = self.category_jump_matches().len();
+        if count > 0 {
+            self.category_jump_index = (self.category_jump_index + 1) % count;
         }
+    }
 
-        // Add regular categories
-        let categories = self.regular_categories();
+    pub fn category_jump_previous(&mut self) {
+        let count = self.category_jump_matches().len();
+        if count > 0 {
+            self.category_jump_index = (self.category_jump_index + count - 1) % count;
+        }
+    }
 
-        for category in categories {
-            // Filter functions for this category
-            let funcs: Vec<&ScriptFunction> = self
-                .functions
-                .iter()
-                .filter(|f| f.category == category)
-                .filter(|f| self.matches_search(f))
-                .collect();
+    /// Select the highlighted category header in the tree and close the overlay
+    pub fn confirm_category_jump(&mut self) {
+        let target = self
+            .category_jump_matches()
+            .get(self.category_jump_index)
+            .cloned();
+        self.close_category_jump();
+        if let Some(category) = target {
+            self.jump_to_category(&category);
+        }
+    }
 
-            // Only show category if it has matching functions (when searching)
-            if !self.search_mode || !funcs.is_empty() {
-                items.push(TreeItem::Category(category.clone()));
+    /// Open the command palette with an empty filter
+    pub fn open_palette(&mut self) {
+        self.palette_mode = true;
+        self.palette_query.clear();
+        self.palette_index = 0;
+    }
 
-                // Auto-expand categories when searching, or show if manually expanded
-                if self.search_mode || self.is_category_expanded(&category) {
-                    for func in funcs {
-                        items.push(TreeItem::Function(func.clone()));
-                    }
+    pub fn close_palette(&mut self) {
+        self.palette_mode = false;
+        self.palette_query.clear();
+        self.palette_index = 0;
+    }
+
+    /// Every function, across all categories, matching the palette filter,
+    /// best matches first (same scoring as `/` search)
+    pub fn palette_results(&self) -> Vec<&ScriptFunction> {
+        let mut scored: Vec<(i64, &ScriptFunction)> = self
+            .functions
+            .iter()
+            .filter_map(|func| {
+                let category = self.get_category_display_name(&func.category);
+                function_score(func, &category, &self.palette_query).map(|score| (score, func))
+            })
+            .collect();
+        // Stable, so equal scores keep the tree's category order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, func)| func).collect()
+    }
+
+    pub fn palette_push_char(&mut self, c: char) {
+        self.palette_query.push(c);
+        self.palette_index = 0;
+    }
+
+    pub fn palette_pop_char(&mut self) {
+        self.palette_query.pop();
+        self.palette_index = 0;
+    }
+
+    pub fn palette_next(&mut self) {
+        let count = self.palette_results().len();
+        if count > 0 {
+            self.palette_index = (self.palette_index + 1) % count;
+        }
+    }
+
+    pub fn palette_previous(&mut self) {
+        let count = self.palette_results().len();
+        if count > 0 {
+            self.palette_index = (self.palette_index + count - 1) % count;
+        }
+    }
+
+    /// Close the palette and return the highlighted function to run.
+    ///
+    /// The function is also selected in the tree when it is currently visible
+    /// there (so its output shows up), without expanding any category.
+    pub fn confirm_palette(&mut self) -> Option<ScriptFunction> {
+        let target = self
+            .palette_results()
+            .get(self.palette_index)
+            .map(|func| (*func).clone());
+        self.close_palette();
+        let func = target?;
+        self.select_function_if_visible(&func);
+        Some(func)
+    }
+
+    /// Open the env modal for the selected function, prefilled with its
+    /// current overrides. Does nothing when a category header is selected.
+    pub fn open_env_editor(&mut self) {
+        let Some(func) = self.selected_function() else {
+            return;
+        };
+        self.env_input = self
+            .command_history
+            .env_for(&func)
+            .map(format_env_pairs)
+            .unwrap_or_default();
+        self.env_error = None;
+        self.env_kind = OverrideKind::Env;
+        self.env_target = Some(func);
+    }
+
+    /// Open the "explain command" modal for the selected function: the
+    /// program, arguments, working directory and environment it would run with
+    pub fn open_explain(&mut self) {
+        let Some(func) = self.selected_function() else {
+            return;
+        };
+        let Some(script_file) = self.script_file_for(&func).cloned() else {
+            self.set_status_message(format!("No script file found for {}", func.display_name));
+            return;
+        };
+        let options = self.run_options(&func, &script_file);
+        let plan = describe_execution(&func, &script_file, &options).map_err(|e| e.to_string());
+        self.explain = Some((func, plan));
+    }
+
+    /// The selected function when it can be previewed with a dry run (see
+    /// [`dry_run_command`]). Otherwise the footer explains why not.
+    pub fn dry_run_target(&mut self) -> Option<ScriptFunction> {
+        let func = self.selected_function()?;
+        if !self.check_available(&func) {
+            return None;
+        }
+        let supported = self
+            .script_file_for(&func)
+            .is_some_and(|sf| dry_run_command(&func, sf).is_some());
+        if !supported {
+            self.set_status_message(format!(
+                "No dry run available for {} ({})",
+                func.display_name,
+                func.script_type.label()
+            ));
+            return None;
+        }
+        Some(func)
+    }
+
+    /// Close the "explain command" modal
+    pub fn close_explain(&mut self) {
+        self.explain = None;
+    }
+
+    /// Open the overrides modal for the `make` variables of the selected
+    /// target, prefilled with its current overrides and suggesting the
+    /// variables its Makefile assigns. Other script types get a footer hint.
+    pub fn open_make_vars_editor(&mut self) {
+        let Some(func) = self.selected_function() else {
+            return;
+        };
+        if func.script_type != ScriptType::Makefile {
+            self.set_status_message("Variables can only be set for make targets".to_string());
+            return;
+        }
+        self.make_var_candidates = self
+            .script_file_for(&func)
+            .and_then(|script_file| {
+                crate::script::makefile_parser::list_variables(&script_file.path).ok()
+            })
+            .unwrap_or_default();
+        self.env_input = self
+            .command_history
+            .make_vars_for(&func)
+            .map(format_env_pairs)
+            .unwrap_or_default();
+        self.env_error = None;
+        self.env_kind = OverrideKind::MakeVars;
+        self.env_target = Some(func);
+    }
+
+    pub fn close_env_editor(&mut self) {
+        self.env_target = None;
+        self.env_input.clear();
+        self.env_error = None;
+        self.make_var_candidates.clear();
+    }
+
+    pub fn env_push_char(&mut self, c: char) {
+        self.env_input.push(c);
+        self.env_error = None;
+    }
+
+    pub fn env_pop_char(&mut self) {
+        self.env_input.pop();
+        self.env_error = None;
+    }
+
+    /// Validate the modal input and store it for the target function.
+    ///
+    /// Invalid input keeps the modal open with an error; empty input clears
+    /// the overrides so the command runs with the inherited environment (or
+    /// as the plain `make` target).
+    pub fn confirm_env_editor(&mut self) {
+        let Some(func) = self.env_target.clone() else {
+            return;
+        };
+        match parse_env_pairs(&self.env_input) {
+            Ok(env) => {
+                let (cleared, title) = match self.env_kind {
+                    OverrideKind::Env => ("env overrides", "Env"),
+                    OverrideKind::MakeVars => ("make variables", "Make variables"),
+                };
+                let message = if env.is_empty() {
+                    format!("Cleared {} for {}", cleared, func.display_name)
+                } else {
+                    format!(
+                        "{} for {}: {}",
+                        title,
+                        func.display_name,
+                        format_env_pairs(&env)
+                    )
+                };
+                match self.env_kind {
+                    OverrideKind::Env => self.command_history.set_env(&func, env),
+                    OverrideKind::MakeVars => self.command_history.set_make_vars(&func, env),
                 }
+                self.close_env_editor();
+                self.set_status_message(message);
             }
+            Err(e) => self.env_error = Some(e.to_string()),
         }
+    }
 
-        items
+    /// Environment overrides to apply when running `func` (empty if none)
+    pub fn env_for(&self, func: &ScriptFunction) -> EnvOverrides {
+        self.command_history
+            .env_for(func)
+            .cloned()
+            .unwrap_or_default()
     }
 
-    fn matches_search(&self, func: &ScriptFunction) -> bool {
-        if !self.search_mode || self.search_query.is_empty() {
-            return true;
+    /// Full environment for running `func`: `.env` files (when enabled),
+    /// overlaid with the active env profile, overlaid with the function's
+    /// explicit overrides
+    pub fn run_env(&self, func: &ScriptFunction, script_file: &ScriptFile) -> EnvOverrides {
+        let mut env = match (&self.dotenv_root, working_dir(func, script_file)) {
+            (Some(root), Ok(dir)) => dotenv::load_for(root, &dir),
+            _ => EnvOverrides::new(),
+        };
+        if let Some((_, profile_env)) = self.active_profile.and_then(|i| self.env_profiles.get(i)) {
+            env.extend(profile_env.clone());
         }
+        env.extend(self.env_for(func));
+        env
+    }
 
-        let query = self.search_query.to_lowercase();
-        func.display_name.to_lowercase().contains(&query)
-            || func.name.to_lowercase().contains(&query)
-            || func.description.to_lowercase().contains(&query)
-            || func.category.to_lowercase().contains(&query)
+    /// Replace the available env profiles, deactivating any active one
+    pub fn set_env_profiles(&mut self, profiles: Vec<(String, EnvOverrides)>) {
+        self.env_profiles = profiles;
+        self.active_profile = None;
     }
 
-    pub fn enter_search_mode(&mut self) {
-        self.search_mode = true;
-        self.search_query.clear();
-        self.selected_index = 0;
-        self.reset_script_scroll();
+    /// Name of the active env profile, if any
+    pub fn active_profile_name(&self) -> Option<&str> {
+        self.active_profile
+            .and_then(|i| self.env_profiles.get(i))
+            .map(|(name, _)| name.as_str())
     }
 
-    pub fn exit_search_mode(&mut self) {
-        self.search_mode = false;
-        self.search_query.clear();
-        self.selected_index = 0;
-        self.reset_script_scroll();
+    /// Activate the next env profile: none, then each profile in order, then
+    /// none again
+    pub fn cycle_env_profile(&mut self) {
+        if self.env_profiles.is_empty() {
+            self.set_status_message("No env profiles defined in jarvis.toml");
+            return;
+        }
+        self.active_profile = match self.active_profile {
+            None => Some(0),
+            Some(i) if i + 1 < self.env_profiles.len() => Some(i + 1),
+            Some(_) => None,
+        };
+        let name = self.active_profile_name().unwrap_or("none").to_string();
+        self.set_status_message(format!("Env profile: {}", name));
     }
 
-    pub fn search_push_char(&mut self, c: char) {
-        self.search_query.push(c);
-        self.selected_index = 0; // Reset selection when search changes
-        self.reset_script_scroll();
+    /// Start re-running the selected command whenever a file below `root`
+    /// that is not in `excludes` changes, replacing any previous watch.
+    /// Returns the command so the caller can run it right away; `None` (with
+    /// a footer message) when no runnable command is selected or the project
+    /// cannot be watched.
+    pub fn start_watch(&mut self, root: &Path, excludes: &ExcludeSet) -> Option<ScriptFunction> {
+        let func = self.selected_function()?;
+        if !self.check_available(&func) {
+            return None;
+        }
+        if self.requires_confirmation(&func) {
+            self.set_status_message(format!(
+                "Cannot watch {}: it asks for confirmation before running",
+                func.display_name
+            ));
+            return None;
+        }
+        match Watch::start(func.clone(), root, excludes) {
+            Ok(watch) => self.watch = Some(watch),
+            Err(e) => {
+                self.set_status_message(format!("Cannot watch {}: {:#}", func.display_name, e));
+                return None;
+            }
+        }
+        self.set_status_message(format!(
+            "Watching {}: re-runs on file changes (w to stop)",
+            func.display_name
+        ));
+        Some(func)
     }
 
-    pub fn search_pop_char(&mut self) {
-        self.search_query.pop();
-        self.selected_index = 0; // Reset selection when search changes
-        self.reset_script_scroll();
+    /// Stop the active watch, if any
+    pub fn stop_watch(&mut self) {
+        if let Some(watch) = self.watch.take() {
+            self.set_status_message(format!("Stopped watching {}", watch.target.display_name));
+        }
     }
 
-    pub fn selected_item(&self) -> Option<TreeItem> {
-        let items = self.tree_items();
-        items.get(self.selected_index).cloned()
+    /// The watched command, when a file change calls for re-running it now.
+    ///
+    /// A run still in progress is cancelled first; the re-run then happens on
+    /// a later tick, once it has stopped.
+    pub fn watch_rerun_target(&mut self) -> Option<ScriptFunction> {
+        if !self.watch.as_mut()?.poll() {
+            return None;
+        }
+        if self.is_command_running() {
+            self.cancel_running_command();
+            return None;
+        }
+        let watch = self.watch.as_mut()?;
+        watch.mark_rerun();
+        Some(watch.target.clone())
     }
 
-    /// Get the `ScriptFunction` for the currently selected tree item (if a function is selected).
-    /// For "Frequently Used" entries, returns a copy with the original category
-    /// so that `CommandHistory` lookups match the key used at execution time.
-    pub fn selected_function(&self) -> Option<ScriptFunction> {
-        if let Some(TreeItem::Function(func)) = self.selected_item() {
-            if func.category == FREQUENTLY_USED_CATEGORY {
-                // Find the original function to get the real category
+    /// Mark or unmark the selected command for the next sequence (`S`).
+    /// Marks keep the order they were made in.
+    pub fn toggle_mark(&mut self) {
+        let Some(func) = self.selected_function() else {
+            self.set_status_message("Select a command to mark it");
+            return;
+        };
+        let key = CommandHistory::key_for(&func);
+        if let Some(index) = self.marks.iter().position(|mark| *mark == key) {
+            self.marks.remove(index);
+            self.set_status_message(format!("Unmarked {}", func.display_name));
+        } else {
+            self.marks.push(key);
+            self.set_status_message(format!(
+                "Marked {} as step {} (S to run the sequence)",
+                func.display_name,
+                self.marks.len()
+            ));
+        }
+    }
+
+    /// The step number (1-based) of a marked command
+    pub fn mark_position(&self, func: &ScriptFunction) -> Option<usize> {
+        let key = CommandHistory::key_for(func);
+        self.marks
+            .iter()
+            .position(|mark| *mark == key)
+            .map(|index| index + 1)
+    }
+
+    /// Marked commands that still exist, in the order they will run
+    pub fn marked_items(&self) -> Vec<ScriptFunction> {
+        self.marks
+            .iter()
+            .filter_map(|key| {
                 self.functions
                     .iter()
-                    .find(|f| f.name == func.name && f.script_type == func.script_type)
+                    .find(|f| CommandHistory::key_for(f) == *key)
                     .cloned()
-            } else {
-                Some(func)
+            })
+            .collect()
+    }
+
+    /// Start running the marked commands in order, clearing the marks.
+    /// Returns the first step, or `None` (with a footer message) when nothing
+    /// is marked or a step cannot run unattended.
+    pub fn start_sequence(&mut self) -> Option<ScriptFunction> {
+        let steps = self.marked_items();
+        if steps.is_empty() {
+            self.set_status_message("Mark commands with Space to run them in sequence");
+            return None;
+        }
+        for step in &steps {
+            if !self.check_available(step) {
+                return None;
+            }
+            if self.requires_confirmation(step) {
+                self.set_status_message(format!(
+                    "Cannot run {} in a sequence: it asks for confirmation before running",
+                    step.display_name
+                ));
+                return None;
             }
-        } else {
-            None
         }
-    }
 
-    pub fn next(&mut self) {
-        let item_count = self.tree_items().len();
+        let count = steps.len();
+        let sequence = Sequence::new(steps, self.sequence_continue_on_error)?;
+        let first = sequence.current().clone();
+        self.sequence = Some(sequence);
+        self.marks.clear();
+        self.set_status_message(format!("Running a sequence of {} commands", count));
+        Some(first)
+    }
 
-        if item_count > 0 {
-            let old_index = self.selected_index;
-            self.selected_index = (self.selected_index + 1) % item_count;
-            if self.selected_index != old_index {
-                self.output_scroll = 0;
-                self.clear_mouse_selection();
+    /// The next step to run once `finished` is done, ending the sequence
+    /// (with a footer message) after the last step, on a failure, or when
+    /// `finished` is not the current step because another command was run.
+    pub fn sequence_next(
+        &mut self,
+        finished: &ScriptFunction,
+        succeeded: bool,
+    ) -> Option<ScriptFunction> {
+        let sequence = self.sequence.as_mut()?;
+        if CommandHistory::key_for(sequence.current()) != CommandHistory::key_for(finished) {
+            self.sequence = None;
+            self.set_status_message("Sequence interrupted by another command");
+            return None;
+        }
+        let (_, total) = sequence.progress();
+        match sequence.advance(succeeded) {
+            SequenceStep::Run(next) => Some(next),
+            SequenceStep::Done(outcome) => {
+                self.sequence = None;
+                self.set_status_message(match outcome {
+                    SequenceOutcome::Finished { failed } if failed.is_empty() => {
+                        format!("\u{2713} Sequence finished: {} commands succeeded", total)
+                    }
+                    SequenceOutcome::Finished { failed } => format!(
+                        "Sequence finished with {} failed: {}",
+                        failed.len(),
+                        failed.join(", ")
+                    ),
+                    SequenceOutcome::Stopped { step } => {
+                        format!("Sequence stopped: {} failed", step)
+                    }
+                });
+                None
             }
         }
     }
 
-    pub fn previous(&mut self) {
-        let item_count = self.tree_items().len();
+    /// `make` variable overrides to append when running `func` (empty if none)
+    pub fn make_vars_for(&self, func: &ScriptFunction) -> EnvOverrides {
+        self.command_history
+            .make_vars_for(func)
+            .cloned()
+            .unwrap_or_default()
+    }
 
-        if item_count > 0 {
-            let old_index = self.selected_index;
-            if self.selected_index > 0 {
-                self.selected_index -= 1;
-            } else {
-                self.selected_index = item_count - 1;
+    /// The Terraform variable file selected for `script_file`, if any and
+    /// it still exists
+    pub fn var_file_for(&self, script_file: &ScriptFile) -> Option<String> {
+        if script_file.script_type != ScriptType::Terraform {
+            return None;
+        }
+        self.var_files
+            .get(&script_file.path.to_string_lossy().into_owned())
+            .filter(|file| script_file.path.join(file).is_file())
+            .cloned()
+    }
+
+    /// The arguments shown for `func` in previews: its `make` variable
+    /// overrides and Terraform var file, without the environment
+    pub fn preview_options(&self, func: &ScriptFunction, script_file: &ScriptFile) -> RunOptions {
+        RunOptions {
+            make_vars: self.make_vars_for(func),
+            var_file: self.var_file_for(script_file),
+            ..RunOptions::default()
+        }
+    }
+
+    /// Everything entered for running `func`: its full environment (see
+    /// [`App::run_env`]), its `make` variable overrides and Terraform var file
+    pub fn run_options(&self, func: &ScriptFunction, script_file: &ScriptFile) -> RunOptions {
+        RunOptions {
+            env: self.run_env(func, script_file),
+            make_vars: self.make_vars_for(func),
+            dry_run: false,
+            var_file: self.var_file_for(script_file),
+        }
+    }
+
+    /// Open the var file picker for the selected Terraform command's
+    /// directory, highlighting the current selection
+    pub fn open_var_file_picker(&mut self) {
+        let Some(func) = self.selected_function() else {
+            self.set_status_message("No command selected");
+            return;
+        };
+        let Some(script_file) = self
+            .script_file_for(&func)
+            .filter(|sf| sf.script_type == ScriptType::Terraform)
+        else {
+            self.set_status_message("Var files apply to Terraform commands");
+            return;
+        };
+        let dir = script_file.path.clone();
+        let files = terraform_parser::discover_tfvars(&dir);
+        if files.is_empty() {
+            self.set_status_message(format!("No .tfvars files in {}", dir.display()));
+            return;
+        }
+        let index = self
+            .var_file_for(script_file)
+            .and_then(|current| files.iter().position(|file| *file == current))
+            .map_or(0, |position| position + 1);
+        self.var_file_picker = Some(VarFilePicker { dir, files, index });
+    }
+
+    /// Highlight the next entry of the var file picker (wrapping)
+    pub fn var_file_picker_next(&mut self) {
+        if let Some(picker) = self.var_file_picker.as_mut() {
+            picker.index = (picker.index + 1) % (picker.files.len() + 1);
+        }
+    }
+
+    /// Highlight the previous entry of the var file picker (wrapping)
+    pub fn var_file_picker_previous(&mut self) {
+        if let Some(picker) = self.var_file_picker.as_mut() {
+            picker.index = picker.index.checked_sub(1).unwrap_or(picker.files.len());
+        }
+    }
+
+    /// Use the highlighted var file for the picker's directory and close it
+    pub fn confirm_var_file_picker(&mut self) {
+        let Some(picker) = self.var_file_picker.take() else {
+            return;
+        };
+        let key = picker.dir.to_string_lossy().into_owned();
+        match picker.index.checked_sub(1).map(|i| picker.files[i].clone()) {
+            Some(file) => {
+                self.set_status_message(format!("Using var file {}", file));
+                self.var_files.insert(key, file);
             }
-            if self.selected_index != old_index {
-                self.output_scroll = 0;
-                self.clear_mouse_selection();
+            None => {
+                self.var_files.remove(&key);
+                self.set_status_message("No var file");
             }
         }
     }
 
-    /// Ensure the selected item is visible within the scrolled viewport
-    pub fn ensure_selected_visible(&mut self, visible_height: usize) {
-        let item_count = self.tree_items().len();
+    /// Close the var file picker without changing the selection
+    pub fn close_var_file_picker(&mut self) {
+        self.var_file_picker = None;
+    }
 
-        if item_count == 0 {
-            return;
+    /// Select a function in the tree if it is currently visible there,
+    /// without expanding any category
+    fn select_function_if_visible(&mut self, func: &ScriptFunction) {
+        let position = self.tree_items().iter().position(|item| {
+            matches!(item, TreeItem::Function(f)
+                if f.name == func.name && f.script_type == func.script_type)
+        });
+        if let Some(index) = position {
+            self.select_index(index);
+            self.focus = FocusPane::ScriptList;
         }
+    }
 
-        // If selected item is above the scroll window, scroll up
-        if self.selected_index < self.script_scroll {
-            self.script_scroll = self.selected_index;
+    /// Move the selection to a category header, leaving search mode if needed
+    pub fn jump_to_category(&mut self, category: &str) {
+        if self.search_mode {
+            self.exit_search_mode();
         }
+        let position = self
+            .tree_items()
+            .iter()
+            .position(|item| matches!(item, TreeItem::Category(c) if c == category));
+        if let Some(index) = position {
+            if index != self.selected_index {
+                self.selected_index = index;
+                self.output_scroll = 0;
+                self.clear_mouse_selection();
+            }
+            self.focus = FocusPane::ScriptList;
+        }
+    }
+
+    /// Move the selection to the next category header (after the selected
+    /// item, wrapping around) whose display name starts with `letter`,
+    /// ignoring case and any leading emoji. Returns whether one was found.
+    pub fn jump_to_category_letter(&mut self, letter: char) -> bool {
+        let starts_with_letter = |category: &str| {
+            self.get_category_display_name(category)
+                .chars()
+                .find(|c| c.is_alphanumeric())
+                .is_some_and(|first| first.to_lowercase().eq(letter.to_lowercase()))
+        };
+        let items = self.tree_items();
+        let count = items.len();
+        let target = (1..=count)
+            .map(|offset| (self.selected_index + offset) % count)
+            .find(|&index| {
+                matches!(&items[index], TreeItem::Category(category) if starts_with_letter(category))
+            });
+
+        match target {
+            Some(index) => {
+                self.select_index(index);
+                true
+            }
+            None => {
+                self.set_status_message(format!("No category starting with '{}'", letter));
+                false
+            }
+        }
+    }
+
+    /// Wait for the letter of a jump-to-letter: the next key is taken as a
+    /// letter even when it is bound, so every category can be reached.
+    pub fn start_letter_jump(&mut self) {
+        self.pending_letter_jump = true;
+        self.set_status_message("Jump to category: type a letter");
+    }
+
+    /// Finish a jump-to-letter started with [`Self::start_letter_jump`] with
+    /// the next typed character (`None` for any other key, which cancels it).
+    pub fn finish_letter_jump(&mut self, key: Option<char>) -> bool {
+        self.pending_letter_jump = false;
+        match key.filter(|c| c.is_alphanumeric()) {
+            Some(letter) => self.jump_to_category_letter(letter),
+            None => {
+                self.status_message = None;
+                false
+            }
+        }
+    }
+
+    /// Switch between inline and full-screen execution for this session
+    pub fn toggle_execution_mode(&mut self) {
+        self.execution_mode = self.execution_mode.toggled();
+    }
+
+    pub fn toggle_info(&mut self) {
+        self.show_info = !self.show_info;
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    pub fn scroll_output_up(&mut self) {
+        // Scrolling "up" means showing earlier content = increasing scroll offset
+        self.scroll_output_by(1);
+    }
+
+    pub fn scroll_output_down(&mut self) {
+        // Scrolling "down" means showing later content = decreasing scroll offset
+        self.scroll_output_by(-1);
+    }
+
+    pub fn reset_output_scroll(&mut self) {
+        self.output_scroll = 0;
+        self.update_output_follow();
+    }
+
+    pub fn toggle_category(&mut self, category: &str) {
+        if let Some(pos) = self.expanded_categories.iter().position(|c| c == category) {
+            self.expanded_categories.remove(pos);
+        } else {
+            self.expanded_categories.push(category.to_string());
+        }
+    }
+
+    pub fn is_category_expanded(&self, category: &str) -> bool {
+        self.expanded_categories.contains(&category.to_string())
+    }
+
+    pub fn expand_category(&mut self, category: &str) {
+        if !self.is_category_expanded(category) {
+            self.expanded_categories.push(category.to_string());
+        }
+    }
+
+    pub fn collapse_category(&mut self, category: &str) {
+        if let Some(pos) = self.expanded_categories.iter().position(|c| c == category) {
+            self.expanded_categories.remove(pos);
+        }
+    }
+
+    /// Expand every category, keeping the selected item selected
+    pub fn expand_all(&mut self) {
+        let selected = self.selected_item();
+        self.expanded_categories = self.categories();
+
+        if let Some(selected) = selected {
+            if let Some(index) = self
+                .tree_items()
+                .iter()
+                .position(|item| match (item, &selected) {
+                    (TreeItem::Category(category), TreeItem::Category(saved)) => category == saved,
+                    (TreeItem::Function(func), TreeItem::Function(saved)) => {
+                        func.category == saved.category
+                            && CommandHistory::key_for(func) == CommandHistory::key_for(saved)
+                    }
+                    _ => false,
+                })
+            {
+                self.selected_index = index;
+            }
+        }
+    }
+
+    /// Collapse every category, moving the selection to the category that
+    /// contained the selected item
+    pub fn collapse_all(&mut self) {
+        let category = self.selected_item().map(|item| match item {
+            TreeItem::Category(category) => category,
+            TreeItem::Function(func) => func.category,
+        });
+        self.expanded_categories.clear();
+
+        let items = self.tree_items();
+        let index = category
+            .and_then(|category| {
+                items
+                    .iter()
+                    .position(|item| matches!(item, TreeItem::Category(c) if *c == category))
+            })
+            .unwrap_or(0)
+            .min(items.len().saturating_sub(1));
+        self.select_index(index);
+    }
+
+    /// Snapshot the expanded categories and selected item for persistence
+    pub fn ui_state(&self) -> UiState {
+        let selected = self.selected_item().map(|item| match item {
+            TreeItem::Category(category) => SelectedItem::Category(category),
+            TreeItem::Function(func) => SelectedItem::Function(CommandHistory::key_for(&func)),
+        });
+        UiState {
+            expanded_categories: self.expanded_categories.clone(),
+            selected,
+            var_files: self.var_files.clone(),
+        }
+    }
+
+    /// Restore a saved [`UiState`]. Categories that no longer exist are
+    /// dropped, and the selection falls back to the first item when the saved
+    /// one is gone.
+    pub fn restore_ui_state(&mut self, state: &UiState) {
+        let categories = self.categories();
+        self.expanded_categories = state
+            .expanded_categories
+            .iter()
+            .filter(|category| categories.contains(category))
+            .cloned()
+            .collect();
+
+        self.selected_index = state
+            .selected
+            .as_ref()
+            .and_then(|selected| {
+                self.tree_items()
+                    .iter()
+                    .position(|item| match (item, selected) {
+                        (TreeItem::Category(category), SelectedItem::Category(saved)) => {
+                            category == saved
+                        }
+                        (TreeItem::Function(func), SelectedItem::Function(saved)) => {
+                            CommandHistory::key_for(func) == *saved
+                        }
+                        _ => false,
+                    })
+            })
+            .unwrap_or(0);
+        self.var_files.clone_from(&state.var_files);
+    }
+
+    // Handle left arrow: collapse category or move to parent category
+    pub fn handle_left(&mut self) {
+        if let Some(item) = self.selected_item() {
+            match item {
+                TreeItem::Category(category) => {
+                    // Collapse if expanded
+                    if self.is_category_expanded(&category) {
+                        self.collapse_category(&category);
+                    }
+                }
+                TreeItem::Function(func) => {
+                    // Move to parent category
+                    let items = self.tree_items();
+                    // Find the category that contains this function
+                    for (i, tree_item) in items.iter().enumerate() {
+                        if let TreeItem::Category(cat) = tree_item {
+                            if cat == &func.category && i < self.selected_index {
+                                self.selected_index = i;
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Handle right arrow: expand category or do nothing on function
+    pub fn handle_right(&mut self) {
+        if let Some(TreeItem::Category(category)) = self.selected_item() {
+            // Expand if collapsed
+            if !self.is_category_expanded(&category) {
+                self.expand_category(&category);
+            }
+        }
+    }
+
+    // Get all items in tree view (categories and their functions)
+    // The "Pinned", "Frequently Used" and "Recently Used" categories appear
+    // first if they have any functions
+    pub fn tree_items(&self) -> Vec<TreeItem> {
+        let mut items = Vec::new();
+
+        let special_sections = [
+            (PINNED_CATEGORY, self.pinned_functions()),
+            (
+                FREQUENTLY_USED_CATEGORY,
+                self.frequent_functions.iter().collect(),
+            ),
+            (
+                RECENTLY_USED_CATEGORY,
+                self.recent_functions.iter().collect(),
+            ),
+        ];
+        for (special_category, funcs) in special_sections {
+            if funcs.is_empty() {
+                continue;
+            }
+
+            // Filter the section's functions by script type and search
+            let mut funcs: Vec<&ScriptFunction> = funcs
+                .into_iter()
+                .filter(|f| self.matches_type_filter(f))
+                .filter(|f| self.matches_search(f))
+                .collect();
+            self.rank_by_search(&mut funcs);
+
+            // Only show if there are matching functions (when searching or filtering)
+            if !self.hides_empty_categories() || !funcs.is_empty() {
+                items.push(TreeItem::Category(special_category.to_string()));
+
+                // Auto-expand when searching, or show if manually expanded
+                if self.search_mode || self.is_category_expanded(special_category) {
+                    for func in funcs {
+                        // Create a copy under the special category
+                        let mut copy = func.clone();
+                        copy.category = special_category.to_string();
+                        items.push(TreeItem::Function(copy));
+                    }
+                }
+            }
+        }
+
+        // Add regular categories; while searching, categories with the best
+        // matches come first and functions are ranked within each category
+        let mut categories: Vec<(String, Vec<&ScriptFunction>)> = self
+            .regular_categories()
+            .into_iter()
+            .map(|category| {
+                let mut funcs: Vec<&ScriptFunction> = self
+                    .functions
+                    .iter()
+                    .filter(|f| f.category == category)
+                    .filter(|f| self.matches_type_filter(f))
+                    .filter(|f| self.matches_search(f))
+                    .collect();
+                self.sort_functions(&mut funcs);
+                self.rank_by_search(&mut funcs);
+                (category, funcs)
+            })
+            .collect();
+        if self.is_filtering() {
+            categories.sort_by_cached_key(|(_, funcs)| {
+                std::cmp::Reverse(funcs.first().and_then(|f| self.search_score(f)))
+            });
+        }
+
+        for (category, funcs) in categories {
+            // Only show category if it has matching functions (when searching or filtering)
+            if !self.hides_empty_categories() || !funcs.is_empty() {
+                items.push(TreeItem::Category(category.clone()));
+
+                // Auto-expand categories when searching, or show if manually expanded
+                if self.search_mode || self.is_category_expanded(&category) {
+                    for func in funcs {
+                        items.push(TreeItem::Function(func.clone()));
+                    }
+                }
+            }
+        }
+
+        items
+    }
+
+    /// Whether a search query is active (search mode with a non-empty query)
+    fn is_filtering(&self) -> bool {
+        self.search_mode && !self.search_query.is_empty()
+    }
+
+    fn matches_search(&self, func: &ScriptFunction) -> bool {
+        !self.is_filtering() || self.search_score(func).is_some()
+    }
+
+    fn matches_type_filter(&self, func: &ScriptFunction) -> bool {
+        self.type_filter
+            .is_none_or(|script_type| func.script_type == script_type)
+    }
+
+    /// Whether the tree hides categories without matching functions (while
+    /// searching or filtering by script type)
+    fn hides_empty_categories(&self) -> bool {
+        self.search_mode || self.type_filter.is_some()
+    }
+
+    /// Distinct script types of the loaded functions, in order of first appearance
+    pub fn script_types(&self) -> Vec<ScriptType> {
+        let mut types = Vec::new();
+        for func in &self.functions {
+            if !types.contains(&func.script_type) {
+                types.push(func.script_type);
+            }
+        }
+        types
+    }
+
+    /// Cycle the script type filter: all → each present type in turn → all.
+    /// The selection is clamped to the filtered tree.
+    pub fn cycle_type_filter(&mut self) {
+        let types = self.script_types();
+        self.type_filter = match self.type_filter {
+            None => types.first().copied(),
+            Some(current) => types
+                .iter()
+                .position(|t| *t == current)
+                .and_then(|i| types.get(i + 1))
+                .copied(),
+        };
+
+        let last = self.tree_items().len().saturating_sub(1);
+        self.select_index(self.selected_index.min(last));
+        self.set_status_message(match self.type_filter {
+            Some(script_type) => format!("Showing {} only", script_type.label()),
+            None => "Showing all script types".to_string(),
+        });
+    }
+
+    /// Cycle the sort mode of the script list, keeping the selection in range
+    pub fn cycle_sort_mode(&mut self) {
+        self.sort_mode = self.sort_mode.next();
+
+        let last = self.tree_items().len().saturating_sub(1);
+        self.select_index(self.selected_index.min(last));
+        self.set_status_message(format!("Sorted by {}", self.sort_mode.label()));
+    }
+
+    /// Recorded usage of a function, if it has been run from this project
+    fn usage_of(&self, func: &ScriptFunction) -> Option<&UsageEntry> {
+        self.usage_stats
+            .get(&func.name)
+            .filter(|entry| entry.script_type == func.script_type)
+    }
+
+    /// Order functions by the active sort mode. Ties (and functions that were
+    /// never run) fall back to alphabetical order.
+    fn sort_functions(&self, funcs: &mut [&ScriptFunction]) {
+        funcs.sort_by_cached_key(|f| f.display_name.to_lowercase());
+        match self.sort_mode {
+            SortMode::Alphabetical => {}
+            SortMode::MostUsed => funcs.sort_by_key(|f| {
+                std::cmp::Reverse(self.usage_of(f).map_or(0, |entry| entry.count))
+            }),
+            SortMode::ByType => funcs.sort_by_key(|f| f.script_type.label()),
+            SortMode::RecentlyUsed => {
+                funcs.sort_by_key(|f| std::cmp::Reverse(self.usage_of(f).map(|e| e.last_used)));
+            }
+        }
+    }
+
+    /// Fuzzy score of a function against the search query (higher is better).
+    ///
+    /// Names, display names and aliases count fully; descriptions (which
+    /// hold the underlying command line for most tools, e.g.
+    /// `nx run app:build`) and categories count half, so a match in the name
+    /// wins over incidental matches in the description. Categories match by
+    /// key and by display name, so an Nx project is found by "Service Auth"
+    /// as well as by `nx:ws:service.auth`.
+    pub fn search_score(&self, func: &ScriptFunction) -> Option<i64> {
+        let category = self.get_category_display_name(&func.category);
+        function_score(func, &category, &self.search_query)
+    }
+
+    /// Sort functions by search score (best first) while a query is active.
+    /// The sort is stable, so equal scores keep their original order.
+    fn rank_by_search(&self, funcs: &mut [&ScriptFunction]) {
+        if self.is_filtering() {
+            funcs.sort_by_cached_key(|f| std::cmp::Reverse(self.search_score(f)));
+        }
+    }
+
+    /// Select the best-scoring function in the tree (or the first item)
+    fn select_best_match(&mut self) {
+        let items = self.tree_items();
+        let best = items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| match item {
+                TreeItem::Function(func) => Some((i, self.search_score(func))),
+                TreeItem::Category(_) => None,
+            })
+            .fold(
+                None::<(usize, Option<i64>)>,
+                |best, (i, score)| match best {
+                    Some((_, best_score)) if best_score >= score => best,
+                    _ => Some((i, score)),
+                },
+            );
+        self.selected_index = if self.is_filtering() {
+            best.map_or(0, |(i, _)| i)
+        } else {
+            0
+        };
+    }
+
+    pub fn enter_search_mode(&mut self) {
+        self.search_mode = true;
+        self.search_query.clear();
+        self.selected_index = 0;
+        self.reset_script_scroll();
+    }
+
+    pub fn exit_search_mode(&mut self) {
+        self.search_mode = false;
+        self.search_query.clear();
+        self.selected_index = 0;
+        self.reset_script_scroll();
+    }
+
+    pub fn search_push_char(&mut self, c: char) {
+        self.search_query.push(c);
+        self.reset_script_scroll();
+        self.select_best_match(); // Reset selection to the top match when search changes
+    }
+
+    pub fn search_pop_char(&mut self) {
+        self.search_query.pop();
+        self.reset_script_scroll();
+        self.select_best_match(); // Reset selection to the top match when search changes
+    }
+
+    pub fn selected_item(&self) -> Option<TreeItem> {
+        let items = self.tree_items();
+        items.get(self.selected_index).cloned()
+    }
+
+    /// Get the `ScriptFunction` for the currently selected tree item (if a function is selected).
+    /// For "Pinned" and "Frequently Used" entries, returns a copy with the
+    /// original category so that `CommandHistory` lookups match the key used at
+    /// execution time.
+    pub fn selected_function(&self) -> Option<ScriptFunction> {
+        if let Some(TreeItem::Function(func)) = self.selected_item() {
+            if is_special_category(&func.category) {
+                // Find the original function to get the real category
+                self.functions
+                    .iter()
+                    .find(|f| f.name == func.name && f.script_type == func.script_type)
+                    .cloned()
+            } else {
+                Some(func)
+            }
+        } else {
+            None
+        }
+    }
+
+    pub fn next(&mut self) {
+        let item_count = self.tree_items().len();
+
+        if item_count > 0 {
+            self.select_index((self.selected_index + 1) % item_count);
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let item_count = self.tree_items().len();
+
+        if item_count > 0 {
+            if self.selected_index > 0 {
+                self.select_index(self.selected_index - 1);
+            } else {
+                self.select_index(item_count - 1);
+            }
+        }
+    }
+
+    /// Jump to the first item in the list (`gg`)
+    pub fn select_first(&mut self) {
+        self.select_index(0);
+    }
+
+    /// Jump to the last item in the list (`G`)
+    pub fn select_last(&mut self) {
+        let item_count = self.tree_items().len();
+        self.select_index(item_count.saturating_sub(1));
+    }
+
+    /// Move the selection down by `n` items. Stops at the last item, and wraps
+    /// to the first one when already there (like [`Self::next`]).
+    pub fn page_down(&mut self, n: usize) {
+        let last = self.tree_items().len().saturating_sub(1);
+
+        if self.selected_index >= last {
+            self.select_index(0);
+        } else {
+            self.select_index((self.selected_index + n.max(1)).min(last));
+        }
+    }
+
+    /// Move the selection up by `n` items. Stops at the first item, and wraps
+    /// to the last one when already there (like [`Self::previous`]).
+    pub fn page_up(&mut self, n: usize) {
+        if self.selected_index == 0 {
+            self.select_last();
+        } else {
+            self.select_index(self.selected_index.saturating_sub(n.max(1)));
+        }
+    }
+
+    /// Number of items a PageUp/PageDown moves, based on the list viewport
+    pub fn script_page_size(&self) -> usize {
+        self.script_list_height.max(1)
+    }
+
+    /// Select the item at `index`, resetting per-selection output state when it changes
+    fn select_index(&mut self, index: usize) {
+        if self.selected_index != index {
+            self.selected_index = index;
+            self.output_scroll = 0;
+            self.clear_mouse_selection();
+            self.clear_output_search();
+        }
+    }
+
+    /// Whether a modal (info, help, theme picker, var file picker, category jump, palette, env, confirmation) is open
+    pub fn is_modal_open(&self) -> bool {
+        self.show_info
+            || self.show_help
+            || self.show_theme_picker
+            || self.var_file_picker.is_some()
+            || self.show_category_jump
+            || self.palette_mode
+            || self.env_target.is_some()
+            || self.pending_confirmation.is_some()
+    }
+
+    /// Map a screen position to the tree index rendered there, if the position
+    /// is inside the script list and on an item
+    pub fn script_index_at(&self, column: u16, row: u16) -> Option<usize> {
+        let (x, y, width, height) = self.script_list_area?;
+        if column < x || column >= x + width || row < y || row >= y + height {
+            return None;
+        }
+        let index = self.script_scroll + (row - y) as usize;
+        (index < self.tree_items().len()).then_some(index)
+    }
+
+    /// Whether a screen position is inside the script list
+    pub fn is_in_script_list(&self, column: u16, row: u16) -> bool {
+        self.script_list_area.is_some_and(|(x, y, width, height)| {
+            column >= x && column < x + width && row >= y && row < y + height
+        })
+    }
+
+    /// Select the item clicked at a screen position.
+    ///
+    /// Returns `true` when the click completes a double-click on the same item,
+    /// in which case the caller should activate it (as with Enter).
+    pub fn click_script_list(&mut self, column: u16, row: u16, now: Instant) -> bool {
+        let Some(index) = self.script_index_at(column, row) else {
+            return false;
+        };
+
+        self.focus = FocusPane::ScriptList;
+        self.pending_g = false;
+        self.select_index(index);
+
+        let is_double_click = self.last_list_click.is_some_and(|(last, at)| {
+            last == index && now.saturating_duration_since(at) <= DOUBLE_CLICK_INTERVAL
+        });
+        // A double-click consumes the click, so a third click starts over
+        self.last_list_click = (!is_double_click).then_some((index, now));
+        is_double_click
+    }
+
+    /// Ensure the selected item is visible within the scrolled viewport
+    pub fn ensure_selected_visible(&mut self, visible_height: usize) {
+        let item_count = self.tree_items().len();
+
+        if item_count == 0 {
+            return;
+        }
+
+        // If selected item is above the scroll window, scroll up
+        if self.selected_index < self.script_scroll {
+            self.script_scroll = self.selected_index;
+        }
+
+        // If selected item is below the scroll window, scroll down
+        if self.selected_index >= self.script_scroll + visible_height {
+            self.script_scroll = self.selected_index.saturating_sub(visible_height - 1);
+        }
+    }
+
+    pub fn reset_script_scroll(&mut self) {
+        self.script_scroll = 0;
+    }
+
+    /// Get all categories including the "Pinned", "Frequently Used" and
+    /// "Recently Used" categories if applicable
+    pub fn categories(&self) -> Vec<String> {
+        let mut cats = Vec::new();
+
+        // Add "Pinned", "Frequently Used" and "Recently Used" first if they
+        // have functions
+        if !self.pinned_functions().is_empty() {
+            cats.push(PINNED_CATEGORY.to_string());
+        }
+        if !self.frequent_functions.is_empty() {
+            cats.push(FREQUENTLY_USED_CATEGORY.to_string());
+        }
+        if !self.recent_functions.is_empty() {
+            cats.push(RECENTLY_USED_CATEGORY.to_string());
+        }
+
+        // Add regular categories
+        cats.extend(self.regular_categories());
+
+        cats
+    }
+
+    /// Get regular categories (excluding "Pinned" and "Frequently Used", and
+    /// any hidden by `jarvis.toml`), in the configured `category_order` first
+    /// and alphabetically after that
+    fn regular_categories(&self) -> Vec<String> {
+        let mut cats: Vec<String> = self
+            .functions
+            .iter()
+            .map(|f| f.category.clone())
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .filter(|cat| !self.is_category_hidden(cat))
+            .collect();
+        cats.sort();
+        if !self.category_order.is_empty() {
+            cats.sort_by_key(|cat| self.category_rank(cat));
+        }
+        cats
+    }
+
+    /// Position of a category in the configured order (`usize::MAX` when unlisted)
+    fn category_rank(&self, category: &str) -> usize {
+        let [key, display] = self.normalized_category_names(category);
+        self.category_order
+            .iter()
+            .position(|entry| *entry == key || *entry == display)
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Whether `jarvis.toml` hides a category
+    fn is_category_hidden(&self, category: &str) -> bool {
+        let [key, display] = self.normalized_category_names(category);
+        self.hidden_categories
+            .iter()
+            .any(|entry| *entry == key || *entry == display)
+    }
+
+    /// A category's key and display name, normalized for matching config entries
+    fn normalized_category_names(&self, category: &str) -> [String; 2] {
+        [
+            normalize_category_name(category),
+            normalize_category_name(&self.get_category_display_name(category)),
+        ]
+    }
+}
+
+/// The most common script type among functions; ties go to the type seen first
+pub fn dominant_script_type<'a>(
+    functions: impl IntoIterator<Item = &'a ScriptFunction>,
+) -> Option<ScriptType> {
+    let mut counts: Vec<(ScriptType, usize)> = Vec::new();
+    for func in functions {
+        match counts.iter_mut().find(|(t, _)| *t == func.script_type) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((func.script_type, 1)),
+        }
+    }
+    // `max_by_key` keeps the last maximum, so search from the back
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(script_type, _)| script_type)
+}
+
+/// Whether a category is one of the special categories listing copies of
+/// functions from elsewhere in the tree ("Pinned", "Frequently Used",
+/// "Recently Used")
+fn is_special_category(category: &str) -> bool {
+    [
+        PINNED_CATEGORY,
+        FREQUENTLY_USED_CATEGORY,
+        RECENTLY_USED_CATEGORY,
+    ]
+    .contains(&category)
+}
+
+/// Fuzzy score of a function against a query (see [`App::search_score`]),
+/// with `category_name` the display name of its category
+fn function_score(func: &ScriptFunction, category_name: &str, query: &str) -> Option<i64> {
+    let primary = [&func.display_name, &func.name]
+        .into_iter()
+        .chain(&func.aliases)
+        .filter_map(|field| fuzzy_score(field, query));
+    let secondary = [
+        func.description.as_str(),
+        func.category.as_str(),
+        category_name,
+    ]
+    .into_iter()
+    .filter_map(|field| fuzzy_score(field, query).map(|score| score / 2));
+    primary.chain(secondary).max()
+}
+
+/// File name for a saved output log:
+/// `jarvis-output-<target>-<YYYYmmdd-HHMMSS>.log`.
+///
+/// Characters of the target key that are not safe in file names (`:`, `/`,
+/// spaces, ...) become `_`, so `Bash:deploy/prod` gives `Bash_deploy_prod`.
+pub fn output_log_file_name<Tz: chrono::TimeZone>(
+    target_key: &str,
+    timestamp: &chrono::DateTime<Tz>,
+) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let target: String = target_key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!(
+        "jarvis-output-{}-{}.log",
+        target,
+        timestamp.format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// `path` itself if nothing exists there, otherwise the first free
+/// `<stem>-<n>.<ext>` next to it (for saves within the same second)
+fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{}-{}{}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn fuzzy_matches(candidate: &str, query: &str) -> bool {
+    fuzzy_score(candidate, query).is_some()
+}
+
+/// Base score for a query found as an exact substring, so precise queries
+/// always outrank scattered subsequence matches
+const SUBSTRING_BOOST: i64 = 1_000;
+
+/// Score `query` against `candidate` (case-insensitive, whitespace in the
+/// query ignored). Returns `None` unless every query character appears in order.
+///
+/// Exact substrings score highest, preferring word starts and earlier
+/// positions. Scattered matches earn points per character, extra for
+/// consecutive runs and word starts, and lose points for gaps.
+pub fn fuzzy_score(candidate: &str, query: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let query: Vec<char> = query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let is_word_start = |i: usize| i == 0 || !candidate[i - 1].is_alphanumeric();
+    let query_len = i64::try_from(query.len()).unwrap_or(i64::MAX);
+
+    if let Some(pos) = candidate
+        .windows(query.len())
+        .position(|window| window == query.as_slice())
+    {
+        let position_penalty = i64::try_from(pos.min(50)).unwrap_or(50);
+        let word_bonus = if is_word_start(pos) { 50 } else { 0 };
+        return Some(SUBSTRING_BOOST + query_len * 10 + word_bonus - position_penalty);
+    }
+
+    let mut score = 0;
+    let mut matched = 0;
+    let mut previous: Option<usize> = None;
+    for (i, &c) in candidate.iter().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if c != query[matched] {
+            continue;
+        }
+        score += 10;
+        match previous {
+            Some(p) if p + 1 == i => score += 15,
+            Some(p) => score -= i64::try_from((i - p - 1).min(10)).unwrap_or(10),
+            None => {}
+        }
+        if is_word_start(i) {
+            score += 20;
+        }
+        previous = Some(i);
+        matched += 1;
+    }
+
+    (matched == query.len()).then_some(score)
+}
+
+#[derive(Debug, Clone)]
+pub enum TreeItem {
+    Category(String),
+    Function(ScriptFunction),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::discovery::format_display_name;
+    use crate::script::ScriptType;
+    use chrono::TimeZone;
+
+    fn test_theme() -> Theme {
+        *Theme::default_theme()
+    }
+
+    fn create_test_functions() -> Vec<ScriptFunction> {
+        vec![
+            ScriptFunction {
+                name: "func1".to_string(),
+                display_name: "Function 1".to_string(),
+                category: "System".to_string(),
+                description: "Test function 1".to_string(),
+                emoji: None,
+                ignored: false,
+                sudo: false,
+                aliases: Vec::new(),
+                file_category: None,
+                usage: None,
+                line: None,
+                script_type: ScriptType::Bash,
+            },
+            ScriptFunction {
+                name: "func2".to_string(),
+                display_name: "Function 2".to_string(),
+                category: "System".to_string(),
+                description: "Test function 2".to_string(),
+                emoji: None,
+                ignored: false,
+                sudo: false,
+                aliases: Vec::new(),
+                file_category: None,
+                usage: None,
+                line: None,
+                script_type: ScriptType::Bash,
+            },
+            ScriptFunction {
+                name: "func3".to_string(),
+                display_name: "Function 3".to_string(),
+                category: "Utilities".to_string(),
+                description: "Test function 3".to_string(),
+                emoji: None,
+                ignored: false,
+                sudo: false,
+                aliases: Vec::new(),
+                file_category: None,
+                usage: None,
+                line: None,
+                script_type: ScriptType::Bash,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_app_new() {
+        let functions = create_test_functions();
+        let app = App::new(functions.clone(), "Test".to_string(), test_theme());
+
+        assert_eq!(app.functions.len(), 3);
+        assert_eq!(app.selected_index, 0);
+        assert!(!app.should_quit);
+        assert!(!app.search_mode);
+        assert_eq!(app.focus, FocusPane::ScriptList);
+        assert_eq!(app.project_title, "Test");
+        assert!(app.pty_handle.is_none());
+        assert!(app.active_function.is_none());
+        assert!(!app.mouse_selecting);
+    }
+
+    #[test]
+    fn test_app_categories() {
+        let functions = create_test_functions();
+        let app = App::new(functions, "Test".to_string(), test_theme());
+
+        let categories = app.categories();
+        assert_eq!(categories.len(), 2);
+        assert!(categories.contains(&"System".to_string()));
+        assert!(categories.contains(&"Utilities".to_string()));
+    }
+
+    #[test]
+    fn test_app_toggle_category() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        assert!(!app.is_category_expanded("System"));
+
+        app.toggle_category("System");
+        assert!(app.is_category_expanded("System"));
+
+        app.toggle_category("System");
+        assert!(!app.is_category_expanded("System"));
+    }
+
+    #[test]
+    fn test_app_expand_collapse_category() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        app.expand_category("System");
+        assert!(app.is_category_expanded("System"));
+
+        // Expanding again should not duplicate
+        app.expand_category("System");
+        assert_eq!(app.expanded_categories.len(), 1);
+
+        app.collapse_category("System");
+        assert!(!app.is_category_expanded("System"));
+
+        // Collapsing again should be safe
+        app.collapse_category("System");
+        assert!(!app.is_category_expanded("System"));
+    }
+
+    #[test]
+    fn test_expand_all_expands_every_category() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        // Select "Utilities"
+        app.selected_index = 1;
+
+        app.expand_all();
+
+        assert_eq!(app.expanded_categories, app.categories());
+        // System, func1, func2, Utilities, func3
+        assert_eq!(app.tree_items().len(), 5);
+        assert!(matches!(
+            app.selected_item(),
+            Some(TreeItem::Category(ref c)) if c == "Utilities"
+        ));
+    }
+
+    #[test]
+    fn test_collapse_all_keeps_selection_valid() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.expand_all();
+        // Select func3, the last item
+        app.selected_index = 4;
+
+        app.collapse_all();
+
+        assert!(app.expanded_categories.is_empty());
+        assert_eq!(app.tree_items().len(), 2);
+        assert_eq!(app.selected_index, 1);
+        assert!(matches!(
+            app.selected_item(),
+            Some(TreeItem::Category(ref c)) if c == "Utilities"
+        ));
+    }
+
+    #[test]
+    fn test_collapse_all_from_frequently_used() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        app.frequent_functions = vec![functions[2].clone()];
+        app.expand_all();
+        assert!(app.is_category_expanded(FREQUENTLY_USED_CATEGORY));
+        // Frequently Used, func3 (frequent), ...
+        app.selected_index = 1;
+
+        app.collapse_all();
+
+        assert_eq!(app.selected_index, 0);
+    }
+
+    fn mixed_type_functions() -> Vec<ScriptFunction> {
+        let mut functions = create_test_functions();
+        functions[1].script_type = ScriptType::Makefile;
+        functions[2].script_type = ScriptType::Makefile;
+        functions
+    }
+
+    #[test]
+    fn test_cycle_type_filter() {
+        let mut app = App::new(mixed_type_functions(), "Test".to_string(), test_theme());
+        assert_eq!(
+            app.script_types(),
+            vec![ScriptType::Bash, ScriptType::Makefile]
+        );
+
+        app.cycle_type_filter();
+        assert_eq!(app.type_filter, Some(ScriptType::Bash));
+        app.cycle_type_filter();
+        assert_eq!(app.type_filter, Some(ScriptType::Makefile));
+        app.cycle_type_filter();
+        assert_eq!(app.type_filter, None);
+    }
+
+    #[test]
+    fn test_type_filter_hides_other_types() {
+        let mut app = App::new(mixed_type_functions(), "Test".to_string(), test_theme());
+        app.expand_all();
+
+        app.type_filter = Some(ScriptType::Bash);
+        let items = app.tree_items();
+        // Utilities has no Bash functions and is hidden
+        assert_eq!(items.len(), 2);
+        assert!(matches!(&items[0], TreeItem::Category(c) if c == "System"));
+        assert!(matches!(&items[1], TreeItem::Function(f) if f.name == "func1"));
+
+        app.type_filter = Some(ScriptType::Makefile);
+        let names: Vec<String> = app
+            .tree_items()
+            .into_iter()
+            .filter_map(|item| match item {
+                TreeItem::Function(f) => Some(f.name),
+                TreeItem::Category(_) => None,
+            })
+            .collect();
+        assert_eq!(names, vec!["func2", "func3"]);
+    }
+
+    #[test]
+    fn test_type_filter_combines_with_search() {
+        let mut app = App::new(mixed_type_functions(), "Test".to_string(), test_theme());
+        app.type_filter = Some(ScriptType::Makefile);
+        app.enter_search_mode();
+        app.search_query = "function".to_string();
+
+        let names: Vec<String> = app
+            .tree_items()
+            .into_iter()
+            .filter_map(|item| match item {
+                TreeItem::Function(f) => Some(f.name),
+                TreeItem::Category(_) => None,
+            })
+            .collect();
+        assert_eq!(names, vec!["func2", "func3"]);
+    }
+
+    #[test]
+    fn test_cycle_type_filter_clamps_selection() {
+        let mut app = App::new(mixed_type_functions(), "Test".to_string(), test_theme());
+        app.expand_all();
+        // func3, the last item
+        app.selected_index = 4;
+
+        app.cycle_type_filter();
+
+        assert_eq!(app.tree_items().len(), 2);
+        assert_eq!(app.selected_index, 1);
+        assert!(app.status_message.is_some());
+    }
+
+    /// Functions of one category (in unsorted order) with recorded usage:
+    /// bravo runs most, delta ran last, charlie never ran
+    fn sortable_app() -> App {
+        let make = |name: &str, script_type: ScriptType| ScriptFunction {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: "Tasks".to_string(),
+            description: String::new(),
+            emoji: None,
+            ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
+            script_type,
+        };
+        let functions = vec![
+            make("delta", ScriptType::Bash),
+            make("charlie", ScriptType::Makefile),
+            make("bravo", ScriptType::Bash),
+            make("alpha", ScriptType::Makefile),
+        ];
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.expand_all();
+
+        let usage = |name: &str, script_type: ScriptType, count: u64, day: u32| UsageEntry {
+            function_name: name.to_string(),
+            script_type,
+            category: "Tasks".to_string(),
+            count,
+            last_used: chrono::Utc
+                .with_ymd_and_hms(2024, 5, day, 12, 0, 0)
+                .unwrap(),
+            last_exit_code: Some(0),
+            last_status: None,
+        };
+        app.set_usage_stats(HashMap::from([
+            (
+                "alpha".to_string(),
+                usage("alpha", ScriptType::Makefile, 1, 10),
+            ),
+            ("bravo".to_string(), usage("bravo", ScriptType::Bash, 5, 2)),
+            ("delta".to_string(), usage("delta", ScriptType::Bash, 2, 20)),
+            // Same name, other type: not charlie's usage
+            (
+                "charlie".to_string(),
+                usage("charlie", ScriptType::Bash, 9, 30),
+            ),
+        ]));
+        app
+    }
+
+    fn sorted_names(app: &App) -> Vec<String> {
+        app.tree_items()
+            .into_iter()
+            .filter_map(|item| match item {
+                TreeItem::Function(func) => Some(func.name),
+                TreeItem::Category(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_mode_alphabetical() {
+        let app = sortable_app();
+        assert_eq!(app.sort_mode, SortMode::Alphabetical);
+        assert_eq!(
+            sorted_names(&app),
+            vec!["alpha", "bravo", "charlie", "delta"]
+        );
+    }
+
+    #[test]
+    fn test_sort_mode_most_used() {
+        let mut app = sortable_app();
+        app.sort_mode = SortMode::MostUsed;
+        assert_eq!(
+            sorted_names(&app),
+            vec!["bravo", "delta", "alpha", "charlie"]
+        );
+    }
+
+    #[test]
+    fn test_sort_mode_by_type() {
+        let mut app = sortable_app();
+        app.sort_mode = SortMode::ByType;
+        // Bash before Makefile, alphabetical within each type
+        assert_eq!(
+            sorted_names(&app),
+            vec!["bravo", "delta", "alpha", "charlie"]
+        );
+    }
+
+    #[test]
+    fn test_sort_mode_recently_used() {
+        let mut app = sortable_app();
+        app.sort_mode = SortMode::RecentlyUsed;
+        assert_eq!(
+            sorted_names(&app),
+            vec!["delta", "alpha", "bravo", "charlie"]
+        );
+    }
+
+    #[test]
+    fn test_cycle_sort_mode() {
+        let mut app = sortable_app();
+        for expected in [
+            SortMode::MostUsed,
+            SortMode::ByType,
+            SortMode::RecentlyUsed,
+            SortMode::Alphabetical,
+        ] {
+            app.cycle_sort_mode();
+            assert_eq!(app.sort_mode, expected);
+        }
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_search_ranking_overrides_sort_mode() {
+        let mut app = sortable_app();
+        app.sort_mode = SortMode::MostUsed;
+        app.search_mode = true;
+        app.search_query = "alpha".to_string();
+        assert_eq!(
+            sorted_names(&app).first().map(String::as_str),
+            Some("alpha")
+        );
+    }
+
+    #[test]
+    fn test_app_navigation_next_previous() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        assert_eq!(app.selected_index, 0);
+
+        app.next();
+        assert_eq!(app.selected_index, 1);
+
+        app.next();
+        assert_eq!(app.selected_index, 0); // Wraps around
+
+        app.previous();
+        assert_eq!(app.selected_index, 1); // Goes to last
+
+        app.previous();
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_app_toggle_focus() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        assert_eq!(app.focus, FocusPane::ScriptList);
+
+        // No terminal output, so toggle stays on ScriptList
+        app.toggle_focus();
+        assert_eq!(app.focus, FocusPane::ScriptList);
+    }
+
+    #[test]
+    fn test_app_toggle_focus_with_output() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        // Expand a category and select a function
+        app.expand_category("System");
+        app.selected_index = 1; // First function under "System"
+
+        // Add a command history entry for the selected function
+        let func = app.selected_function().unwrap();
+        let state = crate::ui::pty_runner::ExecutionState {
+            status: ExecutionStatus::Succeeded,
+            parser: std::sync::Arc::new(std::sync::Mutex::new(vt100::Parser::new(24, 80, 100))),
+            exit_code: Some(0),
+            started_at: Instant::now(),
+            finished_at: Some(Instant::now()),
+            display_name: func.display_name.clone(),
+            category: func.category.clone(),
+            auto_follow: true,
+        };
+        app.command_history.insert(&func, state);
+
+        assert_eq!(app.focus, FocusPane::ScriptList);
+
+        app.toggle_focus();
+        assert_eq!(app.focus, FocusPane::Output);
+
+        app.toggle_focus();
+        assert_eq!(app.focus, FocusPane::ScriptList);
+    }
+
+    #[test]
+    fn test_app_search_mode() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        assert!(!app.search_mode);
+        assert_eq!(app.search_query, "");
+
+        app.enter_search_mode();
+        assert!(app.search_mode);
+        assert_eq!(app.search_query, "");
+
+        app.search_push_char('t');
+        app.search_push_char('e');
+        app.search_push_char('s');
+        app.search_push_char('t');
+        assert_eq!(app.search_query, "test");
+
+        app.search_pop_char();
+        assert_eq!(app.search_query, "tes");
+
+        app.exit_search_mode();
+        assert!(!app.search_mode);
+        assert_eq!(app.search_query, "");
+    }
+
+    #[test]
+    fn test_app_output_scroll() {
+        let mut app = app_with_output("1\r\n2\r\n3\r\n4\r\n5\r\n6");
+
+        assert_eq!(app.output_scroll, 0);
+
+        // Manually set scroll to test scroll_down behavior
+        app.output_scroll = 3;
+
+        app.scroll_output_down();
+        assert_eq!(app.output_scroll, 2);
+
+        app.scroll_output_down();
+        assert_eq!(app.output_scroll, 1);
+
+        app.scroll_output_down();
+        assert_eq!(app.output_scroll, 0);
+
+        // Should not go below 0
+        app.scroll_output_down();
+        assert_eq!(app.output_scroll, 0);
+
+        app.reset_output_scroll();
+        assert_eq!(app.output_scroll, 0);
+    }
+
+    #[test]
+    fn test_app_toggle_help_is_modal() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        assert!(!app.is_modal_open());
+
+        app.toggle_help();
+        assert!(app.show_help);
+        assert!(app.is_modal_open());
+
+        app.toggle_help();
+        assert!(!app.is_modal_open());
+    }
+
+    #[test]
+    fn test_app_toggle_info() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        assert!(!app.show_info);
+
+        app.toggle_info();
+        assert!(app.show_info);
+
+        app.toggle_info();
+        assert!(!app.show_info);
+    }
+
+    fn named_function(name: &str, category: &str) -> ScriptFunction {
+        ScriptFunction {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            category: category.to_string(),
+            description: String::new(),
+            emoji: None,
+            ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
+            script_type: ScriptType::Bash,
+        }
+    }
+
+    #[test]
+    fn test_select_first_and_last() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.expand_category("System");
+        app.expand_category("Utilities");
+        // [System], func1, func2, [Utilities], func3
+
+        app.select_last();
+        assert_eq!(app.selected_index, 4);
+        app.select_first();
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_jump_to_category_letter() {
+        let functions = ["Deploy", "Build", "database", "⭐ Favorites", "Docs"]
+            .iter()
+            .map(|category| named_function("run", category))
+            .collect();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.expand_category("Build");
+        let labels: Vec<String> = app
+            .tree_items()
+            .iter()
+            .map(|item| match item {
+                TreeItem::Category(category) => category.clone(),
+                TreeItem::Function(f) => f.name.clone(),
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["Build", "run", "Deploy", "Docs", "database", "⭐ Favorites"]
+        );
+
+        // Next match after the selection, ignoring case, wrapping around
+        assert!(app.jump_to_category_letter('d'));
+        assert_eq!(app.selected_index, 2);
+        assert!(app.jump_to_category_letter('D'));
+        assert_eq!(app.selected_index, 3);
+        assert!(app.jump_to_category_letter('d'));
+        assert_eq!(app.selected_index, 4);
+        assert!(app.jump_to_category_letter('d'));
+        assert_eq!(app.selected_index, 2);
+
+        // Functions are skipped and leading emoji ignored
+        assert!(app.jump_to_category_letter('b'));
+        assert_eq!(app.selected_index, 0);
+        assert!(app.jump_to_category_letter('f'));
+        assert_eq!(app.selected_index, 5);
+
+        assert!(!app.jump_to_category_letter('z'));
+        assert_eq!(app.selected_index, 5);
+        assert_eq!(app.status_message(), Some("No category starting with 'z'"));
+    }
+
+    #[test]
+    fn test_letter_jump_reaches_categories_with_bound_initials() {
+        use crate::ui::keymap::{self, Action, KeyContext};
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let functions = ["Build", "Cargo", "Scripts"]
+            .iter()
+            .map(|category| named_function("run", category))
+            .collect();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        // `c` and `s` are bound, so typing them alone never jumps
+        for letter in ['c', 's'] {
+            let key = KeyEvent::new(KeyCode::Char(letter), KeyModifiers::NONE);
+            assert!(keymap::action_for(KeyContext::Normal, &key).is_some());
+        }
+        let quote = KeyEvent::new(KeyCode::Char('\''), KeyModifiers::NONE);
+        assert_eq!(
+            keymap::action_for(KeyContext::Normal, &quote),
+            Some(Action::JumpToLetter)
+        );
+
+        app.start_letter_jump();
+        assert!(app.pending_letter_jump);
+        assert!(app.finish_letter_jump(Some('c')));
+        assert!(!app.pending_letter_jump);
+        assert_eq!(app.selected_index, 1);
+
+        app.start_letter_jump();
+        assert!(app.finish_letter_jump(Some('S')));
+        assert_eq!(app.selected_index, 2);
+
+        // Any other key cancels without moving
+        app.start_letter_jump();
+        assert!(!app.finish_letter_jump(None));
+        assert!(!app.finish_letter_jump(Some('/')));
+        assert_eq!(app.selected_index, 2);
+        assert_eq!(app.status_message(), None);
+    }
+
+    #[test]
+    fn test_page_down_and_up() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.expand_category("System");
+        app.expand_category("Utilities");
+
+        app.page_down(2);
+        assert_eq!(app.selected_index, 2);
+        app.page_down(3);
+        assert_eq!(app.selected_index, 4); // Clamped to the last item
+        app.page_down(2);
+        assert_eq!(app.selected_index, 0); // Wraps like next()
+
+        app.page_up(2);
+        assert_eq!(app.selected_index, 4); // Wraps like previous()
+        app.page_up(3);
+        assert_eq!(app.selected_index, 1);
+        app.page_up(3);
+        assert_eq!(app.selected_index, 0); // Clamped to the first item
+    }
+
+    #[test]
+    fn test_page_size_uses_list_height() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        assert_eq!(app.script_page_size(), 1);
+        app.script_list_height = 20;
+        assert_eq!(app.script_page_size(), 20);
+    }
+
+    #[test]
+    fn test_copy_full_output_without_output() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.expand_category("System");
+        app.selected_index = 1;
+
+        assert_eq!(app.copy_full_output(), 0);
+        assert_eq!(app.status_message(), Some("Nothing to copy"));
+    }
+
+    #[test]
+    fn test_last_exit_code_is_shared_with_frequent_copies() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        app.set_frequent_functions(vec![functions[0].clone()]);
+        app.record_last_exit_code(&functions[0], 1);
+
+        // The "Frequently Used" copy resolves to the same key
+        let frequent_copy = ScriptFunction {
+            category: FREQUENTLY_USED_CATEGORY.to_string(),
+            ..functions[0].clone()
+        };
+        assert_eq!(app.last_exit_code(&frequent_copy), Some(1));
+        assert_eq!(app.last_exit_code(&functions[1]), None);
+    }
+
+    #[test]
+    fn test_ui_state_round_trip() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.expand_category("Utilities");
+        app.selected_index = 2; // func3 under the expanded "Utilities"
+
+        let state = app.ui_state();
+        assert_eq!(state.expanded_categories, vec!["Utilities"]);
+
+        let mut restored = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        restored.restore_ui_state(&state);
+        assert!(restored.is_category_expanded("Utilities"));
+        assert_eq!(restored.selected_index, 2);
+        assert!(matches!(
+            restored.selected_item(),
+            Some(TreeItem::Function(f)) if f.name == "func3"
+        ));
+    }
+
+    #[test]
+    fn test_restore_ui_state_with_stale_entries() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.restore_ui_state(&UiState {
+            expanded_categories: vec!["Gone".to_string(), "System".to_string()],
+            selected: Some(SelectedItem::Function("Bash:removed".to_string())),
+            ..UiState::default()
+        });
+
+        assert_eq!(app.expanded_categories, vec!["System"]);
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_fuzzy_score_subsequence() {
+        assert!(fuzzy_score("deploy-production", "dprd").is_some());
+        assert!(fuzzy_score("deploy-production", "DPRD").is_some());
+        assert!(fuzzy_score("deploy-production", "xyz").is_none());
+        // Order matters
+        assert!(fuzzy_score("deploy-production", "drpd").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_substrings_and_word_starts() {
+        let substring = fuzzy_score("deploy-production", "prod").unwrap();
+        let scattered = fuzzy_score("print-out-diff", "prod").unwrap();
+        assert!(substring > scattered);
+
+        let word_start = fuzzy_score("run-tests", "test").unwrap();
+        let mid_word = fuzzy_score("latest", "test").unwrap();
+        assert!(word_start > mid_word);
+
+        let consecutive = fuzzy_score("deprd", "dprd").unwrap();
+        let gappy = fuzzy_score("dxxxpxxxrxxxd", "dprd").unwrap();
+        assert!(consecutive > gappy);
+    }
+
+    #[test]
+    fn test_matches_search_fuzzy() {
+        let functions = vec![
+            named_function("deploy-production", "Deploy"),
+            named_function("build", "Build"),
+        ];
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        app.enter_search_mode();
+        for c in "dprd".chars() {
+            app.search_push_char(c);
+        }
+
+        assert!(app.matches_search(&functions[0]));
+        assert!(!app.matches_search(&functions[1]));
+    }
+
+    #[test]
+    fn test_search_matches_category_display_names() {
+        let mut auth = named_function("build", "nx:monopoly:service.auth");
+        auth.description = "nx run service.auth:build".to_string();
+        let mut billing = named_function("test", "nx:monopoly:billing");
+        billing.description = "nx run billing:test".to_string();
+        let functions = vec![auth, billing];
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.set_category_display_names(HashMap::from([
+            (
+                "nx:monopoly:service.auth".to_string(),
+                "Service Auth".to_string(),
+            ),
+            ("nx:monopoly:billing".to_string(), "Payments".to_string()),
+        ]));
+        app.enter_search_mode();
+
+        let search = |app: &mut App, query: &str| -> Vec<String> {
+            app.search_query.clear();
+            for c in query.chars() {
+                app.search_push_char(c);
+            }
+            app.tree_items()
+                .into_iter()
+                .filter_map(|item| match item {
+                    TreeItem::Function(func) => Some(func.category),
+                    TreeItem::Category(_) => None,
+                })
+                .collect()
+        };
+
+        assert_eq!(search(&mut app, "auth"), vec!["nx:monopoly:service.auth"]);
+        assert_eq!(
+            search(&mut app, "Service Auth"),
+            vec!["nx:monopoly:service.auth"]
+        );
+        // Only the display name says "payments"
+        assert_eq!(search(&mut app, "payments"), vec!["nx:monopoly:billing"]);
+        // The command line in the description
+        assert_eq!(
+            search(&mut app, "nx run billing"),
+            vec!["nx:monopoly:billing"]
+        );
+    }
+
+    #[test]
+    fn test_search_ranks_best_matches_first() {
+        let functions = vec![
+            named_function("tail-errors-stats", "Logs"),
+            named_function("the-best", "Dev"),
+            named_function("test", "Dev"),
+            named_function("test-all", "Testing"),
+        ];
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.enter_search_mode();
+        for c in "test".chars() {
+            app.search_push_char(c);
+        }
+
+        let names: Vec<String> = app
+            .tree_items()
+            .into_iter()
+            .map(|item| match item {
+                TreeItem::Category(c) => format!("[{c}]"),
+                TreeItem::Function(f) => f.name,
+            })
+            .collect();
+        // Exact "test" ranks above the scattered "the-best"; categories follow their best match
+        assert_eq!(
+            names,
+            vec![
+                "[Dev]",
+                "test",
+                "the-best",
+                "[Testing]",
+                "test-all",
+                "[Logs]",
+                "tail-errors-stats"
+            ]
+        );
+
+        // The top match is selected automatically
+        assert_eq!(
+            app.selected_function().map(|f| f.name),
+            Some("test".to_string())
+        );
+    }
+
+    #[test]
+    fn test_app_tree_items_collapsed() {
+        let functions = create_test_functions();
+        let app = App::new(functions, "Test".to_string(), test_theme());
+
+        let items = app.tree_items();
+        // Should only show categories when collapsed
+        assert_eq!(items.len(), 2); // System and Utilities
+
+        match &items[0] {
+            TreeItem::Category(name) => assert!(name == "System" || name == "Utilities"),
+            _ => panic!("Expected category"),
+        }
+    }
+
+    #[test]
+    fn test_app_tree_items_expanded() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        app.expand_category("System");
+
+        let items = app.tree_items();
+        // Should show: System category + 2 functions + Utilities category
+        assert_eq!(items.len(), 4);
+    }
+
+    #[test]
+    fn test_app_selected_item() {
+        let functions = create_test_functions();
+        let app = App::new(functions, "Test".to_string(), test_theme());
+
+        let item = app.selected_item();
+        assert!(item.is_some());
+
+        match item.unwrap() {
+            TreeItem::Category(_) => {} // Expected
+            _ => panic!("Expected category at index 0"),
+        }
+    }
+
+    #[test]
+    fn test_app_handle_left_right() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        // Initially not expanded
+        assert!(!app.is_category_expanded("System"));
+
+        // Simulate selecting first category (index 0)
+        app.selected_index = 0;
+
+        // Right arrow should expand
+        app.handle_right();
+        assert!(app.is_category_expanded("System"));
+
+        // Left arrow should collapse
+        app.handle_left();
+        assert!(!app.is_category_expanded("System"));
+    }
+
+    #[test]
+    fn test_app_empty_functions() {
+        let app = App::new(vec![], "Test".to_string(), test_theme());
+
+        assert_eq!(app.functions.len(), 0);
+        assert_eq!(app.categories().len(), 0);
+        assert_eq!(app.tree_items().len(), 0);
+    }
+
+    #[test]
+    fn test_app_search_filtering() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        app.enter_search_mode();
+        app.search_push_char('f');
+        app.search_push_char('u');
+        app.search_push_char('n');
+        app.search_push_char('c');
+        app.search_push_char('3');
+
+        // Search should filter to only show items matching "func3"
+        let items = app.tree_items();
+
+        // Should show Utilities category + func3
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn test_app_recent_functions_below_frequent() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+
+        app.set_frequent_functions(vec![functions[0].clone()]);
+        app.set_recent_functions(vec![functions[2].clone(), functions[0].clone()]);
+        assert_eq!(
+            &app.categories()[..2],
+            [FREQUENTLY_USED_CATEGORY, RECENTLY_USED_CATEGORY]
+        );
+
+        app.expand_category(RECENTLY_USED_CATEGORY);
+        let items = app.tree_items();
+        assert!(matches!(&items[1], TreeItem::Category(c) if c == RECENTLY_USED_CATEGORY));
+        match &items[2] {
+            TreeItem::Function(func) => {
+                assert_eq!(func.name, functions[2].name);
+                assert_eq!(func.category, RECENTLY_USED_CATEGORY);
+                assert_eq!(app.original_category(func), functions[2].category);
+            }
+            TreeItem::Category(_) => panic!("Expected function"),
+        }
+    }
+
+    #[test]
+    fn test_app_frequent_functions() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+
+        // No frequent functions initially
+        assert!(app.frequent_functions.is_empty());
+        assert_eq!(app.categories().len(), 2);
+
+        // Set frequent functions
+        app.set_frequent_functions(vec![functions[0].clone()]);
+
+        // Should now have 3 categories (Frequently Used + System + Utilities)
+        assert_eq!(app.categories().len(), 3);
+        assert_eq!(app.categories()[0], FREQUENTLY_USED_CATEGORY);
+    }
+
+    #[test]
+    fn test_app_tree_items_with_frequent() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+
+        // Set frequent functions
+        app.set_frequent_functions(vec![functions[0].clone()]);
+
+        // Expand the frequently used category
+        app.expand_category(FREQUENTLY_USED_CATEGORY);
+
+        let items = app.tree_items();
+
+        // Should show: Frequently Used category + 1 func + System category + Utilities category
+        assert_eq!(items.len(), 4);
+
+        // First item should be Frequently Used category
+        match &items[0] {
+            TreeItem::Category(name) => assert_eq!(name, FREQUENTLY_USED_CATEGORY),
+            _ => panic!("Expected Frequently Used category first"),
+        }
+
+        // Second item should be the frequent function
+        match &items[1] {
+            TreeItem::Function(func) => {
+                assert_eq!(func.name, "func1");
+                assert_eq!(func.category, FREQUENTLY_USED_CATEGORY);
+            }
+            _ => panic!("Expected function"),
+        }
+    }
+
+    #[test]
+    fn test_app_tree_items_with_pinned() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        app.set_frequent_functions(vec![functions[0].clone()]);
+        app.pins.toggle(&CommandHistory::key_for(&functions[2]));
+        app.pins.toggle(&CommandHistory::key_for(&functions[0]));
+        // Pins of commands that no longer exist are skipped
+        app.pins.toggle("Bash:gone");
+
+        assert_eq!(
+            app.categories(),
+            vec![
+                PINNED_CATEGORY,
+                FREQUENTLY_USED_CATEGORY,
+                "System",
+                "Utilities"
+            ]
+        );
+
+        app.expand_category(PINNED_CATEGORY);
+        let items = app.tree_items();
+        let pinned: Vec<&str> = items
+            .iter()
+            .skip(1)
+            .map_while(|item| match item {
+                TreeItem::Function(func) => {
+                    assert_eq!(func.category, PINNED_CATEGORY);
+                    Some(func.name.as_str())
+                }
+                TreeItem::Category(_) => None,
+            })
+            .collect();
+        // Pin order, not usage or alphabetical order
+        assert_eq!(pinned, vec!["func3", "func1"]);
+        assert!(matches!(&items[3], TreeItem::Category(c) if c == FREQUENTLY_USED_CATEGORY));
+
+        // Pinned commands stay in their own category and resolve to it
+        app.expand_category("Utilities");
+        assert!(app.tree_items().iter().any(|item| {
+            matches!(item, TreeItem::Function(f) if f.name == "func3" && f.category == "Utilities")
+        }));
+        app.selected_index = 1;
+        assert_eq!(app.selected_function().unwrap().category, "Utilities");
+    }
+
+    #[test]
+    fn test_toggle_pin_keeps_selection_and_saves() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        let store = || PinStore::with_storage_dir(temp_dir.path(), &temp_dir.path().join("pins"));
+        app.pin_store = Some(store());
+        app.expand_category(PINNED_CATEGORY);
+        app.expand_category("System");
+        app.selected_index = 1; // func1 under System
+
+        app.toggle_pin();
+        assert_eq!(app.pins.pinned, vec!["Bash:func1"]);
+        assert_eq!(store().load(), app.pins);
+        // The selection follows the row, which moved down below "Pinned"
+        match app.selected_item() {
+            Some(TreeItem::Function(f)) => {
+                assert_eq!(f.name, "func1");
+                assert_eq!(f.category, "System");
+            }
+            other => panic!("Expected func1 selected, got {other:?}"),
+        }
+
+        // Unpinning from the "Pinned" category
+        app.selected_index = 1;
+        app.toggle_pin();
+        assert!(app.pins.pinned.is_empty());
+        assert!(!app.categories().contains(&PINNED_CATEGORY.to_string()));
+        assert!(store().load().pinned.is_empty());
+    }
+
+    fn typed(name: &str, category: &str, script_type: ScriptType) -> ScriptFunction {
+        ScriptFunction {
+            name: name.to_string(),
+            display_name: format_display_name(name),
+            category: category.to_string(),
+            description: String::new(),
+            emoji: None,
+            ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
+            script_type,
+        }
+    }
+
+    #[test]
+    fn test_dominant_script_type_mixed_functions() {
+        let funcs = [
+            typed("build", "app", ScriptType::Makefile),
+            typed("dev", "app", ScriptType::PackageJson),
+            typed("test", "app", ScriptType::PackageJson),
+            typed("lint", "app", ScriptType::Makefile),
+            typed("start", "app", ScriptType::PackageJson),
+        ];
+        assert_eq!(dominant_script_type(&funcs), Some(ScriptType::PackageJson));
+
+        // Ties go to the type that appears first
+        assert_eq!(
+            dominant_script_type(&funcs[..2]),
+            Some(ScriptType::Makefile)
+        );
+        assert_eq!(
+            dominant_script_type(&funcs[1..4]),
+            Some(ScriptType::PackageJson)
+        );
+        assert_eq!(dominant_script_type(&[]), None);
+    }
+
+    #[test]
+    fn test_category_label_prefixes_type_icon() {
+        let mut app = App::new(
+            vec![
+                typed("dev", "web", ScriptType::PackageJson),
+                typed("build", "web", ScriptType::PackageJson),
+                typed("clean", "web", ScriptType::Makefile),
+                typed("check", "api", ScriptType::CargoToml),
+            ],
+            "Test".to_string(),
+            test_theme(),
+        );
+        app.set_category_display_names(HashMap::from([
+            ("web".to_string(), "Web".to_string()),
+            ("api".to_string(), "🦀 Api".to_string()),
+        ]));
+
+        assert_eq!(app.category_label("web"), "📦 Web");
+        // Names that already carry an emoji are left alone
+        assert_eq!(app.category_label("api"), "🦀 Api");
+
+        app.set_frequent_functions(vec![typed("dev", "web", ScriptType::PackageJson)]);
+        assert_eq!(
+            app.category_label(FREQUENTLY_USED_CATEGORY),
+            FREQUENTLY_USED_CATEGORY
+        );
+    }
+
+    #[test]
+    fn test_app_frequent_search_filtering() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+
+        // Set all functions as frequent
+        app.set_frequent_functions(functions.clone());
+
+        app.enter_search_mode();
+        app.search_push_char('f');
+        app.search_push_char('u');
+        app.search_push_char('n');
+        app.search_push_char('c');
+        app.search_push_char('1');
+
+        let items = app.tree_items();
+
+        // Should show: Frequently Used + func1 + System + func1
+        // (func1 appears in both Frequently Used and System)
+        assert_eq!(items.len(), 4);
+    }
+
+    #[test]
+    fn test_app_execution_status_idle() {
+        let functions = create_test_functions();
+        let app = App::new(functions, "Test".to_string(), test_theme());
+        assert_eq!(app.current_execution_status(), ExecutionStatus::Idle);
+    }
+
+    #[test]
+    fn test_app_mouse_selection() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        assert!(!app.mouse_selecting);
+
+        app.start_mouse_selection(0, 0);
+        assert!(app.mouse_selecting);
+        assert_eq!(app.mouse_sel_start, Some((0, 0)));
+        assert_eq!(app.mouse_sel_end, Some((0, 0)));
+
+        app.update_mouse_selection(2, 5);
+        assert_eq!(app.mouse_sel_end, Some((2, 5)));
+
+        app.clear_mouse_selection();
+        assert!(!app.mouse_selecting);
+        assert!(app.mouse_sel_start.is_none());
+        assert!(app.mouse_sel_end.is_none());
+    }
+
+    #[test]
+    fn test_script_index_at() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        // System (expanded, func1, func2) and Utilities
+        app.expanded_categories.push("System".to_string());
+        assert_eq!(app.tree_items().len(), 4);
+
+        assert_eq!(app.script_index_at(5, 5), None);
+
+        app.script_list_area = Some((1, 2, 30, 10));
+        assert_eq!(app.script_index_at(1, 2), Some(0));
+        assert_eq!(app.script_index_at(10, 4), Some(2));
+        assert_eq!(app.script_index_at(30, 5), Some(3));
+        // Below the last item, and outside the list
+        assert_eq!(app.script_index_at(10, 6), None);
+        assert_eq!(app.script_index_at(0, 2), None);
+        assert_eq!(app.script_index_at(31, 2), None);
+        assert_eq!(app.script_index_at(10, 1), None);
+        assert_eq!(app.script_index_at(10, 12), None);
+
+        app.script_scroll = 2;
+        assert_eq!(app.script_index_at(10, 2), Some(2));
+        assert_eq!(app.script_index_at(10, 4), None);
+    }
+
+    #[test]
+    fn test_click_script_list_selects_and_detects_double_click() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.script_list_area = Some((0, 0, 30, 10));
+        app.focus = FocusPane::Details;
+        let now = Instant::now();
+
+        assert!(!app.click_script_list(5, 1, now));
+        assert_eq!(app.selected_index, 1);
+        assert_eq!(app.focus, FocusPane::ScriptList);
+
+        // Second click on the same row soon after: double-click
+        assert!(app.click_script_list(5, 1, now + Duration::from_millis(100)));
+        // A third click starts a new sequence
+        assert!(!app.click_script_list(5, 1, now + Duration::from_millis(200)));
+
+        // Too slow, or on another row: single clicks
+        let later = now + Duration::from_secs(5);
+        assert!(!app.click_script_list(5, 1, later));
+        assert!(!app.click_script_list(5, 0, later + Duration::from_millis(100)));
+        assert_eq!(app.selected_index, 0);
+
+        // Clicks outside the list are ignored
+        assert!(!app.click_script_list(50, 0, later));
+        assert_eq!(app.selected_index, 0);
+    }
 
-        // If selected item is below the scroll window, scroll down
-        if self.selected_index >= self.script_scroll + visible_height {
-            self.script_scroll = self.selected_index.saturating_sub(visible_height - 1);
-        }
+    #[test]
+    fn test_needs_tick_only_while_running() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        assert!(!app.needs_tick());
+
+        app.command_history.insert(
+            &functions[0],
+            crate::ui::pty_runner::ExecutionState::finished(
+                "Function 1",
+                "System",
+                0,
+                Instant::now(),
+                "done",
+            ),
+        );
+        assert!(!app.needs_tick());
+
+        app.command_history.get_mut(&functions[0]).unwrap().status = ExecutionStatus::Running;
+        assert!(app.needs_tick());
+
+        app.command_history.get_mut(&functions[0]).unwrap().status = ExecutionStatus::Failed;
+        assert!(!app.needs_tick());
     }
 
-    pub fn reset_script_scroll(&mut self) {
-        self.script_scroll = 0;
+    #[test]
+    fn test_needs_tick_until_status_message_expires() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+
+        app.set_status_message("Saved");
+        assert!(app.needs_tick());
+
+        app.status_message = Some((
+            "Saved".to_string(),
+            Instant::now() - STATUS_MESSAGE_DURATION,
+        ));
+        assert!(!app.needs_tick());
     }
 
-    /// Get all categories including the "Frequently Used" category if applicable
-    pub fn categories(&self) -> Vec<String> {
-        let mut cats = Vec::new();
+    #[test]
+    fn test_app_toggle_wrap_output() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        assert!(!app.wrap_output);
 
-        // Add "Frequently Used" first if there are frequent functions
-        if !self.frequent_functions.is_empty() {
-            cats.push(FREQUENTLY_USED_CATEGORY.to_string());
-        }
+        app.start_mouse_selection(0, 0);
+        app.toggle_wrap_output();
+        assert!(app.wrap_output);
+        assert!(app.mouse_sel_start.is_none());
+        assert_eq!(app.status_message(), Some("Line wrap on"));
 
-        // Add regular categories
-        cats.extend(self.regular_categories());
+        // Without output to map against, positions pass through unchanged
+        app.start_mouse_selection(1, 3);
+        assert_eq!(app.mouse_sel_start, Some((1, 3)));
 
-        cats
+        app.toggle_wrap_output();
+        assert!(!app.wrap_output);
+        assert_eq!(app.status_message(), Some("Line wrap off"));
     }
 
-    /// Get regular categories (excluding "Frequently Used")
-    fn regular_categories(&self) -> Vec<String> {
-        let mut cats: Vec<String> = self
-            .functions
-            .iter()
-            .map(|f| f.category.clone())
-            .collect::<std::collections::HashSet<_>>()
-            .into_iter()
-            .collect();
-        cats.sort();
-        cats
-    }
-}
+    #[test]
+    fn test_app_toggle_fullscreen_output() {
+        let mut app = app_with_output("line 1\r\nline 2");
+        app.focus = FocusPane::Output;
+        assert!(!app.is_output_fullscreen());
 
-#[derive(Debug, Clone)]
-pub enum TreeItem {
-    Category(String),
-    Function(ScriptFunction),
-}
+        app.start_mouse_selection(0, 0);
+        app.toggle_fullscreen_output();
+        assert!(app.is_output_fullscreen());
+        assert!(app.mouse_sel_start.is_none());
+        assert_eq!(app.status_message(), Some("Fullscreen output on"));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::script::ScriptType;
+        // The list comes back with focus, fullscreen stays on for next time
+        app.focus = FocusPane::ScriptList;
+        assert!(!app.is_output_fullscreen());
+        assert!(app.fullscreen_output);
 
-    fn test_theme() -> Theme {
-        *Theme::default_theme()
+        app.focus = FocusPane::Output;
+        app.toggle_fullscreen_output();
+        assert!(!app.is_output_fullscreen());
+        assert_eq!(app.status_message(), Some("Fullscreen output off"));
     }
 
-    fn create_test_functions() -> Vec<ScriptFunction> {
-        vec![
-            ScriptFunction {
-                name: "func1".to_string(),
-                display_name: "Function 1".to_string(),
-                category: "System".to_string(),
-                description: "Test function 1".to_string(),
-                emoji: None,
-                ignored: false,
-                script_type: ScriptType::Bash,
-            },
-            ScriptFunction {
-                name: "func2".to_string(),
-                display_name: "Function 2".to_string(),
-                category: "System".to_string(),
-                description: "Test function 2".to_string(),
-                emoji: None,
-                ignored: false,
-                script_type: ScriptType::Bash,
-            },
-            ScriptFunction {
-                name: "func3".to_string(),
-                display_name: "Function 3".to_string(),
-                category: "Utilities".to_string(),
-                description: "Test function 3".to_string(),
-                emoji: None,
-                ignored: false,
-                script_type: ScriptType::Bash,
-            },
-        ]
+    #[test]
+    fn test_app_toggle_line_numbers_maps_selection_past_gutter() {
+        // 12 lines: a 3 column gutter
+        let output: Vec<String> = (1..=12).map(|n| format!("line {n}")).collect();
+        let mut app = app_with_output(&output.join("\r\n"));
+        app.focus = FocusPane::Output;
+
+        app.start_mouse_selection(0, 5);
+        assert_eq!(app.mouse_sel_start, Some((0, 5)));
+
+        app.toggle_line_numbers();
+        assert!(app.show_line_numbers);
+        assert!(app.mouse_sel_start.is_none());
+        assert_eq!(app.status_message(), Some("Line numbers on"));
+        app.start_mouse_selection(0, 5);
+        assert_eq!(app.mouse_sel_start, Some((0, 2)));
+        app.update_mouse_selection(1, 1);
+        assert_eq!(app.mouse_sel_end, Some((1, 0)));
+
+        app.toggle_line_numbers();
+        assert_eq!(app.status_message(), Some("Line numbers off"));
     }
 
     #[test]
-    fn test_app_new() {
+    fn test_app_focus_clears_mouse_selection() {
         let functions = create_test_functions();
-        let app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
 
-        assert_eq!(app.functions.len(), 3);
-        assert_eq!(app.selected_index, 0);
-        assert!(!app.should_quit);
-        assert!(!app.search_mode);
-        assert_eq!(app.focus, FocusPane::ScriptList);
-        assert_eq!(app.project_title, "Test");
-        assert!(app.pty_handle.is_none());
-        assert!(app.active_function.is_none());
+        app.mouse_selecting = true;
+        app.mouse_sel_start = Some((0, 0));
+        app.mouse_sel_end = Some((1, 5));
+
+        // Focus is ScriptList -> stays ScriptList (no terminal output)
+        // but let's test that clearing works when focus != Output
+        app.focus = FocusPane::Output;
+        app.toggle_focus(); // Output -> ScriptList
         assert!(!app.mouse_selecting);
+        assert!(app.mouse_sel_start.is_none());
     }
 
     #[test]
-    fn test_app_categories() {
-        let functions = create_test_functions();
-        let app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_app_sudo_requires_confirmation() {
+        let mut functions = create_test_functions();
+        functions[0].sudo = true;
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
 
-        let categories = app.categories();
-        assert_eq!(categories.len(), 2);
-        assert!(categories.contains(&"System".to_string()));
-        assert!(categories.contains(&"Utilities".to_string()));
+        assert!(app.requires_confirmation(&functions[0]));
+        assert!(!app.requires_confirmation(&functions[1]));
+
+        app.request_confirmation(functions[0].clone());
+        assert_eq!(
+            app.pending_confirmation
+                .as_ref()
+                .map(|p| (p.func.name.as_str(), p.reason)),
+            Some(("func1", ConfirmReason::Sudo))
+        );
+
+        app.cancel_confirmation();
+        assert!(app.pending_confirmation.is_none());
     }
 
     #[test]
-    fn test_app_toggle_category() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_is_destructive_builtin_rules() {
+        let mut func = create_test_functions().remove(0);
 
-        assert!(!app.is_category_expanded("System"));
+        func.script_type = ScriptType::Terraform;
+        for name in ["destroy", "apply", "apply --target=aws_instance.web"] {
+            func.name = name.to_string();
+            assert!(is_destructive(&func), "{name} should be destructive");
+        }
+        func.name = "plan".to_string();
+        assert!(!is_destructive(&func));
 
-        app.toggle_category("System");
-        assert!(app.is_category_expanded("System"));
+        func.script_type = ScriptType::Makefile;
+        for name in ["clean", "deploy"] {
+            func.name = name.to_string();
+            assert!(is_destructive(&func), "{name} should be destructive");
+        }
+        func.name = "build".to_string();
+        assert!(!is_destructive(&func));
 
-        app.toggle_category("System");
-        assert!(!app.is_category_expanded("System"));
+        // Only the listed tools have built-in rules
+        func.script_type = ScriptType::PackageJson;
+        func.name = "clean".to_string();
+        assert!(!is_destructive(&func));
     }
 
     #[test]
-    fn test_app_expand_collapse_category() {
+    fn test_app_danger_pattern_requires_confirmation() {
         let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        assert!(!app.requires_confirmation(&functions[1]));
+
+        app.danger_pattern = Some(Regex::new("^func2$").unwrap());
+        assert_eq!(
+            app.confirmation_reason(&functions[1]),
+            Some(ConfirmReason::Destructive)
+        );
+        assert!(!app.requires_confirmation(&functions[0]));
+    }
 
-        app.expand_category("System");
-        assert!(app.is_category_expanded("System"));
+    #[test]
+    fn test_app_category_descriptions() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        assert_eq!(app.get_category_description("cat1"), None);
 
-        // Expanding again should not duplicate
-        app.expand_category("System");
-        assert_eq!(app.expanded_categories.len(), 1);
+        let mut descriptions = HashMap::new();
+        descriptions.insert("cat1".to_string(), "Build helpers".to_string());
+        app.set_category_descriptions(descriptions);
 
-        app.collapse_category("System");
-        assert!(!app.is_category_expanded("System"));
+        assert_eq!(app.get_category_description("cat1"), Some("Build helpers"));
+        assert_eq!(app.get_category_description("cat2"), None);
+    }
 
-        // Collapsing again should be safe
-        app.collapse_category("System");
-        assert!(!app.is_category_expanded("System"));
+    #[test]
+    fn test_app_category_jump_filters_fuzzy() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.open_category_jump();
+        assert!(app.show_category_jump);
+        assert_eq!(app.category_jump_matches(), vec!["System", "Utilities"]);
+
+        app.category_jump_push_char('u');
+        app.category_jump_push_char('t');
+        app.category_jump_push_char('s');
+        assert_eq!(app.category_jump_matches(), vec!["Utilities"]);
+
+        app.category_jump_pop_char();
+        app.category_jump_pop_char();
+        app.category_jump_pop_char();
+        app.category_jump_push_char('y');
+        assert_eq!(app.category_jump_matches(), vec!["System"]);
     }
 
     #[test]
-    fn test_app_navigation_next_previous() {
-        let functions = create_test_functions();
+    fn test_palette_results_ranked_across_categories() {
+        let functions = vec![
+            named_function("redeploy", "Dev"),
+            named_function("build", "Dev"),
+            named_function("deploy-prod", "Ops"),
+            named_function("lint", "Checks"),
+        ];
         let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.open_palette();
+        assert!(app.palette_mode);
+        assert!(app.is_modal_open());
 
-        assert_eq!(app.selected_index, 0);
-
-        app.next();
-        assert_eq!(app.selected_index, 1);
-
-        app.next();
-        assert_eq!(app.selected_index, 0); // Wraps around
+        // No query: everything, in discovery order
+        let names: Vec<&str> = app
+            .palette_results()
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["redeploy", "build", "deploy-prod", "lint"]);
 
-        app.previous();
-        assert_eq!(app.selected_index, 1); // Goes to last
+        for c in "deploy".chars() {
+            app.palette_push_char(c);
+        }
+        let names: Vec<&str> = app
+            .palette_results()
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        // A word-start match outranks a mid-word one; non-matches are dropped
+        assert_eq!(names, vec!["deploy-prod", "redeploy"]);
 
-        app.previous();
-        assert_eq!(app.selected_index, 0);
+        // Categories stay collapsed: the palette does not touch the tree
+        assert!(app.expanded_categories.is_empty());
     }
 
     #[test]
-    fn test_app_toggle_focus() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
-
-        assert_eq!(app.focus, FocusPane::ScriptList);
+    fn test_palette_navigation_and_confirm() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.expand_category("Utilities");
+        let expanded = app.expanded_categories.clone();
+
+        app.open_palette();
+        app.palette_next();
+        app.palette_next();
+        assert_eq!(app.palette_index, 2);
+        app.palette_next();
+        assert_eq!(app.palette_index, 0);
+        app.palette_previous();
+        assert_eq!(app.palette_index, 2);
+
+        // func3 is visible in the expanded Utilities category: it gets selected
+        let func = app.confirm_palette().unwrap();
+        assert_eq!(func.name, "func3");
+        assert!(!app.palette_mode);
+        assert!(app.palette_query.is_empty());
+        assert!(matches!(
+            app.selected_item(),
+            Some(TreeItem::Function(ref f)) if f.name == "func3"
+        ));
+        assert_eq!(app.expanded_categories, expanded);
+
+        // func1 lives in the collapsed System category: run it, keep the tree as-is
+        app.open_palette();
+        app.palette_push_char('1');
+        assert_eq!(app.confirm_palette().unwrap().name, "func1");
+        assert_eq!(app.expanded_categories, expanded);
+
+        // No results: nothing to run
+        app.open_palette();
+        app.palette_push_char('z');
+        assert!(app.confirm_palette().is_none());
+    }
 
-        // No terminal output, so toggle stays on ScriptList
-        app.toggle_focus();
-        assert_eq!(app.focus, FocusPane::ScriptList);
+    #[test]
+    fn test_rerun_target_tracks_last_run() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        assert!(app.rerun_target().is_none());
+        assert_eq!(app.status_message(), Some("Nothing to re-run yet"));
+
+        // Runs from "Frequently Used" are remembered under the real category
+        let mut frequent = app.functions[2].clone();
+        frequent.category = FREQUENTLY_USED_CATEGORY.to_string();
+        app.record_last_run(&frequent, "Utilities");
+        app.expand_category("Utilities");
+
+        let func = app.rerun_target().unwrap();
+        assert_eq!(func.name, "func3");
+        assert_eq!(func.category, "Utilities");
+        assert!(matches!(
+            app.selected_item(),
+            Some(TreeItem::Function(ref f)) if f.name == "func3"
+        ));
+
+        app.record_last_run(&app.functions[0].clone(), "System");
+        assert_eq!(app.last_run.as_ref().unwrap().name, "func1");
     }
 
     #[test]
-    fn test_app_toggle_focus_with_output() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_rerun_target_cleared_when_command_disappears() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.record_last_run(&app.functions[0].clone(), "System");
+
+        // The project's commands changed and func1 is gone
+        app.functions.remove(0);
+        assert!(app.rerun_target().is_none());
+        assert!(app.last_run.is_none());
+    }
 
-        // Expand a category and select a function
+    /// An app with "func1" selected and `output` as its finished output
+    fn app_with_output(output: &str) -> App {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
         app.expand_category("System");
-        app.selected_index = 1; // First function under "System"
-
-        // Add a command history entry for the selected function
+        app.selected_index = 1;
         let func = app.selected_function().unwrap();
+        let parser = vt100::Parser::new(3, 40, 100);
         let state = crate::ui::pty_runner::ExecutionState {
             status: ExecutionStatus::Succeeded,
-            parser: std::sync::Arc::new(std::sync::Mutex::new(vt100::Parser::new(24, 80, 100))),
+            parser: std::sync::Arc::new(std::sync::Mutex::new(parser)),
             exit_code: Some(0),
             started_at: Instant::now(),
             finished_at: Some(Instant::now()),
             display_name: func.display_name.clone(),
             category: func.category.clone(),
+            auto_follow: true,
         };
+        state.parser.lock().unwrap().process(output.as_bytes());
         app.command_history.insert(&func, state);
+        app
+    }
 
-        assert_eq!(app.focus, FocusPane::ScriptList);
+    #[test]
+    fn test_output_search_jumps_between_matches() {
+        let mut app = app_with_output("error a\r\nok\r\nok\r\nok\r\nERROR b\r\nok");
 
-        app.toggle_focus();
-        assert_eq!(app.focus, FocusPane::Output);
+        app.start_output_search();
+        for c in "error".chars() {
+            app.output_search_push_char(c);
+        }
+        // At the bottom, the first match at or below the view is on line 4
+        app.confirm_output_search();
+        assert!(!app.output_search_input);
+        assert_eq!(app.output_search_matches, vec![(0, 0), (4, 0)]);
+        assert_eq!(app.current_output_match(), Some((4, 0)));
+        assert_eq!(app.output_scroll, 0);
+        assert_eq!(app.status_message(), Some("Match 2 of 2"));
 
-        app.toggle_focus();
-        assert_eq!(app.focus, FocusPane::ScriptList);
+        // n wraps to the first match, scrolled to the top of the view
+        app.output_search_next();
+        assert_eq!(app.current_output_match(), Some((0, 0)));
+        assert_eq!(app.output_scroll, 3);
+
+        app.output_search_previous();
+        assert_eq!(app.current_output_match(), Some((4, 0)));
     }
 
     #[test]
-    fn test_app_search_mode() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_clear_output() {
+        let mut app = app_with_output("error a\r\nok");
+        assert_eq!(app.current_execution_status(), ExecutionStatus::Succeeded);
 
-        assert!(!app.search_mode);
-        assert_eq!(app.search_query, "");
+        app.clear_output();
 
-        app.enter_search_mode();
-        assert!(app.search_mode);
-        assert_eq!(app.search_query, "");
+        assert_eq!(app.current_execution_status(), ExecutionStatus::Idle);
+        assert_eq!(app.status_message(), Some("Cleared output of Function 1"));
+        let parser = app.output_parser().unwrap();
+        assert!(parser.lock().unwrap().screen().contents().is_empty());
+        assert_eq!(app.copy_full_output(), 0);
+    }
 
-        app.search_push_char('t');
-        app.search_push_char('e');
-        app.search_push_char('s');
-        app.search_push_char('t');
-        assert_eq!(app.search_query, "test");
+    /// Eight lines on a three-row screen leave five rows of scrollback
+    const EIGHT_LINES: &str = "1\r\n2\r\n3\r\n4\r\n5\r\n6\r\n7\r\n8";
 
-        app.search_pop_char();
-        assert_eq!(app.search_query, "tes");
+    #[test]
+    fn test_output_scroll_clamps_to_max_scrollback() {
+        let mut app = app_with_output(EIGHT_LINES);
+        assert_eq!(app.max_output_scroll(), 5);
 
-        app.exit_search_mode();
-        assert!(!app.search_mode);
-        assert_eq!(app.search_query, "");
+        for _ in 0..10 {
+            app.scroll_output_up();
+        }
+        assert_eq!(app.output_scroll, 5);
+
+        app.scroll_output_down();
+        assert_eq!(app.output_scroll, 4);
+
+        app.scroll_output_to_top();
+        assert_eq!(app.output_scroll, 5);
+        app.scroll_output_to_bottom();
+        assert_eq!(app.output_scroll, 0);
+        app.scroll_output_down();
+        assert_eq!(app.output_scroll, 0);
     }
 
     #[test]
-    fn test_app_output_scroll() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
-
+    fn test_output_scroll_half_pages_stay_in_range() {
+        let mut app = app_with_output(EIGHT_LINES);
+
+        app.scroll_output_half_page_up(6);
+        assert_eq!(app.output_scroll, 3);
+        app.scroll_output_half_page_up(6);
+        assert_eq!(app.output_scroll, 5);
+        app.scroll_output_half_page_down(6);
+        assert_eq!(app.output_scroll, 2);
+        app.scroll_output_half_page_down(6);
         assert_eq!(app.output_scroll, 0);
 
-        // Manually set scroll to test scroll_down behavior
-        app.output_scroll = 3;
+        // A one-row pane still moves a line at a time
+        app.scroll_output_half_page_up(1);
+        assert_eq!(app.output_scroll, 1);
+    }
+
+    #[test]
+    fn test_output_scroll_clamps_stale_offset() {
+        let mut app = app_with_output(EIGHT_LINES);
+
+        // An offset from a longer output moves relative to the real maximum
+        app.output_scroll = 50;
+        app.scroll_output_down();
+        assert_eq!(app.output_scroll, 4);
+
+        app.output_scroll = 50;
+        app.scroll_output_up();
+        assert_eq!(app.output_scroll, 5);
+
+        // Without output there is nothing to scroll
+        let mut empty = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        empty.scroll_output_up();
+        assert_eq!(empty.output_scroll, 0);
+        assert_eq!(empty.max_output_scroll(), 0);
+    }
+
+    #[test]
+    fn test_current_execution_duration() {
+        let mut app = app_with_output(EIGHT_LINES);
+        let func = app.selected_function().unwrap();
+        let started_at = Instant::now().checked_sub(Duration::from_secs(65)).unwrap();
+
+        // Finished: the total run time
+        let state = app.command_history.get_mut(&func).unwrap();
+        state.started_at = started_at;
+        state.finished_at = Some(started_at + Duration::from_secs(5));
+        assert_eq!(
+            app.current_execution_duration(),
+            Some(Duration::from_secs(5))
+        );
+
+        // Still running: time since it started
+        let state = app.command_history.get_mut(&func).unwrap();
+        state.status = ExecutionStatus::Running;
+        state.finished_at = None;
+        assert!(app.current_execution_duration().unwrap() >= Duration::from_secs(65));
+
+        // Nothing for commands that have not run
+        app.select_index(0);
+        assert_eq!(app.current_execution_duration(), None);
+    }
+
+    #[test]
+    fn test_output_follow_tracks_scrolling() {
+        let mut app = app_with_output(EIGHT_LINES);
+        assert!(app.is_following_output());
+
+        app.scroll_output_up();
+        assert!(!app.is_following_output());
+        app.scroll_output_to_top();
+        assert!(!app.is_following_output());
+
+        // Back at the bottom, one line at a time or in one jump
+        app.scroll_output_half_page_down(6);
+        assert_eq!(app.output_scroll, 2);
+        assert!(!app.is_following_output());
+        app.scroll_output_down();
+        app.scroll_output_down();
+        assert!(app.is_following_output());
+
+        app.scroll_output_half_page_up(6);
+        app.scroll_output_to_bottom();
+        assert!(app.is_following_output());
+    }
+
+    #[test]
+    fn test_output_follow_on_new_content() {
+        let mut app = app_with_output(EIGHT_LINES);
+        let func = app.selected_function().unwrap();
+        let parser = Arc::clone(&app.command_history.get(&func).unwrap().parser);
+
+        // Following: new output keeps the view at the bottom
+        app.output_scroll = 2;
+        app.follow_output();
+        assert_eq!(app.output_scroll, 0);
 
-        app.scroll_output_down();
+        // Scrolled up: new output leaves the offset alone
+        app.scroll_output_up();
+        app.scroll_output_up();
+        parser.lock().unwrap().process(b"\r\n9\r\n10");
+        app.follow_output();
         assert_eq!(app.output_scroll, 2);
+        assert!(!app.is_following_output());
 
-        app.scroll_output_down();
-        assert_eq!(app.output_scroll, 1);
+        // The flag belongs to the output, not the app
+        app.select_index(0);
+        assert!(app.is_following_output());
+        app.select_index(1);
+        assert!(!app.is_following_output());
+    }
 
-        app.scroll_output_down();
+    #[test]
+    fn test_output_search_without_matches() {
+        let mut app = app_with_output("all good\r\n");
+        app.start_output_search();
+        app.output_search_push_char('x');
+        app.confirm_output_search();
+
+        assert!(!app.has_output_search());
+        assert_eq!(app.status_message(), Some("No matches for 'x'"));
+        // n/N are harmless without a search
+        app.output_search_next();
         assert_eq!(app.output_scroll, 0);
+    }
 
-        // Should not go below 0
-        app.scroll_output_down();
-        assert_eq!(app.output_scroll, 0);
+    #[test]
+    fn test_output_search_cleared_on_selection_change() {
+        let mut app = app_with_output("needle\r\n");
+        app.start_output_search();
+        app.output_search_push_char('n');
+        app.confirm_output_search();
+        assert!(app.has_output_search());
 
-        app.reset_output_scroll();
-        assert_eq!(app.output_scroll, 0);
+        app.next();
+        assert!(!app.has_output_search());
+        assert!(app.output_search_query.is_empty());
     }
 
     #[test]
-    fn test_app_toggle_info() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_output_log_file_name_is_sanitized() {
+        let timestamp = chrono::Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 7).unwrap();
+        assert_eq!(
+            output_log_file_name("Bash:deploy", &timestamp),
+            "jarvis-output-Bash_deploy-20240309-140507.log"
+        );
+        assert_eq!(
+            output_log_file_name("Task:../etc/pass wd", &timestamp),
+            "jarvis-output-Task____etc_pass_wd-20240309-140507.log"
+        );
+    }
 
-        assert!(!app.show_info);
+    #[test]
+    fn test_output_log_file_name_differs_by_target_and_time() {
+        let first = chrono::Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 7).unwrap();
+        let second = chrono::Utc.with_ymd_and_hms(2024, 3, 9, 14, 5, 8).unwrap();
+        assert_ne!(
+            output_log_file_name("Bash:build", &first),
+            output_log_file_name("Bash:build", &second)
+        );
+        assert_ne!(
+            output_log_file_name("Bash:build", &first),
+            output_log_file_name("Bash:test", &first)
+        );
+    }
 
-        app.toggle_info();
-        assert!(app.show_info);
+    #[test]
+    fn test_save_full_output_writes_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = app_with_output("line one\r\nline two\r\n");
+
+        let path = app.save_full_output(temp_dir.path()).unwrap();
+        assert!(path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("jarvis-output-Bash_func1-"));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "line one\nline two\n"
+        );
+        assert!(app
+            .status_message()
+            .unwrap()
+            .starts_with("Saved output to "));
+
+        // A second save in the same second gets its own file
+        let again = app.save_full_output(temp_dir.path()).unwrap();
+        assert_ne!(path, again);
+    }
 
-        app.toggle_info();
-        assert!(!app.show_info);
+    #[test]
+    fn test_save_full_output_reports_write_errors() {
+        let mut app = app_with_output("output\r\n");
+        let missing = std::path::Path::new("/nonexistent/jarvis/dir");
+
+        assert!(app.save_full_output(missing).is_none());
+        assert!(app
+            .status_message()
+            .unwrap()
+            .starts_with("Failed to save output: "));
     }
 
     #[test]
-    fn test_app_tree_items_collapsed() {
-        let functions = create_test_functions();
-        let app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_script_file_for_resolves_frequently_used() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.script_files = vec![ScriptFile {
+            path: PathBuf::from("/app/utilities.sh"),
+            name: "utilities".to_string(),
+            category: "Utilities".to_string(),
+            display_name: "Utilities".to_string(),
+            script_type: ScriptType::Bash,
+            available: true,
+        }];
+
+        let mut frequent = app.functions[2].clone();
+        frequent.category = FREQUENTLY_USED_CATEGORY.to_string();
+        assert_eq!(app.original_category(&frequent), "Utilities");
+        assert_eq!(
+            app.script_file_for(&frequent).map(|s| s.path.clone()),
+            Some(PathBuf::from("/app/utilities.sh"))
+        );
+        assert!(app.script_file_for(&app.functions[0]).is_none());
+    }
 
-        let items = app.tree_items();
-        // Should only show categories when collapsed
-        assert_eq!(items.len(), 2); // System and Utilities
+    #[test]
+    fn test_unavailable_target_is_refused_before_spawning() {
+        let mut app = App::new(mixed_type_functions(), "Test".to_string(), test_theme());
+        app.script_files = vec![
+            ScriptFile {
+                path: PathBuf::from("/app/system.sh"),
+                name: "system".to_string(),
+                category: "System".to_string(),
+                display_name: "System".to_string(),
+                script_type: ScriptType::Bash,
+                available: true,
+            },
+            ScriptFile {
+                path: PathBuf::from("/app/Makefile"),
+                name: "System".to_string(),
+                category: "System".to_string(),
+                display_name: "System".to_string(),
+                script_type: ScriptType::Makefile,
+                available: false,
+            },
+        ];
+        app.expand_all();
+        // System, func1 (bash), func2 (make)
+        app.selected_index = 2;
+        let func = app.selected_function().unwrap();
+        assert_eq!(func.script_type, ScriptType::Makefile);
+
+        assert!(!app.is_available(&func));
+        assert!(!app.check_available(&func));
+        assert_eq!(
+            app.status_message(),
+            Some("Cannot run Function 2: 'make' is not installed or not in PATH")
+        );
+        assert!(app.pty_handle.is_none());
+        assert!(app.active_function.is_none());
 
-        match &items[0] {
-            TreeItem::Category(name) => assert!(name == "System" || name == "Utilities"),
-            _ => panic!("Expected category"),
-        }
+        // Targets of installed tools run as usual
+        app.status_message = None;
+        let bash = app.functions[0].clone();
+        assert!(app.check_available(&bash));
+        assert!(app.status_message().is_none());
     }
 
     #[test]
-    fn test_app_tree_items_expanded() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_spinner_frame_follows_elapsed_time() {
+        assert_eq!(spinner_frame_at(Duration::ZERO), '⠋');
+        assert_eq!(spinner_frame_at(Duration::from_millis(99)), '⠋');
+        assert_eq!(spinner_frame_at(Duration::from_millis(100)), '⠙');
+        assert_eq!(spinner_frame_at(Duration::from_millis(250)), '⠹');
+        // Ten frames make a full turn
+        assert_eq!(
+            spinner_frame_at(Duration::from_millis(1_350)),
+            spinner_frame_at(Duration::from_millis(350))
+        );
+
+        // The app's frame is the one for the time since the animation started
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.animation_started = Instant::now()
+            .checked_sub(Duration::from_millis(450))
+            .unwrap();
+        app.tick_animation();
+        assert!(app.animation_tick >= 4);
+        assert_eq!(
+            app.spinner_frame(),
+            spinner_frame_at(SPINNER_FRAME_DURATION * u32::try_from(app.animation_tick).unwrap())
+        );
+    }
 
-        app.expand_category("System");
+    #[test]
+    fn test_reload_script_file_replaces_its_functions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("system.sh");
+        std::fs::write(&path, "func1() {\n  :\n}\n").unwrap();
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        let script_file = ScriptFile {
+            path: path.clone(),
+            name: "system".to_string(),
+            category: "System".to_string(),
+            display_name: "System".to_string(),
+            script_type: ScriptType::Bash,
+            available: true,
+        };
+        app.script_files = vec![script_file.clone()];
+        app.expand_all();
+        app.selected_index = 1;
+        assert_eq!(app.editor_target().unwrap().1.path, path);
+
+        std::fs::write(
+            &path,
+            "# @description Fresh\nfunc1() {\n  :\n}\n\nadded() {\n  :\n}\n",
+        )
+        .unwrap();
+        app.reload_script_file(&script_file);
+
+        let names: Vec<&str> = app.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["func1", "added", "func3"]);
+        assert_eq!(app.functions[0].description, "Fresh");
+        assert_eq!(app.functions[1].line, Some(6));
+        assert_eq!(app.status_message(), Some("Reloaded System (2 commands)"));
+
+        // A file that no longer parses keeps its functions
+        std::fs::remove_file(&path).unwrap();
+        app.reload_script_file(&script_file);
+        assert_eq!(app.functions.len(), 3);
+        assert!(app
+            .status_message()
+            .unwrap()
+            .starts_with("Failed to reload"));
+    }
 
-        let items = app.tree_items();
-        // Should show: System category + 2 functions + Utilities category
-        assert_eq!(items.len(), 4);
+    #[test]
+    fn test_apply_discovery_preserves_selection_and_failed_files() {
+        let bash_file = |category: &str| ScriptFile {
+            path: PathBuf::from(format!("/repo/{}.sh", category.to_lowercase())),
+            name: category.to_lowercase(),
+            category: category.to_string(),
+            display_name: category.to_string(),
+            script_type: ScriptType::Bash,
+            available: true,
+        };
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.script_files = vec![bash_file("System"), bash_file("Utilities")];
+        app.toggle_category("System");
+        app.selected_index = 2;
+        assert_eq!(app.selected_function().unwrap().name, "func2");
+
+        // A new function sorts before the selected one; Utilities fails to parse
+        let mut functions = create_test_functions();
+        functions.truncate(2);
+        functions.insert(0, named_function("a_new", "System"));
+        let parsed = ParsedScripts {
+            functions,
+            parse_errors: vec![(
+                "/repo/utilities.sh".to_string(),
+                anyhow::anyhow!("syntax error"),
+            )],
+            ..ParsedScripts::default()
+        };
+        app.apply_discovery(vec![bash_file("System"), bash_file("Utilities")], parsed);
+
+        assert_eq!(app.selected_function().unwrap().name, "func2");
+        assert_eq!(app.expanded_categories, vec!["System".to_string()]);
+        let names: Vec<&str> = app.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["a_new", "func1", "func2", "func3"]);
+        assert_eq!(
+            app.status_message(),
+            Some("Refreshed 4 commands from 2 files (1 failed to parse, kept their previous commands)")
+        );
+
+        // Functions removed from a file that parsed are dropped
+        app.apply_discovery(
+            vec![bash_file("System")],
+            ParsedScripts {
+                functions: vec![named_function("func1", "System")],
+                ..ParsedScripts::default()
+            },
+        );
+        let names: Vec<&str> = app.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["func1"]);
+        assert_eq!(app.selected_function().map(|f| f.name), None);
     }
 
     #[test]
-    fn test_app_selected_item() {
-        let functions = create_test_functions();
-        let app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_var_file_picker_selects_tfvars() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("prod.tfvars"), "").unwrap();
+        std::fs::write(temp_dir.path().join("dev.tfvars"), "").unwrap();
+
+        let plan = ScriptFunction {
+            script_type: ScriptType::Terraform,
+            ..named_function("plan", "infra")
+        };
+        let mut app = App::new(
+            vec![plan.clone(), named_function("func1", "System")],
+            "Test".to_string(),
+            test_theme(),
+        );
+        let script_file = ScriptFile {
+            path: temp_dir.path().to_path_buf(),
+            name: "infra".to_string(),
+            category: "infra".to_string(),
+            display_name: "infra".to_string(),
+            script_type: ScriptType::Terraform,
+            available: true,
+        };
+        app.script_files = vec![script_file.clone()];
+        app.expand_all();
 
-        let item = app.selected_item();
-        assert!(item.is_some());
+        let position = |app: &App, name: &str| {
+            app.tree_items()
+                .iter()
+                .position(|item| matches!(item, TreeItem::Function(f) if f.name == name))
+                .unwrap()
+        };
 
-        match item.unwrap() {
-            TreeItem::Category(_) => {} // Expected
-            _ => panic!("Expected category at index 0"),
-        }
+        // Only Terraform commands have var files
+        app.selected_index = position(&app, "func1");
+        app.open_var_file_picker();
+        assert!(app.var_file_picker.is_none());
+        assert_eq!(
+            app.status_message(),
+            Some("Var files apply to Terraform commands")
+        );
+
+        app.selected_index = position(&app, "plan");
+        app.open_var_file_picker();
+        let picker = app.var_file_picker.as_ref().unwrap();
+        assert_eq!(picker.files, vec!["dev.tfvars", "prod.tfvars"]);
+        assert_eq!(picker.index, 0);
+        assert!(app.is_modal_open());
+
+        app.var_file_picker_previous();
+        app.confirm_var_file_picker();
+        assert!(app.var_file_picker.is_none());
+        assert_eq!(app.status_message(), Some("Using var file prod.tfvars"));
+        assert_eq!(
+            app.var_file_for(&script_file),
+            Some("prod.tfvars".to_string())
+        );
+        assert_eq!(
+            app.run_options(&plan, &script_file).var_file,
+            Some("prod.tfvars".to_string())
+        );
+
+        // The selection survives a restart, and reopening highlights it
+        let state = app.ui_state();
+        let mut restored = App::new(vec![plan.clone()], "Test".to_string(), test_theme());
+        restored.script_files = vec![script_file.clone()];
+        restored.restore_ui_state(&state);
+        assert_eq!(
+            restored.var_file_for(&script_file),
+            Some("prod.tfvars".to_string())
+        );
+        app.open_var_file_picker();
+        assert_eq!(app.var_file_picker.as_ref().unwrap().index, 2);
+
+        // Back to no var file
+        app.var_file_picker_next();
+        app.confirm_var_file_picker();
+        assert_eq!(app.var_file_for(&script_file), None);
+
+        // A deleted file is no longer used
+        app.var_files.insert(
+            temp_dir.path().to_string_lossy().into_owned(),
+            "gone.tfvars".to_string(),
+        );
+        assert_eq!(app.var_file_for(&script_file), None);
     }
 
     #[test]
-    fn test_app_handle_left_right() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
-
-        // Initially not expanded
-        assert!(!app.is_category_expanded("System"));
-
-        // Simulate selecting first category (index 0)
-        app.selected_index = 0;
+    fn test_copy_command_line_matches_preview() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        let script_file = ScriptFile {
+            path: PathBuf::from("/home/me/my scripts/system.sh"),
+            name: "system".to_string(),
+            category: "System".to_string(),
+            display_name: "System".to_string(),
+            script_type: ScriptType::Bash,
+            available: true,
+        };
+        app.script_files = vec![script_file.clone()];
+        app.expand_all();
 
-        // Right arrow should expand
-        app.handle_right();
-        assert!(app.is_category_expanded("System"));
+        // Nothing to copy on a category header
+        assert!(app.copy_command_line().is_none());
+        assert_eq!(app.status_message(), Some("No command selected"));
 
-        // Left arrow should collapse
-        app.handle_left();
-        assert!(!app.is_category_expanded("System"));
+        app.selected_index = 1;
+        let func = app.selected_function().unwrap();
+        let line = app.copy_command_line().unwrap();
+        assert_eq!(
+            line,
+            preview_command(&func, &script_file, &RunOptions::default())
+        );
+        assert_eq!(
+            line,
+            "bash -c 'cd '\\''/home/me/my scripts'\\'' && source '\\''system.sh'\\'' && func1'"
+        );
+        assert_eq!(
+            app.status_message(),
+            Some(format!("Copied command: {}", line).as_str())
+        );
+
+        // Functions without a script file are reported
+        app.selected_index = 4;
+        assert_eq!(app.selected_function().unwrap().name, "func3");
+        assert!(app.copy_command_line().is_none());
+        assert_eq!(
+            app.status_message(),
+            Some("No script file found for Function 3")
+        );
     }
 
     #[test]
-    fn test_app_empty_functions() {
-        let app = App::new(vec![], "Test".to_string(), test_theme());
+    fn test_dry_run_target_requires_a_dry_run_variant() {
+        let mut app = App::new(mixed_type_functions(), "Test".to_string(), test_theme());
+        app.script_files = vec![
+            ScriptFile {
+                path: PathBuf::from("/app/system.sh"),
+                name: "system".to_string(),
+                category: "System".to_string(),
+                display_name: "System".to_string(),
+                script_type: ScriptType::Bash,
+                available: true,
+            },
+            ScriptFile {
+                path: PathBuf::from("/app/Makefile"),
+                name: "System".to_string(),
+                category: "System".to_string(),
+                display_name: "System".to_string(),
+                script_type: ScriptType::Makefile,
+                available: true,
+            },
+        ];
+        app.expand_all();
+
+        // System, func1 (bash), func2 (make)
+        app.selected_index = 2;
+        let func = app.dry_run_target().unwrap();
+        assert_eq!(func.name, "func2");
+        assert!(app.status_message().is_none());
+
+        app.selected_index = 1;
+        assert!(app.dry_run_target().is_none());
+        assert_eq!(
+            app.status_message(),
+            Some("No dry run available for Function 1 (Bash)")
+        );
+    }
 
-        assert_eq!(app.functions.len(), 0);
-        assert_eq!(app.categories().len(), 0);
-        assert_eq!(app.tree_items().len(), 0);
+    #[test]
+    fn test_open_explain_describes_selected_command() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.script_files = vec![ScriptFile {
+            path: PathBuf::from("/app/system.sh"),
+            name: "system".to_string(),
+            category: "System".to_string(),
+            display_name: "System".to_string(),
+            script_type: ScriptType::Bash,
+            available: true,
+        }];
+        app.expand_all();
+
+        // Nothing to explain on a category header
+        app.open_explain();
+        assert!(app.explain.is_none());
+
+        app.selected_index = 1;
+        app.command_history.set_env(
+            &app.functions[0].clone(),
+            parse_env_pairs("STAGE=dev").unwrap(),
+        );
+        app.open_explain();
+        let (func, plan) = app.explain.clone().unwrap();
+        assert_eq!(func.name, "func1");
+        let plan = plan.unwrap();
+        assert_eq!(plan.program, "bash");
+        assert_eq!(plan.working_dir, PathBuf::from("/app"));
+        assert_eq!(plan.env["STAGE"], "dev");
+
+        app.close_explain();
+        assert!(app.explain.is_none());
+
+        // Utilities has no script file
+        app.selected_index = 4;
+        app.open_explain();
+        assert!(app.explain.is_none());
+        assert_eq!(
+            app.status_message(),
+            Some("No script file found for Function 3")
+        );
     }
 
     #[test]
-    fn test_app_search_filtering() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions, "Test".to_string(), test_theme());
+    fn test_env_editor_stores_overrides_per_target() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        let func = app.functions[0].clone();
+
+        // Nothing happens on a category header
+        app.open_env_editor();
+        assert!(app.env_target.is_none());
+
+        app.expand_category(&func.category);
+        app.select_function_if_visible(&func);
+        app.open_env_editor();
+        assert!(app.is_modal_open());
+        for c in "LOG_LEVEL=debug".chars() {
+            app.env_push_char(c);
+        }
+        app.confirm_env_editor();
+        assert!(app.env_target.is_none());
+        assert_eq!(app.env_for(&func)["LOG_LEVEL"], "debug");
+        assert!(app.env_for(&app.functions[1]).is_empty());
+
+        // Reopening prefills the last-entered env
+        app.open_env_editor();
+        assert_eq!(app.env_input, "LOG_LEVEL=debug");
+        app.close_env_editor();
+    }
 
-        app.enter_search_mode();
-        app.search_push_char('f');
-        app.search_push_char('u');
-        app.search_push_char('n');
-        app.search_push_char('c');
-        app.search_push_char('3');
+    #[test]
+    fn test_run_env_overlays_overrides_on_dotenv() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(".env"), "A=from_file\nB=2\n").unwrap();
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        let func = app.functions[0].clone();
+        let script_file = ScriptFile {
+            path: temp_dir.path().join("test.sh"),
+            name: "test".to_string(),
+            category: func.category.clone(),
+            display_name: func.category.clone(),
+            script_type: ScriptType::Bash,
+            available: true,
+        };
+        app.command_history
+            .set_env(&func, parse_env_pairs("A=typed").unwrap());
 
-        // Search should filter to only show items matching "func3"
-        let items = app.tree_items();
+        // .env files are ignored unless loading is enabled
+        assert_eq!(app.run_env(&func, &script_file).get("B"), None);
 
-        // Should show Utilities category + func3
-        assert_eq!(items.len(), 2);
+        app.dotenv_root = Some(temp_dir.path().to_path_buf());
+        let env = app.run_env(&func, &script_file);
+        assert_eq!(env["A"], "typed");
+        assert_eq!(env["B"], "2");
     }
 
     #[test]
-    fn test_app_frequent_functions() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
-
-        // No frequent functions initially
-        assert!(app.frequent_functions.is_empty());
-        assert_eq!(app.categories().len(), 2);
+    fn test_cycle_env_profile() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.cycle_env_profile();
+        assert_eq!(app.active_profile, None);
+
+        app.set_env_profiles(vec![
+            ("dev".to_string(), EnvOverrides::new()),
+            ("staging".to_string(), EnvOverrides::new()),
+        ]);
+        assert_eq!(app.active_profile_name(), None);
+
+        let mut names = Vec::new();
+        for _ in 0..3 {
+            app.cycle_env_profile();
+            names.push(app.active_profile_name().map(str::to_string));
+        }
+        assert_eq!(
+            names,
+            vec![Some("dev".to_string()), Some("staging".to_string()), None]
+        );
+        assert_eq!(app.status_message(), Some("Env profile: none"));
+    }
 
-        // Set frequent functions
-        app.set_frequent_functions(vec![functions[0].clone()]);
+    #[test]
+    fn test_start_and_stop_watch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        // A category is selected: nothing to watch
+        assert!(app
+            .start_watch(temp_dir.path(), &ExcludeSet::default())
+            .is_none());
+        assert!(app.watch.is_none());
+
+        app.expand_all();
+        app.selected_index = 1;
+        let func = app
+            .start_watch(temp_dir.path(), &ExcludeSet::default())
+            .unwrap();
+        assert_eq!(func.name, "func1");
+        assert_eq!(app.watch.as_ref().unwrap().target.name, "func1");
+        // No change yet, so no re-run
+        assert!(app.watch_rerun_target().is_none());
+
+        app.stop_watch();
+        assert!(app.watch.is_none());
+        assert_eq!(app.status_message(), Some("Stopped watching Function 1"));
+        assert!(app.watch_rerun_target().is_none());
+    }
 
-        // Should now have 3 categories (Frequently Used + System + Utilities)
-        assert_eq!(app.categories().len(), 3);
-        assert_eq!(app.categories()[0], FREQUENTLY_USED_CATEGORY);
+    #[test]
+    fn test_marked_items_follow_mark_order() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.toggle_mark();
+        assert!(app.marks.is_empty());
+        assert_eq!(app.status_message(), Some("Select a command to mark it"));
+
+        app.expand_all();
+        app.selected_index = 2;
+        let second = app.selected_function().unwrap();
+        app.toggle_mark();
+        app.selected_index = 1;
+        let first = app.selected_function().unwrap();
+        app.toggle_mark();
+        app.selected_index = 4;
+        app.toggle_mark();
+        app.toggle_mark();
+
+        let names: Vec<String> = app.marked_items().into_iter().map(|f| f.name).collect();
+        assert_eq!(names, vec![second.name.clone(), first.name.clone()]);
+        assert_eq!(app.mark_position(&second), Some(1));
+        assert_eq!(app.mark_position(&first), Some(2));
+
+        // Marks of commands that no longer exist are skipped
+        app.marks.insert(0, "Bash:gone".to_string());
+        assert_eq!(app.marked_items().len(), 2);
     }
 
     #[test]
-    fn test_app_tree_items_with_frequent() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+    fn test_sequence_runs_marked_commands_until_failure() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        assert!(app.start_sequence().is_none());
+
+        app.expand_all();
+        for index in [1, 2, 4] {
+            app.selected_index = index;
+            app.toggle_mark();
+        }
+        let steps = app.marked_items();
+
+        let first = app.start_sequence().unwrap();
+        assert_eq!(first.name, steps[0].name);
+        assert!(app.marks.is_empty());
+        assert_eq!(app.sequence.as_ref().unwrap().progress(), (1, 3));
+
+        let second = app.sequence_next(&first, true).unwrap();
+        assert_eq!(second.name, steps[1].name);
+        assert!(app.sequence_next(&second, false).is_none());
+        assert!(app.sequence.is_none());
+        assert_eq!(
+            app.status_message(),
+            Some(format!("Sequence stopped: {} failed", second.display_name).as_str())
+        );
+    }
 
-        // Set frequent functions
-        app.set_frequent_functions(vec![functions[0].clone()]);
+    #[test]
+    fn test_sequence_keeps_output_of_earlier_steps() {
+        use crate::ui::pty_runner::{spawn_pty_command, RunOptions};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script = temp_dir.path().join("ops.sh");
+        std::fs::write(
+            &script,
+            "step_one() {\n  echo step one output\n}\n\nstep_two() {\n  echo step two output\n  return 3\n}\n",
+        )
+        .unwrap();
+        let script_file = ScriptFile {
+            path: script.clone(),
+            name: "ops".to_string(),
+            category: "Ops".to_string(),
+            display_name: "Ops".to_string(),
+            script_type: ScriptType::Bash,
+            available: true,
+        };
+        let step = |name: &str| ScriptFunction {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            category: "Ops".to_string(),
+            ..create_test_functions()[0].clone()
+        };
+        let mut app = App::new(
+            vec![step("step_one"), step("step_two")],
+            "Test".to_string(),
+            test_theme(),
+        );
+        app.expand_all();
+        for index in [1, 2] {
+            app.selected_index = index;
+            app.toggle_mark();
+        }
 
-        // Expand the frequently used category
-        app.expand_category(FREQUENTLY_USED_CATEGORY);
+        // Run a step the way the inline runner does: its own PTY, moved into
+        // the command history once it finishes
+        let run = |app: &mut App, func: &ScriptFunction| {
+            let handle = spawn_pty_command(
+                func,
+                &script_file,
+                "Ops",
+                &RunOptions::default(),
+                None,
+                80,
+                24,
+            )
+            .unwrap();
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while handle.poll_finished_at().is_none() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            app.active_function = Some(func.clone());
+            app.pty_handle = Some(handle);
+            app.finalize_pty();
+            assert!(app.pty_handle.is_none(), "{} did not finish", func.name);
+        };
+        let contents = |app: &App, func: &ScriptFunction| {
+            app.command_history
+                .get(func)
+                .unwrap()
+                .parser
+                .lock()
+                .unwrap()
+                .screen()
+                .contents()
+        };
 
-        let items = app.tree_items();
+        let first = app.start_sequence().unwrap();
+        assert_eq!(first.name, "step_one");
+        run(&mut app, &first);
+        let second = app.sequence_next(&first, true).unwrap();
+        assert_eq!(second.name, "step_two");
+        run(&mut app, &second);
+
+        assert!(contents(&app, &first).contains("step one output"));
+        assert!(contents(&app, &second).contains("step two output"));
+        assert!(!contents(&app, &second).contains("step one output"));
+        assert_eq!(app.command_history.get(&first).unwrap().exit_code, Some(0));
+        assert_eq!(app.command_history.get(&second).unwrap().exit_code, Some(3));
+    }
 
-        // Should show: Frequently Used category + 1 func + System category + Utilities category
-        assert_eq!(items.len(), 4);
+    #[test]
+    fn test_run_env_profile_precedence() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(".env"),
+            "API_URL=http://localhost\nDB=local\n",
+        )
+        .unwrap();
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        let func = app.functions[0].clone();
+        let script_file = ScriptFile {
+            path: temp_dir.path().join("test.sh"),
+            name: "test".to_string(),
+            category: func.category.clone(),
+            display_name: func.category.clone(),
+            script_type: ScriptType::Bash,
+            available: true,
+        };
+        app.dotenv_root = Some(temp_dir.path().to_path_buf());
+        app.set_env_profiles(vec![(
+            "staging".to_string(),
+            parse_env_pairs("API_URL=https://staging LOG=info").unwrap(),
+        )]);
+        app.command_history
+            .set_env(&func, parse_env_pairs("LOG=debug").unwrap());
+
+        // No profile is active by default
+        let env = app.run_env(&func, &script_file);
+        assert_eq!(env["API_URL"], "http://localhost");
+        assert_eq!(env["LOG"], "debug");
+
+        // The profile overrides inherited values; typed overrides win over it
+        app.cycle_env_profile();
+        let env = app.run_env(&func, &script_file);
+        assert_eq!(env["API_URL"], "https://staging");
+        assert_eq!(env["DB"], "local");
+        assert_eq!(env["LOG"], "debug");
+    }
 
-        // First item should be Frequently Used category
-        match &items[0] {
-            TreeItem::Category(name) => assert_eq!(name, FREQUENTLY_USED_CATEGORY),
-            _ => panic!("Expected Frequently Used category first"),
+    #[test]
+    fn test_make_vars_editor_suggests_and_stores_per_target() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let makefile = temp_dir.path().join("Makefile");
+        std::fs::write(
+            &makefile,
+            "PROFILE ?= debug\nCC := gcc\n\nbuild:\n\t$(CC) main.c\n",
+        )
+        .unwrap();
+        let build = typed("build", "Makefile", ScriptType::Makefile);
+        let mut app = App::new(
+            vec![build.clone(), typed("deploy", "Scripts", ScriptType::Bash)],
+            "Test".to_string(),
+            test_theme(),
+        );
+        app.script_files = vec![ScriptFile {
+            path: makefile,
+            name: "Makefile".to_string(),
+            category: "Makefile".to_string(),
+            display_name: "Makefile".to_string(),
+            script_type: ScriptType::Makefile,
+            available: true,
+        }];
+        app.expand_all();
+
+        // Only make targets take variables
+        app.select_function_if_visible(&app.functions[1].clone());
+        app.open_make_vars_editor();
+        assert!(app.env_target.is_none());
+        assert_eq!(
+            app.status_message(),
+            Some("Variables can only be set for make targets")
+        );
+
+        app.select_function_if_visible(&build);
+        app.open_make_vars_editor();
+        assert_eq!(app.env_kind, OverrideKind::MakeVars);
+        assert_eq!(app.make_var_candidates, vec!["PROFILE", "CC"]);
+        for c in "PROFILE=release".chars() {
+            app.env_push_char(c);
         }
+        app.confirm_env_editor();
+        assert!(app.env_target.is_none());
+        assert_eq!(app.make_vars_for(&build)["PROFILE"], "release");
+        assert!(app.env_for(&build).is_empty());
+        assert_eq!(
+            app.status_message(),
+            Some("Make variables for Build: PROFILE=release")
+        );
+
+        let options = app.run_options(&build, &app.script_files[0].clone());
+        assert_eq!(options.make_vars["PROFILE"], "release");
+
+        // Reopening prefills them; the env modal keeps its own values
+        app.open_make_vars_editor();
+        assert_eq!(app.env_input, "PROFILE=release");
+        app.close_env_editor();
+        app.open_env_editor();
+        assert_eq!(app.env_kind, OverrideKind::Env);
+        assert!(app.env_input.is_empty());
+    }
 
-        // Second item should be the frequent function
-        match &items[1] {
-            TreeItem::Function(func) => {
-                assert_eq!(func.name, "func1");
-                assert_eq!(func.category, FREQUENTLY_USED_CATEGORY);
-            }
-            _ => panic!("Expected function"),
+    #[test]
+    fn test_env_editor_rejects_invalid_and_clears_on_empty() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        let func = app.functions[0].clone();
+        app.expand_category(&func.category);
+        app.select_function_if_visible(&func);
+        app.command_history
+            .set_env(&func, parse_env_pairs("A=1").unwrap());
+
+        app.open_env_editor();
+        app.env_input = "A=1 debug".to_string();
+        app.confirm_env_editor();
+        assert!(app.env_target.is_some());
+        assert!(app.env_error.is_some());
+        assert_eq!(app.env_for(&func)["A"], "1");
+
+        // Typing clears the error; empty input removes the overrides
+        while !app.env_input.is_empty() {
+            app.env_pop_char();
         }
+        assert!(app.env_error.is_none());
+        app.confirm_env_editor();
+        assert!(app.env_target.is_none());
+        assert!(app.env_for(&func).is_empty());
     }
 
     #[test]
-    fn test_app_frequent_search_filtering() {
-        let functions = create_test_functions();
-        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
-
-        // Set all functions as frequent
-        app.set_frequent_functions(functions.clone());
+    fn test_app_category_jump_selects_header() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.expand_category("System");
+        app.open_category_jump();
+        app.category_jump_next();
+        app.confirm_category_jump();
+
+        assert!(!app.show_category_jump);
+        assert!(matches!(
+            app.selected_item(),
+            Some(TreeItem::Category(ref c)) if c == "Utilities"
+        ));
+    }
 
+    #[test]
+    fn test_app_category_jump_leaves_search_mode() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
         app.enter_search_mode();
-        app.search_push_char('f');
-        app.search_push_char('u');
-        app.search_push_char('n');
-        app.search_push_char('c');
-        app.search_push_char('1');
+        app.search_push_char('z');
+        app.jump_to_category("System");
 
-        let items = app.tree_items();
+        assert!(!app.search_mode);
+        assert!(matches!(
+            app.selected_item(),
+            Some(TreeItem::Category(ref c)) if c == "System"
+        ));
+    }
 
-        // Should show: Frequently Used + func1 + System + func1
-        // (func1 appears in both Frequently Used and System)
-        assert_eq!(items.len(), 4);
+    #[test]
+    fn test_app_hidden_and_renamed_categories() {
+        let mut functions = create_test_functions();
+        functions.push(typed("deploy", "zz-deploy", ScriptType::Bash));
+        functions.push(typed("scratch", "tmp", ScriptType::Bash));
+        let mut app = App::new(functions, "Test".to_string(), test_theme());
+        app.set_category_display_names(HashMap::from([(
+            "tmp".to_string(),
+            "🧪 Scratch".to_string(),
+        )]));
+
+        app.set_hidden_categories(&["scratch".to_string(), "UTILITIES".to_string()]);
+        app.rename_categories(&HashMap::from([
+            ("zz-deploy".to_string(), "🚀 Deployment".to_string()),
+            ("missing".to_string(), "Nothing".to_string()),
+        ]));
+        app.set_category_order(&["deployment".to_string()]);
+
+        // Hidden categories are dropped, renamed ones sort by their new name
+        assert_eq!(app.categories(), vec!["zz-deploy", "System"]);
+        assert_eq!(app.get_category_display_name("zz-deploy"), "🚀 Deployment");
+        assert!(!app
+            .tree_items()
+            .iter()
+            .any(|item| matches!(item, TreeItem::Category(c) if c == "tmp" || c == "Utilities")));
+
+        // Functions of hidden categories stay out of the tree when expanded all
+        app.expand_all();
+        assert!(!app
+            .tree_items()
+            .iter()
+            .any(|item| matches!(item, TreeItem::Function(f) if f.name == "scratch")));
     }
 
     #[test]
-    fn test_app_execution_status_idle() {
-        let functions = create_test_functions();
-        let app = App::new(functions, "Test".to_string(), test_theme());
-        assert_eq!(app.current_execution_status(), ExecutionStatus::Idle);
+    fn test_app_category_order() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        assert_eq!(app.categories(), vec!["System", "Utilities"]);
+
+        app.set_category_order(&["utilities".to_string()]);
+        assert_eq!(app.categories(), vec!["Utilities", "System"]);
+        assert!(matches!(
+            app.tree_items().first(),
+            Some(TreeItem::Category(ref c)) if c == "Utilities"
+        ));
     }
 
     #[test]
-    fn test_app_mouse_selection() {
-        let functions = create_test_functions();
+    fn test_app_category_order_matches_display_name() {
+        let mut functions = create_test_functions();
+        functions.push(ScriptFunction {
+            name: "deploy".to_string(),
+            display_name: "Deploy".to_string(),
+            category: "zz-deploy".to_string(),
+            description: String::new(),
+            emoji: None,
+            ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
+            script_type: ScriptType::Bash,
+        });
         let mut app = App::new(functions, "Test".to_string(), test_theme());
+        let mut names = HashMap::new();
+        names.insert("zz-deploy".to_string(), "🚀 Deploy".to_string());
+        app.set_category_display_names(names);
+        app.set_category_order(&["Deploy".to_string(), "Missing".to_string()]);
 
-        assert!(!app.mouse_selecting);
+        assert_eq!(app.categories(), vec!["zz-deploy", "System", "Utilities"]);
+    }
 
-        app.start_mouse_selection(0, 0);
-        assert!(app.mouse_selecting);
-        assert_eq!(app.mouse_sel_start, Some((0, 0)));
-        assert_eq!(app.mouse_sel_end, Some((0, 0)));
+    #[test]
+    fn test_app_category_order_keeps_frequent_first() {
+        let functions = create_test_functions();
+        let mut app = App::new(functions.clone(), "Test".to_string(), test_theme());
+        app.set_frequent_functions(vec![functions[0].clone()]);
+        app.set_category_order(&["Utilities".to_string()]);
 
-        app.update_mouse_selection(2, 5);
-        assert_eq!(app.mouse_sel_end, Some((2, 5)));
+        assert_eq!(
+            app.categories(),
+            vec![FREQUENTLY_USED_CATEGORY, "Utilities", "System"]
+        );
+    }
 
-        app.clear_mouse_selection();
-        assert!(!app.mouse_selecting);
-        assert!(app.mouse_sel_start.is_none());
-        assert!(app.mouse_sel_end.is_none());
+    #[test]
+    fn test_app_toggle_execution_mode() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        assert_eq!(app.execution_mode, ExecutionMode::Inline);
+
+        app.toggle_execution_mode();
+        assert_eq!(app.execution_mode, ExecutionMode::Fullscreen);
+        app.toggle_execution_mode();
+        assert_eq!(app.execution_mode, ExecutionMode::Inline);
     }
 
     #[test]
-    fn test_app_focus_clears_mouse_selection() {
-        let functions = create_test_functions();
+    fn test_app_search_matches_aliases() {
+        let mut functions = create_test_functions();
+        functions[2].aliases = vec!["zap".to_string()];
         let mut app = App::new(functions, "Test".to_string(), test_theme());
 
-        app.mouse_selecting = true;
-        app.mouse_sel_start = Some((0, 0));
-        app.mouse_sel_end = Some((1, 5));
+        app.enter_search_mode();
+        for c in "zap".chars() {
+            app.search_push_char(c);
+        }
 
-        // Focus is ScriptList -> stays ScriptList (no terminal output)
-        // but let's test that clearing works when focus != Output
-        app.focus = FocusPane::Output;
-        app.toggle_focus(); // Output -> ScriptList
-        assert!(!app.mouse_selecting);
-        assert!(app.mouse_sel_start.is_none());
+        let items = app.tree_items();
+        assert_eq!(items.len(), 2);
+        assert!(matches!(&items[1], TreeItem::Function(f) if f.name == "func3"));
+    }
+
+    #[test]
+    fn test_app_git_info_disabled_by_default() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.update_git_info(Some(Path::new("/nonexistent/script.sh")));
+
+        assert!(app.selected_git_info.is_none());
+        assert!(app.git_info_cache.is_empty());
+    }
+
+    #[test]
+    fn test_app_git_info_uses_cache() {
+        let mut app = App::new(create_test_functions(), "Test".to_string(), test_theme());
+        app.show_git_info = true;
+        let info = GitCommitInfo {
+            hash: "abc1234".to_string(),
+            author: "Jane".to_string(),
+            relative_date: "2 days ago".to_string(),
+        };
+        app.git_info_cache
+            .insert(PathBuf::from("/repo/build.sh"), Some(info.clone()));
+
+        app.update_git_info(Some(Path::new("/repo/build.sh")));
+        assert_eq!(app.selected_git_info, Some(info));
+
+        app.update_git_info(None);
+        assert!(app.selected_git_info.is_none());
     }
 }
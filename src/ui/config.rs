@@ -5,8 +5,10 @@
 //! ## Overview
 //!
 //! The [`Config`] struct is serialized to / deserialized from a JSON file in
-//! the user's XDG config directory. Currently, the only persisted setting is
-//! the selected theme name.
+//! the user's XDG config directory. It persists the selected theme name, the
+//! default [`ExecutionMode`], the size of the "Frequently Used" category, the
+//! default command timeout, remapped keys and opt-in features such as git
+//! provenance, `.env` loading and completion alerts.
 //!
 //! ## File Location
 //!
@@ -17,10 +19,14 @@
 //! The `directories` crate is used to resolve the platform-appropriate config
 //! directory.
 
+use crate::ui::notify::FinishAlerts;
+use crate::usage::{MAX_FREQUENT_COMMANDS, MAX_RECENT_COMMANDS};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Persisted user configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,21 +35,157 @@ pub struct Config {
     /// The name of the selected theme (must match a built-in theme name).
     #[serde(default = "default_theme_name")]
     pub theme: String,
+
+    /// How commands are executed by default (`"inline"` or `"fullscreen"`).
+    #[serde(default)]
+    pub execution_mode: ExecutionMode,
+
+    /// Show the last commit that touched the selected command's source file.
+    /// Off by default since each lookup spawns `git`.
+    #[serde(default)]
+    pub show_git_info: bool,
+
+    /// Maximum number of commands in the "Frequently Used" category.
+    /// `0` hides the category.
+    #[serde(default = "default_frequent_limit")]
+    pub frequent_limit: usize,
+
+    /// Show the "Frequently Used" category at the top of the list.
+    #[serde(default = "default_show_frequent")]
+    pub show_frequent: bool,
+
+    /// Maximum number of commands in the "Recently Used" category, shown
+    /// below "Frequently Used". `0` hides the category.
+    #[serde(default = "default_recent_limit")]
+    pub recent_limit: usize,
+
+    /// Fail inline commands still running after this many seconds.
+    /// `None` (the default) lets commands run for as long as they need.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+
+    /// Load `.env` files from the project root and the command's directory
+    /// into its environment. Off by default to avoid surprises.
+    #[serde(default)]
+    pub load_dotenv: bool,
+
+    /// Ring the terminal bell when an inline command finishes.
+    #[serde(default)]
+    pub bell_on_finish: bool,
+
+    /// Show a desktop notification when an inline command finishes.
+    #[serde(default)]
+    pub notify_on_finish: bool,
+
+    /// Only ring or notify for commands that ran at least this many seconds.
+    #[serde(default = "default_notify_after_secs")]
+    pub notify_after_secs: u64,
+
+    /// Keep running a sequence of marked commands after a step fails.
+    #[serde(default)]
+    pub sequence_continue_on_error: bool,
+
+    /// Script list keys remapped by action name (`"next": "n"`), applied on
+    /// top of the default keybindings. See [`crate::ui::keymap::Keymap`].
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+/// How Enter runs a command.
+///
+/// - [`ExecutionMode::Inline`] runs the command in an embedded PTY inside the
+///   output pane. The TUI stays visible, output is kept per command and can be
+///   scrolled/selected, and several commands can be reviewed side by side.
+/// - [`ExecutionMode::Fullscreen`] suspends the TUI and hands the real terminal
+///   to the command. Programs that need the full terminal (editors, pagers,
+///   complex TUIs) behave exactly as in a shell, but output is not kept once
+///   you return to Jarvis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionMode {
+    #[default]
+    Inline,
+    Fullscreen,
+}
+
+impl ExecutionMode {
+    /// The other mode
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Inline => Self::Fullscreen,
+            Self::Fullscreen => Self::Inline,
+        }
+    }
+
+    /// Short label for the footer
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Inline => "Inline",
+            Self::Fullscreen => "Full-screen",
+        }
+    }
 }
 
 fn default_theme_name() -> String {
     "Catppuccin Mocha".to_string()
 }
 
+fn default_frequent_limit() -> usize {
+    MAX_FREQUENT_COMMANDS
+}
+
+fn default_show_frequent() -> bool {
+    true
+}
+
+fn default_recent_limit() -> usize {
+    MAX_RECENT_COMMANDS
+}
+
+fn default_notify_after_secs() -> u64 {
+    10
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             theme: default_theme_name(),
+            execution_mode: ExecutionMode::default(),
+            show_git_info: false,
+            frequent_limit: default_frequent_limit(),
+            show_frequent: default_show_frequent(),
+            recent_limit: default_recent_limit(),
+            timeout_secs: None,
+            load_dotenv: false,
+            bell_on_finish: false,
+            notify_on_finish: false,
+            notify_after_secs: default_notify_after_secs(),
+            sequence_continue_on_error: false,
+            keys: BTreeMap::new(),
         }
     }
 }
 
 impl Config {
+    /// Number of frequently used commands to show; `0` when the category is
+    /// disabled through `show_frequent`.
+    pub fn effective_frequent_limit(&self) -> usize {
+        if self.show_frequent {
+            self.frequent_limit
+        } else {
+            0
+        }
+    }
+
+    /// The completion alerts enabled by this configuration
+    pub fn finish_alerts(&self) -> FinishAlerts {
+        FinishAlerts {
+            bell: self.bell_on_finish,
+            desktop: self.notify_on_finish,
+            min_duration: Duration::from_secs(self.notify_after_secs),
+        }
+    }
+
     /// Load configuration from disk. Returns `Config::default()` if the file
     /// does not exist or cannot be parsed.
     pub fn load() -> Self {
@@ -114,6 +256,7 @@ mod tests {
     fn test_serialize_deserialize() {
         let config = Config {
             theme: "Dracula".to_string(),
+            ..Config::default()
         };
         let json = serde_json::to_string(&config).expect("serialize");
         let loaded: Config = serde_json::from_str(&json).expect("deserialize");
@@ -134,6 +277,7 @@ mod tests {
 
         let config = Config {
             theme: "Nord".to_string(),
+            ..Config::default()
         };
 
         // Write directly to the temp path
@@ -153,6 +297,7 @@ mod tests {
 
         let config = Config {
             theme: "Dracula".to_string(),
+            ..Config::default()
         };
 
         // Use the actual save_to / load_from methods
@@ -176,4 +321,105 @@ mod tests {
         let result: Result<Config, _> = serde_json::from_str(json);
         assert!(result.is_err(), "should reject unknown fields");
     }
+
+    #[test]
+    fn test_execution_mode_defaults_to_inline() {
+        let config: Config = serde_json::from_str(r#"{"theme": "Nord"}"#).expect("deserialize");
+        assert_eq!(config.execution_mode, ExecutionMode::Inline);
+    }
+
+    #[test]
+    fn test_execution_mode_fullscreen() {
+        let json = r#"{"execution_mode": "fullscreen"}"#;
+        let config: Config = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(config.execution_mode, ExecutionMode::Fullscreen);
+        assert_eq!(config.execution_mode.toggled(), ExecutionMode::Inline);
+    }
+
+    #[test]
+    fn test_frequent_defaults() {
+        let config: Config = serde_json::from_str("{}").expect("deserialize");
+        assert!(config.show_frequent);
+        assert_eq!(config.frequent_limit, MAX_FREQUENT_COMMANDS);
+        assert_eq!(config.effective_frequent_limit(), MAX_FREQUENT_COMMANDS);
+        assert_eq!(config.recent_limit, MAX_RECENT_COMMANDS);
+
+        let config: Config = serde_json::from_str(r#"{"recent_limit": 0}"#).expect("deserialize");
+        assert_eq!(config.recent_limit, 0);
+    }
+
+    #[test]
+    fn test_frequent_limit_and_toggle() {
+        let config: Config =
+            serde_json::from_str(r#"{"frequent_limit": 10}"#).expect("deserialize");
+        assert_eq!(config.effective_frequent_limit(), 10);
+
+        let config: Config =
+            serde_json::from_str(r#"{"frequent_limit": 10, "show_frequent": false}"#)
+                .expect("deserialize");
+        assert_eq!(config.effective_frequent_limit(), 0);
+
+        let config: Config = serde_json::from_str(r#"{"frequent_limit": 0}"#).expect("deserialize");
+        assert_eq!(config.effective_frequent_limit(), 0);
+    }
+
+    #[test]
+    fn test_show_git_info_opt_in() {
+        let config: Config = serde_json::from_str("{}").expect("deserialize");
+        assert!(!config.show_git_info);
+
+        let config: Config =
+            serde_json::from_str(r#"{"show_git_info": true}"#).expect("deserialize");
+        assert!(config.show_git_info);
+    }
+
+    #[test]
+    fn test_timeout_defaults_to_none() {
+        let config: Config = serde_json::from_str("{}").expect("deserialize");
+        assert_eq!(config.timeout_secs, None);
+
+        let config: Config = serde_json::from_str(r#"{"timeout_secs": 300}"#).expect("deserialize");
+        assert_eq!(config.timeout_secs, Some(300));
+    }
+
+    #[test]
+    fn test_finish_alerts_opt_in() {
+        let config: Config = serde_json::from_str("{}").expect("deserialize");
+        assert_eq!(
+            config.finish_alerts(),
+            FinishAlerts {
+                bell: false,
+                desktop: false,
+                min_duration: Duration::from_secs(10),
+            }
+        );
+
+        let config: Config = serde_json::from_str(
+            r#"{"bell_on_finish": true, "notify_on_finish": true, "notify_after_secs": 30}"#,
+        )
+        .expect("deserialize");
+        let alerts = config.finish_alerts();
+        assert!(alerts.bell && alerts.desktop);
+        assert_eq!(alerts.min_duration, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_load_dotenv_opt_in() {
+        let config: Config = serde_json::from_str("{}").expect("deserialize");
+        assert!(!config.load_dotenv);
+
+        let config: Config = serde_json::from_str(r#"{"load_dotenv": true}"#).expect("deserialize");
+        assert!(config.load_dotenv);
+    }
+
+    #[test]
+    fn test_keys_default_to_empty() {
+        let config: Config = serde_json::from_str("{}").expect("deserialize");
+        assert!(config.keys.is_empty());
+
+        let json = r#"{"keys": {"next": "n", "quit": "Ctrl+q"}}"#;
+        let config: Config = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(config.keys.get("next").map(String::as_str), Some("n"));
+        assert_eq!(config.keys.len(), 2);
+    }
 }
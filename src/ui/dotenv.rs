@@ -0,0 +1,186 @@
+//! # `.env` File Loading
+//!
+//! Reads `KEY=value` pairs from `.env` files so commands see the same
+//! variables they would under tools like `direnv` or `dotenv`.
+//!
+//! ## Overview
+//!
+//! Loading is opt-in through `"load_dotenv": true` in the user config. When
+//! enabled, the `.env` in the project root is read first, then the one in the
+//! command's working directory, so the closer file wins. Explicit overrides
+//! entered in the TUI (`e`) take precedence over both.
+//!
+//! ## Format
+//!
+//! ```text
+//! # comments and blank lines are skipped
+//! DATABASE_URL=postgres://localhost/dev
+//! export API_KEY="abc 123"   # `export` prefix and trailing comments are allowed
+//! GREETING='single quotes are taken literally'
+//! ```
+//!
+//! Double-quoted values support `\n`, `\t`, `\"` and `\\` escapes. Lines that
+//! are not `KEY=value` pairs with a valid variable name are ignored.
+
+use crate::ui::pty_runner::{is_valid_env_key, EnvOverrides};
+use std::path::Path;
+
+/// Name of the file read from each directory
+pub const DOTENV_FILE: &str = ".env";
+
+/// Parse `.env` content into key/value pairs, in file order.
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    content.lines().filter_map(parse_line).collect()
+}
+
+/// Parse a single `.env` line, returning `None` for comments, blank lines and
+/// anything that is not a valid assignment
+fn parse_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    if !is_valid_env_key(key) {
+        return None;
+    }
+    Some((key.to_string(), parse_value(value.trim())))
+}
+
+/// Unquote a value: double quotes process escapes, single quotes are literal,
+/// and unquoted values end at a ` #` comment
+fn parse_value(value: &str) -> String {
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut parsed = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => parsed.push('\n'),
+                    Some('t') => parsed.push('\t'),
+                    Some(other) => parsed.push(other),
+                    None => parsed.push('\\'),
+                },
+                _ => parsed.push(c),
+            }
+        }
+        return parsed;
+    }
+    if let Some(rest) = value.strip_prefix('\'') {
+        return rest
+            .split_once('\'')
+            .map_or(rest, |(inner, _)| inner)
+            .to_string();
+    }
+    value
+        .find(" #")
+        .map_or(value, |idx| &value[..idx])
+        .trim_end()
+        .to_string()
+}
+
+/// Load the `.env` files for a command: the project root's first, then the
+/// working directory's. Missing or unreadable files are skipped.
+pub fn load_for(project_root: &Path, working_dir: &Path) -> EnvOverrides {
+    let mut env = EnvOverrides::new();
+    let mut dirs = vec![project_root];
+    if working_dir != project_root {
+        dirs.push(working_dir);
+    }
+    for dir in dirs {
+        if let Ok(content) = std::fs::read_to_string(dir.join(DOTENV_FILE)) {
+            env.extend(parse_dotenv(&content));
+        }
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test_parse_dotenv_plain_values() {
+        assert_eq!(
+            parse_dotenv("FOO=bar\nEMPTY=\nURL=postgres://localhost:5432/db?x=1\n"),
+            vec![
+                pair("FOO", "bar"),
+                pair("EMPTY", ""),
+                pair("URL", "postgres://localhost:5432/db?x=1")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_quotes() {
+        let content = r#"
+DOUBLE="hello world"
+SINGLE='it is $literal'
+ESCAPED="line1\nline2 \"quoted\""
+HASH="a # b"
+"#;
+        assert_eq!(
+            parse_dotenv(content),
+            vec![
+                pair("DOUBLE", "hello world"),
+                pair("SINGLE", "it is $literal"),
+                pair("ESCAPED", "line1\nline2 \"quoted\""),
+                pair("HASH", "a # b")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_comments_and_blank_lines() {
+        let content = "# Database settings\n\n   \nDB_HOST=localhost # local only\n  # indented comment\nDB_PORT=5432\nCOLOR=#fff\n";
+        assert_eq!(
+            parse_dotenv(content),
+            vec![
+                pair("DB_HOST", "localhost"),
+                pair("DB_PORT", "5432"),
+                pair("COLOR", "#fff")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_export_prefix_and_spacing() {
+        assert_eq!(
+            parse_dotenv("export TOKEN=abc\n  NAME = jarvis  \n"),
+            vec![pair("TOKEN", "abc"), pair("NAME", "jarvis")]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_skips_invalid_lines() {
+        assert!(parse_dotenv("not an assignment\n1BAD=x\nBAD KEY=x\n=value\n").is_empty());
+    }
+
+    #[test]
+    fn test_load_for_working_dir_overrides_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let sub = temp_dir.path().join("api");
+        fs::create_dir(&sub).unwrap();
+        fs::write(temp_dir.path().join(".env"), "SHARED=root\nROOT_ONLY=1\n").unwrap();
+        fs::write(sub.join(".env"), "SHARED=api\n").unwrap();
+
+        let env = load_for(temp_dir.path(), &sub);
+        assert_eq!(env.get("SHARED").map(String::as_str), Some("api"));
+        assert_eq!(env.get("ROOT_ONLY").map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn test_load_for_without_files() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_for(temp_dir.path(), temp_dir.path()).is_empty());
+    }
+}
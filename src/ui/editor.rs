@@ -0,0 +1,108 @@
+//! # External Editor
+//!
+//! Builds the command that opens the selected command's script file in the
+//! user's editor (`o`).
+//!
+//! ## Overview
+//!
+//! The editor comes from `$EDITOR`, which may include arguments
+//! (`code --wait`), falling back to `vi` (`notepad` on Windows) when it is
+//! unset. When the command's line is known (bash functions), the editor is
+//! asked to jump to it: `+<line>` for terminal editors like `vi`, `nano` and
+//! `emacs`, `<file>:<line>` for editors that expect it.
+
+use std::path::Path;
+
+/// Editor used when `$EDITOR` is unset or empty
+pub const FALLBACK_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+/// The user's editor command line: `$EDITOR`, or [`FALLBACK_EDITOR`]
+pub fn editor_from_env() -> String {
+    std::env::var("EDITOR")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+/// Program and arguments that open `path` in `editor`, at `line` when given
+pub fn editor_command(editor: &str, path: &Path, line: Option<usize>) -> (String, Vec<String>) {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(FALLBACK_EDITOR).to_string();
+    let mut args: Vec<String> = parts.map(str::to_string).collect();
+    let file = path.display().to_string();
+
+    let name = Path::new(&program)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    match (line, name) {
+        (Some(line), "code" | "code-insiders" | "codium") => {
+            args.push("--goto".to_string());
+            args.push(format!("{}:{}", file, line));
+        }
+        (Some(line), "subl" | "hx" | "zed") => args.push(format!("{}:{}", file, line)),
+        (Some(line), name) if name != "notepad" => {
+            args.push(format!("+{}", line));
+            args.push(file);
+        }
+        _ => args.push(file),
+    }
+
+    (program, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command_with_line() {
+        let path = Path::new("/repo/scripts/deploy.sh");
+        assert_eq!(
+            editor_command("vim", path, Some(12)),
+            (
+                "vim".to_string(),
+                vec!["+12".to_string(), "/repo/scripts/deploy.sh".to_string()]
+            )
+        );
+        assert_eq!(
+            editor_command("/usr/bin/nano", path, Some(3)).1,
+            vec!["+3", "/repo/scripts/deploy.sh"]
+        );
+        assert_eq!(
+            editor_command("code --wait", path, Some(12)),
+            (
+                "code".to_string(),
+                vec![
+                    "--wait".to_string(),
+                    "--goto".to_string(),
+                    "/repo/scripts/deploy.sh:12".to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            editor_command("hx", path, Some(7)).1,
+            vec!["/repo/scripts/deploy.sh:7"]
+        );
+        assert_eq!(
+            editor_command("notepad.exe", path, Some(7)).1,
+            vec!["/repo/scripts/deploy.sh"]
+        );
+    }
+
+    #[test]
+    fn test_editor_command_without_line() {
+        let path = Path::new("Makefile");
+        assert_eq!(
+            editor_command("emacs -nw", path, None),
+            (
+                "emacs".to_string(),
+                vec!["-nw".to_string(), "Makefile".to_string()]
+            )
+        );
+        assert_eq!(
+            editor_command("  ", path, None),
+            (FALLBACK_EDITOR.to_string(), vec!["Makefile".to_string()])
+        );
+    }
+}
@@ -0,0 +1,714 @@
+//! # Keybindings
+//!
+//! The single table of keyboard shortcuts used by the TUI.
+//!
+//! ## Overview
+//!
+//! Every shortcut is a [`KeyBinding`] in [`KEYBINDINGS`]: a key, the
+//! [`KeyContext`] it is active in, the [`Action`] it triggers and a short
+//! description. The event loop resolves key presses with [`action_for`] and
+//! the help overlay (`?`) lists the same table through [`help_sections`], so a
+//! new shortcut only has to be added here to be both handled and documented.
+//! The help, info, theme and var file modals resolve keys with the script list
+//! bindings, so remapped keys open, navigate and close them alike.
+//!
+//! Text input (search queries, the palette, env and make variable overrides) and keys forwarded
+//! to a running command are not bindings and are handled by the event loop
+//! directly.
+//!
+//! ## Remapping
+//!
+//! The character keys of the script list can be remapped from the `keys`
+//! object of the user config, keyed by [`Action::config_name`]:
+//!
+//! ```json
+//! { "keys": { "next": "n", "previous": "e", "quit": "Ctrl+q" } }
+//! ```
+//!
+//! [`Keymap::from_config`] replaces the character keys of each listed action
+//! (arrows, Enter and Tab always stay) and rejects unknown actions, unknown
+//! keys and keys bound to two actions. The resulting [`Keymap`] is installed
+//! once at startup with [`set_keymap`]; until then, and when the config is
+//! invalid, the default [`KEYBINDINGS`] apply.
+
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use Action as A;
+use KeyContext::{Confirm, Normal, Output, Search, Terminal};
+
+/// Where a binding is active
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyContext {
+    /// Script list and details pane
+    Normal,
+    /// Typing a search query in the script list
+    Search,
+    /// Reviewing the output of a finished command
+    Output,
+    /// Output pane while a command runs (other keys go to the command)
+    Terminal,
+    /// Confirmation modal before a `@sudo` or destructive command runs
+    Confirm,
+}
+
+impl KeyContext {
+    /// All contexts, in help overlay order
+    pub const ALL: [KeyContext; 5] = [
+        KeyContext::Normal,
+        KeyContext::Search,
+        KeyContext::Output,
+        KeyContext::Terminal,
+        KeyContext::Confirm,
+    ];
+
+    /// Section title in the help overlay
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyContext::Normal => "Script list",
+            KeyContext::Search => "Search",
+            KeyContext::Output => "Output",
+            KeyContext::Terminal => "Running command",
+            KeyContext::Confirm => "Confirmation",
+        }
+    }
+}
+
+/// What a key press does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    ToggleHelp,
+    ToggleInfo,
+    StartSearch,
+    OpenPalette,
+    CancelCommand,
+    JumpToCategory,
+    JumpToLetter,
+    ExpandAll,
+    CollapseAll,
+    CycleTypeFilter,
+    CycleSort,
+    ExplainCommand,
+    DryRun,
+    CopyCommand,
+    CycleEnvProfile,
+    OpenInEditor,
+    Refresh,
+    ToggleWatch,
+    ToggleMark,
+    RunSequence,
+    ToggleExecutionMode,
+    EditEnv,
+    EditMakeVars,
+    SelectVarFile,
+    Rerun,
+    TogglePin,
+    OpenThemePicker,
+    ToggleFocus,
+    Next,
+    Previous,
+    /// `gg`: the first press arms it, the second jumps
+    First,
+    Last,
+    PageDown,
+    PageUp,
+    Collapse,
+    Expand,
+    Activate,
+    /// Leave the current mode (or clear an output search first)
+    Back,
+    DeleteChar,
+    SearchOutput,
+    NextMatch,
+    PreviousMatch,
+    HalfPageDown,
+    HalfPageUp,
+    CopyOutput,
+    SaveOutput,
+    ClearOutput,
+    ToggleWrap,
+    ToggleLineNumbers,
+    ToggleFullscreen,
+}
+
+/// Names of the script list actions in the `keys` config
+#[rustfmt::skip]
+const ACTION_NAMES: &[(&str, Action)] = &[
+    ("next",                  A::Next),
+    ("previous",              A::Previous),
+    ("collapse",              A::Collapse),
+    ("expand",                A::Expand),
+    ("first",                 A::First),
+    ("last",                  A::Last),
+    ("page_down",             A::PageDown),
+    ("page_up",               A::PageUp),
+    ("execute",               A::Activate),
+    ("expand_all",            A::ExpandAll),
+    ("collapse_all",          A::CollapseAll),
+    ("filter",                A::CycleTypeFilter),
+    ("sort",                  A::CycleSort),
+    ("search",                A::StartSearch),
+    ("jump_to_category",      A::JumpToCategory),
+    ("jump_to_letter",        A::JumpToLetter),
+    ("palette",               A::OpenPalette),
+    ("rerun",                 A::Rerun),
+    ("watch",                 A::ToggleWatch),
+    ("mark",                  A::ToggleMark),
+    ("run_sequence",          A::RunSequence),
+    ("pin",                   A::TogglePin),
+    ("cancel",                A::CancelCommand),
+    ("explain",               A::ExplainCommand),
+    ("dry_run",               A::DryRun),
+    ("copy_command",          A::CopyCommand),
+    ("open_in_editor",        A::OpenInEditor),
+    ("refresh",               A::Refresh),
+    ("cycle_env_profile",     A::CycleEnvProfile),
+    ("edit_env",              A::EditEnv),
+    ("edit_make_vars",        A::EditMakeVars),
+    ("var_file",              A::SelectVarFile),
+    ("toggle_execution_mode", A::ToggleExecutionMode),
+    ("theme",                 A::OpenThemePicker),
+    ("switch_pane",           A::ToggleFocus),
+    ("info",                  A::ToggleInfo),
+    ("help",                  A::ToggleHelp),
+    ("quit",                  A::Quit),
+];
+
+impl Action {
+    /// Name of the action in the `keys` config (script list actions only)
+    pub fn config_name(self) -> Option<&'static str> {
+        ACTION_NAMES
+            .iter()
+            .find(|(_, action)| *action == self)
+            .map(|(name, _)| *name)
+    }
+
+    /// The script list action with a `keys` config name
+    pub fn from_config_name(name: &str) -> Option<Action> {
+        ACTION_NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, action)| *action)
+    }
+}
+
+/// A key with the modifiers it needs (Shift is implied by the character)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    const fn plain(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::plain(KeyCode::Char(c))
+    }
+
+    const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /// Whether a key event is this key. Shift is ignored since it is already
+    /// part of the character (`G`, `?`); Ctrl and Alt must match exactly.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        event.code == self.code && event.modifiers & relevant == self.modifiers
+    }
+
+    /// Label shown in the help overlay (`Ctrl+d`, `↑`, `Enter`)
+    pub fn label(&self) -> String {
+        let code = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "\u{2191}".to_string(),
+            KeyCode::Down => "\u{2193}".to_string(),
+            KeyCode::Left => "\u{2190}".to_string(),
+            KeyCode::Right => "\u{2192}".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            other => other.to_string(),
+        };
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            format!("Ctrl+{code}")
+        } else {
+            code
+        }
+    }
+}
+
+/// Parse a key from the config: a character (`n`, `?`), `Ctrl+<char>`, or a
+/// named key (`Enter`, `Tab`, `Esc`, `Space`, `Backspace`, `Up`, `Down`,
+/// `Left`, `Right`, `PageUp`, `PageDown`, `Home`, `End`)
+pub fn parse_key(text: &str) -> Result<Key> {
+    let (ctrl, name) = match text
+        .strip_prefix("Ctrl+")
+        .or_else(|| text.strip_prefix("ctrl+"))
+    {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match name.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "esc" | "escape" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            _ => bail!("Unknown key '{}'", text),
+        },
+    };
+
+    match (ctrl, code) {
+        (false, code) => Ok(Key::plain(code)),
+        (true, KeyCode::Char(c)) if c != ' ' => Ok(Key::ctrl(c.to_ascii_lowercase())),
+        (true, _) => bail!("Ctrl can only be combined with a character: '{}'", text),
+    }
+}
+
+/// One keyboard shortcut
+#[derive(Debug, Clone, Copy)]
+pub struct KeyBinding {
+    pub context: KeyContext,
+    pub key: Key,
+    pub action: Action,
+    pub description: &'static str,
+}
+
+const fn bind(
+    context: KeyContext,
+    key: Key,
+    action: Action,
+    description: &'static str,
+) -> KeyBinding {
+    KeyBinding {
+        context,
+        key,
+        action,
+        description,
+    }
+}
+
+/// Every keyboard shortcut. Bindings for the same action in a context are
+/// listed together and share a description, so the help overlay can merge them.
+#[rustfmt::skip]
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    // --- Script list ---
+    bind(Normal, Key::plain(KeyCode::Down),     A::Next,                "Next item"),
+    bind(Normal, Key::char('j'),                A::Next,                "Next item"),
+    bind(Normal, Key::plain(KeyCode::Up),       A::Previous,            "Previous item"),
+    bind(Normal, Key::char('k'),                A::Previous,            "Previous item"),
+    bind(Normal, Key::plain(KeyCode::Left),     A::Collapse,            "Collapse category"),
+    bind(Normal, Key::char('h'),                A::Collapse,            "Collapse category"),
+    bind(Normal, Key::plain(KeyCode::Right),    A::Expand,              "Expand category"),
+    bind(Normal, Key::char('l'),                A::Expand,              "Expand category"),
+    bind(Normal, Key::char('g'),                A::First,               "First item (press twice)"),
+    bind(Normal, Key::char('G'),                A::Last,                "Last item"),
+    bind(Normal, Key::plain(KeyCode::PageDown), A::PageDown,            "Page down"),
+    bind(Normal, Key::plain(KeyCode::PageUp),   A::PageUp,              "Page up"),
+    bind(Normal, Key::plain(KeyCode::Enter),    A::Activate,            "Toggle category / run command"),
+    bind(Normal, Key::char('E'),                A::ExpandAll,           "Expand all categories"),
+    bind(Normal, Key::char('C'),                A::CollapseAll,         "Collapse all categories"),
+    bind(Normal, Key::char('f'),                A::CycleTypeFilter,     "Filter by script type"),
+    bind(Normal, Key::char('s'),                A::CycleSort,           "Cycle sort order"),
+    bind(Normal, Key::char('/'),                A::StartSearch,         "Search commands"),
+    bind(Normal, Key::char('c'),                A::JumpToCategory,      "Jump to category"),
+    bind(Normal, Key::char('\''),               A::JumpToLetter,        "Jump to next category starting with a letter"),
+    bind(Normal, Key::char(':'),                A::OpenPalette,         "Command palette"),
+    bind(Normal, Key::ctrl('p'),                A::OpenPalette,         "Command palette"),
+    bind(Normal, Key::char('r'),                A::Rerun,               "Re-run last command"),
+    bind(Normal, Key::char('w'),                A::ToggleWatch,         "Run and watch (re-run on change)"),
+    bind(Normal, Key::char(' '),                A::ToggleMark,          "Mark command for a sequence"),
+    bind(Normal, Key::char('S'),                A::RunSequence,         "Run marked commands in order"),
+    bind(Normal, Key::char('p'),                A::TogglePin,           "Pin / unpin command"),
+    bind(Normal, Key::char('x'),                A::CancelCommand,       "Cancel running command"),
+    bind(Normal, Key::char('X'),                A::ExplainCommand,      "Explain what a command runs"),
+    bind(Normal, Key::char('P'),                A::DryRun,              "Dry run (preview) command"),
+    bind(Normal, Key::char('Y'),                A::CopyCommand,         "Copy command line"),
+    bind(Normal, Key::char('o'),                A::OpenInEditor,        "Open script in $EDITOR"),
+    bind(Normal, Key::char('R'),                A::Refresh,             "Re-discover scripts"),
+    bind(Normal, Key::ctrl('e'),                A::CycleEnvProfile,     "Cycle env profile"),
+    bind(Normal, Key::ctrl('c'),                A::CancelCommand,       "Cancel running command"),
+    bind(Normal, Key::char('e'),                A::EditEnv,             "Environment overrides"),
+    bind(Normal, Key::char('v'),                A::EditMakeVars,        "Make variable overrides"),
+    bind(Normal, Key::char('F'),                A::SelectVarFile,       "Terraform var file"),
+    bind(Normal, Key::char('m'),                A::ToggleExecutionMode, "Toggle inline / full-screen runs"),
+    bind(Normal, Key::char('t'),                A::OpenThemePicker,     "Theme picker"),
+    bind(Normal, Key::plain(KeyCode::Tab),      A::ToggleFocus,         "Switch pane"),
+    bind(Normal, Key::char('i'),                A::ToggleInfo,          "About Jarvis"),
+    bind(Normal, Key::char('?'),                A::ToggleHelp,          "This help"),
+    bind(Normal, Key::char('q'),                A::Quit,                "Quit"),
+    bind(Normal, Key::char('Q'),                A::Quit,                "Quit"),
+    // --- Search ---
+    bind(Search, Key::plain(KeyCode::Down),      A::Next,       "Next match"),
+    bind(Search, Key::plain(KeyCode::Up),        A::Previous,   "Previous match"),
+    bind(Search, Key::plain(KeyCode::Enter),     A::Activate,   "Run selected command"),
+    bind(Search, Key::plain(KeyCode::Backspace), A::DeleteChar, "Delete character"),
+    bind(Search, Key::plain(KeyCode::Esc),       A::Back,       "Exit search"),
+    // --- Output review ---
+    bind(Output, Key::char('j'),             A::Next,          "Scroll down"),
+    bind(Output, Key::plain(KeyCode::Down),  A::Next,          "Scroll down"),
+    bind(Output, Key::char('k'),             A::Previous,      "Scroll up"),
+    bind(Output, Key::plain(KeyCode::Up),    A::Previous,      "Scroll up"),
+    bind(Output, Key::ctrl('d'),             A::HalfPageDown,  "Half page down"),
+    bind(Output, Key::ctrl('u'),             A::HalfPageUp,    "Half page up"),
+    bind(Output, Key::char('g'),             A::First,         "Top (press twice)"),
+    bind(Output, Key::char('G'),             A::Last,          "Bottom"),
+    bind(Output, Key::char('/'),             A::SearchOutput,  "Search output"),
+    bind(Output, Key::char('n'),             A::NextMatch,     "Next match"),
+    bind(Output, Key::char('N'),             A::PreviousMatch, "Previous match"),
+    bind(Output, Key::char('y'),             A::CopyOutput,    "Copy all output"),
+    bind(Output, Key::char('s'),             A::SaveOutput,    "Save output to a file"),
+    bind(Output, Key::char('d'),             A::ClearOutput,   "Clear output"),
+    bind(Output, Key::char('w'),             A::ToggleWrap,    "Toggle line wrap"),
+    bind(Output, Key::char('l'),             A::ToggleLineNumbers, "Toggle line numbers"),
+    bind(Output, Key::char('z'),             A::ToggleFullscreen, "Toggle fullscreen output"),
+    bind(Output, Key::plain(KeyCode::Tab),   A::ToggleFocus,   "Switch pane"),
+    bind(Output, Key::char('i'),             A::ToggleInfo,    "About Jarvis"),
+    bind(Output, Key::char('?'),             A::ToggleHelp,    "This help"),
+    bind(Output, Key::plain(KeyCode::Esc),   A::Back,          "Clear search / back to list"),
+    bind(Output, Key::char('q'),             A::Back,          "Clear search / back to list"),
+    // --- Running command ---
+    bind(Terminal, Key::plain(KeyCode::Esc), A::Back,        "Back to list (other keys go to the command)"),
+    bind(Terminal, Key::plain(KeyCode::Tab), A::ToggleFocus, "Switch pane"),
+    // --- Confirmation ---
+    bind(Confirm, Key::char('y'),             A::Activate, "Run the command"),
+    bind(Confirm, Key::char('Y'),             A::Activate, "Run the command"),
+    bind(Confirm, Key::plain(KeyCode::Enter), A::Activate, "Run the command"),
+    bind(Confirm, Key::char('n'),             A::Back,     "Cancel"),
+    bind(Confirm, Key::char('N'),             A::Back,     "Cancel"),
+    bind(Confirm, Key::plain(KeyCode::Esc),   A::Back,     "Cancel"),
+];
+
+/// The keybindings in effect: the defaults with the user's remaps applied
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<KeyBinding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: KEYBINDINGS.to_vec(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Apply the `keys` config (action name -> space separated keys) to the
+    /// default bindings.
+    ///
+    /// Each listed action loses its default character keys and gets the
+    /// configured ones; its arrow, Enter and Tab keys are kept. Fails on an
+    /// unknown action or key, and when a key ends up bound to two actions.
+    pub fn from_config(keys: &BTreeMap<String, String>) -> Result<Self> {
+        let mut bindings = KEYBINDINGS.to_vec();
+
+        for (name, value) in keys {
+            let Some(action) = Action::from_config_name(name) else {
+                bail!("Unknown action '{}' in keys", name);
+            };
+            let new_keys = value
+                .split_whitespace()
+                .map(parse_key)
+                .collect::<Result<Vec<Key>>>()?;
+            if new_keys.is_empty() {
+                bail!("No key given for '{}'", name);
+            }
+
+            let is_target = |b: &KeyBinding| b.context == Normal && b.action == action;
+            let Some(at) = bindings.iter().position(is_target) else {
+                continue;
+            };
+            let description = bindings[at].description;
+            let mut replacement: Vec<KeyBinding> = bindings
+                .iter()
+                .filter(|b| is_target(b) && !matches!(b.key.code, KeyCode::Char(_)))
+                .copied()
+                .collect();
+            for key in new_keys {
+                if !replacement.iter().any(|b| b.key == key) {
+                    replacement.push(bind(Normal, key, action, description));
+                }
+            }
+            bindings.retain(|b| !is_target(b));
+            bindings.splice(at..at, replacement);
+        }
+
+        let keymap = Self { bindings };
+        if let Some((key, first, second)) = keymap.conflicts().into_iter().next() {
+            bail!(
+                "Key '{}' is bound to both {} and {}",
+                key.label(),
+                first.config_name().unwrap_or("another action"),
+                second.config_name().unwrap_or("another action")
+            );
+        }
+        Ok(keymap)
+    }
+
+    /// Keys bound to two different actions in the same context, with both
+    /// actions
+    pub fn conflicts(&self) -> Vec<(Key, Action, Action)> {
+        let mut conflicts = Vec::new();
+        for (i, a) in self.bindings.iter().enumerate() {
+            for b in &self.bindings[i + 1..] {
+                if a.context == b.context && a.key == b.key && a.action != b.action {
+                    conflicts.push((a.key, a.action, b.action));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// The action bound to a key press in `context`, if any
+    pub fn action_for(&self, context: KeyContext, event: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|b| b.context == context && b.key.matches(event))
+            .map(|b| b.action)
+    }
+
+    /// The first character key bound to an action in `context`, for hints
+    pub fn char_key(&self, context: KeyContext, action: Action) -> Option<String> {
+        self.bindings
+            .iter()
+            .find(|b| {
+                b.context == context && b.action == action && matches!(b.key.code, KeyCode::Char(_))
+            })
+            .map(|b| b.key.label())
+    }
+
+    /// Help overlay rows per context: the keys for each action joined with
+    /// `/`, and the action's description, in table order
+    pub fn help_sections(&self) -> Vec<(KeyContext, Vec<(String, &'static str)>)> {
+        KeyContext::ALL
+            .iter()
+            .map(|&context| {
+                let mut rows: Vec<(Action, String, &'static str)> = Vec::new();
+                for binding in self.bindings.iter().filter(|b| b.context == context) {
+                    let label = binding.key.label();
+                    match rows
+                        .iter_mut()
+                        .find(|(action, _, _)| *action == binding.action)
+                    {
+                        Some((_, keys, _)) => {
+                            keys.push('/');
+                            keys.push_str(&label);
+                        }
+                        None => rows.push((binding.action, label, binding.description)),
+                    }
+                }
+                let rows = rows
+                    .into_iter()
+                    .map(|(_, keys, description)| (keys, description))
+                    .collect();
+                (context, rows)
+            })
+            .collect()
+    }
+}
+
+/// The keymap installed for this process
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Install the keymap for this process. Returns `false` if one was already
+/// installed.
+pub fn set_keymap(keymap: Keymap) -> bool {
+    KEYMAP.set(keymap).is_ok()
+}
+
+/// The installed keymap (the defaults when none was installed)
+pub fn keymap() -> &'static Keymap {
+    KEYMAP.get_or_init(Keymap::default)
+}
+
+/// The action bound to a key press in `context`, if any
+pub fn action_for(context: KeyContext, event: &KeyEvent) -> Option<Action> {
+    keymap().action_for(context, event)
+}
+
+/// Help overlay rows per context, from the installed keymap
+pub fn help_sections() -> Vec<(KeyContext, Vec<(String, &'static str)>)> {
+    keymap().help_sections()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_no_duplicate_keys_within_a_context() {
+        for (i, a) in KEYBINDINGS.iter().enumerate() {
+            for b in &KEYBINDINGS[i + 1..] {
+                assert!(
+                    !(a.context == b.context && a.key == b.key),
+                    "{:?} is bound twice in {:?} ({:?} and {:?})",
+                    a.key,
+                    a.context,
+                    a.action,
+                    b.action
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_action_for_respects_context_and_modifiers() {
+        let c = event(KeyCode::Char('c'), KeyModifiers::NONE);
+        let ctrl_c = event(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(action_for(Normal, &c), Some(A::JumpToCategory));
+        assert_eq!(action_for(Normal, &ctrl_c), Some(A::CancelCommand));
+
+        let g = event(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(action_for(Normal, &g), Some(A::Last));
+        assert_eq!(action_for(Output, &g), Some(A::Last));
+
+        let q = event(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(action_for(Output, &q), Some(A::Back));
+        assert_eq!(action_for(Search, &q), None);
+        assert_eq!(action_for(Terminal, &q), None);
+
+        let y = event(KeyCode::Char('y'), KeyModifiers::NONE);
+        let esc = event(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(action_for(Confirm, &y), Some(A::Activate));
+        assert_eq!(action_for(Confirm, &esc), Some(A::Back));
+        assert_eq!(action_for(Confirm, &q), None);
+    }
+
+    #[test]
+    fn test_key_labels() {
+        assert_eq!(Key::ctrl('d').label(), "Ctrl+d");
+        assert_eq!(Key::plain(KeyCode::Down).label(), "\u{2193}");
+        assert_eq!(Key::plain(KeyCode::Enter).label(), "Enter");
+        assert_eq!(Key::plain(KeyCode::Esc).label(), "Esc");
+    }
+
+    #[test]
+    fn test_help_sections_merge_keys_per_action() {
+        let sections = help_sections();
+        assert_eq!(sections.len(), KeyContext::ALL.len());
+
+        let (context, rows) = &sections[0];
+        assert_eq!(*context, Normal);
+        assert_eq!(rows[0], ("\u{2193}/j".to_string(), "Next item"));
+        assert!(rows.contains(&(":/Ctrl+p".to_string(), "Command palette")));
+        assert!(rows.contains(&("?".to_string(), "This help")));
+    }
+
+    fn keys(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, key)| ((*name).to_string(), (*key).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_script_list_action_has_a_config_name() {
+        for binding in KEYBINDINGS.iter().filter(|b| b.context == Normal) {
+            let name = binding.action.config_name();
+            assert!(name.is_some(), "{:?} has no config name", binding.action);
+            assert_eq!(
+                Action::from_config_name(name.unwrap()),
+                Some(binding.action)
+            );
+        }
+        assert_eq!(Action::from_config_name("execute"), Some(A::Activate));
+        assert_eq!(Action::from_config_name("launch"), None);
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("n").unwrap(), Key::char('n'));
+        assert_eq!(parse_key("?").unwrap(), Key::char('?'));
+        assert_eq!(parse_key("Ctrl+N").unwrap(), Key::ctrl('n'));
+        assert_eq!(parse_key("ctrl+x").unwrap(), Key::ctrl('x'));
+        assert_eq!(parse_key("Enter").unwrap(), Key::plain(KeyCode::Enter));
+        assert_eq!(parse_key("space").unwrap(), Key::char(' '));
+        assert_eq!(parse_key("PgDn").unwrap(), Key::plain(KeyCode::PageDown));
+        assert!(parse_key("").is_err());
+        assert!(parse_key("Hyper").is_err());
+        assert!(parse_key("Ctrl+Enter").is_err());
+    }
+
+    #[test]
+    fn test_keymap_default_matches_keybindings() {
+        let keymap = Keymap::from_config(&BTreeMap::new()).unwrap();
+        assert_eq!(keymap.help_sections(), Keymap::default().help_sections());
+        assert!(keymap.conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_keymap_remap_replaces_character_keys() {
+        let keymap = Keymap::from_config(&keys(&[
+            ("next", "n"),
+            ("previous", "e"),
+            ("edit_env", "Ctrl+v"),
+            ("quit", "Q Ctrl+q"),
+        ]))
+        .unwrap();
+
+        let press = |c: char| event(KeyCode::Char(c), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(Normal, &press('n')), Some(A::Next));
+        assert_eq!(keymap.action_for(Normal, &press('e')), Some(A::Previous));
+        assert_eq!(keymap.action_for(Normal, &press('j')), None);
+        assert_eq!(keymap.action_for(Normal, &press('q')), None);
+        let ctrl_q = event(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action_for(Normal, &ctrl_q), Some(A::Quit));
+        // Arrows stay, and other contexts keep their defaults
+        let down = event(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(Normal, &down), Some(A::Next));
+        assert_eq!(keymap.action_for(Output, &press('j')), Some(A::Next));
+
+        let (_, rows) = &keymap.help_sections()[0];
+        assert_eq!(rows[0], ("\u{2193}/n".to_string(), "Next item"));
+        assert_eq!(keymap.char_key(Normal, A::Quit), Some("Q".to_string()));
+    }
+
+    #[test]
+    fn test_keymap_rejects_conflicts() {
+        // `p` already pins a command
+        let err = Keymap::from_config(&keys(&[("next", "p")])).unwrap_err();
+        assert_eq!(err.to_string(), "Key 'p' is bound to both next and pin");
+
+        // Moving the other binding away resolves it
+        assert!(Keymap::from_config(&keys(&[("next", "p"), ("pin", "b")])).is_ok());
+
+        // Arrows are kept, so they conflict too
+        assert!(Keymap::from_config(&keys(&[("previous", "Down")])).is_err());
+    }
+
+    #[test]
+    fn test_keymap_rejects_unknown_actions_and_keys() {
+        let err = Keymap::from_config(&keys(&[("launch", "l")])).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown action 'launch' in keys");
+        assert!(Keymap::from_config(&keys(&[("next", "Hyper")])).is_err());
+        assert!(Keymap::from_config(&keys(&[("next", " ")])).is_err());
+    }
+}
@@ -5,9 +5,16 @@
 //! ## Components
 //!
 //! - [`App`] - Application state management (selection, focus, search, etc.)
+//! - [`mod@dotenv`] - Optional `.env` loading for command environments
+//! - [`mod@editor`] - Opening script files in `$EDITOR`
 //! - [`mod@render`] - Rendering functions for drawing the TUI
+//! - [`mod@sequence`] - Running marked commands one after another
+//! - [`mod@notify`] - Opt-in bell / desktop notification when a command finishes
+//! - [`mod@keymap`] - The keybinding table shared by the event loop and help overlay
+//! - [`mod@project_config`] - Per-project settings from `jarvis.toml`
 //! - [`mod@pty_runner`] - PTY-based command execution for inline terminal
 //! - [`mod@terminal_widget`] - Embedded terminal widget for rendering PTY output
+//! - [`mod@watch`] - Re-running a command when project files change
 //!
 //! ## Layout
 //!
@@ -42,10 +49,17 @@
 
 pub mod app;
 pub mod config;
+pub mod dotenv;
+pub mod editor;
+pub mod keymap;
+pub mod notify;
+pub mod project_config;
 pub mod pty_runner;
 pub mod render;
+pub mod sequence;
 pub mod terminal_widget;
 pub mod theme;
+pub mod watch;
 
 pub use app::App;
 pub use render::render;
@@ -0,0 +1,234 @@
+//! # Completion Alerts
+//!
+//! Optional alerts when an inline command finishes, so a long build or
+//! deploy can run while you work in another window.
+//!
+//! ## Overview
+//!
+//! [`CompletionDetector`] watches the status of the inline command on every
+//! tick of the event loop and reports the `Running -> Succeeded/Failed`
+//! transition exactly once per run. [`FinishAlerts`] then rings the terminal
+//! bell and/or shows a desktop notification with the command's name and
+//! result, for runs that took at least [`FinishAlerts::min_duration`].
+//!
+//! Both alerts are opt-in through the user config (`bell_on_finish`,
+//! `notify_on_finish`, `notify_after_secs`). Desktop notifications use
+//! `notify-send` on Linux and `osascript` on macOS; when neither is
+//! available the notification is silently skipped.
+
+use crate::ui::pty_runner::ExecutionStatus;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Spots the moment the inline command finishes between two ticks
+#[derive(Debug, Default)]
+pub struct CompletionDetector {
+    was_running: bool,
+}
+
+impl CompletionDetector {
+    /// Feed the status of the inline command (`None` when there is none).
+    /// Returns the final status on the tick it goes from `Running` to
+    /// `Succeeded` or `Failed`, and `None` otherwise.
+    pub fn update(&mut self, status: Option<ExecutionStatus>) -> Option<ExecutionStatus> {
+        let was_running = std::mem::replace(
+            &mut self.was_running,
+            status == Some(ExecutionStatus::Running),
+        );
+        status.filter(|status| {
+            was_running && matches!(status, ExecutionStatus::Succeeded | ExecutionStatus::Failed)
+        })
+    }
+}
+
+/// Which alerts fire when a command finishes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FinishAlerts {
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Show a desktop notification
+    pub desktop: bool,
+    /// Only alert for runs that took at least this long
+    pub min_duration: Duration,
+}
+
+impl FinishAlerts {
+    /// Whether a run that took `elapsed` should alert
+    pub fn should_alert(&self, elapsed: Duration) -> bool {
+        (self.bell || self.desktop) && elapsed >= self.min_duration
+    }
+
+    /// Alert that `name` finished with `status` after `elapsed`, if enabled.
+    /// Failures to ring or notify are ignored.
+    pub fn alert(&self, name: &str, status: ExecutionStatus, elapsed: Duration) {
+        if !self.should_alert(elapsed) {
+            return;
+        }
+        if self.bell {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+        }
+        if self.desktop {
+            let (title, body) = notification_text(name, status, elapsed);
+            if let Some((program, args)) = desktop_notification_command(&title, &body) {
+                spawn_detached(&program, &args);
+            }
+        }
+    }
+}
+
+/// Title and body of the notification for a finished command
+pub fn notification_text(
+    name: &str,
+    status: ExecutionStatus,
+    elapsed: Duration,
+) -> (String, String) {
+    let outcome = if status == ExecutionStatus::Succeeded {
+        "succeeded"
+    } else {
+        "failed"
+    };
+    let secs = elapsed.as_secs();
+    (
+        format!("{} {}", name, outcome),
+        format!("Finished in {:02}:{:02}", secs / 60, secs % 60),
+    )
+}
+
+/// The platform's command for showing a desktop notification (`None` where
+/// there is no known one)
+pub fn desktop_notification_command(title: &str, body: &str) -> Option<(String, Vec<String>)> {
+    if cfg!(target_os = "macos") {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        Some((
+            "osascript".to_string(),
+            vec![
+                "-e".to_string(),
+                format!(
+                    "display notification {} with title \"Jarvis\" subtitle {}",
+                    quote(body),
+                    quote(title)
+                ),
+            ],
+        ))
+    } else if cfg!(unix) {
+        Some((
+            "notify-send".to_string(),
+            vec![
+                "--app-name=Jarvis".to_string(),
+                title.to_string(),
+                body.to_string(),
+            ],
+        ))
+    } else {
+        None
+    }
+}
+
+/// Start a program without waiting for it, reaping it in the background
+fn spawn_detached(program: &str, args: &[String]) {
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        std::thread::spawn(move || child.wait());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_detector_fires_once_per_run() {
+        use ExecutionStatus::{Failed, Running, Succeeded};
+        let mut detector = CompletionDetector::default();
+
+        let ticks = [
+            None,
+            Some(Running),
+            Some(Running),
+            Some(Succeeded),
+            Some(Succeeded),
+            None,
+            Some(Running),
+            Some(Failed),
+            None,
+        ];
+        let fired: Vec<Option<ExecutionStatus>> = ticks
+            .iter()
+            .map(|status| detector.update(*status))
+            .collect();
+
+        assert_eq!(
+            fired,
+            vec![
+                None,
+                None,
+                None,
+                Some(Succeeded),
+                None,
+                None,
+                None,
+                Some(Failed),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_completion_detector_ignores_runs_it_never_saw_running() {
+        let mut detector = CompletionDetector::default();
+        assert_eq!(detector.update(Some(ExecutionStatus::Succeeded)), None);
+        assert_eq!(detector.update(Some(ExecutionStatus::Idle)), None);
+    }
+
+    #[test]
+    fn test_should_alert() {
+        let alerts = FinishAlerts {
+            bell: true,
+            desktop: false,
+            min_duration: Duration::from_secs(10),
+        };
+        assert!(!alerts.should_alert(Duration::from_secs(9)));
+        assert!(alerts.should_alert(Duration::from_secs(10)));
+        assert!(!FinishAlerts::default().should_alert(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_notification_text() {
+        assert_eq!(
+            notification_text(
+                "Deploy",
+                ExecutionStatus::Succeeded,
+                Duration::from_secs(75)
+            ),
+            (
+                "Deploy succeeded".to_string(),
+                "Finished in 01:15".to_string()
+            )
+        );
+        assert_eq!(
+            notification_text("Build", ExecutionStatus::Failed, Duration::from_secs(3)).0,
+            "Build failed"
+        );
+    }
+
+    #[test]
+    fn test_desktop_notification_command() {
+        let command = desktop_notification_command("Say \"hi\" succeeded", "Finished in 00:12");
+        if cfg!(target_os = "macos") {
+            let (program, args) = command.unwrap();
+            assert_eq!(program, "osascript");
+            assert!(args[1].contains("subtitle \"Say \\\"hi\\\" succeeded\""));
+        } else if cfg!(unix) {
+            let (program, args) = command.unwrap();
+            assert_eq!(program, "notify-send");
+            assert_eq!(&args[1..], ["Say \"hi\" succeeded", "Finished in 00:12"]);
+        }
+    }
+}
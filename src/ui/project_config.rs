@@ -0,0 +1,330 @@
+//! # Project Configuration
+//!
+//! Loads optional per-project settings from a `jarvis.toml` (or `.jarvis.toml`)
+//! file in the project root.
+//!
+//! ## Overview
+//!
+//! Unlike [`crate::ui::config::Config`], which stores personal preferences in
+//! the user's config directory, the project config is meant to be committed
+//! alongside the scripts so a whole team sees the same command menu.
+//!
+//! ## Example
+//!
+//! ```toml
+//! # Project name shown in the header (defaults to the directory name)
+//! name = "Billing API"
+//!
+//! # Categories listed here come first, in this order.
+//! # Unlisted categories follow alphabetically.
+//! category_order = ["Deploy", "Build", "Test"]
+//!
+//! # Categories left out of the tree
+//! hidden_categories = ["internal"]
+//!
+//! # Files and directories skipped during discovery (glob patterns)
+//! exclude = ["vendor", "tools/generated/**"]
+//!
+//! # Ask for confirmation before running matching commands
+//! danger_pattern = "^(deploy|release)"
+//!
+//! # Use tools installed outside of PATH
+//! [binaries]
+//! task = "/opt/tools/bin/task"
+//!
+//! # Display names for categories
+//! [category_names]
+//! deploy = "🚀 Deployment"
+//!
+//! # Environment profiles, cycled at runtime
+//! [profiles.staging]
+//! env = { API_URL = "https://staging.example.com" }
+//! ```
+//!
+//! Entries match a category by its key or its display name, ignoring case and
+//! any leading emoji. Special categories such as "Frequently Used" always stay
+//! at the top of the list and are not affected by `category_order` or
+//! `hidden_categories`.
+
+use crate::script::ExcludeSet;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File names checked (in order) for project configuration
+pub const PROJECT_CONFIG_NAMES: &[&str] = &["jarvis.toml", ".jarvis.toml"];
+
+/// Per-project configuration read from `jarvis.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Project name shown in the header instead of the directory name
+    pub name: Option<String>,
+    /// Explicit category order; unlisted categories follow alphabetically.
+    pub category_order: Vec<String>,
+    /// Categories hidden from the tree
+    pub hidden_categories: Vec<String>,
+    /// Glob patterns of files and directories skipped during discovery
+    pub exclude: Vec<String>,
+    /// Display name overrides keyed by category
+    pub category_names: HashMap<String, String>,
+    /// Commands whose name matches this regex ask for confirmation before running
+    pub danger_pattern: Option<String>,
+    /// Program path overrides keyed by tool name (e.g. `task`, `make`, `terraform`)
+    pub binaries: HashMap<String, String>,
+    /// Named environment profiles (e.g. `dev`, `staging`), one of which can
+    /// be made active at runtime
+    pub profiles: BTreeMap<String, EnvProfile>,
+}
+
+/// A named set of environment variables applied to every command while the
+/// profile is active
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnvProfile {
+    pub env: BTreeMap<String, String>,
+}
+
+impl ProjectConfig {
+    /// Load the project config from `project_dir`. Returns `Ok(None)` when no
+    /// config file exists.
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        match Self::find(project_dir) {
+            Some(path) => Self::load_from(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Find the first existing project config file in `project_dir`.
+    pub fn find(project_dir: &Path) -> Option<PathBuf> {
+        PROJECT_CONFIG_NAMES
+            .iter()
+            .map(|name| project_dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// Load the project config from a specific file.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read project config: {}", path.display()))?;
+        let config: Self = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse project config: {}", path.display()))?;
+        config
+            .danger_regex()
+            .with_context(|| format!("Invalid danger_pattern in: {}", path.display()))?;
+        ExcludeSet::new(&config.exclude)
+            .with_context(|| format!("Invalid exclude pattern in: {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Compile [`Self::danger_pattern`], if set.
+    pub fn danger_regex(&self) -> Result<Option<Regex>> {
+        self.danger_pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+/// Normalize a category name for matching against `category_order` entries:
+/// lowercase, with any leading non-alphanumeric characters (emoji, spaces) removed.
+pub fn normalize_category_name(name: &str) -> String {
+    name.trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_file_returns_none() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(ProjectConfig::load(temp_dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_name() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(".jarvis.toml"),
+            "name = \"Billing API\"\n",
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(config.name.as_deref(), Some("Billing API"));
+    }
+
+    #[test]
+    fn test_load_category_order() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("jarvis.toml"),
+            "category_order = [\"Deploy\", \"Build\"]\n",
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(config.category_order, vec!["Deploy", "Build"]);
+    }
+
+    #[test]
+    fn test_load_hidden_and_renamed_categories() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(".jarvis.toml"),
+            "hidden_categories = [\"internal\", \"🧪 Scratch\"]\n\n[category_names]\ndeploy = \"🚀 Deployment\"\n\"my-app\" = \"App\"\n",
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(config.hidden_categories, vec!["internal", "🧪 Scratch"]);
+        assert_eq!(
+            config.category_names.get("deploy").map(String::as_str),
+            Some("🚀 Deployment")
+        );
+        assert_eq!(
+            config.category_names.get("my-app").map(String::as_str),
+            Some("App")
+        );
+    }
+
+    #[test]
+    fn test_load_binaries() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("jarvis.toml"),
+            "[binaries]\ntask = \"/opt/tools/bin/task\"\n",
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(
+            config.binaries.get("task").map(String::as_str),
+            Some("/opt/tools/bin/task")
+        );
+    }
+
+    #[test]
+    fn test_load_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("jarvis.toml"),
+            r#"
+[profiles.staging]
+env = { API_URL = "https://staging.example.com", LOG_LEVEL = "info" }
+
+[profiles.dev.env]
+API_URL = "http://localhost:8080"
+
+[profiles.empty]
+"#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        let names: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["dev", "empty", "staging"]);
+        assert_eq!(
+            config.profiles["staging"].env,
+            BTreeMap::from([
+                (
+                    "API_URL".to_string(),
+                    "https://staging.example.com".to_string()
+                ),
+                ("LOG_LEVEL".to_string(), "info".to_string()),
+            ])
+        );
+        assert_eq!(
+            config.profiles["dev"].env["API_URL"],
+            "http://localhost:8080"
+        );
+        assert!(config.profiles["empty"].env.is_empty());
+
+        // Profiles only hold `env`
+        fs::write(
+            temp_dir.path().join("jarvis.toml"),
+            "[profiles.prod]\nenvironment = { A = \"1\" }\n",
+        )
+        .unwrap();
+        assert!(ProjectConfig::load(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_load_hidden_file_name() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".jarvis.toml"), "").unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap();
+        assert_eq!(config, Some(ProjectConfig::default()));
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("jarvis.toml"), "bogus = 1\n").unwrap();
+
+        assert!(ProjectConfig::load(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_load_danger_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("jarvis.toml"),
+            "danger_pattern = \"^deploy\"\n",
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        let regex = config.danger_regex().unwrap().unwrap();
+        assert!(regex.is_match("deploy-prod"));
+        assert!(!regex.is_match("build"));
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_danger_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("jarvis.toml"),
+            "danger_pattern = \"(\"\n",
+        )
+        .unwrap();
+
+        assert!(ProjectConfig::load(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_load_exclude() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(".jarvis.toml"),
+            "exclude = [\"vendor\", \"tools/generated/**\"]\n",
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(config.exclude, vec!["vendor", "tools/generated/**"]);
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_exclude_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("jarvis.toml"), "exclude = [\"[a-\"]\n").unwrap();
+
+        assert!(ProjectConfig::load(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_normalize_category_name() {
+        assert_eq!(normalize_category_name("🔷 Web App"), "web app");
+        assert_eq!(normalize_category_name("Deploy"), "deploy");
+    }
+}
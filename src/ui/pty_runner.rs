@@ -10,14 +10,48 @@
 //! - Uses `portable-pty` to spawn commands in a pseudo-terminal
 //! - Uses `vt100` to parse ANSI escape sequences and maintain terminal state
 //! - Output is read from the PTY in a background thread and fed to the vt100 parser
+//!   as raw bytes, so `\r` progress bars and CRLF endings render like a real terminal
 //! - The main event loop polls for new output and renders the virtual terminal
+//! - [`PtyHandle::kill`] cancels a running command: Ctrl-C (SIGINT) through the
+//!   PTY first, then SIGKILL after [`KILL_GRACE_PERIOD`]
 
 use crate::script::{self, ScriptFile, ScriptFunction, ScriptType};
 use anyhow::{Context, Result};
-use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use portable_pty::{ChildKiller, CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use std::collections::BTreeMap;
 use std::io::{Read, Write};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Directory every command runs in instead of its script's (`--cwd`)
+static WORKING_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Run every command of this process in `dir` instead of its script's
+/// directory. Returns `false` if an override was already installed.
+pub fn set_working_dir_override(dir: PathBuf) -> bool {
+    WORKING_DIR_OVERRIDE.set(dir).is_ok()
+}
+
+/// How long a running command may go without output before we hint that it
+/// might be waiting for input
+pub const INPUT_WAIT_THRESHOLD: Duration = Duration::from_secs(3);
+
+/// Exit code reported for commands cancelled from the TUI (128 + SIGINT)
+pub const CANCELLED_EXIT_CODE: i32 = 130;
+
+/// Exit code reported for commands killed by a timeout (as with coreutils `timeout`)
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// How often the waiter thread checks a command that has a timeout
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a cancelled command gets to exit after SIGINT before it is killed
+pub const KILL_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Lines of scrollback kept for each command's output
+pub const SCROLLBACK_LINES: usize = 10000;
 
 /// Status of a command execution
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -42,11 +76,77 @@ pub struct ExecutionState {
     pub finished_at: Option<Instant>,
     pub display_name: String,
     pub category: String,
+    /// Whether the output view sticks to the bottom as new output arrives;
+    /// cleared when the user scrolls up, set again at the bottom
+    pub auto_follow: bool,
+}
+
+impl ExecutionState {
+    /// Build the state of a command that already finished outside the PTY
+    /// (e.g. in full-screen mode), with `summary` as the terminal contents.
+    pub fn finished(
+        display_name: &str,
+        category: &str,
+        exit_code: i32,
+        started_at: Instant,
+        summary: &str,
+    ) -> Self {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        parser.process(summary.replace('\n', "\r\n").as_bytes());
+        Self {
+            status: if exit_code == 0 {
+                ExecutionStatus::Succeeded
+            } else {
+                ExecutionStatus::Failed
+            },
+            parser: Arc::new(Mutex::new(parser)),
+            exit_code: Some(exit_code),
+            started_at,
+            finished_at: Some(Instant::now()),
+            display_name: display_name.to_string(),
+            category: category.to_string(),
+            auto_follow: true,
+        }
+    }
+}
+
+/// Clear a finished execution's output: give it a new, empty terminal of
+/// the same size and set it back to `Idle`
+pub fn reset_execution(state: &mut ExecutionState) {
+    let (rows, cols) = state
+        .parser
+        .lock()
+        .map_or((24, 80), |parser| parser.screen().size());
+    state.parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK_LINES)));
+    state.status = ExecutionStatus::Idle;
+    state.exit_code = None;
+    state.finished_at = None;
+    state.auto_follow = true;
+}
+
+/// Environment variables set for a single execution, on top of the inherited environment
+pub type EnvOverrides = BTreeMap<String, String>;
+
+/// What was entered for a single run on top of the command itself
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Environment variables applied on top of the inherited environment
+    pub env: EnvOverrides,
+    /// `VAR=value` overrides appended to `make` invocations
+    pub make_vars: EnvOverrides,
+    /// Run the tool's dry-run variant (see [`dry_run_command`]) instead
+    pub dry_run: bool,
+    /// Terraform variable file passed to `plan`, `apply` and `destroy`
+    pub var_file: Option<String>,
 }
 
 /// Session-scoped command history, keyed by a unique target identifier
 pub struct CommandHistory {
     pub entries: std::collections::HashMap<String, ExecutionState>,
+    /// Last environment overrides entered for each target
+    pub env_overrides: std::collections::HashMap<String, EnvOverrides>,
+    /// Last `make` variable overrides entered for each target
+    pub make_vars: std::collections::HashMap<String, EnvOverrides>,
 }
 
 impl Default for CommandHistory {
@@ -59,12 +159,14 @@ impl CommandHistory {
     pub fn new() -> Self {
         Self {
             entries: std::collections::HashMap::new(),
+            env_overrides: std::collections::HashMap::new(),
+            make_vars: std::collections::HashMap::new(),
         }
     }
 
     /// Get a unique key for a function
     pub fn key_for(func: &ScriptFunction) -> String {
-        format!("{:?}:{}", func.script_type, func.name)
+        format!("{}:{}", func.script_type, func.name)
     }
 
     /// Get the execution state for a given function, if any
@@ -84,6 +186,124 @@ impl CommandHistory {
         let key = Self::key_for(func);
         self.entries.insert(key, state);
     }
+
+    /// Whether any command in the history is still running
+    pub fn has_running(&self) -> bool {
+        self.entries
+            .values()
+            .any(|state| state.status == ExecutionStatus::Running)
+    }
+
+    /// Get the environment overrides for a function, if any were entered
+    pub fn env_for(&self, func: &ScriptFunction) -> Option<&EnvOverrides> {
+        self.env_overrides.get(&Self::key_for(func))
+    }
+
+    /// Store the environment overrides for a function; an empty map clears them
+    pub fn set_env(&mut self, func: &ScriptFunction, env: EnvOverrides) {
+        let key = Self::key_for(func);
+        if env.is_empty() {
+            self.env_overrides.remove(&key);
+        } else {
+            self.env_overrides.insert(key, env);
+        }
+    }
+
+    /// Get the `make` variable overrides for a function, if any were entered
+    pub fn make_vars_for(&self, func: &ScriptFunction) -> Option<&EnvOverrides> {
+        self.make_vars.get(&Self::key_for(func))
+    }
+
+    /// Store the `make` variable overrides for a function; an empty map clears them
+    pub fn set_make_vars(&mut self, func: &ScriptFunction, vars: EnvOverrides) {
+        let key = Self::key_for(func);
+        if vars.is_empty() {
+            self.make_vars.remove(&key);
+        } else {
+            self.make_vars.insert(key, vars);
+        }
+    }
+}
+
+/// Parse whitespace-separated `KEY=VALUE` pairs into environment overrides.
+///
+/// Values may be wrapped in single or double quotes to include spaces
+/// (`MSG="hello world"`). Later pairs win over earlier ones with the same key.
+/// Empty input yields an empty map, leaving the inherited environment unchanged.
+pub fn parse_env_pairs(input: &str) -> Result<EnvOverrides> {
+    let mut env = EnvOverrides::new();
+    for entry in split_env_entries(input)? {
+        let Some((key, value)) = entry.split_once('=') else {
+            anyhow::bail!("'{}' is not a KEY=VALUE pair", entry);
+        };
+        if !is_valid_env_key(key) {
+            anyhow::bail!("'{}' is not a valid variable name", key);
+        }
+        env.insert(key.to_string(), value.to_string());
+    }
+    Ok(env)
+}
+
+/// Split env input on whitespace, keeping quoted sections together and
+/// dropping the quotes themselves
+fn split_env_entries(input: &str) -> Result<Vec<String>> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    let mut in_entry = false;
+    let mut quote: Option<char> = None;
+
+    for c in input.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_entry = true;
+            }
+            None if c.is_whitespace() => {
+                if in_entry {
+                    entries.push(std::mem::take(&mut current));
+                    in_entry = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_entry = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        anyhow::bail!("Unterminated {} quote", q);
+    }
+    if in_entry {
+        entries.push(current);
+    }
+    Ok(entries)
+}
+
+/// Whether `key` is a portable environment variable name (`[A-Za-z_][A-Za-z0-9_]*`)
+pub fn is_valid_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Format environment overrides as space-separated `KEY=VALUE` pairs, quoting
+/// values that contain whitespace so the result parses back with [`parse_env_pairs`]
+pub fn format_env_pairs(env: &EnvOverrides) -> String {
+    env.iter()
+        .map(|(key, value)| {
+            if value.is_empty() || value.chars().any(char::is_whitespace) {
+                format!("{}=\"{}\"", key, value)
+            } else {
+                format!("{}={}", key, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// Escape a string for safe inclusion in a single-quoted shell argument.
@@ -96,11 +316,274 @@ fn shell_escape(s: &str) -> String {
     format!("'{}'", s.replace('\'', "'\\''"))
 }
 
+/// Whether `s` can appear in a shell command line without quoting
+fn is_shell_safe(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c))
+}
+
+/// Join a program and its arguments into a copy-pasteable shell command line,
+/// quoting (with [`shell_escape`]) only the words that need it
+pub fn format_command_line(program: &str, args: &[String]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .map(|word| {
+            if is_shell_safe(word) {
+                word.to_string()
+            } else {
+                shell_escape(word)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The exact command line that running `func` will execute, for display
+/// before running it (e.g. `bash -c 'cd ... && source deploy.sh && deploy'`).
+///
+/// Built by [`build_run_command`], the same builder used for execution, so the
+/// preview never diverges from what actually runs.
+pub fn preview_command(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+    options: &RunOptions,
+) -> String {
+    match build_run_command(func, script_file, options) {
+        Ok((program, args, _)) => format_command_line(&program, &args),
+        Err(e) => format!("<unavailable: {}>", e),
+    }
+}
+
+/// Everything that running a function involves, as shown by the "explain
+/// command" modal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionPlan {
+    /// The program that is started, after `[binaries]` overrides and `sudo`
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_dir: std::path::PathBuf,
+    /// Environment variables set on top of the inherited environment
+    pub env: EnvOverrides,
+    /// Where `program` was found on `PATH` (`None` when it was not found)
+    pub program_path: Option<std::path::PathBuf>,
+    /// The package manager detected from the lockfile, for npm scripts
+    pub package_manager: Option<String>,
+}
+
+impl ExecutionPlan {
+    /// The full command line, quoted like [`preview_command`]
+    pub fn command_line(&self) -> String {
+        format_command_line(&self.program, &self.args)
+    }
+}
+
+/// Look a program up the way the shell would: paths are taken as they are,
+/// bare names are searched for in `PATH`
+pub fn find_program(program: &str) -> Option<std::path::PathBuf> {
+    if program.contains('/') {
+        let path = std::path::PathBuf::from(program);
+        return path.is_file().then_some(path);
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Describe what running `func` with `options` will do, using the same
+/// builder as execution.
+pub fn describe_execution(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+    options: &RunOptions,
+) -> Result<ExecutionPlan> {
+    let (program, args, working_dir) = build_options_command(func, script_file, options)?;
+    let package_manager = (script_file.script_type == ScriptType::PackageJson).then(|| {
+        script::npm_parser::detect_package_manager(&working_dir)
+            .program()
+            .to_string()
+    });
+    Ok(ExecutionPlan {
+        program_path: find_program(&program),
+        program,
+        args,
+        working_dir,
+        env: options.env.clone(),
+        package_manager,
+    })
+}
+
 /// Build the command to execute for a given script function and its script file.
 /// Returns (program, args, `working_dir`).
+///
+/// Functions annotated with `@sudo` have the whole invocation prefixed with
+/// `sudo`. The password prompt is answered through the PTY input path like any
+/// other interactive command.
+///
+/// Program names are resolved through the `[binaries]` overrides from
+/// `jarvis.toml` (see [`script::binaries`]).
 fn build_command(
     func: &ScriptFunction,
     script_file: &ScriptFile,
+) -> Result<(String, Vec<String>, std::path::PathBuf)> {
+    build_command_with_overrides(func, script_file, script::binaries::overrides())
+}
+
+/// [`build_command`] with the per-run arguments from `options` added (see
+/// [`add_run_args`]).
+fn build_run_command(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+    options: &RunOptions,
+) -> Result<(String, Vec<String>, std::path::PathBuf)> {
+    let (program, mut args, working_dir) = build_command(func, script_file)?;
+    add_run_args(script_file.script_type, &mut args, options);
+    Ok((program, args, working_dir))
+}
+
+/// Add the per-run arguments from `options` to a command's arguments: `make`
+/// variable overrides as trailing `VAR=value` arguments, and the Terraform
+/// variable file as `-var-file=<file>`. Other script types ignore both.
+fn add_run_args(script_type: ScriptType, args: &mut Vec<String>, options: &RunOptions) {
+    match script_type {
+        ScriptType::Makefile => args.extend(
+            options
+                .make_vars
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value)),
+        ),
+        ScriptType::Terraform => {
+            if let Some(ref var_file) = options.var_file {
+                script::terraform_parser::insert_var_file(args, var_file);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The command to execute for a run with `options`: [`build_run_command`],
+/// or the dry-run variant when `options.dry_run` is set.
+fn build_options_command(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+    options: &RunOptions,
+) -> Result<(String, Vec<String>, std::path::PathBuf)> {
+    if !options.dry_run {
+        return build_run_command(func, script_file, options);
+    }
+    let (program, mut args) = dry_run_command(func, script_file).with_context(|| {
+        format!(
+            "{} commands have no dry run",
+            script_file.script_type.label()
+        )
+    })?;
+    add_run_args(script_file.script_type, &mut args, options);
+    Ok((program, args, working_dir(func, script_file)?))
+}
+
+/// The dry-run variant of a function's command, which reports what it would
+/// do without doing it: `make -n`, `just --dry-run`, `task --dry`,
+/// `rake --dry-run`, `gradle --dry-run`, and `terraform plan` in place of
+/// `apply` (`plan -destroy` for `destroy`).
+///
+/// Returns `None` for script types without a side-effect free preview. This
+/// includes npm scripts: `npm run --dry-run` still runs the script.
+/// Dry runs never use `sudo`.
+pub fn dry_run_command(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+) -> Option<(String, Vec<String>)> {
+    let (program, mut args, _) = build_located_command(func, script_file).ok()?;
+    match script_file.script_type {
+        ScriptType::Makefile => args.insert(0, "-n".to_string()),
+        ScriptType::Just | ScriptType::Rakefile => args.insert(0, "--dry-run".to_string()),
+        ScriptType::Task => args.insert(0, "--dry".to_string()),
+        ScriptType::Gradle => args.push("--dry-run".to_string()),
+        ScriptType::Terraform => match args.first().map(String::as_str) {
+            Some("plan") => {}
+            Some("apply") => args[0] = "plan".to_string(),
+            Some("destroy") => {
+                args[0] = "plan".to_string();
+                args.insert(1, "-destroy".to_string());
+            }
+            _ => return None,
+        },
+        _ => return None,
+    }
+    Some((
+        script::binaries::resolve(&program, script::binaries::overrides()),
+        args,
+    ))
+}
+
+/// Directory a command runs in (where its `.env` file is looked up).
+pub fn working_dir(func: &ScriptFunction, script_file: &ScriptFile) -> Result<std::path::PathBuf> {
+    build_located_command(func, script_file).map(|(_, _, working_dir)| working_dir)
+}
+
+/// [`build_base_command`] moved to the `--cwd` directory when one is set.
+fn build_located_command(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+) -> Result<(String, Vec<String>, std::path::PathBuf)> {
+    let command = build_base_command(func, script_file)?;
+    Ok(with_working_dir(
+        func,
+        script_file,
+        command,
+        WORKING_DIR_OVERRIDE.get().map(PathBuf::as_path),
+    ))
+}
+
+/// Run `command` in `cwd` instead of its script's directory. The program and
+/// its arguments are unchanged, except that bash functions source their
+/// script by its full path rather than from a `cd` into its directory.
+fn with_working_dir(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+    command: (String, Vec<String>, PathBuf),
+    cwd: Option<&Path>,
+) -> (String, Vec<String>, PathBuf) {
+    let Some(cwd) = cwd else {
+        return command;
+    };
+    let (program, mut args, _) = command;
+    if script_file.script_type == ScriptType::Bash {
+        if let Some(bash_cmd) = args.last_mut() {
+            *bash_cmd = format!(
+                "source {} && {}",
+                shell_escape(&script_file.path.display().to_string()),
+                func.name
+            );
+        }
+    }
+    (program, args, cwd.to_path_buf())
+}
+
+/// [`build_command`] against an explicit binary override map.
+fn build_command_with_overrides(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+    overrides: &std::collections::HashMap<String, String>,
+) -> Result<(String, Vec<String>, std::path::PathBuf)> {
+    let (program, args, working_dir) = build_located_command(func, script_file)?;
+    let program = script::binaries::resolve(&program, overrides);
+    if func.sudo {
+        let mut sudo_args = vec![program];
+        sudo_args.extend(args);
+        return Ok((
+            script::binaries::resolve("sudo", overrides),
+            sudo_args,
+            working_dir,
+        ));
+    }
+    Ok((program, args, working_dir))
+}
+
+/// Build the unprivileged command for a script function, based on its script type.
+fn build_base_command(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
 ) -> Result<(String, Vec<String>, std::path::PathBuf)> {
     let path = &script_file.path;
 
@@ -131,9 +614,32 @@ fn build_command(
                 .parent()
                 .context("Failed to get parent dir")?
                 .to_path_buf();
+            let manager = script::npm_parser::detect_package_manager(&dir);
             Ok((
-                "npm".to_string(),
-                vec!["run".to_string(), func.name.clone()],
+                manager.program().to_string(),
+                manager.run_args(&func.name),
+                dir,
+            ))
+        }
+        ScriptType::ComposerJson => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok((
+                "composer".to_string(),
+                vec!["run-script".to_string(), func.name.clone()],
+                dir,
+            ))
+        }
+        ScriptType::DenoJson => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok((
+                "deno".to_string(),
+                vec!["task".to_string(), func.name.clone()],
                 dir,
             ))
         }
@@ -200,6 +706,101 @@ fn build_command(
                 .to_path_buf();
             Ok(("mage".to_string(), vec![func.name.clone()], dir))
         }
+        ScriptType::Rakefile => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok(("rake".to_string(), vec![func.name.clone()], dir))
+        }
+        ScriptType::Sbt => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok(("sbt".to_string(), vec![func.name.clone()], dir))
+        }
+        ScriptType::Maven => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            let program = script::maven_parser::maven_wrapper(&dir)
+                .map_or_else(|| "mvn".to_string(), |w| w.to_string_lossy().to_string());
+            Ok((program, script::maven_parser::goal_args(&func.name), dir))
+        }
+
+        ScriptType::CMake => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            let args = vec![
+                "--build".to_string(),
+                script::cmake_parser::BUILD_DIR.to_string(),
+                "--target".to_string(),
+                func.name.clone(),
+            ];
+            Ok(("cmake".to_string(), args, dir))
+        }
+
+        ScriptType::Meson => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            let build_dir = script::meson_parser::find_build_dir(&dir)
+                .unwrap_or(script::meson_parser::BUILD_DIRS[0]);
+            Ok((
+                "meson".to_string(),
+                script::meson_parser::build_command(build_dir, &func.name),
+                dir,
+            ))
+        }
+
+        ScriptType::Dagger => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok((
+                "dagger".to_string(),
+                vec!["call".to_string(), func.name.clone()],
+                dir,
+            ))
+        }
+
+        ScriptType::Mix => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok(("mix".to_string(), vec![func.name.clone()], dir))
+        }
+
+        ScriptType::Lefthook => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok((
+                "lefthook".to_string(),
+                script::lefthook_parser::run_args(&func.name),
+                dir,
+            ))
+        }
+
+        ScriptType::PreCommit => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            Ok((
+                "pre-commit".to_string(),
+                vec!["run".to_string(), func.name.clone()],
+                dir,
+            ))
+        }
 
         ScriptType::CargoToml => {
             let dir = path
@@ -236,6 +837,14 @@ fn build_command(
             args.push(func.name.clone());
             Ok((cmd.to_string(), args, dir))
         }
+        ScriptType::PyProject => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            let (program, args) = script::python_parser::command_for(path, &func.name)?;
+            Ok((program, args, dir))
+        }
         ScriptType::Terraform => {
             // For Terraform, the ScriptFile path is the directory containing .tf files
             let dir = path.clone();
@@ -245,6 +854,53 @@ fn build_command(
                 .to_string();
             Ok((binary, args, dir))
         }
+        ScriptType::DockerCompose => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            // Function names are "<subcommand> <service>", e.g. "logs web"
+            let mut args = vec!["compose".to_string()];
+            args.extend(func.name.split_whitespace().map(String::from));
+            Ok(("docker".to_string(), args, dir))
+        }
+        ScriptType::Procfile => {
+            let dir = path
+                .parent()
+                .context("Failed to get parent dir")?
+                .to_path_buf();
+            if script::procfile_parser::is_foreman_available() {
+                let procfile = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Procfile")
+                    .to_string();
+                let args = vec![
+                    "start".to_string(),
+                    "-f".to_string(),
+                    procfile,
+                    func.name.clone(),
+                ];
+                Ok(("foreman".to_string(), args, dir))
+            } else {
+                let command = script::procfile_parser::find_command(path, &func.name)?;
+                Ok(("bash".to_string(), vec!["-c".to_string(), command], dir))
+            }
+        }
+        ScriptType::Ansible => {
+            let dir = script::ansible_parser::playbook_dir(path).to_path_buf();
+            Ok(("ansible-playbook".to_string(), vec![func.name.clone()], dir))
+        }
+        ScriptType::Mise => {
+            let dir = script::mise_parser::project_dir(path)
+                .context("Failed to get mise project dir")?
+                .to_path_buf();
+            Ok((
+                "mise".to_string(),
+                vec!["run".to_string(), func.name.clone()],
+                dir,
+            ))
+        }
         ScriptType::Gradle => {
             let dir = path
                 .parent()
@@ -303,16 +959,41 @@ fn build_command(
                 ))
             }
         }
+        ScriptType::GithubActionsJob => {
+            // path is the workflow file; act runs from the repo root
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .context("Invalid workflow file name")?;
+            let repo_root = path
+                .ancestors()
+                .nth(3)
+                .context("Failed to get repo root from workflow file")?
+                .to_path_buf();
+            let mut args = Vec::new();
+            if let Some(event) = crate::script::github_actions_parser::select_act_event(path) {
+                args.push(event);
+            }
+            args.extend([
+                "-j".to_string(),
+                func.name.clone(),
+                "-W".to_string(),
+                format!(".github/workflows/{}", file_name),
+            ]);
+            Ok(("act".to_string(), args, repo_root))
+        }
     }
 }
 
 /// Find the matching `ScriptFile` for a function, handling the Frequently Used
-/// category indirection and Nx per-project category matching.
+/// category indirection, `@category` overrides and Nx per-project category matching.
 pub fn find_script_file<'a>(
     func: &ScriptFunction,
     original_category: &str,
     script_files: &'a [ScriptFile],
 ) -> Option<&'a ScriptFile> {
+    // Functions moved by an `@category` annotation remember their file's category
+    let original_category = func.file_category.as_deref().unwrap_or(original_category);
     script_files.iter().find(|s| {
         if s.script_type != func.script_type {
             return false;
@@ -320,6 +1001,18 @@ pub fn find_script_file<'a>(
         if s.script_type == ScriptType::NxJson {
             let prefix = format!("nx:{}:", s.category);
             original_category.starts_with(&prefix)
+        } else if s.script_type == ScriptType::Gradle {
+            // Gradle categories are per task group: "gradle:<project>:<group>"
+            let prefix = format!("gradle:{}:", s.category);
+            original_category.starts_with(&prefix)
+        } else if s.script_type == ScriptType::Task {
+            // Included Taskfile namespaces get "task:<category>:<namespace>"
+            let prefix = format!("task:{}:", s.category);
+            s.category == *original_category || original_category.starts_with(&prefix)
+        } else if s.script_type == ScriptType::Just {
+            // Justfile modules get "just:<category>:<module>"
+            let prefix = format!("just:{}:", s.category);
+            s.category == *original_category || original_category.starts_with(&prefix)
         } else {
             s.category == *original_category
         }
@@ -332,13 +1025,26 @@ pub struct PtyHandle {
     pub status: Arc<Mutex<ExecutionStatus>>,
     pub exit_code: Arc<Mutex<Option<i32>>>,
     pub finished_at: Arc<Mutex<Option<Instant>>>,
+    /// When the command last produced output (or started, if it has not yet)
+    pub last_output_at: Arc<Mutex<Instant>>,
     pub started_at: Instant,
     pub display_name: String,
     pub category: String,
+    /// Whether this is a dry run (see [`dry_run_command`]), which is not
+    /// recorded as a use of the command
+    pub dry_run: bool,
+    /// Whether the output view follows new output (see [`ExecutionState::auto_follow`])
+    pub auto_follow: bool,
     // Keep the master alive so the PTY doesn't close prematurely
     _master: Arc<Mutex<Option<Box<dyn portable_pty::MasterPty + Send>>>>,
     /// Writer to send input to the PTY slave (child process stdin)
     writer: Arc<Mutex<Option<Box<dyn Write + Send>>>>,
+    /// Signals the child independently of the waiter thread blocked in `wait`
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    /// Child process id, used to escalate to SIGKILL
+    pid: Option<u32>,
+    /// Set once the user cancels the command; read by the waiter thread
+    cancelled: Arc<AtomicBool>,
 }
 
 impl PtyHandle {
@@ -360,6 +1066,20 @@ impl PtyHandle {
         self.finished_at.lock().ok().and_then(|f| *f)
     }
 
+    /// Time since the command last produced output
+    pub fn idle_duration(&self) -> Duration {
+        self.last_output_at
+            .lock()
+            .map(|t| t.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// Heuristic: the command is running but has been silent for a while,
+    /// so it is probably blocked on a prompt waiting for stdin.
+    pub fn is_probably_waiting_for_input(&self) -> bool {
+        likely_waiting_for_input(self.poll_status(), self.idle_duration())
+    }
+
     /// Write input bytes to the PTY (sends to child process stdin)
     pub fn write_input(&self, data: &[u8]) -> Result<()> {
         if let Ok(mut writer_guard) = self.writer.lock() {
@@ -371,6 +1091,45 @@ impl PtyHandle {
         Ok(())
     }
 
+    /// Whether the user cancelled this command
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Cancel the running command.
+    ///
+    /// Sends Ctrl-C through the PTY (SIGINT to the foreground process group)
+    /// and escalates to SIGKILL if the command is still running after
+    /// [`KILL_GRACE_PERIOD`]. The waiter thread remains the only writer of the
+    /// final status, so the command ends as `Failed` with
+    /// [`CANCELLED_EXIT_CODE`] once it has actually exited.
+    pub fn kill(&self) -> Result<()> {
+        if self.poll_status() != ExecutionStatus::Running {
+            return Ok(());
+        }
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            // Already cancelling; the escalation thread is running
+            return Ok(());
+        }
+
+        self.write_input(&[0x03])?;
+
+        let finished_at = Arc::clone(&self.finished_at);
+        let mut killer = self.killer.clone_killer();
+        let pid = self.pid;
+        std::thread::spawn(move || {
+            let deadline = Instant::now() + KILL_GRACE_PERIOD;
+            while Instant::now() < deadline {
+                if finished_at.lock().map(|f| f.is_some()).unwrap_or(true) {
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            force_kill(pid, killer.as_mut());
+        });
+        Ok(())
+    }
+
     /// Convert into an `ExecutionState` for storage in history
     pub fn into_execution_state(self) -> ExecutionState {
         let status = self.poll_status();
@@ -384,6 +1143,82 @@ impl PtyHandle {
             finished_at,
             display_name: self.display_name,
             category: self.category,
+            auto_follow: self.auto_follow,
+        }
+    }
+}
+
+/// Run a command in the foreground, attached to the real terminal, and wait
+/// for it to exit. Used by full-screen execution mode; the caller is
+/// responsible for suspending the TUI first.
+///
+/// `options.env` is applied on top of the inherited environment.
+///
+/// Returns the process exit code (`1` if it was terminated by a signal).
+pub fn run_foreground_command(
+    func: &ScriptFunction,
+    script_file: &ScriptFile,
+    options: &RunOptions,
+) -> Result<i32> {
+    let (program, args, working_dir) = build_options_command(func, script_file, options)?;
+    let status = std::process::Command::new(&program)
+        .args(&args)
+        .current_dir(&working_dir)
+        .envs(&options.env)
+        .status()
+        .with_context(|| format!("Failed to run command: {}", program))?;
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Send SIGKILL to a process that ignored SIGINT.
+///
+/// Uses `kill(1)` since this crate forbids `unsafe` (and with it `libc::kill`);
+/// falls back to the PTY killer (SIGHUP) if that fails.
+fn force_kill(pid: Option<u32>, killer: &mut dyn ChildKiller) {
+    let killed = pid.is_some_and(|pid| {
+        std::process::Command::new("kill")
+            .args(["-KILL", &pid.to_string()])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    });
+    if !killed {
+        let _ = killer.kill();
+    }
+}
+
+/// Whether a command with this status and idle time looks like it is waiting for input
+pub fn likely_waiting_for_input(status: ExecutionStatus, idle: Duration) -> bool {
+    status == ExecutionStatus::Running && idle >= INPUT_WAIT_THRESHOLD
+}
+
+/// Feed raw PTY output into the vt100 parser until EOF or a read error.
+///
+/// Bytes are passed through exactly as read — never split on `\n` or otherwise
+/// pre-processed — so `\r`-based progress bars and CRLF line endings are
+/// interpreted by vt100. Line-oriented features should work from the parsed
+/// screen, not from this byte stream.
+///
+/// `last_output` is bumped on every read, for input-wait detection.
+fn pump_output<R: Read>(
+    reader: &mut R,
+    parser: &Mutex<vt100::Parser>,
+    last_output: &Mutex<Instant>,
+) {
+    let mut buf = [0u8; 4096];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                if let Ok(mut p) = parser.lock() {
+                    p.process(&buf[..n]);
+                }
+                if let Ok(mut t) = last_output.lock() {
+                    *t = Instant::now();
+                }
+            }
+            Err(_) => break,
         }
     }
 }
@@ -391,14 +1226,18 @@ impl PtyHandle {
 /// Spawn a command in a PTY and return a handle for polling.
 /// This is the primary API — it returns a `PtyHandle` that can be polled
 /// for status, exit code, and terminal output.
+///
+/// `options.env` is applied on top of the inherited environment.
 pub fn spawn_pty_command(
     func: &ScriptFunction,
     script_file: &ScriptFile,
     original_category: &str,
+    options: &RunOptions,
+    timeout: Option<Duration>,
     cols: u16,
     rows: u16,
 ) -> Result<PtyHandle> {
-    let (program, args, working_dir) = build_command(func, script_file)?;
+    let (program, args, working_dir) = build_options_command(func, script_file, options)?;
 
     let pty_system = NativePtySystem::default();
 
@@ -416,6 +1255,9 @@ pub fn spawn_pty_command(
         cmd.arg(arg);
     }
     cmd.cwd(&working_dir);
+    for (key, value) in &options.env {
+        cmd.env(key, value);
+    }
 
     let child = pty_pair
         .slave
@@ -425,10 +1267,15 @@ pub fn spawn_pty_command(
     // Drop the slave side — we only need the master for I/O
     drop(pty_pair.slave);
 
-    let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, 10000)));
+    let killer = child.clone_killer();
+    let pid = child.process_id();
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK_LINES)));
     let status = Arc::new(Mutex::new(ExecutionStatus::Running));
     let exit_code: Arc<Mutex<Option<i32>>> = Arc::new(Mutex::new(None));
     let finished_at: Arc<Mutex<Option<Instant>>> = Arc::new(Mutex::new(None));
+    let last_output_at = Arc::new(Mutex::new(Instant::now()));
 
     let mut reader = pty_pair
         .master
@@ -449,63 +1296,66 @@ pub fn spawn_pty_command(
 
     // Reader thread
     let parser_clone = Arc::clone(&parser);
+    let last_output_clone = Arc::clone(&last_output_at);
     let master_reader_clone = Arc::clone(&master);
     std::thread::spawn(move || {
-        let mut buf = [0u8; 4096];
-        loop {
-            match reader.read(&mut buf) {
-                Ok(0) => break,
-                Ok(n) => {
-                    if let Ok(mut p) = parser_clone.lock() {
-                        p.process(&buf[..n]);
-                    }
-                }
-                Err(_) => break,
-            }
-        }
+        pump_output(&mut reader, &parser_clone, &last_output_clone);
         // Drop master to close PTY when reading is done
         if let Ok(mut m) = master_reader_clone.lock() {
             m.take();
         }
     });
 
-    // Child waiter thread
+    // Child waiter thread. It is the only writer of the final status, so a
+    // cancellation can never race it into reporting success.
     let status_clone = Arc::clone(&status);
     let exit_clone = Arc::clone(&exit_code);
     let finished_clone = Arc::clone(&finished_at);
+    let parser_waiter_clone = Arc::clone(&parser);
+    let cancelled_clone = Arc::clone(&cancelled);
     let child = Arc::new(Mutex::new(child));
     let child_clone = Arc::clone(&child);
     std::thread::spawn(move || {
         if let Ok(mut c) = child_clone.lock() {
-            match c.wait() {
-                Ok(exit_status) => {
-                    let code: i32 = exit_status.exit_code().try_into().unwrap_or(1);
-                    if let Ok(mut ec) = exit_clone.lock() {
-                        *ec = Some(code);
-                    }
-                    if let Ok(mut s) = status_clone.lock() {
-                        *s = if code == 0 {
-                            ExecutionStatus::Succeeded
-                        } else {
-                            ExecutionStatus::Failed
-                        };
-                    }
-                    if let Ok(mut f) = finished_clone.lock() {
-                        *f = Some(Instant::now());
-                    }
+            let timed_out = timeout.is_some_and(|limit| wait_or_timeout(&mut **c, limit));
+            if timed_out {
+                let _ = c.kill();
+            }
+            let code = match c.wait() {
+                Ok(exit_status) => exit_status.exit_code().try_into().unwrap_or(1),
+                Err(_) => 1,
+            };
+            let cancelled = cancelled_clone.load(Ordering::SeqCst);
+            let code = if timed_out {
+                TIMEOUT_EXIT_CODE
+            } else if cancelled {
+                CANCELLED_EXIT_CODE
+            } else {
+                code
+            };
+            if timed_out {
+                if let Ok(mut p) = parser_waiter_clone.lock() {
+                    let secs = timeout.map_or(0, |limit| limit.as_secs());
+                    p.process(format!("\r\n\u{23f1} timed out after {secs}s\r\n").as_bytes());
                 }
-                Err(_) => {
-                    if let Ok(mut ec) = exit_clone.lock() {
-                        *ec = Some(1);
-                    }
-                    if let Ok(mut s) = status_clone.lock() {
-                        *s = ExecutionStatus::Failed;
-                    }
-                    if let Ok(mut f) = finished_clone.lock() {
-                        *f = Some(Instant::now());
-                    }
+            } else if cancelled {
+                if let Ok(mut p) = parser_waiter_clone.lock() {
+                    p.process(b"\r\n^C (cancelled)\r\n");
                 }
             }
+            if let Ok(mut ec) = exit_clone.lock() {
+                *ec = Some(code);
+            }
+            if let Ok(mut s) = status_clone.lock() {
+                *s = if code == 0 {
+                    ExecutionStatus::Succeeded
+                } else {
+                    ExecutionStatus::Failed
+                };
+            }
+            if let Ok(mut f) = finished_clone.lock() {
+                *f = Some(Instant::now());
+            }
         }
     });
 
@@ -514,14 +1364,39 @@ pub fn spawn_pty_command(
         status,
         exit_code,
         finished_at,
+        last_output_at,
         started_at: Instant::now(),
-        display_name: func.display_name.clone(),
+        display_name: if options.dry_run {
+            format!("{} (dry run)", func.display_name)
+        } else {
+            func.display_name.clone()
+        },
         category: original_category.to_string(),
+        dry_run: options.dry_run,
+        auto_follow: true,
         _master: master,
         writer,
+        killer,
+        pid,
+        cancelled,
     })
 }
 
+/// Wait for `child` to exit for at most `limit`.
+///
+/// Returns `true` if the child is still running once the limit has passed.
+/// Exit status is left for the caller's `wait()` to collect.
+fn wait_or_timeout(child: &mut (dyn portable_pty::Child + Send + Sync), limit: Duration) -> bool {
+    let deadline = Instant::now() + limit;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) | Err(_) => return false,
+            Ok(None) if Instant::now() >= deadline => return true,
+            Ok(None) => std::thread::sleep(TIMEOUT_POLL_INTERVAL),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -575,6 +1450,11 @@ mod tests {
             description: String::new(),
             emoji: None,
             ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
             script_type,
         }
     }
@@ -589,6 +1469,7 @@ mod tests {
             category: "Test".to_string(),
             display_name: "Test".to_string(),
             script_type,
+            available: true,
         }
     }
 
@@ -641,6 +1522,26 @@ mod tests {
         assert_eq!(cwd, PathBuf::from("/app"));
     }
 
+    #[test]
+    fn test_build_command_package_json_uses_lockfile_manager() {
+        let func = make_func("build", ScriptType::PackageJson);
+        for (lockfile, expected_program, expected_args) in [
+            ("pnpm-lock.yaml", "pnpm", vec!["run", "build"]),
+            ("yarn.lock", "yarn", vec!["build"]),
+            ("package-lock.json", "npm", vec!["run", "build"]),
+            ("bun.lockb", "bun", vec!["run", "build"]),
+        ] {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::fs::write(temp_dir.path().join(lockfile), "").unwrap();
+            let package_json = temp_dir.path().join("package.json");
+            let sf = make_script_file(package_json.to_str().unwrap(), ScriptType::PackageJson);
+
+            let (program, args, _) = build_base_command(&func, &sf).unwrap();
+            assert_eq!(program, expected_program);
+            assert_eq!(args, expected_args);
+        }
+    }
+
     #[test]
     fn test_build_command_devbox() {
         let func = make_func("start", ScriptType::DevboxJson);
@@ -665,6 +1566,21 @@ mod tests {
         assert_eq!(cwd, PathBuf::from("/app"));
     }
 
+    #[test]
+    fn test_build_command_task_uses_canonical_name() {
+        let mut func = make_func("docker:build", ScriptType::Task);
+        func.display_name = "Docker:build (db)".to_string();
+        func.aliases = vec!["db".to_string()];
+        let sf = make_script_file("/app/Taskfile.yml", ScriptType::Task);
+
+        let (_, args, _) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(
+            args,
+            vec!["--taskfile", "/app/Taskfile.yml", "docker:build"]
+        );
+    }
+
     #[test]
     fn test_build_command_makefile() {
         let func = make_func("clean", ScriptType::Makefile);
@@ -689,6 +1605,260 @@ mod tests {
         assert_eq!(cwd, PathBuf::from("/app"));
     }
 
+    #[test]
+    fn test_build_command_just_module_recipe() {
+        let func = make_func("docker::build", ScriptType::Just);
+        let sf = make_script_file("/app/justfile", ScriptType::Just);
+
+        let (_, args, _) = build_command(&func, &sf).unwrap();
+
+        // Module recipes run by their full path
+        assert_eq!(args, vec!["--justfile", "/app/justfile", "docker::build"]);
+    }
+
+    #[test]
+    fn test_build_command_sbt() {
+        let func = make_func("publishLocal", ScriptType::Sbt);
+        let sf = make_script_file("/app/build.sbt", ScriptType::Sbt);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "sbt");
+        assert_eq!(args, vec!["publishLocal"]);
+        assert_eq!(cwd, PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_build_command_maven() {
+        let sf = make_script_file("/app/pom.xml", ScriptType::Maven);
+
+        let (program, args, cwd) =
+            build_command(&make_func("install", ScriptType::Maven), &sf).unwrap();
+        assert_eq!(program, "mvn");
+        assert_eq!(args, vec!["install"]);
+        assert_eq!(cwd, PathBuf::from("/app"));
+
+        let (_, args, _) =
+            build_command(&make_func("profile:release", ScriptType::Maven), &sf).unwrap();
+        assert_eq!(args, vec!["-Prelease", "package"]);
+    }
+
+    #[test]
+    fn test_build_command_maven_wrapper() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wrapper = temp_dir
+            .path()
+            .join(if cfg!(windows) { "mvnw.cmd" } else { "mvnw" });
+        std::fs::write(&wrapper, "#!/bin/sh\n").unwrap();
+        let pom = temp_dir.path().join("pom.xml");
+        let sf = make_script_file(pom.to_str().unwrap(), ScriptType::Maven);
+
+        let (program, args, _) = build_command(&make_func("test", ScriptType::Maven), &sf).unwrap();
+        assert_eq!(program, wrapper.to_string_lossy());
+        assert_eq!(args, vec!["test"]);
+    }
+
+    #[test]
+    fn test_build_command_rake() {
+        let func = make_func("db:migrate", ScriptType::Rakefile);
+        let sf = make_script_file("/app/Rakefile", ScriptType::Rakefile);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "rake");
+        assert_eq!(args, vec!["db:migrate"]);
+        assert_eq!(cwd, PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_build_command_deno() {
+        let func = make_func("dev", ScriptType::DenoJson);
+        let sf = make_script_file("/app/deno.json", ScriptType::DenoJson);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "deno");
+        assert_eq!(args, vec!["task", "dev"]);
+        assert_eq!(cwd, PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_build_command_composer() {
+        let func = make_func("test", ScriptType::ComposerJson);
+        let sf = make_script_file("/app/composer.json", ScriptType::ComposerJson);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "composer");
+        assert_eq!(args, vec!["run-script", "test"]);
+        assert_eq!(cwd, PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_build_command_docker_compose() {
+        let func = make_func("logs web", ScriptType::DockerCompose);
+        let sf = make_script_file("/app/compose.yaml", ScriptType::DockerCompose);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "docker");
+        assert_eq!(args, vec!["compose", "logs", "web"]);
+        assert_eq!(cwd, PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_build_command_cmake() {
+        let func = make_func("myapp", ScriptType::CMake);
+        let sf = make_script_file("/app/CMakeLists.txt", ScriptType::CMake);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "cmake");
+        assert_eq!(args, vec!["--build", "build", "--target", "myapp"]);
+        assert_eq!(cwd, PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_build_command_meson() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("build/meson-info")).unwrap();
+        let meson_build = temp_dir.path().join("meson.build");
+        let func = make_func("util:static_library", ScriptType::Meson);
+        let sf = make_script_file(&meson_build.display().to_string(), ScriptType::Meson);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "meson");
+        assert_eq!(args, vec!["compile", "-C", "build", "util:static_library"]);
+        assert_eq!(cwd, temp_dir.path());
+    }
+
+    #[test]
+    fn test_build_command_dagger() {
+        let func = make_func("build-env", ScriptType::Dagger);
+        let sf = make_script_file("/repo/ci/dagger.json", ScriptType::Dagger);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "dagger");
+        assert_eq!(args, vec!["call", "build-env"]);
+        assert_eq!(cwd, PathBuf::from("/repo/ci"));
+    }
+
+    #[test]
+    fn test_build_command_mix() {
+        let func = make_func("ecto.migrate", ScriptType::Mix);
+        let sf = make_script_file("/repo/apps/shop/mix.exs", ScriptType::Mix);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "mix");
+        assert_eq!(args, vec!["ecto.migrate"]);
+        assert_eq!(cwd, PathBuf::from("/repo/apps/shop"));
+    }
+
+    #[test]
+    fn test_build_command_lefthook() {
+        let func = make_func("pre-commit:commands:lint", ScriptType::Lefthook);
+        let sf = make_script_file("/repo/lefthook.yml", ScriptType::Lefthook);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "lefthook");
+        assert_eq!(args, vec!["run", "pre-commit", "--commands", "lint"]);
+        assert_eq!(cwd, PathBuf::from("/repo"));
+    }
+
+    #[test]
+    fn test_build_command_pre_commit() {
+        let func = make_func("cargo-fmt", ScriptType::PreCommit);
+        let sf = make_script_file("/repo/.pre-commit-config.yaml", ScriptType::PreCommit);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "pre-commit");
+        assert_eq!(args, vec!["run", "cargo-fmt"]);
+        assert_eq!(cwd, PathBuf::from("/repo"));
+    }
+
+    #[test]
+    fn test_build_command_procfile() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let procfile = temp_dir.path().join("Procfile.dev");
+        std::fs::write(&procfile, "web: bin/server --port 3000\n").unwrap();
+        let func = make_func("web", ScriptType::Procfile);
+        let sf = make_script_file(&procfile.display().to_string(), ScriptType::Procfile);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        if script::procfile_parser::is_foreman_available() {
+            assert_eq!(program, "foreman");
+            assert_eq!(args, vec!["start", "-f", "Procfile.dev", "web"]);
+        } else {
+            assert_eq!(program, "bash");
+            assert_eq!(args, vec!["-c", "bin/server --port 3000"]);
+        }
+        assert_eq!(cwd, temp_dir.path());
+    }
+
+    #[test]
+    fn test_build_command_mise() {
+        let func = make_func("db:migrate", ScriptType::Mise);
+
+        let sf = make_script_file("/app/mise.toml", ScriptType::Mise);
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+        assert_eq!(program, "mise");
+        assert_eq!(args, vec!["run", "db:migrate"]);
+        assert_eq!(cwd, std::path::PathBuf::from("/app"));
+
+        // Tasks of .config/mise/config.toml run from the project root
+        let sf = make_script_file("/app/.config/mise/config.toml", ScriptType::Mise);
+        let (_, _, cwd) = build_command(&func, &sf).unwrap();
+        assert_eq!(cwd, std::path::PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_build_command_ansible() {
+        let func = make_func("site.yml", ScriptType::Ansible);
+
+        let sf = make_script_file("/infra/playbooks", ScriptType::Ansible);
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+        assert_eq!(program, "ansible-playbook");
+        assert_eq!(args, vec!["site.yml"]);
+        assert_eq!(cwd, PathBuf::from("/infra/playbooks"));
+
+        // A single playbook (`--file site.yml`) runs from its directory
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let playbook = temp_dir.path().join("site.yml");
+        std::fs::write(&playbook, "- hosts: all\n").unwrap();
+        let sf = make_script_file(&playbook.display().to_string(), ScriptType::Ansible);
+        let (_, _, cwd) = build_command(&func, &sf).unwrap();
+        assert_eq!(cwd, temp_dir.path());
+    }
+
+    #[test]
+    fn test_build_command_pyproject() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let pyproject = temp_dir.path().join("pyproject.toml");
+        std::fs::write(
+            &pyproject,
+            "[project.scripts]\nserve = \"myapp.server:main\"\n",
+        )
+        .unwrap();
+        let func = make_func("serve", ScriptType::PyProject);
+        let sf = make_script_file(&pyproject.display().to_string(), ScriptType::PyProject);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+        assert_eq!(program, "python");
+        assert_eq!(args, vec!["-m", "myapp.server"]);
+        assert_eq!(cwd, temp_dir.path());
+
+        std::fs::write(temp_dir.path().join("poetry.lock"), "").unwrap();
+        let (program, args, _) = build_command(&func, &sf).unwrap();
+        assert_eq!(program, "poetry");
+        assert_eq!(args, vec!["run", "serve"]);
+    }
+
     #[test]
     fn test_build_command_cargo_bin() {
         let func = make_func("bin:myapp", ScriptType::CargoToml);
@@ -795,6 +1965,67 @@ mod tests {
 
         assert_eq!(cwd, PathBuf::from("/my/project"));
     }
+
+    #[test]
+    fn test_build_command_github_actions_job() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let workflows_dir = temp_dir.path().join(".github").join("workflows");
+        std::fs::create_dir_all(&workflows_dir).unwrap();
+        let workflow = workflows_dir.join("ci.yml");
+        std::fs::write(
+            &workflow,
+            "on:\n  pull_request:\njobs:\n  test:\n    runs-on: ubuntu-latest\n",
+        )
+        .unwrap();
+        let func = make_func("test", ScriptType::GithubActionsJob);
+        let sf = make_script_file(
+            &workflow.display().to_string(),
+            ScriptType::GithubActionsJob,
+        );
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "act");
+        assert_eq!(
+            args,
+            vec![
+                "pull_request",
+                "-j",
+                "test",
+                "-W",
+                ".github/workflows/ci.yml"
+            ]
+        );
+        assert_eq!(cwd, temp_dir.path());
+    }
+
+    #[test]
+    fn test_build_command_sudo_prefix() {
+        let mut func = make_func("build", ScriptType::Makefile);
+        func.sudo = true;
+        let sf = make_script_file("/project/Makefile", ScriptType::Makefile);
+
+        let (program, args, cwd) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "sudo");
+        assert_eq!(args[0], "make");
+        assert_eq!(args[1..], ["--file", "/project/Makefile", "build"]);
+        assert_eq!(cwd, PathBuf::from("/project"));
+    }
+
+    #[test]
+    fn test_build_command_sudo_bash() {
+        let mut func = make_func("restart", ScriptType::Bash);
+        func.sudo = true;
+        let sf = make_script_file("/home/user/scripts/ops.sh", ScriptType::Bash);
+
+        let (program, args, _) = build_command(&func, &sf).unwrap();
+
+        assert_eq!(program, "sudo");
+        assert_eq!(args[0], "bash");
+        assert_eq!(args[1], "-c");
+        assert!(args[2].ends_with("&& restart"));
+    }
     // --- CommandHistory tests ---
 
     #[test]
@@ -837,6 +2068,7 @@ mod tests {
             finished_at: Some(Instant::now()),
             display_name: "Deploy".to_string(),
             category: "Test".to_string(),
+            auto_follow: true,
         };
 
         history.insert(&func, state);
@@ -858,6 +2090,7 @@ mod tests {
             finished_at: None,
             display_name: "Test".to_string(),
             category: "Test".to_string(),
+            auto_follow: true,
         };
 
         history.insert(&func, state);
@@ -869,6 +2102,22 @@ mod tests {
         assert_eq!(history.get(&func).unwrap().status, ExecutionStatus::Failed);
     }
 
+    #[test]
+    fn test_command_history_has_running() {
+        let mut history = CommandHistory::new();
+        assert!(!history.has_running());
+
+        let func = make_func("build", ScriptType::Bash);
+        history.insert(
+            &func,
+            ExecutionState::finished("Build", "Test", 0, Instant::now(), "done"),
+        );
+        assert!(!history.has_running());
+
+        history.get_mut(&func).unwrap().status = ExecutionStatus::Running;
+        assert!(history.has_running());
+    }
+
     #[test]
     fn test_command_history_replace() {
         let mut history = CommandHistory::new();
@@ -882,6 +2131,7 @@ mod tests {
             finished_at: Some(Instant::now()),
             display_name: "Build".to_string(),
             category: "Test".to_string(),
+            auto_follow: true,
         };
         history.insert(&func, state1);
 
@@ -893,6 +2143,7 @@ mod tests {
             finished_at: Some(Instant::now()),
             display_name: "Build".to_string(),
             category: "Test".to_string(),
+            auto_follow: true,
         };
         history.insert(&func, state2);
 
@@ -919,6 +2170,17 @@ mod tests {
         assert_eq!(result.unwrap().script_type, ScriptType::Bash);
     }
 
+    #[test]
+    fn test_find_script_file_category_override() {
+        let mut func = make_func("deploy", ScriptType::Makefile);
+        func.category = "Deployment".to_string();
+        func.file_category = Some("Test".to_string());
+        let files = vec![make_script_file("/app/Makefile", ScriptType::Makefile)];
+
+        let result = find_script_file(&func, "Deployment", &files);
+        assert_eq!(result.unwrap().path, PathBuf::from("/app/Makefile"));
+    }
+
     #[test]
     fn test_find_script_file_no_match_wrong_type() {
         let func = make_func("deploy", ScriptType::Bash);
@@ -954,6 +2216,65 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_find_script_file_task_namespace_match() {
+        let func = make_func("docker:build", ScriptType::Task);
+        let mut api = make_script_file("/repo/api/Taskfile.yml", ScriptType::Task);
+        api.category = "api".to_string();
+        let mut web = make_script_file("/repo/web/Taskfile.yml", ScriptType::Task);
+        web.category = "web".to_string();
+        let files = vec![api, web];
+
+        let result = find_script_file(&func, "task:web:docker", &files).unwrap();
+        assert_eq!(result.category, "web");
+        assert_eq!(
+            find_script_file(&func, "api", &files).unwrap().category,
+            "api"
+        );
+        assert!(find_script_file(&func, "task:docs:docker", &files).is_none());
+    }
+
+    #[test]
+    fn test_find_script_file_just_module_match() {
+        let func = make_func("docker::build", ScriptType::Just);
+        let mut api = make_script_file("/repo/api/justfile", ScriptType::Just);
+        api.category = "api".to_string();
+        let mut web = make_script_file("/repo/web/justfile", ScriptType::Just);
+        web.category = "web".to_string();
+        let files = vec![api, web];
+
+        let result = find_script_file(&func, "just:web:docker", &files).unwrap();
+        assert_eq!(result.category, "web");
+        assert!(find_script_file(&func, "just:docs:docker", &files).is_none());
+    }
+
+    #[test]
+    fn test_find_script_file_gradle_group_prefix_match() {
+        let func = make_func("build", ScriptType::Gradle);
+        let mut api = make_script_file("/repo/api/build.gradle", ScriptType::Gradle);
+        api.category = "api".to_string();
+        let mut app = make_script_file("/repo/app/build.gradle.kts", ScriptType::Gradle);
+        app.category = "app".to_string();
+        let files = vec![api, app];
+
+        let result = find_script_file(&func, "gradle:app:build", &files).unwrap();
+        assert_eq!(result.category, "app");
+        assert!(find_script_file(&func, "gradle:web:build", &files).is_none());
+    }
+
+    #[test]
+    fn test_build_command_gradle_runs_in_project_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let build_file = temp_dir.path().join("build.gradle");
+        std::fs::write(&build_file, "").unwrap();
+        let func = make_func("assemble", ScriptType::Gradle);
+        let sf = make_script_file(build_file.to_str().unwrap(), ScriptType::Gradle);
+
+        let (_, args, dir) = build_base_command(&func, &sf).unwrap();
+        assert_eq!(args, vec!["assemble"]);
+        assert_eq!(dir, temp_dir.path());
+    }
+
     #[test]
     fn test_find_script_file_empty_list() {
         let func = make_func("deploy", ScriptType::Bash);
@@ -961,6 +2282,361 @@ mod tests {
         assert!(result.is_none());
     }
 
+    // --- preview_command tests ---
+
+    /// The command line that `build_command` would actually spawn
+    fn spawned_command_line(func: &ScriptFunction, script_file: &ScriptFile) -> String {
+        let (program, args, _) = build_command(func, script_file).unwrap();
+        format_command_line(&program, &args)
+    }
+
+    #[test]
+    fn test_preview_command_matches_spawned_command() {
+        let cases = [
+            ("deploy", "/app/scripts/deploy.sh", ScriptType::Bash),
+            ("build", "/app/package.json", ScriptType::PackageJson),
+            ("test", "/app/Makefile", ScriptType::Makefile),
+            ("lint", "/app/justfile", ScriptType::Just),
+            ("check", "/app/composer.json", ScriptType::ComposerJson),
+            ("up web", "/app/compose.yaml", ScriptType::DockerCompose),
+            ("myapp", "/app/CMakeLists.txt", ScriptType::CMake),
+            ("build", "/app/mise.toml", ScriptType::Mise),
+        ];
+        for (name, path, script_type) in cases {
+            let func = make_func(name, script_type);
+            let sf = make_script_file(path, script_type);
+            assert_eq!(
+                preview_command(&func, &sf, &RunOptions::default()),
+                spawned_command_line(&func, &sf),
+                "{:?}",
+                script_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_describe_execution_bash() {
+        let func = make_func("deploy", ScriptType::Bash);
+        let sf = make_script_file("/app/scripts/deploy.sh", ScriptType::Bash);
+        let options = RunOptions {
+            env: EnvOverrides::from([("STAGE".to_string(), "prod".to_string())]),
+            ..Default::default()
+        };
+
+        let plan = describe_execution(&func, &sf, &options).unwrap();
+        assert_eq!(plan.program, "bash");
+        assert_eq!(plan.args[0], "-c");
+        assert_eq!(plan.working_dir, PathBuf::from("/app/scripts"));
+        assert_eq!(plan.env["STAGE"], "prod");
+        assert_eq!(plan.package_manager, None);
+        assert_eq!(plan.command_line(), spawned_command_line(&func, &sf));
+    }
+
+    #[test]
+    fn test_describe_execution_nx() {
+        // With npx on PATH, resolving Nx runs `npx nx`, which may try to download it
+        if find_program("npx").is_some() {
+            return;
+        }
+        let func = make_func("web:build", ScriptType::NxJson);
+        let sf = make_script_file("/app/nx.json", ScriptType::NxJson);
+
+        let plan = describe_execution(&func, &sf, &RunOptions::default()).unwrap();
+        // Without npx, the global nx is used
+        assert_eq!(plan.program, "nx");
+        assert_eq!(plan.args, vec!["run", "web:build"]);
+        assert_eq!(plan.working_dir, PathBuf::from("/app"));
+        assert!(plan.env.is_empty());
+    }
+
+    #[test]
+    fn test_describe_execution_terraform() {
+        let func = make_func("plan -var-file=prod.tfvars", ScriptType::Terraform);
+        let sf = make_script_file("/app/infra", ScriptType::Terraform);
+
+        let plan = describe_execution(&func, &sf, &RunOptions::default()).unwrap();
+        // terraform or tofu, whichever is installed
+        assert_eq!(
+            plan.program,
+            script::terraform_parser::resolve_terraform_binary().unwrap_or("terraform")
+        );
+        assert_eq!(plan.args, vec!["plan", "-var-file=prod.tfvars"]);
+        assert_eq!(plan.working_dir, PathBuf::from("/app/infra"));
+        assert_eq!(plan.program_path, find_program(&plan.program));
+    }
+
+    #[test]
+    fn test_describe_execution_package_manager() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        let func = make_func("build", ScriptType::PackageJson);
+        let sf = make_script_file(
+            temp_dir.path().join("package.json").to_str().unwrap(),
+            ScriptType::PackageJson,
+        );
+
+        let plan = describe_execution(&func, &sf, &RunOptions::default()).unwrap();
+        assert_eq!(plan.package_manager.as_deref(), Some("pnpm"));
+        assert_eq!(plan.program, "pnpm");
+    }
+
+    #[test]
+    fn test_find_program() {
+        assert!(find_program("sh").is_some());
+        assert_eq!(find_program("/nonexistent/bin/tool"), None);
+        assert_eq!(find_program("jarvis-no-such-program"), None);
+    }
+
+    #[test]
+    fn test_preview_command_bash() {
+        let func = make_func("deploy", ScriptType::Bash);
+        let sf = make_script_file("/app/scripts/deploy.sh", ScriptType::Bash);
+        assert_eq!(
+            preview_command(&func, &sf, &RunOptions::default()),
+            r#"bash -c 'cd '\''/app/scripts'\'' && source '\''deploy.sh'\'' && deploy'"#
+        );
+    }
+
+    #[test]
+    fn test_preview_command_plain_words_are_unquoted() {
+        let func = make_func("test", ScriptType::Makefile);
+        let sf = make_script_file("/app/Makefile", ScriptType::Makefile);
+        assert_eq!(
+            preview_command(&func, &sf, &RunOptions::default()),
+            "make --file /app/Makefile test"
+        );
+    }
+
+    #[test]
+    fn test_preview_command_includes_sudo() {
+        let mut func = make_func("install", ScriptType::Makefile);
+        func.sudo = true;
+        let sf = make_script_file("/app/Makefile", ScriptType::Makefile);
+        let preview = preview_command(&func, &sf, &RunOptions::default());
+        assert!(preview.starts_with("sudo make "), "{preview}");
+        assert_eq!(preview, spawned_command_line(&func, &sf));
+    }
+
+    #[test]
+    fn test_build_run_command_appends_make_vars() {
+        let func = make_func("build", ScriptType::Makefile);
+        let sf = make_script_file("/app/Makefile", ScriptType::Makefile);
+        let vars = RunOptions {
+            make_vars: parse_env_pairs("PROFILE=release CFLAGS=\"-O2 -g\"").unwrap(),
+            ..RunOptions::default()
+        };
+
+        let (program, args, _) = build_run_command(&func, &sf, &vars).unwrap();
+        assert_eq!(program, "make");
+        assert_eq!(
+            args,
+            vec![
+                "--file",
+                "/app/Makefile",
+                "build",
+                "CFLAGS=-O2 -g",
+                "PROFILE=release"
+            ]
+        );
+        assert_eq!(
+            preview_command(&func, &sf, &vars),
+            "make --file /app/Makefile build 'CFLAGS=-O2 -g' PROFILE=release"
+        );
+
+        // Without overrides the plain target runs unchanged
+        let (_, args, _) = build_run_command(&func, &sf, &RunOptions::default()).unwrap();
+        assert_eq!(args, vec!["--file", "/app/Makefile", "build"]);
+    }
+
+    #[test]
+    fn test_build_run_command_adds_terraform_var_file() {
+        let sf = make_script_file("/infra", ScriptType::Terraform);
+        let options = RunOptions {
+            var_file: Some("prod.tfvars".to_string()),
+            ..RunOptions::default()
+        };
+
+        let cases = [
+            ("plan", vec!["plan", "-var-file=prod.tfvars"]),
+            (
+                "apply --target=aws_instance.web",
+                vec![
+                    "apply",
+                    "-var-file=prod.tfvars",
+                    "--target=aws_instance.web",
+                ],
+            ),
+            ("init", vec!["init"]),
+        ];
+        for (name, expected) in cases {
+            let func = make_func(name, ScriptType::Terraform);
+            let (_, args, _) = build_run_command(&func, &sf, &options).unwrap();
+            assert_eq!(args, expected, "{name}");
+        }
+
+        // Dry runs plan with the same variables
+        let func = make_func("destroy", ScriptType::Terraform);
+        let dry_run = RunOptions {
+            dry_run: true,
+            ..options
+        };
+        let (_, args, _) = build_options_command(&func, &sf, &dry_run).unwrap();
+        assert_eq!(args, vec!["plan", "-var-file=prod.tfvars", "-destroy"]);
+    }
+
+    #[test]
+    fn test_build_run_command_ignores_make_vars_for_other_types() {
+        let func = make_func("build", ScriptType::Just);
+        let sf = make_script_file("/app/justfile", ScriptType::Just);
+        let vars = RunOptions {
+            make_vars: parse_env_pairs("PROFILE=release").unwrap(),
+            var_file: Some("prod.tfvars".to_string()),
+            ..RunOptions::default()
+        };
+        assert_eq!(
+            build_run_command(&func, &sf, &vars).unwrap(),
+            build_command(&func, &sf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_with_working_dir_replaces_only_the_directory() {
+        let func = make_func("build", ScriptType::Makefile);
+        let sf = make_script_file("/app/sub/Makefile", ScriptType::Makefile);
+        let command = build_base_command(&func, &sf).unwrap();
+
+        let (program, args, dir) =
+            with_working_dir(&func, &sf, command.clone(), Some(Path::new("/repo")));
+        assert_eq!(program, command.0);
+        assert_eq!(args, command.1);
+        assert_eq!(dir, PathBuf::from("/repo"));
+
+        // Without an override the command is untouched
+        assert_eq!(with_working_dir(&func, &sf, command.clone(), None), command);
+    }
+
+    #[test]
+    fn test_with_working_dir_sources_bash_scripts_by_path() {
+        let func = make_func("deploy", ScriptType::Bash);
+        let sf = make_script_file("/app/my scripts/deploy.sh", ScriptType::Bash);
+        let command = build_base_command(&func, &sf).unwrap();
+
+        let (program, args, dir) = with_working_dir(&func, &sf, command, Some(Path::new("/repo")));
+        assert_eq!(program, "bash");
+        assert_eq!(
+            args,
+            vec!["-c", "source '/app/my scripts/deploy.sh' && deploy"]
+        );
+        assert_eq!(dir, PathBuf::from("/repo"));
+    }
+
+    #[test]
+    fn test_dry_run_command_per_script_type() {
+        let dry_run = |name: &str, path: &str, script_type: ScriptType| {
+            dry_run_command(
+                &make_func(name, script_type),
+                &make_script_file(path, script_type),
+            )
+        };
+
+        assert_eq!(
+            dry_run("build", "/app/Makefile", ScriptType::Makefile),
+            Some((
+                "make".to_string(),
+                vec![
+                    "-n".to_string(),
+                    "--file".into(),
+                    "/app/Makefile".into(),
+                    "build".into()
+                ]
+            ))
+        );
+        let (program, args) = dry_run("build", "/app/justfile", ScriptType::Just).unwrap();
+        assert_eq!(program, "just");
+        assert_eq!(
+            args,
+            vec!["--dry-run", "--justfile", "/app/justfile", "build"]
+        );
+        let (program, args) = dry_run("build", "/app/Taskfile.yml", ScriptType::Task).unwrap();
+        assert_eq!(program, "task");
+        assert_eq!(
+            args,
+            vec!["--dry", "--taskfile", "/app/Taskfile.yml", "build"]
+        );
+        let (program, args) = dry_run("db:migrate", "/app/Rakefile", ScriptType::Rakefile).unwrap();
+        assert_eq!(program, "rake");
+        assert_eq!(args, vec!["--dry-run", "db:migrate"]);
+        let (_, args) = dry_run("build", "/app/build.gradle", ScriptType::Gradle).unwrap();
+        assert_eq!(args, vec!["build", "--dry-run"]);
+    }
+
+    #[test]
+    fn test_dry_run_command_terraform_plans_instead() {
+        let dry_run = |name: &str| {
+            dry_run_command(
+                &make_func(name, ScriptType::Terraform),
+                &make_script_file("/infra", ScriptType::Terraform),
+            )
+            .map(|(_, args)| args)
+        };
+
+        assert_eq!(dry_run("plan").unwrap(), vec!["plan"]);
+        assert_eq!(dry_run("apply").unwrap(), vec!["plan"]);
+        assert_eq!(dry_run("destroy").unwrap(), vec!["plan", "-destroy"]);
+        assert_eq!(
+            dry_run("apply --target=aws_instance.web").unwrap(),
+            vec!["plan", "--target=aws_instance.web"]
+        );
+        assert_eq!(dry_run("init"), None);
+        assert_eq!(dry_run("workspace select dev"), None);
+    }
+
+    #[test]
+    fn test_dry_run_command_unsupported_types() {
+        for (path, script_type) in [
+            ("/app/scripts/deploy.sh", ScriptType::Bash),
+            ("/app/package.json", ScriptType::PackageJson),
+            ("/app/Cargo.toml", ScriptType::CargoToml),
+        ] {
+            let func = make_func("build", script_type);
+            let sf = make_script_file(path, script_type);
+            assert_eq!(dry_run_command(&func, &sf), None, "{script_type:?}");
+        }
+    }
+
+    #[test]
+    fn test_dry_run_skips_sudo_and_keeps_make_vars() {
+        let mut func = make_func("install", ScriptType::Makefile);
+        func.sudo = true;
+        let sf = make_script_file("/app/Makefile", ScriptType::Makefile);
+        let options = RunOptions {
+            make_vars: parse_env_pairs("PREFIX=/usr").unwrap(),
+            dry_run: true,
+            ..RunOptions::default()
+        };
+
+        let (program, args, working_dir) = build_options_command(&func, &sf, &options).unwrap();
+        assert_eq!(program, "make");
+        assert_eq!(
+            args,
+            vec!["-n", "--file", "/app/Makefile", "install", "PREFIX=/usr"]
+        );
+        assert_eq!(working_dir, std::path::PathBuf::from("/app"));
+
+        let func = make_func("deploy", ScriptType::Bash);
+        let sf = make_script_file("/app/scripts/deploy.sh", ScriptType::Bash);
+        assert!(build_options_command(&func, &sf, &options).is_err());
+    }
+
+    #[test]
+    fn test_format_command_line_quotes_special_words() {
+        let args = vec!["a b".to_string(), "it's".to_string(), String::new()];
+        assert_eq!(
+            format_command_line("echo", &args),
+            r#"echo 'a b' 'it'\''s' ''"#
+        );
+    }
+
     // --- Terraform build_command tests ---
 
     #[test]
@@ -1022,4 +2698,352 @@ mod tests {
         assert_eq!(ExecutionStatus::Failed, ExecutionStatus::Failed);
         assert_ne!(ExecutionStatus::Idle, ExecutionStatus::Running);
     }
+
+    #[test]
+    fn test_reset_execution() {
+        let mut state =
+            ExecutionState::finished("Build", "Dev", 2, Instant::now(), "line 1\nline 2");
+        state.parser.lock().unwrap().screen_mut().set_size(30, 100);
+        state.auto_follow = false;
+
+        reset_execution(&mut state);
+
+        assert_eq!(state.status, ExecutionStatus::Idle);
+        assert_eq!(state.exit_code, None);
+        assert_eq!(state.finished_at, None);
+        assert!(state.auto_follow);
+        let parser = state.parser.lock().unwrap();
+        assert_eq!(parser.screen().size(), (30, 100));
+        assert!(parser.screen().contents().trim().is_empty());
+    }
+
+    /// Reader that hands out at most `chunk` bytes per `read`, to mimic
+    /// PTY reads that split lines and escape sequences at arbitrary points.
+    struct ChunkedReader {
+        data: Vec<u8>,
+        pos: usize,
+        chunk: usize,
+    }
+
+    impl Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let end = (self.pos + self.chunk.min(buf.len())).min(self.data.len());
+            let n = end - self.pos;
+            buf[..n].copy_from_slice(&self.data[self.pos..end]);
+            self.pos = end;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_pump_output_carriage_return_progress() {
+        let output = b"Downloading  10%\rDownloading  55%\rDownloading 100%\r\ndone\r\n";
+        let parser = Mutex::new(vt100::Parser::new(24, 80, 100));
+        let mut reader = ChunkedReader {
+            data: output.to_vec(),
+            pos: 0,
+            chunk: 3,
+        };
+
+        pump_output(&mut reader, &parser, &Mutex::new(Instant::now()));
+
+        let p = parser.lock().unwrap();
+        let rows: Vec<String> = p.screen().rows(0, 80).collect();
+        assert_eq!(rows[0], "Downloading 100%");
+        assert_eq!(rows[1], "done");
+        assert_eq!(rows[2], "");
+    }
+
+    #[test]
+    fn test_pump_output_crlf_line_endings() {
+        let parser = Mutex::new(vt100::Parser::new(24, 80, 100));
+        let mut reader = ChunkedReader {
+            data: b"first\r\nsecond\r\n".to_vec(),
+            pos: 0,
+            chunk: 1,
+        };
+
+        pump_output(&mut reader, &parser, &Mutex::new(Instant::now()));
+
+        let p = parser.lock().unwrap();
+        assert_eq!(p.screen().contents(), "first\nsecond");
+    }
+
+    #[test]
+    fn test_run_foreground_command_exit_code() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("fg.sh");
+        std::fs::write(
+            &script_path,
+            "#!/bin/bash\nfail() {\n  exit 3\n}\nok() {\n  true\n}\n",
+        )
+        .unwrap();
+        let script_file = make_script_file(script_path.to_str().unwrap(), ScriptType::Bash);
+
+        let options = RunOptions::default();
+        let code =
+            run_foreground_command(&make_func("fail", ScriptType::Bash), &script_file, &options);
+        assert_eq!(code.unwrap(), 3);
+        let code =
+            run_foreground_command(&make_func("ok", ScriptType::Bash), &script_file, &options);
+        assert_eq!(code.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_run_foreground_command_applies_env() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("env.sh");
+        std::fs::write(
+            &script_path,
+            "#!/bin/bash
+check() {
+  [ \"$LOG_LEVEL\" = \"debug\" ]
+}
+",
+        )
+        .unwrap();
+        let script_file = make_script_file(script_path.to_str().unwrap(), ScriptType::Bash);
+        let func = make_func("check", ScriptType::Bash);
+
+        let code = run_foreground_command(&func, &script_file, &RunOptions::default());
+        assert_eq!(code.unwrap(), 1);
+        let options = RunOptions {
+            env: parse_env_pairs("LOG_LEVEL=debug").unwrap(),
+            ..RunOptions::default()
+        };
+        let code = run_foreground_command(&func, &script_file, &options);
+        assert_eq!(code.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_parse_env_pairs() {
+        let env = parse_env_pairs("LOG_LEVEL=debug  RUST_BACKTRACE=1").unwrap();
+        assert_eq!(env.len(), 2);
+        assert_eq!(env["LOG_LEVEL"], "debug");
+        assert_eq!(env["RUST_BACKTRACE"], "1");
+    }
+
+    #[test]
+    fn test_parse_env_pairs_empty_input() {
+        assert!(parse_env_pairs("").unwrap().is_empty());
+        assert!(parse_env_pairs("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_env_pairs_quoted_and_empty_values() {
+        let env = parse_env_pairs(r#"MSG="hello world" NAME='a b' EMPTY= URL=a=b"#).unwrap();
+        assert_eq!(env["MSG"], "hello world");
+        assert_eq!(env["NAME"], "a b");
+        assert_eq!(env["EMPTY"], "");
+        assert_eq!(env["URL"], "a=b");
+    }
+
+    #[test]
+    fn test_parse_env_pairs_last_value_wins() {
+        let env = parse_env_pairs("A=1 A=2").unwrap();
+        assert_eq!(env["A"], "2");
+    }
+
+    #[test]
+    fn test_parse_env_pairs_rejects_invalid_entries() {
+        assert!(parse_env_pairs("LOG_LEVEL").is_err());
+        assert!(parse_env_pairs("A=1 debug").is_err());
+        assert!(parse_env_pairs("=value").is_err());
+        assert!(parse_env_pairs("1ABC=x").is_err());
+        assert!(parse_env_pairs("MY-VAR=x").is_err());
+        assert!(parse_env_pairs("MSG=\"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_format_env_pairs_round_trips() {
+        let env = parse_env_pairs(r#"B=2 MSG="hello world" EMPTY="#).unwrap();
+        let formatted = format_env_pairs(&env);
+        assert_eq!(formatted, r#"B=2 EMPTY="" MSG="hello world""#);
+        assert_eq!(parse_env_pairs(&formatted).unwrap(), env);
+    }
+
+    #[test]
+    fn test_command_history_env_overrides() {
+        let mut history = CommandHistory::new();
+        let func = make_func("build", ScriptType::Bash);
+        assert!(history.env_for(&func).is_none());
+
+        history.set_env(&func, parse_env_pairs("A=1").unwrap());
+        assert_eq!(history.env_for(&func).unwrap()["A"], "1");
+
+        history.set_env(&func, EnvOverrides::new());
+        assert!(history.env_for(&func).is_none());
+    }
+
+    #[test]
+    fn test_execution_state_finished() {
+        let state =
+            ExecutionState::finished("Build", "cat", 2, Instant::now(), "line one\nline two");
+
+        assert_eq!(state.status, ExecutionStatus::Failed);
+        assert_eq!(state.exit_code, Some(2));
+        assert!(state.finished_at.is_some());
+        let contents = state.parser.lock().unwrap().screen().contents();
+        assert_eq!(contents, "line one\nline two");
+    }
+
+    #[test]
+    fn test_build_command_binary_override() {
+        let mut overrides = std::collections::HashMap::new();
+        overrides.insert("task".to_string(), "/opt/tools/bin/task".to_string());
+        let func = make_func("build", ScriptType::Task);
+        let script_file = make_script_file("/project/Taskfile.yml", ScriptType::Task);
+
+        let (program, args, _) =
+            build_command_with_overrides(&func, &script_file, &overrides).unwrap();
+        assert_eq!(program, "/opt/tools/bin/task");
+        assert!(args.contains(&"build".to_string()));
+    }
+
+    #[test]
+    fn test_build_command_binary_override_with_sudo() {
+        let mut overrides = std::collections::HashMap::new();
+        overrides.insert("make".to_string(), "/usr/local/bin/gmake".to_string());
+        let mut func = make_func("install", ScriptType::Makefile);
+        func.sudo = true;
+        let script_file = make_script_file("/project/Makefile", ScriptType::Makefile);
+
+        let (program, args, _) =
+            build_command_with_overrides(&func, &script_file, &overrides).unwrap();
+        assert_eq!(program, "sudo");
+        assert_eq!(args[0], "/usr/local/bin/gmake");
+    }
+
+    #[test]
+    fn test_build_command_without_override_keeps_program() {
+        let func = make_func("build", ScriptType::Task);
+        let script_file = make_script_file("/project/Taskfile.yml", ScriptType::Task);
+
+        let (program, _, _) =
+            build_command_with_overrides(&func, &script_file, &std::collections::HashMap::new())
+                .unwrap();
+        assert_eq!(program, "task");
+    }
+
+    #[test]
+    fn test_pump_output_updates_last_output() {
+        let parser = Mutex::new(vt100::Parser::new(24, 80, 100));
+        let start = Instant::now();
+        let last_output = Mutex::new(start);
+        let mut reader = ChunkedReader {
+            data: b"Password: ".to_vec(),
+            pos: 0,
+            chunk: 64,
+        };
+
+        pump_output(&mut reader, &parser, &last_output);
+
+        assert!(*last_output.lock().unwrap() > start);
+    }
+
+    #[test]
+    fn test_likely_waiting_for_input() {
+        let long = INPUT_WAIT_THRESHOLD + Duration::from_secs(1);
+        assert!(likely_waiting_for_input(ExecutionStatus::Running, long));
+        assert!(!likely_waiting_for_input(
+            ExecutionStatus::Running,
+            Duration::from_millis(100)
+        ));
+        assert!(!likely_waiting_for_input(ExecutionStatus::Succeeded, long));
+    }
+
+    /// Poll until the handle reports a finished timestamp (or time out)
+    fn wait_for_finish(handle: &PtyHandle, timeout: Duration) -> Option<Instant> {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if let Some(finished_at) = handle.poll_finished_at() {
+                return Some(finished_at);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        None
+    }
+
+    #[test]
+    fn test_kill_cancels_running_command() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script = temp_dir.path().join("nap.sh");
+        std::fs::write(&script, "nap() {\n  sleep 100\n}\n").unwrap();
+        let func = make_func("nap", ScriptType::Bash);
+        let sf = make_script_file(&script.display().to_string(), ScriptType::Bash);
+
+        let handle =
+            spawn_pty_command(&func, &sf, "Test", &RunOptions::default(), None, 80, 24).unwrap();
+        assert_eq!(handle.poll_status(), ExecutionStatus::Running);
+
+        handle.kill().unwrap();
+        assert!(handle.is_cancelled());
+
+        let finished_at = wait_for_finish(&handle, KILL_GRACE_PERIOD + Duration::from_secs(5));
+        assert!(finished_at.is_some(), "command should finish after kill");
+        assert_eq!(handle.poll_status(), ExecutionStatus::Failed);
+        assert_eq!(handle.poll_exit_code(), Some(CANCELLED_EXIT_CODE));
+
+        let contents = handle.parser.lock().unwrap().screen().contents();
+        assert!(contents.contains("^C (cancelled)"), "{contents}");
+    }
+
+    #[test]
+    fn test_kill_after_finish_is_noop() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script = temp_dir.path().join("quick.sh");
+        std::fs::write(&script, "quick() {\n  true\n}\n").unwrap();
+        let func = make_func("quick", ScriptType::Bash);
+        let sf = make_script_file(&script.display().to_string(), ScriptType::Bash);
+
+        let handle =
+            spawn_pty_command(&func, &sf, "Test", &RunOptions::default(), None, 80, 24).unwrap();
+        assert!(wait_for_finish(&handle, Duration::from_secs(5)).is_some());
+
+        handle.kill().unwrap();
+        assert!(!handle.is_cancelled());
+        assert_eq!(handle.poll_status(), ExecutionStatus::Succeeded);
+    }
+
+    #[test]
+    fn test_timeout_fails_hung_command() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script = temp_dir.path().join("hang.sh");
+        std::fs::write(&script, "hang() {\n  sleep 10\n}\n").unwrap();
+        let func = make_func("hang", ScriptType::Bash);
+        let sf = make_script_file(&script.display().to_string(), ScriptType::Bash);
+
+        let timeout = Some(Duration::from_secs(1));
+        let handle =
+            spawn_pty_command(&func, &sf, "Test", &RunOptions::default(), timeout, 80, 24).unwrap();
+        assert_eq!(handle.poll_status(), ExecutionStatus::Running);
+
+        let finished_at = wait_for_finish(&handle, Duration::from_secs(8));
+        assert!(
+            finished_at.is_some(),
+            "command should be killed by the timeout"
+        );
+        assert!(finished_at.unwrap() - handle.started_at < Duration::from_secs(8));
+        assert_eq!(handle.poll_status(), ExecutionStatus::Failed);
+        assert_eq!(handle.poll_exit_code(), Some(TIMEOUT_EXIT_CODE));
+
+        let contents = handle.parser.lock().unwrap().screen().contents();
+        assert!(contents.contains("timed out after 1s"), "{contents}");
+    }
+
+    #[test]
+    fn test_timeout_does_not_affect_quick_command() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script = temp_dir.path().join("quick.sh");
+        std::fs::write(&script, "quick() {\n  true\n}\n").unwrap();
+        let func = make_func("quick", ScriptType::Bash);
+        let sf = make_script_file(&script.display().to_string(), ScriptType::Bash);
+
+        let timeout = Some(Duration::from_secs(5));
+        let handle =
+            spawn_pty_command(&func, &sf, "Test", &RunOptions::default(), timeout, 80, 24).unwrap();
+        assert!(wait_for_finish(&handle, Duration::from_secs(5)).is_some());
+        assert_eq!(handle.poll_status(), ExecutionStatus::Succeeded);
+        assert_eq!(handle.poll_exit_code(), Some(0));
+    }
 }
@@ -22,10 +22,23 @@
 //! - `render_header` - Draws the top header bar
 //! - `render_search_bar` - Draws the search input when active
 //! - `render_script_tree` - Draws the categorized script list
-//! - `render_details` - Draws the selected script details
-//! - `render_terminal_output` - Draws inline terminal output from PTY
+//! - `render_function_details` - Draws the selected function and the command it runs
+//! - `render_category_details` - Draws the selected category's description
+//! - `render_terminal_output` - Draws inline terminal output from PTY, with a
+//!   scrollbar on its border (see [`output_scrollbar_state`])
 //! - `render_footer` - Draws the keyboard shortcuts
 //! - `render_info_modal` - Draws the info popup overlay
+//! - `render_help_modal` - Draws the keybinding reference from [`keymap`]
+//! - `render_category_jump` - Draws the fuzzy category jump overlay
+//! - `render_command_palette` - Draws the command palette overlay
+//! - `render_confirmation_modal` - Asks before running `@sudo` and destructive commands
+//!
+//! ## Colors
+//!
+//! With `--no-color` or `NO_COLOR` set, [`App::colors_enabled`] is off and
+//! [`render`] strips the colors from the finished frame (see [`plain_style`]),
+//! so every helper above, and the command output, draws in the terminal's
+//! default colors.
 //!
 //! ## Border States
 //!
@@ -35,27 +48,110 @@
 //! - **Success**: Green border
 //! - **Failure**: Red border
 
-use crate::ui::app::{App, FocusPane, TreeItem};
-use crate::ui::pty_runner::ExecutionStatus;
-use crate::ui::terminal_widget::TerminalView;
+use crate::script::ScriptFunction;
+use crate::ui::app::{
+    spinner_frame_at, App, ConfirmReason, FocusPane, OverrideKind, TreeItem, VarFilePicker,
+};
+use crate::ui::keymap::{self, Action, KeyContext};
+use crate::ui::pty_runner::{
+    format_command_line, format_env_pairs, preview_command, ExecutionState, ExecutionStatus,
+};
+use crate::ui::terminal_widget::{max_scrollback, total_content_lines, TerminalView};
 use crate::ui::theme::Theme;
+use crate::usage::{FREQUENTLY_USED_CATEGORY, RECENTLY_USED_CATEGORY};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Wrap,
+    },
     Frame,
 };
+use std::time::Duration;
+
+/// Format a duration compactly: `1.2s` under a minute, `3m04s` under an hour,
+/// `1h02m` above that.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else if secs < 3600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
 
-/// Characters used for the spinning animation on the running border
-const SPINNER_CHARS: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+/// Format a duration as a clock for the footer timer: `mm:ss`, or `h:mm:ss`
+/// from an hour on.
+pub fn format_elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 3600 {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    } else {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    }
+}
+
+/// Trailing badge for a function in the script list: ✅/❌ and how long the
+/// last run took, or a spinner and the elapsed time while it runs.
+pub fn format_status_badge(state: &ExecutionState) -> String {
+    match state.status {
+        ExecutionStatus::Idle => String::new(),
+        ExecutionStatus::Running => {
+            let elapsed = state.started_at.elapsed();
+            format!("{} {}", spinner_frame_at(elapsed), format_duration(elapsed))
+        }
+        ExecutionStatus::Succeeded | ExecutionStatus::Failed => {
+            let icon = if state.status == ExecutionStatus::Succeeded {
+                "\u{2705}"
+            } else {
+                "\u{274c}"
+            };
+            let duration = state
+                .finished_at
+                .map(|finished| finished.saturating_duration_since(state.started_at))
+                .unwrap_or_default();
+            format!("{} {}", icon, format_duration(duration))
+        }
+    }
+}
+
+/// Right-align `badge` after `label` within `width` columns, truncating the
+/// label with `…` when both don't fit.
+fn fit_with_badge(label: &str, badge: &str, width: usize) -> String {
+    let badge_width = Span::raw(badge).width();
+    let available = width.saturating_sub(badge_width + 1);
+    let mut label = label.to_string();
+    if Span::raw(label.as_str()).width() > available {
+        while !label.is_empty() && Span::raw(label.as_str()).width() + 1 > available {
+            label.pop();
+        }
+        label.push('\u{2026}');
+    }
+    let padding = width
+        .saturating_sub(Span::raw(label.as_str()).width() + badge_width)
+        .max(1);
+    format!("{}{}{}", label, " ".repeat(padding), badge)
+}
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     // Tick the animation
     app.tick_animation();
 
-    // Main layout: Header + (optional Search) + Body + Footer
-    let main_constraints = if app.search_mode {
+    let fullscreen = app.is_output_fullscreen();
+
+    // Main layout: Header + (optional Search) + Body + Footer, or only the
+    // output and the footer in fullscreen
+    let main_constraints = if fullscreen {
+        vec![
+            Constraint::Min(0),    // Body
+            Constraint::Length(1), // Footer
+        ]
+    } else if app.search_mode {
         vec![
             Constraint::Length(3), // Header
             Constraint::Length(3), // Search bar
@@ -75,7 +171,9 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         .constraints(main_constraints)
         .split(frame.area());
 
-    let (body_idx, footer_idx) = if app.search_mode {
+    let (body_idx, footer_idx) = if fullscreen {
+        (0, 1)
+    } else if app.search_mode {
         // Render header
         render_header(frame, app, main_chunks[0]);
         // Render search bar
@@ -87,21 +185,27 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         (1, 2)
     };
 
-    // Split body into left (scripts) and right (details/output)
-    let body_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
-        .split(main_chunks[body_idx]);
-
-    // Render script tree on left
-    render_script_tree(frame, app, body_chunks[0]);
+    let body_chunks = body_layout(main_chunks[body_idx], fullscreen);
+    let output_area = if let [list_area, output_area] = body_chunks[..] {
+        // Render script tree on left
+        render_script_tree(frame, app, list_area);
+        output_area
+    } else {
+        // The hidden list must not take mouse clicks
+        app.script_list_area = None;
+        body_chunks[0]
+    };
 
     // Render right side: terminal output (or empty state)
     let has_terminal = app.has_terminal_output();
     if has_terminal {
-        render_terminal_output(frame, app, body_chunks[1]);
+        render_terminal_output(frame, app, output_area);
+    } else if let Some(TreeItem::Category(category)) = app.selected_item() {
+        render_category_details(frame, app, &category, output_area);
+    } else if let Some(TreeItem::Function(func)) = app.selected_item() {
+        render_function_details(frame, app, &func, output_area);
     } else {
-        render_empty_output(frame, app, body_chunks[1]);
+        render_empty_output(frame, app, output_area);
     }
 
     // Render footer
@@ -113,11 +217,97 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_info_modal(frame, app, full_area);
     }
 
+    // Render the keybinding help overlay
+    if app.show_help {
+        let full_area = frame.area();
+        render_help_modal(frame, app, full_area);
+    }
+
     // Render theme picker modal on top if show_theme_picker is true
     if app.show_theme_picker {
         let full_area = frame.area();
         render_theme_picker(frame, app, full_area);
     }
+
+    // Render the Terraform var file picker
+    if let Some(ref picker) = app.var_file_picker {
+        let full_area = frame.area();
+        render_var_file_picker(frame, app, picker, full_area);
+    }
+
+    // Render the category jump overlay
+    if app.show_category_jump {
+        let full_area = frame.area();
+        render_category_jump(frame, app, full_area);
+    }
+
+    // Render the command palette overlay
+    if app.palette_mode {
+        let full_area = frame.area();
+        render_command_palette(frame, app, full_area);
+    }
+
+    // Render the environment overrides modal
+    if app.env_target.is_some() {
+        let full_area = frame.area();
+        render_env_modal(frame, app, full_area);
+    }
+
+    // Render the "explain command" modal
+    if app.explain.is_some() {
+        let full_area = frame.area();
+        render_explain_modal(frame, app, full_area);
+    }
+
+    // Render the confirmation modal on top of everything else
+    if app.pending_confirmation.is_some() {
+        let full_area = frame.area();
+        render_confirmation_modal(frame, app, full_area);
+    }
+
+    if !app.colors_enabled {
+        strip_colors(frame.buffer_mut(), app.theme.bg);
+    }
+}
+
+/// Split the body into the script list (left) and the details/output pane
+/// (right), or give it all to the output pane in fullscreen.
+pub fn body_layout(area: Rect, fullscreen: bool) -> Vec<Rect> {
+    if fullscreen {
+        return vec![area];
+    }
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
+        .split(area)
+        .to_vec()
+}
+
+/// `style` without colors, keeping its modifiers. Highlights (a background
+/// other than the theme's `base_bg`) become reverse video, so the selection
+/// stays visible.
+pub fn plain_style(style: Style, base_bg: Color) -> Style {
+    let highlighted = style
+        .bg
+        .is_some_and(|bg| bg != base_bg && bg != Color::Reset);
+    let plain = Style::default()
+        .add_modifier(style.add_modifier)
+        .remove_modifier(style.sub_modifier);
+    if highlighted {
+        plain.add_modifier(Modifier::REVERSED)
+    } else {
+        plain
+    }
+}
+
+/// Apply [`plain_style`] to every cell of a rendered frame
+fn strip_colors(buffer: &mut Buffer, base_bg: Color) {
+    for cell in &mut buffer.content {
+        let style = plain_style(cell.style(), base_bg);
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+        cell.modifier = style.add_modifier;
+    }
 }
 
 fn render_header(frame: &mut Frame, app: &App, area: Rect) {
@@ -129,6 +319,55 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
             .add_modifier(Modifier::BOLD),
     )];
 
+    if let Some(script_type) = app.type_filter {
+        spans.push(Span::styled(
+            format!("[{} only] ", script_type.label()),
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    spans.push(Span::styled(
+        format!("[sort: {}] ", app.sort_mode.label()),
+        Style::default().fg(app.theme.fg_dim),
+    ));
+
+    if !app.env_profiles.is_empty() {
+        let (name, color) = match app.active_profile_name() {
+            Some(name) => (name, app.theme.accent),
+            None => ("none", app.theme.fg_dim),
+        };
+        spans.push(Span::styled(
+            format!("[env: {}] ", name),
+            Style::default().fg(color),
+        ));
+    }
+
+    if let Some(ref sequence) = app.sequence {
+        let (step, total) = sequence.progress();
+        spans.push(Span::styled(
+            format!(
+                "[sequence {}/{}: {}] ",
+                step,
+                total,
+                sequence.current().display_name
+            ),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(ref watch) = app.watch {
+        spans.push(Span::styled(
+            format!("[watching: {}] ", watch.target.display_name),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     // Append selected item details inline
     match app.selected_item() {
         Some(TreeItem::Function(func)) => {
@@ -142,10 +381,46 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
                     .fg(app.theme.secondary)
                     .add_modifier(Modifier::BOLD),
             ));
-            if !func.description.is_empty() {
+            if !func.aliases.is_empty() {
+                spans.push(Span::styled(
+                    format!("  (aka {})", func.aliases.join(", ")),
+                    Style::default().fg(app.theme.fg_dim),
+                ));
+            }
+            if let Some(ref usage) = func.usage {
+                spans.push(Span::styled(
+                    format!("  usage: {usage}"),
+                    Style::default().fg(app.theme.accent),
+                ));
+            }
+            if func.sudo {
+                spans.push(Span::styled(
+                    "  \u{26a0} runs with sudo",
+                    Style::default()
+                        .fg(app.theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if let Some(env) = app.command_history.env_for(&func) {
+                spans.push(Span::styled(
+                    format!("  env: {}", format_env_pairs(env)),
+                    Style::default().fg(app.theme.success),
+                ));
+            }
+            if let Some(ref info) = app.selected_git_info {
+                spans.push(Span::styled(
+                    format!(
+                        "  \u{2387} {} \u{b7} {} \u{b7} {}",
+                        info.hash, info.author, info.relative_date
+                    ),
+                    Style::default().fg(app.theme.fg_dim),
+                ));
+            }
+            // Only the first line of a multi-line description fits here
+            if let Some(summary) = func.description.lines().next().filter(|l| !l.is_empty()) {
                 spans.push(Span::styled("  ", Style::default()));
                 spans.push(Span::styled(
-                    func.description.clone(),
+                    summary.to_string(),
                     Style::default()
                         .fg(app.theme.fg)
                         .add_modifier(Modifier::ITALIC),
@@ -210,6 +485,15 @@ fn render_script_tree(frame: &mut Frame, app: &mut App, area: Rect) {
 
     // Calculate visible viewport height (subtract 2 for borders)
     let visible_height = area.height.saturating_sub(2) as usize;
+    let inner_width = area.width.saturating_sub(2) as usize;
+    app.script_list_height = visible_height;
+    // Store inner area for mouse hit-testing in the event loop
+    app.script_list_area = Some((
+        area.x + 1,
+        area.y + 1,
+        area.width.saturating_sub(2),
+        area.height.saturating_sub(2),
+    ));
 
     // Ensure the selected item is visible
     app.ensure_selected_visible(visible_height);
@@ -238,9 +522,9 @@ fn render_script_tree(frame: &mut Frame, app: &mut App, area: Rect) {
                 TreeItem::Category(category) => {
                     let is_expanded = app.is_category_expanded(category);
                     let icon = if is_expanded { "\u{25bc}" } else { "\u{25b6}" };
-                    // Use the display name from the app (which includes emoji from filename)
-                    let display_name = app.get_category_display_name(category);
-                    let content = format!("{} {}", icon, display_name);
+                    // The display name (with the emoji from the filename, or
+                    // else the icon of the category's main script type)
+                    let content = format!("{} {}", icon, app.category_label(category));
                     ListItem::new(content).style(style)
                 }
                 TreeItem::Function(func) => {
@@ -249,8 +533,59 @@ fn render_script_tree(frame: &mut Frame, app: &mut App, area: Rect) {
                         .as_ref()
                         .map(|e| format!("{} ", e))
                         .unwrap_or_default();
-                    let content = format!("    {}{}", emoji_prefix, func.display_name);
-                    ListItem::new(content).style(style)
+                    let sudo_suffix = if func.sudo { " \u{1f512}" } else { "" };
+                    let available = app.is_available(func);
+                    let missing_suffix = if available {
+                        ""
+                    } else {
+                        "  \u{26a0} binary not found"
+                    };
+                    // Commands marked for a sequence show their step number
+                    let indent = app
+                        .mark_position(func)
+                        .map_or_else(|| "    ".to_string(), |step| format!("{:>3} ", step));
+                    let label = format!(
+                        "{}{}{}{}{}",
+                        indent, emoji_prefix, func.display_name, sudo_suffix, missing_suffix
+                    );
+                    // Commands whose tool is missing are dimmed
+                    let style = if available || is_selected {
+                        style
+                    } else {
+                        Style::default().fg(app.theme.fg_dim)
+                    };
+                    let badge = app
+                        .command_history
+                        .get(func)
+                        .map(format_status_badge)
+                        .unwrap_or_default();
+                    let content = if badge.is_empty() {
+                        label
+                    } else {
+                        fit_with_badge(&label, &badge, inner_width)
+                    };
+                    // Frequently and recently used entries get a dot for their
+                    // last recorded outcome
+                    let last_exit_code = (func.category == FREQUENTLY_USED_CATEGORY
+                        || func.category == RECENTLY_USED_CATEGORY)
+                        .then(|| app.last_exit_code(func))
+                        .flatten();
+                    match (last_exit_code, content.strip_prefix("    ")) {
+                        (Some(code), Some(rest)) => {
+                            let color = if code == 0 {
+                                app.theme.success
+                            } else {
+                                app.theme.error
+                            };
+                            ListItem::new(Line::from(vec![
+                                Span::raw("  "),
+                                Span::styled("\u{25cf}", Style::default().fg(color)),
+                                Span::raw(format!(" {}", rest)),
+                            ]))
+                            .style(style)
+                        }
+                        _ => ListItem::new(content).style(style),
+                    }
                 }
             }
         })
@@ -314,6 +649,164 @@ fn render_empty_output(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/// Render details for a selected function that has no output yet, including
+/// the exact command line Enter would run
+fn render_function_details(frame: &mut Frame, app: &App, func: &ScriptFunction, area: Rect) {
+    let border_color = if app.focus == FocusPane::Output {
+        app.theme.accent
+    } else {
+        app.theme.fg_dim
+    };
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("  {}", func.display_name),
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ];
+    // Wrap inside the borders, keeping the indent on continuation rows
+    let inner_width = usize::from(area.width.saturating_sub(2));
+    text.extend(
+        wrap_indented(&func.description, 2, inner_width)
+            .into_iter()
+            .map(|row| Line::from(Span::styled(row, Style::default().fg(app.theme.fg)))),
+    );
+
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "  Command",
+        Style::default()
+            .fg(app.theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )));
+    let command = match app.script_file_for(func) {
+        Some(script_file) => {
+            preview_command(func, script_file, &app.preview_options(func, script_file))
+        }
+        None => "<no script file found>".to_string(),
+    };
+    text.push(Line::from(vec![
+        Span::styled("  $ ", Style::default().fg(app.theme.accent)),
+        Span::styled(command, Style::default().fg(app.theme.fg)),
+    ]));
+
+    text.push(Line::from(""));
+    if app.is_available(func) {
+        text.push(Line::from(Span::styled(
+            "  Press Enter to run it",
+            Style::default().fg(app.theme.fg_dim),
+        )));
+    } else {
+        text.push(Line::from(Span::styled(
+            format!(
+                "  \u{26a0} '{}' is not installed or not in PATH, so this command cannot run",
+                app.script_file_for(func)
+                    .and_then(crate::script::ScriptFile::binary)
+                    .unwrap_or("its tool")
+            ),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("\u{1f4cb} Details")
+                .border_style(Style::default().fg(border_color)),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Word-wrap each line of `text` to `width` columns, indenting every row by
+/// `indent` spaces. Blank lines are kept as paragraph breaks; a word longer
+/// than a row gets a row of its own.
+fn wrap_indented(text: &str, indent: usize, width: usize) -> Vec<String> {
+    let available = width.saturating_sub(indent).max(1);
+    let pad = " ".repeat(indent);
+    let mut rows = Vec::new();
+
+    for line in text.lines() {
+        let mut row = String::new();
+        let mut row_width = 0;
+        for word in line.split_whitespace() {
+            let word_width = Span::raw(word).width();
+            if row_width > 0 && row_width + 1 + word_width > available {
+                rows.push(format!("{}{}", pad, row));
+                row.clear();
+                row_width = 0;
+            }
+            if row_width > 0 {
+                row.push(' ');
+                row_width += 1;
+            }
+            row.push_str(word);
+            row_width += word_width;
+        }
+        rows.push(format!("{}{}", pad, row).trim_end().to_string());
+    }
+    rows
+}
+
+/// Render details for a selected category header (name, size and description)
+fn render_category_details(frame: &mut Frame, app: &App, category: &str, area: Rect) {
+    let count = app
+        .functions
+        .iter()
+        .filter(|f| f.category == category)
+        .count();
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("  {}", app.get_category_display_name(category)),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![Span::styled(
+            format!("  {} functions", count),
+            Style::default().fg(app.theme.fg_dim),
+        )]),
+        Line::from(""),
+    ];
+
+    match app.get_category_description(category) {
+        Some(description) => {
+            text.extend(description.lines().map(|line| {
+                Line::from(Span::styled(
+                    format!("  {}", line),
+                    Style::default().fg(app.theme.fg),
+                ))
+            }));
+        }
+        None => text.push(Line::from(Span::styled(
+            "  No description available",
+            Style::default()
+                .fg(app.theme.fg_dim)
+                .add_modifier(Modifier::ITALIC),
+        ))),
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("\u{1f4c1} Category")
+                .border_style(Style::default().fg(app.theme.fg_dim)),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, area);
+}
+
 /// Render the inline terminal output panel with PTY content
 fn render_terminal_output(frame: &mut Frame, app: &mut App, area: Rect) {
     let status = app.current_execution_status();
@@ -356,8 +849,7 @@ fn render_terminal_output(frame: &mut Frame, app: &mut App, area: Rect) {
     let title = match status {
         ExecutionStatus::Idle => "\u{1f4ac} Output".to_string(),
         ExecutionStatus::Running => {
-            let spinner = SPINNER_CHARS[(app.animation_tick as usize) % SPINNER_CHARS.len()];
-            format!("{} Running: {}", spinner, display_name)
+            format!("{} Running: {}", app.spinner_frame(), display_name)
         }
         ExecutionStatus::Succeeded => {
             format!("✅ {}", display_name)
@@ -380,7 +872,7 @@ fn render_terminal_output(frame: &mut Frame, app: &mut App, area: Rect) {
         }
     };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .title(title)
         .border_style(
@@ -389,6 +881,39 @@ fn render_terminal_output(frame: &mut Frame, app: &mut App, area: Rect) {
                 .add_modifier(border_modifier),
         );
 
+    // Running command has gone quiet: it may be sitting on a prompt
+    if pty_is_selected && app.awaiting_input {
+        let hint = if app.focus == FocusPane::Output {
+            " \u{2328} Waiting for input? Type to respond "
+        } else {
+            " \u{2328} Waiting for input? Focus output [Tab] and type "
+        };
+        block = block.title_bottom(Line::from(Span::styled(
+            hint,
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    // Output search prompt while typing, match position once searched
+    if app.output_search_input {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" /{}\u{2588} ", app.output_search_query),
+            Style::default().fg(app.theme.accent),
+        )));
+    } else if app.has_output_search() {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(
+                " /{}  [{}/{}] ",
+                app.output_search_query,
+                app.output_search_index + 1,
+                app.output_search_matches.len()
+            ),
+            Style::default().fg(app.theme.accent),
+        )));
+    }
+
     // Get the inner area (inside the border)
     let inner_area = block.inner(area);
 
@@ -411,8 +936,36 @@ fn render_terminal_output(frame: &mut Frame, app: &mut App, area: Rect) {
         let terminal_view = TerminalView::new(parser)
             .scroll_offset(app.output_scroll)
             .selection(has_selection, app.mouse_sel_start, app.mouse_sel_end)
-            .selection_bg(app.theme.selection_bg);
+            .selection_bg(app.theme.selection_bg)
+            .wrap(app.wrap_output)
+            .line_numbers(app.show_line_numbers, Style::default().fg(app.theme.fg_dim))
+            .search(
+                &app.output_search_matches,
+                app.output_search_query.chars().count(),
+                app.current_output_match(),
+            )
+            .search_styles(
+                Style::default().fg(app.theme.bg).bg(app.theme.secondary),
+                Style::default().fg(app.theme.bg).bg(app.theme.accent),
+            );
         frame.render_widget(terminal_view, inner_area);
+
+        let total = total_content_lines(parser);
+        let viewport = total.saturating_sub(max_scrollback(parser));
+        if let Some(mut state) = output_scrollbar_state(app.output_scroll, total, viewport) {
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(Style::default().fg(app.theme.fg_dim));
+            frame.render_stateful_widget(
+                scrollbar,
+                area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut state,
+            );
+        }
     }
 
     // Store inner area for mouse hit-testing in the event loop
@@ -424,25 +977,110 @@ fn render_terminal_output(frame: &mut Frame, app: &mut App, area: Rect) {
     ));
 }
 
+/// Scrollbar state for the output pane, or `None` when the content fits.
+///
+/// `offset` counts rows up from the bottom, like `App::output_scroll`, while
+/// the scrollbar counts from the top: with `total` lines and `viewport` of
+/// them visible there are `total - viewport + 1` positions, the last one
+/// being the bottom.
+pub fn output_scrollbar_state(
+    offset: usize,
+    total: usize,
+    viewport: usize,
+) -> Option<ScrollbarState> {
+    let max_offset = total.checked_sub(viewport).filter(|&max| max > 0)?;
+    Some(
+        ScrollbarState::new(max_offset + 1)
+            .position(max_offset.saturating_sub(offset))
+            .viewport_content_length(viewport),
+    )
+}
+
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     let help_text = if app.search_mode {
         "[\u{2191}\u{2193}] Navigate  [Enter] Execute  [ESC] Exit Search  [Backspace] Delete"
+            .to_string()
     } else {
         match app.focus {
             FocusPane::Details | FocusPane::ScriptList => {
-                "[\u{2191}\u{2193}/jk] Navigate  [\u{2190}\u{2192}/hl] Collapse/Expand  [/] Search  [t] Theme  [i] Info  [Enter] Toggle/Execute  [Tab] Switch  [Q] Quit"
+                let keys = keymap::keymap();
+                let key = |action| keys.char_key(KeyContext::Normal, action).unwrap_or_default();
+                let pair = |prefix: &str, first, second| {
+                    let chars = format!("{}{}", key(first), key(second));
+                    if chars.is_empty() {
+                        prefix.to_string()
+                    } else {
+                        format!("{}/{}", prefix, chars)
+                    }
+                };
+                format!(
+                    "[{}] Navigate  [{}] Collapse/Expand  [{}/{}] All  [{}] Filter  [{}] Search  [{}] Categories  [{}] Palette  [{}] Theme  [{}] Run: {}  [{}] Re-run  [{}] Env  [{}] Info  [{}] Help  [Enter] Toggle/Execute  {}[Tab] Switch  [{}] Quit",
+                    pair("\u{2191}\u{2193}", Action::Next, Action::Previous),
+                    pair("\u{2190}\u{2192}", Action::Collapse, Action::Expand),
+                    key(Action::ExpandAll),
+                    key(Action::CollapseAll),
+                    key(Action::CycleTypeFilter),
+                    key(Action::StartSearch),
+                    key(Action::JumpToCategory),
+                    key(Action::OpenPalette),
+                    key(Action::OpenThemePicker),
+                    key(Action::ToggleExecutionMode),
+                    app.execution_mode.label(),
+                    key(Action::Rerun),
+                    key(Action::EditEnv),
+                    key(Action::ToggleInfo),
+                    key(Action::ToggleHelp),
+                    if app.is_command_running() {
+                        format!("[{}] Cancel  ", key(Action::CancelCommand))
+                    } else {
+                        String::new()
+                    },
+                    key(Action::Quit)
+                )
             }
             FocusPane::Output => {
-                "[jk] Scroll  [Ctrl+d/u] Half-page  [G] Bottom  [gg] Top  [Mouse] Select+Copy  [Esc/q] Back  [Tab] Switch"
+                "[jk] Scroll  [Ctrl+d/u] Half-page  [G] Bottom  [gg] Top  [/] Search  [n/N] Next/Prev  [y] Copy all  [s] Save  [w] Wrap  [l] Lines  [z] Fullscreen  [Mouse] Select+Copy  [?] Help  [Esc/q] Back  [Tab] Switch".to_string()
             }
         }
     };
 
-    let footer = Paragraph::new(help_text)
-        .style(Style::default().fg(app.theme.fg_dim))
-        .block(Block::default());
+    let footer = match app.status_message() {
+        Some(message) => {
+            Paragraph::new(message.to_string()).style(Style::default().fg(app.theme.accent))
+        }
+        None => Paragraph::new(help_text).style(Style::default().fg(app.theme.fg_dim)),
+    }
+    .block(Block::default());
 
-    frame.render_widget(footer, area);
+    // Elapsed time of the selected command, live while it runs (the tick
+    // loop redraws it), its total duration once it finished
+    let Some(duration) = app.current_execution_duration() else {
+        frame.render_widget(footer, area);
+        return;
+    };
+    let status = app.current_execution_status();
+    let timer_color = match status {
+        ExecutionStatus::Running => app.theme.secondary,
+        ExecutionStatus::Succeeded => app.theme.success,
+        ExecutionStatus::Failed => app.theme.error,
+        ExecutionStatus::Idle => app.theme.fg_dim,
+    };
+    let spinner = if status == ExecutionStatus::Running {
+        format!("{} ", app.spinner_frame())
+    } else {
+        String::new()
+    };
+    let timer = Span::styled(
+        format!(" {}\u{23f1} {} ", spinner, format_elapsed(duration)),
+        Style::default()
+            .fg(timer_color)
+            .add_modifier(Modifier::BOLD),
+    );
+    let timer_width = u16::try_from(timer.width()).unwrap_or(u16::MAX);
+    let [help_area, timer_area] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(timer_width)]).areas(area);
+    frame.render_widget(footer, help_area);
+    frame.render_widget(Paragraph::new(Line::from(timer)), timer_area);
 }
 
 fn render_info_modal(frame: &mut Frame, app: &App, area: Rect) {
@@ -517,6 +1155,80 @@ fn render_info_modal(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(info_modal, modal_area);
 }
 
+/// Render the keybinding reference: the script list bindings on the left and
+/// the search, output and running-command bindings on the right
+fn render_help_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let sections = keymap::help_sections();
+    let key_width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(keys, _)| keys.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    let section_lines = |context: KeyContext, rows: &[(String, &'static str)]| {
+        let mut lines = vec![Line::from(Span::styled(
+            context.label(),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        lines.extend(rows.iter().map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("  {keys:<key_width$}  "),
+                    Style::default().fg(app.theme.secondary),
+                ),
+                Span::styled(*description, Style::default().fg(app.theme.fg)),
+            ])
+        }));
+        lines.push(Line::from(""));
+        lines
+    };
+
+    let mut left: Vec<Line> = Vec::new();
+    let mut right: Vec<Line> = Vec::new();
+    for (context, rows) in &sections {
+        let lines = section_lines(*context, rows);
+        if *context == KeyContext::Normal {
+            left.extend(lines);
+        } else {
+            right.extend(lines);
+        }
+    }
+
+    let content_height = left.len().max(right.len()) as u16;
+    let modal_width: u16 = 110.min(area.width.saturating_sub(2));
+    let modal_height: u16 = (content_height + 2).min(area.height.saturating_sub(2));
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let help_line = Line::from(vec![Span::styled(
+        " [?] or [Esc] to close ",
+        Style::default().fg(app.theme.fg_dim),
+    )]);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" \u{2328} Keybindings ")
+        .title_bottom(help_line)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.bg));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    frame.render_widget(Paragraph::new(left), columns[0]);
+    frame.render_widget(Paragraph::new(right), columns[1]);
+}
+
 fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
     let themes = Theme::all();
     let total = themes.len();
@@ -578,3 +1290,658 @@ fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
 
     frame.render_widget(list, modal_area);
 }
+
+/// Render the Terraform var file picker: "No var file", then the directory's
+/// `.tfvars` files
+fn render_var_file_picker(frame: &mut Frame, app: &App, picker: &VarFilePicker, area: Rect) {
+    let entries: Vec<&str> = std::iter::once("(no var file)")
+        .chain(picker.files.iter().map(String::as_str))
+        .collect();
+
+    let longest = entries
+        .iter()
+        .map(|e| Span::raw(*e).width())
+        .max()
+        .unwrap_or(0) as u16;
+    let modal_width: u16 = (longest + 8).clamp(44, area.width.saturating_sub(4).max(1));
+    let modal_height: u16 = (entries.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let items: Vec<ListItem> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let is_selected = i == picker.index;
+            let marker = if is_selected { "\u{25b6} " } else { "  " };
+            let style = if is_selected {
+                Style::default()
+                    .fg(app.theme.bg)
+                    .bg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.fg)
+            };
+            ListItem::new(format!("{}{}", marker, entry)).style(style)
+        })
+        .collect();
+
+    let help_line = Line::from(vec![Span::styled(
+        " [\u{2191}\u{2193}] Navigate  [Enter] Use  [Esc] Cancel",
+        Style::default().fg(app.theme.fg_dim),
+    )]);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Terraform var file ")
+                .title_bottom(help_line)
+                .border_style(Style::default().fg(app.theme.accent)),
+        )
+        .style(Style::default().bg(app.theme.bg));
+
+    frame.render_widget(list, modal_area);
+}
+
+/// Render the category jump overlay: a filter line above the matching categories
+fn render_category_jump(frame: &mut Frame, app: &App, area: Rect) {
+    let matches = app.category_jump_matches();
+
+    let modal_width: u16 = 50.min(area.width.saturating_sub(4));
+    let modal_height: u16 =
+        (matches.len() as u16 + 5).clamp(7, area.height.saturating_sub(4).max(7));
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let help_line = Line::from(vec![Span::styled(
+        " [\u{2191}\u{2193}] Navigate  [Enter] Jump  [Esc] Cancel",
+        Style::default().fg(app.theme.fg_dim),
+    )]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" \u{1f4c2} Jump to Category ")
+        .title_bottom(help_line)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.bg));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled(" > ", Style::default().fg(app.theme.accent)),
+        Span::styled(
+            app.category_jump_query.clone(),
+            Style::default().fg(app.theme.fg),
+        ),
+    ]));
+    frame.render_widget(query, chunks[0]);
+
+    if matches.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "  No matching categories",
+            Style::default()
+                .fg(app.theme.fg_dim)
+                .add_modifier(Modifier::ITALIC),
+        ));
+        frame.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = matches
+        .iter()
+        .map(|cat| ListItem::new(format!("  {}", app.get_category_display_name(cat))))
+        .collect();
+
+    let list = List::new(items)
+        .style(Style::default().fg(app.theme.fg))
+        .highlight_style(
+            Style::default()
+                .fg(app.theme.bg)
+                .bg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    let mut state = ListState::default().with_selected(Some(app.category_jump_index));
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+fn render_command_palette(frame: &mut Frame, app: &App, area: Rect) {
+    let results = app.palette_results();
+
+    let modal_width: u16 = 70.min(area.width.saturating_sub(4));
+    let modal_height: u16 = (results.len() as u16 + 5).clamp(7, (area.height * 2 / 3).max(7));
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let help_line = Line::from(vec![Span::styled(
+        " [\u{2191}\u{2193}] Navigate  [Enter] Run  [Esc] Cancel",
+        Style::default().fg(app.theme.fg_dim),
+    )]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" \u{26a1} Run Command ")
+        .title_bottom(help_line)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.bg));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled(" : ", Style::default().fg(app.theme.accent)),
+        Span::styled(app.palette_query.clone(), Style::default().fg(app.theme.fg)),
+    ]));
+    frame.render_widget(query, chunks[0]);
+
+    if results.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "  No matching commands",
+            Style::default()
+                .fg(app.theme.fg_dim)
+                .add_modifier(Modifier::ITALIC),
+        ));
+        frame.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = results
+        .iter()
+        .map(|func| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("  {}", func.display_name)),
+                Span::styled(
+                    format!(
+                        "  {} \u{b7} {:?}",
+                        app.get_category_display_name(&func.category),
+                        func.script_type
+                    ),
+                    Style::default().fg(app.theme.fg_dim),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .style(Style::default().fg(app.theme.fg))
+        .highlight_style(
+            Style::default()
+                .fg(app.theme.bg)
+                .bg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    let mut state = ListState::default().with_selected(Some(app.palette_index));
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+fn render_env_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(func) = app.env_target.as_ref() else {
+        return;
+    };
+
+    let is_make = app.env_kind == OverrideKind::MakeVars;
+    let modal_width: u16 = 70.min(area.width);
+    let modal_height: u16 = if is_make { 10 } else { 8 }.min(area.height);
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let (message, message_style) = match app.env_error {
+        Some(ref error) => (error.clone(), Style::default().fg(app.theme.error)),
+        None => (
+            "Space-separated KEY=VALUE pairs; leave empty to clear".to_string(),
+            Style::default().fg(app.theme.fg_dim),
+        ),
+    };
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" $ ", Style::default().fg(app.theme.accent)),
+            Span::styled(app.env_input.clone(), Style::default().fg(app.theme.fg)),
+            Span::styled("\u{2588}", Style::default().fg(app.theme.accent)),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(format!(" {}", message), message_style)]),
+    ];
+    if is_make {
+        let suggestions = if app.make_var_candidates.is_empty() {
+            "No variables assigned in the Makefile".to_string()
+        } else {
+            format!("Makefile variables: {}", app.make_var_candidates.join(", "))
+        };
+        text.push(Line::from(""));
+        text.push(Line::from(vec![Span::styled(
+            format!(" {}", suggestions),
+            Style::default().fg(app.theme.fg_dim),
+        )]));
+    }
+
+    let title = if is_make {
+        format!(" \u{1f528} Make variables for {} ", func.display_name)
+    } else {
+        format!(" \u{1f331} Env for {} ", func.display_name)
+    };
+
+    let help_line = Line::from(vec![Span::styled(
+        " [Enter] Save  [Esc] Cancel",
+        Style::default().fg(app.theme.fg_dim),
+    )]);
+
+    let modal = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_bottom(help_line)
+                .border_style(Style::default().fg(app.theme.accent)),
+        )
+        .style(Style::default().bg(app.theme.bg));
+
+    frame.render_widget(modal, modal_area);
+}
+
+/// Render the "explain command" modal: what running the selected function
+/// starts, where, and with which environment
+fn render_explain_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((func, plan)) = app.explain.as_ref() else {
+        return;
+    };
+
+    let label = |name: &str| {
+        Span::styled(
+            format!(" {:<12}", name),
+            Style::default().fg(app.theme.accent),
+        )
+    };
+    let value = |text: String| Span::styled(text, Style::default().fg(app.theme.fg));
+
+    let mut text = vec![Line::from("")];
+    match plan {
+        Ok(plan) => {
+            let program_path = match plan.program_path {
+                Some(ref path) => value(path.display().to_string()),
+                None => Span::styled(
+                    "\u{26a0} not found in PATH",
+                    Style::default().fg(app.theme.error),
+                ),
+            };
+            text.push(Line::from(vec![
+                label("Program"),
+                value(plan.program.clone()),
+            ]));
+            text.push(Line::from(vec![label("Resolved"), program_path]));
+            if let Some(ref manager) = plan.package_manager {
+                text.push(Line::from(vec![
+                    label("Package mgr"),
+                    value(manager.clone()),
+                ]));
+            }
+            text.push(Line::from(vec![
+                label("Arguments"),
+                value(match plan.args.split_first() {
+                    Some((first, rest)) => format_command_line(first, rest),
+                    None => "(none)".to_string(),
+                }),
+            ]));
+            text.push(Line::from(vec![
+                label("Directory"),
+                value(plan.working_dir.display().to_string()),
+            ]));
+            text.push(Line::from(vec![
+                label("Environment"),
+                value(if plan.env.is_empty() {
+                    "(inherited)".to_string()
+                } else {
+                    format_env_pairs(&plan.env)
+                }),
+            ]));
+            text.push(Line::from(""));
+            text.push(Line::from(vec![
+                Span::styled(" $ ", Style::default().fg(app.theme.accent)),
+                value(plan.command_line()),
+            ]));
+        }
+        Err(error) => text.push(Line::from(Span::styled(
+            format!(" Cannot build the command: {}", error),
+            Style::default().fg(app.theme.error),
+        ))),
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        " Press any key to close",
+        Style::default().fg(app.theme.fg_dim),
+    )));
+
+    let modal_width: u16 = 80.min(area.width);
+    // Leave room for the wrapped command line
+    let modal_height = u16::try_from(text.len() + 4)
+        .unwrap_or(u16::MAX)
+        .min(area.height);
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let modal = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" \u{1f50d} {} ", func.display_name))
+                .border_style(Style::default().fg(app.theme.accent)),
+        )
+        .style(Style::default().bg(app.theme.bg))
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(modal, modal_area);
+}
+
+fn render_confirmation_modal(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(pending) = app.pending_confirmation.as_ref() else {
+        return;
+    };
+    let func = &pending.func;
+
+    let modal_width: u16 = 60.min(area.width);
+    let modal_height: u16 = 9.min(area.height);
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let (question_suffix, warning, hint, title) = match pending.reason {
+        ConfirmReason::Sudo => (
+            " with sudo?",
+            "This command runs with elevated privileges.",
+            "Type your password in the output pane if prompted.",
+            " \u{1f512} Confirm ",
+        ),
+        ConfirmReason::Destructive => (
+            "?",
+            "This command may change or delete resources.",
+            "Make sure you are targeting the right environment.",
+            " \u{26a0}\u{fe0f} Confirm ",
+        ),
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Run ", Style::default().fg(app.theme.fg)),
+            Span::styled(
+                func.display_name.clone(),
+                Style::default()
+                    .fg(app.theme.secondary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(question_suffix, Style::default().fg(app.theme.fg)),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            warning,
+            Style::default().fg(app.theme.error),
+        )]),
+        Line::from(vec![Span::styled(
+            hint,
+            Style::default().fg(app.theme.fg_dim),
+        )]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "[y/Enter] Run  [n/Esc] Cancel",
+            Style::default().fg(app.theme.fg_dim),
+        )]),
+    ];
+
+    let modal = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(app.theme.error)),
+        )
+        .style(Style::default().bg(app.theme.bg));
+
+    frame.render_widget(modal, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    fn state(status: ExecutionStatus, duration: Duration) -> ExecutionState {
+        let finished_at = Instant::now();
+        ExecutionState {
+            status,
+            parser: Arc::new(Mutex::new(vt100::Parser::new(24, 80, 0))),
+            exit_code: None,
+            started_at: finished_at.checked_sub(duration).unwrap_or(finished_at),
+            finished_at: (status != ExecutionStatus::Running).then_some(finished_at),
+            display_name: "Build".to_string(),
+            category: "build".to_string(),
+            auto_follow: true,
+        }
+    }
+
+    #[test]
+    fn test_plain_style_drops_colors() {
+        let base_bg = Color::Black;
+        let bold = Style::default()
+            .fg(Color::Cyan)
+            .bg(base_bg)
+            .add_modifier(Modifier::BOLD);
+        assert_eq!(
+            plain_style(bold, base_bg),
+            Style::default().add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            plain_style(Style::default().fg(Color::Red), base_bg),
+            Style::default()
+        );
+
+        // A highlighted row keeps standing out
+        let selected = Style::default().fg(base_bg).bg(Color::Cyan);
+        assert_eq!(
+            plain_style(selected, base_bg),
+            Style::default().add_modifier(Modifier::REVERSED)
+        );
+    }
+
+    #[test]
+    fn test_body_layout() {
+        let area = Rect::new(0, 3, 100, 40);
+        assert_eq!(body_layout(area, true), vec![area]);
+
+        let split = body_layout(area, false);
+        assert_eq!(split.len(), 2);
+        assert_eq!(split[0].width + split[1].width, area.width);
+        assert_eq!(split[0].width, 20);
+    }
+
+    /// Rows of the thumb when the output scrollbar is drawn on a track of
+    /// `height` cells
+    fn scrollbar_thumb(offset: usize, total: usize, viewport: usize, height: u16) -> Vec<u16> {
+        use ratatui::widgets::StatefulWidget;
+
+        let mut state = output_scrollbar_state(offset, total, viewport).unwrap();
+        let area = Rect::new(0, 0, 1, height);
+        let mut buf = Buffer::empty(area);
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .render(area, &mut buf, &mut state);
+        (0..height)
+            .filter(|&y| buf[(0, y)].symbol() == "█")
+            .collect()
+    }
+
+    #[test]
+    fn test_output_scrollbar_hidden_when_content_fits() {
+        assert!(output_scrollbar_state(0, 0, 0).is_none());
+        assert!(output_scrollbar_state(0, 20, 24).is_none());
+        assert!(output_scrollbar_state(0, 24, 24).is_none());
+        assert!(output_scrollbar_state(0, 25, 24).is_some());
+    }
+
+    #[test]
+    fn test_output_scrollbar_position() {
+        // At the bottom (offset 0) the thumb is at the end of the track
+        assert_eq!(
+            output_scrollbar_state(0, 100, 10),
+            Some(
+                ScrollbarState::new(91)
+                    .position(90)
+                    .viewport_content_length(10)
+            )
+        );
+        // Scrolled all the way up it is at the start
+        assert_eq!(
+            output_scrollbar_state(90, 100, 10),
+            Some(
+                ScrollbarState::new(91)
+                    .position(0)
+                    .viewport_content_length(10)
+            )
+        );
+        // An offset past the top stays at the start
+        assert_eq!(
+            output_scrollbar_state(500, 100, 10),
+            output_scrollbar_state(90, 100, 10)
+        );
+    }
+
+    #[test]
+    fn test_output_scrollbar_thumb() {
+        // A tenth of the content is visible: the thumb is a tenth of the track
+        assert_eq!(scrollbar_thumb(0, 100, 10, 10), vec![9]);
+        assert_eq!(scrollbar_thumb(90, 100, 10, 10), vec![0]);
+        assert_eq!(scrollbar_thumb(45, 100, 10, 10), vec![5]);
+
+        // Half of it is visible: the thumb covers half of the track
+        assert_eq!(scrollbar_thumb(0, 20, 10, 10), vec![5, 6, 7, 8, 9]);
+        assert_eq!(scrollbar_thumb(10, 20, 10, 10), vec![0, 1, 2, 3, 4]);
+
+        // Long output still gets a one-cell thumb
+        assert_eq!(scrollbar_thumb(0, 10_000, 10, 10), vec![9]);
+    }
+
+    #[test]
+    fn test_wrap_indented() {
+        assert_eq!(
+            wrap_indented("Deploy the app to the cluster", 2, 16),
+            vec!["  Deploy the app", "  to the cluster"]
+        );
+        // Each line wraps on its own and blank lines stay
+        assert_eq!(
+            wrap_indented("Restore the backup\n\nStops the app", 2, 12),
+            vec!["  Restore", "  the backup", "", "  Stops the", "  app"]
+        );
+        assert_eq!(
+            wrap_indented("supercalifragilistic", 2, 8),
+            vec!["  supercalifragilistic"]
+        );
+        assert!(wrap_indented("", 2, 20).is_empty());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(1200)), "1.2s");
+        assert_eq!(format_duration(Duration::from_millis(50)), "0.1s");
+        assert_eq!(format_duration(Duration::from_secs(184)), "3m04s");
+        assert_eq!(format_duration(Duration::from_secs(3720)), "1h02m");
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::ZERO), "00:00");
+        assert_eq!(format_elapsed(Duration::from_millis(12_900)), "00:12");
+        assert_eq!(format_elapsed(Duration::from_secs(184)), "03:04");
+        assert_eq!(format_elapsed(Duration::from_secs(3599)), "59:59");
+        assert_eq!(format_elapsed(Duration::from_secs(3600)), "1:00:00");
+        assert_eq!(format_elapsed(Duration::from_secs(37_230)), "10:20:30");
+    }
+
+    #[test]
+    fn test_status_badge_succeeded() {
+        let badge = format_status_badge(&state(
+            ExecutionStatus::Succeeded,
+            Duration::from_millis(1200),
+        ));
+        assert_eq!(badge, "\u{2705} 1.2s");
+    }
+
+    #[test]
+    fn test_status_badge_failed() {
+        let badge = format_status_badge(&state(ExecutionStatus::Failed, Duration::from_secs(184)));
+        assert_eq!(badge, "\u{274c} 3m04s");
+    }
+
+    #[test]
+    fn test_status_badge_running() {
+        let badge = format_status_badge(&state(ExecutionStatus::Running, Duration::from_secs(5)));
+        let (spinner, elapsed) = badge.split_once(' ').unwrap();
+        assert!(spinner
+            .chars()
+            .all(|c| crate::ui::app::SPINNER_FRAMES.contains(&c)));
+        assert!(elapsed.ends_with('s'));
+    }
+
+    #[test]
+    fn test_status_badge_idle_is_empty() {
+        assert_eq!(
+            format_status_badge(&state(ExecutionStatus::Idle, Duration::ZERO)),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_fit_with_badge() {
+        assert_eq!(fit_with_badge("    Build", "ok", 14), "    Build   ok");
+        // Long labels are truncated so the badge stays visible
+        assert_eq!(
+            fit_with_badge("    Deploy Everything", "ok", 14),
+            "    Deploy\u{2026} ok"
+        );
+    }
+}
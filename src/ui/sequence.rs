@@ -0,0 +1,168 @@
+//! # Command Sequences
+//!
+//! Runs several commands one after another, e.g. `build`, then `test`, then
+//! `deploy`.
+//!
+//! ## Overview
+//!
+//! Commands are marked in the script list with `Space`, in the order they
+//! should run, and `S` starts the sequence. Each step runs inline like a
+//! command started with Enter, so its output stays reviewable on its own; the
+//! next step starts on the tick the previous one finishes. A failed step stops
+//! the sequence unless `sequence_continue_on_error` is set in the user config.
+//!
+//! Only one [`Sequence`] runs at a time. Running another command while it is
+//! in progress interrupts it.
+
+use crate::script::ScriptFunction;
+
+/// How a sequence ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceOutcome {
+    /// Every step ran; the display names of the steps that failed, if any
+    Finished { failed: Vec<String> },
+    /// The step with this display name failed and the rest were skipped
+    Stopped { step: String },
+}
+
+/// Commands run in order, one at a time
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    steps: Vec<ScriptFunction>,
+    /// Index of the running step
+    current: usize,
+    continue_on_error: bool,
+    /// Display names of the steps that failed so far
+    failed: Vec<String>,
+}
+
+/// What to do after a step finished
+#[derive(Debug, Clone)]
+pub enum SequenceStep {
+    /// Run this step next
+    Run(ScriptFunction),
+    /// The sequence is over
+    Done(SequenceOutcome),
+}
+
+impl Sequence {
+    /// A sequence of `steps` (in run order), starting at the first. `None`
+    /// when there are no steps.
+    pub fn new(steps: Vec<ScriptFunction>, continue_on_error: bool) -> Option<Self> {
+        if steps.is_empty() {
+            return None;
+        }
+        Some(Self {
+            steps,
+            current: 0,
+            continue_on_error,
+            failed: Vec::new(),
+        })
+    }
+
+    /// The running step
+    pub fn current(&self) -> &ScriptFunction {
+        &self.steps[self.current]
+    }
+
+    /// The running step's position (1-based) and the number of steps
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current + 1, self.steps.len())
+    }
+
+    /// Record the result of the running step and move on
+    pub fn advance(&mut self, succeeded: bool) -> SequenceStep {
+        if !succeeded {
+            let name = self.current().display_name.clone();
+            if !self.continue_on_error {
+                return SequenceStep::Done(SequenceOutcome::Stopped { step: name });
+            }
+            self.failed.push(name);
+        }
+        if self.current + 1 < self.steps.len() {
+            self.current += 1;
+            SequenceStep::Run(self.current().clone())
+        } else {
+            SequenceStep::Done(SequenceOutcome::Finished {
+                failed: std::mem::take(&mut self.failed),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::ScriptType;
+
+    fn step(name: &str) -> ScriptFunction {
+        ScriptFunction {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            category: "Make".to_string(),
+            description: String::new(),
+            emoji: None,
+            ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
+            script_type: ScriptType::Makefile,
+        }
+    }
+
+    /// The name of the next step, or the outcome
+    fn next(sequence: &mut Sequence, succeeded: bool) -> Result<String, SequenceOutcome> {
+        match sequence.advance(succeeded) {
+            SequenceStep::Run(func) => Ok(func.name),
+            SequenceStep::Done(outcome) => Err(outcome),
+        }
+    }
+
+    #[test]
+    fn test_empty_sequence() {
+        assert!(Sequence::new(Vec::new(), false).is_none());
+    }
+
+    #[test]
+    fn test_sequence_runs_steps_in_order() {
+        let mut sequence =
+            Sequence::new(vec![step("build"), step("test"), step("deploy")], false).unwrap();
+        assert_eq!(sequence.current().name, "build");
+        assert_eq!(sequence.progress(), (1, 3));
+
+        assert_eq!(next(&mut sequence, true), Ok("test".to_string()));
+        assert_eq!(sequence.progress(), (2, 3));
+        assert_eq!(next(&mut sequence, true), Ok("deploy".to_string()));
+        assert_eq!(
+            next(&mut sequence, true),
+            Err(SequenceOutcome::Finished { failed: Vec::new() })
+        );
+    }
+
+    #[test]
+    fn test_sequence_stops_on_failure() {
+        let mut sequence = Sequence::new(vec![step("build"), step("test")], false).unwrap();
+        assert_eq!(
+            next(&mut sequence, false),
+            Err(SequenceOutcome::Stopped {
+                step: "build".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_sequence_continue_on_error() {
+        let mut sequence =
+            Sequence::new(vec![step("lint"), step("test"), step("build")], true).unwrap();
+        assert_eq!(next(&mut sequence, false), Ok("test".to_string()));
+        assert_eq!(next(&mut sequence, true), Ok("build".to_string()));
+        assert_eq!(
+            next(&mut sequence, false),
+            Err(SequenceOutcome::Finished {
+                failed: vec!["lint".to_string(), "build".to_string()]
+            })
+        );
+    }
+}
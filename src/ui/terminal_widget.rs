@@ -10,6 +10,9 @@
 //! - Bold, italic, underline, inverse attribute support
 //! - Scrollback buffer navigation
 //! - Visual selection mode for text copying
+//! - Optional soft-wrap of lines wider than the pane
+//! - Optional line-number gutter
+//! - Highlighting of output search matches
 //!
 //! ## Scrollback Design
 //!
@@ -19,6 +22,29 @@
 //! `cell(row, col)` method accesses the "visible" rows (scrollback + screen
 //! rows combined). We use this mechanism directly: our `scroll_offset`
 //! (0 = at bottom) maps to `set_scrollback(scroll_offset)`.
+//!
+//! ## Soft Wrap
+//!
+//! When wrapping is enabled and the PTY is wider than the pane, each visible
+//! vt100 row is split into display rows at render time (see [`wrap_row`]).
+//! The view stays anchored to the bottom of the output, or to the top once
+//! scrolled all the way up. Selection coordinates stay in vt100 `(row, col)`
+//! space; [`wrapped_source_position`] maps a click in the pane back to it.
+//!
+//! ## Line Numbers
+//!
+//! With the gutter enabled, each full-buffer line up to the last one with
+//! content is numbered from 1, counting scrollback, so a line keeps its number
+//! while scrolling. The gutter is as wide as the largest number plus one space
+//! ([`gutter_width`]) and the output is shifted right by that much;
+//! [`content_column`] maps a click back to the output column.
+//!
+//! ## Output Search
+//!
+//! [`find_matches`] searches the whole buffer (scrollback included) and
+//! returns match positions as `(line, col)`, where `line` counts from the
+//! first scrollback line. [`scroll_offset_for_line`] turns a line back into a
+//! scroll offset that brings it into view.
 
 use ratatui::{
     buffer::Buffer,
@@ -26,6 +52,7 @@ use ratatui::{
     style::{Color, Modifier, Style},
     widgets::Widget,
 };
+use std::ops::Range;
 use std::sync::{Arc, Mutex};
 
 /// Convert a vt100 color to a ratatui Color
@@ -52,6 +79,19 @@ pub struct TerminalView<'a> {
     selection_end: Option<(usize, usize)>,
     /// Background color for selected text (from theme)
     selection_bg_color: Color,
+    /// Wrap rows wider than the render area instead of truncating them
+    wrap: bool,
+    /// Show a line-number gutter left of the output
+    line_numbers: bool,
+    gutter_style: Style,
+    /// Search match starts as `(line, col)` in full-buffer coordinates, sorted
+    search_matches: &'a [(usize, usize)],
+    /// Length of every search match, in columns
+    search_len: usize,
+    /// The match the user navigated to, highlighted with `current_match_style`
+    current_match: Option<(usize, usize)>,
+    search_style: Style,
+    current_match_style: Style,
 }
 
 impl<'a> TerminalView<'a> {
@@ -63,6 +103,14 @@ impl<'a> TerminalView<'a> {
             selection_start: None,
             selection_end: None,
             selection_bg_color: Color::Rgb(60, 60, 80),
+            wrap: false,
+            line_numbers: false,
+            gutter_style: Style::default().fg(Color::DarkGray),
+            search_matches: &[],
+            search_len: 0,
+            current_match: None,
+            search_style: Style::default().bg(Color::Yellow).fg(Color::Black),
+            current_match_style: Style::default().bg(Color::LightRed).fg(Color::Black),
         }
     }
 
@@ -88,6 +136,93 @@ impl<'a> TerminalView<'a> {
         self
     }
 
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Show line numbers in a gutter, styled with `style`
+    pub fn line_numbers(mut self, enabled: bool, style: Style) -> Self {
+        self.line_numbers = enabled;
+        self.gutter_style = style;
+        self
+    }
+
+    /// Highlight search matches of `len` columns starting at `matches`
+    /// (full-buffer `(line, col)`, sorted), with `current` stood out
+    pub fn search(
+        mut self,
+        matches: &'a [(usize, usize)],
+        len: usize,
+        current: Option<(usize, usize)>,
+    ) -> Self {
+        self.search_matches = matches;
+        self.search_len = len;
+        self.current_match = current;
+        self
+    }
+
+    /// Styles for search matches and for the current match
+    pub fn search_styles(mut self, matches: Style, current: Style) -> Self {
+        self.search_style = matches;
+        self.current_match_style = current;
+        self
+    }
+
+    /// The search match covering `(line, col)` in full-buffer coordinates, if any
+    fn search_match_at(&self, line: usize, col: usize) -> Option<(usize, usize)> {
+        if self.search_len == 0 {
+            return None;
+        }
+        // Matches are sorted, so only those starting on this line need checking
+        let first = self.search_matches.partition_point(|&(l, _)| l < line);
+        self.search_matches[first..]
+            .iter()
+            .take_while(|&&(l, _)| l == line)
+            .find(|&&(_, start)| col >= start && col < start + self.search_len)
+            .copied()
+    }
+
+    /// Build the display style for a cell, including selection and search
+    /// highlights. `top_line` is the full-buffer line shown in visible row 0.
+    fn cell_style(&self, cell: &vt100::Cell, row: usize, col: usize, top_line: usize) -> Style {
+        let mut style = Style::default();
+
+        if let Some(fg) = vt100_color_to_ratatui(cell.fgcolor()) {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = vt100_color_to_ratatui(cell.bgcolor()) {
+            style = style.bg(bg);
+        }
+        if cell.bold() {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if cell.italic() {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if cell.underline() {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        if cell.inverse() {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+
+        if let Some(found) = self.search_match_at(top_line + row, col) {
+            style = style.patch(if Some(found) == self.current_match {
+                self.current_match_style
+            } else {
+                self.search_style
+            });
+        }
+
+        // Apply selection highlight (mouse drag)
+        if self.is_selected(row, col) {
+            style = style.bg(self.selection_bg_color);
+        }
+
+        style
+    }
+
     /// Check if a cell position is within the selection range.
     /// Positions are in the visible coordinate space (row, col).
     fn is_selected(&self, row: usize, col: usize) -> bool {
@@ -136,22 +271,68 @@ impl Widget for TerminalView<'_> {
 
         // Save the original scrollback offset and set our desired offset
         let original_scrollback = parser.screen().scrollback();
+        let line_count = if self.line_numbers {
+            numbered_lines(&mut parser)
+        } else {
+            0
+        };
+        let gutter = if self.line_numbers {
+            gutter_width(line_count)
+        } else {
+            0
+        };
+        // Too narrow for the gutter and any output: leave it out
+        let gutter = if gutter < area.width as usize {
+            gutter
+        } else {
+            0
+        };
+        let content_x = area.x + gutter as u16;
+        parser.screen_mut().set_scrollback(usize::MAX);
+        let max = parser.screen().scrollback();
         parser.screen_mut().set_scrollback(self.scroll_offset);
+        // Full-buffer line shown in the top visible row (for search highlights)
+        let top_line = max - parser.screen().scrollback();
 
         // Read screen dimensions
         let (screen_rows, screen_cols) = parser.screen().size();
         let visible_rows = area.height as usize;
-        let visible_cols = area.width as usize;
+        let visible_cols = area.width as usize - gutter;
 
         // Collect cell data into a temporary buffer to avoid borrow conflicts.
-        // Each entry: (display_y, display_x, contents, style)
+        // Each entry: (buf_x, buf_y, contents, style)
         let mut cells: Vec<(u16, u16, String, Style)> = Vec::new();
+        // Gutter labels: (buf_y, line number)
+        let mut numbers: Vec<(u16, usize)> = Vec::new();
 
-        {
+        if self.wrap && screen_cols as usize > visible_cols {
+            let lines = wrapped_view(&mut parser, self.scroll_offset, visible_cols, visible_rows);
+            let screen = parser.screen();
+            for (display_y, (row, cols)) in lines.into_iter().enumerate() {
+                // Only the first segment of a wrapped line is numbered
+                if cols.start == 0 {
+                    numbers.push((area.y + display_y as u16, top_line + row as usize));
+                }
+                for (display_x, col) in cols.enumerate() {
+                    if let Some(cell) = screen.cell(row, col as u16) {
+                        let ch = cell.contents();
+                        let ch = if ch.is_empty() { " " } else { ch };
+                        let style = self.cell_style(cell, row as usize, col, top_line);
+                        cells.push((
+                            content_x + display_x as u16,
+                            area.y + display_y as u16,
+                            ch.to_string(),
+                            style,
+                        ));
+                    }
+                }
+            }
+        } else {
             let screen = parser.screen();
             for display_y in 0..visible_rows.min(screen_rows as usize) {
+                numbers.push((area.y + display_y as u16, top_line + display_y));
                 for display_x in 0..visible_cols.min(screen_cols as usize) {
-                    let buf_x = area.x + display_x as u16;
+                    let buf_x = content_x + display_x as u16;
                     let buf_y = area.y + display_y as u16;
 
                     if buf_x >= area.x + area.width || buf_y >= area.y + area.height {
@@ -161,33 +342,7 @@ impl Widget for TerminalView<'_> {
                     if let Some(cell) = screen.cell(display_y as u16, display_x as u16) {
                         let ch = cell.contents();
                         let ch = if ch.is_empty() { " " } else { ch };
-
-                        let mut style = Style::default();
-
-                        if let Some(fg) = vt100_color_to_ratatui(cell.fgcolor()) {
-                            style = style.fg(fg);
-                        }
-                        if let Some(bg) = vt100_color_to_ratatui(cell.bgcolor()) {
-                            style = style.bg(bg);
-                        }
-                        if cell.bold() {
-                            style = style.add_modifier(Modifier::BOLD);
-                        }
-                        if cell.italic() {
-                            style = style.add_modifier(Modifier::ITALIC);
-                        }
-                        if cell.underline() {
-                            style = style.add_modifier(Modifier::UNDERLINED);
-                        }
-                        if cell.inverse() {
-                            style = style.add_modifier(Modifier::REVERSED);
-                        }
-
-                        // Apply selection highlight (mouse drag)
-                        if self.is_selected(display_y, display_x) {
-                            style = style.bg(self.selection_bg_color);
-                        }
-
+                        let style = self.cell_style(cell, display_y, display_x, top_line);
                         cells.push((buf_x, buf_y, ch.to_string(), style));
                     }
                 }
@@ -198,12 +353,161 @@ impl Widget for TerminalView<'_> {
         for (x, y, ch, style) in cells {
             buf.set_string(x, y, &ch, style);
         }
+        if gutter > 0 {
+            for (y, line) in numbers.into_iter().filter(|&(_, line)| line < line_count) {
+                let label = format!("{:>width$} ", line + 1, width = gutter - 1);
+                buf.set_string(area.x, y, label, self.gutter_style);
+            }
+        }
 
         // Restore the original scrollback offset
         parser.screen_mut().set_scrollback(original_scrollback);
     }
 }
 
+/// Width of the line-number gutter for `line_count` numbered lines: the
+/// digits of the largest number plus a separating space
+pub fn gutter_width(line_count: usize) -> usize {
+    line_count.max(1).to_string().len() + 1
+}
+
+/// The output column shown at `col` of a pane whose gutter numbers
+/// `line_count` lines (clicks in the gutter map to the first column)
+pub fn content_column(col: usize, line_count: usize) -> usize {
+    col.saturating_sub(gutter_width(line_count))
+}
+
+/// Number of full-buffer lines up to the last one with content, i.e. the
+/// lines that get a number. The scrollback offset is left unchanged.
+fn numbered_lines(parser: &mut vt100::Parser) -> usize {
+    let original = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let max = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(0);
+    let screen = parser.screen();
+    let (rows, cols) = screen.size();
+    let screen_lines = (0..rows)
+        .rev()
+        .find(|&row| row_content_len(screen, row, cols) > 0)
+        .map_or(0, |row| row as usize + 1);
+    parser.screen_mut().set_scrollback(original);
+    max + screen_lines
+}
+
+/// Number of lines numbered in the gutter (see [`gutter_width`])
+pub fn numbered_line_count(parser: &Arc<Mutex<vt100::Parser>>) -> usize {
+    match parser.lock() {
+        Ok(mut p) => numbered_lines(&mut p),
+        Err(_) => 0,
+    }
+}
+
+/// Split a row whose first `len` columns hold content into display segments
+/// of at most `width` columns. An empty row still takes one display row.
+pub fn wrap_row(len: usize, width: usize) -> Vec<Range<usize>> {
+    if len == 0 || width == 0 {
+        return vec![Range::default()];
+    }
+    (0..len)
+        .step_by(width)
+        .map(|start| start..(start + width).min(len))
+        .collect()
+}
+
+/// Number of leading columns in a row that hold text or a background color
+fn row_content_len(screen: &vt100::Screen, row: u16, cols: u16) -> usize {
+    (0..cols)
+        .rev()
+        .find(|&col| {
+            screen.cell(row, col).is_some_and(|cell| {
+                cell.has_contents() || vt100_color_to_ratatui(cell.bgcolor()).is_some()
+            })
+        })
+        .map_or(0, |col| col as usize + 1)
+}
+
+/// Set the scrollback to `scroll_offset` and return the `(row, columns)`
+/// segments shown when wrapping the view into `width` x `height`.
+///
+/// The caller is responsible for restoring the original scrollback.
+fn wrapped_view(
+    parser: &mut vt100::Parser,
+    scroll_offset: usize,
+    width: usize,
+    height: usize,
+) -> Vec<(u16, Range<usize>)> {
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let max = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(scroll_offset);
+    // Scrolled all the way up: show the first line at the top
+    let anchor_top = scroll_offset > 0 && scroll_offset >= max;
+
+    let screen = parser.screen();
+    let (rows, cols) = screen.size();
+    let mut lens: Vec<usize> = (0..rows)
+        .map(|row| row_content_len(screen, row, cols))
+        .collect();
+    if !anchor_top {
+        // Blank rows below the output would otherwise push it off the top
+        while lens.last() == Some(&0) {
+            lens.pop();
+        }
+    }
+
+    let mut lines: Vec<(u16, Range<usize>)> = lens
+        .iter()
+        .enumerate()
+        .flat_map(|(row, &len)| {
+            wrap_row(len, width)
+                .into_iter()
+                .map(move |cols| (row as u16, cols))
+        })
+        .collect();
+    if anchor_top {
+        lines.truncate(height);
+    } else {
+        lines.drain(..lines.len().saturating_sub(height));
+    }
+    lines
+}
+
+/// Map a `(row, col)` position in a wrapped pane of `width` x `height` back
+/// to the vt100 `(row, col)` it displays, for selection and copying.
+///
+/// Positions are returned unchanged when the screen fits without wrapping.
+pub fn wrapped_source_position(
+    parser: &Arc<Mutex<vt100::Parser>>,
+    scroll_offset: usize,
+    width: usize,
+    height: usize,
+    position: (usize, usize),
+) -> (usize, usize) {
+    let mut parser = match parser.lock() {
+        Ok(p) => p,
+        Err(_) => return position,
+    };
+    let (_, screen_cols) = parser.screen().size();
+    if screen_cols as usize <= width {
+        return position;
+    }
+
+    let original_scrollback = parser.screen().scrollback();
+    let lines = wrapped_view(&mut parser, scroll_offset, width, height);
+    parser.screen_mut().set_scrollback(original_scrollback);
+
+    let (row, col) = position;
+    match lines.get(row) {
+        Some((source_row, cols)) => (
+            *source_row as usize,
+            (cols.start + col).min(screen_cols as usize - 1),
+        ),
+        // Below the last display row: extend to the end of the output
+        None => lines.last().map_or(position, |(source_row, _)| {
+            (*source_row as usize, screen_cols as usize - 1)
+        }),
+    }
+}
+
 /// Get the text contents of a selection range from the vt100 screen.
 ///
 /// Start and end are `(row, col)` pairs in the visible coordinate space
@@ -269,6 +573,129 @@ pub fn get_selected_text(
         .to_string()
 }
 
+/// Get the full text of the terminal: every scrollback line followed by the
+/// screen, with trailing whitespace and trailing blank lines removed.
+pub fn get_full_text(parser: &Arc<Mutex<vt100::Parser>>) -> String {
+    let mut parser = match parser.lock() {
+        Ok(p) => p,
+        Err(_) => return String::new(),
+    };
+
+    let original_scrollback = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let max = parser.screen().scrollback();
+    let (_, screen_cols) = parser.screen().size();
+
+    // At scrollback `n`, the top row of the view is scrollback line `max - n`
+    let mut lines = Vec::new();
+    for offset in (1..=max).rev() {
+        parser.screen_mut().set_scrollback(offset);
+        lines.extend(parser.screen().rows(0, screen_cols).take(1));
+    }
+    parser.screen_mut().set_scrollback(0);
+    lines.extend(parser.screen().rows(0, screen_cols));
+
+    parser.screen_mut().set_scrollback(original_scrollback);
+
+    lines
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Find every case-insensitive occurrence of `query` in the whole buffer,
+/// scrollback included.
+///
+/// Returns match starts as `(line, col)` in reading order, where `line` 0 is
+/// the first scrollback line and `col` is the cell column. Matches do not
+/// span lines. An empty query matches nothing.
+pub fn find_matches(parser: &Arc<Mutex<vt100::Parser>>, query: &str) -> Vec<(usize, usize)> {
+    let needle: Vec<char> = query.chars().map(fold_case).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let mut parser = match parser.lock() {
+        Ok(p) => p,
+        Err(_) => return Vec::new(),
+    };
+
+    let original_scrollback = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let max = parser.screen().scrollback();
+    let (rows, cols) = parser.screen().size();
+
+    // At scrollback `n`, the top row of the view is line `max - n`
+    let mut lines: Vec<Vec<char>> = Vec::with_capacity(max + rows as usize);
+    for offset in (1..=max).rev() {
+        parser.screen_mut().set_scrollback(offset);
+        lines.push(row_chars(parser.screen(), 0, cols));
+    }
+    parser.screen_mut().set_scrollback(0);
+    for row in 0..rows {
+        lines.push(row_chars(parser.screen(), row, cols));
+    }
+    parser.screen_mut().set_scrollback(original_scrollback);
+
+    let mut matches = Vec::new();
+    for (line, chars) in lines.iter().enumerate() {
+        if chars.len() < needle.len() {
+            continue;
+        }
+        for col in 0..=chars.len() - needle.len() {
+            if chars[col..col + needle.len()] == needle[..] {
+                matches.push((line, col));
+            }
+        }
+    }
+    matches
+}
+
+/// Lowercase a character for case-insensitive matching, one char per cell
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// One case-folded character per cell of a visible row (blank cells are spaces)
+fn row_chars(screen: &vt100::Screen, row: u16, cols: u16) -> Vec<char> {
+    (0..cols)
+        .map(|col| {
+            screen
+                .cell(row, col)
+                .and_then(|cell| cell.contents().chars().next())
+                .map_or(' ', fold_case)
+        })
+        .collect()
+}
+
+/// The scroll offset that brings full-buffer `line` into view, keeping
+/// `current_offset` if the line is already visible and otherwise showing the
+/// line at the top of the pane.
+pub fn scroll_offset_for_line(
+    parser: &Arc<Mutex<vt100::Parser>>,
+    line: usize,
+    current_offset: usize,
+) -> usize {
+    let mut parser = match parser.lock() {
+        Ok(p) => p,
+        Err(_) => return current_offset,
+    };
+    let original = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let max = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(original);
+    let (rows, _) = parser.screen().size();
+
+    let top = max - current_offset.min(max);
+    if (top..top + rows as usize).contains(&line) {
+        current_offset
+    } else {
+        max.saturating_sub(line)
+    }
+}
+
 /// Get the maximum number of scrollback rows available.
 ///
 /// This temporarily sets scrollback to `usize::MAX` to find how many
@@ -441,6 +868,38 @@ mod tests {
         assert!(max > 0, "Expected some scrollback, got 0");
     }
 
+    // --- get_full_text tests ---
+
+    #[test]
+    fn test_get_full_text_includes_scrollback() {
+        let parser = make_parser(3, 20, 100);
+        parser
+            .lock()
+            .unwrap()
+            .process(b"line 1\r\nline 2\r\nline 3\r\nline 4\r\nline 5");
+
+        assert_eq!(
+            get_full_text(&parser),
+            "line 1\nline 2\nline 3\nline 4\nline 5"
+        );
+        // The user's scroll position is left untouched
+        assert_eq!(parser.lock().unwrap().screen().scrollback(), 0);
+    }
+
+    #[test]
+    fn test_get_full_text_trims_blank_lines() {
+        let parser = make_parser(10, 20, 100);
+        parser.lock().unwrap().process(b"hello   \r\nworld\r\n");
+
+        assert_eq!(get_full_text(&parser), "hello\nworld");
+    }
+
+    #[test]
+    fn test_get_full_text_empty() {
+        let parser = make_parser(5, 20, 100);
+        assert_eq!(get_full_text(&parser), "");
+    }
+
     // --- get_selected_text tests ---
 
     #[test]
@@ -466,8 +925,258 @@ mod tests {
         assert!(text.contains("line2"));
     }
 
+    // --- wrap tests ---
+
+    #[test]
+    fn test_wrap_row_splits_at_width() {
+        assert_eq!(wrap_row(10, 4), vec![0..4, 4..8, 8..10]);
+        assert_eq!(wrap_row(8, 4), vec![0..4, 4..8]);
+    }
+
+    #[test]
+    fn test_wrap_row_short_and_empty_rows() {
+        assert_eq!(wrap_row(3, 10), vec![0..3]);
+        assert_eq!(wrap_row(0, 10), vec![0..0]);
+        assert_eq!(wrap_row(5, 0), vec![0..0]);
+    }
+
+    fn render_rows(parser: &Arc<Mutex<vt100::Parser>>, wrap: bool, w: u16, h: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, w, h);
+        let mut buf = Buffer::empty(area);
+        TerminalView::new(parser).wrap(wrap).render(area, &mut buf);
+        (0..h)
+            .map(|y| {
+                (0..w)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    // --- line number tests ---
+
+    #[test]
+    fn test_gutter_width() {
+        assert_eq!(gutter_width(0), 2);
+        assert_eq!(gutter_width(9), 2);
+        assert_eq!(gutter_width(10), 3);
+        assert_eq!(gutter_width(999), 4);
+        assert_eq!(gutter_width(1000), 5);
+    }
+
+    #[test]
+    fn test_content_column_skips_gutter() {
+        // 120 lines: 3 digits and a space
+        assert_eq!(content_column(4, 120), 0);
+        assert_eq!(content_column(10, 120), 6);
+        // Clicks in the gutter land on the first column
+        assert_eq!(content_column(1, 120), 0);
+        assert_eq!(content_column(2, 5), 0);
+    }
+
+    #[test]
+    fn test_numbered_line_count_ignores_blank_rows() {
+        let parser = make_parser(5, 20, 100);
+        assert_eq!(numbered_line_count(&parser), 0);
+        parser.lock().unwrap().process(b"a\r\nb\r\nc");
+        assert_eq!(numbered_line_count(&parser), 3);
+
+        let parser = make_parser(3, 20, 100);
+        parser
+            .lock()
+            .unwrap()
+            .process(b"1\r\n2\r\n3\r\n4\r\n5\r\n6\r\n7\r\n8\r\n9\r\n10\r\n11");
+        assert_eq!(numbered_line_count(&parser), 11);
+    }
+
+    fn render_numbered(
+        parser: &Arc<Mutex<vt100::Parser>>,
+        offset: usize,
+        w: u16,
+        h: u16,
+    ) -> Vec<String> {
+        let area = Rect::new(0, 0, w, h);
+        let mut buf = Buffer::empty(area);
+        TerminalView::new(parser)
+            .scroll_offset(offset)
+            .line_numbers(true, Style::default())
+            .render(area, &mut buf);
+        (0..h)
+            .map(|y| {
+                (0..w)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_line_numbers_are_stable_when_scrolling() {
+        let parser = make_parser(3, 20, 100);
+        parser
+            .lock()
+            .unwrap()
+            .process(b"l1\r\nl2\r\nl3\r\nl4\r\nl5\r\nl6\r\nl7\r\nl8\r\nl9\r\nl10");
+
+        assert_eq!(
+            render_numbered(&parser, 0, 10, 3),
+            vec![" 8 l8", " 9 l9", "10 l10"]
+        );
+        assert_eq!(
+            render_numbered(&parser, 7, 10, 3),
+            vec![" 1 l1", " 2 l2", " 3 l3"]
+        );
+    }
+
+    #[test]
+    fn test_render_line_numbers_skip_blank_rows() {
+        let parser = make_parser(4, 20, 100);
+        parser.lock().unwrap().process(b"abcdefghijkl\r\nxyz");
+
+        assert_eq!(
+            render_numbered(&parser, 0, 7, 4),
+            vec!["1 abcde", "2 xyz", "", ""]
+        );
+    }
+
+    #[test]
+    fn test_render_wraps_long_lines() {
+        let parser = make_parser(5, 20, 100);
+        parser.lock().unwrap().process(b"abcdefghijkl\r\nxyz\r\n");
+
+        assert_eq!(
+            render_rows(&parser, true, 5, 5),
+            vec!["abcde", "fghij", "kl", "xyz", ""]
+        );
+        assert_eq!(
+            render_rows(&parser, false, 5, 5),
+            vec!["abcde", "xyz", "", "", ""]
+        );
+    }
+
+    #[test]
+    fn test_render_wrap_keeps_latest_output_visible() {
+        let parser = make_parser(5, 20, 100);
+        parser
+            .lock()
+            .unwrap()
+            .process(b"first\r\nabcdefghij\r\nlast");
+
+        assert_eq!(render_rows(&parser, true, 4, 3), vec!["efgh", "ij", "last"]);
+    }
+
+    #[test]
+    fn test_wrapped_source_position_maps_to_cells() {
+        let parser = make_parser(5, 20, 100);
+        parser.lock().unwrap().process(b"abcdefghijkl\r\nxyz\r\n");
+
+        assert_eq!(wrapped_source_position(&parser, 0, 5, 5, (1, 2)), (0, 7));
+        assert_eq!(wrapped_source_position(&parser, 0, 5, 5, (3, 1)), (1, 1));
+        // Below the output: end of the last line
+        assert_eq!(wrapped_source_position(&parser, 0, 5, 5, (4, 0)), (1, 19));
+        // No wrapping needed: unchanged
+        assert_eq!(wrapped_source_position(&parser, 0, 40, 5, (1, 2)), (1, 2));
+    }
+
+    #[test]
+    fn test_wrapped_selection_copies_source_text() {
+        let parser = make_parser(5, 20, 100);
+        parser.lock().unwrap().process(b"abcdefghijkl\r\n");
+
+        let start = wrapped_source_position(&parser, 0, 5, 5, (0, 3));
+        let end = wrapped_source_position(&parser, 0, 5, 5, (1, 1));
+        assert_eq!(get_selected_text(&parser, 0, start, end), "defg");
+    }
+
     // --- vt100_color_to_ratatui tests ---
 
+    // --- output search tests ---
+
+    #[test]
+    fn test_find_matches_case_insensitive() {
+        let parser = parser_with_content("Error: disk\r\nwarning\r\nanother ERROR and error\r\n");
+        assert_eq!(
+            find_matches(&parser, "error"),
+            vec![(0, 0), (2, 8), (2, 18)]
+        );
+        assert_eq!(find_matches(&parser, "ERR"), vec![(0, 0), (2, 8), (2, 18)]);
+    }
+
+    #[test]
+    fn test_find_matches_empty_and_missing_query() {
+        let parser = parser_with_content("hello\r\n");
+        assert!(find_matches(&parser, "").is_empty());
+        assert!(find_matches(&parser, "bye").is_empty());
+    }
+
+    #[test]
+    fn test_find_matches_counts_overlapping_occurrences() {
+        let parser = parser_with_content("aaaa");
+        assert_eq!(find_matches(&parser, "aa"), vec![(0, 0), (0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn test_find_matches_includes_scrollback() {
+        let parser = make_parser(3, 20, 100);
+        parser
+            .lock()
+            .unwrap()
+            .process(b"needle 0\r\nline 1\r\nline 2\r\nline 3\r\nneedle 4");
+
+        // Lines 0 and 1 scrolled off the 3-row screen
+        assert_eq!(find_matches(&parser, "needle"), vec![(0, 0), (4, 0)]);
+        // Searching leaves the view where it was
+        assert_eq!(parser.lock().unwrap().screen().scrollback(), 0);
+    }
+
+    #[test]
+    fn test_scroll_offset_for_line() {
+        let parser = make_parser(3, 20, 100);
+        parser
+            .lock()
+            .unwrap()
+            .process(b"l0\r\nl1\r\nl2\r\nl3\r\nl4\r\nl5");
+
+        // Lines 3-5 are on screen: no scrolling needed
+        assert_eq!(scroll_offset_for_line(&parser, 4, 0), 0);
+        // Line 0 is at the top once scrolled all the way up
+        assert_eq!(scroll_offset_for_line(&parser, 0, 0), 3);
+        assert_eq!(scroll_offset_for_line(&parser, 1, 0), 2);
+        // Already visible at the current offset: stay put
+        assert_eq!(scroll_offset_for_line(&parser, 2, 2), 2);
+    }
+
+    #[test]
+    fn test_render_highlights_search_matches() {
+        let parser = make_parser(3, 20, 100);
+        parser
+            .lock()
+            .unwrap()
+            .process(b"foo 0\r\nbar 1\r\nfoo 2\r\nfoo 3");
+        let matches = find_matches(&parser, "foo");
+        assert_eq!(matches, vec![(0, 0), (2, 0), (3, 0)]);
+
+        let area = Rect::new(0, 0, 10, 3);
+        let mut buf = Buffer::empty(area);
+        let found = Style::default().bg(Color::Blue);
+        let current = Style::default().bg(Color::Red);
+        TerminalView::new(&parser)
+            .search(&matches, 3, Some((3, 0)))
+            .search_styles(found, current)
+            .render(area, &mut buf);
+
+        // Screen shows lines 1-3: "bar 1", "foo 2", "foo 3"
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+        assert_eq!(buf[(0, 1)].bg, Color::Blue);
+        assert_eq!(buf[(2, 1)].bg, Color::Blue);
+        assert_eq!(buf[(3, 1)].bg, Color::Reset);
+        assert_eq!(buf[(0, 2)].bg, Color::Red);
+    }
+
     #[test]
     fn test_vt100_color_default() {
         assert_eq!(vt100_color_to_ratatui(vt100::Color::Default), None);
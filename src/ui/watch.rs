@@ -0,0 +1,212 @@
+//! # Run and Watch
+//!
+//! Re-runs a command whenever a file in the project changes (`w`), for
+//! test-driven loops like `cargo test` on every save.
+//!
+//! ## Overview
+//!
+//! The project is watched with [`notify`], the platform's file change
+//! notifications (`inotify`, `FSEvents`, ...), so nothing is scanned while the
+//! project is idle. Build output and dependency directories ([`IGNORED_DIRS`])
+//! and the project's exclude patterns (`--exclude`, `exclude` in
+//! `jarvis.toml`) are skipped, so a run that writes to `target/` does not
+//! trigger itself. Ignored top-level directories are not watched at all.
+//!
+//! Only changes count: files merely opened or read (by the command itself,
+//! say) are ignored. Editors often write a file several times per save
+//! (temporary file, rename, formatting), so events go through the `notify`
+//! debouncer: the command re-runs once changes have settled for [`DEBOUNCE`].
+//!
+//! Only one [`Watch`] is active at a time; it stops when dropped.
+
+use crate::script::{ExcludeSet, ScriptFunction};
+use anyhow::{Context, Result};
+use notify_debouncer_full::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, RecommendedCache};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+/// Directories whose changes never trigger a re-run
+pub const IGNORED_DIRS: &[&str] = &["target", "node_modules", ".git", "dist", "build", ".venv"];
+
+/// How long changes must settle before re-running
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Whether a path below `root` is inside one of the [`IGNORED_DIRS`] or
+/// matches an exclude pattern
+pub fn is_ignored(root: &Path, path: &Path, excludes: &ExcludeSet) -> bool {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.components().any(|component| {
+        component
+            .as_os_str()
+            .to_str()
+            .is_some_and(|name| IGNORED_DIRS.contains(&name))
+    }) || excludes.is_excluded(relative)
+}
+
+/// A command re-run whenever the project changes
+pub struct Watch {
+    /// The watched command
+    pub target: ScriptFunction,
+    changes: Receiver<()>,
+    /// Stops watching when dropped
+    _debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+    /// A change settled and the command has not been re-run yet
+    pending: bool,
+}
+
+impl Watch {
+    /// Start watching `root`, skipping the [`IGNORED_DIRS`] and `excludes`
+    pub fn start(target: ScriptFunction, root: &Path, excludes: &ExcludeSet) -> Result<Self> {
+        let (sender, changes) = mpsc::channel();
+        let watched_root = root.to_path_buf();
+        let event_excludes = excludes.clone();
+        let mut debouncer = new_debouncer(DEBOUNCE, None, move |result: DebounceEventResult| {
+            let Ok(events) = result else {
+                return;
+            };
+            if events.iter().any(|event| {
+                !event.kind.is_access()
+                    && event
+                        .paths
+                        .iter()
+                        .any(|path| !is_ignored(&watched_root, path, &event_excludes))
+            }) {
+                let _ = sender.send(());
+            }
+        })
+        .context("Failed to start watching for file changes")?;
+
+        // Watch the root itself for its own files, then each top-level
+        // directory that is not ignored or excluded, so `target/` or
+        // `node_modules/` are never registered with the OS
+        debouncer
+            .watch(root, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch {}", root.display()))?;
+        for entry in std::fs::read_dir(root)
+            .with_context(|| format!("Failed to read {}", root.display()))?
+            .filter_map(Result::ok)
+        {
+            let path = entry.path();
+            if path.is_dir() && !is_ignored(root, &path, excludes) {
+                debouncer
+                    .watch(&path, RecursiveMode::Recursive)
+                    .with_context(|| format!("Failed to watch {}", path.display()))?;
+            }
+        }
+
+        Ok(Self {
+            target,
+            changes,
+            _debouncer: debouncer,
+            pending: false,
+        })
+    }
+
+    /// Whether the command should re-run now: the project changed and the
+    /// changes have settled. Stays `true` until [`Self::mark_rerun`] is
+    /// called, so a re-run can wait for the previous run to stop.
+    pub fn poll(&mut self) -> bool {
+        while self.changes.try_recv().is_ok() {
+            self.pending = true;
+        }
+        self.pending
+    }
+
+    /// Clear the pending re-run once the command has been started again
+    pub fn mark_rerun(&mut self) {
+        self.pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Instant;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_ignored() {
+        let root = Path::new("/repo");
+        let none = ExcludeSet::default();
+        assert!(is_ignored(root, Path::new("/repo/target/debug/app"), &none));
+        assert!(is_ignored(
+            root,
+            Path::new("/repo/web/node_modules/x.js"),
+            &none
+        ));
+        assert!(is_ignored(root, Path::new("/repo/.git/index"), &none));
+        assert!(is_ignored(root, Path::new("/repo/.venv/bin/python"), &none));
+        assert!(!is_ignored(root, Path::new("/repo/src/main.rs"), &none));
+        assert!(!is_ignored(root, Path::new("/repo/src/target.rs"), &none));
+        // Only components below the root count
+        assert!(!is_ignored(
+            Path::new("/home/me/target/repo"),
+            Path::new("/home/me/target/repo/src/lib.rs"),
+            &none
+        ));
+
+        let excludes = ExcludeSet::new(&["vendor", "docs/*.md"]).unwrap();
+        assert!(is_ignored(
+            root,
+            Path::new("/repo/vendor/lib.rs"),
+            &excludes
+        ));
+        assert!(is_ignored(
+            root,
+            Path::new("/repo/docs/guide.md"),
+            &excludes
+        ));
+        assert!(!is_ignored(root, Path::new("/repo/src/lib.rs"), &excludes));
+    }
+
+    fn wait_for_change(watch: &mut Watch, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if watch.poll() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+
+    #[test]
+    fn test_watch_reports_changes_outside_ignored_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+
+        let target = ScriptFunction {
+            name: "test".to_string(),
+            display_name: "Test".to_string(),
+            category: "Project".to_string(),
+            description: String::new(),
+            emoji: None,
+            ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
+            script_type: crate::script::ScriptType::Bash,
+        };
+        let mut watch = Watch::start(target, &root, &ExcludeSet::default()).unwrap();
+
+        fs::write(root.join("target/debug/app"), "binary").unwrap();
+        // Reading is not a change
+        fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(!wait_for_change(&mut watch, Duration::from_millis(1000)));
+
+        fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        assert!(wait_for_change(&mut watch, Duration::from_secs(5)));
+        // Stays pending until the re-run starts
+        assert!(watch.poll());
+        watch.mark_rerun();
+        assert!(!watch.poll());
+    }
+}
@@ -6,7 +6,8 @@
 //! ## Overview
 //!
 //! The usage tracking system helps users quickly access their most-used commands
-//! by displaying a "Frequently Used" category at the top of the TUI.
+//! by displaying a "Frequently Used" category at the top of the TUI, followed
+//! by a "Recently Used" category with the commands run most recently.
 //!
 //! ## Storage
 //!
@@ -16,6 +17,9 @@
 //! - Windows: `%APPDATA%\jarvis\usage\`
 //!
 //! Each project has its own usage file, keyed by a hash of the project path.
+//! The expanded categories and selected item of the script tree are kept the
+//! same way under `jarvis/ui_state/` (see [`UiStateStore`]), and commands
+//! pinned with `p` under `jarvis/pins/` (see [`PinStore`]).
 //!
 //! ## Data Format
 //!
@@ -27,14 +31,21 @@
 //!       "function_name": "build",
 //!       "script_type": "Bash",
 //!       "count": 42,
-//!       "last_used": "2025-02-05T10:30:00Z"
+//!       "last_used": "2025-02-05T10:30:00Z",
+//!       "last_exit_code": 0,
+//!       "last_status": "succeeded"
 //!     }
 //!   }
 //! }
 //! ```
 
+mod pins;
 mod storage;
+mod ui_state;
 
+pub use pins::{PinStore, Pins, PINNED_CATEGORY};
 pub use storage::{
-    ProjectUsage, UsageEntry, UsageTracker, FREQUENTLY_USED_CATEGORY, MAX_FREQUENT_COMMANDS,
+    JsonStore, ProjectUsage, UsageEntry, UsageTracker, FREQUENTLY_USED_CATEGORY,
+    MAX_FREQUENT_COMMANDS, MAX_RECENT_COMMANDS, RECENTLY_USED_CATEGORY,
 };
+pub use ui_state::{SelectedItem, UiState, UiStateStore};
@@ -0,0 +1,121 @@
+//! # Pinned Commands
+//!
+//! Persists the commands a user pinned with `p`, per project. Pinned commands
+//! are listed in a "📌 Pinned" category above "Frequently Used", in the order
+//! they were pinned, independent of usage counts.
+//!
+//! ## Storage Location
+//!
+//! ```text
+//! ~/.local/share/jarvis/pins/
+//! ├── <project-hash-1>.json
+//! └── ...
+//! ```
+//!
+//! Files are keyed by the same project path hash as the usage data.
+
+use super::storage::JsonStore;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Reserved category name for pinned commands
+pub const PINNED_CATEGORY: &str = "📌 Pinned";
+
+/// The pinned commands of a project
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Pins {
+    /// `Type:name` history keys of the pinned commands, in pin order
+    pub pinned: Vec<String>,
+}
+
+impl Pins {
+    /// Whether a command is pinned
+    pub fn contains(&self, key: &str) -> bool {
+        self.pinned.iter().any(|k| k == key)
+    }
+
+    /// Pin a command (appended last) or unpin it. Returns whether it is now pinned.
+    pub fn toggle(&mut self, key: &str) -> bool {
+        if let Some(index) = self.pinned.iter().position(|k| k == key) {
+            self.pinned.remove(index);
+            false
+        } else {
+            self.pinned.push(key.to_string());
+            true
+        }
+    }
+}
+
+/// Loads and saves [`Pins`] for one project
+pub type PinStore = JsonStore<Pins>;
+
+impl JsonStore<Pins> {
+    /// Create a store for a project in the XDG data directory
+    pub fn new(project_path: &Path) -> Result<Self> {
+        Self::in_data_dir(project_path, "pins")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usage::storage::project_file_path;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pins_toggle_keeps_pin_order() {
+        let mut pins = Pins::default();
+        assert!(pins.toggle("Bash:deploy"));
+        assert!(pins.toggle("Makefile:build"));
+        assert!(pins.toggle("Bash:test"));
+        assert!(pins.contains("Makefile:build"));
+
+        assert!(!pins.toggle("Makefile:build"));
+        assert!(!pins.contains("Makefile:build"));
+        assert_eq!(pins.pinned, vec!["Bash:deploy", "Bash:test"]);
+
+        // Re-pinning appends to the end
+        assert!(pins.toggle("Makefile:build"));
+        assert_eq!(
+            pins.pinned,
+            vec!["Bash:deploy", "Bash:test", "Makefile:build"]
+        );
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("my-project");
+        let storage_dir = temp_dir.path().join("pins");
+
+        let store = PinStore::with_storage_dir(&project_path, &storage_dir);
+        assert_eq!(store.load(), Pins::default());
+
+        let pins = Pins {
+            pinned: vec!["Bash:deploy".to_string(), "PackageJson:dev".to_string()],
+        };
+        store.save(&pins).unwrap();
+
+        let reopened = PinStore::with_storage_dir(&project_path, &storage_dir);
+        assert_eq!(reopened.load(), pins);
+
+        // Other projects do not see them
+        let other = PinStore::with_storage_dir(&temp_dir.path().join("other"), &storage_dir);
+        assert_eq!(other.load(), Pins::default());
+    }
+
+    #[test]
+    fn test_store_corrupted_file_loads_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("my-project");
+        let storage_dir = temp_dir.path().join("pins");
+        fs::create_dir_all(&storage_dir).unwrap();
+        fs::write(project_file_path(&storage_dir, &project_path), "not json").unwrap();
+
+        let store = PinStore::with_storage_dir(&project_path, &storage_dir);
+        assert_eq!(store.load(), Pins::default());
+    }
+}
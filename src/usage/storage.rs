@@ -12,21 +12,33 @@
 //! ```
 //!
 //! Each project has its own JSON file, identified by a hash of the project path.
+//! Other per-project data (UI state, pins) is kept the same way through
+//! [`JsonStore`].
 
 use crate::script::ScriptType;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 
-/// Maximum number of frequently used commands to display
+/// Default number of frequently used commands to display
+/// (overridable with `frequent_limit` in the user config)
 pub const MAX_FREQUENT_COMMANDS: usize = 5;
 
 /// Reserved category name for frequently used commands
 pub const FREQUENTLY_USED_CATEGORY: &str = "⭐ Frequently Used";
 
+/// Default number of recently used commands to display
+/// (overridable with `recent_limit` in the user config)
+pub const MAX_RECENT_COMMANDS: usize = 5;
+
+/// Reserved category name for recently used commands
+pub const RECENTLY_USED_CATEGORY: &str = "🕘 Recently Used";
+
 /// A single usage entry tracking how often a command is used
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UsageEntry {
@@ -40,6 +52,31 @@ pub struct UsageEntry {
     pub count: u64,
     /// When this command was last used
     pub last_used: DateTime<Utc>,
+    /// Exit code of the last run (missing in files written by older versions)
+    #[serde(default)]
+    pub last_exit_code: Option<i32>,
+    /// Outcome of the last run: `"succeeded"` or `"failed"`
+    #[serde(default)]
+    pub last_status: Option<String>,
+}
+
+impl UsageEntry {
+    /// Whether the last recorded run succeeded (`None` when unknown)
+    pub fn last_succeeded(&self) -> Option<bool> {
+        self.last_exit_code.map(|code| code == 0)
+    }
+
+    fn set_last_exit_code(&mut self, exit_code: i32) {
+        self.last_exit_code = Some(exit_code);
+        self.last_status = Some(
+            if exit_code == 0 {
+                "succeeded"
+            } else {
+                "failed"
+            }
+            .to_string(),
+        );
+    }
 }
 
 /// Usage data for a specific project
@@ -60,8 +97,25 @@ impl ProjectUsage {
         }
     }
 
-    /// Record a command execution
-    pub fn record_usage(&mut self, function_name: &str, script_type: ScriptType, category: &str) {
+    /// Record a finished command execution and its exit code.
+    ///
+    /// Only successful runs count towards frequency; a failed run just updates
+    /// the last status of a command that is already tracked.
+    pub fn record_usage(
+        &mut self,
+        function_name: &str,
+        script_type: ScriptType,
+        category: &str,
+        exit_code: i32,
+    ) {
+        if exit_code != 0 {
+            if let Some(entry) = self.entries.get_mut(function_name) {
+                entry.last_used = Utc::now();
+                entry.set_last_exit_code(exit_code);
+            }
+            return;
+        }
+
         let entry = self
             .entries
             .entry(function_name.to_string())
@@ -71,10 +125,13 @@ impl ProjectUsage {
                 category: category.to_string(),
                 count: 0,
                 last_used: Utc::now(),
+                last_exit_code: None,
+                last_status: None,
             });
 
         entry.count += 1;
         entry.last_used = Utc::now();
+        entry.set_last_exit_code(exit_code);
         // Update script type and category in case they changed
         entry.script_type = script_type;
         entry.category = category.to_string();
@@ -83,7 +140,15 @@ impl ProjectUsage {
     /// Get the most frequently used commands, sorted by count (descending)
     pub fn get_frequent(&self, limit: usize) -> Vec<&UsageEntry> {
         let mut entries: Vec<_> = self.entries.values().collect();
-        entries.sort_by(|a, b| b.count.cmp(&a.count));
+        entries.sort_by_key(|e| std::cmp::Reverse(e.count));
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Get the most recently used commands, sorted by last use (newest first)
+    pub fn get_recent(&self, limit: usize) -> Vec<&UsageEntry> {
+        let mut entries: Vec<_> = self.entries.values().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.last_used));
         entries.truncate(limit);
         entries
     }
@@ -116,7 +181,7 @@ impl UsageTracker {
         })?;
 
         // Load existing usage data or create new
-        let usage_file = get_usage_file_path(&storage_dir, &project_path);
+        let usage_file = project_file_path(&storage_dir, &project_path);
         let usage = if usage_file.exists() {
             load_usage(&usage_file).unwrap_or_else(|_| ProjectUsage::new(project_path.clone()))
         } else {
@@ -135,7 +200,7 @@ impl UsageTracker {
     pub fn with_storage_dir(project_path: PathBuf, storage_dir: PathBuf) -> Result<Self> {
         fs::create_dir_all(&storage_dir)?;
 
-        let usage_file = get_usage_file_path(&storage_dir, &project_path);
+        let usage_file = project_file_path(&storage_dir, &project_path);
         let usage = if usage_file.exists() {
             load_usage(&usage_file).unwrap_or_else(|_| ProjectUsage::new(project_path.clone()))
         } else {
@@ -149,15 +214,16 @@ impl UsageTracker {
         })
     }
 
-    /// Record a command execution and save to disk
+    /// Record a finished command execution and save to disk
     pub fn record(
         &mut self,
         function_name: &str,
         script_type: ScriptType,
         category: &str,
+        exit_code: i32,
     ) -> Result<()> {
         self.usage
-            .record_usage(function_name, script_type, category);
+            .record_usage(function_name, script_type, category, exit_code);
         self.save()
     }
 
@@ -166,6 +232,16 @@ impl UsageTracker {
         self.usage.get_frequent(limit)
     }
 
+    /// Get the most recently used commands
+    pub fn get_recent(&self, limit: usize) -> Vec<&UsageEntry> {
+        self.usage.get_recent(limit)
+    }
+
+    /// All recorded usage, keyed by function name
+    pub fn entries(&self) -> &HashMap<String, UsageEntry> {
+        &self.usage.entries
+    }
+
     /// Check if there are any recorded usages
     pub fn has_usage_data(&self) -> bool {
         !self.usage.entries.is_empty()
@@ -173,7 +249,7 @@ impl UsageTracker {
 
     /// Save usage data to disk
     fn save(&self) -> Result<()> {
-        let usage_file = get_usage_file_path(&self.storage_dir, &self.project_path);
+        let usage_file = project_file_path(&self.storage_dir, &self.project_path);
         let json =
             serde_json::to_string_pretty(&self.usage).context("Failed to serialize usage data")?;
 
@@ -191,19 +267,73 @@ impl UsageTracker {
 
 /// Get the XDG-compliant storage directory for usage data
 fn get_storage_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("usage"))
+}
+
+/// Get the XDG-compliant application data directory (e.g. `~/.local/share/jarvis`)
+pub fn data_dir() -> Result<PathBuf> {
     let proj_dirs = directories::ProjectDirs::from("", "", "jarvis")
         .context("Failed to determine application data directory")?;
 
-    Ok(proj_dirs.data_dir().join("usage"))
+    Ok(proj_dirs.data_dir().to_path_buf())
 }
 
 /// Generate a unique filename for a project based on its path
-fn get_usage_file_path(storage_dir: &Path, project_path: &Path) -> PathBuf {
+pub fn project_file_path(storage_dir: &Path, project_path: &Path) -> PathBuf {
     // Use a simple hash of the project path for the filename
     let hash = simple_hash(project_path.to_string_lossy().as_ref());
     storage_dir.join(format!("{:016x}.json", hash))
 }
 
+/// Loads and saves one JSON value per project, in a file keyed by the
+/// project path hash
+#[derive(Debug)]
+pub struct JsonStore<T> {
+    /// The data file for this project
+    path: PathBuf,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned + Default> JsonStore<T> {
+    /// Create a store for a project in the `name` directory of the XDG data
+    /// directory
+    pub fn in_data_dir(project_path: &Path, name: &str) -> Result<Self> {
+        Ok(Self::with_storage_dir(
+            project_path,
+            &data_dir()?.join(name),
+        ))
+    }
+
+    /// Create a store with a custom storage directory
+    pub fn with_storage_dir(project_path: &Path, storage_dir: &Path) -> Self {
+        Self {
+            path: project_file_path(storage_dir, project_path),
+            _value: PhantomData,
+        }
+    }
+
+    /// Load the saved value, falling back to the default when the file is
+    /// missing or unreadable
+    pub fn load(&self) -> T {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the value to disk
+    pub fn save(&self, value: &T) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        let json = serde_json::to_string_pretty(value)
+            .with_context(|| format!("Failed to serialize {}", self.path.display()))?;
+        fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
 /// Simple hash function for generating project file names
 fn simple_hash(s: &str) -> u64 {
     use std::collections::hash_map::DefaultHasher;
@@ -241,14 +371,14 @@ mod tests {
     fn test_project_usage_record_usage() {
         let mut usage = ProjectUsage::new(PathBuf::from("/test/project"));
 
-        usage.record_usage("build", ScriptType::Bash, "Build");
+        usage.record_usage("build", ScriptType::Bash, "Build", 0);
         assert_eq!(usage.entries.len(), 1);
         assert_eq!(usage.entries.get("build").unwrap().count, 1);
 
-        usage.record_usage("build", ScriptType::Bash, "Build");
+        usage.record_usage("build", ScriptType::Bash, "Build", 0);
         assert_eq!(usage.entries.get("build").unwrap().count, 2);
 
-        usage.record_usage("test", ScriptType::PackageJson, "Test");
+        usage.record_usage("test", ScriptType::PackageJson, "Test", 0);
         assert_eq!(usage.entries.len(), 2);
         assert_eq!(usage.entries.get("test").unwrap().count, 1);
     }
@@ -259,13 +389,13 @@ mod tests {
 
         // Add some usage data
         for _ in 0..10 {
-            usage.record_usage("build", ScriptType::Bash, "Build");
+            usage.record_usage("build", ScriptType::Bash, "Build", 0);
         }
         for _ in 0..5 {
-            usage.record_usage("test", ScriptType::Bash, "Test");
+            usage.record_usage("test", ScriptType::Bash, "Test", 0);
         }
         for _ in 0..3 {
-            usage.record_usage("lint", ScriptType::Bash, "Lint");
+            usage.record_usage("lint", ScriptType::Bash, "Lint", 0);
         }
 
         let frequent = usage.get_frequent(2);
@@ -297,10 +427,14 @@ mod tests {
         {
             let mut tracker =
                 UsageTracker::with_storage_dir(project_path.clone(), storage_dir.clone()).unwrap();
-            tracker.record("build", ScriptType::Bash, "Build").unwrap();
-            tracker.record("build", ScriptType::Bash, "Build").unwrap();
             tracker
-                .record("test", ScriptType::PackageJson, "Test")
+                .record("build", ScriptType::Bash, "Build", 0)
+                .unwrap();
+            tracker
+                .record("build", ScriptType::Bash, "Build", 0)
+                .unwrap();
+            tracker
+                .record("test", ScriptType::PackageJson, "Test", 0)
                 .unwrap();
         }
 
@@ -332,13 +466,13 @@ mod tests {
         let mut tracker = UsageTracker::with_storage_dir(project_path, storage_dir).unwrap();
 
         for _ in 0..10 {
-            tracker.record("cmd1", ScriptType::Bash, "Cat1").unwrap();
+            tracker.record("cmd1", ScriptType::Bash, "Cat1", 0).unwrap();
         }
         for _ in 0..5 {
-            tracker.record("cmd2", ScriptType::Bash, "Cat2").unwrap();
+            tracker.record("cmd2", ScriptType::Bash, "Cat2", 0).unwrap();
         }
         for _ in 0..3 {
-            tracker.record("cmd3", ScriptType::Bash, "Cat3").unwrap();
+            tracker.record("cmd3", ScriptType::Bash, "Cat3", 0).unwrap();
         }
 
         let frequent = tracker.get_frequent(2);
@@ -347,6 +481,95 @@ mod tests {
         assert_eq!(frequent[1].function_name, "cmd2");
     }
 
+    #[test]
+    fn test_get_frequent_honors_limit() {
+        let mut usage = ProjectUsage::new(PathBuf::from("/test/project"));
+        for name in ["a", "b", "c", "d", "e", "f", "g"] {
+            usage.record_usage(name, ScriptType::Bash, "Cat", 0);
+        }
+
+        assert!(usage.get_frequent(0).is_empty());
+        assert_eq!(usage.get_frequent(3).len(), 3);
+        assert_eq!(usage.get_frequent(6).len(), 6);
+        assert_eq!(usage.get_frequent(100).len(), 7);
+    }
+
+    #[test]
+    fn test_project_usage_get_recent() {
+        let mut usage = ProjectUsage::new(PathBuf::from("/test/project"));
+        for _ in 0..10 {
+            usage.record_usage("build", ScriptType::Bash, "Build", 0);
+        }
+        usage.record_usage("test", ScriptType::Bash, "Test", 0);
+        usage.record_usage("lint", ScriptType::Bash, "Lint", 0);
+
+        let now = Utc::now();
+        for (name, days_ago) in [("build", 3), ("test", 1), ("lint", 2)] {
+            usage.entries.get_mut(name).unwrap().last_used = now - chrono::Duration::days(days_ago);
+        }
+
+        let recent = usage.get_recent(10);
+        let names: Vec<&str> = recent.iter().map(|e| e.function_name.as_str()).collect();
+        assert_eq!(names, vec!["test", "lint", "build"]);
+
+        let recent = usage.get_recent(2);
+        let names: Vec<&str> = recent.iter().map(|e| e.function_name.as_str()).collect();
+        assert_eq!(names, vec!["test", "lint"]);
+        assert!(usage.get_recent(0).is_empty());
+    }
+
+    #[test]
+    fn test_record_usage_updates_exit_code() {
+        let mut usage = ProjectUsage::new(PathBuf::from("/test/project"));
+
+        usage.record_usage("build", ScriptType::Bash, "Build", 0);
+        let entry = usage.entries.get("build").unwrap();
+        assert_eq!(entry.last_exit_code, Some(0));
+        assert_eq!(entry.last_status.as_deref(), Some("succeeded"));
+        assert_eq!(entry.last_succeeded(), Some(true));
+
+        // A failure updates the status without counting as a use
+        usage.record_usage("build", ScriptType::Bash, "Build", 2);
+        let entry = usage.entries.get("build").unwrap();
+        assert_eq!(entry.count, 1);
+        assert_eq!(entry.last_exit_code, Some(2));
+        assert_eq!(entry.last_status.as_deref(), Some("failed"));
+        assert_eq!(entry.last_succeeded(), Some(false));
+
+        // Failures of untracked commands are not recorded
+        usage.record_usage("lint", ScriptType::Bash, "Lint", 1);
+        assert!(!usage.entries.contains_key("lint"));
+    }
+
+    #[test]
+    fn test_load_usage_without_exit_status_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("old.json");
+        fs::write(
+            &path,
+            r#"{
+  "project_path": "/test/project",
+  "entries": {
+    "build": {
+      "function_name": "build",
+      "script_type": "Bash",
+      "category": "Build",
+      "count": 3,
+      "last_used": "2025-02-05T10:30:00Z"
+    }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let usage = load_usage(&path).unwrap();
+        let entry = usage.entries.get("build").unwrap();
+        assert_eq!(entry.count, 3);
+        assert_eq!(entry.last_exit_code, None);
+        assert_eq!(entry.last_status, None);
+        assert_eq!(entry.last_succeeded(), None);
+    }
+
     #[test]
     fn test_simple_hash() {
         let hash1 = simple_hash("/home/user/project1");
@@ -365,7 +588,7 @@ mod tests {
 
         // Create storage dir and write corrupted file
         fs::create_dir_all(&storage_dir).unwrap();
-        let usage_file = get_usage_file_path(&storage_dir, &project_path);
+        let usage_file = project_file_path(&storage_dir, &project_path);
         fs::write(&usage_file, "not valid json").unwrap();
 
         // Should gracefully handle corrupted file
@@ -381,6 +604,8 @@ mod tests {
             category: "Build".to_string(),
             count: 42,
             last_used: Utc::now(),
+            last_exit_code: Some(0),
+            last_status: Some("succeeded".to_string()),
         };
 
         let json = serde_json::to_string(&entry).unwrap();
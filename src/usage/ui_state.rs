@@ -0,0 +1,116 @@
+//! # UI State Storage
+//!
+//! Persists the script tree's expanded categories and selected item per
+//! project, along with the selected Terraform var files, so reopening Jarvis
+//! picks up where the last session left off.
+//!
+//! ## Storage Location
+//!
+//! ```text
+//! ~/.local/share/jarvis/ui_state/
+//! ├── <project-hash-1>.json
+//! └── ...
+//! ```
+//!
+//! Files are keyed by the same project path hash as the usage data.
+
+use super::storage::JsonStore;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The tree item that was selected when the UI state was saved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectedItem {
+    /// A category header, by category key
+    Category(String),
+    /// A command, by its `Type:name` history key
+    Function(String),
+}
+
+/// Per-project UI state restored on startup
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    /// Categories that were expanded, in the order they were opened
+    pub expanded_categories: Vec<String>,
+    /// The selected tree item, if any
+    pub selected: Option<SelectedItem>,
+    /// Terraform var file selected per Terraform directory
+    pub var_files: BTreeMap<String, String>,
+}
+
+/// Loads and saves [`UiState`] for one project
+pub type UiStateStore = JsonStore<UiState>;
+
+impl JsonStore<UiState> {
+    /// Create a store for a project in the XDG data directory
+    pub fn new(project_path: &Path) -> Result<Self> {
+        Self::in_data_dir(project_path, "ui_state")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usage::storage::project_file_path;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn sample_state() -> UiState {
+        UiState {
+            expanded_categories: vec!["deploy".to_string(), "build".to_string()],
+            selected: Some(SelectedItem::Function("Bash:deploy_prod".to_string())),
+            var_files: BTreeMap::from([("/repo/infra".to_string(), "prod.tfvars".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_ui_state_serialization_round_trip() {
+        let state = sample_state();
+        let json = serde_json::to_string(&state).unwrap();
+        let parsed: UiState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, state);
+
+        let category = UiState {
+            expanded_categories: Vec::new(),
+            selected: Some(SelectedItem::Category("build".to_string())),
+            var_files: BTreeMap::new(),
+        };
+        let json = serde_json::to_string(&category).unwrap();
+        assert_eq!(serde_json::from_str::<UiState>(&json).unwrap(), category);
+    }
+
+    #[test]
+    fn test_ui_state_missing_fields_default() {
+        let parsed: UiState = serde_json::from_str("{}").unwrap();
+        assert_eq!(parsed, UiState::default());
+    }
+
+    #[test]
+    fn test_store_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("my-project");
+        let storage_dir = temp_dir.path().join("ui_state");
+
+        let store = UiStateStore::with_storage_dir(&project_path, &storage_dir);
+        assert_eq!(store.load(), UiState::default());
+
+        store.save(&sample_state()).unwrap();
+        let reopened = UiStateStore::with_storage_dir(&project_path, &storage_dir);
+        assert_eq!(reopened.load(), sample_state());
+    }
+
+    #[test]
+    fn test_store_corrupted_file_loads_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join("my-project");
+        let storage_dir = temp_dir.path().join("ui_state");
+        fs::create_dir_all(&storage_dir).unwrap();
+        fs::write(project_file_path(&storage_dir, &project_path), "not json").unwrap();
+
+        let store = UiStateStore::with_storage_dir(&project_path, &storage_dir);
+        assert_eq!(store.load(), UiState::default());
+    }
+}
@@ -21,6 +21,11 @@ fn create_test_app() -> App {
             description: "Test description 1".to_string(),
             emoji: Some("\u{1f680}".to_string()),
             ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
             script_type: ScriptType::Bash,
         },
         ScriptFunction {
@@ -30,19 +35,48 @@ fn create_test_app() -> App {
             description: "Test description 2".to_string(),
             emoji: None,
             ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
             script_type: ScriptType::Bash,
         },
     ];
     App::new(
         functions,
         "Test Project".to_string(),
-        Theme::default_theme().clone(),
+        *Theme::default_theme(),
     )
 }
 
 #[tokio::test]
 async fn test_output_scroll() {
     let mut app = create_test_app();
+    app.expand_category("test_category");
+    app.selected_index = 1;
+
+    // Ten lines on a five-row screen leave five rows of scrollback
+    let func = app.selected_function().unwrap();
+    let mut parser = vt100::Parser::new(5, 80, 100);
+    parser.process(
+        (1..=10)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\r\n")
+            .as_bytes(),
+    );
+    let state = ExecutionState {
+        status: ExecutionStatus::Succeeded,
+        parser: Arc::new(Mutex::new(parser)),
+        exit_code: Some(0),
+        started_at: Instant::now(),
+        finished_at: Some(Instant::now()),
+        display_name: func.display_name.clone(),
+        category: func.category.clone(),
+        auto_follow: true,
+    };
+    app.command_history.insert(&func, state);
     app.focus = FocusPane::Output;
 
     // Manually set scroll to test scroll mechanics
@@ -52,10 +86,11 @@ async fn test_output_scroll() {
     app.scroll_output_down();
     assert_eq!(app.output_scroll, 4);
 
-    // Test scroll up (needs terminal_total_lines > 0, so set scroll manually)
-    app.output_scroll = 3;
-    app.scroll_output_down();
-    assert_eq!(app.output_scroll, 2);
+    // Scrolling up stops at the top of the scrollback
+    for _ in 0..10 {
+        app.scroll_output_up();
+    }
+    assert_eq!(app.output_scroll, 5);
 
     // Reset scroll
     app.reset_output_scroll();
@@ -80,6 +115,7 @@ async fn test_focus_toggle() {
         finished_at: Some(Instant::now()),
         display_name: func.display_name.clone(),
         category: func.category.clone(),
+        auto_follow: true,
     };
     app.command_history.insert(&func, state);
 
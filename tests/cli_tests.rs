@@ -3,7 +3,7 @@
 use std::path::PathBuf;
 use tempfile::TempDir;
 
-/// Test that run_application correctly handles a nonexistent directory
+/// Test that `run_application` correctly handles a nonexistent directory
 #[tokio::test]
 async fn test_run_application_nonexistent_directory() {
     let path = PathBuf::from("/nonexistent/directory/that/does/not/exist");
@@ -12,7 +12,7 @@ async fn test_run_application_nonexistent_directory() {
     assert!(result.is_err());
 }
 
-/// Test that run_application correctly handles a file instead of a directory
+/// Test that `run_application` correctly handles a file instead of a directory
 #[tokio::test]
 async fn test_run_application_file_instead_of_directory() {
     use std::fs;
@@ -104,20 +104,17 @@ async fn test_run_application_script_parse_errors() {
     let mut parse_errors = Vec::new();
 
     for script_file in &script_files {
-        match &script_file.script_type {
-            script::ScriptType::Bash => {
-                match script::parse_script(&script_file.path, &script_file.category) {
-                    Ok(functions) => {
-                        let visible_functions: Vec<_> =
-                            functions.into_iter().filter(|f| !f.ignored).collect();
-                        all_functions.extend(visible_functions);
-                    }
-                    Err(e) => {
-                        parse_errors.push((script_file.path.display().to_string(), e));
-                    }
+        if script_file.script_type == script::ScriptType::Bash {
+            match script::parse_script(&script_file.path, &script_file.category) {
+                Ok(functions) => {
+                    let visible_functions: Vec<_> =
+                        functions.into_iter().filter(|f| !f.ignored).collect();
+                    all_functions.extend(visible_functions);
+                }
+                Err(e) => {
+                    parse_errors.push((script_file.path.display().to_string(), e));
                 }
             }
-            _ => {}
         }
     }
 
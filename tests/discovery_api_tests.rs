@@ -0,0 +1,127 @@
+//! Library discovery API tests
+//!
+//! Exercises [`jarvis::script::discover_all`] the way an embedding program
+//! would: from outside the crate, with no terminal setup.
+
+use jarvis::script::{self, makefile_parser, DiscoveryOptions, ScriptType};
+use std::fs;
+use tempfile::TempDir;
+
+/// Options without background tool pre-warming, to keep tests deterministic
+fn options() -> DiscoveryOptions {
+    DiscoveryOptions {
+        prewarm: false,
+        ..DiscoveryOptions::default()
+    }
+}
+
+/// Test that a bash script and a Makefile are discovered and parsed together
+#[test]
+fn test_discover_all_bash_and_makefile() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("deploy.sh"),
+        "#!/bin/bash\ndeploy() {\n  echo deploy\n}\n\n# @ignore\nhelper() {\n  echo help\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("Makefile"),
+        "build:\n\techo build\n\ntest: build\n\techo test\n",
+    )
+    .unwrap();
+
+    let discovery = script::discover_all(temp_dir.path(), &options()).unwrap();
+
+    assert!(discovery.parse_errors.is_empty());
+    assert!(discovery
+        .script_files
+        .iter()
+        .any(|f| f.script_type == ScriptType::Bash));
+
+    let bash: Vec<&str> = discovery
+        .functions
+        .iter()
+        .filter(|f| f.script_type == ScriptType::Bash)
+        .map(|f| f.name.as_str())
+        .collect();
+    // Ignored functions are filtered out
+    assert_eq!(bash, vec!["deploy"]);
+
+    let mut make: Vec<&str> = discovery
+        .functions
+        .iter()
+        .filter(|f| f.script_type == ScriptType::Makefile)
+        .map(|f| f.name.as_str())
+        .collect();
+    make.sort_unstable();
+    if makefile_parser::is_make_available() {
+        assert_eq!(make, vec!["build", "test"]);
+    } else {
+        assert!(make.is_empty());
+    }
+}
+
+/// Test that every function belongs to one of the returned script files' categories
+#[test]
+fn test_discover_all_functions_match_script_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let scripts_dir = temp_dir.path().join("scripts");
+    fs::create_dir(&scripts_dir).unwrap();
+    fs::write(scripts_dir.join("db.sh"), "migrate() {\n  echo up\n}\n").unwrap();
+    fs::write(temp_dir.path().join("Makefile"), "lint:\n\techo lint\n").unwrap();
+
+    let discovery = script::discover_all(temp_dir.path(), &options()).unwrap();
+
+    assert!(!discovery.functions.is_empty());
+    for func in &discovery.functions {
+        assert!(
+            discovery
+                .script_files
+                .iter()
+                .any(|f| f.category == func.category && f.script_type == func.script_type),
+            "{} has no matching script file",
+            func.name
+        );
+    }
+}
+
+/// Test that `@category` annotations regroup commands from different files
+#[test]
+fn test_discover_all_category_annotations() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("deploy.sh"),
+        "# @category Release\nship() {\n  echo ship\n}\n\nstatus() {\n  echo ok\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("Makefile"),
+        "# @category Release\npublish:\n\techo publish\n",
+    )
+    .unwrap();
+
+    let discovery = script::discover_all(temp_dir.path(), &options()).unwrap();
+
+    let ship = discovery
+        .functions
+        .iter()
+        .find(|f| f.name == "ship")
+        .unwrap();
+    assert_eq!(ship.category, "Release");
+    assert_eq!(ship.file_category.as_deref(), Some("deploy"));
+    let status = discovery
+        .functions
+        .iter()
+        .find(|f| f.name == "status")
+        .unwrap();
+    assert_eq!(status.category, "deploy");
+
+    if makefile_parser::is_make_available() {
+        let publish = discovery
+            .functions
+            .iter()
+            .find(|f| f.name == "publish")
+            .unwrap();
+        assert_eq!(publish.category, "Release");
+    }
+}
@@ -23,6 +23,11 @@ fn create_test_app() -> App {
             description: "Test description 1".to_string(),
             emoji: Some("\u{1f680}".to_string()),
             ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
             script_type: ScriptType::Bash,
         },
         ScriptFunction {
@@ -32,13 +37,18 @@ fn create_test_app() -> App {
             description: "Test description 2".to_string(),
             emoji: None,
             ignored: false,
+            sudo: false,
+            aliases: Vec::new(),
+            file_category: None,
+            usage: None,
+            line: None,
             script_type: ScriptType::Bash,
         },
     ];
     App::new(
         functions,
         "Test Project".to_string(),
-        Theme::default_theme().clone(),
+        *Theme::default_theme(),
     )
 }
 